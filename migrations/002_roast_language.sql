@@ -0,0 +1,2 @@
+-- Output language of each roast (id = Bahasa Indonesia, en = English)
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS language VARCHAR(8) NOT NULL DEFAULT 'id';
@@ -0,0 +1,4 @@
+-- Coarse startup category used for battle suggestions
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS category VARCHAR(32) NOT NULL DEFAULT 'other';
+
+CREATE INDEX IF NOT EXISTS idx_roasts_category ON roasts(category);
@@ -0,0 +1,2 @@
+-- Regional slang style used for the roast
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS dialect VARCHAR(16) NOT NULL DEFAULT 'jakarta';
@@ -0,0 +1,2 @@
+-- Structured roast sections (JSON-encoded RoastSections), NULL for free-form roasts
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS sections TEXT;
@@ -0,0 +1,17 @@
+-- Roast requests parked while the LLM is unavailable or the daily budget is spent
+CREATE TABLE IF NOT EXISTS roast_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    url TEXT NOT NULL,
+    language VARCHAR(8) NOT NULL DEFAULT 'id',
+    dialect VARCHAR(16) NOT NULL DEFAULT 'jakarta',
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    status VARCHAR(16) NOT NULL DEFAULT 'deferred',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    roast_id UUID REFERENCES roasts(id) ON DELETE SET NULL,
+    last_error TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_roast_jobs_status ON roast_jobs(status, created_at);
+CREATE INDEX IF NOT EXISTS idx_roast_jobs_user_id ON roast_jobs(user_id);
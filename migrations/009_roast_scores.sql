@@ -0,0 +1,5 @@
+-- 1-10 scorecard emitted with the roast, NULL for roasts without scores
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS score_originality SMALLINT;
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS score_ui SMALLINT;
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS score_viability SMALLINT;
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS score_buzzword SMALLINT;
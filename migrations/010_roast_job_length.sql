@@ -0,0 +1,2 @@
+-- Remember the requested roast length for deferred jobs
+ALTER TABLE roast_jobs ADD COLUMN IF NOT EXISTS length VARCHAR(16) NOT NULL DEFAULT 'standar';
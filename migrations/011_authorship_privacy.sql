@@ -0,0 +1,4 @@
+-- Let authors show up as "Anonim" publicly while keeping ownership
+ALTER TABLE users ADD COLUMN IF NOT EXISTS hide_authorship BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS anonymous BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE roast_jobs ADD COLUMN IF NOT EXISTS anonymous BOOLEAN NOT NULL DEFAULT FALSE;
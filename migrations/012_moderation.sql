@@ -0,0 +1,24 @@
+-- User reports against roasts and the removals that resolve them
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS removed_at TIMESTAMPTZ;
+
+CREATE TABLE IF NOT EXISTS roast_reports (
+    id UUID PRIMARY KEY,
+    roast_id UUID NOT NULL REFERENCES roasts(id) ON DELETE CASCADE,
+    reporter_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    reason TEXT NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending',
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_roast_reports_status ON roast_reports(status);
+
+CREATE TABLE IF NOT EXISTS roast_removals (
+    id UUID PRIMARY KEY,
+    roast_id UUID NOT NULL REFERENCES roasts(id) ON DELETE CASCADE,
+    kind VARCHAR(16) NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_roast_removals_kind ON roast_removals(kind);
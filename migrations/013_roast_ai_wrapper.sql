@@ -0,0 +1,2 @@
+-- Confidence (0-100) that the roasted startup is a thin LLM API wrapper
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS ai_wrapper_confidence SMALLINT;
@@ -0,0 +1,9 @@
+-- Generated roasts keyed by canonical URL + options, reused within a TTL
+CREATE TABLE IF NOT EXISTS roast_cache (
+    cache_key TEXT PRIMARY KEY,
+    url TEXT NOT NULL,
+    roast TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_roast_cache_created_at ON roast_cache(created_at);
@@ -0,0 +1,14 @@
+-- Earlier texts of a roast, kept when its owner regenerates it
+CREATE TABLE IF NOT EXISTS roast_versions (
+    id UUID PRIMARY KEY,
+    roast_id UUID NOT NULL REFERENCES roasts(id) ON DELETE CASCADE,
+    roast_text TEXT NOT NULL,
+    sections TEXT,
+    score_originality SMALLINT,
+    score_ui SMALLINT,
+    score_viability SMALLINT,
+    score_buzzword SMALLINT,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_roast_versions_roast_id ON roast_versions(roast_id);
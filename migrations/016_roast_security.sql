@@ -0,0 +1,2 @@
+-- JSON-encoded SecurityPosture flags of the roasted site (HTTPS, mixed content, exposed files)
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS security TEXT;
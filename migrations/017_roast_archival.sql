@@ -0,0 +1,4 @@
+-- Set by the retention job; archived roasts stay reachable by link but leave listings
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS archived_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_roast_jobs_updated_at ON roast_jobs(updated_at);
@@ -0,0 +1,12 @@
+-- One "roast lebih dalam" follow-up question per user per roast, with the answer
+CREATE TABLE IF NOT EXISTS roast_follow_ups (
+    id UUID PRIMARY KEY,
+    roast_id UUID NOT NULL REFERENCES roasts(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    question TEXT NOT NULL,
+    answer TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (roast_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_roast_follow_ups_roast_id ON roast_follow_ups(roast_id);
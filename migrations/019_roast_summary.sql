@@ -0,0 +1,2 @@
+-- One-line summary for crawlers and AI agents (/llms.txt, /api/roast/{id}/summary)
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS summary TEXT;
@@ -0,0 +1,4 @@
+-- Family-safe roasts: no-profanity prompt plus scrubbed output
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS safe BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE roast_jobs ADD COLUMN IF NOT EXISTS safe BOOLEAN NOT NULL DEFAULT FALSE;
+CREATE INDEX IF NOT EXISTS idx_roasts_safe_fire_count ON roasts(safe, fire_count DESC) WHERE safe;
@@ -0,0 +1,3 @@
+-- "Ditujukan untuk": who a roast is gifted to, shown on the roast card
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS dedicated_to TEXT;
+ALTER TABLE roast_jobs ADD COLUMN IF NOT EXISTS dedicated_to TEXT;
@@ -0,0 +1,8 @@
+-- Site owners refusing future roasts; only verified rows are enforced
+CREATE TABLE IF NOT EXISTS domain_opt_outs (
+    domain TEXT PRIMARY KEY,
+    token TEXT NOT NULL,
+    requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
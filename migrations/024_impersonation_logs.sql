@@ -0,0 +1,11 @@
+-- Audit trail for admins viewing the site as another user
+CREATE TABLE IF NOT EXISTS impersonation_logs (
+    id UUID PRIMARY KEY,
+    admin_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    target_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    action VARCHAR(16) NOT NULL,
+    path TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_impersonation_logs_created ON impersonation_logs(created_at DESC);
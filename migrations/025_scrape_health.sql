@@ -0,0 +1,20 @@
+-- Every scraping method tried per roast, backing the scrape health report
+CREATE TABLE IF NOT EXISTS scrape_attempts (
+    id BIGSERIAL PRIMARY KEY,
+    method VARCHAR(32) NOT NULL,
+    domain VARCHAR(255) NOT NULL,
+    success BOOLEAN NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    error TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_scrape_attempts_created_at ON scrape_attempts(created_at DESC);
+
+-- Periodic scrape health reports, kept as JSON
+CREATE TABLE IF NOT EXISTS scrape_health_reports (
+    id BIGSERIAL PRIMARY KEY,
+    period_hours INTEGER NOT NULL,
+    report TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
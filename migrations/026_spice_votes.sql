@@ -0,0 +1,13 @@
+-- Reader "spice meter" poll: one answer per roast per user or anonymous session
+CREATE TABLE IF NOT EXISTS spice_votes (
+    roast_id UUID NOT NULL REFERENCES roasts(id) ON DELETE CASCADE,
+    -- "user:<uuid>" when logged in, otherwise "session:<uuid>"
+    voter_key TEXT NOT NULL,
+    level VARCHAR(16) NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (roast_id, voter_key)
+);
+
+-- PROMPT_VARIANT a roast was generated with, so spice answers can be compared per variant
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS prompt_variant VARCHAR(64);
@@ -0,0 +1,4 @@
+-- Startup's likely country (ISO 3166-1 alpha-2), detected while scraping
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS country VARCHAR(2);
+
+CREATE INDEX IF NOT EXISTS idx_roasts_country ON roasts(country) WHERE country IS NOT NULL;
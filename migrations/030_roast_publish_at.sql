@@ -0,0 +1,4 @@
+-- Embargoed roasts stay out of public queries until the scheduler clears publish_at
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS publish_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_roasts_publish_at ON roasts(publish_at) WHERE publish_at IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Users watching a startup domain get notified about its new roasts and follow-ups
+CREATE TABLE IF NOT EXISTS watches (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Normalized like opt-outs: lowercase host without www.
+    domain VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (user_id, domain)
+);
+
+CREATE INDEX IF NOT EXISTS idx_watches_domain ON watches(domain);
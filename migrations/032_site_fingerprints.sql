@@ -0,0 +1,15 @@
+-- Last seen landing page of each watched domain, to notify watchers when the startup pivots
+CREATE TABLE IF NOT EXISTS site_fingerprints (
+    -- Normalized like watches: lowercase host without www.
+    domain VARCHAR(255) PRIMARY KEY,
+    -- SHA-256 hex of the canonical title and headings
+    content_hash VARCHAR(64) NOT NULL,
+    title TEXT NOT NULL,
+    -- Newline-separated
+    headings TEXT NOT NULL,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Last time watchers were told about a pivot
+    changed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_site_fingerprints_checked_at ON site_fingerprints(checked_at);
@@ -0,0 +1,14 @@
+-- How much a fire counts, fixed when it's cast: 0 on the voter's own roast,
+-- VOTE_NEW_ACCOUNT_WEIGHT for accounts younger than VOTE_NEW_ACCOUNT_DAYS
+ALTER TABLE votes ADD COLUMN IF NOT EXISTS weight REAL NOT NULL DEFAULT 1;
+
+-- Self-votes cast before the rule stop counting. Both statements only touch
+-- self-votes that still carry weight, so rerunning them is a no-op.
+UPDATE roasts r SET fire_count = GREATEST(r.fire_count - 1, 0)
+WHERE EXISTS (
+    SELECT 1 FROM votes v WHERE v.roast_id = r.id AND v.user_id = r.user_id AND v.weight > 0
+);
+
+UPDATE votes v SET weight = 0
+FROM roasts r
+WHERE v.roast_id = r.id AND v.user_id = r.user_id AND v.weight > 0;
@@ -0,0 +1,2 @@
+-- JSON-encoded SocialLink list of the roasted site, shown on /r/{id}
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS social_links TEXT;
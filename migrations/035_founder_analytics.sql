@@ -0,0 +1,30 @@
+-- Founders who proved control of their startup's domain with a token file, one row per user and domain
+CREATE TABLE IF NOT EXISTS founder_claims (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Normalized like opt-outs: lowercase host without www.
+    domain VARCHAR(255) NOT NULL,
+    token TEXT NOT NULL,
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (user_id, domain)
+);
+
+-- Daily /r/{id} page views per referring host, an empty referrer for direct visits
+CREATE TABLE IF NOT EXISTS roast_daily_views (
+    roast_id UUID NOT NULL REFERENCES roasts(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    referrer VARCHAR(255) NOT NULL DEFAULT '',
+    views INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (roast_id, day, referrer)
+);
+
+-- Daily share button clicks per channel
+CREATE TABLE IF NOT EXISTS roast_daily_shares (
+    roast_id UUID NOT NULL REFERENCES roasts(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    channel VARCHAR(32) NOT NULL,
+    shares INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (roast_id, day, channel)
+);
+
+CREATE INDEX IF NOT EXISTS idx_votes_roast_created ON votes(roast_id, created_at);
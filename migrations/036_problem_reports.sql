@@ -0,0 +1,13 @@
+-- "Laporkan masalah" reports from error pages, tied to the failed request's trace ID
+CREATE TABLE IF NOT EXISTS problem_reports (
+    id UUID PRIMARY KEY,
+    request_id VARCHAR(64) NOT NULL,
+    reporter_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    -- The error the user was shown, as pre-filled from the error page
+    error TEXT NOT NULL DEFAULT '',
+    message TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_problem_reports_open ON problem_reports(created_at) WHERE resolved_at IS NULL
@@ -0,0 +1,6 @@
+-- Full-page WebP screenshot of the roasted site, taken by the headless browser while scraping
+CREATE TABLE IF NOT EXISTS roast_screenshots (
+    roast_id UUID PRIMARY KEY REFERENCES roasts(id) ON DELETE CASCADE,
+    image BYTEA NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+)
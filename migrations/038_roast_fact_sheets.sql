@@ -0,0 +1,2 @@
+-- JSON-encoded FactSheet: the startup's own claims, shown as "Fakta (katanya)" and searched on the leaderboard
+ALTER TABLE roasts ADD COLUMN IF NOT EXISTS fact_sheet TEXT;
@@ -0,0 +1,8 @@
+-- Scraped StartupInfo keyed by canonical URL, reused by repeat roasts and regenerations within a TTL
+CREATE TABLE IF NOT EXISTS scrape_cache (
+    url TEXT PRIMARY KEY,
+    startup_info TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_scrape_cache_created_at ON scrape_cache(created_at);
@@ -0,0 +1,2 @@
+-- Popular sites refreshed nightly by the warm-up job keep their scrape past the normal TTL
+ALTER TABLE scrape_cache ADD COLUMN IF NOT EXISTS warm BOOLEAN NOT NULL DEFAULT FALSE;
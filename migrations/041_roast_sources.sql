@@ -0,0 +1,8 @@
+-- What each roast was based on: the scraped StartupInfo as JSON and the gzipped page HTML
+-- (NULL when the scrape came from the scrape cache, which keeps only StartupInfo)
+CREATE TABLE IF NOT EXISTS roast_sources (
+    roast_id UUID PRIMARY KEY REFERENCES roasts(id) ON DELETE CASCADE,
+    startup_info TEXT NOT NULL,
+    html_gz BYTEA,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+)
@@ -0,0 +1,7 @@
+-- Requests and model spend against the daily budget, one row per UTC day, so a restart doesn't start the day over
+CREATE TABLE IF NOT EXISTS daily_usage (
+    day DATE PRIMARY KEY,
+    requests INTEGER NOT NULL DEFAULT 0,
+    cost_micros BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,12 @@
+-- Every priced completion behind a roast, its regenerations included
+CREATE TABLE IF NOT EXISTS roast_costs (
+    id BIGSERIAL PRIMARY KEY,
+    roast_id UUID NOT NULL REFERENCES roasts(id) ON DELETE CASCADE,
+    model VARCHAR(255) NOT NULL,
+    prompt_tokens INTEGER NOT NULL,
+    completion_tokens INTEGER NOT NULL,
+    cost_micros BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_roast_costs_roast_id ON roast_costs(roast_id)
@@ -0,0 +1,11 @@
+-- Addresses and CIDR ranges refused on every request; a NULL expires_at bans for good
+CREATE TABLE IF NOT EXISTS ip_bans (
+    id UUID PRIMARY KEY,
+    ip_range TEXT NOT NULL,
+    reason TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ip_bans_expires_at ON ip_bans (expires_at);
@@ -0,0 +1,8 @@
+-- Fire milestones already announced to a roast's creator, so taking a fire
+-- back and casting it again doesn't repeat the notification
+CREATE TABLE IF NOT EXISTS fire_milestones (
+    roast_id UUID NOT NULL REFERENCES roasts(id) ON DELETE CASCADE,
+    milestone INTEGER NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (roast_id, milestone)
+);
@@ -0,0 +1,19 @@
+// Web push handler, registered from the roast page. Payloads are
+// {title, body, url} as sent by WebPushChannel.
+self.addEventListener('push', event => {
+    const data = event.data ? event.data.json() : {};
+    event.waitUntil(self.registration.showNotification(data.title || 'Roasting Startup', {
+        body: data.body || '',
+        icon: '/favicon.ico',
+        data: { url: data.url || '/' },
+    }));
+});
+
+self.addEventListener('notificationclick', event => {
+    event.notification.close();
+    const url = event.notification.data.url;
+    event.waitUntil(clients.matchAll({ type: 'window', includeUncontrolled: true }).then(windows => {
+        const open = windows.find(w => w.url === url);
+        return open ? open.focus() : clients.openWindow(url);
+    }));
+});
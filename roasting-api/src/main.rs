@@ -1,24 +1,268 @@
 use axum::{
-    extract::{Path, Query},
-    http::StatusCode,
-    response::{Html, IntoResponse, Redirect},
-    routing::{get, post},
-    Form, Json, Router,
+    extract::{ConnectInfo, DefaultBodyLimit, MatchedPath, Multipart, Path, Query, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::{delete, get, post, put},
+    Extension, Form, Json, Router,
 };
 use leptos::prelude::*;
 use leptos_axum::{generate_route_list, handle_server_fns_with_context, LeptosRoutes};
-use roasting_app::domain::{PersistedRoast, RoastWithDetails, User};
+use roasting_app::application::{BanError, FounderError, OptOutError, PreferenceError, ScheduleError, VoteError};
+use roasting_app::domain::{
+    country_name, parse_country_code, AiWrapperScore, Announcement, BattleSuggestion, CountryStats,
+    DiffSentence, Dialect, DomainOptOut, FactSheet, FounderAnalytics,
+    HealthReport, ImpersonationEvent, InviteCode, JobStatus, Language, ModerationStats,
+    NotificationKind,
+    PersistedRoast, PitchDeck, RemovalKind, RoastDiff, RoastFollowUp, RoastJob, RoastLength, RoastOptions,
+    RoastScore, RoastSections, RoastSummary, RoastWithDetails, RouteStats, ShareChannel, SpiceDistribution,
+    SocialLink, SpiceLevel, StartupIdea, SubsystemHealth, User, WallTile,
+};
+use roasting_app::infrastructure::db::entities::user;
+use roasting_app::infrastructure::logo_store::sniff_image_type;
+use roasting_app::infrastructure::metrics::{
+    current_request_id, is_valid_request_id, request_id_from, with_request_id, REQUEST_ID_HEADER,
+};
+use roasting_app::infrastructure::notification::WebPushChannel;
+use roasting_app::infrastructure::pitch_deck::MAX_DECK_BYTES;
+use roasting_app::infrastructure::security::{ClientIp, LoadStats, RateLimitError};
 use roasting_app::AppContext;
+use roasting_errors::AppError;
+use roasting_ui::components::{GetLatestAnnouncementFn, RoastFollowUpFn};
 use roasting_ui::pages::{GenerateRoastFn, GetCurrentUserFn};
+use roasting_ui::i18n::labels;
 use roasting_ui::App;
 use serde::Deserialize;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Instant;
+use tracing::Instrument;
 use tower_http::compression::CompressionLayer;
 use tower_sessions::{Expiry, MemoryStore, Session, SessionManagerLayer};
 use uuid::Uuid;
 
 #[derive(Deserialize)]
 struct RoastForm {
+    /// Empty when the startup is described in `description` instead
+    #[serde(default)]
     url: String,
+    /// Free-text idea for stealth startups without a site; replaces `url`
+    #[serde(default)]
+    description: Option<String>,
+    /// Working name of the described idea
+    #[serde(default)]
+    idea_name: Option<String>,
+    #[serde(default)]
+    language: Language,
+    #[serde(default)]
+    dialect: Dialect,
+    #[serde(default)]
+    length: RoastLength,
+    /// Show the roast as "Anonim" instead of the logged-in author
+    #[serde(default)]
+    anonymous: bool,
+    /// User agreed to have the roast generated later if the AI is unavailable
+    #[serde(default)]
+    defer: bool,
+    /// Skip the roast cache and generate a fresh one
+    #[serde(default)]
+    regenerate: bool,
+    /// Family-safe roast without profanity
+    #[serde(default)]
+    safe: bool,
+    /// "Ditujukan untuk": gift the roast to someone
+    #[serde(default)]
+    dedicated_to: Option<String>,
+    /// Private beta invite code, only needed while INVITE_ONLY is on
+    #[serde(default)]
+    invite_code: Option<String>,
+    /// Local pool model to run on instead of the one routed by length
+    #[serde(default)]
+    model: Option<String>,
+    /// Issued with the "lagi rame" page; lets an anonymous retry through
+    #[serde(default)]
+    retry_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct InviteInput {
+    #[serde(default = "default_invite_count")]
+    count: u32,
+    max_uses: i32,
+    #[serde(default)]
+    note: Option<String>,
+}
+
+fn default_invite_count() -> u32 {
+    1
+}
+
+/// An address or CIDR range; without `expires_at` the ban is permanent
+#[derive(Deserialize)]
+struct BanInput {
+    range: String,
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// `null` makes a ban permanent; a time in the past lifts it
+#[derive(Deserialize)]
+struct BanExpiryInput {
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Longest leaderboard search kept
+const MAX_SEARCH_CHARS: usize = 100;
+
+#[derive(Deserialize)]
+struct LeaderboardQuery {
+    /// Only family-safe roasts
+    #[serde(default)]
+    safe: bool,
+    /// ISO alpha-2 code; anything else shows every country
+    #[serde(default)]
+    country: Option<String>,
+    /// Matches startup names and fact sheets
+    #[serde(default)]
+    q: Option<String>,
+}
+
+impl LeaderboardQuery {
+    fn country(&self) -> Option<String> {
+        self.country.as_deref().and_then(parse_country_code)
+    }
+
+    fn search(&self) -> Option<String> {
+        self.q
+            .as_deref()
+            .map(str::trim)
+            .filter(|q| !q.is_empty())
+            .map(|q| q.chars().take(MAX_SEARCH_CHARS).collect())
+    }
+}
+
+#[derive(Deserialize)]
+struct WallQuery {
+    /// 1-based
+    #[serde(default)]
+    page: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct AnnouncementInput {
+    title: String,
+    body: String,
+    #[serde(default)]
+    published: bool,
+}
+
+#[derive(Deserialize)]
+struct ReportInput {
+    reason: String,
+}
+
+#[derive(Deserialize)]
+struct SpiceInput {
+    level: SpiceLevel,
+}
+
+#[derive(Deserialize)]
+struct ProblemReportInput {
+    request_id: String,
+    message: String,
+    /// The error message shown on the page the report came from
+    #[serde(default)]
+    error: String,
+}
+
+#[derive(Deserialize)]
+struct ProblemReportQuery {
+    request_id: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ResolveReportInput {
+    /// Remove the reported roast instead of dismissing the report
+    remove: bool,
+}
+
+#[derive(Deserialize)]
+struct OptOutInput {
+    domain: String,
+}
+
+#[derive(Deserialize)]
+struct FounderClaimInput {
+    domain: String,
+}
+
+#[derive(Deserialize)]
+struct FounderAnalyticsQuery {
+    #[serde(default)]
+    days: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct ShareInput {
+    channel: String,
+}
+
+#[derive(Deserialize)]
+struct TakedownInput {
+    reason: String,
+}
+
+#[derive(Deserialize)]
+struct SwapModelInput {
+    model_id: String,
+    /// Pool model to replace; defaults to the first in LOCAL_LLM_MODELS
+    #[serde(default)]
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AnonymousInput {
+    anonymous: bool,
+}
+
+/// `null` publishes an embargoed roast right away
+#[derive(Deserialize)]
+struct ScheduleInput {
+    #[serde(default)]
+    publish_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Deserialize)]
+struct PrivacyInput {
+    hide_authorship: bool,
+}
+
+#[derive(Deserialize)]
+struct NotificationChannelInput {
+    enabled: bool,
+    #[serde(default)]
+    address: Option<String>,
+}
+
+/// `PushSubscription.toJSON()` as sent by the browser
+#[derive(Deserialize)]
+struct PushSubscriptionInput {
+    endpoint: String,
+    keys: PushSubscriptionKeys,
+}
+
+#[derive(Deserialize)]
+struct PushSubscriptionKeys {
+    p256dh: String,
+    auth: String,
+}
+
+#[derive(Deserialize)]
+struct PushUnsubscribeInput {
+    endpoint: String,
 }
 
 #[derive(Deserialize)]
@@ -38,6 +282,18 @@ async fn main() {
         )
         .init();
 
+    // `roasting-api models [list | evict <owner/model>]` manages the local model cache
+    if std::env::args().nth(1).as_deref() == Some("models") {
+        let args: Vec<String> = std::env::args().skip(2).collect();
+        std::process::exit(run_models_command(&args));
+    }
+
+    // `roasting-api bench [--runs N] [--lang id|en] [--length ...]` times the local LLM
+    if std::env::args().nth(1).as_deref() == Some("bench") {
+        let args: Vec<String> = std::env::args().skip(2).collect();
+        std::process::exit(run_bench_command(&args).await);
+    }
+
     let conf = get_configuration(Some("Cargo.toml")).expect("Failed to load Leptos config");
     let leptos_options = conf.leptos_options;
     let addr = leptos_options.site_addr;
@@ -45,6 +301,18 @@ async fn main() {
     // Initialize app context (database, OAuth, etc.)
     let app_context = AppContext::from_env().await;
 
+    match app_context.opt_out_domain.load().await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Loaded {} opted-out domains", n),
+        Err(e) => tracing::error!("Failed to load opted-out domains: {:?}", e),
+    }
+
+    match app_context.ban_ip.load().await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Loaded {} IP bans", n),
+        Err(e) => tracing::error!("Failed to load IP bans: {:?}", e),
+    }
+
     // Set up session store
     // Use MemoryStore for sessions (sessions lost on restart - consider PostgresStore in production)
     let session_store = MemoryStore::default();
@@ -56,23 +324,209 @@ async fn main() {
     // Pre-initialize local LLM model at startup (downloads model on first run)
     #[cfg(feature = "local-llm")]
     if std::env::var("USE_LOCAL_LLM").is_ok() {
-        tracing::info!("Pre-initializing local LLM model (this may take a while on first run)...");
-        match roasting_app::infrastructure::local_llm::LocalLlm::get_or_init().await {
-            Ok(_) => tracing::info!("Local LLM model ready!"),
-            Err(e) => {
-                tracing::error!("Failed to initialize local LLM: {}", e);
-                std::process::exit(1);
+        use roasting_app::infrastructure::local_llm::{LocalLlm, ModelPoolConfig};
+
+        tracing::info!("Pre-initializing local LLM models (this may take a while on first run)...");
+        let pool = ModelPoolConfig::from_env().unwrap_or_else(|e| {
+            tracing::error!("Invalid local model pool: {}", e);
+            std::process::exit(1);
+        });
+        for name in pool.names() {
+            match LocalLlm::get_or_init(name, &pool).await {
+                Ok(llm) => tracing::info!("Local LLM model {} ready: {}", name, llm.model_id()),
+                Err(e) => {
+                    tracing::error!("Failed to initialize local LLM {}: {}", name, e);
+                    std::process::exit(1);
+                }
             }
         }
     }
 
+    // Warm connections, the browser and templates in the background; /readyz waits for it
+    {
+        let warmup = app_context.warmup.clone();
+        tokio::spawn(async move {
+            warmup.run().await;
+        });
+    }
+
+    // Prune old request logs once an hour
+    {
+        let metrics = app_context.request_metrics.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                match metrics.prune().await {
+                    Ok(0) => {}
+                    Ok(n) => tracing::info!("Pruned {} old request logs", n),
+                    Err(e) => tracing::warn!("Failed to prune request logs: {}", e),
+                }
+            }
+        });
+    }
+
+    // Drop expired roast and scrape cache entries once an hour
+    {
+        let generate_roast = app_context.generate_roast.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                let purged = generate_roast.purge_cache().await;
+                if purged > 0 {
+                    tracing::info!("Purged {} expired cache entries", purged);
+                }
+            }
+        });
+    }
+
+    // Scrape health report, hourly or daily (SCRAPE_REPORT_SCHEDULE)
+    {
+        let report = app_context.report_scrape_health.clone();
+        tokio::spawn(async move {
+            let period = std::time::Duration::from_secs(report.period_hours() as u64 * 3600);
+            // First report after a full period, not on every restart
+            let mut interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            loop {
+                interval.tick().await;
+                if let Err(e) = report.execute().await {
+                    tracing::warn!("Scrape health report failed: {}", e);
+                }
+            }
+        });
+    }
+
+    // Apply retention policies once a day
+    {
+        let retention = app_context.apply_retention.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(24 * 3600));
+            loop {
+                interval.tick().await;
+                if let Err(e) = retention.execute(false).await {
+                    tracing::warn!("Retention run failed: {}", e);
+                }
+            }
+        });
+    }
+
+    // Generate deferred roasts once the LLM or budget is available again
+    {
+        let queue = app_context.roast_queue.clone();
+        tokio::spawn(async move {
+            if let Ok(n) = queue.recover().await {
+                if n > 0 {
+                    tracing::info!("Requeued {} interrupted roast jobs", n);
+                }
+            }
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(120));
+            loop {
+                interval.tick().await;
+                if let Err(e) = queue.process_deferred().await {
+                    tracing::warn!("Deferred roast processing failed: {}", e);
+                }
+            }
+        });
+    }
+
+    // Take embargoed roasts live once their publish time passes
+    {
+        let schedule = app_context.schedule_roast.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+            loop {
+                interval.tick().await;
+                if let Err(e) = schedule.publish_due().await {
+                    tracing::warn!("Publishing scheduled roasts failed: {}", e);
+                }
+            }
+        });
+    }
+
+    // Pick up bans added on other instances and drop expired ones
+    {
+        let ban_ip = app_context.ban_ip.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = ban_ip.load().await {
+                    tracing::warn!("Reloading IP bans failed: {:?}", e);
+                }
+            }
+        });
+    }
+
+    // Notify watchers when a watched startup's landing page pivots
+    if app_context.detect_site_changes.is_enabled() {
+        let detect = app_context.detect_site_changes.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                match detect.execute().await {
+                    Ok(run) if run.pivots > 0 => tracing::info!(
+                        "Checked {} watched sites, {} pivoted",
+                        run.checked,
+                        run.pivots
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Site change detection failed: {}", e),
+                }
+            }
+        });
+    }
+
+    // Re-scrape the most roasted sites every night so user requests hit the cache
+    if app_context.warm_popular_scrapes.is_enabled() {
+        let warm = app_context.warm_popular_scrapes.clone();
+        tokio::spawn(async move {
+            let period = std::time::Duration::from_secs(24 * 3600);
+            let mut interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + warm.until_next_run(), period);
+            loop {
+                interval.tick().await;
+                match warm.execute().await {
+                    Ok(run) => tracing::info!(
+                        "Warmed {} popular sites ({} recently scraped, {} failed)",
+                        run.refreshed,
+                        run.skipped,
+                        run.failed
+                    ),
+                    Err(e) => tracing::warn!("Popular site warm-up failed: {}", e),
+                }
+            }
+        });
+    }
+
     let routes = generate_route_list(App);
 
     server_fn::axum::register_explicit::<GenerateRoastFn>();
     server_fn::axum::register_explicit::<GetCurrentUserFn>();
-    tracing::info!("Registered server functions: GenerateRoastFn, GetCurrentUserFn");
+    server_fn::axum::register_explicit::<GetLatestAnnouncementFn>();
+    server_fn::axum::register_explicit::<RoastFollowUpFn>();
+    tracing::info!(
+        "Registered server functions: GenerateRoastFn, GetCurrentUserFn, GetLatestAnnouncementFn, RoastFollowUpFn"
+    );
 
     let app = Router::new()
+        .route("/healthz", get({
+            let ctx = app_context.clone();
+            move || {
+                let ctx = ctx.clone();
+                async move { handle_healthz(ctx).await }
+            }
+        }))
+        .route("/readyz", get({
+            let ctx = app_context.clone();
+            move || {
+                let ctx = ctx.clone();
+                async move { handle_readyz(ctx).await }
+            }
+        }))
         // Auth routes
         .route("/auth/login", get({
             let ctx = app_context.clone();
@@ -106,531 +560,4686 @@ async fn main() {
                 async move { handle_vote(ctx, session, path.0).await }
             }
         }))
-        .route("/api/leaderboard", get({
+        .route("/api/roast/{id}/report", post({
             let ctx = app_context.clone();
-            move |session: Session| {
+            move |session: Session, path: Path<Uuid>, input: Json<ReportInput>| {
                 let ctx = ctx.clone();
-                async move { handle_leaderboard(ctx, session).await }
+                async move { handle_report_roast(ctx, session, path.0, input.0).await }
             }
         }))
-        .route("/api/roast/{id}", get({
+        .route("/api/roast/{id}/spice", post({
             let ctx = app_context.clone();
-            move |session: Session, path: Path<Uuid>| {
+            move |session: Session, path: Path<Uuid>, input: Json<SpiceInput>| {
                 let ctx = ctx.clone();
-                async move { handle_get_roast(ctx, session, path.0).await }
+                async move { handle_spice_vote(ctx, session, path.0, input.0).await }
             }
         }))
-        // View roast page
-        .route("/r/{id}", get({
+        .route("/api/opt-out", post({
+            let ctx = app_context.clone();
+            move |session: Session, input: Json<OptOutInput>| {
+                let ctx = ctx.clone();
+                async move { handle_opt_out_request(ctx, session, input.0).await }
+            }
+        }))
+        .route("/api/opt-out/verify", post({
+            let ctx = app_context.clone();
+            move |input: Json<OptOutInput>| {
+                let ctx = ctx.clone();
+                async move { handle_opt_out_verify(ctx, input.0).await }
+            }
+        }))
+        .route("/api/founder/claim", post({
+            let ctx = app_context.clone();
+            move |session: Session, input: Json<FounderClaimInput>| {
+                let ctx = ctx.clone();
+                async move { handle_founder_claim(ctx, session, input.0).await }
+            }
+        }))
+        .route("/api/founder/verify", post({
+            let ctx = app_context.clone();
+            move |session: Session, input: Json<FounderClaimInput>| {
+                let ctx = ctx.clone();
+                async move { handle_founder_verify(ctx, session, input.0).await }
+            }
+        }))
+        .route("/api/founder/analytics/{domain}", get({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<String>, query: Query<FounderAnalyticsQuery>| {
+                let ctx = ctx.clone();
+                async move { handle_founder_analytics(ctx, session, path.0, query.0).await }
+            }
+        }))
+        .route("/api/roast/{id}/share", post({
+            let ctx = app_context.clone();
+            move |path: Path<Uuid>, input: Json<ShareInput>| {
+                let ctx = ctx.clone();
+                async move { handle_share(ctx, path.0, input.0).await }
+            }
+        }))
+        .route("/api/moderation", get({
+            let ctx = app_context.clone();
+            move || {
+                let ctx = ctx.clone();
+                async move { handle_moderation_stats(ctx).await }
+            }
+        }))
+        .route("/api/roast/{id}/anonymous", post({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>, input: Json<AnonymousInput>| {
+                let ctx = ctx.clone();
+                async move { handle_set_anonymous(ctx, session, path.0, input.0).await }
+            }
+        }))
+        .route("/api/roast/{id}/schedule", put({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>, input: Json<ScheduleInput>| {
+                let ctx = ctx.clone();
+                async move { handle_schedule_roast(ctx, session, path.0, input.0).await }
+            }
+        }))
+        .route("/api/roast/{id}/regenerate", post({
+            let ctx = app_context.clone();
+            move |session: Session, Extension(ClientIp(client_ip)): Extension<ClientIp>, path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_regenerate_roast(ctx, session, client_ip, path.0).await }
+            }
+        }))
+        .route("/api/roast/{id}/versions", get({
             let ctx = app_context.clone();
             move |session: Session, path: Path<Uuid>| {
                 let ctx = ctx.clone();
-                async move { handle_view_roast_page(ctx, session, path.0).await }
+                async move { handle_roast_versions(ctx, session, path.0).await }
             }
         }))
-        // Leaderboard page
-        .route("/leaderboard", get({
+        .route("/api/me/privacy", post({
+            let ctx = app_context.clone();
+            move |session: Session, input: Json<PrivacyInput>| {
+                let ctx = ctx.clone();
+                async move { handle_set_privacy(ctx, session, input.0).await }
+            }
+        }))
+        .route("/api/me/notifications", get({
             let ctx = app_context.clone();
             move |session: Session| {
                 let ctx = ctx.clone();
-                async move { handle_leaderboard_page(ctx, session).await }
+                async move { handle_list_notifications(ctx, session).await }
             }
         }))
-        // Roast form route
-        .route("/roast", get({
+        .route("/api/me/notifications/read", post({
             let ctx = app_context.clone();
-            move |session: Session, query: Query<RoastForm>| {
+            move |session: Session| {
                 let ctx = ctx.clone();
-                async move {
-                    handle_roast_form(ctx, session, query.0).await
-                }
+                async move { handle_mark_notifications_read(ctx, session).await }
             }
-        }).post({
+        }))
+        .route("/api/me/notifications/channels", get({
             let ctx = app_context.clone();
-            move |session: Session, form: Form<RoastForm>| {
+            move |session: Session| {
                 let ctx = ctx.clone();
-                async move {
-                    handle_roast_form(ctx, session, form.0).await
-                }
+                async move { handle_notification_channels(ctx, session).await }
             }
         }))
-        .route("/api/{*fn_name}", post({
+        .route("/api/me/notifications/channels/{channel}", put({
             let ctx = app_context.clone();
-            move |session: Session, req: axum::http::Request<axum::body::Body>| {
+            move |session: Session, path: Path<String>, input: Json<NotificationChannelInput>| {
                 let ctx = ctx.clone();
-                let session = session.clone();
-                tracing::info!("Server function called, session available: true");
-                async move {
-                    handle_server_fns_with_context(
-                        {
-                            let ctx = ctx.clone();
-                            let session = session.clone();
-                            move || {
-                                tracing::info!("Providing context with session");
-                                provide_context(ctx.clone());
-                                provide_context(session.clone());
-                            }
-                        },
-                        req
-                    ).await
-                }
+                async move { handle_set_notification_channel(ctx, session, path.0, input.0).await }
             }
         }))
-        .leptos_routes_with_context(
-            &leptos_options,
-            routes,
-            {
-                let ctx = app_context.clone();
-                move || provide_context(ctx.clone())
-            },
-            {
-                let leptos_options = leptos_options.clone();
-                move || shell(leptos_options.clone())
-            },
-        )
-        .fallback(leptos_axum::file_and_error_handler(shell))
-        .layer(session_layer)
-        .layer(CompressionLayer::new())
-        .with_state(leptos_options);
-
-    tracing::info!("Listening on http://{}", addr);
-    tracing::info!(
-        "Security: Rate limit 5/min, 20/hour. Daily limit: {} requests",
-        app_context.cost_tracker.get_remaining_requests()
+        .route("/api/me/watches", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_list_watches(ctx, session).await }
+            }
+        }))
+        .route("/api/me/watches/{domain}", put({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<String>| {
+                let ctx = ctx.clone();
+                async move { handle_watch(ctx, session, path.0).await }
+            }
+        }).delete({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<String>| {
+                let ctx = ctx.clone();
+                async move { handle_unwatch(ctx, session, path.0).await }
+            }
+        }))
+        .route("/api/push/public-key", get({
+            let ctx = app_context.clone();
+            move || {
+                let ctx = ctx.clone();
+                async move { handle_push_public_key(ctx).await }
+            }
+        }))
+        .route("/api/me/push-subscriptions", post({
+            let ctx = app_context.clone();
+            move |session: Session, input: Json<PushSubscriptionInput>| {
+                let ctx = ctx.clone();
+                async move { handle_push_subscribe(ctx, session, input.0).await }
+            }
+        }).delete({
+            let ctx = app_context.clone();
+            move |session: Session, input: Json<PushUnsubscribeInput>| {
+                let ctx = ctx.clone();
+                async move { handle_push_unsubscribe(ctx, session, input.0).await }
+            }
+        }))
+        .route("/api/leaderboard", get({
+            let ctx = app_context.clone();
+            move |session: Session, query: Query<LeaderboardQuery>| {
+                let ctx = ctx.clone();
+                async move { handle_leaderboard(ctx, session, query.0).await }
+            }
+        }))
+        .route("/api/stats/countries", get({
+            let ctx = app_context.clone();
+            move || {
+                let ctx = ctx.clone();
+                async move { handle_country_stats(ctx).await }
+            }
+        }))
+        .route("/api/roast/{id}/battle", get({
+            let ctx = app_context.clone();
+            move |path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_battle_suggestion(ctx, path.0).await }
+            }
+        }))
+        .route("/api/roast/{id}", get({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_get_roast(ctx, session, path.0).await }
+            }
+        }))
+        .route("/api/roast/{id}/summary", get({
+            let ctx = app_context.clone();
+            move |path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_roast_summary(ctx, path.0).await }
+            }
+        }))
+        .route("/api/roast/{id}/sticker.webp", get({
+            let ctx = app_context.clone();
+            move |path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_roast_sticker(ctx, path.0).await }
+            }
+        }))
+        .route("/api/roast/{id}/screenshot.webp", get({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_roast_screenshot(ctx, session, path.0).await }
+            }
+        }))
+        .route("/api/roast/deck", post({
+            let ctx = app_context.clone();
+            move |session: Session, Extension(ClientIp(client_ip)): Extension<ClientIp>, multipart: Multipart| {
+                let ctx = ctx.clone();
+                async move { handle_roast_deck(ctx, session, client_ip, multipart).await }
+            }
+        })
+        // Room for the other form fields next to the file
+        .layer(DefaultBodyLimit::max(MAX_DECK_BYTES + 64 * 1024)))
+        .route("/api/roast/{id}/source", get({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_roast_source(ctx, session, path.0).await }
+            }
+        }))
+        .route("/logos/{domain}", get({
+            let ctx = app_context.clone();
+            move |path: Path<String>| {
+                let ctx = ctx.clone();
+                async move { handle_logo(ctx, path.0).await }
+            }
+        }))
+        .route("/llms.txt", get({
+            let ctx = app_context.clone();
+            move || {
+                let ctx = ctx.clone();
+                async move { handle_llms_txt(ctx).await }
+            }
+        }))
+        // View roast page
+        .route("/r/{id}", get({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>, headers: HeaderMap| {
+                let ctx = ctx.clone();
+                async move { handle_view_roast_page(ctx, session, path.0, headers).await }
+            }
+        }))
+        .route("/r/{id}/compare/{other_id}", get({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<(Uuid, Uuid)>| {
+                let ctx = ctx.clone();
+                async move { handle_compare_roasts_page(ctx, session, path.0 .0, path.0 .1).await }
+            }
+        }))
+        // Deferred roast jobs
+        .route("/jobs/{id}", get({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_job_page(ctx, session, path.0).await }
+            }
+        }))
+        .route("/api/jobs", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_my_jobs(ctx, session).await }
+            }
+        }))
+        // Leaderboard page
+        .route("/leaderboard", get({
+            let ctx = app_context.clone();
+            move |session: Session, query: Query<LeaderboardQuery>| {
+                let ctx = ctx.clone();
+                async move { handle_leaderboard_page(ctx, session, query.0).await }
+            }
+        }))
+        // Wall of shame: every roasted startup's logo
+        .route("/wall", get({
+            let ctx = app_context.clone();
+            move |query: Query<WallQuery>| {
+                let ctx = ctx.clone();
+                async move { handle_wall_page(ctx, query.0).await }
+            }
+        }))
+        // Moderation transparency page
+        .route("/moderation", get({
+            let ctx = app_context.clone();
+            move || {
+                let ctx = ctx.clone();
+                async move { handle_moderation_page(ctx).await }
+            }
+        }))
+        // "Laporkan masalah" from error pages
+        .route("/report-problem", get(|query: Query<ProblemReportQuery>| async move {
+            Html(render_report_problem_page(
+                query.0.request_id.as_deref().unwrap_or_default(),
+                query.0.error.as_deref().unwrap_or_default(),
+            ))
+        }))
+        .route("/api/problem-reports", post({
+            let ctx = app_context.clone();
+            move |session: Session, Extension(ClientIp(client_ip)): Extension<ClientIp>, input: Json<ProblemReportInput>| {
+                let ctx = ctx.clone();
+                async move { handle_report_problem(ctx, session, client_ip, input.0).await }
+            }
+        }))
+        // Changelog page
+        .route("/changelog", get({
+            let ctx = app_context.clone();
+            move || {
+                let ctx = ctx.clone();
+                async move { handle_changelog_page(ctx).await }
+            }
+        }))
+        // Admin routes
+        .route("/api/admin/announcements", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_list_announcements(ctx, session).await }
+            }
+        }).post({
+            let ctx = app_context.clone();
+            move |session: Session, input: Json<AnnouncementInput>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_create_announcement(ctx, session, input.0).await }
+            }
+        }))
+        .route("/api/admin/announcements/{id}", put({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>, input: Json<AnnouncementInput>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_update_announcement(ctx, session, path.0, input.0).await }
+            }
+        }).delete({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_delete_announcement(ctx, session, path.0).await }
+            }
+        }))
+        .route("/api/admin/reports", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_list_reports(ctx, session).await }
+            }
+        }))
+        .route("/api/admin/reports/{id}/resolve", post({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>, input: Json<ResolveReportInput>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_resolve_report(ctx, session, path.0, input.0).await }
+            }
+        }))
+        .route("/api/admin/problem-reports", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_list_problem_reports(ctx, session).await }
+            }
+        }))
+        .route("/api/admin/problem-reports/{id}/resolve", post({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_resolve_problem_report(ctx, session, path.0).await }
+            }
+        }))
+        .route("/api/admin/roasts/{id}/takedown", post({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>, input: Json<TakedownInput>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_takedown(ctx, session, path.0, input.0).await }
+            }
+        }))
+        .route("/api/admin/invites", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_list_invites(ctx, session).await }
+            }
+        }).post({
+            let ctx = app_context.clone();
+            move |session: Session, input: Json<InviteInput>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_create_invites(ctx, session, input.0).await }
+            }
+        }))
+        .route("/api/admin/bans", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_list_bans(ctx, session).await }
+            }
+        }).post({
+            let ctx = app_context.clone();
+            move |session: Session, input: Json<BanInput>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_create_ban(ctx, session, input.0).await }
+            }
+        }))
+        .route("/api/admin/bans/{id}", put({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>, input: Json<BanExpiryInput>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_expire_ban(ctx, session, path.0, input.0).await }
+            }
+        }).delete({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_delete_ban(ctx, session, path.0).await }
+            }
+        }))
+        .route("/api/admin/impersonate/stop", post({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_stop_impersonation(ctx, session).await }
+            }
+        }))
+        .route("/api/admin/impersonate/{user_id}", post({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_impersonate(ctx, session, path.0).await }
+            }
+        }))
+        .route("/api/admin/impersonations", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_list_impersonations(ctx, session).await }
+            }
+        }))
+        .route("/api/admin/scrape-health", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_scrape_health(ctx, session).await }
+            }
+        }))
+        .route("/api/admin/models", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_list_models(ctx, session).await }
+            }
+        }))
+        .route("/api/admin/models/{owner}/{name}", delete({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<(String, String)>| {
+                let ctx = ctx.clone();
+                let (owner, name) = path.0;
+                async move { handle_admin_evict_model(ctx, session, format!("{}/{}", owner, name)).await }
+            }
+        }))
+        .route("/api/admin/local-llm/model", post({
+            let ctx = app_context.clone();
+            move |session: Session, input: Json<SwapModelInput>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_swap_model(ctx, session, input.0).await }
+            }
+        }))
+        .route("/api/admin/spice", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_spice(ctx, session).await }
+            }
+        }))
+        .route("/api/admin/costs", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_costs(ctx, session).await }
+            }
+        }))
+        .route("/api/admin/roasts/{id}/costs", get({
+            let ctx = app_context.clone();
+            move |session: Session, path: Path<Uuid>| {
+                let ctx = ctx.clone();
+                async move { handle_admin_roast_costs(ctx, session, path.0).await }
+            }
+        }))
+        .route("/api/admin/retention", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_retention(ctx, session, true).await }
+            }
+        }))
+        .route("/api/admin/retention/run", post({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_retention(ctx, session, false).await }
+            }
+        }))
+        .route("/api/admin/metrics", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_metrics(ctx, session).await }
+            }
+        }))
+        .route("/admin/metrics", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_metrics_page(ctx, session).await }
+            }
+        }))
+        .route("/api/admin/prompts", get({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_list_prompts(ctx, session).await }
+            }
+        }))
+        .route("/api/admin/prompts/reload", post({
+            let ctx = app_context.clone();
+            move |session: Session| {
+                let ctx = ctx.clone();
+                async move { handle_admin_reload_prompts(ctx, session).await }
+            }
+        }))
+        // Roast form route
+        .route("/roast", get({
+            let ctx = app_context.clone();
+            move |session: Session, Extension(ClientIp(client_ip)): Extension<ClientIp>, query: Query<RoastForm>| {
+                let ctx = ctx.clone();
+                async move {
+                    handle_roast_form(ctx, session, client_ip, query.0).await
+                }
+            }
+        }).post({
+            let ctx = app_context.clone();
+            move |session: Session, Extension(ClientIp(client_ip)): Extension<ClientIp>, form: Form<RoastForm>| {
+                let ctx = ctx.clone();
+                async move {
+                    handle_roast_form(ctx, session, client_ip, form.0).await
+                }
+            }
+        }))
+        .route("/api/{*fn_name}", post({
+            let ctx = app_context.clone();
+            move |session: Session, req: axum::http::Request<axum::body::Body>| {
+                let ctx = ctx.clone();
+                let session = session.clone();
+                tracing::info!("Server function called, session available: true");
+                let client_ip = req.extensions().get::<ClientIp>().copied();
+                async move {
+                    handle_server_fns_with_context(
+                        {
+                            let ctx = ctx.clone();
+                            let session = session.clone();
+                            move || {
+                                tracing::info!("Providing context with session");
+                                provide_context(ctx.clone());
+                                provide_context(session.clone());
+                                if let Some(client_ip) = client_ip {
+                                    provide_context(client_ip);
+                                }
+                            }
+                        },
+                        req
+                    ).await
+                }
+            }
+        }))
+        .leptos_routes_with_context(
+            &leptos_options,
+            routes,
+            {
+                let ctx = app_context.clone();
+                move || provide_context(ctx.clone())
+            },
+            {
+                let leptos_options = leptos_options.clone();
+                move || shell(leptos_options.clone())
+            },
+        )
+        .fallback(leptos_axum::file_and_error_handler(shell))
+        .layer(axum::middleware::from_fn_with_state(
+            app_context.clone(),
+            track_request,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            app_context.clone(),
+            impersonation_guard,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            app_context.clone(),
+            reject_banned_ips,
+        ))
+        .layer(axum::middleware::from_fn(assign_request_id))
+        .layer(axum::middleware::from_fn_with_state(
+            app_context.clone(),
+            resolve_client_ip,
+        ))
+        .layer(session_layer)
+        .layer(CompressionLayer::new())
+        .with_state(leptos_options);
+
+    tracing::info!("Listening on http://{}", addr);
+    let limits = app_context.rate_limiter.limits();
+    tracing::info!(
+        "Security: Rate limit {}/min, {}/hour per IP, {}/min, {}/hour per user ({} store). Daily limit: {} requests",
+        limits.anonymous.per_minute,
+        limits.anonymous.per_hour,
+        limits.user.per_minute,
+        limits.user.per_hour,
+        app_context.rate_limiter.store_name(),
+        app_context.cost_tracker.get_remaining_requests()
+    );
+
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .expect("Failed to bind address");
+
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .expect("Server error");
+}
+
+/// Tag every request with a trace ID: recorded on its log span, echoed in
+/// the `x-request-id` header and shown with errors so users can report them
+async fn assign_request_id(req: Request, next: Next) -> Response {
+    let request_id = request_id_from(
+        req.headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok()),
     );
+    let span = tracing::info_span!("request", request_id = %request_id);
+    let mut response = with_request_id(request_id.clone(), next.run(req))
+        .instrument(span)
+        .await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Record who sent the request as a `ClientIp` extension, looking through
+/// trusted reverse proxies to the address they forwarded for
+async fn resolve_client_ip(State(ctx): State<AppContext>, mut req: Request, next: Next) -> Response {
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map_or(IpAddr::V4(Ipv4Addr::LOCALHOST), |info| info.0.ip());
+    let headers = req.headers();
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let client_ip = ctx
+        .trusted_proxies
+        .resolve(peer, header("x-forwarded-for"), header("x-real-ip"));
+    req.extensions_mut().insert(ClientIp(client_ip));
+    next.run(req).await
+}
+
+/// Turn banned addresses away before any handler or generation runs. Admin
+/// endpoints stay reachable so an admin who bans their own range can still
+/// lift it.
+async fn reject_banned_ips(State(ctx): State<AppContext>, req: Request, next: Next) -> Response {
+    if !req.uri().path().starts_with("/api/admin/") {
+        if let Some(ClientIp(ip)) = req.extensions().get::<ClientIp>().copied() {
+            if let Some(ban) = ctx.ban_ip.banning(ip) {
+                tracing::debug!("Rejected {} under ban {} ({})", ip, ban.id, ban.range);
+                return json_error(StatusCode::FORBIDDEN, "Akses dari alamat ini diblokir");
+            }
+        }
+    }
+    next.run(req).await
+}
+
+/// Time matched routes and hand them to the sampled request log
+async fn track_request(State(ctx): State<AppContext>, req: Request, next: Next) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    let method = req.method().clone();
+    let started = Instant::now();
+
+    let response = next.run(req).await;
+
+    if let Some(route) = route {
+        ctx.request_metrics.record(
+            method.as_str(),
+            &route,
+            response.status().as_u16(),
+            started.elapsed().as_millis() as u64,
+        );
+    }
+    response
+}
+
+/// Keeps "view as user" sessions read-only and bannered: writes are refused
+/// and audited, and every HTML page gets a banner with a way out
+async fn impersonation_guard(
+    State(ctx): State<AppContext>,
+    session: Session,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(admin_id) = session.get::<Uuid>(SESSION_IMPERSONATOR_ID).await.ok().flatten() else {
+        return next.run(req).await;
+    };
+    let Some(target_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return next.run(req).await;
+    };
+
+    let path = req.uri().path().to_string();
+    let allowed = if matches!(*req.method(), axum::http::Method::GET | axum::http::Method::HEAD) {
+        !IMPERSONATION_BLOCKED_READS.contains(&path.as_str())
+    } else {
+        IMPERSONATION_ALLOWED_WRITES.contains(&path.as_str())
+    };
+    if !allowed {
+        tracing::warn!("Blocked {} {} while impersonating {}", req.method(), path, target_id);
+        if let Err(e) = ctx
+            .impersonation_repo
+            .log(admin_id, target_id, ImpersonationEvent::BLOCKED, Some(&path))
+            .await
+        {
+            tracing::error!("Failed to audit blocked write: {}", e);
+        }
+        return json_error(
+            StatusCode::FORBIDDEN,
+            "Mode lihat sebagai user hanya bisa membaca",
+        );
+    }
+
+    let response = next.run(req).await;
+    let is_html = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/html"));
+    if !is_html {
+        return response;
+    }
+
+    let name = match ctx.user_repo.find_by_id(target_id).await {
+        Ok(Some(user)) => user.name,
+        _ => target_id.to_string(),
+    };
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let html = String::from_utf8_lossy(&bytes);
+    let banner = render_impersonation_banner(&name);
+    let html = match html.find("<body").and_then(|i| html[i..].find('>').map(|j| i + j + 1)) {
+        Some(at) => format!("{}{}{}", &html[..at], banner, &html[at..]),
+        None => format!("{}{}", banner, html),
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, axum::body::Body::from(html))
+}
+
+fn render_impersonation_banner(name: &str) -> String {
+    format!(
+        r#"<div style="position:sticky;top:0;z-index:1000;background:#b4637a;color:#fffaf3;padding:0.6rem 1rem;font-family:sans-serif;font-size:0.9rem;text-align:center;">
+            👁 Mode lihat sebagai <strong>{name}</strong> · hanya baca, semua aksi diblokir dan dicatat
+            <form method="post" action="/api/admin/impersonate/stop" style="display:inline;margin-left:0.75rem;">
+                <button type="submit" style="cursor:pointer;">Keluar</button>
+            </form>
+        </div>"#,
+        name = escape_html(name),
+    )
+}
+
+/// Private beta gate for roast creation. A session that redeemed a code once
+/// stays unlocked, so each visitor uses up one redemption.
+async fn check_invite(
+    ctx: &AppContext,
+    session: &Session,
+    code: Option<&str>,
+) -> Result<(), &'static str> {
+    if !ctx.invite_only {
+        return Ok(());
+    }
+    if session.get::<bool>(SESSION_INVITE_OK).await.ok().flatten().unwrap_or(false) {
+        return Ok(());
+    }
+
+    let code = code.map(str::trim).unwrap_or_default();
+    if code.is_empty() {
+        return Err("Roasting Startup lagi private beta. Masukkan kode undangan dulu ya!");
+    }
+    match ctx.invite_repo.redeem(code).await {
+        Ok(true) => {
+            if let Err(e) = session.insert(SESSION_INVITE_OK, true).await {
+                tracing::warn!("Failed to remember redeemed invite: {}", e);
+            }
+            Ok(())
+        }
+        Ok(false) => Err("Kode undangan tidak valid atau sudah habis dipakai."),
+        Err(e) => {
+            tracing::error!("Failed to redeem invite code: {}", e);
+            Err("Gagal memeriksa kode undangan. Coba lagi nanti.")
+        }
+    }
+}
+
+async fn handle_roast_form(ctx: AppContext, session: Session, client_ip: IpAddr, form: RoastForm) -> Response {
+    use roasting_app::infrastructure::security::InputSanitizer;
+
+    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+    if ctx.login_required && user_id.is_none() {
+        return Html(render_login_required_page()).into_response();
+    }
+    if let Err(error) = check_invite(&ctx, &session, form.invite_code.as_deref()).await {
+        return Html(render_invite_page(&form, error)).into_response();
+    }
+
+    if let Err(e) = ctx.rate_limiter.check_rate_limit(client_ip, user_id).await {
+        return rate_limited(&e, Html(render_error_page(&e.message_id())));
+    }
+
+    // A described idea skips URL validation, the queue and the roast cache
+    let idea = match form.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        Some(description) => match InputSanitizer::sanitize_idea(form.idea_name.as_deref(), description) {
+            Ok(idea) => Some(idea),
+            Err(e) => return Html(render_error_page(e.user_message())).into_response(),
+        },
+        None => None,
+    };
+    let from_idea = idea.is_some();
+    let validated_url = match &idea {
+        Some(idea) => idea.source_url(),
+        None => {
+            let validated_url = match InputSanitizer::validate_url(&form.url) {
+                Ok(url) => url,
+                Err(e) => return Html(render_error_page(e.user_message())).into_response(),
+            };
+            if let Err(e) = ctx.generate_roast.ensure_roastable(&validated_url) {
+                return Html(render_error_page(e.user_message())).into_response();
+            }
+            ctx.generate_roast.resolve_url(&validated_url).await
+        }
+    };
+
+    let options = RoastOptions::new(form.language)
+        .with_dialect(form.dialect)
+        .with_length(form.length)
+        .with_safe(form.safe);
+    let dedicated_to = form
+        .dedicated_to
+        .as_deref()
+        .and_then(InputSanitizer::sanitize_dedication);
+
+    if form.defer && !from_idea {
+        return match ctx
+            .roast_queue
+            .enqueue(&validated_url, options, user_id, form.anonymous, dedicated_to.as_deref())
+            .await
+        {
+            Ok(job) => Redirect::to(&format!("/jobs/{}", job.id)).into_response(),
+            Err(e) => {
+                tracing::error!("Failed to queue roast: {}", e);
+                Html(render_error_page(e.user_message())).into_response()
+            }
+        };
+    }
+
+    // An explicitly chosen model is never answered from the cache
+    let cached = if form.regenerate || form.model.is_some() || from_idea {
+        None
+    } else {
+        ctx.generate_roast.cached(&validated_url, options).await
+    };
+    let result = match cached {
+        Some(roast) => Ok(roast),
+        None => {
+            // Logged-in users keep their place when generation is overloaded
+            if user_id.is_none() {
+                if let Err(shed) = ctx.load_shedder.admit_anonymous(form.retry_token.as_deref()) {
+                    tracing::info!("Shedding anonymous roast of {}: {}", validated_url, shed.reason);
+                    return Html(render_busy_page(&form, &shed.retry_token)).into_response();
+                }
+            }
+            if let Err(e) = ctx.cost_tracker.check_and_increment() {
+                if from_idea {
+                    return Html(render_error_page(e.message_id())).into_response();
+                }
+                return Html(render_defer_offer_page(e.message_id(), &form)).into_response();
+            }
+            match idea {
+                Some(idea) => ctx.generate_roast.execute_description(idea, options).await,
+                None => {
+                    ctx.generate_roast
+                        .execute_with_model(validated_url.clone(), options, form.model.as_deref())
+                        .await
+                }
+            }
+        }
+    };
+
+    match result {
+        Ok(roast) => {
+            // Create PersistedRoast and save to database
+            let persisted = PersistedRoast::from_roast(&roast, validated_url.clone(), user_id)
+                .with_anonymous(form.anonymous)
+                .with_dedication(dedicated_to.clone())
+                .with_prompt_variant(ctx.generate_roast.prompt_variant());
+
+            let mut html_content = format!(
+                "{}{}{}",
+                render_logo(&validated_url, "roast__logo", 48),
+                render_ai_wrapper_badge(roast.ai_wrapper_confidence, roast.language),
+                render_roast_content(&roast.roast_text, roast.sections.as_ref(), roast.language)
+            );
+
+            // Persist the roast to database
+            match ctx.roast_repo.create(&persisted).await {
+                Ok(saved_roast) => {
+                    ctx.notify_user
+                        .watch_event(saved_roast.id, NotificationKind::WatchedRoast, user_id);
+                    let battle = suggest_battle(&ctx, saved_roast.id).await;
+                    // Structured data comes from the stored row, as on /r/{id}
+                    if let Ok(Some(details)) = ctx
+                        .roast_repo
+                        .find_by_id_with_details(saved_roast.id, user_id)
+                        .await
+                    {
+                        html_content.push_str(&render_json_ld(roast_json_ld(
+                            &ctx.public_base_url,
+                            &details,
+                        )));
+                    }
+                    Html(render_result_page_with_id(
+                        &roast.startup_name,
+                        &html_content,
+                        dedicated_to.as_deref(),
+                        saved_roast.id,
+                        roast.language,
+                        roast.score.as_ref(),
+                        battle.as_ref(),
+                    ))
+                }
+                Err(e) => {
+                    tracing::error!("Failed to persist roast: {}", e);
+                    // Still show the roast even if persistence fails
+                    Html(render_result_page(
+                        &roast.startup_name,
+                        &html_content,
+                        &validated_url,
+                        roast.language,
+                        roast.score.as_ref(),
+                    ))
+                }
+            }
+            .into_response()
+        }
+        Err(e @ (AppError::OpenRouterError(_) | AppError::LlmError(_) | AppError::QueueFull(_)))
+            if !from_idea =>
+        {
+            Html(render_defer_offer_page(e.user_message(), &form)).into_response()
+        }
+        Err(e) => Html(render_error_page(e.user_message())).into_response(),
+    }
+}
+
+/// Roast an uploaded pitch deck: multipart with the PDF in `deck` and the
+/// same optional `language`, `dialect`, `length`, `safe`, `anonymous`,
+/// `dedicated_to` and `invite_code` fields as the roast form. The roast is
+/// stored like a site roast, under a `deck:<file name>` URL.
+async fn handle_roast_deck(ctx: AppContext, session: Session, client_ip: IpAddr, mut multipart: Multipart) -> Response {
+    use roasting_app::infrastructure::pitch_deck::extract_deck;
+    use roasting_app::infrastructure::security::InputSanitizer;
+    use std::collections::HashMap;
+
+    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+    if ctx.login_required && user_id.is_none() {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    }
+
+    let mut file = None;
+    let mut fields = HashMap::new();
+    loop {
+        let field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => return json_error(e.status(), &e.body_text()),
+        };
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "deck" {
+            let file_name = field.file_name().unwrap_or("deck.pdf").to_string();
+            match field.bytes().await {
+                Ok(bytes) => file = Some((file_name, bytes.to_vec())),
+                Err(e) => return json_error(e.status(), &e.body_text()),
+            }
+        } else {
+            match field.text().await {
+                Ok(value) => {
+                    fields.insert(name, value);
+                }
+                Err(e) => return json_error(e.status(), &e.body_text()),
+            }
+        }
+    }
+    let Some((file_name, bytes)) = file else {
+        return json_error(StatusCode::BAD_REQUEST, "Missing deck file");
+    };
+    let field = |name: &str| fields.get(name).map(|v| v.trim()).filter(|v| !v.is_empty());
+    let flag = |name: &str| matches!(field(name), Some("true" | "on" | "1"));
+
+    if let Err(error) = check_invite(&ctx, &session, field("invite_code")).await {
+        return json_error(StatusCode::FORBIDDEN, error);
+    }
+    if let Err(e) = ctx.rate_limiter.check_rate_limit(client_ip, user_id).await {
+        return rate_limited(&e, json_error(StatusCode::TOO_MANY_REQUESTS, &e.message_id()));
+    }
+
+    let deck = match extract_deck(&file_name, bytes).await {
+        Ok(deck) => deck,
+        Err(e @ AppError::QueueFull(_)) => {
+            return json_error(StatusCode::SERVICE_UNAVAILABLE, e.user_message());
+        }
+        Err(e) => {
+            tracing::info!("Rejected pitch deck {}: {}", file_name, e);
+            return json_error(StatusCode::BAD_REQUEST, e.user_message());
+        }
+    };
+    let startup_url = deck.source_url();
+
+    if user_id.is_none() {
+        if let Err(shed) = ctx.load_shedder.admit_anonymous(field("retry_token")) {
+            tracing::info!("Shedding anonymous deck roast: {}", shed.reason);
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({
+                    "success": false,
+                    "error": "Server lagi rame, coba lagi sebentar lagi",
+                    "retry_token": shed.retry_token,
+                })),
+            )
+                .into_response();
+        }
+    }
+    if let Err(e) = ctx.cost_tracker.check_and_increment() {
+        return json_error(StatusCode::SERVICE_UNAVAILABLE, e.message_id());
+    }
+
+    let options = RoastOptions::new(field("language").and_then(|v| v.parse().ok()).unwrap_or_default())
+        .with_dialect(field("dialect").and_then(|v| v.parse().ok()).unwrap_or_default())
+        .with_length(field("length").and_then(|v| v.parse().ok()).unwrap_or_default())
+        .with_safe(flag("safe"));
+    let roast = match ctx.generate_roast.execute_deck(deck, options).await {
+        Ok(roast) => roast,
+        Err(e) => {
+            tracing::warn!("Deck roast of {} failed: {}", startup_url, e);
+            return json_error(StatusCode::BAD_GATEWAY, e.user_message());
+        }
+    };
+
+    let dedicated_to = field("dedicated_to").and_then(InputSanitizer::sanitize_dedication);
+    let persisted = PersistedRoast::from_roast(&roast, startup_url, user_id)
+        .with_anonymous(flag("anonymous"))
+        .with_dedication(dedicated_to)
+        .with_prompt_variant(ctx.generate_roast.prompt_variant());
+    match ctx.roast_repo.create(&persisted).await {
+        Ok(saved) => {
+            ctx.notify_user
+                .watch_event(saved.id, NotificationKind::WatchedRoast, user_id);
+            Json(serde_json::json!({
+                "success": true,
+                "roast": {
+                    "id": saved.id,
+                    "url": format!("/r/{}", saved.id),
+                    "startup_name": roast.startup_name,
+                    "roast_text": roast.roast_text,
+                    "sections": roast.sections,
+                    "score": roast.score,
+                    "fact_sheet": roast.fact_sheet,
+                },
+            }))
+            .into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to persist deck roast: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to save roast")
+        }
+    }
+}
+
+async fn handle_job_page(ctx: AppContext, session: Session, job_id: Uuid) -> Response {
+    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+
+    match ctx.roast_job_repo.find_by_id(job_id).await {
+        // Jobs queued while logged in are only visible to their owner
+        Ok(Some(job)) if job.user_id.is_none() || job.user_id == user_id => {
+            let job = RoastJob::from(job);
+            match (job.status, job.roast_id) {
+                (JobStatus::Completed, Some(roast_id)) => {
+                    Redirect::to(&format!("/r/{}", roast_id)).into_response()
+                }
+                _ => Html(render_job_page(&job)).into_response(),
+            }
+        }
+        Ok(_) => Html(render_error_page("Antrian roast tidak ditemukan")).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to load roast job: {}", e);
+            Html(render_error_page("Gagal memuat antrian roast")).into_response()
+        }
+    }
+}
+
+async fn handle_my_jobs(ctx: AppContext, session: Session) -> impl IntoResponse {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Must be logged in");
+    };
+
+    match ctx.roast_job_repo.list_for_user(user_id, 20).await {
+        Ok(jobs) => Json(serde_json::json!({
+            "success": true,
+            "jobs": jobs.into_iter().map(RoastJob::from).collect::<Vec<_>>(),
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list roast jobs: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to list roast jobs")
+        }
+    }
+}
+
+// Session keys
+const SESSION_USER_ID: &str = "user_id";
+const SESSION_CSRF_TOKEN: &str = "csrf_token";
+const SESSION_PKCE_VERIFIER: &str = "pkce_verifier";
+/// Set once the session redeemed a private beta invite code
+const SESSION_INVITE_OK: &str = "invite_ok";
+/// The admin behind a read-only "view as user" session; `SESSION_USER_ID`
+/// then holds the impersonated user
+const SESSION_IMPERSONATOR_ID: &str = "impersonator_id";
+/// Random ID that lets an anonymous session answer the spice poll once per roast
+const SESSION_SPICE_VOTER: &str = "spice_voter";
+
+/// Non-GET routes that stay usable while impersonating: leaving the mode and
+/// the server functions that only read
+const IMPERSONATION_ALLOWED_WRITES: &[&str] = &[
+    "/api/admin/impersonate/stop",
+    "/auth/logout",
+    "/api/current_user",
+    "/api/latest_announcement",
+    "/api/home_leaderboard",
+];
+
+/// GET routes that act rather than read, refused while impersonating
+const IMPERSONATION_BLOCKED_READS: &[&str] = &["/roast"];
+
+async fn handle_auth_login(ctx: AppContext, session: Session) -> impl IntoResponse {
+    let (auth_url, csrf_token, pkce_verifier) = ctx.google_oauth.get_auth_url();
+
+    // Store CSRF token and PKCE verifier in session
+    if let Err(e) = session.insert(SESSION_CSRF_TOKEN, csrf_token.secret().clone()).await {
+        tracing::error!("Failed to store CSRF token: {}", e);
+        return Redirect::to("/?error=session_error");
+    }
+    if let Err(e) = session.insert(SESSION_PKCE_VERIFIER, pkce_verifier.secret().clone()).await {
+        tracing::error!("Failed to store PKCE verifier: {}", e);
+        return Redirect::to("/?error=session_error");
+    }
+
+    Redirect::to(&auth_url)
+}
+
+async fn handle_auth_callback(
+    ctx: AppContext,
+    session: Session,
+    query: AuthCallbackQuery,
+) -> impl IntoResponse {
+    // Verify CSRF token
+    let stored_csrf: Option<String> = session.get(SESSION_CSRF_TOKEN).await.ok().flatten();
+    if stored_csrf.is_none() {
+        tracing::warn!("CSRF token not found in session - session may have expired or server restarted");
+        // Redirect to login again instead of showing error
+        return Redirect::to("/auth/login");
+    }
+    if stored_csrf.as_ref() != Some(&query.state) {
+        tracing::warn!("CSRF token mismatch: stored={:?}, received={}", stored_csrf, &query.state);
+        return Redirect::to("/auth/login");
+    }
+
+    // Get PKCE verifier
+    let pkce_secret: Option<String> = session.get(SESSION_PKCE_VERIFIER).await.ok().flatten();
+    let pkce_verifier = match pkce_secret {
+        Some(secret) => oauth2::PkceCodeVerifier::new(secret),
+        None => {
+            tracing::warn!("PKCE verifier not found in session");
+            return Redirect::to("/?error=session_error");
+        }
+    };
+
+    // Exchange code for user info
+    let user_info = match ctx.google_oauth.exchange_code(&query.code, pkce_verifier).await {
+        Ok(info) => info,
+        Err(e) => {
+            tracing::error!("OAuth exchange failed: {}", e);
+            return Redirect::to("/?error=oauth_failed");
+        }
+    };
+
+    // Create User object
+    let new_user = User {
+        id: Uuid::new_v4(),
+        google_id: user_info.sub.clone(),
+        email: user_info.email.clone(),
+        name: user_info.name.clone(),
+        avatar_url: user_info.picture.clone(),
+        hide_authorship: false,
+        created_at: None,
+        updated_at: None,
+    };
+
+    // Upsert user in database
+    let user = match ctx.user_repo.upsert(&new_user).await {
+        Ok(user) => user,
+        Err(e) => {
+            tracing::error!("Failed to upsert user: {}", e);
+            return Redirect::to("/?error=db_error");
+        }
+    };
+
+    // Store user ID in session
+    if let Err(e) = session.insert(SESSION_USER_ID, user.id).await {
+        tracing::error!("Failed to store user ID in session: {}", e);
+        return Redirect::to("/?error=session_error");
+    }
+
+    // A fresh login ends any impersonation left in this session
+    let _ = session.remove::<Uuid>(SESSION_IMPERSONATOR_ID).await;
+
+    // Clean up OAuth state from session
+    let _ = session.remove::<String>(SESSION_CSRF_TOKEN).await;
+    let _ = session.remove::<String>(SESSION_PKCE_VERIFIER).await;
+
+    tracing::info!("User logged in: {} ({})", user.name, user.email);
+    Redirect::to("/")
+}
+
+async fn handle_auth_logout(session: Session) -> impl IntoResponse {
+    session.flush().await.ok();
+    Redirect::to("/")
+}
+
+async fn handle_auth_me(ctx: AppContext, session: Session) -> impl IntoResponse {
+    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+    let impersonator_id: Option<Uuid> = session.get(SESSION_IMPERSONATOR_ID).await.ok().flatten();
+
+    match user_id {
+        Some(id) => match ctx.user_repo.find_by_id(id).await {
+            Ok(Some(user)) => Json(serde_json::json!({
+                "authenticated": true,
+                "login_required": ctx.login_required,
+                "impersonated_by": impersonator_id,
+                "user": {
+                    "id": user.id,
+                    "name": user.name,
+                    "email": user.email,
+                    "avatar_url": user.avatar_url,
+                    "hide_authorship": user.hide_authorship,
+                }
+            })).into_response(),
+            _ => Json(serde_json::json!({
+                "authenticated": false,
+                "login_required": ctx.login_required,
+            })).into_response(),
+        },
+        None => Json(serde_json::json!({
+            "authenticated": false,
+            "login_required": ctx.login_required,
+        })).into_response(),
+    }
+}
+
+/// Who is answering the spice poll: the logged-in user, else a random ID kept
+/// in the session. With `create`, an anonymous session gets one if missing.
+async fn spice_voter_key(session: &Session, create: bool) -> Option<String> {
+    if let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() {
+        return Some(format!("user:{}", user_id));
+    }
+    if let Some(voter) = session.get::<Uuid>(SESSION_SPICE_VOTER).await.ok().flatten() {
+        return Some(format!("session:{}", voter));
+    }
+    if !create {
+        return None;
+    }
+    let voter = Uuid::new_v4();
+    if let Err(e) = session.insert(SESSION_SPICE_VOTER, voter).await {
+        tracing::warn!("Failed to store spice voter ID: {}", e);
+        return None;
+    }
+    Some(format!("session:{}", voter))
+}
+
+fn spice_json(spice: &SpiceDistribution) -> serde_json::Value {
+    let percent: serde_json::Map<String, serde_json::Value> = SpiceLevel::all()
+        .iter()
+        .map(|level| (level.as_str().to_string(), spice.percent(*level).into()))
+        .collect();
+    serde_json::json!({
+        "counts": spice,
+        "percent": percent,
+        "total": spice.total(),
+    })
+}
+
+async fn handle_spice_vote(
+    ctx: AppContext,
+    session: Session,
+    roast_id: Uuid,
+    input: SpiceInput,
+) -> axum::response::Response {
+    match ctx.roast_repo.find_by_id(roast_id).await {
+        Ok(Some(roast)) if roast.removed_at.is_none() => {}
+        Ok(_) => return json_error(StatusCode::NOT_FOUND, "Roast not found"),
+        Err(e) => {
+            tracing::error!("Failed to load roast for spice vote: {}", e);
+            return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load roast");
+        }
+    }
+    let Some(voter_key) = spice_voter_key(&session, true).await else {
+        return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Session unavailable");
+    };
+
+    if let Err(e) = ctx.spice_vote_repo.vote(roast_id, &voter_key, input.level).await {
+        tracing::error!("Failed to save spice vote: {}", e);
+        return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to save vote");
+    }
+    match ctx.spice_vote_repo.distribution(roast_id).await {
+        Ok(spice) => Json(serde_json::json!({
+            "success": true,
+            "level": input.level,
+            "spice": spice_json(&spice),
+        }))
+        .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to load spice poll: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load poll")
+        }
+    }
+}
+
+async fn handle_set_anonymous(
+    ctx: AppContext,
+    session: Session,
+    roast_id: Uuid,
+    input: AnonymousInput,
+) -> axum::response::Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    match ctx.roast_repo.set_anonymous(roast_id, user_id, input.anonymous).await {
+        Ok(Some(roast)) => Json(serde_json::json!({
+            "success": true,
+            "anonymous": roast.anonymous,
+        }))
+        .into_response(),
+        Ok(None) => json_error(StatusCode::NOT_FOUND, "Roast not found"),
+        Err(e) => {
+            tracing::error!("Failed to update roast privacy: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to update roast")
+        }
+    }
+}
+
+/// Set or lift a roast's embargo; owner only
+async fn handle_schedule_roast(
+    ctx: AppContext,
+    session: Session,
+    roast_id: Uuid,
+    input: ScheduleInput,
+) -> axum::response::Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    match ctx.schedule_roast.schedule(roast_id, user_id, input.publish_at).await {
+        Ok(publish_at) => Json(serde_json::json!({
+            "success": true,
+            "publish_at": publish_at,
+        }))
+        .into_response(),
+        Err(error) => {
+            let status = match error {
+                ScheduleError::NotFound => StatusCode::NOT_FOUND,
+                ScheduleError::Invalid(_) => StatusCode::BAD_REQUEST,
+                ScheduleError::Internal(ref e) => {
+                    tracing::error!("Failed to schedule roast: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+            };
+            json_error(status, error.user_message())
+        }
+    }
+}
+
+/// Re-run the roast for the stored URL; only the owner may, and it costs the
+/// same rate/cost budget as a new roast
+async fn handle_regenerate_roast(
+    ctx: AppContext,
+    session: Session,
+    client_ip: IpAddr,
+    roast_id: Uuid,
+) -> axum::response::Response {
+
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    let existing = match ctx.roast_repo.find_by_id(roast_id).await {
+        Ok(Some(r)) if r.removed_at.is_none() => r,
+        Ok(_) => return json_error(StatusCode::NOT_FOUND, "Roast not found"),
+        Err(e) => {
+            tracing::error!("Failed to load roast for regeneration: {}", e);
+            return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load roast");
+        }
+    };
+    if existing.user_id != Some(user_id) {
+        return json_error(StatusCode::FORBIDDEN, "Only the roast's author can regenerate it");
+    }
+    if PitchDeck::is_deck_url(&existing.startup_url) {
+        return json_error(StatusCode::BAD_REQUEST, "Deck roasts can't be regenerated; upload the deck again");
+    }
+    if StartupIdea::is_idea_url(&existing.startup_url) {
+        return json_error(StatusCode::BAD_REQUEST, "Idea roasts can't be regenerated; describe the idea again");
+    }
+
+    if let Err(e) = ctx.rate_limiter.check_rate_limit(client_ip, Some(user_id)).await {
+        return rate_limited(&e, json_error(StatusCode::TOO_MANY_REQUESTS, &e.message_id()));
+    }
+    if let Err(e) = ctx.cost_tracker.check_and_increment() {
+        return json_error(StatusCode::SERVICE_UNAVAILABLE, e.message_id());
+    }
+
+    let options = RoastOptions::new(existing.language.parse().unwrap_or_default())
+        .with_dialect(existing.dialect.parse().unwrap_or_default())
+        .with_safe(existing.safe);
+    let roast = match ctx
+        .generate_roast
+        .execute(existing.startup_url.clone(), options)
+        .await
+    {
+        Ok(roast) => roast,
+        Err(e) => {
+            tracing::warn!("Regenerating roast {} failed: {}", roast_id, e);
+            return json_error(StatusCode::BAD_GATEWAY, e.user_message());
+        }
+    };
+
+    match ctx.roast_repo.replace_with_version(roast_id, &roast).await {
+        Ok(Some(updated)) => Json(serde_json::json!({
+            "success": true,
+            "roast": {
+                "id": updated.id,
+                "roast_text": updated.roast_text,
+                "sections": roast.sections,
+                "score": roast.score,
+                "ai_wrapper_confidence": roast.ai_wrapper_confidence,
+                "security": roast.security,
+                "social_links": roast.social_links,
+                "fact_sheet": roast.fact_sheet,
+            },
+        }))
+        .into_response(),
+        Ok(None) => json_error(StatusCode::NOT_FOUND, "Roast not found"),
+        Err(e) => {
+            tracing::error!("Failed to save regenerated roast: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to save roast")
+        }
+    }
+}
+
+async fn handle_roast_versions(ctx: AppContext, session: Session, roast_id: Uuid) -> axum::response::Response {
+    let user_id = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten();
+    // Removed roasts keep their history private too, embargoed ones to all but their owner
+    match ctx.roast_repo.find_by_id(roast_id).await {
+        Ok(Some(r))
+            if r.removed_at.is_none()
+                && (r.publish_at.is_none() || (user_id.is_some() && r.user_id == user_id)) => {}
+        Ok(_) => return json_error(StatusCode::NOT_FOUND, "Roast not found"),
+        Err(e) => {
+            tracing::error!("Failed to load roast: {}", e);
+            return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load roast");
+        }
+    }
+
+    match ctx.roast_repo.list_versions(roast_id).await {
+        Ok(versions) => Json(serde_json::json!({
+            "success": true,
+            "versions": versions.into_iter().map(|v| serde_json::json!({
+                "roast_text": v.roast_text,
+                "replaced_at": v.created_at,
+            })).collect::<Vec<_>>(),
+        }))
+        .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list roast versions: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to list versions")
+        }
+    }
+}
+
+async fn handle_set_privacy(
+    ctx: AppContext,
+    session: Session,
+    input: PrivacyInput,
+) -> axum::response::Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    match ctx.user_repo.set_hide_authorship(user_id, input.hide_authorship).await {
+        Ok(user) => Json(serde_json::json!({
+            "success": true,
+            "hide_authorship": user.hide_authorship,
+        }))
+        .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to update privacy settings: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to update settings")
+        }
+    }
+}
+
+/// The in-app inbox, newest first
+async fn handle_list_notifications(ctx: AppContext, session: Session) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    let listed = ctx.notification_repo.list_for_user(user_id, 50).await;
+    let unread = ctx.notification_repo.unread_count(user_id).await;
+    match listed.and_then(|notifications| Ok((notifications, unread?))) {
+        Ok((notifications, unread)) => Json(serde_json::json!({
+            "success": true,
+            "unread": unread,
+            "notifications": notifications.into_iter().map(|n| serde_json::json!({
+                "id": n.id,
+                "kind": n.kind,
+                "title": n.title,
+                "body": n.body,
+                "link": n.link,
+                "read": n.read_at.is_some(),
+                "created_at": n.created_at,
+            })).collect::<Vec<_>>(),
+        }))
+        .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list notifications: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to list notifications")
+        }
+    }
+}
+
+async fn handle_mark_notifications_read(ctx: AppContext, session: Session) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    match ctx.notification_repo.mark_all_read(user_id).await {
+        Ok(marked) => Json(serde_json::json!({ "success": true, "marked": marked })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to mark notifications read: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to mark notifications read")
+        }
+    }
+}
+
+/// Every configured delivery channel with the user's setting for it
+async fn handle_notification_channels(ctx: AppContext, session: Session) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    match ctx.notify_user.preferences(user_id).await {
+        Ok(channels) => Json(serde_json::json!({ "success": true, "channels": channels })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to load notification preferences: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load preferences")
+        }
+    }
+}
+
+async fn handle_set_notification_channel(
+    ctx: AppContext,
+    session: Session,
+    channel: String,
+    input: NotificationChannelInput,
+) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    match ctx
+        .notify_user
+        .set_preference(user_id, &channel, input.enabled, input.address.as_deref())
+        .await
+    {
+        Ok(preference) => Json(serde_json::json!({ "success": true, "channel": preference })).into_response(),
+        Err(error) => {
+            let status = match error {
+                PreferenceError::UnknownChannel => StatusCode::NOT_FOUND,
+                PreferenceError::Invalid(_) => StatusCode::BAD_REQUEST,
+                PreferenceError::Internal(ref e) => {
+                    tracing::error!("Failed to save notification preference: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+            };
+            json_error(status, error.user_message())
+        }
+    }
+}
+
+/// Most startup domains one user can watch
+const MAX_WATCHES: u64 = 100;
+
+/// Startup domains the user watches, newest first
+async fn handle_list_watches(ctx: AppContext, session: Session) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    match ctx.watch_repo.list_for_user(user_id).await {
+        Ok(watches) => Json(serde_json::json!({
+            "success": true,
+            "watches": watches.into_iter().map(|w| serde_json::json!({
+                "domain": w.domain,
+                "created_at": w.created_at,
+            })).collect::<Vec<_>>(),
+        }))
+        .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list watches: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to list watches")
+        }
+    }
+}
+
+/// Get notified about new roasts and follow-ups of a startup domain
+async fn handle_watch(ctx: AppContext, session: Session, domain: String) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+    let Some(domain) = DomainOptOut::normalize_domain(&domain) else {
+        return json_error(StatusCode::BAD_REQUEST, "Invalid domain");
+    };
+
+    match ctx.watch_repo.count_for_user(user_id).await {
+        Ok(count) if count >= MAX_WATCHES => {
+            return json_error(StatusCode::BAD_REQUEST, "Too many watched startups");
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("Failed to count watches: {}", e);
+            return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to watch startup");
+        }
+    }
+    match ctx.watch_repo.watch(user_id, &domain).await {
+        Ok(()) => Json(serde_json::json!({ "success": true, "domain": domain, "watching": true })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to watch {}: {}", domain, e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to watch startup")
+        }
+    }
+}
+
+async fn handle_unwatch(ctx: AppContext, session: Session, domain: String) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+    let Some(domain) = DomainOptOut::normalize_domain(&domain) else {
+        return json_error(StatusCode::BAD_REQUEST, "Invalid domain");
+    };
+
+    match ctx.watch_repo.unwatch(user_id, &domain).await {
+        Ok(_) => Json(serde_json::json!({ "success": true, "domain": domain, "watching": false })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to unwatch {}: {}", domain, e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to unwatch startup")
+        }
+    }
+}
+
+/// VAPID key for `pushManager.subscribe`; 404 while web push is off
+async fn handle_push_public_key(ctx: AppContext) -> Response {
+    match &ctx.web_push {
+        Some(web_push) => Json(serde_json::json!({ "success": true, "public_key": web_push.public_key() }))
+            .into_response(),
+        None => json_error(StatusCode::NOT_FOUND, "Web push is not configured"),
+    }
+}
+
+async fn handle_push_subscribe(ctx: AppContext, session: Session, input: PushSubscriptionInput) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+    if ctx.web_push.is_none() {
+        return json_error(StatusCode::NOT_FOUND, "Web push is not configured");
+    }
+    if let Err(reason) = WebPushChannel::check_subscription(&input.endpoint, &input.keys.p256dh, &input.keys.auth) {
+        return json_error(StatusCode::BAD_REQUEST, &reason);
+    }
+
+    match ctx
+        .push_subscription_repo
+        .upsert(user_id, &input.endpoint, &input.keys.p256dh, &input.keys.auth)
+        .await
+    {
+        Ok(()) => Json(serde_json::json!({ "success": true })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to save push subscription: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to save subscription")
+        }
+    }
+}
+
+async fn handle_push_unsubscribe(ctx: AppContext, session: Session, input: PushUnsubscribeInput) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    match ctx.push_subscription_repo.delete(user_id, &input.endpoint).await {
+        Ok(removed) => Json(serde_json::json!({ "success": true, "removed": removed })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to delete push subscription: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete subscription")
+        }
+    }
+}
+
+async fn handle_vote(ctx: AppContext, session: Session, roast_id: Uuid) -> impl IntoResponse {
+    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+
+    match user_id {
+        Some(user_id) => {
+            // Weighting, self-vote rules and milestone notifications all live in ToggleVote
+            match ctx.toggle_vote.execute(user_id, roast_id).await {
+                Ok(result) => {
+                    Json(serde_json::json!({
+                        "success": true,
+                        "voted": result.voted,
+                        "fire_count": result.new_fire_count,
+                    })).into_response()
+                }
+                Err(e) => {
+                    let status = match &e {
+                        VoteError::NotFound => StatusCode::NOT_FOUND,
+                        VoteError::OwnRoast => StatusCode::FORBIDDEN,
+                        VoteError::Internal(reason) => {
+                            tracing::error!("Vote failed: {}", reason);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        }
+                    };
+                    json_error(status, e.user_message())
+                }
+            }
+        }
+        None => {
+            (StatusCode::UNAUTHORIZED, Json(serde_json::json!({
+                "success": false,
+                "error": "Must be logged in to vote"
+            }))).into_response()
+        }
+    }
+}
+
+async fn handle_leaderboard(ctx: AppContext, session: Session, query: LeaderboardQuery) -> impl IntoResponse {
+    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+
+    let country = query.country();
+    let search = query.search();
+    match ctx
+        .roast_repo
+        .get_leaderboard(50, user_id, query.safe, country.as_deref(), search.as_deref())
+        .await
+    {
+        Ok(roasts) => Json(serde_json::json!({
+            "success": true,
+            "roasts": roasts.into_iter().map(|r| serde_json::json!({
+                "id": r.id,
+                "startup_name": r.startup_name,
+                "startup_url": r.startup_url,
+                "roast_text": r.roast_text,
+                "sections": r.sections,
+                "score": r.score,
+                "ai_wrapper_confidence": r.ai_wrapper_confidence,
+                "safe": r.safe,
+                "language": r.language,
+                "dialect": r.dialect,
+                "category": r.category,
+                "country": r.country,
+                "fire_count": r.fire_count,
+                "created_at": r.created_at,
+                "author_name": r.author_name,
+                "author_avatar": r.author_avatar,
+                "is_anonymous": r.is_anonymous,
+                "is_owner": r.is_owner,
+                "user_has_voted": r.user_has_voted,
+            })).collect::<Vec<_>>(),
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to get leaderboard: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "success": false,
+                "error": "Failed to fetch leaderboard"
+            }))).into_response()
+        }
+    }
+}
+
+async fn handle_leaderboard_page(ctx: AppContext, session: Session, query: LeaderboardQuery) -> impl IntoResponse {
+    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+
+    let country = query.country();
+    let countries = match ctx.roast_repo.country_stats().await {
+        Ok(countries) => countries,
+        Err(e) => {
+            tracing::warn!("Failed to load country stats: {}", e);
+            Vec::new()
+        }
+    };
+    let search = query.search();
+    match ctx
+        .roast_repo
+        .get_leaderboard(50, user_id, query.safe, country.as_deref(), search.as_deref())
+        .await
+    {
+        Ok(roasts) => Html(render_leaderboard_page(
+            &roasts,
+            query.safe,
+            country.as_deref(),
+            search.as_deref(),
+            &countries,
+            &ctx.public_base_url,
+        )),
+        Err(e) => {
+            tracing::error!("Failed to get leaderboard: {}", e);
+            Html(render_error_page("Gagal memuat leaderboard"))
+        }
+    }
+}
+
+/// Live roasts and fires per detected country
+async fn handle_country_stats(ctx: AppContext) -> Response {
+    match ctx.roast_repo.country_stats().await {
+        Ok(countries) => Json(serde_json::json!({
+            "success": true,
+            "countries": countries,
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to load country stats: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load country stats")
+        }
+    }
+}
+
+async fn handle_view_roast_page(
+    ctx: AppContext,
+    session: Session,
+    roast_id: Uuid,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+
+    match ctx.roast_repo.find_by_id_with_details(roast_id, user_id).await {
+        Ok(Some(roast)) => {
+            // The author reloading their own roast isn't an audience
+            if !roast.is_owner && roast.publish_at.is_none() {
+                ctx.track_roast_analytics.record_view(
+                    roast_id,
+                    headers.get(header::REFERER).and_then(|v| v.to_str().ok()),
+                    headers.get(header::USER_AGENT).and_then(|v| v.to_str().ok()),
+                );
+            }
+            let battle = suggest_battle(&ctx, roast_id).await;
+            let follow_ups: Vec<RoastFollowUp> = match ctx.follow_up_repo.list_for_roast(roast_id).await {
+                Ok(rows) => rows.into_iter().map(RoastFollowUp::from).collect(),
+                Err(e) => {
+                    tracing::warn!("Failed to load follow-ups for {}: {}", roast_id, e);
+                    Vec::new()
+                }
+            };
+            let compare_html = match ctx.roast_repo.find_previous_for_url(&roast).await {
+                Ok(Some(previous_id)) => format!(
+                    r#"<p class="roast__compare"><a href="/r/{}/compare/{}">↔️ {}</a></p>"#,
+                    previous_id,
+                    roast_id,
+                    labels(roast.language).compare_link
+                ),
+                Ok(None) => String::new(),
+                Err(e) => {
+                    tracing::warn!("Failed to look up earlier roasts of {}: {}", roast_id, e);
+                    String::new()
+                }
+            };
+            let spice = match ctx.spice_vote_repo.distribution(roast_id).await {
+                Ok(spice) => spice,
+                Err(e) => {
+                    tracing::warn!("Failed to load spice poll of {}: {}", roast_id, e);
+                    SpiceDistribution::default()
+                }
+            };
+            let my_spice = match spice_voter_key(&session, false).await {
+                Some(key) => ctx.spice_vote_repo.find_vote(roast_id, &key).await.ok().flatten(),
+                None => None,
+            };
+            let html_content = format!(
+                "{}{}{}{}{}{}{}{}{}{}",
+                render_logo(&roast.startup_url, "roast__logo", 48),
+                render_ai_wrapper_badge(roast.ai_wrapper_confidence, roast.language),
+                render_roast_content(&roast.roast_text, roast.sections.as_ref(), roast.language),
+                render_fact_sheet(roast.fact_sheet.as_ref(), roast.language),
+                render_screenshot(roast_id, roast.language),
+                render_social_links(&roast.social_links, roast.language),
+                compare_html,
+                render_spice_poll(roast_id, &spice, my_spice, roast.language),
+                render_follow_ups(roast_id, &follow_ups, roast.language),
+                render_json_ld(roast_json_ld(&ctx.public_base_url, &roast))
+            );
+            Html(render_result_page_with_id(
+                &roast.startup_name,
+                &html_content,
+                roast.dedicated_to.as_deref(),
+                roast_id,
+                roast.language,
+                roast.score.as_ref(),
+                battle.as_ref(),
+            ))
+        }
+        Ok(None) => Html(render_error_page("Roast tidak ditemukan")),
+        Err(e) => {
+            tracing::error!("Failed to get roast: {}", e);
+            Html(render_error_page("Gagal memuat roast"))
+        }
+    }
+}
+
+/// Two roasts of the same startup side by side, older first
+async fn handle_compare_roasts_page(
+    ctx: AppContext,
+    session: Session,
+    roast_id: Uuid,
+    other_id: Uuid,
+) -> Html<String> {
+    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+
+    let first = ctx.roast_repo.find_by_id_with_details(roast_id, user_id).await;
+    let second = ctx.roast_repo.find_by_id_with_details(other_id, user_id).await;
+    let (first, second) = match (first, second) {
+        (Ok(Some(first)), Ok(Some(second))) => (first, second),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("Failed to load roasts to compare: {}", e);
+            return Html(render_error_page("Gagal memuat roast"));
+        }
+        _ => return Html(render_error_page("Roast tidak ditemukan")),
+    };
+    if roast_id == other_id
+        || DomainOptOut::normalize_domain(&first.startup_url)
+            != DomainOptOut::normalize_domain(&second.startup_url)
+    {
+        return Html(render_error_page(
+            "Cuma dua roast dari startup yang sama yang bisa dibandingkan",
+        ));
+    }
+
+    let (older, newer) = if first.created_at <= second.created_at {
+        (first, second)
+    } else {
+        (second, first)
+    };
+    let diff = RoastDiff::between(&older.roast_text, &newer.roast_text);
+    Html(render_compare_page(&older, &newer, &diff))
+}
+
+async fn handle_get_roast(ctx: AppContext, session: Session, roast_id: Uuid) -> impl IntoResponse {
+    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+
+    match ctx.roast_repo.find_by_id_with_details(roast_id, user_id).await {
+        Ok(Some(roast)) => {
+            let domain = DomainOptOut::normalize_domain(&roast.startup_url);
+            let watching = match (user_id, &domain) {
+                (Some(uid), Some(domain)) => ctx.watch_repo.is_watching(uid, domain).await.unwrap_or_else(|e| {
+                    tracing::warn!("Failed to check watch on {}: {}", domain, e);
+                    false
+                }),
+                _ => false,
+            };
+            let founder = match (user_id, &domain) {
+                (Some(uid), Some(domain)) => ctx.track_roast_analytics.is_founder(uid, domain).await,
+                _ => false,
+            };
+            Json(serde_json::json!({
+                "success": true,
+                "roast": {
+                    "id": roast.id,
+                    "startup_name": roast.startup_name,
+                    "startup_url": roast.startup_url,
+                    "roast_text": roast.roast_text,
+                    "sections": roast.sections,
+                    "score": roast.score,
+                    "ai_wrapper_confidence": roast.ai_wrapper_confidence,
+                    "security": roast.security,
+                    "social_links": roast.social_links,
+                    "fact_sheet": roast.fact_sheet,
+                    "safe": roast.safe,
+                    "language": roast.language,
+                    "dialect": roast.dialect,
+                    "category": roast.category,
+                    "fire_count": roast.fire_count,
+                    "created_at": roast.created_at,
+                    "author_name": roast.author_name,
+                    "author_avatar": roast.author_avatar,
+                    "is_anonymous": roast.is_anonymous,
+                    "is_owner": roast.is_owner,
+                    "publish_at": roast.publish_at,
+                },
+                "has_voted": roast.user_has_voted,
+                "watch": {
+                    "domain": domain,
+                    "watching": watching,
+                },
+                "founder": founder,
+            })).into_response()
+        }
+        Ok(None) => {
+            (StatusCode::NOT_FOUND, Json(serde_json::json!({
+                "success": false,
+                "error": "Roast not found"
+            }))).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to get roast: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "success": false,
+                "error": "Failed to fetch roast"
+            }))).into_response()
+        }
+    }
+}
+
+fn json_error(status: StatusCode, error: &str) -> axum::response::Response {
+    (status, Json(serde_json::json!({
+        "success": false,
+        "error": error,
+        "request_id": current_request_id(),
+    }))).into_response()
+}
+
+/// A 429 carrying `Retry-After` and `X-RateLimit-*` so clients know when to
+/// come back
+fn rate_limited(e: &RateLimitError, body: impl IntoResponse) -> Response {
+    let mut response = (StatusCode::TOO_MANY_REQUESTS, body).into_response();
+    for (name, value) in e.headers() {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            response.headers_mut().insert(name, value);
+        }
+    }
+    response
+}
+
+/// Resolve the logged-in user and make sure they are listed in ADMIN_EMAILS.
+/// While impersonating, this is the admin rather than the viewed user.
+async fn require_admin(
+    ctx: &AppContext,
+    session: &Session,
+) -> Result<user::Model, (StatusCode, &'static str)> {
+    let user_id: Option<Uuid> = match session.get(SESSION_IMPERSONATOR_ID).await.ok().flatten() {
+        Some(admin_id) => Some(admin_id),
+        None => session.get(SESSION_USER_ID).await.ok().flatten(),
+    };
+    let user = match user_id {
+        Some(id) => ctx.user_repo.find_by_id(id).await.ok().flatten(),
+        None => None,
+    };
+
+    match user {
+        Some(user) if ctx.is_admin(&user.email) => Ok(user),
+        Some(_) => Err((StatusCode::FORBIDDEN, "Admin only")),
+        None => Err((StatusCode::UNAUTHORIZED, "Must be logged in")),
+    }
+}
+
+fn validate_announcement(input: &AnnouncementInput) -> Result<(), &'static str> {
+    let title = input.title.trim();
+    if title.is_empty() || title.len() > 255 || input.body.trim().is_empty() {
+        return Err("Title (max 255 chars) and body are required");
+    }
+    Ok(())
+}
+
+async fn handle_admin_list_announcements(ctx: AppContext, session: Session) -> impl IntoResponse {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match ctx.announcement_repo.list_all().await {
+        Ok(items) => Json(serde_json::json!({
+            "success": true,
+            "announcements": items.into_iter().map(Announcement::from).collect::<Vec<_>>(),
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list announcements: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "success": false,
+                "error": "Failed to list announcements"
+            }))).into_response()
+        }
+    }
+}
+
+async fn handle_admin_create_announcement(
+    ctx: AppContext,
+    session: Session,
+    input: AnnouncementInput,
+) -> impl IntoResponse {
+    let admin = match require_admin(&ctx, &session).await {
+        Ok(admin) => admin,
+        Err((status, error)) => return json_error(status, error),
+    };
+    if let Err(error) = validate_announcement(&input) {
+        return json_error(StatusCode::BAD_REQUEST, error);
+    }
+
+    match ctx
+        .announcement_repo
+        .create(input.title.trim(), input.body.trim(), input.published)
+        .await
+    {
+        Ok(created) => {
+            tracing::info!("Announcement {} created by {}", created.id, admin.email);
+            (StatusCode::CREATED, Json(serde_json::json!({
+                "success": true,
+                "announcement": Announcement::from(created),
+            }))).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to create announcement: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "success": false,
+                "error": "Failed to create announcement"
+            }))).into_response()
+        }
+    }
+}
+
+async fn handle_admin_update_announcement(
+    ctx: AppContext,
+    session: Session,
+    id: Uuid,
+    input: AnnouncementInput,
+) -> impl IntoResponse {
+    let admin = match require_admin(&ctx, &session).await {
+        Ok(admin) => admin,
+        Err((status, error)) => return json_error(status, error),
+    };
+    if let Err(error) = validate_announcement(&input) {
+        return json_error(StatusCode::BAD_REQUEST, error);
+    }
+
+    match ctx
+        .announcement_repo
+        .update(id, input.title.trim(), input.body.trim(), input.published)
+        .await
+    {
+        Ok(Some(updated)) => {
+            tracing::info!("Announcement {} updated by {}", id, admin.email);
+            Json(serde_json::json!({
+                "success": true,
+                "announcement": Announcement::from(updated),
+            })).into_response()
+        }
+        Ok(None) => (StatusCode::NOT_FOUND, Json(serde_json::json!({
+            "success": false,
+            "error": "Announcement not found"
+        }))).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to update announcement: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "success": false,
+                "error": "Failed to update announcement"
+            }))).into_response()
+        }
+    }
+}
+
+async fn handle_admin_delete_announcement(
+    ctx: AppContext,
+    session: Session,
+    id: Uuid,
+) -> impl IntoResponse {
+    let admin = match require_admin(&ctx, &session).await {
+        Ok(admin) => admin,
+        Err((status, error)) => return json_error(status, error),
+    };
+
+    match ctx.announcement_repo.delete(id).await {
+        Ok(true) => {
+            tracing::info!("Announcement {} deleted by {}", id, admin.email);
+            Json(serde_json::json!({ "success": true })).into_response()
+        }
+        Ok(false) => (StatusCode::NOT_FOUND, Json(serde_json::json!({
+            "success": false,
+            "error": "Announcement not found"
+        }))).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to delete announcement: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "success": false,
+                "error": "Failed to delete announcement"
+            }))).into_response()
+        }
+    }
+}
+
+/// Longest report or takedown reason we keep
+const MAX_REASON_CHARS: usize = 500;
+
+fn clean_reason(reason: &str) -> Option<String> {
+    let reason: String = reason.trim().chars().take(MAX_REASON_CHARS).collect();
+    (!reason.is_empty()).then_some(reason)
+}
+
+async fn handle_report_roast(
+    ctx: AppContext,
+    session: Session,
+    roast_id: Uuid,
+    input: ReportInput,
+) -> axum::response::Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+    let Some(reason) = clean_reason(&input.reason) else {
+        return json_error(StatusCode::BAD_REQUEST, "Reason is required");
+    };
+
+    match ctx.moderation_repo.report(roast_id, Some(user_id), &reason).await {
+        Ok(Some(_)) => Json(serde_json::json!({ "success": true })).into_response(),
+        Ok(None) => json_error(StatusCode::NOT_FOUND, "Roast not found"),
+        Err(e) => {
+            tracing::error!("Failed to file report: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to file report")
+        }
+    }
+}
+
+/// File a "laporkan masalah" report; anonymous users can report too, since
+/// a failed roast is often their first visit. Reports count against the
+/// rate limit, and a repeat of an open report is accepted but not stored.
+async fn handle_report_problem(
+    ctx: AppContext,
+    session: Session,
+    client_ip: IpAddr,
+    input: ProblemReportInput,
+) -> Response {
+    let request_id = input.request_id.trim();
+    if !is_valid_request_id(request_id) {
+        return json_error(StatusCode::BAD_REQUEST, "Invalid request ID");
+    }
+    let Some(message) = clean_reason(&input.message) else {
+        return json_error(StatusCode::BAD_REQUEST, "Message is required");
+    };
+    let error = clean_reason(&input.error).unwrap_or_default();
+    let reporter_id = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten();
+
+    if let Err(e) = ctx.rate_limiter.check_rate_limit(client_ip, reporter_id).await {
+        return rate_limited(&e, json_error(StatusCode::TOO_MANY_REQUESTS, &e.message_id()));
+    }
+    match ctx.problem_report_repo.has_open(request_id, reporter_id).await {
+        Ok(true) => return Json(serde_json::json!({ "success": true })).into_response(),
+        Ok(false) => {}
+        Err(e) => {
+            tracing::error!("Failed to check for a duplicate problem report: {}", e);
+            return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to file report");
+        }
+    }
+
+    match ctx.problem_report_repo.create(request_id, reporter_id, &error, &message).await {
+        Ok(report) => {
+            tracing::info!(reported_request_id = %request_id, "Problem reported: {}", report.id);
+            Json(serde_json::json!({ "success": true })).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to file problem report: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to file report")
+        }
+    }
+}
+
+fn opt_out_error(error: OptOutError) -> Response {
+    let status = match error {
+        OptOutError::InvalidDomain => StatusCode::BAD_REQUEST,
+        OptOutError::NotRequested => StatusCode::NOT_FOUND,
+        OptOutError::Unverified(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        OptOutError::Internal(ref e) => {
+            tracing::error!("Opt-out failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    json_error(status, error.user_message())
+}
+
+/// Hand out the token a site owner must publish to opt their domain out
+async fn handle_opt_out_request(ctx: AppContext, session: Session, input: OptOutInput) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    match ctx.opt_out_domain.request(&input.domain, Some(user_id)).await {
+        Ok(opt_out) => Json(serde_json::json!({
+            "success": true,
+            "domain": opt_out.domain,
+            "verified": opt_out.is_verified(),
+            "token": opt_out.token,
+            "verification_url": opt_out.verification_url(),
+            "instructions": format!(
+                "Publish a text file at {} containing the token on its own line, then POST the domain to /api/opt-out/verify",
+                opt_out.verification_url()
+            ),
+        }))
+        .into_response(),
+        Err(e) => opt_out_error(e),
+    }
+}
+
+async fn handle_opt_out_verify(ctx: AppContext, input: OptOutInput) -> Response {
+    match ctx.opt_out_domain.verify(&input.domain).await {
+        Ok((opt_out, unlisted)) => Json(serde_json::json!({
+            "success": true,
+            "domain": opt_out.domain,
+            "verified_at": opt_out.verified_at,
+            "roasts_unlisted": unlisted,
+        }))
+        .into_response(),
+        Err(e) => opt_out_error(e),
+    }
+}
+
+fn founder_error(error: FounderError) -> Response {
+    let status = match error {
+        FounderError::InvalidDomain => StatusCode::BAD_REQUEST,
+        FounderError::NotClaimed => StatusCode::NOT_FOUND,
+        FounderError::Unverified(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        FounderError::NotFounder => StatusCode::FORBIDDEN,
+        FounderError::Internal(ref e) => {
+            tracing::error!("Founder analytics failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    json_error(status, error.user_message())
+}
+
+/// Hand out the token a founder must publish to see their startup's stats
+async fn handle_founder_claim(ctx: AppContext, session: Session, input: FounderClaimInput) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    match ctx.track_roast_analytics.claim(user_id, &input.domain).await {
+        Ok(claim) => Json(serde_json::json!({
+            "success": true,
+            "domain": claim.domain,
+            "verified": claim.is_verified(),
+            "token": claim.token,
+            "verification_url": claim.verification_url(),
+            "instructions": format!(
+                "Publish a text file at {} containing the token on its own line, then POST the domain to /api/founder/verify",
+                claim.verification_url()
+            ),
+        }))
+        .into_response(),
+        Err(e) => founder_error(e),
+    }
+}
+
+async fn handle_founder_verify(ctx: AppContext, session: Session, input: FounderClaimInput) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+
+    match ctx.track_roast_analytics.verify(user_id, &input.domain).await {
+        Ok(claim) => Json(serde_json::json!({
+            "success": true,
+            "domain": claim.domain,
+            "verified_at": claim.verified_at,
+        }))
+        .into_response(),
+        Err(e) => founder_error(e),
+    }
+}
+
+/// Views, referrers, shares and vote velocity across a verified founder's roasts
+async fn handle_founder_analytics(
+    ctx: AppContext,
+    session: Session,
+    domain: String,
+    query: FounderAnalyticsQuery,
+) -> Response {
+    let Some(user_id) = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten() else {
+        return json_error(StatusCode::UNAUTHORIZED, "Login required");
+    };
+    let days = query.days.unwrap_or(FounderAnalytics::DEFAULT_DAYS);
+
+    match ctx.track_roast_analytics.report(user_id, &domain, days).await {
+        Ok(analytics) => Json(serde_json::json!({
+            "success": true,
+            "total_views": analytics.total_views(),
+            "total_shares": analytics.total_shares(),
+            "vote_velocity": analytics.vote_velocity(),
+            "analytics": analytics,
+        }))
+        .into_response(),
+        Err(e) => founder_error(e),
+    }
+}
+
+/// Share button beacon; unknown channels are refused so the counters stay clean
+async fn handle_share(ctx: AppContext, roast_id: Uuid, input: ShareInput) -> Response {
+    let Ok(channel) = input.channel.parse::<ShareChannel>() else {
+        return json_error(StatusCode::BAD_REQUEST, "Unknown share channel");
+    };
+    match ctx.roast_repo.find_by_id(roast_id).await {
+        Ok(Some(roast)) if roast.removed_at.is_none() => {}
+        Ok(_) => return json_error(StatusCode::NOT_FOUND, "Roast not found"),
+        Err(e) => {
+            tracing::error!("Failed to load roast {}: {}", roast_id, e);
+            return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to record share");
+        }
+    }
+    match ctx.track_roast_analytics.record_share(roast_id, channel).await {
+        Ok(()) => Json(serde_json::json!({ "success": true })).into_response(),
+        Err(e) => founder_error(e),
+    }
+}
+
+async fn handle_moderation_stats(ctx: AppContext) -> impl IntoResponse {
+    match ctx.moderation_repo.stats().await {
+        Ok(stats) => Json(serde_json::json!({
+            "success": true,
+            "stats": stats,
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to load moderation stats: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load moderation stats")
+        }
+    }
+}
+
+async fn handle_moderation_page(ctx: AppContext) -> impl IntoResponse {
+    match ctx.moderation_repo.stats().await {
+        Ok(stats) => Html(render_moderation_page(&stats)),
+        Err(e) => {
+            tracing::error!("Failed to load moderation stats: {}", e);
+            Html(render_error_page("Gagal memuat data moderasi"))
+        }
+    }
+}
+
+async fn handle_admin_list_reports(ctx: AppContext, session: Session) -> impl IntoResponse {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
 
-    let listener = tokio::net::TcpListener::bind(&addr)
+    match ctx.moderation_repo.list_pending(100).await {
+        Ok(reports) => Json(serde_json::json!({
+            "success": true,
+            "reports": reports,
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list reports: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to list reports")
+        }
+    }
+}
+
+async fn handle_admin_resolve_report(
+    ctx: AppContext,
+    session: Session,
+    report_id: Uuid,
+    input: ResolveReportInput,
+) -> impl IntoResponse {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match ctx.moderation_repo.resolve_report(report_id, input.remove).await {
+        Ok(Some(report)) => {
+            if input.remove {
+                ctx.notify_user.roast_event(report.roast_id, NotificationKind::RoastRemoved);
+            }
+            Json(serde_json::json!({
+                "success": true,
+                "report": report,
+            })).into_response()
+        }
+        Ok(None) => json_error(StatusCode::NOT_FOUND, "Report not found"),
+        Err(e) => {
+            tracing::error!("Failed to resolve report: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to resolve report")
+        }
+    }
+}
+
+async fn handle_admin_list_problem_reports(ctx: AppContext, session: Session) -> impl IntoResponse {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match ctx.problem_report_repo.list_open(100).await {
+        Ok(reports) => Json(serde_json::json!({
+            "success": true,
+            "reports": reports,
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list problem reports: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to list problem reports")
+        }
+    }
+}
+
+async fn handle_admin_resolve_problem_report(
+    ctx: AppContext,
+    session: Session,
+    report_id: Uuid,
+) -> impl IntoResponse {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match ctx.problem_report_repo.resolve(report_id).await {
+        Ok(Some(report)) => Json(serde_json::json!({
+            "success": true,
+            "report": report,
+        })).into_response(),
+        Ok(None) => json_error(StatusCode::NOT_FOUND, "Report not found"),
+        Err(e) => {
+            tracing::error!("Failed to resolve problem report: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to resolve problem report")
+        }
+    }
+}
+
+async fn handle_admin_takedown(
+    ctx: AppContext,
+    session: Session,
+    roast_id: Uuid,
+    input: TakedownInput,
+) -> impl IntoResponse {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+    let Some(reason) = clean_reason(&input.reason) else {
+        return json_error(StatusCode::BAD_REQUEST, "Reason is required");
+    };
+
+    match ctx.moderation_repo.remove_roast(roast_id, RemovalKind::Takedown, &reason).await {
+        Ok(true) => {
+            ctx.notify_user.roast_event(roast_id, NotificationKind::RoastRemoved);
+            Json(serde_json::json!({ "success": true })).into_response()
+        }
+        Ok(false) => json_error(StatusCode::NOT_FOUND, "Roast not found"),
+        Err(e) => {
+            tracing::error!("Failed to take down roast: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to take down roast")
+        }
+    }
+}
+
+/// Per-subsystem status for dashboards. Only a database outage fails the
+/// check; every other subsystem can be degraded or switched off.
+async fn handle_healthz(ctx: AppContext) -> Response {
+    let database = match ctx.db.ping().await {
+        Ok(()) => SubsystemHealth::active("database"),
+        Err(e) => SubsystemHealth::degraded("database", e.to_string()),
+    };
+    let mut subsystems = vec![database];
+    subsystems.extend(ctx.generate_roast.health());
+    subsystems.push(SubsystemHealth::toggle(
+        "stickers",
+        ctx.sticker_renderer.is_enabled(),
+        "STICKER_FONT not loaded",
+    ));
+
+    let report = HealthReport::new(subsystems);
+    let status = if report.is_degraded("database") {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (status, Json(report)).into_response()
+}
+
+/// Ready for traffic once the startup warmup has finished and the database
+/// answers; 503 until then, so a load balancer holds the first roasts back
+async fn handle_readyz(ctx: AppContext) -> Response {
+    let database = ctx.db.ping().await.map_err(|e| e.to_string());
+    let warmup = ctx.warmup.report();
+    let ready = database.is_ok() && warmup.is_some();
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(serde_json::json!({
+            "ready": ready,
+            "database": database.err().unwrap_or_else(|| "ok".to_string()),
+            "warmup": warmup,
+        })),
+    )
+        .into_response()
+}
+
+async fn handle_admin_list_invites(ctx: AppContext, session: Session) -> Response {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match ctx.invite_repo.list().await {
+        Ok(codes) => Json(serde_json::json!({
+            "success": true,
+            "invite_only": ctx.invite_only,
+            "codes": codes.into_iter().map(InviteCode::from).collect::<Vec<_>>(),
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list invite codes: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to list invite codes")
+        }
+    }
+}
+
+async fn handle_admin_create_invites(
+    ctx: AppContext,
+    session: Session,
+    input: InviteInput,
+) -> Response {
+    let admin = match require_admin(&ctx, &session).await {
+        Ok(admin) => admin,
+        Err((status, error)) => return json_error(status, error),
+    };
+    if input.count == 0 || input.count > InviteCode::MAX_BATCH {
+        return json_error(StatusCode::BAD_REQUEST, "count must be between 1 and 100");
+    }
+    if input.max_uses < 1 {
+        return json_error(StatusCode::BAD_REQUEST, "max_uses must be at least 1");
+    }
+    let note = input.note.as_deref().map(str::trim).filter(|n| !n.is_empty());
+
+    match ctx
+        .invite_repo
+        .create_batch(input.count, input.max_uses, note, admin.id)
         .await
-        .expect("Failed to bind address");
+    {
+        Ok(codes) => {
+            tracing::info!("{} invite codes created by {}", codes.len(), admin.email);
+            (StatusCode::CREATED, Json(serde_json::json!({
+                "success": true,
+                "codes": codes.into_iter().map(InviteCode::from).collect::<Vec<_>>(),
+            }))).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to create invite codes: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to create invite codes")
+        }
+    }
+}
+
+fn ban_error(error: BanError) -> Response {
+    match error {
+        BanError::InvalidRange => json_error(
+            StatusCode::BAD_REQUEST,
+            "range must be an IP address or CIDR range, e.g. 203.0.113.0/24",
+        ),
+        BanError::NotFound => json_error(StatusCode::NOT_FOUND, "Ban not found"),
+        BanError::Internal(e) => {
+            tracing::error!("IP ban update failed: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to update IP bans")
+        }
+    }
+}
+
+async fn handle_admin_list_bans(ctx: AppContext, session: Session) -> Response {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match ctx.ban_ip.list().await {
+        Ok(bans) => {
+            let now = chrono::Utc::now();
+            let bans: Vec<_> = bans
+                .into_iter()
+                .map(|ban| {
+                    let active = ban.is_active_at(now);
+                    serde_json::json!({ "ban": ban, "active": active })
+                })
+                .collect();
+            Json(serde_json::json!({ "success": true, "bans": bans })).into_response()
+        }
+        Err(e) => ban_error(e),
+    }
+}
+
+async fn handle_admin_create_ban(ctx: AppContext, session: Session, input: BanInput) -> Response {
+    let admin = match require_admin(&ctx, &session).await {
+        Ok(admin) => admin,
+        Err((status, error)) => return json_error(status, error),
+    };
 
-    axum::serve(listener, app.into_make_service())
+    match ctx
+        .ban_ip
+        .ban(&input.range, input.reason.as_deref(), input.expires_at, admin.id)
         .await
-        .expect("Server error");
+    {
+        Ok(ban) => {
+            tracing::info!("IP ban {} on {} created by {}", ban.id, ban.range, admin.email);
+            (StatusCode::CREATED, Json(serde_json::json!({
+                "success": true,
+                "ban": ban,
+            }))).into_response()
+        }
+        Err(e) => ban_error(e),
+    }
+}
+
+async fn handle_admin_expire_ban(
+    ctx: AppContext,
+    session: Session,
+    id: Uuid,
+    input: BanExpiryInput,
+) -> Response {
+    let admin = match require_admin(&ctx, &session).await {
+        Ok(admin) => admin,
+        Err((status, error)) => return json_error(status, error),
+    };
+
+    match ctx.ban_ip.set_expiry(id, input.expires_at).await {
+        Ok(ban) => {
+            tracing::info!("IP ban {} now expires {:?}, set by {}", id, ban.expires_at, admin.email);
+            Json(serde_json::json!({ "success": true, "ban": ban })).into_response()
+        }
+        Err(e) => ban_error(e),
+    }
+}
+
+async fn handle_admin_delete_ban(ctx: AppContext, session: Session, id: Uuid) -> Response {
+    let admin = match require_admin(&ctx, &session).await {
+        Ok(admin) => admin,
+        Err((status, error)) => return json_error(status, error),
+    };
+
+    match ctx.ban_ip.unban(id).await {
+        Ok(()) => {
+            tracing::info!("IP ban {} removed by {}", id, admin.email);
+            Json(serde_json::json!({ "success": true })).into_response()
+        }
+        Err(e) => ban_error(e),
+    }
+}
+
+/// Start a read-only "view as user" session for `target_id`
+async fn handle_admin_impersonate(ctx: AppContext, session: Session, target_id: Uuid) -> Response {
+    let admin = match require_admin(&ctx, &session).await {
+        Ok(admin) => admin,
+        Err((status, error)) => return json_error(status, error),
+    };
+    if admin.id == target_id {
+        return json_error(StatusCode::BAD_REQUEST, "Cannot impersonate yourself");
+    }
+    let target = match ctx.user_repo.find_by_id(target_id).await {
+        Ok(Some(target)) => target,
+        Ok(None) => return json_error(StatusCode::NOT_FOUND, "User not found"),
+        Err(e) => {
+            tracing::error!("Failed to load user {}: {}", target_id, e);
+            return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load user");
+        }
+    };
+
+    // Audit first: no unlogged impersonation
+    if let Err(e) = ctx
+        .impersonation_repo
+        .log(admin.id, target.id, ImpersonationEvent::START, None)
+        .await
+    {
+        tracing::error!("Failed to audit impersonation: {}", e);
+        return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to start impersonation");
+    }
+    if session.insert(SESSION_IMPERSONATOR_ID, admin.id).await.is_err()
+        || session.insert(SESSION_USER_ID, target.id).await.is_err()
+    {
+        return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to start impersonation");
+    }
+
+    tracing::warn!("{} is viewing the site as {} ({})", admin.email, target.email, target.id);
+    Json(serde_json::json!({
+        "success": true,
+        "impersonating": { "id": target.id, "name": target.name },
+    })).into_response()
+}
+
+/// Leave impersonation and restore the admin's own session
+async fn handle_admin_stop_impersonation(ctx: AppContext, session: Session) -> Response {
+    let Some(admin_id) = session.get::<Uuid>(SESSION_IMPERSONATOR_ID).await.ok().flatten() else {
+        return Redirect::to("/").into_response();
+    };
+    let target_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+
+    if session.insert(SESSION_USER_ID, admin_id).await.is_err() {
+        return Html(render_error_page("Gagal keluar dari mode lihat sebagai user")).into_response();
+    }
+    let _ = session.remove::<Uuid>(SESSION_IMPERSONATOR_ID).await;
+
+    if let Some(target_id) = target_id {
+        if let Err(e) = ctx
+            .impersonation_repo
+            .log(admin_id, target_id, ImpersonationEvent::STOP, None)
+            .await
+        {
+            tracing::error!("Failed to audit end of impersonation: {}", e);
+        }
+    }
+    Redirect::to("/").into_response()
+}
+
+async fn handle_admin_list_impersonations(ctx: AppContext, session: Session) -> Response {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match ctx.impersonation_repo.list_recent(200).await {
+        Ok(events) => Json(serde_json::json!({
+            "success": true,
+            "events": events.into_iter().map(ImpersonationEvent::from).collect::<Vec<_>>(),
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list impersonation log: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to list impersonation log")
+        }
+    }
+}
+
+/// Live scrape health over the configured period plus recent stored reports
+async fn handle_admin_scrape_health(ctx: AppContext, session: Session) -> Response {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    let report = &ctx.report_scrape_health;
+    match (report.preview(report.period_hours()).await, report.history(24).await) {
+        (Ok(current), Ok(history)) => Json(serde_json::json!({
+            "success": true,
+            "current": current,
+            "history": history,
+        })).into_response(),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("Failed to build scrape health report: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to build scrape health report")
+        }
+    }
+}
+
+/// Cached local models with disk usage and the configured cap
+#[cfg(feature = "local-llm")]
+fn model_cache_report() -> Result<serde_json::Value, String> {
+    use roasting_app::infrastructure::local_llm::{loaded_models, LocalLlmConfig, ModelCache};
+
+    let cache = ModelCache::from_env().map_err(|e| e.to_string())?;
+    let config = LocalLlmConfig::from_env().map_err(|e| e.to_string())?;
+    let models = cache.list().map_err(|e| e.to_string())?;
+    let loaded: serde_json::Map<String, serde_json::Value> = loaded_models()
+        .into_iter()
+        .map(|(name, llm)| (name, llm.model_id().into()))
+        .collect();
+    Ok(serde_json::json!({
+        "root": cache.root(),
+        "loaded_models": loaded,
+        "total_bytes": models.iter().map(|m| m.bytes).sum::<u64>(),
+        "cap_bytes": (config.cache_max_mb > 0).then(|| config.cache_max_mb * 1024 * 1024),
+        "models": models,
+    }))
+}
+
+#[cfg(not(feature = "local-llm"))]
+fn model_cache_report() -> Result<serde_json::Value, String> {
+    Err("local LLM support is not compiled in".to_string())
+}
+
+/// Delete a cached model outside the configured pool and not loaded, returning bytes freed
+#[cfg(feature = "local-llm")]
+fn evict_cached_model(model_id: &str) -> Result<u64, String> {
+    use roasting_app::infrastructure::local_llm::{loaded_models, ModelCache, ModelPoolConfig};
+
+    let pool = ModelPoolConfig::from_env().map_err(|e| e.to_string())?;
+    if pool.model_ids().contains(&model_id) {
+        return Err(format!("{} is a configured local model", model_id));
+    }
+    if loaded_models().iter().any(|(_, llm)| llm.model_id() == model_id) {
+        return Err(format!("{} is a model currently loaded", model_id));
+    }
+    ModelCache::from_env()
+        .and_then(|cache| cache.evict(model_id))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "local-llm"))]
+fn evict_cached_model(_model_id: &str) -> Result<u64, String> {
+    Err("local LLM support is not compiled in".to_string())
+}
+
+fn run_models_command(args: &[String]) -> i32 {
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [] | ["list"] => match model_cache_report() {
+            Ok(report) => {
+                println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+                0
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                1
+            }
+        },
+        ["evict", model_id] => match evict_cached_model(model_id) {
+            Ok(bytes) => {
+                println!("Evicted {} ({} MB freed)", model_id, bytes / (1024 * 1024));
+                0
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                1
+            }
+        },
+        _ => {
+            eprintln!("usage: roasting-api models [list | evict <owner/model>]");
+            2
+        }
+    }
+}
+
+#[cfg(feature = "local-llm")]
+async fn run_bench_command(args: &[String]) -> i32 {
+    use roasting_app::infrastructure::local_llm::{run_bench, BenchOptions};
+
+    let bench = match BenchOptions::parse(args) {
+        Ok(bench) => bench,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("usage: roasting-api bench [--runs N] [--lang id|en] [--length singkat|standar|panjang]");
+            return 2;
+        }
+    };
+    match run_bench(bench.runs, bench.options).await {
+        Ok(report) => {
+            println!("{}", report.summary());
+            0
+        }
+        Err(e) => {
+            eprintln!("Benchmark failed: {}", e);
+            1
+        }
+    }
+}
+
+#[cfg(not(feature = "local-llm"))]
+async fn run_bench_command(_args: &[String]) -> i32 {
+    eprintln!("local LLM support is not compiled in; rebuild with --features local-llm");
+    1
+}
+
+async fn handle_admin_list_models(ctx: AppContext, session: Session) -> Response {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match model_cache_report() {
+        Ok(report) => Json(serde_json::json!({ "success": true, "cache": report })).into_response(),
+        Err(e) => json_error(StatusCode::NOT_FOUND, &e),
+    }
+}
+
+/// Load another local model and switch to it once in-flight generations drain.
+/// Blocks until the new model is downloaded and loaded.
+#[cfg(feature = "local-llm")]
+async fn handle_admin_swap_model(ctx: AppContext, session: Session, input: SwapModelInput) -> Response {
+    let admin = match require_admin(&ctx, &session).await {
+        Ok(admin) => admin,
+        Err((status, error)) => return json_error(status, error),
+    };
+
+    let loaded = roasting_app::infrastructure::local_llm::loaded_models();
+    tracing::info!("Local model swap to {} requested by {}", input.model_id, admin.email);
+    match ctx
+        .generate_roast
+        .swap_local_model(input.name.as_deref(), &input.model_id)
+        .await
+    {
+        Ok((name, model_id)) => Json(serde_json::json!({
+            "success": true,
+            "name": name,
+            "model_id": model_id,
+            "previous": loaded
+                .iter()
+                .find(|(loaded_name, _)| *loaded_name == name)
+                .map(|(_, llm)| llm.model_id()),
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Local model swap to {} failed: {}", input.model_id, e);
+            json_error(StatusCode::BAD_REQUEST, &e.to_string())
+        }
+    }
+}
+
+#[cfg(not(feature = "local-llm"))]
+async fn handle_admin_swap_model(ctx: AppContext, session: Session, input: SwapModelInput) -> Response {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+    tracing::warn!(
+        "Local model swap of {:?} to {} requested without local-llm support",
+        input.name,
+        input.model_id
+    );
+    json_error(StatusCode::NOT_FOUND, "local LLM support is not compiled in")
+}
+
+async fn handle_admin_evict_model(ctx: AppContext, session: Session, model_id: String) -> Response {
+    let admin = match require_admin(&ctx, &session).await {
+        Ok(admin) => admin,
+        Err((status, error)) => return json_error(status, error),
+    };
+
+    match evict_cached_model(&model_id) {
+        Ok(bytes) => {
+            tracing::info!("Cached model {} evicted by {}", model_id, admin.email);
+            Json(serde_json::json!({ "success": true, "freed_bytes": bytes })).into_response()
+        }
+        Err(e) => json_error(StatusCode::BAD_REQUEST, &e),
+    }
+}
+
+/// Spice poll answers per prompt variant, to compare PROMPT_VARIANT runs
+async fn handle_admin_spice(ctx: AppContext, session: Session) -> Response {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match ctx.spice_vote_repo.by_variant().await {
+        Ok(variants) => Json(serde_json::json!({
+            "success": true,
+            "current_variant": ctx.prompt_templates.variant(),
+            "variants": variants
+                .iter()
+                .map(|v| serde_json::json!({
+                    "variant": v.variant,
+                    "roasts": v.roasts,
+                    "spice": spice_json(&v.distribution),
+                }))
+                .collect::<Vec<_>>(),
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to load spice poll by variant: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load spice poll")
+        }
+    }
+}
+
+async fn handle_admin_costs(ctx: AppContext, session: Session) -> impl IntoResponse {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    Json(serde_json::json!({
+        "success": true,
+        "costs": ctx.cost_tracker.stats(),
+    })).into_response()
+}
+
+/// Every completion a roast and its regenerations cost
+async fn handle_admin_roast_costs(ctx: AppContext, session: Session, id: Uuid) -> Response {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match ctx.roast_repo.find_costs(id).await {
+        Ok(costs) => {
+            let total_micros: u64 = costs.iter().map(|c| c.cost_micros).sum();
+            Json(serde_json::json!({
+                "success": true,
+                "costs": costs,
+                "total_usd": total_micros as f64 / 1_000_000.0,
+            }))
+            .into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to load costs of roast {}: {}", id, e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load roast costs")
+        }
+    }
+}
+
+/// Preview (`dry_run`) or apply the retention policies, reporting rows per table
+async fn handle_admin_retention(ctx: AppContext, session: Session, dry_run: bool) -> Response {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match ctx.apply_retention.execute(dry_run).await {
+        Ok(outcomes) => Json(serde_json::json!({
+            "success": true,
+            "outcomes": outcomes,
+        }))
+        .into_response(),
+        Err(e) => {
+            tracing::error!("Retention run failed: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Retention run failed")
+        }
+    }
+}
+
+async fn handle_admin_metrics(ctx: AppContext, session: Session) -> impl IntoResponse {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
+    }
+
+    match ctx.request_metrics.route_stats().await {
+        Ok(routes) => Json(serde_json::json!({
+            "success": true,
+            "window_hours": 24,
+            "sample_rate": ctx.request_metrics.sample_rate(),
+            "load": ctx.load_shedder.stats(),
+            "routes": routes,
+        })).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to load route metrics: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load route metrics")
+        }
+    }
 }
 
-async fn handle_roast_form(ctx: AppContext, session: Session, form: RoastForm) -> impl IntoResponse {
-    use roasting_app::infrastructure::security::InputSanitizer;
-    use std::net::{IpAddr, Ipv4Addr};
-
-    let client_ip = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+async fn handle_admin_metrics_page(ctx: AppContext, session: Session) -> impl IntoResponse {
+    if let Err((_, error)) = require_admin(&ctx, &session).await {
+        return Html(render_error_page(error));
+    }
 
-    if let Err(e) = ctx.rate_limiter.check_rate_limit(client_ip) {
-        return Html(render_error_page(&e.message_id()));
+    match ctx.request_metrics.route_stats().await {
+        Ok(routes) => Html(render_metrics_page(
+            &routes,
+            ctx.request_metrics.sample_rate(),
+            &ctx.load_shedder.stats(),
+        )),
+        Err(e) => {
+            tracing::error!("Failed to load route metrics: {}", e);
+            Html(render_error_page("Gagal memuat metrics"))
+        }
     }
+}
 
-    if let Err(e) = ctx.cost_tracker.check_and_increment() {
-        return Html(render_error_page(&e.message_id()));
+async fn handle_admin_list_prompts(ctx: AppContext, session: Session) -> impl IntoResponse {
+    if let Err((status, error)) = require_admin(&ctx, &session).await {
+        return json_error(status, error);
     }
 
-    let validated_url = match InputSanitizer::validate_url(&form.url) {
-        Ok(url) => url,
-        Err(e) => return Html(render_error_page(&e.user_message())),
+    let templates = &ctx.prompt_templates;
+    Json(serde_json::json!({
+        "success": true,
+        "dir": templates.dir().display().to_string(),
+        "variant": templates.variant(),
+        "templates": templates.names(),
+    })).into_response()
+}
+
+async fn handle_admin_reload_prompts(ctx: AppContext, session: Session) -> impl IntoResponse {
+    let admin = match require_admin(&ctx, &session).await {
+        Ok(admin) => admin,
+        Err((status, error)) => return json_error(status, error),
     };
 
-    match ctx.generate_roast.execute(validated_url).await {
-        Ok(roast) => {
-            // Get current user if logged in
-            let user_id: Option<Uuid> = session.get("user_id").await.ok().flatten();
+    match ctx.prompt_templates.reload() {
+        Ok(names) => {
+            tracing::info!("Prompt templates reloaded by {}", admin.email);
+            Json(serde_json::json!({
+                "success": true,
+                "templates": names,
+            })).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to reload prompt templates: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to reload prompt templates")
+        }
+    }
+}
 
-            // Create PersistedRoast and save to database
-            let persisted = PersistedRoast::new(
-                roast.startup_name.clone(),
-                form.url.clone(),
-                roast.roast_text.clone(),
-                user_id,
-            );
+/// Logos per /wall page
+const WALL_PAGE_SIZE: u64 = 60;
 
-            // Persist the roast to database
-            match ctx.roast_repo.create(&persisted).await {
-                Ok(saved_roast) => {
-                    Html(render_result_page_with_id(
-                        &roast.startup_name,
-                        &roast.roast_text,
-                        &form.url,
-                        saved_roast.id,
-                    ))
-                }
-                Err(e) => {
-                    tracing::error!("Failed to persist roast: {}", e);
-                    // Still show the roast even if persistence fails
-                    Html(render_result_page(&roast.startup_name, &roast.roast_text, &form.url))
-                }
-            }
+async fn handle_wall_page(ctx: AppContext, query: WallQuery) -> impl IntoResponse {
+    let total = match ctx.roast_repo.count_wall_startups().await {
+        Ok(total) => total,
+        Err(e) => {
+            tracing::error!("Failed to count wall startups: {}", e);
+            return Html(render_error_page("Gagal memuat wall"));
+        }
+    };
+    let pages = total.div_ceil(WALL_PAGE_SIZE).max(1);
+    let page = query.page.unwrap_or(1).clamp(1, pages);
+    match ctx
+        .roast_repo
+        .wall(WALL_PAGE_SIZE, (page - 1) * WALL_PAGE_SIZE)
+        .await
+    {
+        Ok(tiles) => Html(render_wall_page(&tiles, page, pages, total)),
+        Err(e) => {
+            tracing::error!("Failed to load wall: {}", e);
+            Html(render_error_page("Gagal memuat wall"))
         }
-        Err(e) => Html(render_error_page(&e.user_message())),
     }
 }
 
-// Session keys
-const SESSION_USER_ID: &str = "user_id";
-const SESSION_CSRF_TOKEN: &str = "csrf_token";
-const SESSION_PKCE_VERIFIER: &str = "pkce_verifier";
+async fn handle_changelog_page(ctx: AppContext) -> impl IntoResponse {
+    match ctx.announcement_repo.list_published(50).await {
+        Ok(items) => {
+            let items: Vec<Announcement> = items.into_iter().map(Announcement::from).collect();
+            Html(render_changelog_page(&items))
+        }
+        Err(e) => {
+            tracing::error!("Failed to get announcements: {}", e);
+            Html(render_error_page("Gagal memuat changelog"))
+        }
+    }
+}
 
-async fn handle_auth_login(ctx: AppContext, session: Session) -> impl IntoResponse {
-    let (auth_url, csrf_token, pkce_verifier) = ctx.google_oauth.get_auth_url();
+/// Roasts listed in /llms.txt
+const LLMS_TXT_RECENT_ROASTS: u64 = 30;
 
-    // Store CSRF token and PKCE verifier in session
-    if let Err(e) = session.insert(SESSION_CSRF_TOKEN, csrf_token.secret().clone()).await {
-        tracing::error!("Failed to store CSRF token: {}", e);
-        return Redirect::to("/?error=session_error");
+/// Short summary, scores, and canonical link of a roast for bots and AI agents
+async fn handle_roast_summary(ctx: AppContext, roast_id: Uuid) -> Response {
+    match ctx.roast_repo.find_summary(roast_id).await {
+        Ok(Some(summary)) => (
+            [(header::CACHE_CONTROL, "public, max-age=300")],
+            Json(serde_json::json!({
+                "success": true,
+                "url": format!("{}/r/{}", ctx.public_base_url, summary.id),
+                "roast": summary,
+            })),
+        )
+            .into_response(),
+        Ok(None) => json_error(StatusCode::NOT_FOUND, "Roast not found"),
+        Err(e) => {
+            tracing::error!("Failed to get roast summary: {}", e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch roast")
+        }
     }
-    if let Err(e) = session.insert(SESSION_PKCE_VERIFIER, pkce_verifier.secret().clone()).await {
-        tracing::error!("Failed to store PKCE verifier: {}", e);
-        return Redirect::to("/?error=session_error");
+}
+
+/// WhatsApp-ready 512x512 WebP sticker of the roast's punchline
+async fn handle_roast_sticker(ctx: AppContext, roast_id: Uuid) -> Response {
+    if !ctx.sticker_renderer.is_enabled() {
+        return json_error(StatusCode::SERVICE_UNAVAILABLE, "Stickers are not available");
     }
+    let summary = match ctx.roast_repo.find_summary(roast_id).await {
+        Ok(Some(summary)) => summary,
+        Ok(None) => return json_error(StatusCode::NOT_FOUND, "Roast not found"),
+        Err(e) => {
+            tracing::error!("Failed to get roast for sticker: {}", e);
+            return json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch roast");
+        }
+    };
 
-    Redirect::to(&auth_url)
+    // Rasterizing is CPU-bound, keep it off the async workers
+    let renderer = ctx.sticker_renderer.clone();
+    let rendered = tokio::task::spawn_blocking(move || {
+        renderer.render(&summary.startup_name, &summary.summary)
+    })
+    .await;
+    match rendered {
+        Ok(Ok(bytes)) => (
+            [
+                (header::CONTENT_TYPE, "image/webp"),
+                (header::CACHE_CONTROL, "public, max-age=86400"),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Ok(Err(e)) => {
+            tracing::error!("Failed to render sticker for {}: {}", roast_id, e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to render sticker")
+        }
+        Err(e) => {
+            tracing::error!("Sticker task panicked for {}: {}", roast_id, e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to render sticker")
+        }
+    }
 }
 
-async fn handle_auth_callback(
-    ctx: AppContext,
-    session: Session,
-    query: AuthCallbackQuery,
-) -> impl IntoResponse {
-    // Verify CSRF token
-    let stored_csrf: Option<String> = session.get(SESSION_CSRF_TOKEN).await.ok().flatten();
-    if stored_csrf.is_none() {
-        tracing::warn!("CSRF token not found in session - session may have expired or server restarted");
-        // Redirect to login again instead of showing error
-        return Redirect::to("/auth/login");
+/// Screenshot of the roasted site taken while scraping, if there was one
+async fn handle_roast_screenshot(ctx: AppContext, session: Session, roast_id: Uuid) -> Response {
+    let user_id = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten();
+    match ctx.roast_repo.find_screenshot(roast_id, user_id).await {
+        Ok(Some(image)) => (
+            [
+                (header::CONTENT_TYPE, "image/webp"),
+                (header::CACHE_CONTROL, "public, max-age=86400"),
+            ],
+            image,
+        )
+            .into_response(),
+        Ok(None) => json_error(StatusCode::NOT_FOUND, "Screenshot not found"),
+        Err(e) => {
+            tracing::error!("Failed to get screenshot of {}: {}", roast_id, e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch screenshot")
+        }
     }
-    if stored_csrf.as_ref() != Some(&query.state) {
-        tracing::warn!("CSRF token mismatch: stored={:?}, received={}", stored_csrf, &query.state);
-        return Redirect::to("/auth/login");
+}
+
+/// The scraped data and page HTML a roast was generated from, for settling
+/// "the roast got it wrong" arguments
+async fn handle_roast_source(ctx: AppContext, session: Session, roast_id: Uuid) -> Response {
+    let user_id = session.get::<Uuid>(SESSION_USER_ID).await.ok().flatten();
+    match ctx.roast_repo.find_source(roast_id, user_id).await {
+        Ok(Some((source, captured_at))) => Json(serde_json::json!({
+            "roast_id": roast_id,
+            "captured_at": captured_at,
+            "startup_info": source.startup_info,
+            "html": source.html,
+        }))
+        .into_response(),
+        Ok(None) => json_error(StatusCode::NOT_FOUND, "Source not found"),
+        Err(e) => {
+            tracing::error!("Failed to get source of {}: {}", roast_id, e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch source")
+        }
+    }
+}
+
+/// Full-page capture in a scrollable frame; the frame goes away when the
+/// roast has no screenshot
+/// Collapsed "Fakta (katanya)" panel with the startup's own claims
+fn render_fact_sheet(fact_sheet: Option<&FactSheet>, language: Language) -> String {
+    let Some(fact_sheet) = fact_sheet.filter(|f| !f.is_empty()) else {
+        return String::new();
+    };
+    let rows: String = fact_sheet
+        .entries(language)
+        .iter()
+        .map(|(label, value)| format!("<dt>{}</dt><dd>{}</dd>", label, escape_html(value)))
+        .collect();
+    format!(
+        r#"<details class="fact-sheet"><summary>📋 {}</summary><dl>{}</dl></details>"#,
+        labels(language).fact_sheet_title,
+        rows
+    )
+}
+
+fn render_screenshot(roast_id: Uuid, language: Language) -> String {
+    format!(
+        r#"<figure class="roast__screenshot"><figcaption>{caption}</figcaption><div class="roast__screenshot-frame"><img src="/api/roast/{roast_id}/screenshot.webp" alt="{caption}" loading="lazy" decoding="async" onerror="this.closest('figure').remove()"></div></figure>"#,
+        caption = labels(language).screenshot_caption,
+    )
+}
+
+/// A startup's stored logo. Without logo storage this falls back to the
+/// site's own favicon, as the wall did before logos were stored.
+async fn handle_logo(ctx: AppContext, domain: String) -> Response {
+    let Some(domain) = DomainOptOut::normalize_domain(&domain) else {
+        return json_error(StatusCode::NOT_FOUND, "Logo not found");
+    };
+    let Some(store) = &ctx.logo_store else {
+        return Redirect::temporary(&format!("https://{}/favicon.ico", domain)).into_response();
+    };
+    match store.get(&domain).await {
+        Ok(Some(bytes)) => match sniff_image_type(&bytes) {
+            Some(content_type) => (
+                [
+                    (header::CONTENT_TYPE, content_type),
+                    (header::CACHE_CONTROL, "public, max-age=86400"),
+                    (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+                ],
+                bytes,
+            )
+                .into_response(),
+            None => json_error(StatusCode::NOT_FOUND, "Logo not found"),
+        },
+        Ok(None) => json_error(StatusCode::NOT_FOUND, "Logo not found"),
+        Err(e) => {
+            tracing::error!("Failed to read logo of {}: {}", domain, e);
+            json_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to read logo")
+        }
     }
+}
 
-    // Get PKCE verifier
-    let pkce_secret: Option<String> = session.get(SESSION_PKCE_VERIFIER).await.ok().flatten();
-    let pkce_verifier = match pkce_secret {
-        Some(secret) => oauth2::PkceCodeVerifier::new(secret),
-        None => {
-            tracing::warn!("PKCE verifier not found in session");
-            return Redirect::to("/?error=session_error");
+/// The startup's logo from `/logos/{domain}`; removed in the browser while
+/// none is stored yet, leaving the text-only layout
+fn render_logo(startup_url: &str, class: &str, size: u32) -> String {
+    DomainOptOut::normalize_domain(startup_url)
+        .map(|domain| {
+            format!(
+                r#"<img class="{class}" src="/logos/{domain}" alt="" width="{size}" height="{size}" loading="lazy" decoding="async" onerror="this.remove()">"#
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// llms.txt index: what the site is, where the machine-readable endpoints
+/// are, and the latest roasts as one-line summaries
+async fn handle_llms_txt(ctx: AppContext) -> Response {
+    let recent: Vec<RoastSummary> = match ctx.roast_repo.find_recent(LLMS_TXT_RECENT_ROASTS).await {
+        Ok(roasts) => roasts.into_iter().map(RoastSummary::from).collect(),
+        Err(e) => {
+            tracing::warn!("Failed to list roasts for llms.txt: {}", e);
+            Vec::new()
         }
     };
+    (
+        [
+            (header::CONTENT_TYPE, "text/plain; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=600"),
+        ],
+        render_llms_txt(&ctx.public_base_url, &recent),
+    )
+        .into_response()
+}
 
-    // Exchange code for user info
-    let user_info = match ctx.google_oauth.exchange_code(&query.code, pkce_verifier).await {
-        Ok(info) => info,
+fn render_llms_txt(base_url: &str, recent: &[RoastSummary]) -> String {
+    let mut out = format!(
+        "# Roasting Startup Indonesia\n\n\
+> Satirical AI roasts of startup landing pages, mostly in Indonesian. Roasts are comedy, not factual reviews.\n\n\
+## API\n\n\
+- [Roast summary]({base}/api/roast/{{id}}/summary): JSON with a one-line summary, 1-10 scores, and the canonical link\n\
+- [Leaderboard]({base}/api/leaderboard): most-voted roasts as JSON\n\n\
+## Recent roasts\n\n",
+        base = base_url
+    );
+    for roast in recent {
+        out.push_str(&format!(
+            "- [{}]({}/r/{}): {}\n",
+            roast.startup_name.replace(['[', ']'], ""),
+            base_url,
+            roast.id,
+            roast.summary.replace('\n', " ")
+        ));
+    }
+    out
+}
+
+/// Battle suggestions are a nice-to-have; failures only get logged
+async fn suggest_battle(ctx: &AppContext, roast_id: Uuid) -> Option<BattleSuggestion> {
+    match ctx.suggest_battle_opponent.execute(roast_id).await {
+        Ok(suggestion) => suggestion,
         Err(e) => {
-            tracing::error!("OAuth exchange failed: {}", e);
-            return Redirect::to("/?error=oauth_failed");
+            tracing::warn!("Battle suggestion failed for {}: {}", roast_id, e);
+            None
         }
+    }
+}
+
+async fn handle_battle_suggestion(ctx: AppContext, roast_id: Uuid) -> impl IntoResponse {
+    match ctx.suggest_battle_opponent.execute(roast_id).await {
+        Ok(suggestion) => Json(serde_json::json!({
+            "success": true,
+            "suggestion": suggestion,
+        })).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Roast body as HTML, one block per section when the roast is structured
+fn render_roast_content(roast_text: &str, sections: Option<&RoastSections>, language: Language) -> String {
+    match sections {
+        Some(sections) => sections
+            .entries(language)
+            .iter()
+            .map(|(title, body)| {
+                format!(
+                    r#"<section class="roast__section"><h3 class="roast__section-title">{}</h3>{}</section>"#,
+                    title,
+                    simple_markdown_to_html(body)
+                )
+            })
+            .collect(),
+        None => simple_markdown_to_html(roast_text),
+    }
+}
+
+/// Badge for startups that look like thin ChatGPT wrappers; empty below the threshold
+fn render_ai_wrapper_badge(confidence: Option<u8>, language: Language) -> String {
+    match confidence {
+        Some(c) if c >= AiWrapperScore::LIKELY => format!(
+            r#"<span class="ai-badge">🤖 {label} · {c}%</span>"#,
+            label = labels(language).ai_wrapper_badge,
+            c = c,
+        ),
+        _ => String::new(),
+    }
+}
+
+/// The startup's own profiles under the roast; empty when it links none
+fn render_social_links(links: &[SocialLink], language: Language) -> String {
+    if links.is_empty() {
+        return String::new();
+    }
+    let items = links
+        .iter()
+        .map(|link| {
+            format!(
+                r#"<a href="{url}" target="_blank" rel="nofollow noopener ugc" title="{platform}">{icon} {handle}</a>"#,
+                url = escape_html(&link.url),
+                platform = link.platform.label(),
+                icon = link.platform.icon(),
+                handle = escape_html(&link.handle),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" · ");
+    format!(
+        r#"<p class="social-links"><span class="social-links__title">{}:</span> {}</p>"#,
+        labels(language).social_links_title,
+        items
+    )
+}
+
+/// Score bars shown under the roast
+/// Earlier "roast lebih dalam" answers plus the form to ask one
+fn render_follow_ups(roast_id: Uuid, follow_ups: &[RoastFollowUp], language: Language) -> String {
+    let labels = labels(language);
+    let answered: String = follow_ups
+        .iter()
+        .map(|f| {
+            format!(
+                r#"<div class="follow-up__item"><p class="follow-up__question">“{}”</p>{}</div>"#,
+                escape_html(&f.question),
+                simple_markdown_to_html(&escape_html(&f.answer))
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<section class="follow-up">
+    <h3 class="roast__section-title">{title}</h3>
+    {answered}
+    <form class="follow-up__form" onsubmit="askFollowUp(event)">
+        <input type="text" name="question" class="follow-up__input" maxlength="{max_chars}" placeholder="{placeholder}" required>
+        <button type="submit" class="roast__button--secondary">{cta}</button>
+    </form>
+    <script>
+        function askFollowUp(event) {{
+            event.preventDefault();
+            const form = event.target;
+            const button = form.querySelector('button');
+            button.disabled = true;
+            const body = new URLSearchParams({{ roast_id: '{roast_id}', question: form.question.value }});
+            fetch('/api/roast_follow_up', {{ method: 'POST', body }})
+                .then(r => r.ok ? window.location.reload() : r.text().then(text => {{
+                    alert(text.replace(/^.*?:\s*/, ''));
+                    button.disabled = false;
+                }}));
+        }}
+    </script>
+</section>"#,
+        title = labels.follow_up_title,
+        answered = answered,
+        max_chars = RoastFollowUp::MAX_QUESTION_CHARS,
+        placeholder = labels.follow_up_placeholder,
+        cta = labels.follow_up_cta,
+        roast_id = roast_id,
+    )
+}
+
+/// "Terlalu halus / pas / kelewatan" poll with the current answer distribution
+fn render_spice_poll(
+    roast_id: Uuid,
+    spice: &SpiceDistribution,
+    mine: Option<SpiceLevel>,
+    language: Language,
+) -> String {
+    let labels = labels(language);
+    let options: String = SpiceLevel::all()
+        .iter()
+        .map(|level| {
+            format!(
+                r#"<button type="button" class="spice__option{chosen}" data-level="{level}" onclick="voteSpice('{level}')">{name}</button>"#,
+                chosen = if mine == Some(*level) { " spice__option--chosen" } else { "" },
+                level = level.as_str(),
+                name = level.display_name(language),
+            )
+        })
+        .collect();
+    let bar: String = SpiceLevel::all()
+        .iter()
+        .map(|level| {
+            format!(
+                r#"<span class="spice__segment spice__segment--{level}" id="spice-bar-{level}" style="width:{percent}%" title="{name}"></span>"#,
+                level = level.as_str(),
+                percent = spice.percent(*level),
+                name = level.display_name(language),
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<section class="spice">
+    <h3 class="roast__section-title">🌶️ {title}</h3>
+    <div class="spice__options">{options}</div>
+    <div class="spice__bar">{bar}</div>
+    <p class="spice__total" id="spice-total" data-template="{answers_template}">{answers}</p>
+    <script>
+        function voteSpice(level) {{
+            fetch('/api/roast/{roast_id}/spice', {{
+                method: 'POST',
+                headers: {{ 'Content-Type': 'application/json' }},
+                body: JSON.stringify({{ level }})
+            }})
+                .then(r => r.json())
+                .then(data => {{
+                    if (!data.success) {{
+                        alert(data.error);
+                        return;
+                    }}
+                    for (const [key, percent] of Object.entries(data.spice.percent)) {{
+                        document.getElementById('spice-bar-' + key).style.width = percent + '%';
+                    }}
+                    document.querySelectorAll('.spice__option').forEach(button => {{
+                        button.classList.toggle('spice__option--chosen', button.dataset.level === level);
+                    }});
+                    const total = document.getElementById('spice-total');
+                    total.textContent = total.dataset.template.replace('{{count}}', data.spice.total);
+                }});
+        }}
+    </script>
+</section>"#,
+        title = labels.spice_title,
+        options = options,
+        bar = bar,
+        answers_template = labels.spice_answers,
+        answers = labels.spice_answers.replace("{count}", &spice.total().to_string()),
+        roast_id = roast_id,
+    )
+}
+
+fn render_scorecard(score: &RoastScore, language: Language) -> String {
+    let labels = labels(language);
+    let rows: String = score
+        .entries(language)
+        .iter()
+        .map(|(label, value)| {
+            format!(
+                r#"<div class="scorecard__row">
+                    <span class="scorecard__label">{label}</span>
+                    <div class="scorecard__bar"><div class="scorecard__fill" style="width: {width}%"></div></div>
+                    <span class="scorecard__value">{value}/10</span>
+                </div>"#,
+                label = label,
+                width = *value as u32 * 10,
+                value = value,
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<div class="scorecard">
+            <h3 class="scorecard__title">{title}</h3>
+            {rows}
+            <p class="scorecard__overall">{overall_label}: <strong>{overall:.1}</strong>/10</p>
+        </div>"#,
+        title = labels.scorecard_title,
+        rows = rows,
+        overall_label = labels.overall_score,
+        overall = score.overall(),
+    )
+}
+
+/// schema.org `Review` of the startup, built from the same row as the roast page
+fn roast_json_ld(base_url: &str, roast: &RoastWithDetails) -> serde_json::Value {
+    let author = match &roast.author_name {
+        Some(name) => serde_json::json!({ "@type": "Person", "name": name }),
+        None => serde_json::json!({ "@type": "Organization", "name": "Roasting Startup" }),
     };
+    let mut review = serde_json::json!({
+        "@type": "Review",
+        "name": format!("{}{}", labels(roast.language).roast_title_prefix, roast.startup_name),
+        "url": format!("{}/r/{}", base_url, roast.id),
+        "inLanguage": labels(roast.language).html_lang,
+        "itemReviewed": {
+            "@type": "Organization",
+            "name": roast.startup_name,
+            "url": roast.startup_url,
+        },
+        "reviewBody": roast.roast_text.chars().take(500).collect::<String>(),
+        "author": author,
+        "publisher": { "@type": "Organization", "name": "Roasting Startup", "url": base_url },
+        "interactionStatistic": {
+            "@type": "InteractionCounter",
+            "interactionType": "https://schema.org/LikeAction",
+            "userInteractionCount": roast.fire_count,
+        },
+    });
+    if let Some(score) = &roast.score {
+        review["reviewRating"] = serde_json::json!({
+            "@type": "Rating",
+            "ratingValue": format!("{:.1}", score.overall()),
+            "bestRating": RoastScore::MAX,
+            "worstRating": RoastScore::MIN,
+        });
+    }
+    if let Some(created_at) = roast.created_at {
+        review["datePublished"] = serde_json::json!(created_at.to_rfc3339());
+    }
+    review
+}
 
-    // Create User object
-    let new_user = User {
-        id: Uuid::new_v4(),
-        google_id: user_info.sub.clone(),
-        email: user_info.email.clone(),
-        name: user_info.name.clone(),
-        avatar_url: user_info.picture.clone(),
-        created_at: None,
-        updated_at: None,
+/// `ItemList` of the leaderboard, in rank order
+fn leaderboard_json_ld(base_url: &str, roasts: &[RoastWithDetails]) -> serde_json::Value {
+    let items: Vec<_> = roasts
+        .iter()
+        .enumerate()
+        .map(|(i, roast)| {
+            serde_json::json!({
+                "@type": "ListItem",
+                "position": i + 1,
+                "item": roast_json_ld(base_url, roast),
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "@type": "ItemList",
+        "name": "Leaderboard Roasting Startup",
+        "url": format!("{}/leaderboard", base_url),
+        "numberOfItems": items.len(),
+        "itemListElement": items,
+    })
+}
+
+/// A JSON-LD `<script>` block; `<` is escaped so page text can't close the tag
+fn render_json_ld(mut data: serde_json::Value) -> String {
+    data["@context"] = serde_json::json!("https://schema.org");
+    format!(
+        r#"<script type="application/ld+json">{}</script>"#,
+        data.to_string().replace('<', "\\u003c")
+    )
+}
+
+fn render_result_page(
+    startup_name: &str,
+    html_content: &str,
+    url: &str,
+    language: Language,
+    score: Option<&RoastScore>,
+) -> String {
+    let encoded_url = urlencoding::encode(url);
+    let labels = labels(language);
+    let scorecard_html = score
+        .map(|s| render_scorecard(s, language))
+        .unwrap_or_default();
+    format!(r#"<!DOCTYPE html>
+<html lang="{html_lang}">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>{title_prefix}{startup_name}</title>
+    <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
+    <style>{CSS}</style>
+    <script>history.replaceState(null, '', '/roast?url={encoded_url}');</script>
+</head>
+<body>
+    <main class="container">
+        <div class="roast">
+            <h2 class="roast__title">{title_prefix}{startup_name}</h2>
+            <div class="roast__content">{html_content}</div>
+            {scorecard_html}
+            <div class="roast__actions">
+                <a href="/" class="roast__button--primary" style="text-decoration:none;display:inline-block;">{roast_again}</a>
+            </div>
+        </div>
+    </main>
+</body>
+</html>"#,
+        html_lang = labels.html_lang,
+        title_prefix = labels.roast_title_prefix,
+        roast_again = labels.roast_again,
+        startup_name = startup_name,
+        html_content = html_content,
+        scorecard_html = scorecard_html,
+        CSS = CSS,
+        encoded_url = encoded_url,
+    )
+}
+
+fn render_result_page_with_id(
+    startup_name: &str,
+    html_content: &str,
+    dedicated_to: Option<&str>,
+    roast_id: Uuid,
+    language: Language,
+    score: Option<&RoastScore>,
+    battle: Option<&BattleSuggestion>,
+) -> String {
+    let labels = labels(language);
+    let dedication_html = dedicated_to
+        .map(|name| {
+            format!(
+                r#"<p class="roast__dedication">🎁 {label} <strong>{name}</strong></p>"#,
+                label = labels.dedicated_to,
+                name = escape_html(name),
+            )
+        })
+        .unwrap_or_default();
+    let og_description = match dedicated_to {
+        Some(name) => format!("{} {}", labels.dedicated_to, name),
+        None => labels.share_description.to_string(),
     };
+    // The share link is appended client-side from location.origin
+    let whatsapp_message = match dedicated_to {
+        Some(name) => labels
+            .whatsapp_gift
+            .replace("{name}", name)
+            .replace("{startup}", startup_name),
+        None => labels.whatsapp_share.replace("{startup}", startup_name),
+    };
+    let scorecard_html = score
+        .map(|s| render_scorecard(s, language))
+        .unwrap_or_default();
+    let battle_html = battle
+        .map(|b| {
+            format!(
+                r#"<div class="battle">
+                <p class="battle__prompt">⚔️ {prompt}</p>
+                <a href="/r/{opponent_id}" class="battle__opponent">{opponent_name}</a>
+                <a href="/r/{opponent_id}" class="roast__button--secondary">{cta}</a>
+            </div>"#,
+                prompt = labels.battle_prompt,
+                opponent_id = b.opponent_id,
+                opponent_name = escape_html(&b.opponent_name),
+                cta = labels.battle_cta,
+            )
+        })
+        .unwrap_or_default();
+    format!(r#"<!DOCTYPE html>
+<html lang="{html_lang}">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>{title_prefix}{startup_name}</title>
+    <meta property="og:type" content="article">
+    <meta property="og:title" content="{title_prefix}{startup_name}">
+    <meta property="og:description" content="{og_description}">
+    <meta name="twitter:card" content="summary">
+    <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
+    <style>{CSS}</style>
+    <script>history.replaceState(null, '', '/r/{roast_id}');</script>
+</head>
+<body>
+    <main class="container">
+        <div class="roast">
+            {dedication_html}
+            <h2 class="roast__title">{title_prefix}{startup_name}</h2>
+            <div class="roast__content">{html_content}</div>
+            {scorecard_html}
+            <div class="roast__actions">
+                <button id="vote-btn" class="roast__vote-btn" onclick="toggleVote()">
+                    <span class="fire-emoji">🔥</span>
+                    <span id="fire-count">0</span>
+                </button>
+                <a href="/" class="roast__button--primary" style="text-decoration:none;display:inline-block;">{roast_again}</a>
+                <a href="/leaderboard" class="roast__button--secondary" style="text-decoration:none;display:inline-block;margin-left:0.5rem;">{leaderboard}</a>
+                <button id="regenerate-btn" class="roast__button--secondary" style="display:none;margin-left:0.5rem;" onclick="regenerateRoast()">{regenerate}</button>
+                <button id="push-btn" class="roast__button--secondary" style="display:none;margin-left:0.5rem;" onclick="enablePush()">{push_subscribe}</button>
+                <button id="watch-btn" class="roast__button--secondary" style="display:none;margin-left:0.5rem;" onclick="toggleWatch()">{watch}</button>
+                <a id="whatsapp-btn" class="roast__button--whatsapp" target="_blank" rel="noopener" data-message="{whatsapp_message}">{share_whatsapp}</a>
+            </div>
+            <div id="schedule-box" class="roast__schedule" style="display:none;">
+                <p id="embargo-note" style="display:none;">{embargoed_until} <strong id="embargo-time"></strong></p>
+                <input type="datetime-local" id="publish-at">
+                <button class="roast__button--secondary" onclick="schedulePublish(false)">{schedule_publish}</button>
+                <button id="publish-now-btn" class="roast__button--secondary" style="display:none;" onclick="schedulePublish(true)">{publish_now}</button>
+            </div>
+            <div id="founder-box" class="founder" style="display:none;">
+                <p class="founder__title">{founder_title}</p>
+                <div id="founder-stats" class="founder__stats"></div>
+                <div id="founder-chart" class="founder__chart"></div>
+                <p class="founder__label">{founder_referrers}</p>
+                <ul id="founder-referrers" class="founder__referrers"></ul>
+            </div>
+            {battle_html}
+        </div>
+    </main>
+    <script>
+        const roastId = '{roast_id}';
+        let hasVoted = false;
+
+        const whatsappBtn = document.getElementById('whatsapp-btn');
+        whatsappBtn.href = 'https://wa.me/?text=' + encodeURIComponent(
+            whatsappBtn.dataset.message + ' ' + location.origin + '/r/' + roastId
+        );
+        whatsappBtn.addEventListener('click', () => {{
+            navigator.sendBeacon('/api/roast/' + roastId + '/share', new Blob(
+                [JSON.stringify({{ channel: 'whatsapp' }})],
+                {{ type: 'application/json' }}
+            ));
+        }});
+
+        // Load initial vote state
+        fetch('/api/roast/' + roastId)
+            .then(r => r.json())
+            .then(data => {{
+                if (data.success) {{
+                    document.getElementById('fire-count').textContent = data.roast.fire_count;
+                    hasVoted = data.has_voted;
+                    updateVoteButton();
+                    if (data.watch.domain) {{
+                        watchDomain = data.watch.domain;
+                        watching = data.watch.watching;
+                        updateWatchButton();
+                    }}
+                    if (data.founder) {{
+                        loadFounderStats(data.watch.domain);
+                    }}
+                    if (data.roast.is_owner) {{
+                        document.getElementById('regenerate-btn').style.display = 'inline-block';
+                        showPushButton();
+                        showSchedule(data.roast.publish_at);
+                    }}
+                }}
+            }});
+
+        // Watch the startup's domain for new roasts and follow-ups
+        let watchDomain = null;
+        let watching = false;
+
+        function updateWatchButton() {{
+            const btn = document.getElementById('watch-btn');
+            btn.style.display = 'inline-block';
+            btn.textContent = watching ? '{unwatch}' : '{watch}';
+        }}
+
+        function toggleWatch() {{
+            fetch('/api/me/watches/' + encodeURIComponent(watchDomain), {{ method: watching ? 'DELETE' : 'PUT' }})
+                .then(r => r.json())
+                .then(data => {{
+                    if (data.success) {{
+                        watching = data.watching;
+                        updateWatchButton();
+                    }} else if (data.error === 'Login required') {{
+                        if (confirm('{login_to_watch}')) {{
+                            window.location.href = '/auth/login';
+                        }}
+                    }} else {{
+                        alert(data.error);
+                    }}
+                }});
+        }}
 
-    // Upsert user in database
-    let user = match ctx.user_repo.upsert(&new_user).await {
-        Ok(user) => user,
-        Err(e) => {
-            tracing::error!("Failed to upsert user: {}", e);
-            return Redirect::to("/?error=db_error");
-        }
-    };
+        // Private stats, only served to verified founders of this domain
+        function loadFounderStats(domain) {{
+            fetch('/api/founder/analytics/' + encodeURIComponent(domain))
+                .then(r => r.ok ? r.json() : null)
+                .then(data => {{
+                    if (!data || !data.success) return;
+                    const analytics = data.analytics;
+                    document.getElementById('founder-box').style.display = 'block';
+                    const stat = (value, label) =>
+                        '<div class="founder__stat"><strong>' + value + '</strong><span>' + label + '</span></div>';
+                    document.getElementById('founder-stats').innerHTML =
+                        stat(data.total_views, '{founder_views}') +
+                        stat(data.total_shares, '{founder_shares}') +
+                        stat(data.vote_velocity.toFixed(1), '{founder_fires_per_day}');
+                    const peak = Math.max(1, ...analytics.views.map(d => d.count));
+                    document.getElementById('founder-chart').replaceChildren(...analytics.views.map(d => {{
+                        const bar = document.createElement('span');
+                        bar.style.height = Math.max(4, d.count / peak * 100) + '%';
+                        bar.title = d.day + ': ' + d.count;
+                        return bar;
+                    }}));
+                    document.getElementById('founder-referrers').replaceChildren(...analytics.referrers.map(r => {{
+                        const item = document.createElement('li');
+                        item.textContent = (r.referrer || '{founder_direct}') + ' · ' + r.views;
+                        return item;
+                    }}));
+                }});
+        }}
 
-    // Store user ID in session
-    if let Err(e) = session.insert(SESSION_USER_ID, user.id).await {
-        tracing::error!("Failed to store user ID in session: {}", e);
-        return Redirect::to("/?error=session_error");
-    }
+        // Embargo: the roast stays hidden from everyone else until publish_at
+        function showSchedule(publishAt) {{
+            document.getElementById('schedule-box').style.display = 'block';
+            const embargoed = !!publishAt;
+            document.getElementById('embargo-note').style.display = embargoed ? 'block' : 'none';
+            document.getElementById('publish-now-btn').style.display = embargoed ? 'inline-block' : 'none';
+            if (embargoed) {{
+                document.getElementById('embargo-time').textContent = new Date(publishAt).toLocaleString();
+            }}
+        }}
 
-    // Clean up OAuth state from session
-    let _ = session.remove::<String>(SESSION_CSRF_TOKEN).await;
-    let _ = session.remove::<String>(SESSION_PKCE_VERIFIER).await;
+        function schedulePublish(now) {{
+            const value = document.getElementById('publish-at').value;
+            if (!now && !value) return;
+            fetch('/api/roast/' + roastId + '/schedule', {{
+                method: 'PUT',
+                headers: {{ 'Content-Type': 'application/json' }},
+                body: JSON.stringify({{ publish_at: now ? null : new Date(value).toISOString() }}),
+            }})
+                .then(r => r.json())
+                .then(data => {{
+                    if (data.success) {{
+                        showSchedule(data.publish_at);
+                    }} else {{
+                        alert(data.error);
+                    }}
+                }});
+        }}
 
-    tracing::info!("User logged in: {} ({})", user.name, user.email);
-    Redirect::to("/")
-}
+        // Fire milestone notifications, delivered by /sw.js even with the tab closed
+        let pushKey = null;
 
-async fn handle_auth_logout(session: Session) -> impl IntoResponse {
-    session.flush().await.ok();
-    Redirect::to("/")
-}
+        function showPushButton() {{
+            if (!('serviceWorker' in navigator) || !('PushManager' in window)) return;
+            fetch('/api/push/public-key')
+                .then(r => r.ok ? r.json() : null)
+                .then(data => {{
+                    if (!data) return;
+                    pushKey = data.public_key;
+                    document.getElementById('push-btn').style.display = 'inline-block';
+                }});
+        }}
 
-async fn handle_auth_me(ctx: AppContext, session: Session) -> impl IntoResponse {
-    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+        function urlBase64ToUint8Array(value) {{
+            const base64 = (value + '='.repeat((4 - value.length % 4) % 4)).replace(/-/g, '+').replace(/_/g, '/');
+            return Uint8Array.from(atob(base64), c => c.charCodeAt(0));
+        }}
 
-    match user_id {
-        Some(id) => match ctx.user_repo.find_by_id(id).await {
-            Ok(Some(user)) => Json(serde_json::json!({
-                "authenticated": true,
-                "user": {
-                    "id": user.id,
-                    "name": user.name,
-                    "email": user.email,
-                    "avatar_url": user.avatar_url,
-                }
-            })).into_response(),
-            _ => Json(serde_json::json!({ "authenticated": false })).into_response(),
-        },
-        None => Json(serde_json::json!({ "authenticated": false })).into_response(),
-    }
-}
+        async function enablePush() {{
+            const btn = document.getElementById('push-btn');
+            btn.disabled = true;
+            try {{
+                if (await Notification.requestPermission() !== 'granted') {{
+                    btn.disabled = false;
+                    return;
+                }}
+                const registration = await navigator.serviceWorker.register('/sw.js');
+                const subscription = await registration.pushManager.subscribe({{
+                    userVisibleOnly: true,
+                    applicationServerKey: urlBase64ToUint8Array(pushKey),
+                }});
+                const response = await fetch('/api/me/push-subscriptions', {{
+                    method: 'POST',
+                    headers: {{ 'Content-Type': 'application/json' }},
+                    body: JSON.stringify(subscription),
+                }});
+                if (!response.ok) throw new Error((await response.json()).error);
+                btn.textContent = '{push_subscribed}';
+            }} catch (e) {{
+                alert(e.message);
+                btn.disabled = false;
+            }}
+        }}
 
-async fn handle_vote(ctx: AppContext, session: Session, roast_id: Uuid) -> impl IntoResponse {
-    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+        function regenerateRoast() {{
+            if (!confirm('{regenerate_confirm}')) return;
+            const btn = document.getElementById('regenerate-btn');
+            btn.disabled = true;
+            btn.textContent = '{regenerating}';
+            fetch('/api/roast/' + roastId + '/regenerate', {{ method: 'POST' }})
+                .then(r => r.json())
+                .then(data => {{
+                    if (data.success) {{
+                        window.location.reload();
+                    }} else {{
+                        alert(data.error);
+                        btn.disabled = false;
+                        btn.textContent = '{regenerate}';
+                    }}
+                }});
+        }}
 
-    match user_id {
-        Some(user_id) => {
-            // toggle() already handles incrementing/decrementing fire count
-            match ctx.vote_repo.toggle(user_id, roast_id, &ctx.roast_repo).await {
-                Ok(result) => {
-                    Json(serde_json::json!({
-                        "success": true,
-                        "voted": result.voted,
-                        "fire_count": result.new_fire_count,
-                    })).into_response()
-                }
-                Err(e) => {
-                    tracing::error!("Vote failed: {}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
-                        "success": false,
-                        "error": "Failed to toggle vote"
-                    }))).into_response()
-                }
-            }
-        }
-        None => {
-            (StatusCode::UNAUTHORIZED, Json(serde_json::json!({
-                "success": false,
-                "error": "Must be logged in to vote"
-            }))).into_response()
-        }
-    }
-}
+        function updateVoteButton() {{
+            const btn = document.getElementById('vote-btn');
+            if (hasVoted) {{
+                btn.classList.add('voted');
+            }} else {{
+                btn.classList.remove('voted');
+            }}
+        }}
 
-async fn handle_leaderboard(ctx: AppContext, session: Session) -> impl IntoResponse {
-    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+        function toggleVote() {{
+            fetch('/api/roast/' + roastId + '/vote', {{ method: 'POST' }})
+                .then(r => r.json())
+                .then(data => {{
+                    if (data.success) {{
+                        hasVoted = data.voted;
+                        document.getElementById('fire-count').textContent = data.fire_count;
+                        updateVoteButton();
+                    }} else if (data.error === 'Must be logged in to vote') {{
+                        if (confirm('{login_to_vote}')) {{
+                            window.location.href = '/auth/login';
+                        }}
+                    }} else if (data.error === 'Cannot vote on your own roast') {{
+                        alert('{own_roast_vote}');
+                    }}
+                }});
+        }}
+    </script>
+</body>
+</html>"#,
+        html_lang = labels.html_lang,
+        title_prefix = labels.roast_title_prefix,
+        roast_again = labels.roast_again,
+        leaderboard = labels.leaderboard,
+        login_to_vote = labels.login_to_vote,
+        own_roast_vote = labels.own_roast_vote,
+        regenerate = labels.regenerate,
+        regenerate_confirm = labels.regenerate_confirm,
+        regenerating = labels.regenerating,
+        push_subscribe = labels.push_subscribe,
+        push_subscribed = labels.push_subscribed,
+        schedule_publish = labels.schedule_publish,
+        publish_now = labels.publish_now,
+        embargoed_until = labels.embargoed_until,
+        watch = labels.watch,
+        unwatch = labels.unwatch,
+        login_to_watch = labels.login_to_watch,
+        founder_title = labels.founder_title,
+        founder_views = labels.founder_views,
+        founder_shares = labels.founder_shares,
+        founder_fires_per_day = labels.founder_fires_per_day,
+        founder_referrers = labels.founder_referrers,
+        founder_direct = labels.founder_direct,
+        share_whatsapp = labels.share_whatsapp,
+        whatsapp_message = escape_html(&whatsapp_message),
+        og_description = escape_html(&og_description),
+        dedication_html = dedication_html,
+        battle_html = battle_html,
+        startup_name = startup_name,
+        html_content = html_content,
+        CSS = CSS,
+        roast_id = roast_id,
+    )
+}
 
-    match ctx.roast_repo.get_leaderboard(50, user_id).await {
-        Ok(roasts) => Json(serde_json::json!({
-            "success": true,
-            "roasts": roasts.into_iter().map(|r| serde_json::json!({
-                "id": r.id,
-                "startup_name": r.startup_name,
-                "startup_url": r.startup_url,
-                "roast_text": r.roast_text,
-                "fire_count": r.fire_count,
-                "created_at": r.created_at,
-                "author_name": r.author_name,
-                "author_avatar": r.author_avatar,
-                "user_has_voted": r.user_has_voted,
-            })).collect::<Vec<_>>(),
-        })).into_response(),
-        Err(e) => {
-            tracing::error!("Failed to get leaderboard: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
-                "success": false,
-                "error": "Failed to fetch leaderboard"
-            }))).into_response()
-        }
+/// Ask for an invite code, resubmitting the original roast request with it
+/// Hidden inputs carrying a described idea through the invite and busy pages
+fn idea_fields(form: &RoastForm) -> String {
+    match form.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        Some(description) => format!(
+            r#"<input type="hidden" name="description" value="{}"><input type="hidden" name="idea_name" value="{}">"#,
+            escape_html(description),
+            escape_html(form.idea_name.as_deref().unwrap_or_default())
+        ),
+        None => String::new(),
     }
 }
 
-async fn handle_leaderboard_page(ctx: AppContext, session: Session) -> impl IntoResponse {
-    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
-
-    match ctx.roast_repo.get_leaderboard(50, user_id).await {
-        Ok(roasts) => Html(render_leaderboard_page(&roasts)),
-        Err(e) => {
-            tracing::error!("Failed to get leaderboard: {}", e);
-            Html(render_error_page("Gagal memuat leaderboard"))
+fn render_invite_page(form: &RoastForm, message: &str) -> String {
+    let checkbox = |name: &str, on: bool| {
+        if on {
+            format!(r#"<input type="hidden" name="{}" value="true">"#, name)
+        } else {
+            String::new()
         }
-    }
+    };
+    format!(r#"<!DOCTYPE html>
+<html lang="id">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>Private Beta - Roasting Startup</title>
+    <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
+    <style>{CSS}</style>
+</head>
+<body>
+    <main class="container">
+        <div class="invite">
+            <p class="invite__title">🔒 Private Beta</p>
+            <p class="invite__message">{message}</p>
+            <form method="post" action="/roast" class="invite__form">
+                <input type="hidden" name="url" value="{url}">
+                <input type="hidden" name="language" value="{language}">
+                <input type="hidden" name="dialect" value="{dialect}">
+                <input type="hidden" name="length" value="{length}">
+                <input type="hidden" name="dedicated_to" value="{dedicated_to}">
+                {idea}
+                {flags}
+                <input type="text" name="invite_code" class="invite__input" placeholder="BETA-XXXXXXXX" required autofocus>
+                <button type="submit" class="roast__button--primary">Roast!</button>
+            </form>
+        </div>
+    </main>
+</body>
+</html>"#,
+        CSS = CSS,
+        message = message,
+        url = escape_html(&form.url),
+        language = form.language.as_str(),
+        dialect = form.dialect.as_str(),
+        length = form.length.as_str(),
+        dedicated_to = escape_html(form.dedicated_to.as_deref().unwrap_or_default()),
+        idea = idea_fields(form),
+        flags = [
+            checkbox("anonymous", form.anonymous),
+            checkbox("defer", form.defer),
+            checkbox("regenerate", form.regenerate),
+            checkbox("safe", form.safe),
+        ]
+        .concat(),
+    )
 }
 
-async fn handle_view_roast_page(ctx: AppContext, session: Session, roast_id: Uuid) -> impl IntoResponse {
-    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
-
-    match ctx.roast_repo.find_by_id_with_details(roast_id, user_id).await {
-        Ok(Some(roast)) => {
-            Html(render_result_page_with_id(
-                &roast.startup_name,
-                &roast.roast_text,
-                &roast.startup_url,
-                roast_id,
-            ))
-        }
-        Ok(None) => Html(render_error_page("Roast tidak ditemukan")),
-        Err(e) => {
-            tracing::error!("Failed to get roast: {}", e);
-            Html(render_error_page("Gagal memuat roast"))
-        }
-    }
+fn render_login_required_page() -> String {
+    format!(r#"<!DOCTYPE html>
+<html lang="id">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>Login Dulu - Roasting Startup</title>
+    <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
+    <style>{CSS}</style>
+</head>
+<body>
+    <main class="container">
+        <div class="invite">
+            <p class="invite__title">🔑 Login Dulu</p>
+            <p class="invite__message">Biar semua roast ada yang bertanggung jawab, bikin roast sekarang butuh login. Lihat-lihat roast dan leaderboard tetap bebas tanpa login.</p>
+            <div class="invite__form">
+                <a href="/auth/login" class="roast__button--primary" style="text-decoration:none;display:inline-block;">Login dengan Google</a>
+                <a href="/" class="roast__button--secondary">Kembali</a>
+            </div>
+        </div>
+    </main>
+</body>
+</html>"#,
+        CSS = CSS,
+    )
 }
 
-async fn handle_get_roast(ctx: AppContext, session: Session, roast_id: Uuid) -> impl IntoResponse {
-    let user_id: Option<Uuid> = session.get(SESSION_USER_ID).await.ok().flatten();
+/// Report form pre-filled with the failed request's ID and error
+fn render_report_problem_page(request_id: &str, error: &str) -> String {
+    let error_html = if error.is_empty() {
+        String::new()
+    } else {
+        format!(r#"<p class="error__message">{}</p>"#, escape_html(error))
+    };
+    format!(r#"<!DOCTYPE html>
+<html lang="id">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>Laporkan Masalah - Roasting Startup</title>
+    <meta name="robots" content="noindex">
+    <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
+    <style>{CSS}</style>
+</head>
+<body>
+    <main class="container">
+        <div class="roast">
+            <h2 class="roast__title">Laporkan Masalah</h2>
+            {error_html}
+            <form id="problem-form" class="problem-form" onsubmit="submitProblem(event)">
+                <label>ID permintaan <input id="request-id" value="{request_id}" required></label>
+                <textarea id="problem-message" maxlength="500" placeholder="Apa yang kamu lakukan waktu error-nya muncul?" required></textarea>
+                <button type="submit" class="roast__button--primary">Kirim Laporan</button>
+            </form>
+            <p id="problem-status" class="error__trace"></p>
+        </div>
+    </main>
+    <script>
+        function submitProblem(event) {{
+            event.preventDefault();
+            const status = document.getElementById('problem-status');
+            fetch('/api/problem-reports', {{
+                method: 'POST',
+                headers: {{ 'Content-Type': 'application/json' }},
+                body: JSON.stringify({{
+                    request_id: document.getElementById('request-id').value,
+                    message: document.getElementById('problem-message').value,
+                    error: {error_json},
+                }}),
+            }})
+                .then(r => r.json())
+                .then(data => {{
+                    if (data.success) {{
+                        document.getElementById('problem-form').style.display = 'none';
+                        status.textContent = 'Makasih! Laporanmu sudah masuk ke tim kami.';
+                    }} else {{
+                        status.textContent = data.error;
+                    }}
+                }})
+                .catch(() => {{ status.textContent = 'Gagal mengirim laporan. Coba lagi nanti.'; }});
+        }}
+    </script>
+</body>
+</html>"#,
+        CSS = CSS,
+        error_html = error_html,
+        request_id = escape_html(request_id),
+        error_json = serde_json::to_string(error).unwrap_or_default().replace('<', "\\u003c"),
+    )
+}
 
-    match ctx.roast_repo.find_by_id_with_details(roast_id, user_id).await {
-        Ok(Some(roast)) => {
-            Json(serde_json::json!({
-                "success": true,
-                "roast": {
-                    "id": roast.id,
-                    "startup_name": roast.startup_name,
-                    "startup_url": roast.startup_url,
-                    "roast_text": roast.roast_text,
-                    "fire_count": roast.fire_count,
-                    "created_at": roast.created_at,
-                    "author_name": roast.author_name,
-                    "author_avatar": roast.author_avatar,
-                },
-                "has_voted": roast.user_has_voted,
-            })).into_response()
-        }
-        Ok(None) => {
-            (StatusCode::NOT_FOUND, Json(serde_json::json!({
-                "success": false,
-                "error": "Roast not found"
-            }))).into_response()
-        }
-        Err(e) => {
-            tracing::error!("Failed to get roast: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
-                "success": false,
-                "error": "Failed to fetch roast"
-            }))).into_response()
-        }
-    }
+fn render_error_page(message: &str) -> String {
+    let report_html = current_request_id()
+        .map(|request_id| {
+            format!(
+                r#"<p class="error__trace">ID: <code>{request_id}</code> · <a href="/report-problem?request_id={request_id}&error={error}">Laporkan masalah</a></p>"#,
+                error = urlencoding::encode(message),
+            )
+        })
+        .unwrap_or_default();
+    format!(r#"<!DOCTYPE html>
+<html lang="id">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>Error - Roasting Startup</title>
+    <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
+    <style>{CSS}</style>
+</head>
+<body>
+    <main class="container">
+        <div class="error">
+            <p class="error__title">Yah, error nih!</p>
+            <p class="error__message">{message}</p>
+            <a href="/" class="error__retry" style="text-decoration:none;display:inline-block;margin-top:1rem;">Coba Lagi</a>
+            {report_html}
+        </div>
+    </main>
+</body>
+</html>"#, message = message, report_html = report_html, CSS = CSS)
 }
 
-fn render_result_page(startup_name: &str, roast_text: &str, url: &str) -> String {
-    let html_content = simple_markdown_to_html(roast_text);
-    let encoded_url = urlencoding::encode(url);
+/// Error page that offers to queue the roast instead of giving up
+fn render_defer_offer_page(message: &str, form: &RoastForm) -> String {
     format!(r#"<!DOCTYPE html>
 <html lang="id">
 <head>
     <meta charset="utf-8">
     <meta name="viewport" content="width=device-width, initial-scale=1">
-    <title>Roasting: {startup_name}</title>
+    <title>AI Lagi Sibuk - Roasting Startup</title>
     <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
     <style>{CSS}</style>
-    <script>history.replaceState(null, '', '/roast?url={encoded_url}');</script>
 </head>
 <body>
     <main class="container">
-        <div class="roast">
-            <h2 class="roast__title">Roasting: {startup_name}</h2>
-            <div class="roast__content">{html_content}</div>
-            <div class="roast__actions">
-                <a href="/" class="roast__button--primary" style="text-decoration:none;display:inline-block;">Roast Lagi!</a>
-            </div>
+        <div class="error">
+            <p class="error__title">Yah, AI-nya lagi istirahat!</p>
+            <p class="error__message">{message}</p>
+            <p class="error__message">Mau diantrikan? Roast-mu akan dibuat otomatis begitu AI tersedia lagi, dan kamu bisa cek hasilnya di halaman antrian.</p>
+            <form action="/roast" method="post" style="margin-top:1rem;">
+                <input type="hidden" name="url" value="{url}">
+                <input type="hidden" name="language" value="{language}">
+                <input type="hidden" name="dialect" value="{dialect}">
+                <input type="hidden" name="length" value="{length}">
+                <input type="hidden" name="anonymous" value="{anonymous}">
+                <input type="hidden" name="dedicated_to" value="{dedicated_to}">
+                <input type="hidden" name="defer" value="true">
+                <button type="submit" class="roast__button--primary">Antrikan &amp; kabari aku</button>
+            </form>
+            <a href="/" class="error__retry" style="text-decoration:none;display:inline-block;margin-top:1rem;">Nggak jadi</a>
         </div>
     </main>
 </body>
-</html>"#, startup_name = startup_name, html_content = html_content, CSS = CSS, encoded_url = encoded_url)
+</html>"#,
+        message = message,
+        url = escape_html(&form.url),
+        language = form.language.as_str(),
+        dialect = form.dialect.as_str(),
+        length = form.length.as_str(),
+        anonymous = form.anonymous,
+        dedicated_to = escape_html(form.dedicated_to.as_deref().unwrap_or_default()),
+        CSS = CSS,
+    )
 }
 
-fn render_result_page_with_id(startup_name: &str, roast_text: &str, url: &str, roast_id: Uuid) -> String {
-    let html_content = simple_markdown_to_html(roast_text);
+/// Shown to anonymous submissions while generation is overloaded. The retry
+/// button unlocks once the token is redeemable.
+fn render_busy_page(form: &RoastForm, retry_token: &str) -> String {
+    use roasting_app::infrastructure::security::RETRY_AFTER;
+
     format!(r#"<!DOCTYPE html>
 <html lang="id">
 <head>
     <meta charset="utf-8">
     <meta name="viewport" content="width=device-width, initial-scale=1">
-    <title>Roasting: {startup_name}</title>
+    <title>Lagi Rame Banget - Roasting Startup</title>
     <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
     <style>{CSS}</style>
-    <script>history.replaceState(null, '', '/r/{roast_id}');</script>
 </head>
 <body>
     <main class="container">
-        <div class="roast">
-            <h2 class="roast__title">Roasting: {startup_name}</h2>
-            <div class="roast__content">{html_content}</div>
-            <div class="roast__actions">
-                <button id="vote-btn" class="roast__vote-btn" onclick="toggleVote()">
-                    <span class="fire-emoji">🔥</span>
-                    <span id="fire-count">0</span>
-                </button>
-                <a href="/" class="roast__button--primary" style="text-decoration:none;display:inline-block;">Roast Lagi!</a>
-                <a href="/leaderboard" class="roast__button--secondary" style="text-decoration:none;display:inline-block;margin-left:0.5rem;">Leaderboard</a>
-            </div>
+        <div class="error">
+            <p class="error__title">Lagi rame banget! 🔥</p>
+            <p class="error__message">Dapur roasting lagi penuh antrian. Tunggu sebentar lalu coba lagi, tempatmu sudah kami catat. Login juga bikin kamu didahulukan.</p>
+            <form action="/roast" method="post" style="margin-top:1rem;">
+                <input type="hidden" name="url" value="{url}">
+                <input type="hidden" name="language" value="{language}">
+                <input type="hidden" name="dialect" value="{dialect}">
+                <input type="hidden" name="length" value="{length}">
+                <input type="hidden" name="safe" value="{safe}">
+                <input type="hidden" name="anonymous" value="{anonymous}">
+                <input type="hidden" name="dedicated_to" value="{dedicated_to}">
+                <input type="hidden" name="retry_token" value="{retry_token}">
+                {idea}
+                <button type="submit" id="busy-retry" class="roast__button--primary" disabled>Coba lagi dalam <span id="busy-countdown">{wait}</span> detik</button>
+            </form>
+            <form action="/roast" method="post" style="margin-top:0.5rem;{defer_hidden}">
+                <input type="hidden" name="url" value="{url}">
+                <input type="hidden" name="language" value="{language}">
+                <input type="hidden" name="dialect" value="{dialect}">
+                <input type="hidden" name="length" value="{length}">
+                <input type="hidden" name="safe" value="{safe}">
+                <input type="hidden" name="anonymous" value="{anonymous}">
+                <input type="hidden" name="dedicated_to" value="{dedicated_to}">
+                <input type="hidden" name="defer" value="true">
+                <button type="submit" class="error__retry">Antrikan aja &amp; kabari aku</button>
+            </form>
+            <a href="/" class="error__retry" style="text-decoration:none;display:inline-block;margin-top:1rem;">Nggak jadi</a>
         </div>
     </main>
     <script>
-        const roastId = '{roast_id}';
-        let hasVoted = false;
-
-        // Load initial vote state
-        fetch('/api/roast/' + roastId)
-            .then(r => r.json())
-            .then(data => {{
-                if (data.success) {{
-                    document.getElementById('fire-count').textContent = data.roast.fire_count;
-                    hasVoted = data.has_voted;
-                    updateVoteButton();
+        (function () {{
+            var left = {wait};
+            var button = document.getElementById('busy-retry');
+            var countdown = document.getElementById('busy-countdown');
+            var timer = setInterval(function () {{
+                left -= 1;
+                countdown.textContent = left;
+                if (left <= 0) {{
+                    clearInterval(timer);
+                    button.disabled = false;
+                    button.textContent = 'Coba lagi sekarang';
                 }}
-            }});
+            }}, 1000);
+        }})();
+    </script>
+</body>
+</html>"#,
+        url = escape_html(&form.url),
+        language = form.language.as_str(),
+        dialect = form.dialect.as_str(),
+        length = form.length.as_str(),
+        safe = form.safe,
+        anonymous = form.anonymous,
+        dedicated_to = escape_html(form.dedicated_to.as_deref().unwrap_or_default()),
+        retry_token = escape_html(retry_token),
+        idea = idea_fields(form),
+        // Described ideas can't be queued; the worker only knows how to scrape
+        defer_hidden = if idea_fields(form).is_empty() { "" } else { "display:none;" },
+        wait = RETRY_AFTER.as_secs(),
+        CSS = CSS,
+    )
+}
 
-        function updateVoteButton() {{
-            const btn = document.getElementById('vote-btn');
-            if (hasVoted) {{
-                btn.classList.add('voted');
-            }} else {{
-                btn.classList.remove('voted');
-            }}
-        }}
+fn render_compare_page(older: &RoastWithDetails, newer: &RoastWithDetails, diff: &RoastDiff) -> String {
+    let labels = labels(newer.language);
+    let column = |roast: &RoastWithDetails, sentences: &[DiffSentence], changed_class: &str| {
+        let date = roast
+            .created_at
+            .map(|d| d.format("%d %b %Y").to_string())
+            .unwrap_or_default();
+        let score = roast
+            .score
+            .map(|s| format!(" · ⭐ {:.1}", s.overall()))
+            .unwrap_or_default();
+        let body: String = sentences
+            .iter()
+            .map(|s| {
+                let class = if s.changed { changed_class } else { "" };
+                format!(
+                    r#"<span class="compare__sentence {class}">{text}</span> "#,
+                    class = class,
+                    text = escape_html(&s.text),
+                )
+            })
+            .collect();
+        format!(
+            r#"<div class="compare__column">
+                <a href="/r/{id}" class="compare__meta">📅 {date} · 🔥 {fire}{score}</a>
+                <div class="compare__text">{body}</div>
+            </div>"#,
+            id = roast.id,
+            date = date,
+            fire = roast.fire_count,
+            score = score,
+            body = body,
+        )
+    };
 
-        function toggleVote() {{
-            fetch('/api/roast/' + roastId + '/vote', {{ method: 'POST' }})
-                .then(r => r.json())
-                .then(data => {{
-                    if (data.success) {{
-                        hasVoted = data.voted;
-                        document.getElementById('fire-count').textContent = data.fire_count;
-                        updateVoteButton();
-                    }} else if (data.error === 'Must be logged in to vote') {{
-                        if (confirm('Kamu harus login untuk vote. Login dengan Google?')) {{
-                            window.location.href = '/auth/login';
-                        }}
-                    }}
-                }});
-        }}
-    </script>
+    format!(r#"<!DOCTYPE html>
+<html lang="{html_lang}">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>{compare_title}: {startup_name} - Roasting Startup</title>
+    <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
+    <style>{CSS}
+    .compare {{ display: grid; grid-template-columns: 1fr 1fr; gap: 1rem; margin-top: 1rem; }}
+    @media (max-width: 640px) {{ .compare {{ grid-template-columns: 1fr; }} }}
+    .compare__column {{ background: var(--surface); border-radius: 12px; padding: 1rem; }}
+    .compare__meta {{ display: block; color: var(--subtle); font-size: 0.85rem; margin-bottom: 0.75rem; text-decoration: none; }}
+    .compare__text {{ line-height: 1.7; }}
+    .compare__sentence--removed {{ background: rgba(180, 99, 122, 0.15); text-decoration: line-through; text-decoration-color: var(--love); }}
+    .compare__sentence--added {{ background: rgba(86, 148, 159, 0.18); }}
+    .compare__summary {{ color: var(--subtle); }}
+    </style>
+</head>
+<body>
+    <main class="container">
+        <div class="roast">
+            <h2 class="roast__title">{compare_title}: {startup_name}</h2>
+            <p class="compare__summary">{changed}</p>
+            <div class="compare">
+                {older_column}
+                {newer_column}
+            </div>
+            <div class="roast__actions">
+                <a href="/r/{newer_id}" class="roast__button--primary" style="text-decoration:none;display:inline-block;">{title_prefix}{startup_name}</a>
+            </div>
+        </div>
+    </main>
 </body>
-</html>"#, startup_name = startup_name, html_content = html_content, CSS = CSS, roast_id = roast_id)
+</html>"#,
+        html_lang = labels.html_lang,
+        compare_title = labels.compare_title,
+        title_prefix = labels.roast_title_prefix,
+        startup_name = escape_html(&newer.startup_name),
+        changed = labels
+            .compare_changed
+            .replace("{count}", &diff.changed_count().to_string()),
+        older_column = column(older, &diff.older, "compare__sentence--removed"),
+        newer_column = column(newer, &diff.newer, "compare__sentence--added"),
+        newer_id = newer.id,
+        CSS = CSS,
+    )
 }
 
-fn render_error_page(message: &str) -> String {
+fn render_job_page(job: &RoastJob) -> String {
+    let (title, message) = match job.status {
+        JobStatus::Deferred | JobStatus::Running => (
+            "⏳ Roast-mu lagi antri",
+            "Kami akan generate roast ini begitu AI tersedia lagi. Halaman ini akan refresh sendiri dan pindah ke hasilnya kalau sudah jadi.",
+        ),
+        JobStatus::Completed => ("✅ Roast-mu sudah jadi", "Roast sudah selesai dibuat."),
+        JobStatus::Failed => (
+            "❌ Roast gagal dibuat",
+            "Maaf, roast ini tetap gagal setelah beberapa kali dicoba. Silakan coba lagi dari awal.",
+        ),
+    };
+    let refresh = if job.status.is_finished() {
+        ""
+    } else {
+        r#"<meta http-equiv="refresh" content="30">"#
+    };
+
     format!(r#"<!DOCTYPE html>
 <html lang="id">
 <head>
     <meta charset="utf-8">
     <meta name="viewport" content="width=device-width, initial-scale=1">
-    <title>Error - Roasting Startup</title>
+    {refresh}
+    <title>Antrian Roast - Roasting Startup</title>
     <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
     <style>{CSS}</style>
 </head>
 <body>
     <main class="container">
         <div class="error">
-            <p class="error__title">Yah, error nih!</p>
+            <p class="error__title">{title}</p>
+            <p class="error__message"><code>{url}</code></p>
             <p class="error__message">{message}</p>
-            <a href="/" class="error__retry" style="text-decoration:none;display:inline-block;margin-top:1rem;">Coba Lagi</a>
+            <a href="/" class="error__retry" style="text-decoration:none;display:inline-block;margin-top:1rem;">Kembali</a>
         </div>
     </main>
 </body>
-</html>"#, message = message, CSS = CSS)
+</html>"#,
+        refresh = refresh,
+        title = title,
+        url = escape_html(&job.url),
+        message = message,
+        CSS = CSS,
+    )
+}
+
+/// Leaderboard link keeping the other filter
+fn leaderboard_href(safe_only: bool, country: Option<&str>) -> String {
+    let mut params = Vec::new();
+    if safe_only {
+        params.push("safe=true".to_string());
+    }
+    if let Some(country) = country {
+        params.push(format!("country={}", country));
+    }
+    if params.is_empty() {
+        "/leaderboard".to_string()
+    } else {
+        format!("/leaderboard?{}", params.join("&amp;"))
+    }
 }
 
-fn render_leaderboard_page(roasts: &[RoastWithDetails]) -> String {
+fn render_leaderboard_page(
+    roasts: &[RoastWithDetails],
+    safe_only: bool,
+    country: Option<&str>,
+    search: Option<&str>,
+    countries: &[CountryStats],
+    base_url: &str,
+) -> String {
+    let safe_toggle = if safe_only {
+        format!(
+            r#"<a href="{}" class="lb-filter lb-filter--active">👪 Mode aman keluarga · Tampilkan semua</a>"#,
+            leaderboard_href(false, country)
+        )
+    } else {
+        format!(
+            r#"<a href="{}" class="lb-filter">👪 Mode aman keluarga</a>"#,
+            leaderboard_href(true, country)
+        )
+    };
+    // Only worth filtering once roasts come from more than one country
+    let detected: Vec<&CountryStats> = countries.iter().filter(|c| c.country.is_some()).collect();
+    let country_filters = if detected.len() > 1 || country.is_some() {
+        let mut chips = vec![format!(
+            r#"<a href="{}" class="lb-filter{}">🌏 Semua negara</a>"#,
+            leaderboard_href(safe_only, None),
+            if country.is_none() { " lb-filter--active" } else { "" }
+        )];
+        for stats in detected {
+            let code = stats.country.as_deref().unwrap_or_default();
+            chips.push(format!(
+                r#"<a href="{}" class="lb-filter{}">{} ({})</a>"#,
+                leaderboard_href(safe_only, Some(code)),
+                if country == Some(code) { " lb-filter--active" } else { "" },
+                escape_html(country_name(code, Language::Id)),
+                stats.roasts
+            ));
+        }
+        format!(r#"<div class="lb-filters">{}</div>"#, chips.join(" "))
+    } else {
+        String::new()
+    };
+    // Filters stay applied while searching; the chips above start a fresh list
+    let search_form = format!(
+        r#"<form class="lb-search" method="get" action="/leaderboard">{safe}{country}<input type="search" name="q" value="{q}" maxlength="{max}" placeholder="Cari startup, target pasar, model harga..."><button type="submit">🔍</button></form>"#,
+        safe = if safe_only { r#"<input type="hidden" name="safe" value="true">"# } else { "" },
+        country = country
+            .map(|c| format!(r#"<input type="hidden" name="country" value="{}">"#, c))
+            .unwrap_or_default(),
+        q = escape_html(search.unwrap_or_default()),
+        max = MAX_SEARCH_CHARS,
+    );
     let mut cards = String::new();
     for (i, roast) in roasts.iter().enumerate() {
         let rank = i + 1;
-        let preview: String = roast.roast_text.chars().take(80).collect();
+        let preview_source = roast
+            .sections
+            .as_ref()
+            .map(|s| s.produk.as_str())
+            .unwrap_or(&roast.roast_text);
+        let preview: String = preview_source.chars().take(80).collect();
         let user_display = roast.author_name.as_deref().unwrap_or("Anonim");
+        let score_html = roast
+            .score
+            .map(|s| {
+                let details = s
+                    .entries(roast.language)
+                    .iter()
+                    .map(|(label, value)| format!("{}: {}", label, value))
+                    .collect::<Vec<_>>()
+                    .join(" · ");
+                format!(
+                    r#"<span class="lb-card__score" title="{details}">⭐ {overall:.1}</span>"#,
+                    details = details,
+                    overall = s.overall(),
+                )
+            })
+            .unwrap_or_default();
         let rank_class = match rank {
             1 => "lb-card__rank--gold",
             2 => "lb-card__rank--silver",
@@ -640,11 +5249,14 @@ fn render_leaderboard_page(roasts: &[RoastWithDetails]) -> String {
         cards.push_str(&format!(
             r#"<a href="/r/{id}" class="lb-card">
                 <div class="lb-card__rank {rank_class}">{rank}</div>
+                {logo_html}
                 <div class="lb-card__content">
                     <div class="lb-card__startup">{startup_name}</div>
                     <div class="lb-card__preview">{preview}...</div>
                     <div class="lb-card__meta">
                         <span class="lb-card__fire">🔥 {fire_count}</span>
+                        {score_html}
+                        {ai_badge}
                         <span class="lb-card__user">oleh {user_display}</span>
                     </div>
                 </div>
@@ -652,9 +5264,12 @@ fn render_leaderboard_page(roasts: &[RoastWithDetails]) -> String {
             id = roast.id,
             rank = rank,
             rank_class = rank_class,
+            logo_html = render_logo(&roast.startup_url, "lb-card__logo", 40),
             startup_name = roast.startup_name,
             preview = preview,
             fire_count = roast.fire_count,
+            score_html = score_html,
+            ai_badge = render_ai_wrapper_badge(roast.ai_wrapper_confidence, roast.language),
             user_display = user_display,
         ));
     }
@@ -665,6 +5280,7 @@ fn render_leaderboard_page(roasts: &[RoastWithDetails]) -> String {
     <meta charset="utf-8">
     <meta name="viewport" content="width=device-width, initial-scale=1">
     <title>Leaderboard - Roasting Startup</title>
+    {json_ld}
     <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
     <style>{CSS}
     .lb-page {{ padding: 1rem 0; }}
@@ -676,6 +5292,27 @@ fn render_leaderboard_page(roasts: &[RoastWithDetails]) -> String {
         margin-bottom: 1.5rem;
     }}
     @media (min-width: 640px) {{ .lb-title {{ font-size: 2rem; margin-bottom: 2rem; }} }}
+    .lb-filters {{ text-align: center; margin-bottom: 1rem; }}
+    .lb-filter {{
+        display: inline-block;
+        padding: 0.35rem 0.85rem;
+        border: 2px solid var(--overlay);
+        border-radius: 999px;
+        color: var(--subtle);
+        font-size: 0.85rem;
+        text-decoration: none;
+    }}
+    .lb-filter--active {{ border-color: var(--pine); color: var(--pine); }}
+    .lb-search {{ display: flex; gap: 0.5rem; max-width: 28rem; margin: 0 auto 1rem; }}
+    .lb-search input {{
+        flex: 1;
+        padding: 0.5rem 0.85rem;
+        border: 2px solid var(--overlay);
+        border-radius: 999px;
+        background: var(--surface);
+        color: var(--text);
+    }}
+    .lb-search button {{ padding: 0 0.9rem; border: 2px solid var(--overlay); border-radius: 999px; background: var(--surface); cursor: pointer; }}
     .lb-list {{
         display: flex;
         flex-direction: column;
@@ -717,6 +5354,14 @@ fn render_leaderboard_page(roasts: &[RoastWithDetails]) -> String {
     .lb-card__rank--gold {{ background: var(--gold); color: #fff; }}
     .lb-card__rank--silver {{ background: #a0a0a0; color: #fff; }}
     .lb-card__rank--bronze {{ background: #cd7f32; color: #fff; }}
+    .lb-card__logo {{
+        flex-shrink: 0;
+        width: 40px;
+        height: 40px;
+        border-radius: 10px;
+        object-fit: contain;
+        background: var(--surface);
+    }}
     .lb-card__content {{
         flex: 1;
         min-width: 0;
@@ -754,6 +5399,11 @@ fn render_leaderboard_page(roasts: &[RoastWithDetails]) -> String {
         font-size: 0.9rem;
         color: var(--gold);
     }}
+    .lb-card__score {{
+        font-weight: 600;
+        font-size: 0.85rem;
+        color: var(--pine);
+    }}
     .lb-card__user {{
         font-size: 0.8rem;
         color: var(--muted);
@@ -777,11 +5427,83 @@ fn render_leaderboard_page(roasts: &[RoastWithDetails]) -> String {
     <main class="container">
         <div class="lb-page">
             <h1 class="lb-title">🔥 Leaderboard Roasting 🔥</h1>
+            <div class="lb-filters">{safe_toggle}</div>
+            {country_filters}
+            {search_form}
             <div class="lb-list">
                 {cards}
             </div>
             <div class="lb-actions">
                 <a href="/" class="roast__button--primary">Roast Startup Lain!</a>
+                <a href="/wall" class="lb-filter">🧱 Wall of Shame</a>
+            </div>
+        </div>
+    </main>
+</body>
+</html>"#,
+        CSS = CSS,
+        cards = cards,
+        safe_toggle = safe_toggle,
+        country_filters = country_filters,
+        search_form = search_form,
+        json_ld = render_json_ld(leaderboard_json_ld(base_url, roasts)),
+    )
+}
+
+fn render_moderation_page(stats: &ModerationStats) -> String {
+    let cards = [
+        (stats.removed_roasts, "Roast dihapus", "Total sepanjang masa"),
+        (stats.removed_after_reports, "Dihapus karena laporan", "Setelah laporan ditinjau admin"),
+        (stats.takedowns_honored, "Permintaan takedown", "Dari startup yang di-roast, dikabulkan"),
+        (stats.removed_last_30_days, "Dihapus 30 hari terakhir", "Semua alasan"),
+        (stats.pending_reports, "Laporan menunggu", "Belum ditinjau"),
+        (stats.dismissed_reports, "Laporan ditolak", "Ditinjau, roast tetap tayang"),
+    ]
+    .iter()
+    .map(|(count, label, note)| {
+        format!(
+            r#"<div class="moderation__card">
+                <div class="moderation__count">{count}</div>
+                <div class="moderation__label">{label}</div>
+                <div class="moderation__note">{note}</div>
+            </div>"#,
+            count = count,
+            label = label,
+            note = note,
+        )
+    })
+    .collect::<Vec<_>>()
+    .join("\n");
+
+    format!(r#"<!DOCTYPE html>
+<html lang="id">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>Transparansi Moderasi - Roasting Startup</title>
+    <meta name="description" content="Berapa roast yang dihapus, takedown yang dikabulkan, dan laporan yang menunggu ditinjau.">
+    <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
+    <style>{CSS}
+    .moderation {{ padding: 1rem 0; }}
+    .moderation__heading {{ color: var(--love); font-size: 1.75rem; font-weight: 800; text-align: center; }}
+    .moderation__intro {{ text-align: center; color: var(--subtle); margin: 0.5rem 0 1.5rem; line-height: 1.6; }}
+    .moderation__grid {{ display: grid; grid-template-columns: repeat(auto-fit, minmax(180px, 1fr)); gap: 1rem; }}
+    .moderation__card {{ background: var(--surface); border-radius: 12px; padding: 1.25rem; text-align: center; }}
+    .moderation__count {{ color: var(--pine); font-size: 2rem; font-weight: 800; }}
+    .moderation__label {{ font-weight: 700; margin-top: 0.25rem; }}
+    .moderation__note {{ color: var(--muted); font-size: 0.8rem; margin-top: 0.25rem; }}
+    </style>
+</head>
+<body>
+    <main class="container">
+        <div class="moderation">
+            <h1 class="moderation__heading">⚖️ Transparansi Moderasi</h1>
+            <p class="moderation__intro">Roast hanya dihapus setelah laporan ditinjau admin atau atas permintaan takedown dari startup yang bersangkutan. Angka di bawah dihitung langsung dari catatan moderasi, tanpa data pribadi pelapor maupun penulis.</p>
+            <div class="moderation__grid">
+                {cards}
+            </div>
+            <div style="text-align:center;margin-top:2rem;">
+                <a href="/" class="roast__button--secondary" style="text-decoration:none;display:inline-block;">Kembali</a>
             </div>
         </div>
     </main>
@@ -789,6 +5511,281 @@ fn render_leaderboard_page(roasts: &[RoastWithDetails]) -> String {
 </html>"#, CSS = CSS, cards = cards)
 }
 
+fn render_metrics_page(routes: &[RouteStats], sample_rate: f64, load: &LoadStats) -> String {
+    let limit = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_else(|| "off".to_string());
+    let load_line = format!(
+        "Generasi berjalan {in_flight}/{max_in_flight} · p95 {p95} ms (batas {max_p95}) · {state} · {shed} ditolak, {retries} retry masuk",
+        in_flight = load.in_flight,
+        max_in_flight = limit((load.max_in_flight > 0).then_some(load.max_in_flight as u64)),
+        p95 = load.p95_ms.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string()),
+        max_p95 = limit(load.max_p95_ms),
+        state = if load.overloaded { "🔴 menolak anonim" } else { "🟢 normal" },
+        shed = load.shed_total,
+        retries = load.retries_admitted,
+    );
+
+    let rows = if routes.is_empty() {
+        r#"<tr><td colspan="7" class="metrics__empty">Belum ada data dalam 24 jam terakhir.</td></tr>"#
+            .to_string()
+    } else {
+        routes
+            .iter()
+            .map(|r| {
+                let error_class = if r.error_rate >= 0.05 { " metrics__cell--bad" } else { "" };
+                format!(
+                    r#"<tr>
+                <td><code>{method} {route}</code></td>
+                <td>{requests}</td>
+                <td>{avg:.0}</td>
+                <td>{p95:.0}</td>
+                <td>{max}</td>
+                <td class="metrics__cell{error_class}">{errors:.1}%</td>
+                <td>{client_errors:.1}%</td>
+            </tr>"#,
+                    method = escape_html(&r.method),
+                    route = escape_html(&r.route),
+                    requests = r.requests,
+                    avg = r.avg_ms,
+                    p95 = r.p95_ms,
+                    max = r.max_ms,
+                    error_class = error_class,
+                    errors = r.error_rate * 100.0,
+                    client_errors = r.client_error_rate * 100.0,
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    format!(r#"<!DOCTYPE html>
+<html lang="id">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>Metrics - Roasting Startup</title>
+    <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
+    <style>{CSS}
+    .container {{ max-width: 960px; }}
+    .metrics {{ padding: 1rem 0; }}
+    .metrics__heading {{ color: var(--love); font-size: 1.75rem; font-weight: 800; text-align: center; }}
+    .metrics__note {{ text-align: center; color: var(--muted); font-size: 0.85rem; margin-bottom: 1.5rem; }}
+    .metrics__table {{ width: 100%; border-collapse: collapse; background: var(--surface); border-radius: 12px; overflow: hidden; font-size: 0.85rem; }}
+    .metrics__table th, .metrics__table td {{ padding: 0.6rem 0.75rem; text-align: right; border-bottom: 1px solid var(--overlay); }}
+    .metrics__table th:first-child, .metrics__table td:first-child {{ text-align: left; }}
+    .metrics__table th {{ color: var(--pine); font-weight: 700; }}
+    .metrics__cell--bad {{ color: var(--love); font-weight: 700; }}
+    .metrics__empty {{ text-align: center !important; color: var(--muted); font-style: italic; padding: 2rem !important; }}
+    </style>
+</head>
+<body>
+    <main class="container">
+        <div class="metrics">
+            <h1 class="metrics__heading">📈 Route Metrics</h1>
+            <p class="metrics__note">24 jam terakhir · sampling {sample_pct:.0}% request · diurutkan dari p95 terlambat</p>
+            <p class="metrics__note">{load_line}</p>
+            <table class="metrics__table">
+                <thead>
+                    <tr>
+                        <th>Route</th>
+                        <th>Sampel</th>
+                        <th>Avg (ms)</th>
+                        <th>p95 (ms)</th>
+                        <th>Max (ms)</th>
+                        <th>5xx</th>
+                        <th>4xx</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {rows}
+                </tbody>
+            </table>
+        </div>
+    </main>
+</body>
+</html>"#, CSS = CSS, sample_pct = sample_rate * 100.0, load_line = load_line, rows = rows)
+}
+
+fn render_wall_page(tiles: &[WallTile], page: u64, pages: u64, total: u64) -> String {
+    let grid = if tiles.is_empty() {
+        r#"<p class="wall__empty">Belum ada startup yang di-roast.</p>"#.to_string()
+    } else {
+        tiles
+            .iter()
+            .map(|tile| {
+                let initial = tile
+                    .startup_name
+                    .chars()
+                    .find(|c| c.is_alphanumeric())
+                    .unwrap_or('?')
+                    .to_uppercase();
+                // Stored logos, or the startup's favicon without storage; a missing one falls back to the initial
+                format!(
+                    r#"<a href="/r/{id}" class="wall__tile" title="{name} · 🔥 {fires}">
+                <span class="wall__logo" data-initial="{initial}"><img src="/logos/{domain}" alt="" width="48" height="48" loading="lazy" decoding="async" referrerpolicy="no-referrer" onerror="this.remove()"></span>
+                <span class="wall__name">{name}</span>
+            </a>"#,
+                    id = tile.roast_id,
+                    name = escape_html(&tile.startup_name),
+                    fires = tile.fire_count,
+                    initial = escape_html(&initial.to_string()),
+                    domain = escape_html(&tile.domain),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let prev = if page > 1 {
+        format!(r#"<a href="/wall?page={}" class="lb-filter">← Sebelumnya</a>"#, page - 1)
+    } else {
+        String::new()
+    };
+    let next = if page < pages {
+        format!(r#"<a href="/wall?page={}" class="lb-filter">Berikutnya →</a>"#, page + 1)
+    } else {
+        String::new()
+    };
+
+    format!(r#"<!DOCTYPE html>
+<html lang="id">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>Wall of Shame - Roasting Startup</title>
+    <meta property="og:title" content="Wall of Shame - {total} startup sudah di-roast">
+    <meta property="og:description" content="Semua startup yang pernah dihancurkan di Roasting Startup">
+    <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
+    <style>{CSS}
+    .wall {{ padding: 1rem 0; }}
+    .wall__heading {{ color: var(--love); font-size: 1.75rem; font-weight: 800; text-align: center; margin-bottom: 0.25rem; }}
+    .wall__count {{ text-align: center; color: var(--muted); margin-bottom: 1.5rem; }}
+    .wall__grid {{ display: grid; grid-template-columns: repeat(auto-fill, minmax(96px, 1fr)); gap: 0.75rem; }}
+    .wall__tile {{
+        display: flex;
+        flex-direction: column;
+        align-items: center;
+        gap: 0.4rem;
+        padding: 0.75rem 0.5rem;
+        background: var(--surface);
+        border: 2px solid var(--overlay);
+        border-radius: 12px;
+        text-decoration: none;
+        color: inherit;
+        transition: all 0.2s ease;
+    }}
+    .wall__tile:hover {{ border-color: var(--love); transform: translateY(-2px); }}
+    .wall__logo {{
+        position: relative;
+        width: 48px;
+        height: 48px;
+        border-radius: 10px;
+        background: var(--overlay);
+        overflow: hidden;
+    }}
+    .wall__logo::before {{
+        content: attr(data-initial);
+        position: absolute;
+        inset: 0;
+        display: flex;
+        align-items: center;
+        justify-content: center;
+        font-weight: 800;
+        font-size: 1.4rem;
+        color: var(--subtle);
+    }}
+    .wall__logo img {{ position: relative; width: 100%; height: 100%; object-fit: contain; background: var(--surface); }}
+    .wall__name {{ font-size: 0.75rem; text-align: center; overflow: hidden; text-overflow: ellipsis; white-space: nowrap; max-width: 100%; }}
+    .wall__empty {{ text-align: center; padding: 3rem 1rem; color: var(--muted); font-style: italic; }}
+    .wall__pager {{ display: flex; justify-content: center; align-items: center; gap: 0.75rem; margin-top: 1.5rem; color: var(--muted); }}
+    </style>
+</head>
+<body>
+    <main class="container">
+        <div class="wall">
+            <h1 class="wall__heading">🧱 Wall of Shame</h1>
+            <p class="wall__count">{total} startup sudah di-roast</p>
+            <div class="wall__grid">
+            {grid}
+            </div>
+            <nav class="wall__pager">{prev}<span>{page} / {pages}</span>{next}</nav>
+            <div class="lb-actions" style="text-align:center;margin-top:1.5rem;">
+                <a href="/" class="roast__button--primary">Roast Startup!</a>
+            </div>
+        </div>
+    </main>
+</body>
+</html>"#, CSS = CSS, total = total, grid = grid, prev = prev, next = next, page = page, pages = pages)
+}
+
+fn render_changelog_page(announcements: &[Announcement]) -> String {
+    let entries = if announcements.is_empty() {
+        r#"<p class="changelog__empty">Belum ada pengumuman.</p>"#.to_string()
+    } else {
+        announcements
+            .iter()
+            .map(|a| {
+                format!(
+                    r#"<article class="changelog__entry">
+                <time class="changelog__date">{date}</time>
+                <h2 class="changelog__title">{title}</h2>
+                <div class="changelog__body">{body}</div>
+            </article>"#,
+                    date = a
+                        .created_at
+                        .map(|d| d.format("%d %b %Y").to_string())
+                        .unwrap_or_default(),
+                    title = escape_html(&a.title),
+                    body = simple_markdown_to_html(&escape_html(&a.body)),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    format!(r#"<!DOCTYPE html>
+<html lang="id">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>Changelog - Roasting Startup</title>
+    <link rel="icon" href="data:image/svg+xml,<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'><text y='.9em' font-size='90'>🔥</text></svg>">
+    <style>{CSS}
+    .changelog {{ padding: 1rem 0; }}
+    .changelog__heading {{ color: var(--love); font-size: 1.75rem; font-weight: 800; text-align: center; margin-bottom: 1.5rem; }}
+    .changelog__entry {{
+        background: var(--surface);
+        border: 2px solid var(--overlay);
+        border-radius: 12px;
+        padding: 1.25rem;
+        margin-bottom: 1rem;
+    }}
+    .changelog__date {{ font-size: 0.8rem; color: var(--muted); }}
+    .changelog__title {{ font-size: 1.15rem; color: var(--pine); margin: 0.25rem 0 0.5rem; }}
+    .changelog__body p {{ margin-bottom: 0.5rem; }}
+    .changelog__empty {{ text-align: center; padding: 3rem 1rem; color: var(--muted); font-style: italic; }}
+    </style>
+</head>
+<body>
+    <main class="container">
+        <div class="changelog">
+            <h1 class="changelog__heading">📣 Changelog</h1>
+            {entries}
+            <div class="lb-actions" style="text-align:center;margin-top:1.5rem;">
+                <a href="/" class="roast__button--primary">Roast Startup!</a>
+            </div>
+        </div>
+    </main>
+</body>
+</html>"#, CSS = CSS, entries = entries)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
 fn simple_markdown_to_html(text: &str) -> String {
     let mut result = String::new();
     for line in text.lines() {
@@ -877,6 +5874,15 @@ body {
     border-bottom: 2px solid var(--overlay);
 }
 @media (min-width: 640px) { .roast__title { font-size: 1.5rem; } }
+.roast__logo {
+    display: block;
+    width: 48px;
+    height: 48px;
+    margin-bottom: 0.75rem;
+    border-radius: 12px;
+    object-fit: contain;
+    background: var(--surface);
+}
 .roast__content {
     color: var(--text);
     line-height: 1.9;
@@ -890,6 +5896,14 @@ body {
 .roast__content h3 { font-size: 1.15rem; color: var(--pine); margin: 1.25rem 0 0.5rem; font-weight: 600; }
 .roast__content h4 { font-size: 1.05rem; color: var(--subtle); margin: 1rem 0 0.5rem; font-weight: 600; }
 .roast__content li { margin-left: 1.5rem; margin-bottom: 0.5rem; list-style: disc; }
+.roast__section { margin-bottom: 1.25rem; }
+.roast__section:last-child { margin-bottom: 0; }
+.roast__section-title { font-size: 0.8rem; font-weight: 700; text-transform: uppercase; letter-spacing: 0.05em; color: var(--love); margin-bottom: 0.35rem; }
+.roast__dedication { color: var(--subtle); font-size: 0.95rem; margin-bottom: 0.5rem; }
+.roast__dedication strong { color: var(--love); }
+.roast__schedule { margin-top: 1rem; color: var(--subtle); font-size: 0.9rem; }
+.roast__schedule input { margin-right: 0.5rem; }
+.roast__button--whatsapp { display: inline-block; padding: 0.5rem 1rem; border-radius: 9999px; background: #25d366; color: #fff; font-weight: 600; text-decoration: none; }
 .roast__actions {
     display: flex;
     flex-wrap: wrap;
@@ -948,6 +5962,83 @@ body {
 .roast__vote-btn:hover { border-color: var(--gold); background: #fff8ed; }
 .roast__vote-btn.voted { background: #fff8ed; border-color: var(--gold); color: var(--gold); }
 .roast__vote-btn .fire-emoji { font-size: 1.2rem; }
+.ai-badge {
+    display: inline-block; padding: 0.15rem 0.6rem; border-radius: 999px;
+    background: var(--overlay); color: var(--foam); font-size: 0.75rem; font-weight: 700;
+}
+.roast .ai-badge { margin-bottom: 1rem; }
+.founder {
+    margin-top: 1.25rem;
+    padding: 1rem;
+    border: 2px dashed var(--pine);
+    border-radius: 12px;
+}
+.founder__title { font-size: 0.8rem; font-weight: 700; text-transform: uppercase; letter-spacing: 0.05em; color: var(--pine); margin-bottom: 0.75rem; }
+.founder__stats { display: flex; gap: 1.5rem; margin-bottom: 0.75rem; }
+.founder__stat strong { display: block; font-size: 1.4rem; color: var(--text); }
+.founder__stat span { font-size: 0.8rem; color: var(--subtle); }
+.founder__chart { display: flex; align-items: flex-end; gap: 2px; height: 4rem; margin-bottom: 0.75rem; }
+.founder__chart span { flex: 1; background: var(--pine); border-radius: 2px 2px 0 0; }
+.founder__label { font-size: 0.8rem; font-weight: 700; color: var(--subtle); margin-bottom: 0.25rem; }
+.founder__referrers li { font-size: 0.85rem; color: var(--text); list-style: none; }
+.fact-sheet { margin-top: 1.25rem; padding: 0.75rem 1rem; border: 2px solid var(--overlay); border-radius: 10px; }
+.fact-sheet summary { cursor: pointer; font-weight: 700; color: var(--love); }
+.fact-sheet dl { display: grid; grid-template-columns: max-content 1fr; gap: 0.35rem 1rem; margin-top: 0.75rem; font-size: 0.9rem; }
+.fact-sheet dt { color: var(--subtle); }
+.fact-sheet dd { margin: 0; color: var(--text); }
+.roast__screenshot { margin-top: 1.25rem; }
+.roast__screenshot figcaption { font-size: 0.85rem; color: var(--subtle); margin-bottom: 0.4rem; }
+.roast__screenshot-frame { max-height: 420px; overflow-y: auto; border: 2px solid var(--overlay); border-radius: 10px; }
+.roast__screenshot-frame img { display: block; width: 100%; height: auto; }
+.social-links { margin-top: 1rem; font-size: 0.9rem; color: var(--subtle); }
+.social-links__title { font-weight: 700; color: var(--love); }
+.social-links a { color: var(--pine); text-decoration: none; }
+.social-links a:hover { text-decoration: underline; }
+.scorecard {
+    margin-top: 1.25rem;
+    padding: 1rem;
+    border: 2px solid var(--overlay);
+    border-radius: 12px;
+}
+.scorecard__title { font-size: 0.8rem; font-weight: 700; text-transform: uppercase; letter-spacing: 0.05em; color: var(--love); margin-bottom: 0.75rem; }
+.scorecard__row { display: flex; align-items: center; gap: 0.75rem; margin-bottom: 0.5rem; font-size: 0.9rem; }
+.scorecard__label { flex: 0 0 9rem; color: var(--subtle); }
+.scorecard__bar { flex: 1; height: 0.5rem; background: var(--overlay); border-radius: 9999px; overflow: hidden; }
+.scorecard__fill { height: 100%; background: var(--love); border-radius: 9999px; }
+.scorecard__value { flex: 0 0 3rem; text-align: right; font-weight: 600; color: var(--pine); }
+.scorecard__overall { margin-top: 0.75rem; color: var(--subtle); }
+.battle {
+    display: flex;
+    flex-wrap: wrap;
+    align-items: center;
+    gap: 0.75rem;
+    margin-top: 1.25rem;
+    padding: 1rem;
+    background: var(--overlay);
+    border-radius: 12px;
+}
+.battle__prompt { flex-basis: 100%; font-weight: 600; color: var(--subtle); }
+.battle__opponent { flex: 1; font-weight: 700; color: var(--pine); text-decoration: none; }
+.follow-up { margin-top: 1.25rem; padding-top: 1rem; border-top: 2px dashed var(--overlay); }
+.follow-up__item { margin-bottom: 1rem; }
+.follow-up__question { font-style: italic; color: var(--subtle); margin-bottom: 0.35rem; }
+.follow-up__form { display: flex; gap: 0.5rem; }
+.follow-up__input { flex: 1; padding: 0.6rem 0.9rem; border: 2px solid var(--overlay); border-radius: 9999px; font: inherit; }
+.spice { margin-top: 1.25rem; padding-top: 1rem; border-top: 2px dashed var(--overlay); }
+.spice__options { display: flex; gap: 0.5rem; flex-wrap: wrap; margin-bottom: 0.75rem; }
+.spice__option { cursor: pointer; padding: 0.4rem 0.9rem; border: 2px solid var(--overlay); border-radius: 9999px; background: transparent; font: inherit; color: var(--text); }
+.spice__option--chosen { border-color: var(--love); color: var(--love); font-weight: 700; }
+.spice__bar { display: flex; height: 0.6rem; border-radius: 9999px; overflow: hidden; background: var(--overlay); }
+.spice__segment { display: block; height: 100%; transition: width 0.3s; }
+.spice__segment--too_mild { background: var(--foam); }
+.spice__segment--just_right { background: var(--gold); }
+.spice__segment--too_spicy { background: var(--love); }
+.spice__total { color: var(--subtle); font-size: 0.85rem; margin-top: 0.35rem; }
+.invite { text-align: center; padding: 2rem 1rem; }
+.invite__title { color: var(--love); font-weight: 800; font-size: 1.5rem; margin-bottom: 0.5rem; }
+.invite__message { color: var(--subtle); line-height: 1.6; margin-bottom: 1.25rem; }
+.invite__form { display: flex; gap: 0.5rem; justify-content: center; flex-wrap: wrap; }
+.invite__input { padding: 0.6rem 0.9rem; border: 2px solid var(--overlay); border-radius: 9999px; font: inherit; text-transform: uppercase; }
 .error {
     background: #fef2f4;
     border: 2px solid var(--love);
@@ -971,6 +6062,10 @@ body {
     transition: all 0.2s ease;
 }
 .error__retry:hover { background: #a3566a; }
+.error__trace { margin-top: 1rem; font-size: 0.85rem; color: var(--subtle); }
+.error__trace a { color: var(--love); }
+.problem-form { display: flex; flex-direction: column; gap: 0.75rem; }
+.problem-form textarea { min-height: 120px; padding: 0.75rem; border: 2px solid var(--overlay); border-radius: 8px; font: inherit; }
 "#;
 
 fn shell(_options: LeptosOptions) -> impl IntoView {
@@ -1008,7 +6103,18 @@ fn shell(_options: LeptosOptions) -> impl IntoView {
             border-radius: 8px; background: var(--surface); color: var(--text); font-size: 1rem;
         }
         .url-form__input:focus { outline: none; border-color: var(--pine); }
+        .url-form__select {
+            padding: 0.75rem 1rem; border: 2px solid var(--overlay); border-radius: 8px;
+            background: var(--surface); color: var(--text); font-size: 1rem;
+        }
         .url-form__input::placeholder { color: var(--muted); }
+        .url-form__checkbox {
+            display: flex; align-items: center; gap: 0.5rem;
+            color: var(--subtle); font-size: 0.9rem; cursor: pointer;
+        }
+        .url-form__idea { display: flex; flex-direction: column; gap: 0.5rem; }
+        .url-form__idea summary { color: var(--subtle); font-size: 0.9rem; cursor: pointer; }
+        .url-form__textarea { min-height: 8rem; resize: vertical; }
         .url-form__button {
             padding: 1rem 2rem; background: var(--love); color: var(--base);
             border: none; border-radius: 8px; font-size: 1rem; font-weight: 600; cursor: pointer;
@@ -1034,12 +6140,41 @@ fn shell(_options: LeptosOptions) -> impl IntoView {
         .roast__content h3 { font-size: 1.2rem; color: var(--pine); margin: 1rem 0 0.5rem; }
         .roast__content h4 { font-size: 1.1rem; color: var(--subtle); margin: 0.75rem 0 0.5rem; }
         .roast__content li { margin-left: 1.5rem; margin-bottom: 0.5rem; list-style: disc; }
+        .roast__section { margin-bottom: 1.25rem; }
+        .roast__section:last-child { margin-bottom: 0; }
+        .roast__section-title { font-size: 0.8rem; font-weight: 700; text-transform: uppercase; letter-spacing: 0.05em; color: var(--love); margin-bottom: 0.35rem; }
+        .ai-badge {
+            display: inline-block; padding: 0.15rem 0.6rem; border-radius: 999px;
+            background: var(--overlay); color: var(--foam); font-size: 0.75rem; font-weight: 700;
+        }
+        .roast .ai-badge { margin-bottom: 1rem; }
+        .scorecard {
+            margin-top: 1.25rem;
+            padding: 1rem;
+            border: 2px solid var(--overlay);
+            border-radius: 12px;
+        }
+        .scorecard__title { font-size: 0.8rem; font-weight: 700; text-transform: uppercase; letter-spacing: 0.05em; color: var(--love); margin-bottom: 0.75rem; }
+        .scorecard__row { display: flex; align-items: center; gap: 0.75rem; margin-bottom: 0.5rem; font-size: 0.9rem; }
+        .scorecard__label { flex: 0 0 9rem; color: var(--subtle); }
+        .scorecard__bar { flex: 1; height: 0.5rem; background: var(--overlay); border-radius: 9999px; overflow: hidden; }
+        .scorecard__fill { height: 100%; background: var(--love); border-radius: 9999px; }
+        .scorecard__value { flex: 0 0 3rem; text-align: right; font-weight: 600; color: var(--pine); }
+        .scorecard__overall { margin-top: 0.75rem; color: var(--subtle); }
         .roast__actions { margin-top: 1.5rem; padding-top: 1rem; border-top: 2px solid var(--overlay); }
         .roast__button--primary { padding: 0.75rem 1.5rem; background: var(--pine); color: var(--base); border: none; border-radius: 8px; font-weight: 600; cursor: pointer; }
         .error { background: #fce8ec; border: 2px solid var(--love); border-radius: 8px; padding: 1.25rem; margin: 2rem 0; }
         .error__title { color: var(--love); font-weight: 700; margin-bottom: 0.5rem; }
         .error__message { color: #8b3d4d; }
         .error__retry { margin-top: 1rem; padding: 0.5rem 1rem; background: var(--love); color: var(--base); border: none; border-radius: 4px; cursor: pointer; }
+        .announcement-banner {
+            display: flex; align-items: flex-start; gap: 0.75rem; padding: 0.75rem 1rem;
+            background: var(--overlay); border-left: 4px solid var(--gold); border-radius: 8px; margin-bottom: 1rem;
+        }
+        .announcement-banner__content { flex: 1; display: flex; flex-wrap: wrap; gap: 0.5rem; align-items: baseline; }
+        .announcement-banner__title { color: var(--pine); }
+        .announcement-banner__link { color: var(--love); font-size: 0.9rem; }
+        .announcement-banner__dismiss { background: none; border: none; font-size: 1.25rem; color: var(--muted); cursor: pointer; }
         .footer { text-align: center; padding: 2rem 0; color: var(--muted); font-size: 0.9rem; border-top: 1px solid var(--overlay); margin-top: 3rem; }
     "#;
 
@@ -1047,6 +6182,7 @@ fn shell(_options: LeptosOptions) -> impl IntoView {
         document.addEventListener('DOMContentLoaded', function() {
             const form = document.querySelector('.url-form');
             const input = document.querySelector('.url-form__input');
+            const idea = document.querySelector('.url-form__textarea');
             const button = document.querySelector('.url-form__button');
             const originalText = button.textContent;
 
@@ -1060,7 +6196,8 @@ fn shell(_options: LeptosOptions) -> impl IntoView {
             }
 
             function updateButton() {
-                const isValid = validateUrl(input.value.trim());
+                const isValid = validateUrl(input.value.trim())
+                    || (idea !== null && idea.value.trim().length >= 30);
                 button.disabled = !isValid;
             }
 
@@ -1072,6 +6209,9 @@ fn shell(_options: LeptosOptions) -> impl IntoView {
 
             input.addEventListener('input', updateButton);
             input.addEventListener('change', updateButton);
+            if (idea) {
+                idea.addEventListener('input', updateButton);
+            }
             updateButton();
         });
     "#;
@@ -1094,3 +6234,46 @@ fn shell(_options: LeptosOptions) -> impl IntoView {
         </html>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use roasting_app::domain::StartupCategory;
+
+    #[test]
+    fn test_battle_card_escapes_opponent_name() {
+        let battle = BattleSuggestion {
+            opponent_id: Uuid::nil(),
+            opponent_name: "<script>alert(1)</script>".to_string(),
+            opponent_category: StartupCategory::Other,
+            similarity: 0.9,
+            same_category: true,
+            score: 0.9,
+        };
+        let html = render_result_page_with_id(
+            "Kopi Nusantara",
+            "<p>roast</p>",
+            None,
+            Uuid::nil(),
+            Language::Id,
+            None,
+            Some(&battle),
+        );
+        assert!(!html.contains("<script>alert(1)</script>"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+    }
+
+    #[test]
+    fn test_follow_up_answer_is_escaped() {
+        let follow_up = RoastFollowUp {
+            id: Uuid::nil(),
+            roast_id: Uuid::nil(),
+            question: "Gimana pricing-nya?".to_string(),
+            answer: "Mahal banget <script>alert(1)</script>".to_string(),
+            created_at: None,
+        };
+        let html = render_follow_ups(Uuid::nil(), &[follow_up], Language::Id);
+        assert!(!html.contains("<script>alert(1)"));
+        assert!(html.contains("<p>Mahal banget &lt;script&gt;alert(1)&lt;/script&gt;</p>"));
+    }
+}
@@ -1,19 +1,45 @@
-use crate::application::GenerateRoast;
-use crate::infrastructure::security::{CostTracker, RateLimiter};
+use crate::application::{GenerateRoast, SuggestBattleOpponent};
+#[cfg(feature = "ssr")]
+use crate::application::{
+    ApplyRetention, AskFollowUp, BanIp, DetectSiteChanges, NotifyUser, OptOutDomain, ReportScrapeHealth, RetentionPolicy, RoastQueue,
+    ScheduleRoast, ToggleVote, TrackRoastAnalytics, WarmPopularScrapes, Warmup,
+};
+use crate::infrastructure::prompt_templates::PromptTemplates;
+use crate::infrastructure::security::{CostTracker, LoadShedder, RateLimiter, TrustedProxies};
 use std::sync::Arc;
 
 #[cfg(feature = "ssr")]
 use crate::infrastructure::auth::GoogleOAuth;
 #[cfg(feature = "ssr")]
-use crate::infrastructure::db::{RoastRepository, UserRepository, VoteRepository};
+use crate::infrastructure::db::{
+    AnnouncementRepository, DailyUsageRepository, FollowUpRepository, FounderClaimRepository, ImpersonationRepository, InviteRepository, IpBanRepository, ModerationRepository,
+    NotificationRepository, OptOutRepository, ProblemReportRepository, PushSubscriptionRepository, RequestLogRepository, RetentionRepository, RoastCacheRepository, RoastJobRepository, ScrapeCacheRepository,
+    RoastRepository, RoastStatsRepository, ScrapeLogRepository, SiteFingerprintRepository, SpiceVoteRepository, UserRepository, VoteRepository, WatchRepository,
+};
+#[cfg(feature = "ssr")]
+use crate::infrastructure::cache_purger::CachePurger;
+#[cfg(feature = "ssr")]
+use crate::infrastructure::logo_store::LogoStore;
+#[cfg(feature = "ssr")]
+use crate::infrastructure::notification::WebPushChannel;
+#[cfg(feature = "ssr")]
+use crate::infrastructure::metrics::{RequestMetrics, ScrapeMetrics};
+#[cfg(feature = "ssr")]
+use crate::infrastructure::sticker::StickerRenderer;
 #[cfg(feature = "ssr")]
 use sea_orm::DatabaseConnection;
 
 #[derive(Clone)]
 pub struct AppContext {
     pub generate_roast: Arc<GenerateRoast>,
+    pub suggest_battle_opponent: Arc<SuggestBattleOpponent>,
     pub rate_limiter: RateLimiter,
+    /// Proxies whose forwarded-for headers name the real client (TRUSTED_PROXIES)
+    pub trusted_proxies: Arc<TrustedProxies>,
+    /// Turns anonymous submissions away while generation is overloaded
+    pub load_shedder: Arc<LoadShedder>,
     pub cost_tracker: Arc<CostTracker>,
+    pub prompt_templates: Arc<PromptTemplates>,
     #[cfg(feature = "ssr")]
     pub db: DatabaseConnection,
     #[cfg(feature = "ssr")]
@@ -24,31 +50,253 @@ pub struct AppContext {
     pub roast_repo: RoastRepository,
     #[cfg(feature = "ssr")]
     pub vote_repo: VoteRepository,
+    #[cfg(feature = "ssr")]
+    pub toggle_vote: Arc<ToggleVote>,
+    #[cfg(feature = "ssr")]
+    pub spice_vote_repo: SpiceVoteRepository,
+    #[cfg(feature = "ssr")]
+    pub announcement_repo: AnnouncementRepository,
+    #[cfg(feature = "ssr")]
+    pub moderation_repo: ModerationRepository,
+    #[cfg(feature = "ssr")]
+    pub problem_report_repo: ProblemReportRepository,
+    #[cfg(feature = "ssr")]
+    pub roast_job_repo: RoastJobRepository,
+    #[cfg(feature = "ssr")]
+    pub roast_queue: Arc<RoastQueue>,
+    #[cfg(feature = "ssr")]
+    pub apply_retention: Arc<ApplyRetention>,
+    #[cfg(feature = "ssr")]
+    pub follow_up_repo: FollowUpRepository,
+    #[cfg(feature = "ssr")]
+    pub ask_follow_up: Arc<AskFollowUp>,
+    #[cfg(feature = "ssr")]
+    pub invite_repo: InviteRepository,
+    #[cfg(feature = "ssr")]
+    pub impersonation_repo: ImpersonationRepository,
+    #[cfg(feature = "ssr")]
+    pub opt_out_domain: Arc<OptOutDomain>,
+    /// Addresses and ranges refused before any handler runs
+    #[cfg(feature = "ssr")]
+    pub ban_ip: Arc<BanIp>,
+    #[cfg(feature = "ssr")]
+    pub request_metrics: Arc<RequestMetrics>,
+    #[cfg(feature = "ssr")]
+    pub report_scrape_health: Arc<ReportScrapeHealth>,
+    #[cfg(feature = "ssr")]
+    pub sticker_renderer: Arc<StickerRenderer>,
+    #[cfg(feature = "ssr")]
+    pub warmup: Arc<Warmup>,
+    #[cfg(feature = "ssr")]
+    pub notification_repo: NotificationRepository,
+    #[cfg(feature = "ssr")]
+    pub notify_user: Arc<NotifyUser>,
+    #[cfg(feature = "ssr")]
+    pub schedule_roast: Arc<ScheduleRoast>,
+    #[cfg(feature = "ssr")]
+    pub watch_repo: WatchRepository,
+    #[cfg(feature = "ssr")]
+    pub detect_site_changes: Arc<DetectSiteChanges>,
+    #[cfg(feature = "ssr")]
+    pub warm_popular_scrapes: Arc<WarmPopularScrapes>,
+    #[cfg(feature = "ssr")]
+    pub push_subscription_repo: PushSubscriptionRepository,
+    #[cfg(feature = "ssr")]
+    pub track_roast_analytics: Arc<TrackRoastAnalytics>,
+    /// Set when VAPID keys are configured
+    #[cfg(feature = "ssr")]
+    pub web_push: Option<Arc<WebPushChannel>>,
+    /// Stored startup logos, unless LOGO_STORAGE=off
+    #[cfg(feature = "ssr")]
+    pub logo_store: Option<Arc<LogoStore>>,
+    /// Private beta: creating roasts needs an invite code (INVITE_ONLY)
+    pub invite_only: bool,
+    /// Creating roasts needs a logged-in user; browsing stays anonymous (LOGIN_REQUIRED)
+    pub login_required: bool,
+    /// Lowercased emails allowed to use admin endpoints (ADMIN_EMAILS)
+    pub admin_emails: Arc<Vec<String>>,
+    /// Origin used for canonical links, without a trailing slash (PUBLIC_BASE_URL)
+    pub public_base_url: Arc<String>,
 }
 
 impl AppContext {
     #[cfg(feature = "ssr")]
     pub fn new(
         generate_roast: Arc<GenerateRoast>,
+        prompt_templates: Arc<PromptTemplates>,
+        cost_tracker: Arc<CostTracker>,
         db: DatabaseConnection,
         google_oauth: Arc<GoogleOAuth>,
+        admin_emails: Vec<String>,
+        public_base_url: String,
     ) -> Self {
         let user_repo = UserRepository::new(db.clone());
-        let roast_repo = RoastRepository::new(db.clone());
+        let logo_store = generate_roast.logo_store();
+        let cache_purger = CachePurger::from_env(&public_base_url).map(Arc::new);
+        if cache_purger.is_some() {
+            tracing::info!("CDN purging enabled for roast changes");
+        }
+        let roast_repo = RoastRepository::new(db.clone()).with_cache_purger(cache_purger.clone());
         let vote_repo = VoteRepository::new(db.clone());
+        let spice_vote_repo = SpiceVoteRepository::new(db.clone());
+        let announcement_repo = AnnouncementRepository::new(db.clone());
+        let moderation_repo = ModerationRepository::new(db.clone()).with_cache_purger(cache_purger);
+        let problem_report_repo = ProblemReportRepository::new(db.clone());
+        let request_metrics = Arc::new(RequestMetrics::from_env(RequestLogRepository::new(
+            db.clone(),
+        )));
+        let report_scrape_health = Arc::new(ReportScrapeHealth::from_env(
+            ScrapeLogRepository::new(db.clone()),
+        ));
+
+        let roast_job_repo = RoastJobRepository::new(db.clone());
+
+        let apply_retention = Arc::new(ApplyRetention::new(
+            RetentionRepository::new(db.clone()),
+            RetentionPolicy::from_env(),
+        ));
+
+        let invite_repo = InviteRepository::new(db.clone());
+        let impersonation_repo = ImpersonationRepository::new(db.clone());
+        let invite_only = std::env::var("INVITE_ONLY")
+            .map(|v| matches!(v.trim(), "true" | "1"))
+            .unwrap_or(false);
+        if invite_only {
+            tracing::info!("Private beta: roast creation requires an invite code");
+        }
+        let login_required = std::env::var("LOGIN_REQUIRED")
+            .map(|v| matches!(v.trim(), "true" | "1"))
+            .unwrap_or(false);
+        if login_required {
+            tracing::info!("Roast creation requires login");
+        }
+
+        let opt_out_domain = Arc::new(OptOutDomain::new(
+            OptOutRepository::new(db.clone()),
+            roast_repo.clone(),
+            moderation_repo.clone(),
+            generate_roast.opt_outs(),
+        ));
+
+        let ban_ip = Arc::new(BanIp::new(IpBanRepository::new(db.clone())));
+
+        let suggest_battle_opponent = Arc::new(SuggestBattleOpponent::new(roast_repo.clone()));
+        let warmup = Arc::new(Warmup::from_env(generate_roast.clone()));
+        let notification_repo = NotificationRepository::new(db.clone());
+        let watch_repo = WatchRepository::new(db.clone());
+        let push_subscription_repo = PushSubscriptionRepository::new(db.clone());
+        let web_push = WebPushChannel::from_env(push_subscription_repo.clone(), &public_base_url).map(Arc::new);
+        let mut notify_user = NotifyUser::from_env(
+            notification_repo.clone(),
+            user_repo.clone(),
+            roast_repo.clone(),
+            watch_repo.clone(),
+            public_base_url.clone(),
+        );
+        if let Some(web_push) = &web_push {
+            notify_user = notify_user.with_channel(web_push.clone());
+        }
+        let notify_user = Arc::new(notify_user);
+        tracing::info!(
+            "Notification channels: {}",
+            notify_user.channel_names().join(", ")
+        );
+        let follow_up_repo = FollowUpRepository::new(db.clone());
+        let ask_follow_up = Arc::new(AskFollowUp::new(
+            generate_roast.clone(),
+            roast_repo.clone(),
+            follow_up_repo.clone(),
+            notify_user.clone(),
+        ));
+        let schedule_roast = Arc::new(ScheduleRoast::new(roast_repo.clone(), notify_user.clone()));
+        let toggle_vote = Arc::new(ToggleVote::from_env(
+            vote_repo.clone(),
+            roast_repo.clone(),
+            user_repo.clone(),
+            notify_user.clone(),
+        ));
+        let detect_site_changes = Arc::new(DetectSiteChanges::from_env(
+            generate_roast.clone(),
+            SiteFingerprintRepository::new(db.clone()),
+            notify_user.clone(),
+        ));
+        if !detect_site_changes.is_enabled() {
+            tracing::info!("Site change detection disabled (SITE_CHANGE_CHECK_HOURS=0)");
+        }
+        let warm_popular_scrapes = Arc::new(WarmPopularScrapes::from_env(
+            generate_roast.clone(),
+            RoastStatsRepository::new(db.clone()),
+            ScrapeCacheRepository::from_env(db.clone()),
+        ));
+        if !warm_popular_scrapes.is_enabled() {
+            tracing::info!("Popular site warm-up disabled (SCRAPE_WARM_DOMAINS=0 or no scrape cache)");
+        }
+        let track_roast_analytics = Arc::new(TrackRoastAnalytics::new(
+            FounderClaimRepository::new(db.clone()),
+            RoastStatsRepository::new(db.clone()),
+            roast_repo.clone(),
+        ));
+        let roast_queue = Arc::new(RoastQueue::new(
+            generate_roast.clone(),
+            roast_repo.clone(),
+            roast_job_repo.clone(),
+            cost_tracker.clone(),
+            notify_user.clone(),
+        ));
+        let load_shedder = generate_roast.load_shedder();
 
         Self {
             generate_roast,
-            rate_limiter: RateLimiter::new(),
-            cost_tracker: Arc::new(CostTracker::new()),
+            suggest_battle_opponent,
+            rate_limiter: RateLimiter::from_env(),
+            trusted_proxies: Arc::new(TrustedProxies::from_env()),
+            load_shedder,
+            cost_tracker,
+            prompt_templates,
             db,
             google_oauth,
             user_repo,
             roast_repo,
             vote_repo,
+            toggle_vote,
+            spice_vote_repo,
+            announcement_repo,
+            moderation_repo,
+            problem_report_repo,
+            roast_job_repo,
+            roast_queue,
+            apply_retention,
+            follow_up_repo,
+            ask_follow_up,
+            invite_repo,
+            impersonation_repo,
+            opt_out_domain,
+            ban_ip,
+            request_metrics,
+            report_scrape_health,
+            sticker_renderer: Arc::new(StickerRenderer::from_env()),
+            warmup,
+            notification_repo,
+            notify_user,
+            schedule_roast,
+            watch_repo,
+            detect_site_changes,
+            warm_popular_scrapes,
+            push_subscription_repo,
+            track_roast_analytics,
+            web_push,
+            logo_store,
+            invite_only,
+            login_required,
+            admin_emails: Arc::new(admin_emails),
+            public_base_url: Arc::new(public_base_url),
         }
     }
 
+    pub fn is_admin(&self, email: &str) -> bool {
+        self.admin_emails.contains(&email.to_lowercase())
+    }
+
     #[cfg(feature = "ssr")]
     pub async fn from_env() -> Self {
         // Database
@@ -77,18 +325,47 @@ impl AppContext {
         );
         tracing::info!("Google OAuth configured");
 
+        // Admins
+        let admin_emails: Vec<String> = std::env::var("ADMIN_EMAILS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|e| e.trim().to_lowercase())
+            .filter(|e| !e.is_empty())
+            .collect();
+        if admin_emails.is_empty() {
+            tracing::warn!("ADMIN_EMAILS not set, admin endpoints are disabled");
+        }
+
+        let public_base_url = std::env::var("PUBLIC_BASE_URL")
+            .unwrap_or_else(|_| "http://localhost:3000".to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        // Prompt templates
+        let prompt_templates = Arc::new(PromptTemplates::from_env());
+
+        // Shared by the OpenRouter client (actual usage) and request gating
+        let cost_tracker = Arc::new(
+            CostTracker::from_env().with_usage_repo(DailyUsageRepository::new(db.clone())),
+        );
+        cost_tracker.rehydrate().await;
+
         // LLM Backend
         let generate_roast = {
             #[cfg(feature = "local-llm")]
             {
                 if std::env::var("USE_LOCAL_LLM").is_ok() {
-                    tracing::info!("Using local LLM backend (SmolLM2-135M-Instruct)");
-                    Arc::new(GenerateRoast::new_local())
+                    tracing::info!("Using local LLM backend");
+                    GenerateRoast::new_local()
                 } else {
                     let api_key = std::env::var("OPENROUTER_API_KEY")
                         .expect("OPENROUTER_API_KEY or USE_LOCAL_LLM must be set");
                     tracing::info!("Using OpenRouter backend");
-                    Arc::new(GenerateRoast::new_openrouter(api_key))
+                    GenerateRoast::new_openrouter(
+                        api_key,
+                        prompt_templates.clone(),
+                        cost_tracker.clone(),
+                    )
                 }
             }
             #[cfg(not(feature = "local-llm"))]
@@ -96,10 +373,42 @@ impl AppContext {
                 let api_key = std::env::var("OPENROUTER_API_KEY")
                     .expect("OPENROUTER_API_KEY must be set");
                 tracing::info!("Using OpenRouter backend");
-                Arc::new(GenerateRoast::new_openrouter(api_key))
+                GenerateRoast::new_openrouter(
+                    api_key,
+                    prompt_templates.clone(),
+                    cost_tracker.clone(),
+                )
             }
         };
+        let roast_cache = RoastCacheRepository::from_env(db.clone());
+        if roast_cache.is_none() {
+            tracing::info!("Roast cache disabled (ROAST_CACHE_TTL_HOURS=0)");
+        }
+        let scrape_cache = ScrapeCacheRepository::from_env(db.clone());
+        if scrape_cache.is_none() {
+            tracing::info!("Scrape cache disabled (SCRAPE_CACHE_TTL_HOURS=0)");
+        }
+        let scrape_metrics = Arc::new(ScrapeMetrics::new(ScrapeLogRepository::new(db.clone())));
+        let logo_store = LogoStore::from_env().map(Arc::new);
+        if logo_store.is_none() {
+            tracing::info!("Logo storage disabled");
+        }
+        let generate_roast = Arc::new(
+            generate_roast
+                .with_cache(roast_cache)
+                .with_scrape_cache(scrape_cache)
+                .with_scrape_metrics(scrape_metrics)
+                .with_logo_store(logo_store),
+        );
 
-        Self::new(generate_roast, db, google_oauth)
+        Self::new(
+            generate_roast,
+            prompt_templates,
+            cost_tracker,
+            db,
+            google_oauth,
+            admin_emails,
+            public_base_url,
+        )
     }
 }
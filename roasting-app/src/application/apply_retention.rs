@@ -0,0 +1,170 @@
+use crate::domain::RetentionOutcome;
+use crate::infrastructure::db::RetentionRepository;
+use chrono::{Duration, Utc};
+use roasting_errors::AppError;
+
+/// How long each kind of data is kept; `None` disables that policy
+#[derive(Debug, Clone, Copy)]
+pub struct RetentionPolicy {
+    /// Zero-fire roasts older than this are archived
+    pub archive_roasts_after_days: Option<i64>,
+    /// Finished queue jobs older than this are deleted
+    pub roast_jobs_days: Option<i64>,
+    /// Previous roast versions older than this are deleted
+    pub roast_versions_days: Option<i64>,
+    /// Stored scrape snapshots (StartupInfo and page HTML) older than this are deleted
+    pub roast_sources_days: Option<i64>,
+    /// Scheduled runs only report what they would do
+    pub dry_run: bool,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            archive_roasts_after_days: Some(365),
+            roast_jobs_days: Some(90),
+            roast_versions_days: Some(180),
+            roast_sources_days: Some(90),
+            dry_run: false,
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// Reads RETENTION_ARCHIVE_ROASTS_DAYS, RETENTION_ROAST_JOBS_DAYS,
+    /// RETENTION_ROAST_VERSIONS_DAYS, RETENTION_ROAST_SOURCES_DAYS (`0`
+    /// disables one) and RETENTION_DRY_RUN
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let days = |key: &str, default: Option<i64>| match std::env::var(key) {
+            Ok(v) => v.trim().parse::<i64>().ok().filter(|d| *d > 0),
+            Err(_) => default,
+        };
+
+        Self {
+            archive_roasts_after_days: days(
+                "RETENTION_ARCHIVE_ROASTS_DAYS",
+                defaults.archive_roasts_after_days,
+            ),
+            roast_jobs_days: days("RETENTION_ROAST_JOBS_DAYS", defaults.roast_jobs_days),
+            roast_versions_days: days(
+                "RETENTION_ROAST_VERSIONS_DAYS",
+                defaults.roast_versions_days,
+            ),
+            roast_sources_days: days(
+                "RETENTION_ROAST_SOURCES_DAYS",
+                defaults.roast_sources_days,
+            ),
+            dry_run: std::env::var("RETENTION_DRY_RUN")
+                .map(|v| matches!(v.trim(), "true" | "1"))
+                .unwrap_or(defaults.dry_run),
+        }
+    }
+}
+
+pub struct ApplyRetention {
+    repo: RetentionRepository,
+    policy: RetentionPolicy,
+}
+
+impl ApplyRetention {
+    pub fn new(repo: RetentionRepository, policy: RetentionPolicy) -> Self {
+        Self { repo, policy }
+    }
+
+    pub fn policy(&self) -> RetentionPolicy {
+        self.policy
+    }
+
+    /// Run every enabled policy, or just count affected rows when `dry_run`
+    /// (a configured RETENTION_DRY_RUN forces dry runs regardless)
+    pub async fn execute(&self, dry_run: bool) -> Result<Vec<RetentionOutcome>, AppError> {
+        let dry_run = dry_run || self.policy.dry_run;
+        let now = Utc::now();
+        let mut outcomes = Vec::new();
+
+        if let Some(days) = self.policy.archive_roasts_after_days {
+            let cutoff = now - Duration::days(days);
+            let rows = self.repo.archive_cold_roasts(cutoff, dry_run).await;
+            outcomes.push(outcome(
+                "cold_roasts",
+                "roasts",
+                "archive",
+                cutoff,
+                rows,
+                dry_run,
+            )?);
+        }
+
+        if let Some(days) = self.policy.roast_jobs_days {
+            let cutoff = now - Duration::days(days);
+            let rows = self.repo.purge_finished_jobs(cutoff, dry_run).await;
+            outcomes.push(outcome(
+                "finished_jobs",
+                "roast_jobs",
+                "delete",
+                cutoff,
+                rows,
+                dry_run,
+            )?);
+        }
+
+        if let Some(days) = self.policy.roast_versions_days {
+            let cutoff = now - Duration::days(days);
+            let rows = self.repo.purge_roast_versions(cutoff, dry_run).await;
+            outcomes.push(outcome(
+                "old_versions",
+                "roast_versions",
+                "delete",
+                cutoff,
+                rows,
+                dry_run,
+            )?);
+        }
+
+        if let Some(days) = self.policy.roast_sources_days {
+            let cutoff = now - Duration::days(days);
+            let rows = self.repo.purge_roast_sources(cutoff, dry_run).await;
+            outcomes.push(outcome(
+                "old_sources",
+                "roast_sources",
+                "delete",
+                cutoff,
+                rows,
+                dry_run,
+            )?);
+        }
+
+        for o in &outcomes {
+            tracing::info!(
+                policy = %o.policy,
+                table = %o.table,
+                action = %o.action,
+                rows = o.rows,
+                dry_run = o.dry_run,
+                "Retention policy applied"
+            );
+        }
+        Ok(outcomes)
+    }
+}
+
+fn outcome(
+    policy: &str,
+    table: &str,
+    action: &str,
+    cutoff: chrono::DateTime<Utc>,
+    rows: Result<u64, sea_orm::DbErr>,
+    dry_run: bool,
+) -> Result<RetentionOutcome, AppError> {
+    let rows =
+        rows.map_err(|e| AppError::Internal(format!("Retention {} failed: {}", policy, e)))?;
+    Ok(RetentionOutcome {
+        policy: policy.to_string(),
+        table: table.to_string(),
+        action: action.to_string(),
+        cutoff,
+        rows,
+        dry_run,
+    })
+}
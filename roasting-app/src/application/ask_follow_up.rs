@@ -0,0 +1,108 @@
+use super::{GenerateRoast, NotifyUser};
+use crate::domain::{NotificationKind, RoastFollowUp, RoastOptions};
+use crate::infrastructure::db::{FollowUpRepository, RoastRepository};
+use roasting_errors::AppError;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum FollowUpError {
+    RoastNotFound,
+    AlreadyAsked,
+    InvalidQuestion(&'static str),
+    Generation(AppError),
+}
+
+impl FollowUpError {
+    pub fn user_message(&self) -> &str {
+        match self {
+            Self::RoastNotFound => "Roast tidak ditemukan",
+            Self::AlreadyAsked => "Kamu sudah minta roast lebih dalam untuk roast ini",
+            Self::InvalidQuestion(message) => message,
+            Self::Generation(e) => e.user_message(),
+        }
+    }
+}
+
+/// One follow-up question per user per roast, answered in the roast's own
+/// language and dialect with the original roast as conversation state
+pub struct AskFollowUp {
+    generate_roast: Arc<GenerateRoast>,
+    roast_repo: RoastRepository,
+    follow_up_repo: FollowUpRepository,
+    notify_user: Arc<NotifyUser>,
+}
+
+impl AskFollowUp {
+    pub fn new(
+        generate_roast: Arc<GenerateRoast>,
+        roast_repo: RoastRepository,
+        follow_up_repo: FollowUpRepository,
+        notify_user: Arc<NotifyUser>,
+    ) -> Self {
+        Self {
+            generate_roast,
+            roast_repo,
+            follow_up_repo,
+            notify_user,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        roast_id: Uuid,
+        user_id: Uuid,
+        question: &str,
+    ) -> Result<RoastFollowUp, FollowUpError> {
+        let question = question.trim();
+        if question.is_empty() {
+            return Err(FollowUpError::InvalidQuestion(
+                "Pertanyaan tidak boleh kosong",
+            ));
+        }
+        if question.chars().count() > RoastFollowUp::MAX_QUESTION_CHARS {
+            return Err(FollowUpError::InvalidQuestion("Pertanyaan terlalu panjang"));
+        }
+
+        let internal =
+            |e: sea_orm::DbErr| FollowUpError::Generation(AppError::Internal(e.to_string()));
+
+        let roast = match self
+            .roast_repo
+            .find_by_id(roast_id)
+            .await
+            .map_err(internal)?
+        {
+            Some(r) if r.removed_at.is_none() => r,
+            _ => return Err(FollowUpError::RoastNotFound),
+        };
+        if self
+            .follow_up_repo
+            .find_for_user(roast_id, user_id)
+            .await
+            .map_err(internal)?
+            .is_some()
+        {
+            return Err(FollowUpError::AlreadyAsked);
+        }
+
+        let options = RoastOptions::new(roast.language.parse().unwrap_or_default())
+            .with_dialect(roast.dialect.parse().unwrap_or_default())
+            .with_safe(roast.safe);
+        let answer = self
+            .generate_roast
+            .follow_up(&roast.startup_name, &roast.roast_text, question, options)
+            .await
+            .map_err(FollowUpError::Generation)?;
+
+        let follow_up = self
+            .follow_up_repo
+            .create(roast_id, user_id, question, answer.trim())
+            .await
+            .map(RoastFollowUp::from)
+            .map_err(internal)?;
+        self.notify_user
+            .watch_event(roast_id, NotificationKind::WatchedFollowUp, Some(user_id));
+        Ok(follow_up)
+    }
+}
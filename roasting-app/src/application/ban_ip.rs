@@ -0,0 +1,105 @@
+use crate::domain::IpBan;
+use crate::infrastructure::db::IpBanRepository;
+use crate::infrastructure::security::IpBanList;
+use chrono::{DateTime, Utc};
+use std::net::IpAddr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Longest ban reason we keep
+const MAX_REASON_CHARS: usize = 500;
+
+#[derive(Debug)]
+pub enum BanError {
+    InvalidRange,
+    NotFound,
+    Internal(String),
+}
+
+/// Admins banning repeat abusers by address or CIDR range. Bans live in the
+/// database and are enforced from an in-memory list, so no redeploy is needed.
+pub struct BanIp {
+    ban_repo: IpBanRepository,
+    bans: Arc<IpBanList>,
+}
+
+impl BanIp {
+    pub fn new(ban_repo: IpBanRepository) -> Self {
+        Self {
+            ban_repo,
+            bans: Arc::new(IpBanList::new()),
+        }
+    }
+
+    /// Load active bans into the in-memory list; called at startup and
+    /// periodically so bans made on other instances take effect here too
+    pub async fn load(&self) -> Result<usize, BanError> {
+        let bans = self
+            .ban_repo
+            .list_active()
+            .await
+            .map_err(|e| BanError::Internal(e.to_string()))?;
+        self.bans.replace(bans.into_iter().map(IpBan::from).collect());
+        Ok(self.bans.len())
+    }
+
+    /// The active ban covering `ip`, if any
+    pub fn banning(&self, ip: IpAddr) -> Option<IpBan> {
+        self.bans.banning(ip)
+    }
+
+    /// Every ban including expired ones, newest first
+    pub async fn list(&self) -> Result<Vec<IpBan>, BanError> {
+        self.ban_repo
+            .list_all()
+            .await
+            .map(|bans| bans.into_iter().map(IpBan::from).collect())
+            .map_err(|e| BanError::Internal(e.to_string()))
+    }
+
+    pub async fn ban(
+        &self,
+        range: &str,
+        reason: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+        admin_id: Uuid,
+    ) -> Result<IpBan, BanError> {
+        let range = IpBanList::normalize_range(range).ok_or(BanError::InvalidRange)?;
+        let reason = reason
+            .map(|r| r.trim().chars().take(MAX_REASON_CHARS).collect::<String>())
+            .filter(|r| !r.is_empty());
+        let ban = self
+            .ban_repo
+            .create(&range, reason, expires_at, Some(admin_id))
+            .await
+            .map_err(|e| BanError::Internal(e.to_string()))?;
+        self.load().await?;
+        Ok(ban.into())
+    }
+
+    /// Change when a ban ends; `None` makes it permanent, a past time lifts it
+    /// while keeping the record
+    pub async fn set_expiry(&self, id: Uuid, expires_at: Option<DateTime<Utc>>) -> Result<IpBan, BanError> {
+        let ban = self
+            .ban_repo
+            .set_expiry(id, expires_at)
+            .await
+            .map_err(|e| BanError::Internal(e.to_string()))?
+            .ok_or(BanError::NotFound)?;
+        self.load().await?;
+        Ok(ban.into())
+    }
+
+    pub async fn unban(&self, id: Uuid) -> Result<(), BanError> {
+        let deleted = self
+            .ban_repo
+            .delete(id)
+            .await
+            .map_err(|e| BanError::Internal(e.to_string()))?;
+        if !deleted {
+            return Err(BanError::NotFound);
+        }
+        self.load().await?;
+        Ok(())
+    }
+}
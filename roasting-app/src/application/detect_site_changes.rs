@@ -0,0 +1,201 @@
+use crate::application::{GenerateRoast, NotifyUser};
+use crate::domain::{Language, Notification, NotificationKind, SiteFingerprint};
+use crate::infrastructure::db::SiteFingerprintRepository;
+use crate::infrastructure::embedding::{cosine_similarity, HashingEmbedder};
+use chrono::{Duration, Utc};
+use roasting_errors::AppError;
+use std::sync::Arc;
+
+const DEFAULT_CHECK_HOURS: i64 = 24;
+/// Watched domains fingerprinted per run; the rest wait for the next one
+const MAX_DOMAINS_PER_RUN: u64 = 100;
+/// Pause between domains so a run never bursts outbound requests
+const FETCH_SPACING: std::time::Duration = std::time::Duration::from_secs(2);
+/// Below this the old and new title and headings share too little to be a copy tweak
+const PIVOT_SIMILARITY: f32 = 0.4;
+
+/// What one run did, for the log
+#[derive(Debug, Default)]
+pub struct SiteChangeRun {
+    pub checked: usize,
+    pub pivots: usize,
+}
+
+/// Periodically re-fetches the landing page title and headings of every
+/// watched domain and notifies its watchers when the site pivoted
+pub struct DetectSiteChanges {
+    generate_roast: Arc<GenerateRoast>,
+    repo: SiteFingerprintRepository,
+    notify: Arc<NotifyUser>,
+    embedder: HashingEmbedder,
+    /// How old a fingerprint may get before it's re-fetched, 0 turns checks off
+    check_hours: i64,
+}
+
+impl DetectSiteChanges {
+    pub fn new(
+        generate_roast: Arc<GenerateRoast>,
+        repo: SiteFingerprintRepository,
+        notify: Arc<NotifyUser>,
+        check_hours: i64,
+    ) -> Self {
+        Self {
+            generate_roast,
+            repo,
+            notify,
+            embedder: HashingEmbedder::new(),
+            check_hours,
+        }
+    }
+
+    /// SITE_CHANGE_CHECK_HOURS, default 24; 0 turns change detection off
+    pub fn from_env(
+        generate_roast: Arc<GenerateRoast>,
+        repo: SiteFingerprintRepository,
+        notify: Arc<NotifyUser>,
+    ) -> Self {
+        let check_hours = std::env::var("SITE_CHANGE_CHECK_HOURS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_CHECK_HOURS)
+            .max(0);
+        Self::new(generate_roast, repo, notify, check_hours)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.check_hours > 0
+    }
+
+    /// Check the stalest due domains, one at a time
+    pub async fn execute(&self) -> Result<SiteChangeRun, AppError> {
+        let mut run = SiteChangeRun::default();
+        if !self.is_enabled() {
+            return Ok(run);
+        }
+        match self.repo.prune_unwatched().await {
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Dropped {} fingerprints of unwatched domains", n),
+            Err(e) => tracing::warn!("Failed to prune site fingerprints: {}", e),
+        }
+
+        let cutoff = Utc::now() - Duration::hours(self.check_hours);
+        let domains = self
+            .repo
+            .due_domains(cutoff, MAX_DOMAINS_PER_RUN)
+            .await
+            .map_err(internal)?;
+        for (i, domain) in domains.iter().enumerate() {
+            if i > 0 {
+                tokio::time::sleep(FETCH_SPACING).await;
+            }
+            match self.check(domain).await {
+                Ok(pivoted) => {
+                    run.checked += 1;
+                    run.pivots += usize::from(pivoted);
+                }
+                Err(e) => tracing::warn!("Change check for {} failed: {}", domain, e),
+            }
+        }
+        Ok(run)
+    }
+
+    /// Returns whether the site pivoted since the last check
+    async fn check(&self, domain: &str) -> Result<bool, AppError> {
+        let Some(fingerprint) = self
+            .generate_roast
+            .site_fingerprint(&format!("https://{}/", domain))
+            .await
+        else {
+            // Unreachable this round; keep the old fingerprint to compare against later
+            self.repo.touch(domain).await.map_err(internal)?;
+            return Ok(false);
+        };
+        let hash = content_hash(&fingerprint);
+        let previous = self.repo.find(domain).await.map_err(internal)?;
+
+        let pivoted = previous.as_ref().is_some_and(|previous| {
+            previous.content_hash != hash && {
+                let old = SiteFingerprint {
+                    title: previous.title.clone(),
+                    headings: previous.headings.lines().map(str::to_string).collect(),
+                };
+                is_pivot(&self.embedder, &old, &fingerprint)
+            }
+        });
+        self.repo
+            .save(domain, &hash, &fingerprint, pivoted)
+            .await
+            .map_err(internal)?;
+
+        if pivoted {
+            tracing::info!("{} pivoted, now \"{}\"", domain, fingerprint.title);
+            let notification = Notification::for_roast(
+                NotificationKind::WatchedPivot,
+                domain,
+                0,
+                Language::Id,
+                format!("https://{}/", domain),
+            );
+            self.notify.notify_watchers(domain, &notification, None).await?;
+        }
+        Ok(pivoted)
+    }
+}
+
+/// Whether `new` reads like a different product rather than a reworded `old`
+fn is_pivot(embedder: &HashingEmbedder, old: &SiteFingerprint, new: &SiteFingerprint) -> bool {
+    cosine_similarity(
+        &embedder.embed(&old.canonical_text()),
+        &embedder.embed(&new.canonical_text()),
+    ) < PIVOT_SIMILARITY
+}
+
+/// SHA-256 hex of the canonical title and headings
+fn content_hash(fingerprint: &SiteFingerprint) -> String {
+    ring::digest::digest(&ring::digest::SHA256, fingerprint.canonical_text().as_bytes())
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn internal(e: sea_orm::DbErr) -> AppError {
+    AppError::Internal(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint(title: &str, headings: &[&str]) -> SiteFingerprint {
+        SiteFingerprint {
+            title: title.to_string(),
+            headings: headings.iter().map(|h| h.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_copy_tweak_is_not_a_pivot() {
+        let embedder = HashingEmbedder::new();
+        let old = fingerprint(
+            "KopiKilat - Coffee delivery in 10 minutes",
+            &["Coffee delivered in 10 minutes", "Order from 200 cafes", "Download the app"],
+        );
+        let tweak = fingerprint(
+            "KopiKilat - Coffee delivery in 15 minutes",
+            &["Coffee delivered in 15 minutes", "Order from 500 cafes", "Download the app"],
+        );
+        let pivot = fingerprint(
+            "KopiKilat AI - Autonomous agents for enterprise sales",
+            &["AI agents that close deals", "Book a demo", "Trusted by Fortune 500 teams"],
+        );
+
+        assert_eq!(content_hash(&old), content_hash(&fingerprint(&old.title.to_uppercase(), &[
+            "Coffee  delivered in 10 minutes",
+            "Order from 200 cafes",
+            "Download the app",
+        ])));
+        assert!(!is_pivot(&embedder, &old, &tweak));
+        assert!(is_pivot(&embedder, &old, &pivot));
+    }
+}
@@ -1,27 +1,124 @@
-use crate::domain::{Roast, StartupInfo};
+use crate::domain::{
+    FactSheet, Language, PitchDeck, Roast, RoastLength, RoastOptions, RoastScore, RoastSections, ScrapeSnapshot, SiteFingerprint,
+    StartupCategory, StartupIdea, StartupInfo, SubsystemHealth,
+};
+use crate::infrastructure::circuit_breaker::CircuitBreaker;
+use crate::infrastructure::db::{RoastCacheRepository, ScrapeCacheRepository};
+use crate::infrastructure::logo_store::LogoStore;
 use crate::infrastructure::openrouter::OpenRouterClient;
-use crate::infrastructure::scraper::WebsiteScraper;
+use crate::infrastructure::prompt_templates::PromptTemplates;
+use crate::infrastructure::dns_tls::DnsTlsProbe;
+use crate::infrastructure::enrichment::EnrichmentClient;
+use crate::infrastructure::rdap::RdapClient;
+use crate::infrastructure::metrics::ScrapeMetrics;
+use crate::infrastructure::scraper::{SecurityProbe, WebsiteScraper};
+use crate::infrastructure::security::{
+    ContentFilter, ContentViolation, CostTracker, InputSanitizer, LoadShedder, OptOutList,
+};
 use roasting_errors::AppError;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "local-llm")]
-use crate::infrastructure::local_llm::LocalLlm;
+use crate::infrastructure::local_llm::{GenerationQueue, LocalLlm, ModelPoolConfig, QueueError};
+
+/// Pages with more scraped text than this get summarized before roasting
+const SUMMARIZE_THRESHOLD_CHARS: usize = 800;
+
+/// Consecutive LLM failures before generation is paused for `LLM_COOLDOWN`
+const LLM_FAILURE_THRESHOLD: u32 = 3;
+const LLM_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Cache key: the canonical URL plus every option that changes the output
+fn cache_key(url: &str, options: RoastOptions) -> String {
+    format!(
+        "{}|{}|{}|{}|{}",
+        InputSanitizer::canonical_url(url),
+        options.language.as_str(),
+        options.dialect.as_str(),
+        options.length.as_str(),
+        if options.safe { "safe" } else { "full" }
+    )
+}
+
+#[cfg(feature = "local-llm")]
+fn queue_error(error: QueueError) -> AppError {
+    match error {
+        QueueError::Full { waiting } => AppError::QueueFull(format!(
+            "AI lokal lagi sibuk, posisi antrian kamu {} sudah lewat batas. Coba lagi sebentar lagi.",
+            waiting + 1
+        )),
+        QueueError::TimedOut { position } => AppError::QueueFull(format!(
+            "Sudah kelamaan nunggu di posisi antrian {}. Coba lagi sebentar lagi.",
+            position
+        )),
+        QueueError::Join(e) => AppError::LlmError(format!("Task join error: {}", e)),
+    }
+}
+
+/// SAFE_MODE=true makes every roast family-safe regardless of the request
+fn safe_mode_from_env() -> bool {
+    std::env::var("SAFE_MODE")
+        .map(|v| matches!(v.trim(), "true" | "1"))
+        .unwrap_or(false)
+}
 
 pub enum LlmBackend {
     OpenRouter(OpenRouterClient),
+    /// Generations go through the queue, which bounds how many run in parallel;
+    /// the pool names the models a request can be routed to
     #[cfg(feature = "local-llm")]
-    Local,
+    Local(GenerationQueue, ModelPoolConfig),
 }
 
 pub struct GenerateRoast {
     scraper: WebsiteScraper,
+    rdap: RdapClient,
+    dns_tls: DnsTlsProbe,
+    enrichment: EnrichmentClient,
+    security_probe: SecurityProbe,
     backend: LlmBackend,
+    cache: Option<RoastCacheRepository>,
+    scrape_cache: Option<ScrapeCacheRepository>,
+    content_filter: ContentFilter,
+    llm_breaker: CircuitBreaker,
+    /// Force family-safe output for every roast (SAFE_MODE)
+    safe_mode: bool,
+    opt_outs: Arc<OptOutList>,
+    logo_store: Option<Arc<LogoStore>>,
+    /// Generations in flight and their latency, for shedding anonymous load
+    load: Arc<LoadShedder>,
+    /// Learns what roasts really cost; the local model is free
+    cost_tracker: Option<Arc<CostTracker>>,
 }
 
 impl GenerateRoast {
-    pub fn new_openrouter(openrouter_api_key: String) -> Self {
+    pub fn new_openrouter(
+        openrouter_api_key: String,
+        templates: Arc<PromptTemplates>,
+        cost_tracker: Arc<CostTracker>,
+    ) -> Self {
         Self {
             scraper: WebsiteScraper::new(),
-            backend: LlmBackend::OpenRouter(OpenRouterClient::new(openrouter_api_key)),
+            rdap: RdapClient::from_env(),
+            dns_tls: DnsTlsProbe::from_env(),
+            enrichment: EnrichmentClient::from_env(),
+            security_probe: SecurityProbe::from_env(),
+            cache: None,
+            scrape_cache: None,
+            content_filter: ContentFilter::from_env(),
+            llm_breaker: CircuitBreaker::new(LLM_FAILURE_THRESHOLD, LLM_COOLDOWN),
+            safe_mode: safe_mode_from_env(),
+            opt_outs: Arc::new(OptOutList::new()),
+            logo_store: None,
+            load: Arc::new(LoadShedder::from_env()),
+            cost_tracker: Some(cost_tracker.clone()),
+            backend: LlmBackend::OpenRouter(OpenRouterClient::new(
+                openrouter_api_key,
+                templates,
+                cost_tracker,
+            )),
         }
     }
 
@@ -29,40 +126,901 @@ impl GenerateRoast {
     pub fn new_local() -> Self {
         Self {
             scraper: WebsiteScraper::new(),
-            backend: LlmBackend::Local,
+            rdap: RdapClient::from_env(),
+            dns_tls: DnsTlsProbe::from_env(),
+            enrichment: EnrichmentClient::from_env(),
+            security_probe: SecurityProbe::from_env(),
+            cache: None,
+            scrape_cache: None,
+            content_filter: ContentFilter::from_env(),
+            llm_breaker: CircuitBreaker::new(LLM_FAILURE_THRESHOLD, LLM_COOLDOWN),
+            safe_mode: safe_mode_from_env(),
+            opt_outs: Arc::new(OptOutList::new()),
+            logo_store: None,
+            load: Arc::new(LoadShedder::from_env()),
+            cost_tracker: None,
+            backend: LlmBackend::Local(
+                GenerationQueue::from_env(),
+                ModelPoolConfig::from_env()
+                    .unwrap_or_else(|e| panic!("Invalid local model pool: {}", e)),
+            ),
+        }
+    }
+
+    /// Reuse stored roasts for repeat submissions of the same page and options
+    pub fn with_cache(mut self, cache: Option<RoastCacheRepository>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    pub fn with_scrape_cache(mut self, scrape_cache: Option<ScrapeCacheRepository>) -> Self {
+        self.scrape_cache = scrape_cache;
+        self
+    }
+
+    /// Record scraping attempts for the scrape health report
+    pub fn with_scrape_metrics(mut self, metrics: Arc<ScrapeMetrics>) -> Self {
+        self.scraper = self.scraper.with_metrics(metrics);
+        self
+    }
+
+    /// Download and keep the logo of every scraped site
+    pub fn with_logo_store(mut self, logo_store: Option<Arc<LogoStore>>) -> Self {
+        self.logo_store = logo_store;
+        self
+    }
+
+    pub fn logo_store(&self) -> Option<Arc<LogoStore>> {
+        self.logo_store.clone()
+    }
+
+    pub fn load_shedder(&self) -> Arc<LoadShedder> {
+        self.load.clone()
+    }
+
+    /// Prompt variant new roasts are generated with; the local model has none
+    pub fn prompt_variant(&self) -> Option<String> {
+        match &self.backend {
+            LlmBackend::OpenRouter(client) => client.prompt_variant().map(str::to_string),
+            #[cfg(feature = "local-llm")]
+            LlmBackend::Local(..) => None,
+        }
+    }
+
+    /// Pool model a request runs on, or `None` on OpenRouter, which ignores
+    /// the selection. Unknown names are an error before anything is scraped.
+    #[cfg_attr(not(feature = "local-llm"), allow(unused_variables))]
+    fn local_model(&self, requested: Option<&str>, length: RoastLength) -> Result<Option<String>, AppError> {
+        match &self.backend {
+            LlmBackend::OpenRouter(_) => Ok(None),
+            #[cfg(feature = "local-llm")]
+            LlmBackend::Local(_, pool) => pool
+                .resolve(requested, length)
+                .map(|name| Some(name.to_string()))
+                .map_err(|e| AppError::LlmError(e.to_string())),
+        }
+    }
+
+    /// Domains refused by `ensure_roastable`, shared with the opt-out flow
+    pub fn opt_outs(&self) -> Arc<OptOutList> {
+        self.opt_outs.clone()
+    }
+
+    /// Fails with `AppError::OptedOut` if the site owner opted out. Entry
+    /// points call this right after URL validation; `execute` checks again.
+    pub fn ensure_roastable(&self, url: &str) -> Result<(), AppError> {
+        match self.opt_outs.blocking_domain(url) {
+            Some(domain) => Err(AppError::OptedOut(domain)),
+            None => Ok(()),
+        }
+    }
+
+    /// The URL a roast should be stored under, see `WebsiteScraper::resolve_url`.
+    /// Entry points call this after `ensure_roastable` and use the result throughout.
+    pub async fn resolve_url(&self, url: &str) -> String {
+        self.scraper.resolve_url(url).await
+    }
+
+    /// Landing page title and headings for change detection, never for opted-out sites
+    pub async fn site_fingerprint(&self, url: &str) -> Option<SiteFingerprint> {
+        self.ensure_roastable(url).ok()?;
+        self.scraper.fingerprint(url).await
+    }
+
+    /// A roast generated for this URL and options within the cache TTL, if any.
+    /// Callers check this before spending rate/cost budget on `execute`.
+    pub async fn cached(&self, url: &str, options: RoastOptions) -> Option<Roast> {
+        self.ensure_roastable(url).ok()?;
+        let options = self.effective_options(options);
+        let cache = self.cache.as_ref()?;
+        match cache.get(&cache_key(url, options)).await {
+            Ok(Some(roast)) => {
+                tracing::info!(stage = "cache", url = %url, "Serving cached roast");
+                Some(roast)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!("Roast cache lookup failed: {}", e);
+                None
+            }
+        }
+    }
+
+    /// The requested options, with family-safe mode forced on under SAFE_MODE
+    fn effective_options(&self, options: RoastOptions) -> RoastOptions {
+        if self.safe_mode {
+            options.with_safe(true)
+        } else {
+            options
         }
     }
 
-    pub async fn execute(&self, url: String) -> Result<Roast, AppError> {
-        let startup_info = self.scraper.scrape(&url).await?;
-        let startup_name = startup_info
-            .title
-            .clone()
-            .unwrap_or_else(|| "Startup Misterius".to_string());
+    /// Switch a pool model (default: the first) at runtime; see `LocalLlm::swap`.
+    /// Returns the pool name and the model ID now serving it.
+    #[cfg(feature = "local-llm")]
+    pub async fn swap_local_model(
+        &self,
+        name: Option<&str>,
+        model_id: &str,
+    ) -> Result<(String, String), AppError> {
+        let LlmBackend::Local(queue, pool) = &self.backend else {
+            return Err(AppError::Internal(
+                "local LLM is not the active backend (set USE_LOCAL_LLM)".to_string(),
+            ));
+        };
+        let name = name.unwrap_or(pool.default_name());
+        LocalLlm::swap(name, model_id, pool, queue)
+            .await
+            .map(|llm| (name.to_string(), llm.model_id().to_string()))
+            .map_err(|e| AppError::LlmError(e.to_string()))
+    }
 
-        let roast_text = self.generate_roast_text(&startup_info).await?;
-        Ok(Roast::new(startup_name, roast_text))
+    /// Startup warmup of the LLM API: DNS lookup plus a pooled connection
+    pub async fn warm_up_llm(&self) -> SubsystemHealth {
+        match &self.backend {
+            LlmBackend::OpenRouter(client) => match client.preconnect().await {
+                Ok(addresses) => SubsystemHealth::active("openrouter")
+                    .with_detail(format!("{} addresses resolved, connection pooled", addresses)),
+                Err(e) => SubsystemHealth::degraded("openrouter", e.to_string()),
+            },
+            #[cfg(feature = "local-llm")]
+            LlmBackend::Local(..) => SubsystemHealth::disabled("openrouter", "local backend"),
+        }
     }
 
-    async fn generate_roast_text(&self, startup_info: &StartupInfo) -> Result<String, AppError> {
+    /// Startup warmup of the prompt templates; the local backend has its own prompts
+    pub fn warm_up_prompts(&self) -> SubsystemHealth {
         match &self.backend {
-            LlmBackend::OpenRouter(client) => client.generate_roast(startup_info).await,
+            LlmBackend::OpenRouter(client) => SubsystemHealth::active("prompt_templates")
+                .with_detail(format!("{} roast prompts rendered", client.prime_prompts())),
+            #[cfg(feature = "local-llm")]
+            LlmBackend::Local(..) => {
+                SubsystemHealth::disabled("prompt_templates", "local backend")
+            }
+        }
+    }
+
+    /// Startup check that the headless browser works
+    pub async fn check_headless(&self) -> SubsystemHealth {
+        self.scraper.check_headless().await
+    }
+
+    /// Subsystem states for /healthz, from kill switches and circuit breakers
+    pub fn health(&self) -> Vec<SubsystemHealth> {
+        let backend = match &self.backend {
+            LlmBackend::OpenRouter(_) => "openrouter",
+            #[cfg(feature = "local-llm")]
+            LlmBackend::Local(..) => "local",
+        };
+        let llm = if self.llm_breaker.is_open() {
+            SubsystemHealth::degraded(
+                "llm",
+                format!(
+                    "{} paused after {} consecutive failures",
+                    backend,
+                    self.llm_breaker.consecutive_failures()
+                ),
+            )
+        } else {
+            match &self.backend {
+                #[cfg(feature = "local-llm")]
+                LlmBackend::Local(queue, pool) => {
+                    let detail = format!(
+                        "local ({}), {} running, {}/{} queued",
+                        pool.names().join(", "),
+                        queue.running(),
+                        queue.waiting(),
+                        queue.max_waiting()
+                    );
+                    if queue.is_full() {
+                        SubsystemHealth::degraded("llm", detail)
+                    } else {
+                        SubsystemHealth::active("llm").with_detail(detail)
+                    }
+                }
+                _ => SubsystemHealth::active("llm").with_detail(backend),
+            }
+        };
+
+        let mut subsystems = vec![llm];
+        subsystems.extend(self.scraper.health());
+        subsystems.extend([
+            SubsystemHealth::toggle("rdap", self.rdap.is_enabled(), "RDAP_LOOKUP=false"),
+            SubsystemHealth::toggle("dns_tls", self.dns_tls.is_enabled(), "DNS_TLS_LOOKUP not set"),
+            SubsystemHealth::toggle(
+                "enrichment",
+                self.enrichment.is_enabled(),
+                "CRUNCHBASE_API_KEY and PRODUCT_HUNT_TOKEN not set",
+            ),
+            SubsystemHealth::toggle(
+                "security_probe",
+                self.security_probe.is_enabled(),
+                "SECURITY_PROBE=false",
+            ),
+            SubsystemHealth::toggle(
+                "roast_cache",
+                self.cache.is_some(),
+                "ROAST_CACHE_TTL_HOURS=0",
+            ),
+            SubsystemHealth::toggle(
+                "moderation_classifier",
+                self.content_filter.llm_check(),
+                "MODERATION_LLM_CHECK not set",
+            ),
+        ]);
+        subsystems
+    }
+
+    /// Drop expired roast and scrape cache entries
+    pub async fn purge_cache(&self) -> u64 {
+        let roasts = match &self.cache {
+            Some(cache) => cache.purge_expired().await.unwrap_or_else(|e| {
+                tracing::warn!("Failed to purge roast cache: {}", e);
+                0
+            }),
+            None => 0,
+        };
+        let scrapes = match &self.scrape_cache {
+            Some(cache) => cache.purge_expired().await.unwrap_or_else(|e| {
+                tracing::warn!("Failed to purge scrape cache: {}", e);
+                0
+            }),
+            None => 0,
+        };
+        roasts + scrapes
+    }
+
+    /// Always generates a fresh roast, replacing any cached one for the same key
+    pub async fn execute(&self, url: String, options: RoastOptions) -> Result<Roast, AppError> {
+        self.execute_with_model(url, options, None).await
+    }
+
+    /// `execute` on a named local model instead of the one routed by length.
+    /// A roast from an explicitly chosen model is not cached, so it never
+    /// answers a later request that didn't choose it.
+    pub async fn execute_with_model(
+        &self,
+        url: String,
+        options: RoastOptions,
+        model: Option<&str>,
+    ) -> Result<Roast, AppError> {
+        self.metered(self.generate_site(url, options, model)).await
+    }
+
+    async fn generate_site(
+        &self,
+        url: String,
+        options: RoastOptions,
+        model: Option<&str>,
+    ) -> Result<Roast, AppError> {
+        self.ensure_roastable(&url)?;
+        let local_model = self.local_model(model, options.length)?;
+        let options = self.effective_options(options);
+        let _in_flight = self.load.track();
+        let mut startup_info = self.scrape(&url).await?;
+        let html = startup_info.raw_html.take();
+        self.save_logo(&url, &startup_info.logo_candidates);
+        // Taken while the roast is generated, unless the live site is gone
+        let screenshot = startup_info
+            .archived
+            .as_ref()
+            .is_none_or(|snapshot| snapshot.live_site_up)
+            .then(|| self.scraper.start_screenshot(&url))
+            .flatten();
+        let startup_name = startup_info.title.clone().unwrap_or_else(|| {
+            match options.language {
+                Language::Id => "Startup Misterius",
+                Language::En => "Mystery Startup",
+            }
+            .to_string()
+        });
+
+        let (fact_sheet, brief) = tokio::join!(
+            self.extract_facts(&startup_info, &options),
+            self.condense(&startup_info, &options)
+        );
+
+        // The fact sheet goes first: it names the product without the page's noise
+        let category = StartupCategory::classify(&format!(
+            "{} {} {} {} {}",
+            fact_sheet.as_ref().map(FactSheet::search_text).unwrap_or_default(),
+            startup_name,
+            startup_info.description.as_deref().unwrap_or_default(),
+            startup_info.headings.join(" "),
+            startup_info.content_summary
+        ));
+
+        let text = async {
+            let started = Instant::now();
+            let mut raw_text = self.generate_roast_text(&brief, &options, local_model.as_deref()).await?;
+            if let Err(violation) = self.moderate(&raw_text).await {
+                tracing::warn!(stage = "moderation", url = %url, reason = %violation, "Roast rejected, regenerating once");
+                raw_text = self.generate_roast_text(&brief, &options, local_model.as_deref()).await?;
+                if let Err(violation) = self.moderate(&raw_text).await {
+                    tracing::warn!(stage = "moderation", url = %url, reason = %violation, "Regenerated roast rejected");
+                    return Err(AppError::ContentRejected(violation.to_string()));
+                }
+            }
+            if options.safe {
+                raw_text = self.content_filter.scrub_profanity(&raw_text);
+            }
+            tracing::info!(
+                stage = "roast",
+                url = %url,
+                chars = raw_text.len(),
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                "Roast pipeline stage finished"
+            );
+            Ok(raw_text)
+        };
+        // The screenshot and the design roast on it run alongside the text roast
+        let visuals = async {
+            let screenshot = match screenshot {
+                Some(capture) => capture.await.ok().flatten(),
+                None => None,
+            };
+            let design = match &screenshot {
+                Some(image) => self.roast_design(&url, &startup_name, image, &options).await,
+                None => None,
+            };
+            (screenshot, design)
+        };
+        let (raw_text, (screenshot, design)) = tokio::join!(text, visuals);
+        let raw_text = raw_text?;
+
+        // Prefer the structured sections; keep the raw reply if the model ignored the format
+        let mut sections = RoastSections::parse(&raw_text);
+        let score = RoastScore::parse(&raw_text);
+        // What the model saw beats what the text roast guessed from the HTML
+        let roast_text = match (sections.as_mut(), design) {
+            (Some(s), Some(design)) => {
+                s.desain = design;
+                s.to_text(options.language)
+            }
+            (Some(s), None) => s.to_text(options.language),
+            (None, Some(design)) => format!(
+                "{}\n\n## {}\n{}",
+                raw_text,
+                RoastSections::titles(options.language)[1],
+                design
+            ),
+            (None, None) => raw_text,
+        };
+
+        let roast = Roast::new(startup_name, roast_text, options, category)
+            .with_sections(sections)
+            .with_score(score)
+            .with_ai_wrapper_confidence(startup_info.ai_wrapper.as_ref().map(|w| w.confidence))
+            .with_security(startup_info.security.clone())
+            .with_social_links(startup_info.social_links.clone().unwrap_or_default())
+            .with_fact_sheet(fact_sheet)
+            .with_screenshot(screenshot)
+            .with_country(startup_info.country.clone())
+            .with_source(Some(ScrapeSnapshot { startup_info, html }));
+
+        if let Some(cache) = self.cache.as_ref().filter(|_| model.is_none()) {
+            if let Err(e) = cache.put(&cache_key(&url, options), &url, &roast).await {
+                tracing::warn!("Failed to cache roast for {}: {}", url, e);
+            }
+        }
+
+        Ok(roast)
+    }
+
+    /// Roast an uploaded pitch deck instead of a site. The result has the
+    /// same sections, score and fact sheet as a site roast, with the slides
+    /// as its source; it is never cached since no two uploads are the same.
+    pub async fn execute_deck(&self, deck: PitchDeck, options: RoastOptions) -> Result<Roast, AppError> {
+        self.metered(self.generate_deck(deck, options)).await
+    }
+
+    async fn generate_deck(&self, deck: PitchDeck, options: RoastOptions) -> Result<Roast, AppError> {
+        let options = self.effective_options(options);
+        let _in_flight = self.load.track();
+        let url = deck.source_url();
+        let startup_name = deck.startup_name();
+        let startup_info = StartupInfo::new(url.clone())
+            .with_title(Some(startup_name.clone()))
+            .with_content_summary(deck.slides.join("\n\n"));
+
+        let started = Instant::now();
+        let (fact_sheet, raw_text) = tokio::join!(
+            self.extract_facts(&startup_info, &options),
+            self.generate_deck_text(&deck, &options)
+        );
+        let mut raw_text = raw_text?;
+        if let Err(violation) = self.moderate(&raw_text).await {
+            tracing::warn!(stage = "moderation", url = %url, reason = %violation, "Deck roast rejected, regenerating once");
+            raw_text = self.generate_deck_text(&deck, &options).await?;
+            if let Err(violation) = self.moderate(&raw_text).await {
+                return Err(AppError::ContentRejected(violation.to_string()));
+            }
+        }
+        if options.safe {
+            raw_text = self.content_filter.scrub_profanity(&raw_text);
+        }
+        tracing::info!(
+            stage = "roast",
+            url = %url,
+            slides = deck.slides.len(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Roast pipeline stage finished"
+        );
+
+        let sections = RoastSections::parse(&raw_text);
+        let score = RoastScore::parse(&raw_text);
+        let roast_text = match &sections {
+            Some(s) => s.to_text(options.language),
+            None => raw_text,
+        };
+        let category = StartupCategory::classify(&format!(
+            "{} {} {}",
+            fact_sheet.as_ref().map(FactSheet::search_text).unwrap_or_default(),
+            startup_name,
+            startup_info.content_summary
+        ));
+
+        Ok(Roast::new(startup_name, roast_text, options, category)
+            .with_sections(sections)
+            .with_score(score)
+            .with_fact_sheet(fact_sheet)
+            .with_source(Some(ScrapeSnapshot { startup_info, html: None })))
+    }
+
+    /// Roast a stealth startup from the founder's own description. The idea
+    /// stands in for the scraped page, so nothing is fetched; like a deck
+    /// roast it is never cached.
+    pub async fn execute_description(&self, idea: StartupIdea, options: RoastOptions) -> Result<Roast, AppError> {
+        self.metered(self.generate_description(idea, options)).await
+    }
+
+    async fn generate_description(&self, idea: StartupIdea, options: RoastOptions) -> Result<Roast, AppError> {
+        let local_model = self.local_model(None, options.length)?;
+        let options = self.effective_options(options);
+        let _in_flight = self.load.track();
+        let url = idea.source_url();
+        let (fallback_name, note) = match options.language {
+            Language::Id => ("Startup Stealth", "Startup stealth, belum punya website. Dideskripsikan sendiri oleh foundernya."),
+            Language::En => ("Stealth Startup", "Stealth startup without a website yet, described by its founder."),
+        };
+        let startup_name = idea.name.clone().unwrap_or_else(|| fallback_name.to_string());
+        let startup_info = StartupInfo::new(url.clone())
+            .with_title(Some(startup_name.clone()))
+            .with_description(Some(note.to_string()))
+            .with_content_summary(idea.description);
+
+        let started = Instant::now();
+        let (fact_sheet, raw_text) = tokio::join!(
+            self.extract_facts(&startup_info, &options),
+            self.generate_roast_text(&startup_info, &options, local_model.as_deref())
+        );
+        let mut raw_text = raw_text?;
+        if let Err(violation) = self.moderate(&raw_text).await {
+            tracing::warn!(stage = "moderation", url = %url, reason = %violation, "Idea roast rejected, regenerating once");
+            raw_text = self.generate_roast_text(&startup_info, &options, local_model.as_deref()).await?;
+            if let Err(violation) = self.moderate(&raw_text).await {
+                return Err(AppError::ContentRejected(violation.to_string()));
+            }
+        }
+        if options.safe {
+            raw_text = self.content_filter.scrub_profanity(&raw_text);
+        }
+        tracing::info!(
+            stage = "roast",
+            url = %url,
+            chars = raw_text.len(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Roast pipeline stage finished"
+        );
+
+        let sections = RoastSections::parse(&raw_text);
+        let score = RoastScore::parse(&raw_text);
+        let roast_text = match &sections {
+            Some(s) => s.to_text(options.language),
+            None => raw_text,
+        };
+        let category = StartupCategory::classify(&format!(
+            "{} {} {}",
+            fact_sheet.as_ref().map(FactSheet::search_text).unwrap_or_default(),
+            startup_name,
+            startup_info.content_summary
+        ));
+
+        Ok(Roast::new(startup_name, roast_text, options, category)
+            .with_sections(sections)
+            .with_score(score)
+            .with_fact_sheet(fact_sheet)
+            .with_source(Some(ScrapeSnapshot { startup_info, html: None })))
+    }
+
+    /// Attach what the generation's completions cost to the roast; a failed
+    /// one that was billed still counts towards the estimate for the next
+    async fn metered(&self, generation: impl Future<Output = Result<Roast, AppError>>) -> Result<Roast, AppError> {
+        let (roast, costs) = CostTracker::metered(generation).await;
+        if let Some(cost_tracker) = &self.cost_tracker {
+            cost_tracker.record_roast(&costs);
+        }
+        roast.map(|roast| roast.with_costs(costs))
+    }
+
+    async fn generate_deck_text(&self, deck: &PitchDeck, options: &RoastOptions) -> Result<String, AppError> {
+        if !self.llm_breaker.allow() {
+            return Err(self.llm_paused());
+        }
+        let result = match &self.backend {
+            LlmBackend::OpenRouter(client) => client.generate_deck_roast(deck, options).await,
             #[cfg(feature = "local-llm")]
-            LlmBackend::Local => {
-                let llm = LocalLlm::get_or_init()
+            LlmBackend::Local(queue, pool) => {
+                let name = pool
+                    .resolve(None, options.length)
+                    .map_err(|e| AppError::LlmError(e.to_string()))?
+                    .to_string();
+                let llm = LocalLlm::get_or_init(&name, pool)
                     .await
                     .map_err(|e| AppError::LlmError(e.to_string()))?;
+                let (deck, options) = (deck.clone(), *options);
+                queue
+                    .run(move || {
+                        let llm = LocalLlm::current(&name).unwrap_or(llm);
+                        llm.generate_deck_roast(&deck, &options)
+                    })
+                    .await
+                    .map_err(queue_error)?
+                    .map_err(|e| AppError::LlmError(e.to_string()))
+            }
+        };
+        if matches!(result, Err(AppError::QueueFull(_))) {
+            return result;
+        }
+        self.llm_breaker.track(result)
+    }
+
+    /// Scrape the site with its domain, DNS/TLS and security lookups, or
+    /// reuse a scrape of the same URL from within the scrape cache TTL
+    async fn scrape(&self, url: &str) -> Result<StartupInfo, AppError> {
+        if let Some(cache) = &self.scrape_cache {
+            match cache.get(url).await {
+                Ok(Some(startup_info)) => {
+                    tracing::info!(stage = "scrape", url = %url, "Reusing cached scrape");
+                    return Ok(startup_info);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Scrape cache lookup failed: {}", e),
+            }
+        }
+
+        let startup_info = self.fetch(url).await?;
+        if let Some(cache) = &self.scrape_cache {
+            if let Err(e) = cache.put(url, &startup_info, false).await {
+                tracing::warn!("Failed to cache scrape of {}: {}", url, e);
+            }
+        }
+        Ok(startup_info)
+    }
+
+    /// A fresh scrape bypassing the scrape cache, for the nightly warm-up of
+    /// popular sites. Opted-out sites are never fetched.
+    pub async fn scrape_fresh(&self, url: &str) -> Result<StartupInfo, AppError> {
+        self.ensure_roastable(url)?;
+        self.fetch(url).await
+    }
+
+    async fn fetch(&self, url: &str) -> Result<StartupInfo, AppError> {
+        let started = Instant::now();
+        let (scraped, domain_info, dns_tls, funding) = tokio::join!(
+            self.scraper.scrape(url),
+            self.rdap.lookup(url),
+            self.dns_tls.lookup(url),
+            self.enrichment.lookup(url)
+        );
+        let scraped = scraped?
+            .with_domain_info(domain_info)
+            .with_dns_tls(dns_tls)
+            .with_funding(funding);
+        let startup_info = self.security_probe.check(scraped).await;
+        tracing::info!(
+            stage = "scrape",
+            url = %url,
+            chars = startup_info.text_len(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Roast pipeline stage finished"
+        );
+        Ok(startup_info)
+    }
+
+    /// Store the site's logo in the background; the roast never waits on it
+    fn save_logo(&self, url: &str, candidates: &[String]) {
+        let Some(store) = self.logo_store.clone() else {
+            return;
+        };
+        let url = url.to_string();
+        let candidates = candidates.to_vec();
+        tokio::spawn(async move {
+            if let Err(e) = store.save_from(&url, &candidates).await {
+                tracing::warn!("Failed to store logo of {}: {}", url, e);
+            }
+        });
+    }
+
+    /// Word-list check, then the optional LLM classifier. A failed classifier
+    /// call lets the text through rather than blocking every roast.
+    async fn moderate(&self, text: &str) -> Result<(), ContentViolation> {
+        self.content_filter.check(text)?;
+
+        let client = match &self.backend {
+            LlmBackend::OpenRouter(client) if self.content_filter.llm_check() => client,
+            _ => return Ok(()),
+        };
+        match client.classify_safe(text).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(ContentViolation::Classifier),
+            Err(e) => {
+                tracing::warn!(stage = "moderation", "Safety classifier failed: {}", e);
+                Ok(())
+            }
+        }
+    }
+
+    /// Design burns from the screenshot, when a vision model is configured.
+    /// Moderated like the roast itself; anything rejected or failed is dropped
+    /// and the text roast's own design section stays.
+    async fn roast_design(
+        &self,
+        url: &str,
+        startup_name: &str,
+        screenshot: &[u8],
+        options: &RoastOptions,
+    ) -> Option<String> {
+        let client = match &self.backend {
+            LlmBackend::OpenRouter(client) if client.vision_model().is_some() => client,
+            _ => return None,
+        };
 
-                // Clone data for spawn_blocking
-                let startup_info = startup_info.clone();
+        let started = Instant::now();
+        let design = match client.roast_design(startup_name, screenshot, options).await {
+            Ok(design) if !design.trim().is_empty() => design.trim().to_string(),
+            Ok(_) => {
+                tracing::warn!(stage = "design", url = %url, "Empty design roast");
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!(stage = "design", url = %url, "Design roast failed: {}", e);
+                return None;
+            }
+        };
+        if let Err(violation) = self.moderate(&design).await {
+            tracing::warn!(stage = "moderation", url = %url, reason = %violation, "Design roast rejected");
+            return None;
+        }
+        tracing::info!(
+            stage = "design",
+            url = %url,
+            chars = design.len(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Roast pipeline stage finished"
+        );
+        Some(if options.safe {
+            self.content_filter.scrub_profanity(&design)
+        } else {
+            design
+        })
+    }
+
+    /// Fact sheet of what the startup claims. OpenRouter only; a failed or
+    /// unparseable extraction just leaves the panel out.
+    async fn extract_facts(&self, startup_info: &StartupInfo, options: &RoastOptions) -> Option<FactSheet> {
+        let started = Instant::now();
+        let extracted = match &self.backend {
+            LlmBackend::OpenRouter(client) => client.extract_facts(startup_info, options.language).await,
+            #[cfg(feature = "local-llm")]
+            LlmBackend::Local(..) => return None,
+        };
+        match extracted {
+            Ok(Some(fact_sheet)) => {
+                tracing::info!(
+                    stage = "fact_sheet",
+                    url = %startup_info.url,
+                    elapsed_ms = started.elapsed().as_millis() as u64,
+                    "Roast pipeline stage finished"
+                );
+                Some(fact_sheet)
+            }
+            Ok(None) => {
+                tracing::warn!(stage = "fact_sheet", url = %startup_info.url, "Fact sheet reply had no usable JSON");
+                None
+            }
+            Err(e) => {
+                tracing::warn!(stage = "fact_sheet", url = %startup_info.url, "Fact sheet extraction failed: {}", e);
+                None
+            }
+        }
+    }
 
-                // Run CPU-intensive generation in blocking thread pool
-                tokio::task::spawn_blocking(move || {
-                    llm.generate_roast(&startup_info)
-                })
-                .await
-                .map_err(|e| AppError::LlmError(format!("Task join error: {}", e)))?
-                .map_err(|e| AppError::LlmError(e.to_string()))
+    /// Summarize content-heavy pages into key claims so the roast prompt stays
+    /// focused. Falls back to the scraped info if the summary pass fails.
+    async fn condense(&self, startup_info: &StartupInfo, options: &RoastOptions) -> StartupInfo {
+        let input_chars = startup_info.text_len();
+        let client = match &self.backend {
+            LlmBackend::OpenRouter(client) if input_chars > SUMMARIZE_THRESHOLD_CHARS => client,
+            _ => {
+                tracing::debug!(stage = "summarize", input_chars, "Skipping summary pass");
+                return startup_info.clone();
+            }
+        };
+
+        let started = Instant::now();
+        match client.summarize(startup_info, options.language).await {
+            Ok(summary) if !summary.trim().is_empty() => {
+                tracing::info!(
+                    stage = "summarize",
+                    url = %startup_info.url,
+                    input_chars,
+                    output_chars = summary.len(),
+                    elapsed_ms = started.elapsed().as_millis() as u64,
+                    "Roast pipeline stage finished"
+                );
+                // The roast prompt strips newlines, so keep the bullets apart with spaces
+                let claims = summary
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                startup_info
+                    .clone()
+                    .with_headings(Vec::new())
+                    .with_content_summary(claims)
+                    .with_pages(Vec::new())
+            }
+            Ok(_) => {
+                tracing::warn!(stage = "summarize", url = %startup_info.url, "Empty summary, using full content");
+                startup_info.clone()
+            }
+            Err(e) => {
+                tracing::warn!(stage = "summarize", url = %startup_info.url, "Summary failed, using full content: {}", e);
+                startup_info.clone()
+            }
+        }
+    }
+
+    /// `local_model` is the pool name from `local_model`; OpenRouter ignores it
+    #[cfg_attr(not(feature = "local-llm"), allow(unused_variables))]
+    async fn generate_roast_text(
+        &self,
+        startup_info: &StartupInfo,
+        options: &RoastOptions,
+        local_model: Option<&str>,
+    ) -> Result<String, AppError> {
+        if !self.llm_breaker.allow() {
+            return Err(self.llm_paused());
+        }
+        let result = async {
+            match &self.backend {
+                LlmBackend::OpenRouter(client) => client.generate_roast(startup_info, options).await,
+                #[cfg(feature = "local-llm")]
+                LlmBackend::Local(queue, pool) => {
+                    let name = local_model.unwrap_or(pool.default_name()).to_string();
+                    let llm = LocalLlm::get_or_init(&name, pool)
+                        .await
+                        .map_err(|e| AppError::LlmError(e.to_string()))?;
+
+                    // Clone data for the blocking thread pool
+                    let startup_info = startup_info.clone();
+                    let options = *options;
+
+                    queue
+                        .run(move || {
+                            // Pick up a model swapped in while this request waited
+                            let llm = LocalLlm::current(&name).unwrap_or(llm);
+                            llm.generate_roast(&startup_info, &options)
+                        })
+                        .await
+                        .map_err(queue_error)?
+                        .map_err(|e| AppError::LlmError(e.to_string()))
+                }
+            }
+        }
+        .await;
+        // A full queue says nothing about the model's health
+        if matches!(result, Err(AppError::QueueFull(_))) {
+            return result;
+        }
+        self.llm_breaker.track(result)
+    }
+
+    fn llm_paused(&self) -> AppError {
+        let message = "LLM dijeda sementara setelah beberapa kali gagal".to_string();
+        match &self.backend {
+            LlmBackend::OpenRouter(_) => AppError::OpenRouterError(message),
+            #[cfg(feature = "local-llm")]
+            LlmBackend::Local(..) => AppError::LlmError(message),
+        }
+    }
+
+    /// Answer a "roast lebih dalam" question about an already generated roast,
+    /// with the same moderation pass and single retry as `execute`
+    pub async fn follow_up(
+        &self,
+        startup_name: &str,
+        roast_text: &str,
+        question: &str,
+        options: RoastOptions,
+    ) -> Result<String, AppError> {
+        let options = self.effective_options(options);
+        let mut answer = self
+            .follow_up_text(startup_name, roast_text, question, options)
+            .await?;
+        if let Err(violation) = self.moderate(&answer).await {
+            tracing::warn!(stage = "moderation", reason = %violation, "Follow-up rejected, regenerating once");
+            answer = self
+                .follow_up_text(startup_name, roast_text, question, options)
+                .await?;
+            if let Err(violation) = self.moderate(&answer).await {
+                return Err(AppError::ContentRejected(violation.to_string()));
+            }
+        }
+        if options.safe {
+            answer = self.content_filter.scrub_profanity(&answer);
+        }
+        Ok(answer)
+    }
+
+    async fn follow_up_text(
+        &self,
+        startup_name: &str,
+        roast_text: &str,
+        question: &str,
+        options: RoastOptions,
+    ) -> Result<String, AppError> {
+        match &self.backend {
+            LlmBackend::OpenRouter(client) => {
+                client
+                    .follow_up(startup_name, roast_text, question, &options)
+                    .await
+            }
+            #[cfg(feature = "local-llm")]
+            LlmBackend::Local(queue, pool) => {
+                let name = pool
+                    .resolve(None, options.length)
+                    .map_err(|e| AppError::LlmError(e.to_string()))?
+                    .to_string();
+                let llm = LocalLlm::get_or_init(&name, pool)
+                    .await
+                    .map_err(|e| AppError::LlmError(e.to_string()))?;
+
+                let (startup_name, roast_text, question) = (
+                    startup_name.to_string(),
+                    roast_text.to_string(),
+                    question.to_string(),
+                );
+                queue
+                    .run(move || {
+                        let llm = LocalLlm::current(&name).unwrap_or(llm);
+                        llm.follow_up(&startup_name, &roast_text, &question, &options)
+                    })
+                    .await
+                    .map_err(queue_error)?
+                    .map_err(|e| AppError::LlmError(e.to_string()))
             }
         }
     }
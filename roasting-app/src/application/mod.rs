@@ -1,3 +1,31 @@
+mod apply_retention;
+mod ask_follow_up;
+mod ban_ip;
+mod detect_site_changes;
 mod generate_roast;
+mod notify_user;
+mod opt_out_domain;
+mod report_scrape_health;
+mod roast_queue;
+mod schedule_roast;
+mod suggest_battle_opponent;
+mod toggle_vote;
+mod track_roast_analytics;
+mod warm_popular_scrapes;
+mod warmup;
 
+pub use apply_retention::{ApplyRetention, RetentionPolicy};
+pub use ask_follow_up::{AskFollowUp, FollowUpError};
+pub use ban_ip::{BanError, BanIp};
+pub use detect_site_changes::{DetectSiteChanges, SiteChangeRun};
 pub use generate_roast::GenerateRoast;
+pub use notify_user::{NotifyUser, PreferenceError};
+pub use opt_out_domain::{OptOutDomain, OptOutError};
+pub use report_scrape_health::ReportScrapeHealth;
+pub use roast_queue::RoastQueue;
+pub use schedule_roast::{ScheduleError, ScheduleRoast};
+pub use suggest_battle_opponent::SuggestBattleOpponent;
+pub use toggle_vote::{ToggleVote, VoteError};
+pub use track_roast_analytics::{FounderError, TrackRoastAnalytics};
+pub use warm_popular_scrapes::{WarmPopularScrapes, WarmRun};
+pub use warmup::Warmup;
@@ -0,0 +1,266 @@
+use crate::domain::{ChannelPreference, DomainOptOut, Language, Notification, NotificationKind};
+use crate::infrastructure::db::{NotificationRepository, RoastRepository, UserRepository, WatchRepository};
+use crate::infrastructure::notification::{
+    EmailChannel, InAppChannel, NotificationChannel, Recipient, TelegramChannel,
+};
+use roasting_errors::AppError;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const MAX_ADDRESS_LEN: usize = 256;
+
+#[derive(Debug)]
+pub enum PreferenceError {
+    UnknownChannel,
+    Invalid(String),
+    Internal(String),
+}
+
+impl PreferenceError {
+    pub fn user_message(&self) -> &str {
+        match self {
+            Self::UnknownChannel => "Unknown notification channel",
+            Self::Invalid(reason) => reason,
+            Self::Internal(_) => "Ada masalah di server. Coba lagi nanti.",
+        }
+    }
+}
+
+/// Delivers notifications to users on every channel they have enabled.
+/// Producers only build a `Notification`; adding a channel means
+/// registering it here, not touching them.
+pub struct NotifyUser {
+    channels: Vec<Arc<dyn NotificationChannel>>,
+    repo: NotificationRepository,
+    user_repo: UserRepository,
+    roast_repo: RoastRepository,
+    watch_repo: WatchRepository,
+    public_base_url: String,
+}
+
+impl NotifyUser {
+    pub fn new(
+        repo: NotificationRepository,
+        user_repo: UserRepository,
+        roast_repo: RoastRepository,
+        watch_repo: WatchRepository,
+        public_base_url: String,
+    ) -> Self {
+        Self {
+            channels: Vec::new(),
+            repo,
+            user_repo,
+            roast_repo,
+            watch_repo,
+            public_base_url,
+        }
+    }
+
+    pub fn with_channel(mut self, channel: Arc<dyn NotificationChannel>) -> Self {
+        self.channels.push(channel);
+        self
+    }
+
+    /// In-app always, plus every external channel whose credentials are set
+    pub fn from_env(
+        repo: NotificationRepository,
+        user_repo: UserRepository,
+        roast_repo: RoastRepository,
+        watch_repo: WatchRepository,
+        public_base_url: String,
+    ) -> Self {
+        let mut notify = Self::new(repo.clone(), user_repo, roast_repo, watch_repo, public_base_url)
+            .with_channel(Arc::new(InAppChannel::new(repo)));
+        if let Some(email) = EmailChannel::from_env() {
+            notify = notify.with_channel(Arc::new(email));
+        }
+        if let Some(telegram) = TelegramChannel::from_env() {
+            notify = notify.with_channel(Arc::new(telegram));
+        }
+        notify
+    }
+
+    pub fn channel_names(&self) -> Vec<&'static str> {
+        self.channels.iter().map(|c| c.name()).collect()
+    }
+
+    /// The user's setting for every registered channel, defaults included
+    pub async fn preferences(&self, user_id: Uuid) -> Result<Vec<ChannelPreference>, AppError> {
+        let stored = self.repo.preferences(user_id).await.map_err(internal)?;
+        Ok(self
+            .channels
+            .iter()
+            .map(|channel| match stored.iter().find(|p| p.channel == channel.name()) {
+                Some(p) => ChannelPreference {
+                    channel: p.channel.clone(),
+                    enabled: p.enabled,
+                    address: p.address.clone(),
+                },
+                None => ChannelPreference {
+                    channel: channel.name().to_string(),
+                    enabled: channel.enabled_by_default(),
+                    address: None,
+                },
+            })
+            .collect())
+    }
+
+    pub async fn set_preference(
+        &self,
+        user_id: Uuid,
+        channel: &str,
+        enabled: bool,
+        address: Option<&str>,
+    ) -> Result<ChannelPreference, PreferenceError> {
+        let Some(channel) = self.channels.iter().find(|c| c.name() == channel) else {
+            return Err(PreferenceError::UnknownChannel);
+        };
+        let address = address
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(str::to_string);
+        if address.as_ref().is_some_and(|a| a.len() > MAX_ADDRESS_LEN) {
+            return Err(PreferenceError::Invalid("Address is too long".to_string()));
+        }
+        if enabled && channel.needs_address() && address.is_none() {
+            return Err(PreferenceError::Invalid(format!(
+                "The {} channel needs an address",
+                channel.name()
+            )));
+        }
+        self.repo
+            .set_preference(user_id, channel.name(), enabled, address.clone())
+            .await
+            .map_err(|e| PreferenceError::Internal(e.to_string()))?;
+        Ok(ChannelPreference {
+            channel: channel.name().to_string(),
+            enabled,
+            address,
+        })
+    }
+
+    /// Deliver on every enabled channel, returning how many succeeded.
+    /// A failing channel is logged and doesn't stop the others.
+    pub async fn notify(&self, user_id: Uuid, notification: &Notification) -> Result<usize, AppError> {
+        let Some(user) = self.user_repo.find_by_id(user_id).await.map_err(internal)? else {
+            return Ok(0);
+        };
+        let preferences = self.preferences(user_id).await?;
+
+        let mut delivered = 0;
+        for channel in &self.channels {
+            let Some(preference) = preferences.iter().find(|p| p.channel == channel.name()) else {
+                continue;
+            };
+            if !preference.enabled || (channel.needs_address() && preference.address.is_none()) {
+                continue;
+            }
+            let recipient = Recipient {
+                user_id,
+                email: user.email.clone(),
+                address: preference.address.clone(),
+            };
+            match channel.send(&recipient, notification).await {
+                Ok(()) => delivered += 1,
+                Err(e) => tracing::warn!(
+                    "Failed to send {} notification via {}: {}",
+                    notification.kind.as_str(),
+                    channel.name(),
+                    e
+                ),
+            }
+        }
+        Ok(delivered)
+    }
+
+    /// Tell a roast's creator about `kind` in the background; roasts
+    /// without a creator are skipped
+    pub fn roast_event(self: &Arc<Self>, roast_id: Uuid, kind: NotificationKind) {
+        let notify = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = notify.send_roast_event(roast_id, kind).await {
+                tracing::warn!("Failed to notify about roast {}: {}", roast_id, e);
+            }
+        });
+    }
+
+    async fn send_roast_event(&self, roast_id: Uuid, kind: NotificationKind) -> Result<(), AppError> {
+        let Some(roast) = self.roast_repo.find_by_id(roast_id).await.map_err(internal)? else {
+            return Ok(());
+        };
+        let Some(user_id) = roast.user_id else {
+            return Ok(());
+        };
+        let notification = Notification::for_roast(
+            kind,
+            &roast.startup_name,
+            roast.fire_count,
+            roast.language.parse().unwrap_or(Language::Id),
+            format!("{}/r/{}", self.public_base_url, roast_id),
+        );
+        self.notify(user_id, &notification).await?;
+        Ok(())
+    }
+
+    /// Tell everyone watching the roast's startup about `kind` in the
+    /// background, except `actor`, who caused it. Hidden roasts are skipped.
+    pub fn watch_event(self: &Arc<Self>, roast_id: Uuid, kind: NotificationKind, actor: Option<Uuid>) {
+        let notify = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = notify.send_watch_event(roast_id, kind, actor).await {
+                tracing::warn!("Failed to notify watchers of roast {}: {}", roast_id, e);
+            }
+        });
+    }
+
+    async fn send_watch_event(
+        &self,
+        roast_id: Uuid,
+        kind: NotificationKind,
+        actor: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        let Some(roast) = self.roast_repo.find_by_id(roast_id).await.map_err(internal)? else {
+            return Ok(());
+        };
+        if roast.removed_at.is_some() || roast.publish_at.is_some() {
+            return Ok(());
+        }
+        let Some(domain) = DomainOptOut::normalize_domain(&roast.startup_url) else {
+            return Ok(());
+        };
+        let notification = Notification::for_roast(
+            kind,
+            &roast.startup_name,
+            roast.fire_count,
+            roast.language.parse().unwrap_or(Language::Id),
+            format!("{}/r/{}", self.public_base_url, roast_id),
+        );
+        self.notify_watchers(&domain, &notification, actor).await?;
+        Ok(())
+    }
+
+    /// Deliver to everyone watching `domain` except `actor`, returning how
+    /// many watchers were notified; one watcher failing doesn't stop the rest
+    pub async fn notify_watchers(
+        &self,
+        domain: &str,
+        notification: &Notification,
+        actor: Option<Uuid>,
+    ) -> Result<usize, AppError> {
+        let mut notified = 0;
+        for watcher in self.watch_repo.watchers(domain).await.map_err(internal)? {
+            if Some(watcher) == actor {
+                continue;
+            }
+            match self.notify(watcher, notification).await {
+                Ok(_) => notified += 1,
+                Err(e) => tracing::warn!("Failed to notify watcher {} of {}: {}", watcher, domain, e),
+            }
+        }
+        Ok(notified)
+    }
+}
+
+fn internal(e: sea_orm::DbErr) -> AppError {
+    AppError::Internal(e.to_string())
+}
@@ -0,0 +1,171 @@
+use crate::domain::{DomainOptOut, RemovalKind};
+use crate::infrastructure::db::{ModerationRepository, OptOutRepository, RoastRepository};
+use crate::infrastructure::scraper::read_capped;
+use crate::infrastructure::security::{InputSanitizer, OptOutList};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// The token file is one line; anything bigger is not what we asked for
+const MAX_TOKEN_FILE_BYTES: usize = 4096;
+
+#[derive(Debug)]
+pub enum OptOutError {
+    InvalidDomain,
+    NotRequested,
+    Unverified(String),
+    Internal(String),
+}
+
+impl OptOutError {
+    pub fn user_message(&self) -> &str {
+        match self {
+            Self::InvalidDomain => "Domain tidak valid",
+            Self::NotRequested => "Minta token opt-out dulu untuk domain ini",
+            Self::Unverified(reason) => reason,
+            Self::Internal(_) => "Ada masalah di server. Coba lagi nanti.",
+        }
+    }
+}
+
+/// Site owners opting their domain out of roasts. Ownership is proven by
+/// serving a token at `DomainOptOut::VERIFICATION_PATH`; once verified the
+/// domain is refused for new roasts and its existing roasts are unlisted.
+pub struct OptOutDomain {
+    opt_out_repo: OptOutRepository,
+    roast_repo: RoastRepository,
+    moderation_repo: ModerationRepository,
+    opt_outs: Arc<OptOutList>,
+    client: reqwest::Client,
+}
+
+impl OptOutDomain {
+    pub fn new(
+        opt_out_repo: OptOutRepository,
+        roast_repo: RoastRepository,
+        moderation_repo: ModerationRepository,
+        opt_outs: Arc<OptOutList>,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .redirect(reqwest::redirect::Policy::limited(3))
+            .user_agent("roasting-startup-optout/1.0")
+            .build()
+            .unwrap_or_default();
+        Self {
+            opt_out_repo,
+            roast_repo,
+            moderation_repo,
+            opt_outs,
+            client,
+        }
+    }
+
+    /// Load verified domains into the in-memory list; called at startup
+    pub async fn load(&self) -> Result<usize, OptOutError> {
+        let domains = self
+            .opt_out_repo
+            .list_verified_domains()
+            .await
+            .map_err(|e| OptOutError::Internal(e.to_string()))?;
+        self.opt_outs.replace(domains);
+        Ok(self.opt_outs.len())
+    }
+
+    /// Issue (or re-issue) the verification token for a domain
+    pub async fn request(
+        &self,
+        domain: &str,
+        user_id: Option<Uuid>,
+    ) -> Result<DomainOptOut, OptOutError> {
+        let domain = DomainOptOut::normalize_domain(domain).ok_or(OptOutError::InvalidDomain)?;
+        self.opt_out_repo
+            .request(&domain, user_id)
+            .await
+            .map(DomainOptOut::from)
+            .map_err(|e| OptOutError::Internal(e.to_string()))
+    }
+
+    /// Check the token file and, if it matches, enforce the opt-out.
+    /// Returns the opt-out and how many existing roasts were unlisted.
+    pub async fn verify(&self, domain: &str) -> Result<(DomainOptOut, usize), OptOutError> {
+        let internal = |e: sea_orm::DbErr| OptOutError::Internal(e.to_string());
+        let domain = DomainOptOut::normalize_domain(domain).ok_or(OptOutError::InvalidDomain)?;
+        let pending = self
+            .opt_out_repo
+            .find(&domain)
+            .await
+            .map_err(internal)?
+            .ok_or(OptOutError::NotRequested)?;
+
+        if pending.verified_at.is_none() {
+            self.check_token(&DomainOptOut::from(pending.clone()))
+                .await?;
+        }
+        let verified = self
+            .opt_out_repo
+            .mark_verified(pending)
+            .await
+            .map_err(internal)?;
+        self.opt_outs.insert(&domain);
+
+        let mut unlisted = 0;
+        for roast_id in self
+            .roast_repo
+            .find_listed_ids_for_domain(&domain)
+            .await
+            .map_err(internal)?
+        {
+            if self
+                .moderation_repo
+                .remove_roast(roast_id, RemovalKind::OptOut, "Site owner opted out")
+                .await
+                .map_err(internal)?
+            {
+                unlisted += 1;
+            }
+        }
+        tracing::info!("Domain {} opted out, {} roasts unlisted", domain, unlisted);
+
+        Ok((DomainOptOut::from(verified), unlisted))
+    }
+
+    async fn check_token(&self, opt_out: &DomainOptOut) -> Result<(), OptOutError> {
+        let url = opt_out.verification_url();
+        // Same rules as roast URLs: no private hosts or odd schemes
+        InputSanitizer::validate_url(&url).map_err(|_| OptOutError::InvalidDomain)?;
+        check_token_file(&self.client, &url, &opt_out.token)
+            .await
+            .map_err(OptOutError::Unverified)
+    }
+}
+
+/// Whether `url` serves `token` on a line of its own; `Err` is the reason
+/// shown to the site owner
+pub(super) async fn check_token_file(
+    client: &reqwest::Client,
+    url: &str,
+    token: &str,
+) -> Result<(), String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|_| format!("Gagal mengakses {}", url))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "{} membalas HTTP {}",
+            url,
+            response.status().as_u16()
+        ));
+    }
+    let body = read_capped(response, MAX_TOKEN_FILE_BYTES)
+        .await
+        .map_err(|_| format!("Gagal membaca {}", url))?;
+    let body = String::from_utf8_lossy(&body);
+    if body.lines().any(|line| line.trim() == token) {
+        Ok(())
+    } else {
+        Err(format!("Token tidak ditemukan di {}", url))
+    }
+}
@@ -0,0 +1,97 @@
+use crate::domain::ScrapeHealthReport;
+use crate::infrastructure::db::ScrapeLogRepository;
+use crate::infrastructure::ops_channel::OpsChannel;
+use chrono::{Duration, Utc};
+use roasting_errors::AppError;
+
+const TOP_FAILING_DOMAINS: u64 = 10;
+/// Raw attempts are only needed for the daily window
+const ATTEMPT_RETENTION_DAYS: i64 = 7;
+
+/// Summarises scraping success per method and the worst domains, stores the
+/// report and posts it to the ops channel
+pub struct ReportScrapeHealth {
+    repo: ScrapeLogRepository,
+    ops: OpsChannel,
+    period_hours: i64,
+}
+
+impl ReportScrapeHealth {
+    pub fn new(repo: ScrapeLogRepository, ops: OpsChannel, period_hours: i64) -> Self {
+        Self {
+            repo,
+            ops,
+            period_hours,
+        }
+    }
+
+    /// SCRAPE_REPORT_SCHEDULE=hourly reports every hour; anything else daily
+    pub fn from_env(repo: ScrapeLogRepository) -> Self {
+        let period_hours = match std::env::var("SCRAPE_REPORT_SCHEDULE")
+            .as_deref()
+            .map(str::trim)
+        {
+            Ok("hourly") => 1,
+            _ => 24,
+        };
+        Self::new(repo, OpsChannel::from_env(), period_hours)
+    }
+
+    /// Also the interval between scheduled runs
+    pub fn period_hours(&self) -> i64 {
+        self.period_hours
+    }
+
+    /// Build a report over the last `hours` without storing or posting it
+    pub async fn preview(&self, hours: i64) -> Result<ScrapeHealthReport, AppError> {
+        let since = Utc::now() - Duration::hours(hours);
+        let methods = self.repo.method_stats(since).await.map_err(internal)?;
+        let failing_domains = self
+            .repo
+            .failing_domains(since, TOP_FAILING_DOMAINS)
+            .await
+            .map_err(internal)?;
+        Ok(ScrapeHealthReport {
+            period_hours: hours,
+            generated_at: Utc::now(),
+            methods,
+            failing_domains,
+        })
+    }
+
+    /// Scheduled run: report on the configured period, store it, post it,
+    /// and drop attempts past the retention window
+    pub async fn execute(&self) -> Result<ScrapeHealthReport, AppError> {
+        let report = self.preview(self.period_hours).await?;
+        let json = serde_json::to_string(&report).map_err(|e| AppError::Internal(e.to_string()))?;
+        self.repo
+            .save_report(self.period_hours, &json)
+            .await
+            .map_err(internal)?;
+
+        if let Err(e) = self.ops.post(&report.summary()).await {
+            tracing::warn!("Failed to post scrape health report: {}", e);
+        }
+
+        let cutoff = Utc::now() - Duration::days(ATTEMPT_RETENTION_DAYS);
+        match self.repo.prune_before(cutoff).await {
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Pruned {} old scrape attempts", n),
+            Err(e) => tracing::warn!("Failed to prune scrape attempts: {}", e),
+        }
+        Ok(report)
+    }
+
+    /// Stored reports, newest first
+    pub async fn history(&self, limit: u64) -> Result<Vec<ScrapeHealthReport>, AppError> {
+        let rows = self.repo.recent_reports(limit).await.map_err(internal)?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| serde_json::from_str(&row.report).ok())
+            .collect())
+    }
+}
+
+fn internal(e: sea_orm::DbErr) -> AppError {
+    AppError::Internal(e.to_string())
+}
@@ -0,0 +1,135 @@
+use super::{GenerateRoast, NotifyUser};
+use crate::domain::{JobStatus, NotificationKind, PersistedRoast, RoastJob, RoastOptions};
+use crate::infrastructure::db::{RoastJobRepository, RoastRepository};
+use crate::infrastructure::security::CostTracker;
+use roasting_errors::AppError;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const MAX_ATTEMPTS: i32 = 5;
+const BATCH_SIZE: u64 = 5;
+
+/// Parks roast requests while the LLM is down or the daily budget is spent,
+/// and generates them once capacity is back
+pub struct RoastQueue {
+    generate_roast: Arc<GenerateRoast>,
+    roast_repo: RoastRepository,
+    job_repo: RoastJobRepository,
+    cost_tracker: Arc<CostTracker>,
+    notify_user: Arc<NotifyUser>,
+}
+
+impl RoastQueue {
+    pub fn new(
+        generate_roast: Arc<GenerateRoast>,
+        roast_repo: RoastRepository,
+        job_repo: RoastJobRepository,
+        cost_tracker: Arc<CostTracker>,
+        notify_user: Arc<NotifyUser>,
+    ) -> Self {
+        Self {
+            generate_roast,
+            roast_repo,
+            job_repo,
+            cost_tracker,
+            notify_user,
+        }
+    }
+
+    pub async fn enqueue(
+        &self,
+        url: &str,
+        options: RoastOptions,
+        user_id: Option<Uuid>,
+        anonymous: bool,
+        dedicated_to: Option<&str>,
+    ) -> Result<RoastJob, AppError> {
+        self.job_repo
+            .create_deferred(url, options, user_id, anonymous, dedicated_to)
+            .await
+            .map(RoastJob::from)
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+
+    /// Run deferred jobs until the batch or the daily budget runs out,
+    /// returning how many completed
+    pub async fn process_deferred(&self) -> Result<usize, AppError> {
+        let jobs = self
+            .job_repo
+            .list_deferred(BATCH_SIZE)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        let mut completed = 0;
+        for job in jobs {
+            if self.cost_tracker.check_and_increment().is_err() {
+                break;
+            }
+
+            let job = self
+                .job_repo
+                .set_status(job, JobStatus::Running, None, None)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+            let job_id = job.id;
+
+            let outcome = self.run(&RoastJob::from(job.clone())).await;
+            let update = match outcome {
+                Ok(roast_id) => {
+                    completed += 1;
+                    tracing::info!("Deferred roast job {} completed as roast {}", job_id, roast_id);
+                    self.notify_user.roast_event(roast_id, NotificationKind::RoastReady);
+                    self.job_repo
+                        .set_status(job, JobStatus::Completed, Some(roast_id), None)
+                        .await
+                }
+                Err(e) => {
+                    // Only LLM outages are worth retrying; bad URLs will not fix themselves
+                    let retryable = matches!(
+                        e,
+                        AppError::OpenRouterError(_) | AppError::LlmError(_) | AppError::QueueFull(_)
+                    );
+                    let status = if retryable && job.attempts < MAX_ATTEMPTS {
+                        JobStatus::Deferred
+                    } else {
+                        JobStatus::Failed
+                    };
+                    tracing::warn!("Deferred roast job {} {}: {}", job_id, status.as_str(), e);
+                    self.job_repo
+                        .set_status(job, status, None, Some(e.to_string()))
+                        .await
+                }
+            };
+            update.map_err(|e| AppError::Internal(e.to_string()))?;
+        }
+
+        Ok(completed)
+    }
+
+    async fn run(&self, job: &RoastJob) -> Result<Uuid, AppError> {
+        let roast = match self.generate_roast.cached(&job.url, job.options).await {
+            Some(roast) => roast,
+            None => self.generate_roast.execute(job.url.clone(), job.options).await?,
+        };
+        let persisted = PersistedRoast::from_roast(&roast, job.url.clone(), job.user_id)
+            .with_anonymous(job.anonymous)
+            .with_dedication(job.dedicated_to.clone())
+            .with_prompt_variant(self.generate_roast.prompt_variant());
+        let saved = self
+            .roast_repo
+            .create(&persisted)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        self.notify_user
+            .watch_event(saved.id, NotificationKind::WatchedRoast, job.user_id);
+        Ok(saved.id)
+    }
+
+    /// Put jobs interrupted by a restart back in the queue
+    pub async fn recover(&self) -> Result<u64, AppError> {
+        self.job_repo
+            .requeue_running()
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))
+    }
+}
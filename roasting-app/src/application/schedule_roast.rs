@@ -0,0 +1,88 @@
+use super::NotifyUser;
+use crate::domain::NotificationKind;
+use crate::infrastructure::db::RoastRepository;
+use chrono::{DateTime, Duration, Utc};
+use roasting_errors::AppError;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Longest embargo a roast can be held back for
+const MAX_EMBARGO_DAYS: i64 = 365;
+
+#[derive(Debug)]
+pub enum ScheduleError {
+    NotFound,
+    Invalid(String),
+    Internal(String),
+}
+
+impl ScheduleError {
+    pub fn user_message(&self) -> &str {
+        match self {
+            Self::NotFound => "Roast not found",
+            Self::Invalid(reason) => reason,
+            Self::Internal(_) => "Ada masalah di server. Coba lagi nanti.",
+        }
+    }
+}
+
+/// Embargoed roasts, e.g. revealed at demo day: the owner picks a publish
+/// time and the scheduler takes the roast live once it passes
+pub struct ScheduleRoast {
+    roast_repo: RoastRepository,
+    notify_user: Arc<NotifyUser>,
+}
+
+impl ScheduleRoast {
+    pub fn new(roast_repo: RoastRepository, notify_user: Arc<NotifyUser>) -> Self {
+        Self {
+            roast_repo,
+            notify_user,
+        }
+    }
+
+    /// Hide the roast until `publish_at`, or publish it right away with `None`
+    pub async fn schedule(
+        &self,
+        roast_id: Uuid,
+        owner_id: Uuid,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<DateTime<Utc>>, ScheduleError> {
+        if let Some(at) = publish_at {
+            let now = Utc::now();
+            if at <= now {
+                return Err(ScheduleError::Invalid("Publish time must be in the future".to_string()));
+            }
+            if at > now + Duration::days(MAX_EMBARGO_DAYS) {
+                return Err(ScheduleError::Invalid(format!(
+                    "Publish time must be within {} days",
+                    MAX_EMBARGO_DAYS
+                )));
+            }
+        }
+
+        match self.roast_repo.schedule(roast_id, owner_id, publish_at).await {
+            Ok(Some(roast)) => Ok(roast.publish_at),
+            Ok(None) => Err(ScheduleError::NotFound),
+            Err(e) => Err(ScheduleError::Internal(e.to_string())),
+        }
+    }
+
+    /// Scheduler tick: publish every roast whose embargo ended and tell
+    /// their creators, returning how many went live
+    pub async fn publish_due(&self) -> Result<usize, AppError> {
+        let published = self
+            .roast_repo
+            .publish_due(Utc::now())
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        for roast_id in &published {
+            tracing::info!("Embargo ended, roast {} is live", roast_id);
+            self.notify_user
+                .roast_event(*roast_id, NotificationKind::RoastPublished);
+            self.notify_user
+                .watch_event(*roast_id, NotificationKind::WatchedRoast, None);
+        }
+        Ok(published.len())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::domain::{BattleSuggestion, StartupCategory};
+use crate::infrastructure::db::RoastRepository;
+use crate::infrastructure::embedding::{cosine_similarity, HashingEmbedder};
+use dashmap::DashMap;
+use roasting_errors::AppError;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const CANDIDATE_POOL_SIZE: u64 = 200;
+const SIMILARITY_WEIGHT: f32 = 0.7;
+const CATEGORY_WEIGHT: f32 = 0.3;
+const MIN_SCORE: f32 = 0.2;
+/// Embeddings kept between calls; a few candidate pools' worth
+const MAX_CACHED_EMBEDDINGS: usize = 1000;
+
+/// Suggests a similar startup to pit against a roast in battle mode
+pub struct SuggestBattleOpponent {
+    roast_repo: RoastRepository,
+    embedder: HashingEmbedder,
+    /// Per roast: a hash of the embedded text, so a regenerated roast is
+    /// embedded again, and its embedding
+    embeddings: DashMap<Uuid, (u64, Arc<Vec<f32>>)>,
+}
+
+impl SuggestBattleOpponent {
+    pub fn new(roast_repo: RoastRepository) -> Self {
+        Self {
+            roast_repo,
+            embedder: HashingEmbedder::new(),
+            embeddings: DashMap::new(),
+        }
+    }
+
+    /// The roast's embedding, computed once per text
+    fn embedding(&self, id: Uuid, startup_name: &str, roast_text: &str) -> Arc<Vec<f32>> {
+        let mut hasher = DefaultHasher::new();
+        (startup_name, roast_text).hash(&mut hasher);
+        let text_hash = hasher.finish();
+        if let Some(entry) = self.embeddings.get(&id).filter(|e| e.0 == text_hash) {
+            return entry.1.clone();
+        }
+
+        let embedding = Arc::new(self.embedder.embed(&format!("{} {}", startup_name, roast_text)));
+        if self.embeddings.len() >= MAX_CACHED_EMBEDDINGS {
+            self.embeddings.clear();
+        }
+        self.embeddings.insert(id, (text_hash, embedding.clone()));
+        embedding
+    }
+
+    pub async fn execute(&self, roast_id: Uuid) -> Result<Option<BattleSuggestion>, AppError> {
+        let roast = self
+            .roast_repo
+            .find_by_id(roast_id)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?
+            .ok_or(AppError::NotFound)?;
+
+        let candidates = self
+            .roast_repo
+            .find_recent(CANDIDATE_POOL_SIZE)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+
+        let category: StartupCategory = roast.category.parse().unwrap_or_default();
+        let embedding = self.embedding(roast.id, &roast.startup_name, &roast.roast_text);
+        let own_host = host_of(&roast.startup_url);
+
+        let best = candidates
+            .into_iter()
+            .filter(|c| c.id != roast.id && host_of(&c.startup_url) != own_host)
+            .map(|c| {
+                let candidate_category: StartupCategory = c.category.parse().unwrap_or_default();
+                let similarity = cosine_similarity(
+                    &embedding,
+                    &self.embedding(c.id, &c.startup_name, &c.roast_text),
+                );
+                let same_category =
+                    category != StartupCategory::Other && candidate_category == category;
+                let score = SIMILARITY_WEIGHT * similarity
+                    + if same_category { CATEGORY_WEIGHT } else { 0.0 };
+
+                BattleSuggestion {
+                    opponent_id: c.id,
+                    opponent_name: c.startup_name,
+                    opponent_category: candidate_category,
+                    similarity,
+                    same_category,
+                    score,
+                }
+            })
+            .filter(|s| s.score >= MIN_SCORE)
+            .max_by(|a, b| a.score.total_cmp(&b.score));
+
+        if let Some(ref suggestion) = best {
+            tracing::debug!(
+                "Battle suggestion for {}: {} (score {:.2})",
+                roast_id,
+                suggestion.opponent_id,
+                suggestion.score
+            );
+        }
+
+        Ok(best)
+    }
+}
+
+fn host_of(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.trim_start_matches("www.").to_string()))
+        .unwrap_or_else(|| url.to_string())
+}
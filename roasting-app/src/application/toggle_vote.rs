@@ -0,0 +1,117 @@
+use crate::application::NotifyUser;
+use crate::domain::{is_fire_milestone, NotificationKind, VotePolicy, VoteRejection, VoteResult};
+use crate::infrastructure::db::{RoastRepository, UserRepository, VoteRepository};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum VoteError {
+    NotFound,
+    OwnRoast,
+    Internal(String),
+}
+
+impl VoteError {
+    pub fn user_message(&self) -> &str {
+        match self {
+            Self::NotFound => "Roast not found",
+            Self::OwnRoast => "Cannot vote on your own roast",
+            Self::Internal(_) => "Failed to toggle vote",
+        }
+    }
+}
+
+impl From<sea_orm::DbErr> for VoteError {
+    fn from(e: sea_orm::DbErr) -> Self {
+        Self::Internal(e.to_string())
+    }
+}
+
+/// The one place fires are added and taken back. New fires are weighed by
+/// `VotePolicy`; taking a fire back is always allowed.
+pub struct ToggleVote {
+    vote_repo: VoteRepository,
+    roast_repo: RoastRepository,
+    user_repo: UserRepository,
+    notify: Arc<NotifyUser>,
+    policy: VotePolicy,
+}
+
+impl ToggleVote {
+    pub fn new(
+        vote_repo: VoteRepository,
+        roast_repo: RoastRepository,
+        user_repo: UserRepository,
+        notify: Arc<NotifyUser>,
+        policy: VotePolicy,
+    ) -> Self {
+        Self {
+            vote_repo,
+            roast_repo,
+            user_repo,
+            notify,
+            policy,
+        }
+    }
+
+    /// VOTE_NEW_ACCOUNT_DAYS (default 3) and VOTE_NEW_ACCOUNT_WEIGHT (default 0.5)
+    pub fn from_env(
+        vote_repo: VoteRepository,
+        roast_repo: RoastRepository,
+        user_repo: UserRepository,
+        notify: Arc<NotifyUser>,
+    ) -> Self {
+        let default = VotePolicy::default();
+        let policy = VotePolicy::new(
+            std::env::var("VOTE_NEW_ACCOUNT_DAYS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default.new_account_days),
+            std::env::var("VOTE_NEW_ACCOUNT_WEIGHT")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default.new_account_weight),
+        );
+        Self::new(vote_repo, roast_repo, user_repo, notify, policy)
+    }
+
+    pub async fn execute(&self, user_id: Uuid, roast_id: Uuid) -> Result<VoteResult, VoteError> {
+        let roast = self
+            .roast_repo
+            .find_by_id(roast_id)
+            .await?
+            .ok_or(VoteError::NotFound)?;
+
+        if self.vote_repo.exists(user_id, roast_id).await? {
+            self.vote_repo.delete(user_id, roast_id).await?;
+            let new_fire_count = self.roast_repo.recount_fire_count(roast_id).await?;
+            return Ok(VoteResult {
+                voted: false,
+                new_fire_count,
+            });
+        }
+
+        let voter = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| VoteError::Internal(format!("Voter {} not found", user_id)))?;
+        let weight = self
+            .policy
+            .weigh(user_id, voter.created_at, roast.user_id, chrono::Utc::now())
+            .map_err(|VoteRejection::OwnRoast| VoteError::OwnRoast)?;
+        self.vote_repo.create(user_id, roast_id, weight).await?;
+        let new_fire_count = self.roast_repo.recount_fire_count(roast_id).await?;
+
+        if new_fire_count > roast.fire_count
+            && is_fire_milestone(new_fire_count)
+            && self.vote_repo.claim_milestone(roast_id, new_fire_count).await?
+        {
+            self.notify.roast_event(roast_id, NotificationKind::RoastFired);
+        }
+        Ok(VoteResult {
+            voted: true,
+            new_fire_count,
+        })
+    }
+}
@@ -0,0 +1,222 @@
+use super::opt_out_domain::check_token_file;
+use crate::domain::{DomainOptOut, FounderAnalytics, FounderClaim, ShareChannel};
+use crate::infrastructure::db::{FounderClaimRepository, RoastRepository, RoastStatsRepository};
+use crate::infrastructure::security::InputSanitizer;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Referrers beyond this are too thin to be worth a row in the dashboard
+const MAX_REFERRERS: u64 = 10;
+/// Link previews and crawlers fetch /r/{id} too; none of them is a reader
+const CRAWLER_AGENTS: &[&str] = &[
+    "bot",
+    "crawler",
+    "spider",
+    "preview",
+    "facebookexternalhit",
+    "whatsapp",
+    "telegram",
+    "slurp",
+    "headless",
+];
+
+#[derive(Debug)]
+pub enum FounderError {
+    InvalidDomain,
+    NotClaimed,
+    Unverified(String),
+    /// The user has no verified claim on the domain
+    NotFounder,
+    Internal(String),
+}
+
+impl FounderError {
+    pub fn user_message(&self) -> &str {
+        match self {
+            Self::InvalidDomain => "Domain tidak valid",
+            Self::NotClaimed => "Klaim domain ini dulu untuk dapat token verifikasi",
+            Self::Unverified(reason) => reason,
+            Self::NotFounder => "Statistik ini cuma untuk founder yang sudah terverifikasi",
+            Self::Internal(_) => "Ada masalah di server. Coba lagi nanti.",
+        }
+    }
+}
+
+/// Private roast stats for verified founders: page views by day and
+/// referrer, shares per channel and how fast the fires come in. Founders
+/// prove control of their domain with a token file, as for opt-outs.
+pub struct TrackRoastAnalytics {
+    claim_repo: FounderClaimRepository,
+    stats_repo: RoastStatsRepository,
+    roast_repo: RoastRepository,
+    client: reqwest::Client,
+}
+
+impl TrackRoastAnalytics {
+    pub fn new(
+        claim_repo: FounderClaimRepository,
+        stats_repo: RoastStatsRepository,
+        roast_repo: RoastRepository,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .redirect(reqwest::redirect::Policy::limited(3))
+            .user_agent("roasting-startup-founder/1.0")
+            .build()
+            .unwrap_or_default();
+        Self {
+            claim_repo,
+            stats_repo,
+            roast_repo,
+            client,
+        }
+    }
+
+    /// Issue (or re-issue) the user's verification token for a domain
+    pub async fn claim(&self, user_id: Uuid, domain: &str) -> Result<FounderClaim, FounderError> {
+        let domain = DomainOptOut::normalize_domain(domain).ok_or(FounderError::InvalidDomain)?;
+        self.claim_repo
+            .claim(user_id, &domain)
+            .await
+            .map(FounderClaim::from)
+            .map_err(|e| FounderError::Internal(e.to_string()))
+    }
+
+    /// Check the token file and mark the claim verified when it matches
+    pub async fn verify(&self, user_id: Uuid, domain: &str) -> Result<FounderClaim, FounderError> {
+        let internal = |e: sea_orm::DbErr| FounderError::Internal(e.to_string());
+        let domain = DomainOptOut::normalize_domain(domain).ok_or(FounderError::InvalidDomain)?;
+        let pending = self
+            .claim_repo
+            .find(user_id, &domain)
+            .await
+            .map_err(internal)?
+            .ok_or(FounderError::NotClaimed)?;
+
+        if pending.verified_at.is_none() {
+            let claim = FounderClaim::from(pending.clone());
+            let url = claim.verification_url();
+            InputSanitizer::validate_url(&url).map_err(|_| FounderError::InvalidDomain)?;
+            check_token_file(&self.client, &url, &claim.token)
+                .await
+                .map_err(FounderError::Unverified)?;
+        }
+        let verified = self.claim_repo.mark_verified(pending).await.map_err(internal)?;
+        tracing::info!("User {} verified as founder of {}", user_id, domain);
+        Ok(FounderClaim::from(verified))
+    }
+
+    pub async fn is_founder(&self, user_id: Uuid, domain: &str) -> bool {
+        self.claim_repo
+            .is_verified(user_id, domain)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to check founder claim on {}: {}", domain, e);
+                false
+            })
+    }
+
+    /// Stats over every listed roast of `domain` for the last `days` days
+    pub async fn report(
+        &self,
+        user_id: Uuid,
+        domain: &str,
+        days: u32,
+    ) -> Result<FounderAnalytics, FounderError> {
+        let internal = |e: sea_orm::DbErr| FounderError::Internal(e.to_string());
+        let domain = DomainOptOut::normalize_domain(domain).ok_or(FounderError::InvalidDomain)?;
+        if !self.claim_repo.is_verified(user_id, &domain).await.map_err(internal)? {
+            return Err(FounderError::NotFounder);
+        }
+
+        let days = days.clamp(1, FounderAnalytics::MAX_DAYS);
+        // Today counts as one of the days
+        let since = chrono::Utc::now().date_naive() - chrono::Days::new(u64::from(days) - 1);
+        let roast_ids = self
+            .roast_repo
+            .find_listed_ids_for_domain(&domain)
+            .await
+            .map_err(internal)?;
+        if roast_ids.is_empty() {
+            return Ok(FounderAnalytics {
+                domain,
+                days,
+                roasts: 0,
+                views: Vec::new(),
+                referrers: Vec::new(),
+                votes: Vec::new(),
+                shares: Vec::new(),
+            });
+        }
+
+        let (views, referrers, votes, shares) = tokio::try_join!(
+            self.stats_repo.daily_views(&roast_ids, since),
+            self.stats_repo.referrers(&roast_ids, since, MAX_REFERRERS),
+            self.stats_repo.daily_votes(&roast_ids, since),
+            self.stats_repo.shares(&roast_ids, since),
+        )
+        .map_err(internal)?;
+        Ok(FounderAnalytics {
+            domain,
+            days,
+            roasts: roast_ids.len(),
+            views,
+            referrers,
+            votes,
+            shares,
+        })
+    }
+
+    /// Count a page view in the background; crawlers and link previews are skipped
+    pub fn record_view(&self, roast_id: Uuid, referer: Option<&str>, user_agent: Option<&str>) {
+        if user_agent.is_none_or(is_crawler) {
+            return;
+        }
+        let referrer = referrer_host(referer);
+        let stats_repo = self.stats_repo.clone();
+        tokio::spawn(async move {
+            if let Err(e) = stats_repo.record_view(roast_id, &referrer).await {
+                tracing::warn!("Failed to record view of roast {}: {}", roast_id, e);
+            }
+        });
+    }
+
+    pub async fn record_share(&self, roast_id: Uuid, channel: ShareChannel) -> Result<(), FounderError> {
+        self.stats_repo
+            .record_share(roast_id, channel)
+            .await
+            .map_err(|e| FounderError::Internal(e.to_string()))
+    }
+}
+
+fn is_crawler(user_agent: &str) -> bool {
+    let user_agent = user_agent.to_lowercase();
+    CRAWLER_AGENTS.iter().any(|needle| user_agent.contains(needle))
+}
+
+/// Host of the `Referer` header without `www.`; empty when there is none
+fn referrer_host(referer: Option<&str>) -> String {
+    referer
+        .and_then(|r| url::Url::parse(r).ok())
+        .filter(|u| matches!(u.scheme(), "http" | "https"))
+        .and_then(|u| DomainOptOut::normalize_domain(u.host_str()?))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_referrer_and_crawler_filter() {
+        assert_eq!(referrer_host(Some("https://www.google.com/search?q=x")), "google.com");
+        assert_eq!(referrer_host(Some("android-app://com.whatsapp/")), "");
+        assert_eq!(referrer_host(None), "");
+
+        assert!(is_crawler("WhatsApp/2.23.20.0 A"));
+        assert!(is_crawler("Mozilla/5.0 (compatible; Googlebot/2.1)"));
+        assert!(!is_crawler(
+            "Mozilla/5.0 (Linux; Android 14) AppleWebKit/537.36 Chrome/126.0 Mobile Safari/537.36"
+        ));
+    }
+}
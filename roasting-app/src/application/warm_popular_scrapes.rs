@@ -0,0 +1,127 @@
+use crate::application::GenerateRoast;
+use crate::infrastructure::db::{RoastStatsRepository, ScrapeCacheRepository};
+use chrono::{Duration, NaiveTime, Utc};
+use roasting_errors::AppError;
+use std::sync::Arc;
+
+const DEFAULT_DOMAINS: u64 = 20;
+/// How far back roasts and regenerations count towards popularity
+const POPULARITY_WINDOW_DAYS: i64 = 30;
+/// A site scraped this recently (say, by a user's roast) is skipped tonight
+const MIN_REFRESH_AGE_HOURS: i64 = 12;
+/// Pause between sites so a run never bursts outbound requests
+const FETCH_SPACING: std::time::Duration = std::time::Duration::from_secs(10);
+/// 03:00 WIB, when Indonesian sites see the least traffic
+const RUN_AT_UTC_HOUR: u32 = 20;
+
+/// What one run did, for the log
+#[derive(Debug, Default)]
+pub struct WarmRun {
+    pub refreshed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+/// Nightly re-scrape of the most roasted sites into the scrape cache, so
+/// roasting a Tokopedia-sized site during the day never hits it (or its bot
+/// protection) from a user request. Each site is fetched at most once a
+/// night, one at a time.
+pub struct WarmPopularScrapes {
+    generate_roast: Arc<GenerateRoast>,
+    stats_repo: RoastStatsRepository,
+    cache: Option<ScrapeCacheRepository>,
+    /// How many sites are kept warm, 0 turns the job off
+    domains: u64,
+}
+
+impl WarmPopularScrapes {
+    pub fn new(
+        generate_roast: Arc<GenerateRoast>,
+        stats_repo: RoastStatsRepository,
+        cache: Option<ScrapeCacheRepository>,
+        domains: u64,
+    ) -> Self {
+        Self {
+            generate_roast,
+            stats_repo,
+            cache,
+            domains,
+        }
+    }
+
+    /// SCRAPE_WARM_DOMAINS, default 20; 0 turns the warm-up off. Needs the
+    /// scrape cache to keep anything.
+    pub fn from_env(
+        generate_roast: Arc<GenerateRoast>,
+        stats_repo: RoastStatsRepository,
+        cache: Option<ScrapeCacheRepository>,
+    ) -> Self {
+        let domains = std::env::var("SCRAPE_WARM_DOMAINS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_DOMAINS);
+        Self::new(generate_roast, stats_repo, cache, domains)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.cache.is_some() && self.domains > 0
+    }
+
+    /// Time until the next nightly run
+    pub fn until_next_run(&self) -> std::time::Duration {
+        let now = Utc::now();
+        let run_at = NaiveTime::from_hms_opt(RUN_AT_UTC_HOUR, 0, 0).expect("valid hour");
+        let today = now.date_naive().and_time(run_at).and_utc();
+        let next = if today > now { today } else { today + Duration::days(1) };
+        (next - now).to_std().unwrap_or_default()
+    }
+
+    /// Refresh the popular sites, busiest first
+    pub async fn execute(&self) -> Result<WarmRun, AppError> {
+        let mut run = WarmRun::default();
+        let Some(cache) = self.cache.as_ref().filter(|_| self.domains > 0) else {
+            return Ok(run);
+        };
+
+        let since = Utc::now() - Duration::days(POPULARITY_WINDOW_DAYS);
+        let urls = self
+            .stats_repo
+            .popular_urls(since, self.domains)
+            .await
+            .map_err(internal)?;
+        match cache.cool_down_except(&urls).await {
+            Ok(0) => {}
+            Ok(n) => tracing::info!("{} sites left the warm scrape cache", n),
+            Err(e) => tracing::warn!("Failed to cool down scrape cache entries: {}", e),
+        }
+
+        let fresh_cutoff = Utc::now() - Duration::hours(MIN_REFRESH_AGE_HOURS);
+        let mut fetched_any = false;
+        for url in &urls {
+            if cache.cached_at(url).await.map_err(internal)?.is_some_and(|at| at > fresh_cutoff) {
+                cache.mark_warm(url).await.map_err(internal)?;
+                run.skipped += 1;
+                continue;
+            }
+            if fetched_any {
+                tokio::time::sleep(FETCH_SPACING).await;
+            }
+            fetched_any = true;
+            match self.generate_roast.scrape_fresh(url).await {
+                Ok(startup_info) => {
+                    cache.put(url, &startup_info, true).await.map_err(internal)?;
+                    run.refreshed += 1;
+                }
+                Err(e) => {
+                    tracing::warn!("Warming scrape of {} failed: {}", url, e);
+                    run.failed += 1;
+                }
+            }
+        }
+        Ok(run)
+    }
+}
+
+fn internal(e: sea_orm::DbErr) -> AppError {
+    AppError::Internal(e.to_string())
+}
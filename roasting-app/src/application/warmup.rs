@@ -0,0 +1,93 @@
+use crate::application::GenerateRoast;
+use crate::domain::{SubsystemHealth, SubsystemState, WarmupReport};
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+const DEFAULT_STEP_TIMEOUT_SECS: u64 = 20;
+
+/// Startup warmup so the first roast after a deploy doesn't pay for a cold
+/// DNS lookup and TLS handshake or unrendered prompt templates, plus a check
+/// that the headless browser launches. The HTTP clients themselves are built
+/// with the app context. /readyz reports ready once it has finished.
+pub struct Warmup {
+    generate_roast: Arc<GenerateRoast>,
+    step_timeout: Duration,
+    report: RwLock<Option<WarmupReport>>,
+}
+
+impl Warmup {
+    pub fn new(generate_roast: Arc<GenerateRoast>, step_timeout: Duration) -> Self {
+        Self {
+            generate_roast,
+            step_timeout,
+            report: RwLock::new(None),
+        }
+    }
+
+    /// WARMUP_TIMEOUT_SECS bounds each step, and so how long /readyz can stay unready
+    pub fn from_env(generate_roast: Arc<GenerateRoast>) -> Self {
+        let secs = std::env::var("WARMUP_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_STEP_TIMEOUT_SECS);
+        Self::new(generate_roast, Duration::from_secs(secs))
+    }
+
+    /// None until `run` has finished
+    pub fn report(&self) -> Option<WarmupReport> {
+        self.report.read().unwrap().clone()
+    }
+
+    /// Run every step concurrently; a failed step is reported, never fatal
+    pub async fn run(&self) -> WarmupReport {
+        let started = Instant::now();
+        let (prompts, llm, headless) = tokio::join!(
+            self.step("prompt_templates", async {
+                self.generate_roast.warm_up_prompts()
+            }),
+            self.step("openrouter", self.generate_roast.warm_up_llm()),
+            self.step("headless", self.generate_roast.check_headless()),
+        );
+
+        let report = WarmupReport {
+            total_ms: started.elapsed().as_millis() as u64,
+            steps: vec![prompts, llm, headless],
+        };
+        for step in &report.steps {
+            tracing::info!(
+                "Warmup {}: {} ({})",
+                step.name,
+                step.state.as_str(),
+                step.detail.as_deref().unwrap_or_default()
+            );
+        }
+        tracing::info!("Warmup finished in {} ms", report.total_ms);
+        *self.report.write().unwrap() = Some(report.clone());
+        report
+    }
+
+    /// Time out `warm_up` and add its duration to the detail of a step that ran
+    async fn step(
+        &self,
+        name: &str,
+        warm_up: impl Future<Output = SubsystemHealth>,
+    ) -> SubsystemHealth {
+        let started = Instant::now();
+        match tokio::time::timeout(self.step_timeout, warm_up).await {
+            Ok(health) if health.state == SubsystemState::Disabled => health,
+            Ok(health) => {
+                let elapsed = started.elapsed().as_millis();
+                let detail = match &health.detail {
+                    Some(detail) => format!("{}, {} ms", detail, elapsed),
+                    None => format!("{} ms", elapsed),
+                };
+                health.with_detail(detail)
+            }
+            Err(_) => SubsystemHealth::degraded(
+                name,
+                format!("timed out after {}s", self.step_timeout.as_secs()),
+            ),
+        }
+    }
+}
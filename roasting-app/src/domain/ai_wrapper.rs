@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// How likely a startup is a thin wrapper around a hosted LLM API, with the
+/// page signals that led there
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AiWrapperScore {
+    /// 0-100
+    pub confidence: u8,
+    pub signals: Vec<String>,
+}
+
+impl AiWrapperScore {
+    /// Confidence from which the roast card shows the wrapper badge
+    pub const LIKELY: u8 = 50;
+
+    pub fn is_likely(&self) -> bool {
+        self.confidence >= Self::LIKELY
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Operator-authored news item (new modes, personas, maintenance)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Announcement {
+    pub id: uuid::Uuid,
+    pub title: String,
+    pub body: String,
+    pub published: bool,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
@@ -0,0 +1,51 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Set on a `StartupInfo` scraped from the Internet Archive because the live
+/// site could not be reached, or answered with next to nothing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveSnapshot {
+    /// `https://web.archive.org/web/<timestamp>/<url>`
+    pub snapshot_url: String,
+    pub captured_at: Option<DateTime<Utc>>,
+    /// The live site answered, but with too little content to roast
+    #[serde(default)]
+    pub live_site_up: bool,
+}
+
+impl ArchiveSnapshot {
+    /// From the 14-digit `YYYYMMDDhhmmss` timestamp the Wayback Machine uses
+    pub fn new(snapshot_url: String, timestamp: &str) -> Self {
+        let captured_at = NaiveDateTime::parse_from_str(timestamp, "%Y%m%d%H%M%S")
+            .ok()
+            .map(|t| t.and_utc());
+        Self {
+            snapshot_url,
+            captured_at,
+            live_site_up: false,
+        }
+    }
+
+    pub fn with_live_site_up(mut self, live_site_up: bool) -> Self {
+        self.live_site_up = live_site_up;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_wayback_timestamp() {
+        let snapshot = ArchiveSnapshot::new(
+            "https://web.archive.org/web/20230412153000/https://example.com/".to_string(),
+            "20230412153000",
+        );
+        assert_eq!(
+            snapshot.captured_at.map(|t| t.format("%Y-%m-%d %H:%M").to_string()),
+            Some("2023-04-12 15:30".to_string())
+        );
+        assert_eq!(ArchiveSnapshot::new(String::new(), "2023").captured_at, None);
+    }
+}
@@ -0,0 +1,13 @@
+use super::StartupCategory;
+use serde::{Deserialize, Serialize};
+
+/// Suggested opponent for a roast battle ("mau diadu sama startup serupa ini?")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleSuggestion {
+    pub opponent_id: uuid::Uuid,
+    pub opponent_name: String,
+    pub opponent_category: StartupCategory,
+    pub similarity: f32,
+    pub same_category: bool,
+    pub score: f32,
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Coarse startup category, guessed from scraped keywords
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StartupCategory {
+    Fintech,
+    Edtech,
+    Ecommerce,
+    Healthtech,
+    Ai,
+    Logistics,
+    Food,
+    Saas,
+    #[default]
+    Other,
+}
+
+const CATEGORY_KEYWORDS: &[(StartupCategory, &[&str])] = &[
+    (
+        StartupCategory::Fintech,
+        &["payment", "pembayaran", "pinjaman", "loan", "paylater", "wallet", "dompet", "invest", "kredit", "bank"],
+    ),
+    (
+        StartupCategory::Edtech,
+        &["belajar", "kursus", "course", "learning", "sekolah", "bootcamp", "edukasi", "education", "kelas"],
+    ),
+    (
+        StartupCategory::Ecommerce,
+        &["belanja", "shop", "toko", "marketplace", "checkout", "keranjang", "diskon", "produk"],
+    ),
+    (
+        StartupCategory::Healthtech,
+        &["dokter", "doctor", "kesehatan", "health", "klinik", "obat", "apotek", "medical"],
+    ),
+    (
+        StartupCategory::Ai,
+        &["ai", "gpt", "llm", "machine learning", "artificial intelligence", "chatbot", "kecerdasan buatan"],
+    ),
+    (
+        StartupCategory::Logistics,
+        &["kirim", "pengiriman", "delivery", "logistik", "logistics", "kurir", "ekspedisi", "shipping"],
+    ),
+    (
+        StartupCategory::Food,
+        &["makanan", "food", "kuliner", "resto", "restaurant", "kopi", "coffee", "catering"],
+    ),
+    (
+        StartupCategory::Saas,
+        &["saas", "dashboard", "workflow", "crm", "erp", "platform", "api", "software"],
+    ),
+];
+
+impl StartupCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fintech => "fintech",
+            Self::Edtech => "edtech",
+            Self::Ecommerce => "ecommerce",
+            Self::Healthtech => "healthtech",
+            Self::Ai => "ai",
+            Self::Logistics => "logistics",
+            Self::Food => "food",
+            Self::Saas => "saas",
+            Self::Other => "other",
+        }
+    }
+
+    /// Pick the category whose keywords appear most often in the text
+    pub fn classify(text: &str) -> Self {
+        let lower = text.to_lowercase();
+        let words: Vec<&str> = lower
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        CATEGORY_KEYWORDS
+            .iter()
+            .map(|(category, keywords)| {
+                let hits = keywords
+                    .iter()
+                    .map(|kw| {
+                        if kw.contains(' ') {
+                            lower.matches(kw).count()
+                        } else {
+                            words.iter().filter(|w| *w == kw).count()
+                        }
+                    })
+                    .sum::<usize>();
+                (*category, hits)
+            })
+            .filter(|(_, hits)| *hits > 0)
+            .max_by_key(|(_, hits)| *hits)
+            .map(|(category, _)| category)
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for StartupCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CATEGORY_KEYWORDS
+            .iter()
+            .map(|(category, _)| *category)
+            .chain(std::iter::once(Self::Other))
+            .find(|category| category.as_str() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("Unknown category: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            StartupCategory::classify("Aplikasi pinjaman online dan paylater tercepat"),
+            StartupCategory::Fintech
+        );
+        assert_eq!(
+            StartupCategory::classify("Kursus coding bootcamp untuk pemula"),
+            StartupCategory::Edtech
+        );
+        assert_eq!(StartupCategory::classify("Kami hebat"), StartupCategory::Other);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for category in [StartupCategory::Fintech, StartupCategory::Other] {
+            assert_eq!(category.as_str().parse::<StartupCategory>(), Ok(category));
+        }
+    }
+}
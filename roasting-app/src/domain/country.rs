@@ -0,0 +1,170 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+
+/// Live roasts per detected country; `country` is `None` for undetected ones
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountryStats {
+    pub country: Option<String>,
+    pub roasts: i64,
+    pub fires: i64,
+}
+
+/// Countries the detector knows: ISO 3166-1 alpha-2 code, country-code TLD,
+/// calling code, primary-language tag (when specific to the country), and
+/// names or big cities that show up in footer addresses
+struct CountryProfile {
+    code: &'static str,
+    tld: &'static str,
+    calling_code: &'static str,
+    language: Option<&'static str>,
+    places: &'static [&'static str],
+    name_id: &'static str,
+    name_en: &'static str,
+}
+
+const COUNTRIES: &[CountryProfile] = &[
+    CountryProfile { code: "ID", tld: "id", calling_code: "62", language: Some("id"), places: &["indonesia", "jakarta", "bandung", "surabaya", "yogyakarta", "bali", "tangerang"], name_id: "Indonesia", name_en: "Indonesia" },
+    CountryProfile { code: "SG", tld: "sg", calling_code: "65", language: None, places: &["singapore"], name_id: "Singapura", name_en: "Singapore" },
+    CountryProfile { code: "MY", tld: "my", calling_code: "60", language: Some("ms"), places: &["malaysia", "kuala lumpur"], name_id: "Malaysia", name_en: "Malaysia" },
+    CountryProfile { code: "PH", tld: "ph", calling_code: "63", language: Some("tl"), places: &["philippines", "manila"], name_id: "Filipina", name_en: "Philippines" },
+    CountryProfile { code: "TH", tld: "th", calling_code: "66", language: Some("th"), places: &["thailand", "bangkok"], name_id: "Thailand", name_en: "Thailand" },
+    CountryProfile { code: "VN", tld: "vn", calling_code: "84", language: Some("vi"), places: &["vietnam", "viet nam", "hanoi", "ho chi minh"], name_id: "Vietnam", name_en: "Vietnam" },
+    CountryProfile { code: "IN", tld: "in", calling_code: "91", language: Some("hi"), places: &["india", "bangalore", "bengaluru", "mumbai", "new delhi"], name_id: "India", name_en: "India" },
+    CountryProfile { code: "AU", tld: "au", calling_code: "61", language: None, places: &["australia", "sydney", "melbourne"], name_id: "Australia", name_en: "Australia" },
+    CountryProfile { code: "JP", tld: "jp", calling_code: "81", language: Some("ja"), places: &["japan", "tokyo"], name_id: "Jepang", name_en: "Japan" },
+    CountryProfile { code: "GB", tld: "uk", calling_code: "44", language: None, places: &["united kingdom", "london"], name_id: "Inggris", name_en: "United Kingdom" },
+    CountryProfile { code: "DE", tld: "de", calling_code: "49", language: Some("de"), places: &["germany", "deutschland", "berlin"], name_id: "Jerman", name_en: "Germany" },
+    CountryProfile { code: "FR", tld: "fr", calling_code: "33", language: Some("fr"), places: &["france", "paris"], name_id: "Prancis", name_en: "France" },
+    CountryProfile { code: "US", tld: "us", calling_code: "1", language: None, places: &["united states", "usa", "san francisco", "new york"], name_id: "Amerika Serikat", name_en: "United States" },
+];
+
+const TLD_WEIGHT: u32 = 3;
+const LANGUAGE_WEIGHT: u32 = 2;
+const PHONE_WEIGHT: u32 = 2;
+const PLACE_WEIGHT: u32 = 1;
+/// A lone address mention is too weak to tag a startup
+const MIN_SCORE: u32 = 2;
+
+/// Guess a startup's country from its host's TLD, the page's `<html lang>`
+/// (region subtags like `en-SG` count), phone numbers in international
+/// format and place names in its footer/contact text. Returns the ISO
+/// alpha-2 code of the best-scoring country, or `None` without enough signal.
+pub fn detect_country(host: &str, html_lang: Option<&str>, contact_text: &str) -> Option<&'static str> {
+    let mut scores = vec![0u32; COUNTRIES.len()];
+
+    let tld = host.trim_end_matches('.').rsplit('.').next().unwrap_or_default();
+    for (i, country) in COUNTRIES.iter().enumerate() {
+        if tld.eq_ignore_ascii_case(country.tld) {
+            scores[i] += TLD_WEIGHT;
+        }
+    }
+
+    if let Some(lang) = html_lang {
+        let lang = lang.trim().to_lowercase();
+        let (primary, region) = match lang.split_once(['-', '_']) {
+            Some((primary, region)) => (primary, Some(region)),
+            None => (lang.as_str(), None),
+        };
+        for (i, country) in COUNTRIES.iter().enumerate() {
+            let region_match = region.is_some_and(|r| r.eq_ignore_ascii_case(country.code));
+            if region_match || country.language == Some(primary) {
+                scores[i] += LANGUAGE_WEIGHT;
+            }
+        }
+    }
+
+    let text = contact_text.to_lowercase();
+    for digits in phone_prefixes(&text) {
+        // Calling codes are prefix-free, so at most one country matches
+        if let Some(i) = COUNTRIES
+            .iter()
+            .position(|c| digits.starts_with(c.calling_code))
+        {
+            scores[i] += PHONE_WEIGHT;
+        }
+    }
+
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let joined = format!(" {} ", words.join(" "));
+    for (i, country) in COUNTRIES.iter().enumerate() {
+        if country
+            .places
+            .iter()
+            .any(|place| joined.contains(&format!(" {} ", place)))
+        {
+            scores[i] += PLACE_WEIGHT;
+        }
+    }
+
+    scores
+        .iter()
+        .enumerate()
+        .filter(|(_, score)| **score >= MIN_SCORE)
+        .max_by_key(|(_, score)| **score)
+        .map(|(i, _)| COUNTRIES[i].code)
+}
+
+/// Digits right after each `+` that starts an international phone number
+fn phone_prefixes(text: &str) -> Vec<String> {
+    text.split('+')
+        .skip(1)
+        .map(|rest| {
+            rest.chars()
+                .take_while(|c| c.is_ascii_digit() || *c == ' ' || *c == '(' || *c == '-')
+                .filter(char::is_ascii_digit)
+                .collect::<String>()
+        })
+        .filter(|digits| digits.len() >= 8)
+        .collect()
+}
+
+/// Normalizes a country filter: two ASCII letters, uppercased
+pub fn parse_country_code(code: &str) -> Option<String> {
+    let code = code.trim();
+    (code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic()))
+        .then(|| code.to_ascii_uppercase())
+}
+
+/// Display name for a known code, falling back to the code itself
+pub fn country_name(code: &str, language: Language) -> &str {
+    COUNTRIES
+        .iter()
+        .find(|c| c.code.eq_ignore_ascii_case(code))
+        .map_or(code, |c| match language {
+            Language::Id => c.name_id,
+            Language::En => c.name_en,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_country() {
+        assert_eq!(detect_country("kopikilat.co.id", None, ""), Some("ID"));
+        assert_eq!(detect_country("kopikilat.com", Some("id-ID"), ""), Some("ID"));
+        assert_eq!(
+            detect_country("payfast.io", Some("en"), "Call us: +65 6123 4567, Singapore"),
+            Some("SG")
+        );
+        assert_eq!(
+            detect_country("app.example.com", Some("en-US"), "WA +62 812-3456-7890, Jakarta Selatan"),
+            Some("ID")
+        );
+        // One address mention alone is not enough
+        assert_eq!(detect_country("example.com", Some("en"), "Offices in London"), None);
+        assert_eq!(detect_country("example.com", None, ""), None);
+    }
+
+    #[test]
+    fn test_parse_country_code() {
+        assert_eq!(parse_country_code(" id "), Some("ID".to_string()));
+        assert_eq!(parse_country_code("IDN"), None);
+        assert_eq!(country_name("sg", Language::Id), "Singapura");
+        assert_eq!(country_name("ZZ", Language::En), "ZZ");
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Regional slang style for Indonesian roasts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dialect {
+    #[default]
+    Jakarta,
+    Jaksel,
+    Jawa,
+    Medan,
+    Sunda,
+}
+
+impl Dialect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Jakarta => "jakarta",
+            Self::Jaksel => "jaksel",
+            Self::Jawa => "jawa",
+            Self::Medan => "medan",
+            Self::Sunda => "sunda",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Jakarta => "Gaul Jakarta",
+            Self::Jaksel => "Anak Jaksel",
+            Self::Jawa => "Medok Jawa",
+            Self::Medan => "Logat Medan",
+            Self::Sunda => "Sunda Pisan",
+        }
+    }
+
+    pub fn all() -> &'static [Dialect] {
+        &[Self::Jakarta, Self::Jaksel, Self::Jawa, Self::Medan, Self::Sunda]
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|d| d.as_str() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("Unknown dialect: {}", s))
+    }
+}
@@ -0,0 +1,76 @@
+use super::Language;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Days before expiry from which a certificate counts as about to lapse
+const CERT_EXPIRY_WARNING_DAYS: i64 = 14;
+
+/// The certificate the site's HTTPS endpoint presented
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsCertificate {
+    /// Issuer organization, or its common name when there is none
+    pub issuer: String,
+    pub expires_at: DateTime<Utc>,
+    pub self_signed: bool,
+}
+
+/// Mail, DNS and certificate setup of the startup's domain
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DnsTlsInfo {
+    pub domain: String,
+    pub mx_hosts: Vec<String>,
+    /// Who handles the mail, e.g. "Google Workspace", from the MX hosts
+    pub mail_provider: Option<String>,
+    pub nameservers: Vec<String>,
+    /// Who hosts the DNS, e.g. "Cloudflare", from the nameservers
+    pub dns_provider: Option<String>,
+    /// `None` when nothing answered on port 443
+    pub certificate: Option<TlsCertificate>,
+}
+
+impl DnsTlsInfo {
+    /// Facts worth a joke, one short phrase each
+    pub fn facts(&self, now: DateTime<Utc>, language: Language) -> Vec<String> {
+        let mut facts = Vec::new();
+        match (&self.mail_provider, self.mx_hosts.first(), language) {
+            (Some(provider), _, Language::Id) => facts.push(format!("email pakai {}", provider)),
+            (Some(provider), _, Language::En) => facts.push(format!("email runs on {}", provider)),
+            (None, Some(host), Language::Id) => facts.push(format!("server email sendiri ({})", host)),
+            (None, Some(host), Language::En) => facts.push(format!("self-run mail server ({})", host)),
+            (None, None, Language::Id) => facts.push("tidak punya MX, domainnya gak bisa terima email".to_string()),
+            (None, None, Language::En) => facts.push("no MX records, the domain can't receive email".to_string()),
+        }
+        match (&self.dns_provider, language) {
+            (Some(provider), Language::Id) => facts.push(format!("DNS di {}", provider)),
+            (Some(provider), Language::En) => facts.push(format!("DNS hosted on {}", provider)),
+            (None, _) => {}
+        }
+        if let Some(cert) = &self.certificate {
+            let days_left = (cert.expires_at - now).num_days();
+            let expiry = match language {
+                Language::Id if cert.expires_at <= now => {
+                    format!("sertifikat TLS SUDAH KEDALUWARSA sejak {}", cert.expires_at.format("%Y-%m-%d"))
+                }
+                Language::En if cert.expires_at <= now => {
+                    format!("TLS certificate EXPIRED on {}", cert.expires_at.format("%Y-%m-%d"))
+                }
+                Language::Id if days_left < CERT_EXPIRY_WARNING_DAYS => {
+                    format!("sertifikat TLS kedaluwarsa {} hari lagi", days_left)
+                }
+                Language::En if days_left < CERT_EXPIRY_WARNING_DAYS => {
+                    format!("TLS certificate expires in {} days", days_left)
+                }
+                Language::Id => format!("sertifikat TLS dari {}", cert.issuer),
+                Language::En => format!("TLS certificate from {}", cert.issuer),
+            };
+            facts.push(expiry);
+            if cert.self_signed {
+                facts.push(match language {
+                    Language::Id => "sertifikatnya self-signed".to_string(),
+                    Language::En => "the certificate is self-signed".to_string(),
+                });
+            }
+        }
+        facts
+    }
+}
@@ -0,0 +1,33 @@
+use super::Language;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Registration details for the startup's domain, from RDAP
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DomainInfo {
+    pub domain: String,
+    pub registered_at: Option<DateTime<Utc>>,
+    pub registrar: Option<String>,
+}
+
+impl DomainInfo {
+    pub fn age_days(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.registered_at
+            .map(|registered| (now - registered).num_days().max(0))
+    }
+
+    /// Rough human age like "3 minggu" / "3 weeks"
+    pub fn age_text(&self, now: DateTime<Utc>, language: Language) -> Option<String> {
+        let days = self.age_days(now)?;
+        let (amount, unit) = match days {
+            0..=13 => (days, ("hari", "days")),
+            14..=59 => (days / 7, ("minggu", "weeks")),
+            60..=729 => (days / 30, ("bulan", "months")),
+            _ => (days / 365, ("tahun", "years")),
+        };
+        Some(match language {
+            Language::Id => format!("{} {}", amount, unit.0),
+            Language::En => format!("{} {}", amount, unit.1),
+        })
+    }
+}
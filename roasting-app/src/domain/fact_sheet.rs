@@ -0,0 +1,98 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+
+/// Longest value kept per field; the extraction is meant to be one phrase
+const MAX_FACT_CHARS: usize = 200;
+
+/// What the startup says about itself, pulled from its site before roasting.
+/// Every field is the startup's own claim ("katanya"), never a verdict.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FactSheet {
+    /// What they claim to do
+    #[serde(default)]
+    pub product: Option<String>,
+    #[serde(default)]
+    pub target_market: Option<String>,
+    /// Free, freemium, subscription, per seat...
+    #[serde(default)]
+    pub pricing_model: Option<String>,
+    /// The main call to action, e.g. "Coba gratis 14 hari"
+    #[serde(default)]
+    pub cta: Option<String>,
+}
+
+impl FactSheet {
+    /// The JSON object in the model's reply. Fields the model could not find
+    /// ("-", "unknown", empty) are left out; `None` when nothing is left.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let start = raw.find('{')?;
+        let end = raw.rfind('}')?;
+        if end <= start {
+            return None;
+        }
+
+        let parsed: FactSheet = serde_json::from_str(&raw[start..=end]).ok()?;
+        let clean = |value: Option<String>| {
+            value
+                .map(|v| v.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|v| {
+                    !matches!(
+                        v.to_lowercase().as_str(),
+                        "" | "-" | "n/a" | "null" | "unknown" | "tidak diketahui" | "tidak ada"
+                    )
+                })
+                .map(|v| v.chars().take(MAX_FACT_CHARS).collect())
+        };
+        let sheet = Self {
+            product: clean(parsed.product),
+            target_market: clean(parsed.target_market),
+            pricing_model: clean(parsed.pricing_model),
+            cta: clean(parsed.cta),
+        };
+        (!sheet.is_empty()).then_some(sheet)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries(Language::Id).is_empty()
+    }
+
+    /// Label and value pairs in display order, skipping unknown fields
+    pub fn entries(&self, language: Language) -> Vec<(&'static str, &str)> {
+        let labels = match language {
+            Language::Id => ["Produk", "Target pasar", "Model harga", "Ajakan utama"],
+            Language::En => ["Product", "Target market", "Pricing model", "Main call to action"],
+        };
+        [&self.product, &self.target_market, &self.pricing_model, &self.cta]
+            .iter()
+            .zip(labels)
+            .filter_map(|(value, label)| value.as_deref().map(|v| (label, v)))
+            .collect()
+    }
+
+    /// Every claim on one line, for category keywords and search
+    pub fn search_text(&self) -> String {
+        self.entries(Language::Id)
+            .iter()
+            .map(|(_, value)| *value)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_drops_unknown_fields() {
+        let raw = r#"Berikut faktanya:
+{"product": "Aplikasi  kasir untuk warung", "target_market": "UMKM", "pricing_model": "-", "cta": "Coba gratis"}"#;
+        let sheet = FactSheet::parse(raw).unwrap();
+        assert_eq!(sheet.product.as_deref(), Some("Aplikasi kasir untuk warung"));
+        assert_eq!(sheet.pricing_model, None);
+        assert_eq!(sheet.search_text(), "Aplikasi kasir untuk warung UMKM Coba gratis");
+
+        assert!(FactSheet::parse(r#"{"product": "unknown", "cta": ""}"#).is_none());
+        assert!(FactSheet::parse("no json here").is_none());
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// A single "roast lebih dalam" question on an existing roast and its answer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoastFollowUp {
+    pub id: uuid::Uuid,
+    pub roast_id: uuid::Uuid,
+    pub question: String,
+    pub answer: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl RoastFollowUp {
+    /// Longest follow-up question accepted, in characters
+    pub const MAX_QUESTION_CHARS: usize = 200;
+}
@@ -0,0 +1,114 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A user's claim to run a startup's domain. Proven the same way as an
+/// opt-out: by serving `token` at `VERIFICATION_PATH`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FounderClaim {
+    pub domain: String,
+    pub token: String,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl FounderClaim {
+    /// One token per line, so co-founders can share the file
+    pub const VERIFICATION_PATH: &'static str = "/.well-known/roasting-startup-founder.txt";
+
+    pub fn is_verified(&self) -> bool {
+        self.verified_at.is_some()
+    }
+
+    pub fn verification_url(&self) -> String {
+        format!("https://{}{}", self.domain, Self::VERIFICATION_PATH)
+    }
+}
+
+/// Share buttons whose clicks are counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareChannel {
+    Whatsapp,
+    X,
+    CopyLink,
+    /// The browser's own share sheet
+    Native,
+}
+
+impl ShareChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Whatsapp => "whatsapp",
+            Self::X => "x",
+            Self::CopyLink => "copy_link",
+            Self::Native => "native",
+        }
+    }
+}
+
+impl std::str::FromStr for ShareChannel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "whatsapp" => Ok(Self::Whatsapp),
+            "x" => Ok(Self::X),
+            "copy_link" => Ok(Self::CopyLink),
+            "native" => Ok(Self::Native),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyCount {
+    pub day: NaiveDate,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferrerCount {
+    /// Referring host; empty for direct visits and apps that send none
+    pub referrer: String,
+    pub views: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShareCount {
+    pub channel: String,
+    pub shares: i64,
+}
+
+/// Private stats over every roast of a founder's domain for the last `days` days
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FounderAnalytics {
+    pub domain: String,
+    pub days: u32,
+    pub roasts: usize,
+    /// Oldest day first; days without views are left out
+    pub views: Vec<DailyCount>,
+    /// Most views first
+    pub referrers: Vec<ReferrerCount>,
+    /// Fires given per day, oldest first
+    pub votes: Vec<DailyCount>,
+    pub shares: Vec<ShareCount>,
+}
+
+impl FounderAnalytics {
+    pub const DEFAULT_DAYS: u32 = 30;
+    pub const MAX_DAYS: u32 = 90;
+
+    pub fn total_views(&self) -> i64 {
+        self.views.iter().map(|d| d.count).sum()
+    }
+
+    pub fn total_shares(&self) -> i64 {
+        self.shares.iter().map(|s| s.shares).sum()
+    }
+
+    /// Average fires per day over the window
+    pub fn vote_velocity(&self) -> f64 {
+        let votes: i64 = self.votes.iter().map(|d| d.count).sum();
+        votes as f64 / self.days.max(1) as f64
+    }
+}
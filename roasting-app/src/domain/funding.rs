@@ -0,0 +1,78 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+
+/// A Product Hunt launch of the startup's product
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProductLaunch {
+    pub name: String,
+    pub tagline: String,
+    #[serde(default)]
+    pub votes: Option<i64>,
+}
+
+/// Funding from Crunchbase and launches from Product Hunt, so the roast can
+/// weigh "disrupting a $10B market" against a $50k pre-seed round
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FundingInfo {
+    /// Last funding round, e.g. `pre seed` or `series a`
+    #[serde(default)]
+    pub stage: Option<String>,
+    #[serde(default)]
+    pub total_usd: Option<u64>,
+    #[serde(default)]
+    pub investors: Vec<String>,
+    #[serde(default)]
+    pub launches: Vec<ProductLaunch>,
+}
+
+impl FundingInfo {
+    pub fn is_empty(&self) -> bool {
+        self.stage.is_none() && self.total_usd.is_none() && self.investors.is_empty() && self.launches.is_empty()
+    }
+
+    /// One short phrase per known fact
+    pub fn facts(&self, language: Language) -> Vec<String> {
+        let mut facts = Vec::new();
+        match (&self.stage, self.total_usd, language) {
+            (Some(stage), Some(total), Language::Id) => {
+                facts.push(format!("pendanaan terakhir {}, total {}", stage, format_usd(total)))
+            }
+            (Some(stage), Some(total), Language::En) => {
+                facts.push(format!("last round {}, {} raised in total", stage, format_usd(total)))
+            }
+            (Some(stage), None, Language::Id) => facts.push(format!("pendanaan terakhir {}", stage)),
+            (Some(stage), None, Language::En) => facts.push(format!("last round {}", stage)),
+            (None, Some(total), Language::Id) => facts.push(format!("total pendanaan {}", format_usd(total))),
+            (None, Some(total), Language::En) => facts.push(format!("{} raised in total", format_usd(total))),
+            (None, None, _) => {}
+        }
+        if !self.investors.is_empty() {
+            facts.push(match language {
+                Language::Id => format!("investor {}", self.investors.join(", ")),
+                Language::En => format!("backed by {}", self.investors.join(", ")),
+            });
+        }
+        for launch in &self.launches {
+            let votes = match (launch.votes, language) {
+                (Some(votes), Language::Id) => format!(" ({} upvote)", votes),
+                (Some(votes), Language::En) => format!(" ({} upvotes)", votes),
+                (None, _) => String::new(),
+            };
+            facts.push(match language {
+                Language::Id => format!("launching di Product Hunt \"{}: {}\"{}", launch.name, launch.tagline, votes),
+                Language::En => format!("Product Hunt launch \"{}: {}\"{}", launch.name, launch.tagline, votes),
+            });
+        }
+        facts
+    }
+}
+
+/// `$50k`, `$1.2M`
+fn format_usd(amount: u64) -> String {
+    match amount {
+        1_000_000_000.. => format!("${:.1}B", amount as f64 / 1e9),
+        1_000_000.. => format!("${:.1}M", amount as f64 / 1e6),
+        1_000.. => format!("${}k", amount / 1_000),
+        _ => format!("${}", amount),
+    }
+}
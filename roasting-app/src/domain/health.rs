@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+/// Runtime state of one subsystem as reported by /healthz
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubsystemState {
+    Active,
+    /// Configured but currently failing, e.g. its circuit breaker is open
+    Degraded,
+    /// Switched off by config or not compiled in
+    Disabled,
+}
+
+impl SubsystemState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Degraded => "degraded",
+            Self::Disabled => "disabled",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubsystemHealth {
+    pub name: String,
+    pub state: SubsystemState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl SubsystemHealth {
+    pub fn active(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: SubsystemState::Active,
+            detail: None,
+        }
+    }
+
+    pub fn degraded(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            state: SubsystemState::Degraded,
+            detail: Some(detail.into()),
+        }
+    }
+
+    pub fn disabled(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            state: SubsystemState::Disabled,
+            detail: Some(detail.into()),
+        }
+    }
+
+    /// Active or disabled depending on a kill switch, with `off_detail` naming the switch
+    pub fn toggle(name: &str, enabled: bool, off_detail: &str) -> Self {
+        if enabled {
+            Self::active(name)
+        } else {
+            Self::disabled(name, off_detail)
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// Outcome of the startup warmup reported by /readyz; each step is active
+/// when it ran, degraded when it failed or timed out, disabled when skipped
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarmupReport {
+    pub total_ms: u64,
+    pub steps: Vec<SubsystemHealth>,
+}
+
+/// Overall status plus every subsystem; "degraded" as soon as one subsystem is
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub status: String,
+    pub subsystems: Vec<SubsystemHealth>,
+}
+
+impl HealthReport {
+    pub fn new(subsystems: Vec<SubsystemHealth>) -> Self {
+        let degraded = subsystems
+            .iter()
+            .any(|s| s.state == SubsystemState::Degraded);
+        Self {
+            status: if degraded { "degraded" } else { "ok" }.to_string(),
+            subsystems,
+        }
+    }
+
+    pub fn is_degraded(&self, name: &str) -> bool {
+        self.subsystems
+            .iter()
+            .any(|s| s.name == name && s.state == SubsystemState::Degraded)
+    }
+}
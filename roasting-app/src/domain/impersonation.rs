@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// One audited step of an admin viewing the site as another user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImpersonationEvent {
+    pub admin_id: uuid::Uuid,
+    pub target_user_id: uuid::Uuid,
+    /// `start`, `stop`, or `blocked` for a write refused during the session
+    pub action: String,
+    /// Request path of a blocked write
+    pub path: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ImpersonationEvent {
+    pub const START: &'static str = "start";
+    pub const STOP: &'static str = "stop";
+    pub const BLOCKED: &'static str = "blocked";
+}
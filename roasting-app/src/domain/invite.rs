@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Private beta invite code; each redemption unlocks roast creation for one session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InviteCode {
+    pub code: String,
+    pub max_uses: i32,
+    pub uses: i32,
+    pub note: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl InviteCode {
+    /// Most codes an admin can generate in one request
+    pub const MAX_BATCH: u32 = 100;
+
+    pub fn remaining(&self) -> i32 {
+        (self.max_uses - self.uses).max(0)
+    }
+
+    /// Codes are matched case-insensitively and without surrounding spaces
+    pub fn normalize(code: &str) -> String {
+        code.trim().to_uppercase()
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An address or CIDR range refused at the door, e.g. `203.0.113.7` or
+/// `198.51.100.0/24`. Permanent unless `expires_at` is set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpBan {
+    pub id: Uuid,
+    pub range: String,
+    pub reason: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl IpBan {
+    pub fn is_active_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Output language of a roast
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    Id,
+    En,
+}
+
+impl Language {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::En => "en",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Id => "Bahasa Indonesia",
+            Self::En => "English",
+        }
+    }
+
+    pub fn all() -> &'static [Language] {
+        &[Self::Id, Self::En]
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "id" | "indonesia" | "bahasa" => Ok(Self::Id),
+            "en" | "english" => Ok(Self::En),
+            other => Err(format!("Unknown language: {}", other)),
+        }
+    }
+}
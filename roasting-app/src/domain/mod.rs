@@ -1,11 +1,101 @@
+mod ai_wrapper;
+mod archive_snapshot;
+mod announcement;
+mod battle;
+mod category;
+mod country;
+mod dialect;
+mod dns_tls;
+mod domain_info;
+mod fact_sheet;
+mod follow_up;
+mod founder;
+mod funding;
+mod health;
+mod impersonation;
+mod invite;
+mod ip_ban;
+mod language;
+mod moderation;
+mod notification;
+mod opt_out;
+mod page_performance;
+mod page_section;
+mod retention;
 mod roast;
+mod startup_idea;
 mod startup_info;
 mod user;
 mod persisted_roast;
+mod pitch_deck;
+mod roast_job;
+mod roast_cost;
+mod roast_diff;
+mod roast_length;
+mod roast_options;
+mod roast_score;
+mod roast_sections;
+mod roast_summary;
+mod scrape_health;
+mod scrape_snapshot;
+mod security_posture;
+mod seo_audit;
+mod site_fingerprint;
+mod social_links;
+mod spice;
+mod structured_data;
+mod tech_stack;
+mod route_stats;
 mod vote;
+mod wall;
 
+pub use ai_wrapper::AiWrapperScore;
+pub use archive_snapshot::ArchiveSnapshot;
+pub use announcement::Announcement;
+pub use battle::BattleSuggestion;
+pub use category::StartupCategory;
+pub use country::{country_name, detect_country, parse_country_code, CountryStats};
+pub use dialect::Dialect;
+pub use dns_tls::{DnsTlsInfo, TlsCertificate};
+pub use domain_info::DomainInfo;
+pub use fact_sheet::FactSheet;
+pub use follow_up::RoastFollowUp;
+pub use founder::{DailyCount, FounderAnalytics, FounderClaim, ReferrerCount, ShareChannel, ShareCount};
+pub use funding::{FundingInfo, ProductLaunch};
+pub use health::{HealthReport, SubsystemHealth, SubsystemState, WarmupReport};
+pub use impersonation::ImpersonationEvent;
+pub use invite::InviteCode;
+pub use ip_ban::IpBan;
+pub use language::Language;
+pub use moderation::{ModerationStats, RemovalKind, ReportStatus};
+pub use notification::{is_fire_milestone, ChannelPreference, Notification, NotificationKind};
+pub use opt_out::DomainOptOut;
+pub use page_performance::{format_bytes, AssetKind, PageAsset, PagePerformance};
+pub use page_section::{PageKind, PageSection};
+pub use retention::RetentionOutcome;
 pub use roast::Roast;
+pub use startup_idea::StartupIdea;
 pub use startup_info::StartupInfo;
 pub use user::User;
 pub use persisted_roast::{PersistedRoast, RoastWithDetails};
-pub use vote::{Vote, VoteResult};
+pub use pitch_deck::PitchDeck;
+pub use roast_job::{JobStatus, RoastJob};
+pub use roast_cost::RoastCost;
+pub use roast_diff::{DiffSentence, RoastDiff};
+pub use roast_length::RoastLength;
+pub use roast_options::RoastOptions;
+pub use roast_score::RoastScore;
+pub use roast_sections::RoastSections;
+pub use roast_summary::{summarize_roast, RoastSummary};
+pub use scrape_health::{FailingDomain, ScrapeHealthReport, ScrapeMethod, ScrapeMethodStats};
+pub use scrape_snapshot::ScrapeSnapshot;
+pub use security_posture::SecurityPosture;
+pub use seo_audit::SeoAudit;
+pub use site_fingerprint::SiteFingerprint;
+pub use social_links::{describe_social_links, SocialLink, SocialPlatform};
+pub use spice::{SpiceDistribution, SpiceLevel, VariantSpice};
+pub use structured_data::{Offer, Rating, StructuredData};
+pub use tech_stack::{TechCategory, TechStack, Technology};
+pub use route_stats::RouteStats;
+pub use vote::{Vote, VotePolicy, VoteRejection, VoteResult};
+pub use wall::WallTile;
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Lifecycle of a user report against a roast
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportStatus {
+    #[default]
+    Pending,
+    /// Reviewed and left up
+    Dismissed,
+    /// Reviewed and the roast was removed
+    Actioned,
+}
+
+impl ReportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Dismissed => "dismissed",
+            Self::Actioned => "actioned",
+        }
+    }
+}
+
+impl FromStr for ReportStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pending" => Ok(Self::Pending),
+            "dismissed" => Ok(Self::Dismissed),
+            "actioned" => Ok(Self::Actioned),
+            other => Err(format!("Unknown report status: {}", other)),
+        }
+    }
+}
+
+/// Why a roast was taken down
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemovalKind {
+    /// Removed after a report was reviewed
+    #[default]
+    Moderation,
+    /// Removed at the request of the roasted startup
+    Takedown,
+    /// Unlisted because the site owner verified an opt-out for the domain
+    OptOut,
+}
+
+impl RemovalKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Moderation => "moderation",
+            Self::Takedown => "takedown",
+            Self::OptOut => "opt_out",
+        }
+    }
+}
+
+impl FromStr for RemovalKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "moderation" => Ok(Self::Moderation),
+            "takedown" => Ok(Self::Takedown),
+            "opt_out" => Ok(Self::OptOut),
+            other => Err(format!("Unknown removal kind: {}", other)),
+        }
+    }
+}
+
+/// Aggregate moderation numbers for the public transparency page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModerationStats {
+    pub removed_roasts: u64,
+    pub removed_after_reports: u64,
+    pub takedowns_honored: u64,
+    pub pending_reports: u64,
+    pub dismissed_reports: u64,
+    pub removed_last_30_days: u64,
+}
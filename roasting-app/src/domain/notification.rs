@@ -0,0 +1,189 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Fire counts worth telling the roast's creator about
+const FIRE_MILESTONES: &[i32] = &[1, 10, 25, 50, 100, 250, 500, 1000];
+
+/// Events users can be notified about, delivered the same way on every channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A deferred roast job finished generating
+    RoastReady,
+    /// A roast crossed a fire milestone
+    RoastFired,
+    /// A roast was taken down or removed after a report
+    RoastRemoved,
+    /// An embargoed roast reached its publish time and went live
+    RoastPublished,
+    /// Someone roasted a startup the user watches
+    WatchedRoast,
+    /// Someone asked a follow-up on a roast of a startup the user watches
+    WatchedFollowUp,
+    /// A watched startup's landing page changed enough to look like a pivot
+    WatchedPivot,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RoastReady => "roast_ready",
+            Self::RoastFired => "roast_fired",
+            Self::RoastRemoved => "roast_removed",
+            Self::RoastPublished => "roast_published",
+            Self::WatchedRoast => "watched_roast",
+            Self::WatchedFollowUp => "watched_follow_up",
+            Self::WatchedPivot => "watched_pivot",
+        }
+    }
+}
+
+impl FromStr for NotificationKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "roast_ready" => Ok(Self::RoastReady),
+            "roast_fired" => Ok(Self::RoastFired),
+            "roast_removed" => Ok(Self::RoastRemoved),
+            "roast_published" => Ok(Self::RoastPublished),
+            "watched_roast" => Ok(Self::WatchedRoast),
+            "watched_follow_up" => Ok(Self::WatchedFollowUp),
+            "watched_pivot" => Ok(Self::WatchedPivot),
+            other => Err(format!("Unknown notification kind: {}", other)),
+        }
+    }
+}
+
+/// Channel-independent message; each channel decides how to render it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: String,
+    /// Absolute URL the notification points to
+    pub link: Option<String>,
+}
+
+impl Notification {
+    /// Message about a roast, in the roast's own language
+    pub fn for_roast(
+        kind: NotificationKind,
+        startup_name: &str,
+        fire_count: i32,
+        language: Language,
+        link: String,
+    ) -> Self {
+        let (title, body) = match (kind, language) {
+            (NotificationKind::RoastReady, Language::Id) => (
+                "Roast kamu sudah jadi".to_string(),
+                format!("Roast untuk {} sudah selesai dibuat.", startup_name),
+            ),
+            (NotificationKind::RoastReady, Language::En) => (
+                "Your roast is ready".to_string(),
+                format!("The roast of {} has finished generating.", startup_name),
+            ),
+            (NotificationKind::RoastFired, Language::Id) => (
+                "Roast kamu lagi panas".to_string(),
+                format!("Roast {} sudah dapat {} 🔥.", startup_name, fire_count),
+            ),
+            (NotificationKind::RoastFired, Language::En) => (
+                "Your roast is on fire".to_string(),
+                format!("The roast of {} reached {} 🔥.", startup_name, fire_count),
+            ),
+            (NotificationKind::RoastRemoved, Language::Id) => (
+                "Roast kamu diturunkan".to_string(),
+                format!("Roast untuk {} tidak lagi tampil publik.", startup_name),
+            ),
+            (NotificationKind::RoastRemoved, Language::En) => (
+                "Your roast was removed".to_string(),
+                format!("The roast of {} is no longer public.", startup_name),
+            ),
+            (NotificationKind::RoastPublished, Language::Id) => (
+                "Roast kamu sudah tayang".to_string(),
+                format!("Roast untuk {} sekarang bisa dilihat semua orang.", startup_name),
+            ),
+            (NotificationKind::RoastPublished, Language::En) => (
+                "Your roast is live".to_string(),
+                format!("The roast of {} is now public.", startup_name),
+            ),
+            (NotificationKind::WatchedRoast, Language::Id) => (
+                "Startup pantauanmu di-roast".to_string(),
+                format!("Ada roast baru untuk {}.", startup_name),
+            ),
+            (NotificationKind::WatchedRoast, Language::En) => (
+                "A startup you watch got roasted".to_string(),
+                format!("There's a new roast of {}.", startup_name),
+            ),
+            (NotificationKind::WatchedFollowUp, Language::Id) => (
+                "Roast lebih dalam untuk startup pantauanmu".to_string(),
+                format!("Ada yang minta roast lebih dalam soal {}.", startup_name),
+            ),
+            (NotificationKind::WatchedFollowUp, Language::En) => (
+                "New follow-up on a startup you watch".to_string(),
+                format!("Someone asked for a deeper roast of {}.", startup_name),
+            ),
+            (NotificationKind::WatchedPivot, Language::Id) => (
+                "Startup pantauanmu ganti haluan".to_string(),
+                format!("Halaman depan {} berubah total. Saatnya roast ulang?", startup_name),
+            ),
+            (NotificationKind::WatchedPivot, Language::En) => (
+                "A startup you watch pivoted".to_string(),
+                format!("The landing page of {} changed completely. Time for a fresh roast?", startup_name),
+            ),
+        };
+        Self {
+            kind,
+            title,
+            body,
+            link: Some(link),
+        }
+    }
+}
+
+/// Whether a vote that brought a roast to `fire_count` should notify its creator
+pub fn is_fire_milestone(fire_count: i32) -> bool {
+    FIRE_MILESTONES.contains(&fire_count)
+}
+
+/// A user's setting for one delivery channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelPreference {
+    pub channel: String,
+    pub enabled: bool,
+    /// Channel-specific destination such as a Telegram chat ID
+    pub address: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kinds_and_milestones() {
+        for kind in [
+            NotificationKind::RoastReady,
+            NotificationKind::RoastFired,
+            NotificationKind::RoastRemoved,
+            NotificationKind::RoastPublished,
+            NotificationKind::WatchedRoast,
+            NotificationKind::WatchedFollowUp,
+            NotificationKind::WatchedPivot,
+        ] {
+            assert_eq!(kind.as_str().parse::<NotificationKind>(), Ok(kind));
+        }
+        assert!(is_fire_milestone(1));
+        assert!(is_fire_milestone(100));
+        assert!(!is_fire_milestone(2));
+
+        let n = Notification::for_roast(
+            NotificationKind::RoastFired,
+            "KopiKilat",
+            10,
+            Language::En,
+            "https://roast.example/r/1".to_string(),
+        );
+        assert_eq!(n.body, "The roast of KopiKilat reached 10 🔥.");
+    }
+}
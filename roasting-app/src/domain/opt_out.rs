@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+/// A site owner's request to never be roasted. Enforced once `verified_at`
+/// is set, i.e. the owner served `token` at `VERIFICATION_PATH`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DomainOptOut {
+    pub domain: String,
+    pub token: String,
+    pub verified_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl DomainOptOut {
+    /// Where the owner publishes the token to prove control of the domain
+    pub const VERIFICATION_PATH: &'static str = "/.well-known/roasting-startup-optout.txt";
+
+    pub fn is_verified(&self) -> bool {
+        self.verified_at.is_some()
+    }
+
+    pub fn verification_url(&self) -> String {
+        format!("https://{}{}", self.domain, Self::VERIFICATION_PATH)
+    }
+
+    /// Registrable form of a domain or URL: lowercase host without `www.`
+    /// or port. `None` if there is no plausible hostname.
+    pub fn normalize_domain(input: &str) -> Option<String> {
+        let input = input.trim().to_lowercase();
+        let host = input
+            .split_once("://")
+            .map_or(input.as_str(), |(_, rest)| rest)
+            .split(['/', '?', '#'])
+            .next()?
+            .rsplit('@')
+            .next()?
+            .split(':')
+            .next()?
+            .trim_end_matches('.');
+        let host = host.strip_prefix("www.").unwrap_or(host);
+
+        let valid = host.contains('.')
+            && host.len() <= 253
+            && host.split('.').all(|label| {
+                !label.is_empty()
+                    && !label.starts_with('-')
+                    && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+        valid.then(|| host.to_string())
+    }
+
+    /// Whether `host` is `domain` itself or one of its subdomains
+    pub fn covers(domain: &str, host: &str) -> bool {
+        host == domain || host.ends_with(&format!(".{}", domain))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_and_cover() {
+        assert_eq!(
+            DomainOptOut::normalize_domain("https://WWW.Example.co.id:8443/pricing?x=1"),
+            Some("example.co.id".to_string())
+        );
+        assert_eq!(
+            DomainOptOut::normalize_domain("shop.example.com"),
+            Some("shop.example.com".to_string())
+        );
+        assert_eq!(DomainOptOut::normalize_domain("localhost"), None);
+        assert_eq!(DomainOptOut::normalize_domain("exa mple.com"), None);
+
+        assert!(DomainOptOut::covers("example.com", "example.com"));
+        assert!(DomainOptOut::covers("example.com", "app.example.com"));
+        assert!(!DomainOptOut::covers("example.com", "notexample.com"));
+    }
+}
@@ -0,0 +1,101 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+
+/// Time to first byte past which the server counts as slow
+const SLOW_TTFB_MS: u64 = 800;
+/// Page weight past which the landing page counts as bloated
+const HEAVY_PAGE_BYTES: u64 = 3 * 1024 * 1024;
+/// A single image or video past this is worth calling out by name
+const HEAVY_ASSET_BYTES: u64 = 1024 * 1024;
+const MANY_BLOCKING_SCRIPTS: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetKind {
+    Image,
+    Video,
+    Script,
+    Stylesheet,
+}
+
+impl AssetKind {
+    pub fn label(&self, language: Language) -> &'static str {
+        match (self, language) {
+            (Self::Image, Language::Id) => "gambar",
+            (Self::Image, Language::En) => "image",
+            (Self::Video, _) => "video",
+            (Self::Script, _) => "script",
+            (Self::Stylesheet, _) => "CSS",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageAsset {
+    pub url: String,
+    pub kind: AssetKind,
+    pub bytes: u64,
+}
+
+/// How heavy and slow the landing page is, measured on the direct fetch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PagePerformance {
+    /// Until the response headers arrived, redirects included
+    pub ttfb_ms: u64,
+    pub html_bytes: u64,
+    /// HTML plus every asset whose size could be measured
+    pub total_bytes: u64,
+    /// `<script src>` in `<head>` without `async`, `defer` or `type="module"`
+    pub blocking_scripts: u32,
+    pub image_count: u32,
+    /// Measured assets, largest first
+    pub heaviest: Vec<PageAsset>,
+}
+
+impl PagePerformance {
+    /// Facts worth a joke, one short phrase each; empty for a fast, light page
+    pub fn facts(&self, language: Language) -> Vec<String> {
+        let mut facts = Vec::new();
+        if self.ttfb_ms >= SLOW_TTFB_MS {
+            facts.push(match language {
+                Language::Id => format!("server butuh {} ms cuma buat mulai jawab", self.ttfb_ms),
+                Language::En => format!("the server takes {} ms just to start answering", self.ttfb_ms),
+            });
+        }
+        if self.total_bytes >= HEAVY_PAGE_BYTES {
+            facts.push(match language {
+                Language::Id => format!("halaman depannya seberat {}", format_bytes(self.total_bytes)),
+                Language::En => format!("the landing page weighs {}", format_bytes(self.total_bytes)),
+            });
+        }
+        for asset in self
+            .heaviest
+            .iter()
+            .filter(|a| matches!(a.kind, AssetKind::Image | AssetKind::Video) && a.bytes >= HEAVY_ASSET_BYTES)
+            .take(2)
+        {
+            let name = asset.url.rsplit('/').next().unwrap_or(&asset.url);
+            facts.push(match language {
+                Language::Id => format!("{} {} ukurannya {}", asset.kind.label(language), name, format_bytes(asset.bytes)),
+                Language::En => format!("{} {} is {}", asset.kind.label(language), name, format_bytes(asset.bytes)),
+            });
+        }
+        if self.blocking_scripts >= MANY_BLOCKING_SCRIPTS {
+            facts.push(match language {
+                Language::Id => format!("{} script nge-blok render di <head>", self.blocking_scripts),
+                Language::En => format!("{} render-blocking scripts in <head>", self.blocking_scripts),
+            });
+        }
+        facts
+    }
+}
+
+/// `14.2 MB`, `350 KB`
+pub fn format_bytes(bytes: u64) -> String {
+    const MB: u64 = 1024 * 1024;
+    if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else {
+        format!("{} KB", bytes.div_ceil(1024))
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+/// Internal pages worth crawling beyond the landing page, in crawl priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PageKind {
+    Pricing,
+    About,
+    Careers,
+    Blog,
+}
+
+impl PageKind {
+    pub const ALL: [PageKind; 4] = [Self::Pricing, Self::About, Self::Careers, Self::Blog];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pricing => "pricing",
+            Self::About => "about",
+            Self::Careers => "careers",
+            Self::Blog => "blog",
+        }
+    }
+
+    /// Path keywords, English and Indonesian
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            Self::Pricing => &["pricing", "prices", "plans", "harga", "paket"],
+            Self::About => &["about", "tentang", "company", "team", "tim-kami"],
+            Self::Careers => &["career", "karir", "karier", "jobs", "lowongan", "hiring"],
+            Self::Blog => &["blog", "news", "berita", "artikel"],
+        }
+    }
+
+    /// Which kind of page a URL path looks like, judged by its first segment
+    /// so `/blog/some-post` counts but `/products/team-plan` doesn't
+    pub fn from_path(path: &str) -> Option<Self> {
+        let segment = path
+            .trim_start_matches('/')
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if segment.is_empty() {
+            return None;
+        }
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.keywords().iter().any(|k| segment.starts_with(k)))
+    }
+}
+
+/// Text scraped from one internal page, shown to the prompt under its kind
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageSection {
+    pub kind: PageKind,
+    pub url: String,
+    pub title: Option<String>,
+    pub content: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_kind_from_path() {
+        assert_eq!(PageKind::from_path("/pricing"), Some(PageKind::Pricing));
+        assert_eq!(PageKind::from_path("/Tentang-Kami/"), Some(PageKind::About));
+        assert_eq!(PageKind::from_path("/careers/engineering"), Some(PageKind::Careers));
+        assert_eq!(PageKind::from_path("/blog/launch-day"), Some(PageKind::Blog));
+        assert_eq!(PageKind::from_path("/products/team-plan"), None);
+        assert_eq!(PageKind::from_path("/"), None);
+    }
+}
@@ -1,3 +1,7 @@
+use super::{
+    Dialect, FactSheet, Language, Roast, RoastCost, RoastScore, RoastSections, ScrapeSnapshot, SecurityPosture,
+    SocialLink, StartupCategory,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,28 +10,82 @@ pub struct PersistedRoast {
     pub startup_name: String,
     pub startup_url: String,
     pub roast_text: String,
+    pub language: Language,
+    pub dialect: Dialect,
+    pub category: StartupCategory,
+    pub sections: Option<RoastSections>,
+    pub score: Option<RoastScore>,
+    pub ai_wrapper_confidence: Option<u8>,
+    pub security: Option<SecurityPosture>,
+    pub social_links: Vec<SocialLink>,
+    pub fact_sheet: Option<FactSheet>,
+    pub summary: Option<String>,
+    pub safe: bool,
+    /// "Ditujukan untuk": who the roast is gifted to
+    pub dedicated_to: Option<String>,
+    /// PROMPT_VARIANT that generated the roast, for comparing variants
+    pub prompt_variant: Option<String>,
+    pub country: Option<String>,
+    /// WebP screenshot saved with the roast, see `Roast::screenshot`
+    #[serde(skip)]
+    pub screenshot: Option<Vec<u8>>,
+    /// Scraped data and HTML saved with the roast, see `Roast::source`
+    #[serde(skip)]
+    pub source: Option<ScrapeSnapshot>,
+    /// Completion costs saved with the roast, see `Roast::costs`
+    #[serde(skip)]
+    pub costs: Vec<RoastCost>,
     pub user_id: Option<uuid::Uuid>,
+    pub anonymous: bool,
     pub fire_count: i32,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl PersistedRoast {
-    pub fn new(
-        startup_name: String,
-        startup_url: String,
-        roast_text: String,
-        user_id: Option<uuid::Uuid>,
-    ) -> Self {
+    pub fn from_roast(roast: &Roast, startup_url: String, user_id: Option<uuid::Uuid>) -> Self {
         Self {
             id: uuid::Uuid::new_v4(),
-            startup_name,
+            startup_name: roast.startup_name.clone(),
             startup_url,
-            roast_text,
+            roast_text: roast.roast_text.clone(),
+            language: roast.language,
+            dialect: roast.dialect,
+            category: roast.category,
+            sections: roast.sections.clone(),
+            score: roast.score,
+            ai_wrapper_confidence: roast.ai_wrapper_confidence,
+            security: roast.security.clone(),
+            social_links: roast.social_links.clone(),
+            fact_sheet: roast.fact_sheet.clone(),
+            summary: Some(roast.summary()),
+            safe: roast.safe,
+            dedicated_to: None,
+            prompt_variant: None,
+            country: roast.country.clone(),
+            screenshot: roast.screenshot.clone(),
+            source: roast.source.clone(),
+            costs: roast.costs.clone(),
             user_id,
+            anonymous: false,
             fire_count: 0,
             created_at: None,
         }
     }
+
+    pub fn with_anonymous(mut self, anonymous: bool) -> Self {
+        self.anonymous = anonymous;
+        self
+    }
+
+    pub fn with_dedication(mut self, dedicated_to: Option<String>) -> Self {
+        self.dedicated_to = dedicated_to;
+        self
+    }
+
+    pub fn with_prompt_variant(mut self, prompt_variant: Option<String>) -> Self {
+        self.prompt_variant = prompt_variant;
+        self
+    }
 }
 
 /// Roast with additional info for display (e.g., author name, user's vote status)
@@ -37,9 +95,33 @@ pub struct RoastWithDetails {
     pub startup_name: String,
     pub startup_url: String,
     pub roast_text: String,
+    pub language: Language,
+    pub dialect: Dialect,
+    pub category: StartupCategory,
+    pub sections: Option<RoastSections>,
+    pub score: Option<RoastScore>,
+    pub ai_wrapper_confidence: Option<u8>,
+    pub security: Option<SecurityPosture>,
+    #[serde(default)]
+    pub social_links: Vec<SocialLink>,
+    /// "Fakta (katanya)": what the startup claims about itself
+    #[serde(default)]
+    pub fact_sheet: Option<FactSheet>,
+    pub safe: bool,
+    pub dedicated_to: Option<String>,
+    /// ISO alpha-2 code of the startup's likely country
+    #[serde(default)]
+    pub country: Option<String>,
     pub fire_count: i32,
+    /// `None` when the roast or its author's account hides authorship
     pub author_name: Option<String>,
     pub author_avatar: Option<String>,
+    pub is_anonymous: bool,
+    /// The current user wrote this roast, even if it is shown anonymously
+    pub is_owner: bool,
     pub user_has_voted: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Set while the roast is embargoed; only its owner sees it then
+    #[serde(default)]
+    pub publish_at: Option<chrono::DateTime<chrono::Utc>>,
 }
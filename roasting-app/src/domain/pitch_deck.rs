@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// Scheme of the stand-in URL deck roasts are stored under. It has no dot,
+/// so nothing treats it as a domain to fetch a logo or a scrape from.
+const DECK_URL_PREFIX: &str = "deck:";
+const MAX_NAME_CHARS: usize = 60;
+
+/// Text of an uploaded pitch deck, one entry per slide with text on it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PitchDeck {
+    pub file_name: String,
+    pub slides: Vec<String>,
+}
+
+impl PitchDeck {
+    pub fn is_deck_url(url: &str) -> bool {
+        url.starts_with(DECK_URL_PREFIX)
+    }
+
+    /// `deck:<file-stem>`, what the roast is stored under instead of a site URL
+    pub fn source_url(&self) -> String {
+        let stem = self
+            .file_name
+            .rsplit_once('.')
+            .map_or(self.file_name.as_str(), |(stem, _)| stem);
+        let slug = stem
+            .to_lowercase()
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        format!("{}{}", DECK_URL_PREFIX, if slug.is_empty() { "pitch-deck" } else { &slug })
+    }
+
+    /// First line of the cover slide, which is nearly always the startup's
+    /// name, falling back to the file name
+    pub fn startup_name(&self) -> String {
+        self.slides
+            .first()
+            .and_then(|cover| cover.lines().map(str::trim).find(|line| !line.is_empty()))
+            .map(|line| line.chars().take(MAX_NAME_CHARS).collect())
+            .unwrap_or_else(|| self.file_name.clone())
+    }
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Result of applying (or previewing) one retention policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionOutcome {
+    pub policy: String,
+    pub table: String,
+    /// "archive" or "delete"
+    pub action: String,
+    /// Rows older than this are affected
+    pub cutoff: DateTime<Utc>,
+    pub rows: u64,
+    /// Rows were only counted, nothing changed
+    pub dry_run: bool,
+}
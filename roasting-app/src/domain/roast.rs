@@ -1,16 +1,125 @@
+use super::{
+    summarize_roast, Dialect, FactSheet, Language, RoastCost, RoastOptions, RoastScore, RoastSections, SecurityPosture,
+    ScrapeSnapshot, SocialLink, StartupCategory,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Roast {
     pub startup_name: String,
     pub roast_text: String,
+    pub language: Language,
+    pub dialect: Dialect,
+    pub category: StartupCategory,
+    pub sections: Option<RoastSections>,
+    pub score: Option<RoastScore>,
+    /// 0-100, from the page's AI wrapper signals
+    pub ai_wrapper_confidence: Option<u8>,
+    #[serde(default)]
+    pub security: Option<SecurityPosture>,
+    /// Profiles the startup links to from its own site
+    #[serde(default)]
+    pub social_links: Vec<SocialLink>,
+    /// The startup's own claims, extracted before roasting
+    #[serde(default)]
+    pub fact_sheet: Option<FactSheet>,
+    /// Generated in family-safe mode
+    #[serde(default)]
+    pub safe: bool,
+    /// ISO alpha-2 code of the startup's likely country
+    #[serde(default)]
+    pub country: Option<String>,
+    /// Full-page WebP capture of the site, when the headless browser took one.
+    /// Stored next to the roast, never sent along with it.
+    #[serde(skip)]
+    pub screenshot: Option<Vec<u8>>,
+    /// The scrape the roast was generated from, stored next to it like the screenshot
+    #[serde(skip)]
+    pub source: Option<ScrapeSnapshot>,
+    /// What the completions behind this roast cost, stored next to it
+    #[serde(skip)]
+    pub costs: Vec<RoastCost>,
 }
 
 impl Roast {
-    pub fn new(startup_name: String, roast_text: String) -> Self {
+    pub fn new(
+        startup_name: String,
+        roast_text: String,
+        options: RoastOptions,
+        category: StartupCategory,
+    ) -> Self {
         Self {
             startup_name,
             roast_text,
+            language: options.language,
+            dialect: options.dialect,
+            category,
+            sections: None,
+            score: None,
+            ai_wrapper_confidence: None,
+            security: None,
+            social_links: Vec::new(),
+            fact_sheet: None,
+            safe: options.safe,
+            country: None,
+            screenshot: None,
+            source: None,
+            costs: Vec::new(),
         }
     }
+
+    pub fn with_sections(mut self, sections: Option<RoastSections>) -> Self {
+        self.sections = sections;
+        self
+    }
+
+    pub fn with_score(mut self, score: Option<RoastScore>) -> Self {
+        self.score = score;
+        self
+    }
+
+    pub fn with_ai_wrapper_confidence(mut self, confidence: Option<u8>) -> Self {
+        self.ai_wrapper_confidence = confidence;
+        self
+    }
+
+    pub fn with_security(mut self, security: Option<SecurityPosture>) -> Self {
+        self.security = security;
+        self
+    }
+
+    pub fn with_social_links(mut self, social_links: Vec<SocialLink>) -> Self {
+        self.social_links = social_links;
+        self
+    }
+
+    pub fn with_fact_sheet(mut self, fact_sheet: Option<FactSheet>) -> Self {
+        self.fact_sheet = fact_sheet;
+        self
+    }
+
+    pub fn with_screenshot(mut self, screenshot: Option<Vec<u8>>) -> Self {
+        self.screenshot = screenshot;
+        self
+    }
+
+    pub fn with_source(mut self, source: Option<ScrapeSnapshot>) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn with_costs(mut self, costs: Vec<RoastCost>) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    pub fn with_country(mut self, country: Option<String>) -> Self {
+        self.country = country;
+        self
+    }
+
+    /// One-line summary persisted for /llms.txt and the summary API
+    pub fn summary(&self) -> String {
+        summarize_roast(self.sections.as_ref(), &self.roast_text)
+    }
 }
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// One completion spent on generating a roast, priced from the model table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoastCost {
+    pub model: String,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    /// Millionths of a USD
+    pub cost_micros: u64,
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+/// One sentence of a roast in the comparison view
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffSentence {
+    pub text: String,
+    /// Missing from the other roast (removed on the left, added on the right)
+    pub changed: bool,
+}
+
+/// Sentence-level diff of two roasts of the same startup, for side-by-side display
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoastDiff {
+    pub older: Vec<DiffSentence>,
+    pub newer: Vec<DiffSentence>,
+}
+
+impl RoastDiff {
+    /// Longest common subsequence over sentences, compared case- and
+    /// whitespace-insensitively with markdown markers stripped
+    pub fn between(older: &str, newer: &str) -> Self {
+        let left = split_sentences(older);
+        let right = split_sentences(newer);
+        let left_keys: Vec<String> = left.iter().map(|s| normalize(s)).collect();
+        let right_keys: Vec<String> = right.iter().map(|s| normalize(s)).collect();
+
+        // lcs[i][j]: common sentences between left[i..] and right[j..]
+        let mut lcs = vec![vec![0usize; right.len() + 1]; left.len() + 1];
+        for i in (0..left.len()).rev() {
+            for j in (0..right.len()).rev() {
+                lcs[i][j] = if left_keys[i] == right_keys[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut left_changed = vec![true; left.len()];
+        let mut right_changed = vec![true; right.len()];
+        let (mut i, mut j) = (0, 0);
+        while i < left.len() && j < right.len() {
+            if left_keys[i] == right_keys[j] {
+                left_changed[i] = false;
+                right_changed[j] = false;
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        let pair = |sentences: Vec<String>, changed: Vec<bool>| {
+            sentences
+                .into_iter()
+                .zip(changed)
+                .map(|(text, changed)| DiffSentence { text, changed })
+                .collect()
+        };
+        Self {
+            older: pair(left, left_changed),
+            newer: pair(right, right_changed),
+        }
+    }
+
+    pub fn changed_count(&self) -> usize {
+        self.older
+            .iter()
+            .chain(&self.newer)
+            .filter(|s| s.changed)
+            .count()
+    }
+}
+
+/// Sentences and headings of a markdown roast, with list and heading markers removed
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    for line in text.lines() {
+        let line = line
+            .trim()
+            .trim_start_matches('#')
+            .trim_start_matches(['-', '*', '•'])
+            .trim();
+        let mut current = String::new();
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            current.push(c);
+            if matches!(c, '.' | '!' | '?') && chars.peek().is_none_or(|n| n.is_whitespace()) {
+                push_sentence(&mut sentences, &mut current);
+            }
+        }
+        push_sentence(&mut sentences, &mut current);
+    }
+    sentences
+}
+
+fn push_sentence(sentences: &mut Vec<String>, current: &mut String) {
+    let sentence = current.trim();
+    if sentence.chars().any(char::is_alphanumeric) {
+        sentences.push(sentence.to_string());
+    }
+    current.clear();
+}
+
+fn normalize(sentence: &str) -> String {
+    sentence
+        .chars()
+        .filter(|c| !matches!(c, '*' | '_' | '`'))
+        .flat_map(char::to_lowercase)
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marks_changed_sentences() {
+        let older = "## Produk\nLanding page-nya kosong. Pricing-nya rahasia negara!";
+        let newer = "## Produk\nLanding page-nya **kosong**. Pricing udah ada, tapi mahal.";
+        let diff = RoastDiff::between(older, newer);
+
+        let flags = |side: &[DiffSentence]| side.iter().map(|s| s.changed).collect::<Vec<_>>();
+        assert_eq!(flags(&diff.older), vec![false, false, true]);
+        assert_eq!(flags(&diff.newer), vec![false, false, true]);
+        assert_eq!(diff.older[0].text, "Produk");
+        assert_eq!(diff.newer[2].text, "Pricing udah ada, tapi mahal.");
+        assert_eq!(diff.changed_count(), 2);
+    }
+}
@@ -0,0 +1,61 @@
+use super::RoastOptions;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Lifecycle of a queued roast request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Waiting for the LLM or daily budget to become available again
+    #[default]
+    Deferred,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deferred => "deferred",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed)
+    }
+}
+
+impl FromStr for JobStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "deferred" => Ok(Self::Deferred),
+            "running" => Ok(Self::Running),
+            "completed" => Ok(Self::Completed),
+            "failed" => Ok(Self::Failed),
+            other => Err(format!("Unknown job status: {}", other)),
+        }
+    }
+}
+
+/// A roast request the user agreed to have generated later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoastJob {
+    pub id: uuid::Uuid,
+    pub url: String,
+    pub options: RoastOptions,
+    pub user_id: Option<uuid::Uuid>,
+    pub anonymous: bool,
+    pub dedicated_to: Option<String>,
+    pub status: JobStatus,
+    pub attempts: i32,
+    pub roast_id: Option<uuid::Uuid>,
+    pub last_error: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// How long the generated roast should be
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoastLength {
+    Singkat,
+    #[default]
+    Standar,
+    Panjang,
+}
+
+impl RoastLength {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Singkat => "singkat",
+            Self::Standar => "standar",
+            Self::Panjang => "panjang",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Singkat => "Singkat",
+            Self::Standar => "Standar",
+            Self::Panjang => "Panjang",
+        }
+    }
+
+    pub fn all() -> &'static [RoastLength] {
+        &[Self::Singkat, Self::Standar, Self::Panjang]
+    }
+
+    /// Upper bound on words across the whole roast
+    pub fn word_limit(&self) -> u32 {
+        match self {
+            Self::Singkat => 120,
+            Self::Standar => 300,
+            Self::Panjang => 550,
+        }
+    }
+}
+
+impl FromStr for RoastLength {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "singkat" | "short" => Ok(Self::Singkat),
+            "standar" | "standard" => Ok(Self::Standar),
+            "panjang" | "long" => Ok(Self::Panjang),
+            other => Err(format!("Unknown roast length: {}", other)),
+        }
+    }
+}
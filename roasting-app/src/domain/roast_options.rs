@@ -0,0 +1,37 @@
+use super::{Dialect, Language, RoastLength};
+use serde::{Deserialize, Serialize};
+
+/// User-selected knobs for a single roast generation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoastOptions {
+    pub language: Language,
+    pub dialect: Dialect,
+    pub length: RoastLength,
+    /// Family-safe: no-profanity prompt and scrubbed output
+    #[serde(default)]
+    pub safe: bool,
+}
+
+impl RoastOptions {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    pub fn with_length(mut self, length: RoastLength) -> Self {
+        self.length = length;
+        self
+    }
+
+    pub fn with_safe(mut self, safe: bool) -> Self {
+        self.safe = safe;
+        self
+    }
+}
@@ -0,0 +1,85 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+
+/// 1–10 ratings the LLM gives alongside the roast
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoastScore {
+    #[serde(alias = "orisinalitas")]
+    pub originality: u8,
+    pub ui: u8,
+    #[serde(alias = "kelayakan")]
+    pub viability: u8,
+    #[serde(alias = "buzzword")]
+    pub buzzword_density: u8,
+}
+
+#[derive(Deserialize)]
+struct ScoreEnvelope {
+    #[serde(alias = "scores")]
+    skor: RoastScore,
+}
+
+impl RoastScore {
+    pub const MIN: u8 = 1;
+    pub const MAX: u8 = 10;
+
+    pub fn new(originality: u8, ui: u8, viability: u8, buzzword_density: u8) -> Self {
+        let clamp = |v: u8| v.clamp(Self::MIN, Self::MAX);
+        Self {
+            originality: clamp(originality),
+            ui: clamp(ui),
+            viability: clamp(viability),
+            buzzword_density: clamp(buzzword_density),
+        }
+    }
+
+    /// Read the `skor` object out of the LLM's JSON reply
+    pub fn parse(raw: &str) -> Option<Self> {
+        let start = raw.find('{')?;
+        let end = raw.rfind('}')?;
+        if end <= start {
+            return None;
+        }
+
+        let envelope: ScoreEnvelope = serde_json::from_str(&raw[start..=end]).ok()?;
+        let s = envelope.skor;
+        Some(Self::new(s.originality, s.ui, s.viability, s.buzzword_density))
+    }
+
+    pub fn labels(language: Language) -> [&'static str; 4] {
+        match language {
+            Language::Id => ["Orisinalitas", "UI", "Kelayakan", "Kepadatan Buzzword"],
+            Language::En => ["Originality", "UI", "Viability", "Buzzword Density"],
+        }
+    }
+
+    pub fn values(&self) -> [u8; 4] {
+        [self.originality, self.ui, self.viability, self.buzzword_density]
+    }
+
+    /// Label and value pairs in display order
+    pub fn entries(&self, language: Language) -> [(&'static str, u8); 4] {
+        let labels = Self::labels(language);
+        let values = self.values();
+        std::array::from_fn(|i| (labels[i], values[i]))
+    }
+
+    /// Average of the three quality scores; buzzword density counts against it
+    pub fn overall(&self) -> f32 {
+        let buzzword_penalty = (Self::MAX + Self::MIN - self.buzzword_density) as f32;
+        (self.originality as f32 + self.ui as f32 + self.viability as f32 + buzzword_penalty) / 4.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_clamp() {
+        let raw = r#"{"produk": "a", "skor": {"orisinalitas": 0, "ui": 7, "kelayakan": 15, "buzzword": 9}}"#;
+        let score = RoastScore::parse(raw).unwrap();
+        assert_eq!(score.values(), [1, 7, 10, 9]);
+        assert!(RoastScore::parse(r#"{"produk": "a"}"#).is_none());
+    }
+}
@@ -0,0 +1,113 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+
+/// Roast split into the fixed sections the LLM is asked to return as JSON
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoastSections {
+    #[serde(alias = "product")]
+    pub produk: String,
+    #[serde(alias = "design")]
+    pub desain: String,
+    #[serde(alias = "business_model")]
+    pub model_bisnis: String,
+    #[serde(alias = "failure_prediction")]
+    pub prediksi_kegagalan: String,
+    /// Only asked for when the site has security problems worth roasting
+    #[serde(default, alias = "security", skip_serializing_if = "Option::is_none")]
+    pub keamanan: Option<String>,
+    /// Only asked for when the landing page fails the SEO basics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seo: Option<String>,
+}
+
+impl RoastSections {
+    /// Parse the LLM reply, tolerating code fences or chatter around the JSON object.
+    /// Returns `None` when the reply is not usable so callers can keep the raw text.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let start = raw.find('{')?;
+        let end = raw.rfind('}')?;
+        if end <= start {
+            return None;
+        }
+
+        let sections: Self = serde_json::from_str(&raw[start..=end]).ok()?;
+        if sections.bodies().iter().any(|body| body.trim().is_empty()) {
+            return None;
+        }
+        Some(sections)
+    }
+
+    pub fn titles(language: Language) -> [&'static str; 4] {
+        match language {
+            Language::Id => ["Produk", "Desain", "Model Bisnis", "Prediksi Kegagalan"],
+            Language::En => ["Product", "Design", "Business Model", "Failure Prediction"],
+        }
+    }
+
+    pub fn security_title(language: Language) -> &'static str {
+        match language {
+            Language::Id => "Keamanan: Auto-Bocor",
+            Language::En => "Security: Leaky by Default",
+        }
+    }
+
+    pub fn seo_title(language: Language) -> &'static str {
+        match language {
+            Language::Id => "SEO: Gaib di Google",
+            Language::En => "SEO: Invisible to Google",
+        }
+    }
+
+    pub fn bodies(&self) -> [&str; 4] {
+        [
+            &self.produk,
+            &self.desain,
+            &self.model_bisnis,
+            &self.prediksi_kegagalan,
+        ]
+    }
+
+    /// Section title and body pairs in display order, with the security
+    /// and SEO sections last when present
+    pub fn entries(&self, language: Language) -> Vec<(&'static str, &str)> {
+        let mut entries: Vec<_> = Self::titles(language)
+            .into_iter()
+            .zip(self.bodies())
+            .collect();
+        if let Some(keamanan) = self.keamanan.as_deref().filter(|k| !k.trim().is_empty()) {
+            entries.push((Self::security_title(language), keamanan));
+        }
+        if let Some(seo) = self.seo.as_deref().filter(|s| !s.trim().is_empty()) {
+            entries.push((Self::seo_title(language), seo));
+        }
+        entries
+    }
+
+    /// Flatten into markdown for previews and clients that only read `roast_text`
+    pub fn to_text(&self, language: Language) -> String {
+        self.entries(language)
+            .iter()
+            .map(|(title, body)| format!("## {}\n{}", title, body.trim()))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fenced_json() {
+        let raw = "Nih roasting-nya:\n```json\n{\"produk\": \"a\", \"desain\": \"b\", \"model_bisnis\": \"c\", \"prediksi_kegagalan\": \"d\"}\n```";
+        let sections = RoastSections::parse(raw).unwrap();
+        assert_eq!(sections.model_bisnis, "c");
+        assert!(sections.to_text(Language::Id).starts_with("## Produk\na"));
+    }
+
+    #[test]
+    fn test_parse_rejects_incomplete() {
+        assert!(RoastSections::parse("just some markdown").is_none());
+        assert!(RoastSections::parse(r#"{"produk": "a", "desain": "", "model_bisnis": "c", "prediksi_kegagalan": "d"}"#).is_none());
+    }
+}
@@ -0,0 +1,82 @@
+use super::{Language, RoastScore, RoastSections, StartupCategory};
+use serde::{Deserialize, Serialize};
+
+/// Longest persisted summary, in characters
+const MAX_SUMMARY_CHARS: usize = 240;
+
+/// Compact, machine-readable view of a roast for bots and AI agents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoastSummary {
+    pub id: uuid::Uuid,
+    pub startup_name: String,
+    pub startup_url: String,
+    pub summary: String,
+    pub category: StartupCategory,
+    pub language: Language,
+    pub safe: bool,
+    pub score: Option<RoastScore>,
+    pub overall: Option<f32>,
+    pub fire_count: i32,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// First sentence of the failure prediction (the verdict), or of the roast
+/// text when it has no sections, capped at `MAX_SUMMARY_CHARS`
+pub fn summarize_roast(sections: Option<&RoastSections>, roast_text: &str) -> String {
+    let source = match sections {
+        Some(s) if !s.prediksi_kegagalan.trim().is_empty() => s.prediksi_kegagalan.as_str(),
+        _ => roast_text,
+    };
+    let body = source
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let sentence = body
+        .char_indices()
+        .find(|&(i, c)| {
+            matches!(c, '.' | '!' | '?')
+                && body[i + c.len_utf8()..].starts_with(|n: char| n.is_whitespace())
+        })
+        .map(|(i, c)| &body[..i + c.len_utf8()])
+        .unwrap_or(&body);
+
+    if sentence.chars().count() <= MAX_SUMMARY_CHARS {
+        return sentence.to_string();
+    }
+    let truncated: String = sentence.chars().take(MAX_SUMMARY_CHARS - 1).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_prefers_verdict_sentence() {
+        let sections = RoastSections {
+            produk: "Produk".to_string(),
+            desain: "Desain".to_string(),
+            model_bisnis: "Bisnis".to_string(),
+            prediksi_kegagalan: "Tutup dalam 6 bulan. Investor kabur duluan.".to_string(),
+            keamanan: None,
+            seo: None,
+        };
+        assert_eq!(
+            summarize_roast(Some(&sections), "ignored"),
+            "Tutup dalam 6 bulan."
+        );
+        assert_eq!(
+            summarize_roast(None, "## Produk\nHarga Rp.50rb! Mahal."),
+            "Harga Rp.50rb!"
+        );
+
+        let long = "a".repeat(300);
+        assert_eq!(
+            summarize_roast(None, &long).chars().count(),
+            MAX_SUMMARY_CHARS
+        );
+    }
+}
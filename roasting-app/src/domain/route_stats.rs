@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Latency and error summary for one route over a time window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteStats {
+    pub method: String,
+    pub route: String,
+    /// Number of sampled requests, not total traffic
+    pub requests: i64,
+    pub error_rate: f64,
+    pub client_error_rate: f64,
+    pub avg_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: i32,
+}
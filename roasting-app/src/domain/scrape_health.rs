@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+/// The ways `WebsiteScraper` tries to get a page, in fallback order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScrapeMethod {
+    FlareSolverr,
+    Http,
+    CloudflareSolver,
+    Headless,
+    /// Headless browser on a linkedin.com/company page
+    LinkedIn,
+    GoogleCache,
+    /// Most recent Internet Archive snapshot
+    Wayback,
+    /// Every method failed and the roast only had the URL to go on
+    UrlOnly,
+}
+
+impl ScrapeMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FlareSolverr => "flaresolverr",
+            Self::Http => "http",
+            Self::CloudflareSolver => "cloudflare_solver",
+            Self::Headless => "headless",
+            Self::LinkedIn => "linkedin",
+            Self::GoogleCache => "google_cache",
+            Self::Wayback => "wayback",
+            Self::UrlOnly => "url_only",
+        }
+    }
+}
+
+/// Success rate and latency of one scraping method over the report window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScrapeMethodStats {
+    pub method: String,
+    pub attempts: i64,
+    pub success_rate: f64,
+    pub avg_ms: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailingDomain {
+    pub domain: String,
+    pub failures: i64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScrapeHealthReport {
+    pub period_hours: i64,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+    pub methods: Vec<ScrapeMethodStats>,
+    pub failing_domains: Vec<FailingDomain>,
+}
+
+impl ScrapeHealthReport {
+    /// Plain-text digest for the ops channel
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!(
+            "Scrape health, last {} hours ({})",
+            self.period_hours,
+            self.generated_at.format("%Y-%m-%d %H:%M UTC")
+        )];
+        if self.methods.is_empty() {
+            lines.push("No scrapes in this window.".to_string());
+            return lines.join("\n");
+        }
+        for m in &self.methods {
+            lines.push(format!(
+                "• {}: {:.0}% of {} ok, avg {:.0} ms",
+                m.method,
+                m.success_rate * 100.0,
+                m.attempts,
+                m.avg_ms
+            ));
+        }
+        if !self.failing_domains.is_empty() {
+            lines.push("Top failing domains:".to_string());
+            for d in &self.failing_domains {
+                lines.push(format!(
+                    "• {} ({}x){}",
+                    d.domain,
+                    d.failures,
+                    d.last_error
+                        .as_deref()
+                        .map(|e| format!(": {}", e))
+                        .unwrap_or_default()
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_lists_methods_and_domains() {
+        let report = ScrapeHealthReport {
+            period_hours: 24,
+            generated_at: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            methods: vec![ScrapeMethodStats {
+                method: "http".to_string(),
+                attempts: 40,
+                success_rate: 0.75,
+                avg_ms: 812.4,
+            }],
+            failing_domains: vec![FailingDomain {
+                domain: "example.com".to_string(),
+                failures: 6,
+                last_error: Some("HTTP 403".to_string()),
+            }],
+        };
+        assert_eq!(
+            report.summary(),
+            "Scrape health, last 24 hours (2023-11-14 22:13 UTC)\n\
+             • http: 75% of 40 ok, avg 812 ms\n\
+             Top failing domains:\n\
+             • example.com (6x): HTTP 403"
+        );
+    }
+}
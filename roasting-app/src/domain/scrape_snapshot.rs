@@ -0,0 +1,12 @@
+use super::StartupInfo;
+use serde::{Deserialize, Serialize};
+
+/// What a roast was based on, kept so anyone disputing it can check: the
+/// scraped `StartupInfo` and the HTML it was parsed from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrapeSnapshot {
+    pub startup_info: StartupInfo,
+    /// `None` when the scrape came from the scrape cache, which keeps only
+    /// `StartupInfo`
+    pub html: Option<String>,
+}
@@ -0,0 +1,59 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+
+/// Surface-level security hygiene of the roasted site. Only flags are kept;
+/// the contents of anything found exposed are never stored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityPosture {
+    /// Site answers over HTTPS
+    pub https: bool,
+    /// HTTPS page that still loads scripts, styles, or images over plain HTTP
+    pub mixed_content: bool,
+    /// Server shows an auto-generated "Index of /" listing
+    pub directory_listing: bool,
+    /// Well-known sensitive paths that were publicly readable, e.g. `/.env`
+    #[serde(default)]
+    pub exposed_files: Vec<String>,
+}
+
+impl SecurityPosture {
+    pub fn is_leaky(&self) -> bool {
+        !self.https
+            || self.mixed_content
+            || self.directory_listing
+            || !self.exposed_files.is_empty()
+    }
+
+    /// Human-readable findings, empty when nothing looks wrong
+    pub fn issues(&self, language: Language) -> Vec<String> {
+        let mut issues = Vec::new();
+        let (no_https, mixed, listing, exposed) = match language {
+            Language::Id => (
+                "tidak pakai HTTPS",
+                "mixed content (HTTPS tapi masih muat aset lewat HTTP)",
+                "directory listing terbuka",
+                "file sensitif bisa diakses publik",
+            ),
+            Language::En => (
+                "no HTTPS",
+                "mixed content (HTTPS page loading assets over HTTP)",
+                "directory listing enabled",
+                "sensitive files publicly readable",
+            ),
+        };
+
+        if !self.https {
+            issues.push(no_https.to_string());
+        }
+        if self.mixed_content {
+            issues.push(mixed.to_string());
+        }
+        if self.directory_listing {
+            issues.push(listing.to_string());
+        }
+        if !self.exposed_files.is_empty() {
+            issues.push(format!("{}: {}", exposed, self.exposed_files.join(", ")));
+        }
+        issues
+    }
+}
@@ -0,0 +1,93 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+
+/// Titles past this get cut off in search results
+const MAX_TITLE_CHARS: usize = 70;
+/// A title split into this many pieces is a keyword list, not a title
+const STUFFED_TITLE_SEGMENTS: u32 = 5;
+/// Missing alt text only counts once it's this many images and at least half
+const MIN_IMAGES_MISSING_ALT: u32 = 3;
+
+/// On-page SEO basics of the landing page
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeoAudit {
+    pub title_missing: bool,
+    pub title_chars: usize,
+    /// Pieces of the title between `|`, `,`, `-`, `–` and `·`
+    pub title_segments: u32,
+    /// A word the title repeats three or more times
+    pub repeated_title_word: Option<String>,
+    pub meta_description_missing: bool,
+    pub h1_count: u32,
+    /// H1 text that appears more than once
+    pub duplicate_h1s: Vec<String>,
+    pub images: u32,
+    /// `<img>` without an `alt` attribute; `alt=""` is a valid decorative image
+    pub images_missing_alt: u32,
+    /// `<meta name="robots">` asks search engines not to index the page
+    pub noindex: bool,
+}
+
+impl SeoAudit {
+    pub fn is_keyword_stuffed(&self) -> bool {
+        self.repeated_title_word.is_some() || self.title_segments >= STUFFED_TITLE_SEGMENTS
+    }
+
+    /// Findings worth mocking, empty when the basics are in place
+    pub fn issues(&self, language: Language) -> Vec<String> {
+        let mut issues = Vec::new();
+        if self.noindex {
+            issues.push(match language {
+                Language::Id => "halamannya pasang noindex, minta sendiri biar nggak muncul di Google".to_string(),
+                Language::En => "the page sets noindex, asking Google not to list it".to_string(),
+            });
+        }
+        if self.title_missing {
+            issues.push(match language {
+                Language::Id => "tidak punya <title>".to_string(),
+                Language::En => "no <title> at all".to_string(),
+            });
+        } else if let Some(word) = &self.repeated_title_word {
+            issues.push(match language {
+                Language::Id => format!("judulnya keyword stuffing, \"{}\" diulang-ulang", word),
+                Language::En => format!("keyword-stuffed title repeating \"{}\"", word),
+            });
+        } else if self.title_segments >= STUFFED_TITLE_SEGMENTS {
+            issues.push(match language {
+                Language::Id => format!("judulnya daftar keyword {} potong", self.title_segments),
+                Language::En => format!("the title is a {}-piece keyword list", self.title_segments),
+            });
+        } else if self.title_chars > MAX_TITLE_CHARS {
+            issues.push(match language {
+                Language::Id => format!("judulnya {} karakter, kepotong di Google", self.title_chars),
+                Language::En => format!("{}-character title that Google cuts off", self.title_chars),
+            });
+        }
+        if self.meta_description_missing {
+            issues.push(match language {
+                Language::Id => "tidak ada meta description".to_string(),
+                Language::En => "no meta description".to_string(),
+            });
+        }
+        match (self.h1_count, language) {
+            (0, Language::Id) => issues.push("tidak ada H1".to_string()),
+            (0, Language::En) => issues.push("no H1".to_string()),
+            (1, _) => {}
+            (n, Language::Id) => issues.push(format!("{} H1 sekaligus", n)),
+            (n, Language::En) => issues.push(format!("{} H1s on one page", n)),
+        }
+        if let Some(h1) = self.duplicate_h1s.first() {
+            issues.push(match language {
+                Language::Id => format!("H1 \"{}\" ditulis lebih dari sekali", h1),
+                Language::En => format!("the H1 \"{}\" appears more than once", h1),
+            });
+        }
+        if self.images_missing_alt >= MIN_IMAGES_MISSING_ALT && self.images_missing_alt * 2 >= self.images {
+            issues.push(match language {
+                Language::Id => format!("{} dari {} gambar tanpa alt text", self.images_missing_alt, self.images),
+                Language::En => format!("{} of {} images have no alt text", self.images_missing_alt, self.images),
+            });
+        }
+        issues
+    }
+}
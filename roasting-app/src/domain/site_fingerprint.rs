@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Title and top headings of a startup's landing page: cheap to fetch and
+/// enough to tell a pivot from a copy tweak
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SiteFingerprint {
+    pub title: String,
+    pub headings: Vec<String>,
+}
+
+impl SiteFingerprint {
+    pub fn is_empty(&self) -> bool {
+        self.title.trim().is_empty() && self.headings.is_empty()
+    }
+
+    /// Lowercased, whitespace-collapsed title and headings, one per line;
+    /// the content hash is taken over this so markup churn doesn't count
+    pub fn canonical_text(&self) -> String {
+        std::iter::once(self.title.as_str())
+            .chain(self.headings.iter().map(String::as_str))
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
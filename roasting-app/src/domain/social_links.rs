@@ -0,0 +1,65 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SocialPlatform {
+    Instagram,
+    TikTok,
+    LinkedIn,
+    /// Also matched on twitter.com links
+    X,
+}
+
+impl SocialPlatform {
+    pub const ALL: [SocialPlatform; 4] = [Self::Instagram, Self::TikTok, Self::LinkedIn, Self::X];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Instagram => "Instagram",
+            Self::TikTok => "TikTok",
+            Self::LinkedIn => "LinkedIn",
+            Self::X => "X",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Instagram => "📸",
+            Self::TikTok => "🎵",
+            Self::LinkedIn => "💼",
+            Self::X => "𝕏",
+        }
+    }
+}
+
+/// A profile the startup links to from its own header or footer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SocialLink {
+    pub platform: SocialPlatform,
+    pub url: String,
+    /// `@handle`, or the company slug on LinkedIn
+    pub handle: String,
+}
+
+/// `Instagram @acme, LinkedIn acme-inc; no TikTok, X`
+pub fn describe_social_links(links: &[SocialLink], language: Language) -> String {
+    let found = links
+        .iter()
+        .map(|l| format!("{} {}", l.platform.label(), l.handle))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let missing = SocialPlatform::ALL
+        .iter()
+        .filter(|p| !links.iter().any(|l| l.platform == **p))
+        .map(SocialPlatform::label)
+        .collect::<Vec<_>>()
+        .join(", ");
+    match (found.is_empty(), missing.is_empty(), language) {
+        (true, _, Language::Id) => "tidak ada satu pun link sosmed di situsnya".to_string(),
+        (true, _, Language::En) => "not a single social media link on the site".to_string(),
+        (false, true, _) => found,
+        (false, false, Language::Id) => format!("{}; tidak ada {}", found, missing),
+        (false, false, Language::En) => format!("{}; no {}", found, missing),
+    }
+}
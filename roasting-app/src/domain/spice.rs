@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::Language;
+
+/// A reader's answer to "how spicy was this roast?"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpiceLevel {
+    TooMild,
+    JustRight,
+    TooSpicy,
+}
+
+impl SpiceLevel {
+    pub fn all() -> &'static [SpiceLevel] {
+        &[Self::TooMild, Self::JustRight, Self::TooSpicy]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TooMild => "too_mild",
+            Self::JustRight => "just_right",
+            Self::TooSpicy => "too_spicy",
+        }
+    }
+
+    pub fn display_name(&self, language: Language) -> &'static str {
+        match (self, language) {
+            (Self::TooMild, Language::Id) => "Terlalu halus",
+            (Self::JustRight, Language::Id) => "Pas",
+            (Self::TooSpicy, Language::Id) => "Kelewatan",
+            (Self::TooMild, Language::En) => "Too mild",
+            (Self::JustRight, Language::En) => "Just right",
+            (Self::TooSpicy, Language::En) => "Too much",
+        }
+    }
+}
+
+impl FromStr for SpiceLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "too_mild" | "halus" => Ok(Self::TooMild),
+            "just_right" | "pas" => Ok(Self::JustRight),
+            "too_spicy" | "kelewatan" => Ok(Self::TooSpicy),
+            other => Err(format!("Unknown spice level: {}", other)),
+        }
+    }
+}
+
+/// Answer counts of the spice poll for one roast or one prompt variant
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpiceDistribution {
+    pub too_mild: i64,
+    pub just_right: i64,
+    pub too_spicy: i64,
+}
+
+impl SpiceDistribution {
+    pub fn add(&mut self, level: SpiceLevel, count: i64) {
+        match level {
+            SpiceLevel::TooMild => self.too_mild += count,
+            SpiceLevel::JustRight => self.just_right += count,
+            SpiceLevel::TooSpicy => self.too_spicy += count,
+        }
+    }
+
+    pub fn count(&self, level: SpiceLevel) -> i64 {
+        match level {
+            SpiceLevel::TooMild => self.too_mild,
+            SpiceLevel::JustRight => self.just_right,
+            SpiceLevel::TooSpicy => self.too_spicy,
+        }
+    }
+
+    pub fn total(&self) -> i64 {
+        self.too_mild + self.just_right + self.too_spicy
+    }
+
+    /// Rounded share of `level`, 0 when nobody answered
+    pub fn percent(&self, level: SpiceLevel) -> u8 {
+        match self.total() {
+            0 => 0,
+            total => ((self.count(level) * 100 + total / 2) / total) as u8,
+        }
+    }
+}
+
+/// Spice answers across all roasts of one PROMPT_VARIANT, for A/B comparisons
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariantSpice {
+    /// `None` for the default templates
+    pub variant: Option<String>,
+    pub roasts: i64,
+    pub distribution: SpiceDistribution,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distribution_percentages() {
+        let mut spice = SpiceDistribution::default();
+        assert_eq!(spice.percent(SpiceLevel::JustRight), 0);
+
+        spice.add(SpiceLevel::TooMild, 1);
+        spice.add(SpiceLevel::JustRight, 2);
+        assert_eq!(spice.total(), 3);
+        assert_eq!(spice.percent(SpiceLevel::TooMild), 33);
+        assert_eq!(spice.percent(SpiceLevel::JustRight), 67);
+        assert_eq!(spice.percent(SpiceLevel::TooSpicy), 0);
+
+        assert_eq!("kelewatan".parse(), Ok(SpiceLevel::TooSpicy));
+        assert_eq!("just_right".parse(), Ok(SpiceLevel::JustRight));
+        assert!("mild".parse::<SpiceLevel>().is_err());
+    }
+}
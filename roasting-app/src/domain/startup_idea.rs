@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Scheme of the stand-in URL idea roasts are stored under; dotless like
+/// `deck:` so nothing tries to fetch it
+const IDEA_URL_PREFIX: &str = "idea:";
+
+/// A stealth startup described in the user's own words instead of a site
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartupIdea {
+    /// Working name, if the founder has picked one yet
+    pub name: Option<String>,
+    pub description: String,
+}
+
+impl StartupIdea {
+    pub fn is_idea_url(url: &str) -> bool {
+        url.starts_with(IDEA_URL_PREFIX)
+    }
+
+    /// `idea:<name>`, or `idea:stealth` for an unnamed idea
+    pub fn source_url(&self) -> String {
+        let slug = self
+            .name
+            .as_deref()
+            .unwrap_or_default()
+            .to_lowercase()
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        format!("{}{}", IDEA_URL_PREFIX, if slug.is_empty() { "stealth" } else { &slug })
+    }
+}
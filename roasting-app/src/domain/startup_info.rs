@@ -1,3 +1,7 @@
+use super::{
+    AiWrapperScore, ArchiveSnapshot, DnsTlsInfo, DomainInfo, FundingInfo, PagePerformance, PageSection, SecurityPosture,
+    SeoAudit, SocialLink, StructuredData, TechStack,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,6 +11,50 @@ pub struct StartupInfo {
     pub description: Option<String>,
     pub headings: Vec<String>,
     pub content_summary: String,
+    #[serde(default)]
+    pub ai_wrapper: Option<AiWrapperScore>,
+    #[serde(default)]
+    pub domain_info: Option<DomainInfo>,
+    /// Mail, DNS and certificate setup, when DNS_TLS_LOOKUP is on
+    #[serde(default)]
+    pub dns_tls: Option<DnsTlsInfo>,
+    /// Funding and Product Hunt launches, when an enrichment key is set
+    #[serde(default)]
+    pub funding: Option<FundingInfo>,
+    #[serde(default)]
+    pub security: Option<SecurityPosture>,
+    /// Title, meta description, H1 and alt text basics of the landing page
+    #[serde(default)]
+    pub seo: Option<SeoAudit>,
+    /// Instagram, TikTok, LinkedIn and X profiles linked from the page;
+    /// `None` when the page itself was never parsed
+    #[serde(default)]
+    pub social_links: Option<Vec<SocialLink>>,
+    /// Icon, logo and og:image URLs to download the startup's logo from, best first
+    #[serde(default)]
+    pub logo_candidates: Vec<String>,
+    /// CMS, builders, frameworks and hosting the site was fingerprinted with
+    #[serde(default)]
+    pub tech_stack: Option<TechStack>,
+    /// Schema.org Organization and Product data the page embeds
+    #[serde(default)]
+    pub structured_data: Option<StructuredData>,
+    /// Load time and page weight, only when the live site was fetched directly
+    #[serde(default)]
+    pub performance: Option<PagePerformance>,
+    /// ISO alpha-2 code of the likely country, see `detect_country`
+    #[serde(default)]
+    pub country: Option<String>,
+    /// Set when the content came from archive.org instead of the live site
+    #[serde(default)]
+    pub archived: Option<ArchiveSnapshot>,
+    /// Pricing, about, careers and blog pages crawled next to the landing page
+    #[serde(default)]
+    pub pages: Vec<PageSection>,
+    /// The HTML the fields were parsed from, for the roast's source snapshot.
+    /// Never cached or sent anywhere with the rest.
+    #[serde(skip)]
+    pub raw_html: Option<String>,
 }
 
 impl StartupInfo {
@@ -17,6 +65,21 @@ impl StartupInfo {
             description: None,
             headings: Vec::new(),
             content_summary: String::new(),
+            ai_wrapper: None,
+            domain_info: None,
+            dns_tls: None,
+            funding: None,
+            security: None,
+            seo: None,
+            social_links: None,
+            logo_candidates: Vec::new(),
+            tech_stack: None,
+            structured_data: None,
+            performance: None,
+            country: None,
+            archived: None,
+            pages: Vec::new(),
+            raw_html: None,
         }
     }
 
@@ -39,4 +102,92 @@ impl StartupInfo {
         self.content_summary = content_summary;
         self
     }
+
+    pub fn with_ai_wrapper(mut self, ai_wrapper: Option<AiWrapperScore>) -> Self {
+        self.ai_wrapper = ai_wrapper;
+        self
+    }
+
+    pub fn with_domain_info(mut self, domain_info: Option<DomainInfo>) -> Self {
+        self.domain_info = domain_info;
+        self
+    }
+
+    pub fn with_dns_tls(mut self, dns_tls: Option<DnsTlsInfo>) -> Self {
+        self.dns_tls = dns_tls;
+        self
+    }
+
+    pub fn with_funding(mut self, funding: Option<FundingInfo>) -> Self {
+        self.funding = funding;
+        self
+    }
+
+    pub fn with_security(mut self, security: Option<SecurityPosture>) -> Self {
+        self.security = security;
+        self
+    }
+
+    pub fn with_seo(mut self, seo: Option<SeoAudit>) -> Self {
+        self.seo = seo;
+        self
+    }
+
+    pub fn with_social_links(mut self, social_links: Option<Vec<SocialLink>>) -> Self {
+        self.social_links = social_links;
+        self
+    }
+
+    pub fn with_logo_candidates(mut self, logo_candidates: Vec<String>) -> Self {
+        self.logo_candidates = logo_candidates;
+        self
+    }
+
+    pub fn with_tech_stack(mut self, tech_stack: Option<TechStack>) -> Self {
+        self.tech_stack = tech_stack;
+        self
+    }
+
+    pub fn with_structured_data(mut self, structured_data: Option<StructuredData>) -> Self {
+        self.structured_data = structured_data;
+        self
+    }
+
+    pub fn with_performance(mut self, performance: Option<PagePerformance>) -> Self {
+        self.performance = performance;
+        self
+    }
+
+    pub fn with_country(mut self, country: Option<String>) -> Self {
+        self.country = country;
+        self
+    }
+
+    pub fn with_archived(mut self, archived: Option<ArchiveSnapshot>) -> Self {
+        self.archived = archived;
+        self
+    }
+
+    pub fn with_pages(mut self, pages: Vec<PageSection>) -> Self {
+        self.pages = pages;
+        self
+    }
+
+    pub fn with_raw_html(mut self, raw_html: Option<String>) -> Self {
+        self.raw_html = raw_html;
+        self
+    }
+
+    /// Characters of scraped text that would end up in a prompt
+    pub fn text_len(&self) -> usize {
+        self.title.as_deref().map_or(0, str::len)
+            + self.description.as_deref().map_or(0, str::len)
+            + self.headings.iter().map(String::len).sum::<usize>()
+            + self.content_summary.len()
+            + self
+                .pages
+                .iter()
+                .map(|p| p.title.as_deref().map_or(0, str::len) + p.content.len())
+                .sum::<usize>()
+    }
 }
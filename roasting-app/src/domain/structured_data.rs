@@ -0,0 +1,115 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Offer {
+    /// The plan or product the price is for
+    #[serde(default)]
+    pub name: Option<String>,
+    pub price: String,
+    #[serde(default)]
+    pub currency: Option<String>,
+}
+
+impl Offer {
+    /// `Pro: 99000 IDR`
+    pub fn describe(&self) -> String {
+        let price = match &self.currency {
+            Some(currency) => format!("{} {}", self.price, currency),
+            None => self.price.clone(),
+        };
+        match &self.name {
+            Some(name) => format!("{}: {}", name, price),
+            None => price,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rating {
+    pub value: String,
+    #[serde(default)]
+    pub best: Option<String>,
+    #[serde(default)]
+    pub count: Option<u64>,
+}
+
+/// Schema.org facts the site publishes about itself in JSON-LD or
+/// microdata: who runs it, since when, what it charges. Concrete claims
+/// the roast can quote instead of guessing from the copy.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructuredData {
+    #[serde(default)]
+    pub organization: Option<String>,
+    /// As written, usually `2023` or `2023-04-01`
+    #[serde(default)]
+    pub founding_date: Option<String>,
+    #[serde(default)]
+    pub founders: Vec<String>,
+    #[serde(default)]
+    pub employees: Option<String>,
+    #[serde(default)]
+    pub offers: Vec<Offer>,
+    #[serde(default)]
+    pub rating: Option<Rating>,
+}
+
+impl StructuredData {
+    pub fn is_empty(&self) -> bool {
+        self.organization.is_none()
+            && self.founding_date.is_none()
+            && self.founders.is_empty()
+            && self.employees.is_none()
+            && self.offers.is_empty()
+            && self.rating.is_none()
+    }
+
+    /// One short phrase per field that was published
+    pub fn facts(&self, language: Language) -> Vec<String> {
+        let mut facts = Vec::new();
+        if let Some(organization) = &self.organization {
+            facts.push(match language {
+                Language::Id => format!("nama perusahaan {}", organization),
+                Language::En => format!("company name {}", organization),
+            });
+        }
+        if let Some(date) = &self.founding_date {
+            facts.push(match language {
+                Language::Id => format!("berdiri {}", date),
+                Language::En => format!("founded {}", date),
+            });
+        }
+        if !self.founders.is_empty() {
+            facts.push(match language {
+                Language::Id => format!("pendiri {}", self.founders.join(", ")),
+                Language::En => format!("founders {}", self.founders.join(", ")),
+            });
+        }
+        if let Some(employees) = &self.employees {
+            facts.push(match language {
+                Language::Id => format!("{} karyawan", employees),
+                Language::En => format!("{} employees", employees),
+            });
+        }
+        if !self.offers.is_empty() {
+            let offers = self.offers.iter().map(Offer::describe).collect::<Vec<_>>().join(", ");
+            facts.push(match language {
+                Language::Id => format!("harga {}", offers),
+                Language::En => format!("pricing {}", offers),
+            });
+        }
+        if let Some(rating) = &self.rating {
+            let score = match &rating.best {
+                Some(best) => format!("{}/{}", rating.value, best),
+                None => rating.value.clone(),
+            };
+            facts.push(match (language, rating.count) {
+                (Language::Id, Some(count)) => format!("ngaku rating {} dari {} ulasan", score, count),
+                (Language::Id, None) => format!("ngaku rating {}", score),
+                (Language::En, Some(count)) => format!("claims a {} rating from {} reviews", score, count),
+                (Language::En, None) => format!("claims a {} rating", score),
+            });
+        }
+        facts
+    }
+}
@@ -0,0 +1,68 @@
+use super::Language;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TechCategory {
+    Cms,
+    NoCode,
+    /// Prompt-to-app builders like Lovable or Bolt, i.e. vibe-coded
+    AiBuilder,
+    Ecommerce,
+    Framework,
+    Backend,
+    Hosting,
+    Library,
+}
+
+impl TechCategory {
+    pub fn label(&self, language: Language) -> &'static str {
+        match (self, language) {
+            (Self::Cms, _) => "CMS",
+            (Self::NoCode, Language::Id) => "no-code",
+            (Self::NoCode, Language::En) => "no-code builder",
+            (Self::AiBuilder, Language::Id) => "AI app builder, alias vibe-coded",
+            (Self::AiBuilder, Language::En) => "AI app builder, i.e. vibe-coded",
+            (Self::Ecommerce, _) => "e-commerce",
+            (Self::Framework, _) => "framework",
+            (Self::Backend, _) => "backend",
+            (Self::Hosting, _) => "hosting",
+            (Self::Library, _) => "library",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Technology {
+    pub name: String,
+    pub category: TechCategory,
+}
+
+/// Technologies the roasted site was fingerprinted with, from its HTML,
+/// response headers and cookies
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TechStack {
+    pub technologies: Vec<Technology>,
+}
+
+impl TechStack {
+    pub fn is_empty(&self) -> bool {
+        self.technologies.is_empty()
+    }
+
+    /// Built with a prompt-to-app builder
+    pub fn is_vibe_coded(&self) -> bool {
+        self.technologies
+            .iter()
+            .any(|t| t.category == TechCategory::AiBuilder)
+    }
+
+    /// `WordPress (CMS), Cloudflare (hosting)`
+    pub fn describe(&self, language: Language) -> String {
+        self.technologies
+            .iter()
+            .map(|t| format!("{} ({})", t.name, t.category.label(language)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
@@ -7,6 +7,8 @@ pub struct User {
     pub email: String,
     pub name: String,
     pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub hide_authorship: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -19,6 +21,7 @@ impl User {
             email,
             name,
             avatar_url,
+            hide_authorship: false,
             created_at: None,
             updated_at: None,
         }
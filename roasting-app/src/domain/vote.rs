@@ -23,3 +23,94 @@ pub struct VoteResult {
     pub voted: bool,
     pub new_fire_count: i32,
 }
+
+/// Why a fire was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteRejection {
+    /// Authors can't fire their own roast up the leaderboard
+    OwnRoast,
+}
+
+/// How much a fire counts toward a roast's fire count, decided once when
+/// it's cast: brand-new accounts count for less so a batch of fresh sign-ups
+/// can't carry a roast to the top
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VotePolicy {
+    /// Accounts younger than this are new
+    pub new_account_days: i64,
+    /// Between 0 (new accounts don't count) and 1 (no discount)
+    pub new_account_weight: f32,
+}
+
+impl VotePolicy {
+    pub fn new(new_account_days: i64, new_account_weight: f32) -> Self {
+        Self {
+            new_account_days: new_account_days.max(0),
+            new_account_weight: if new_account_weight.is_finite() {
+                new_account_weight.clamp(0.0, 1.0)
+            } else {
+                1.0
+            },
+        }
+    }
+
+    /// Weight of a fire from `voter_id` on a roast by `author_id`. Anonymous
+    /// roasts have no author; a voter without a signup date counts in full.
+    pub fn weigh(
+        &self,
+        voter_id: uuid::Uuid,
+        voter_created_at: Option<chrono::DateTime<chrono::Utc>>,
+        author_id: Option<uuid::Uuid>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<f32, VoteRejection> {
+        if author_id == Some(voter_id) {
+            return Err(VoteRejection::OwnRoast);
+        }
+        let is_new = voter_created_at
+            .is_some_and(|created| now - created < chrono::Duration::days(self.new_account_days));
+        Ok(if is_new { self.new_account_weight } else { 1.0 })
+    }
+}
+
+impl Default for VotePolicy {
+    fn default() -> Self {
+        Self::new(3, 0.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_vote_policy_edge_cases() {
+        let policy = VotePolicy::default();
+        let now = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+        let voter = Uuid::new_v4();
+        let author = Uuid::new_v4();
+        let veteran = Some(now - Duration::days(30));
+
+        // Own roast is refused even for veterans; anonymous roasts have no author
+        assert_eq!(
+            policy.weigh(voter, veteran, Some(voter), now),
+            Err(VoteRejection::OwnRoast)
+        );
+        assert_eq!(policy.weigh(voter, veteran, None, now), Ok(1.0));
+        assert_eq!(policy.weigh(voter, veteran, Some(author), now), Ok(1.0));
+
+        // New until exactly `new_account_days` old
+        let just_new = Some(now - Duration::days(3) + Duration::seconds(1));
+        let just_old = Some(now - Duration::days(3));
+        assert_eq!(policy.weigh(voter, just_new, Some(author), now), Ok(0.5));
+        assert_eq!(policy.weigh(voter, just_old, Some(author), now), Ok(1.0));
+        assert_eq!(policy.weigh(voter, None, Some(author), now), Ok(1.0));
+
+        // Weights are clamped; a zero-day window turns the discount off
+        assert_eq!(VotePolicy::new(3, 2.0).new_account_weight, 1.0);
+        assert_eq!(VotePolicy::new(3, -1.0).new_account_weight, 0.0);
+        assert_eq!(VotePolicy::new(3, f32::NAN).new_account_weight, 1.0);
+        assert_eq!(VotePolicy::new(0, 0.0).weigh(voter, Some(now), Some(author), now), Ok(1.0));
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// One startup on the /wall: its most-fired live roast, keyed by domain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WallTile {
+    pub roast_id: Uuid,
+    pub startup_name: String,
+    /// Host without `www.`, e.g. `tokopedia.com`
+    pub domain: String,
+    pub fire_count: i32,
+}
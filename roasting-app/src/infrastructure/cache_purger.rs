@@ -0,0 +1,160 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+const CLOUDFLARE_API_URL: &str = "https://api.cloudflare.com/client/v4/zones";
+const FASTLY_API_URL: &str = "https://api.fastly.com/purge";
+/// Cloudflare accepts at most 30 URLs per purge request
+const CLOUDFLARE_BATCH: usize = 30;
+
+enum CdnProvider {
+    Cloudflare { zone_id: String, api_token: String },
+    Fastly { api_token: String },
+}
+
+/// Purges a roast's public URLs from the CDN in front of the site when the
+/// roast is regenerated, anonymized or removed, so edge caches stop serving
+/// the old version. CDN_PURGE_PROVIDER selects `cloudflare`
+/// (CLOUDFLARE_ZONE_ID, CLOUDFLARE_API_TOKEN) or `fastly` (FASTLY_API_TOKEN).
+pub struct CachePurger {
+    http_client: reqwest::Client,
+    provider: CdnProvider,
+    base_url: String,
+}
+
+impl CachePurger {
+    /// None when no provider is configured; an incomplete configuration is
+    /// logged and also disables purging
+    pub fn from_env(public_base_url: &str) -> Option<Self> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let required = |name: &str| {
+            let value = var(name);
+            if value.is_none() {
+                tracing::warn!(
+                    "CDN_PURGE_PROVIDER is set but {} is missing, CDN purging disabled",
+                    name
+                );
+            }
+            value
+        };
+        let provider = match var("CDN_PURGE_PROVIDER")?.to_lowercase().as_str() {
+            "cloudflare" => CdnProvider::Cloudflare {
+                zone_id: required("CLOUDFLARE_ZONE_ID")?,
+                api_token: required("CLOUDFLARE_API_TOKEN")?,
+            },
+            "fastly" => CdnProvider::Fastly {
+                api_token: required("FASTLY_API_TOKEN")?,
+            },
+            other => {
+                tracing::warn!(
+                    "Unknown CDN_PURGE_PROVIDER {:?}, CDN purging disabled",
+                    other
+                );
+                return None;
+            }
+        };
+        Some(Self {
+            http_client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .expect("Failed to create HTTP client"),
+            provider,
+            base_url: public_base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Permalink, JSON API, summary, sticker image and the feeds that list roasts
+    pub fn roast_urls(&self, roast_id: Uuid) -> Vec<String> {
+        [
+            format!("/r/{}", roast_id),
+            format!("/api/roast/{}", roast_id),
+            format!("/api/roast/{}/summary", roast_id),
+            format!("/api/roast/{}/sticker.webp", roast_id),
+            format!("/api/roast/{}/screenshot.webp", roast_id),
+            format!("/api/roast/{}/versions", roast_id),
+            "/leaderboard".to_string(),
+            "/api/leaderboard".to_string(),
+            "/llms.txt".to_string(),
+        ]
+        .into_iter()
+        .map(|path| format!("{}{}", self.base_url, path))
+        .collect()
+    }
+
+    pub async fn purge(&self, urls: &[String]) -> Result<(), String> {
+        match &self.provider {
+            CdnProvider::Cloudflare { zone_id, api_token } => {
+                for batch in urls.chunks(CLOUDFLARE_BATCH) {
+                    let response = self
+                        .http_client
+                        .post(format!("{}/{}/purge_cache", CLOUDFLARE_API_URL, zone_id))
+                        .bearer_auth(api_token)
+                        .json(&serde_json::json!({ "files": batch }))
+                        .send()
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    if !response.status().is_success() {
+                        return Err(format!("Cloudflare HTTP {}", response.status()));
+                    }
+                }
+            }
+            CdnProvider::Fastly { api_token } => {
+                for url in urls {
+                    let target = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+                    let response = self
+                        .http_client
+                        .post(format!("{}/{}", FASTLY_API_URL, target))
+                        .header("Fastly-Key", api_token)
+                        .send()
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    if !response.status().is_success() {
+                        return Err(format!("Fastly HTTP {} for {}", response.status(), url));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Purge in the background so the write that triggered it doesn't wait
+    /// on the CDN; failures are only logged
+    pub fn purge_roast(self: &Arc<Self>, roast_id: Uuid) {
+        let purger = self.clone();
+        tokio::spawn(async move {
+            let urls = purger.roast_urls(roast_id);
+            match purger.purge(&urls).await {
+                Ok(()) => tracing::info!("Purged {} CDN URLs for roast {}", urls.len(), roast_id),
+                Err(e) => tracing::warn!("CDN purge for roast {} failed: {}", roast_id, e),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roast_urls() {
+        let purger = CachePurger {
+            http_client: reqwest::Client::new(),
+            provider: CdnProvider::Fastly {
+                api_token: "token".to_string(),
+            },
+            base_url: "https://roast.example".to_string(),
+        };
+        let id = Uuid::nil();
+        let urls = purger.roast_urls(id);
+        assert!(urls.contains(&format!("https://roast.example/r/{}", id)));
+        assert!(urls.contains(&format!(
+            "https://roast.example/api/roast/{}/sticker.webp",
+            id
+        )));
+        assert!(urls.contains(&"https://roast.example/leaderboard".to_string()));
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Stops calling a failing dependency after `threshold` consecutive failures,
+/// then lets a single trial call through once `cooldown` has passed.
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    consecutive_failures: AtomicU32,
+    opened_at: Mutex<Option<Instant>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            consecutive_failures: AtomicU32::new(0),
+            opened_at: Mutex::new(None),
+        }
+    }
+
+    /// Whether a call may go through; true again once the cooldown has passed
+    pub fn allow(&self) -> bool {
+        match *self.opened_at.lock().unwrap() {
+            Some(opened) => opened.elapsed() >= self.cooldown,
+            None => true,
+        }
+    }
+
+    /// Open and still cooling down
+    pub fn is_open(&self) -> bool {
+        !self.allow()
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures.load(Ordering::Relaxed)
+    }
+
+    pub fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        *self.opened_at.lock().unwrap() = None;
+    }
+
+    pub fn record_failure(&self) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= self.threshold {
+            *self.opened_at.lock().unwrap() = Some(Instant::now());
+        }
+    }
+
+    /// Record the outcome of a call and pass it through
+    pub fn track<T, E>(&self, result: Result<T, E>) -> Result<T, E> {
+        match &result {
+            Ok(_) => self.record_success(),
+            Err(_) => self.record_failure(),
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold_and_recovers() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        breaker.record_failure();
+        assert!(breaker.allow());
+        breaker.record_failure();
+        assert!(breaker.is_open());
+
+        breaker.record_success();
+        assert!(breaker.allow());
+        assert_eq!(breaker.consecutive_failures(), 0);
+
+        let instant = CircuitBreaker::new(1, Duration::ZERO);
+        instant.record_failure();
+        assert!(instant.allow(), "half-open once the cooldown has passed");
+    }
+}
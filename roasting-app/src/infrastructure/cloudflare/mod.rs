@@ -1,3 +1,5 @@
+mod pool;
 mod solver;
 
+pub use pool::{BrowserPool, PoolConfig, PoolStats, PooledTab};
 pub use solver::CloudflareSolver;
@@ -0,0 +1,402 @@
+use crate::domain::SubsystemHealth;
+use headless_chrome::{Browser, Tab};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// How long a solve waits for a free tab before giving up
+const CHECKOUT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    /// Chrome processes alive at once (SOLVER_MAX_BROWSERS)
+    pub max_browsers: usize,
+    /// Solves sharing one browser (SOLVER_TABS_PER_BROWSER)
+    pub max_tabs: usize,
+    /// A browser without tabs for this long is closed (SOLVER_BROWSER_IDLE_SECS)
+    pub idle_timeout: Duration,
+    /// Solves before a browser is replaced, as Chrome leaks memory (SOLVER_BROWSER_MAX_USES)
+    pub max_uses: u32,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_browsers: 2,
+            max_tabs: 2,
+            idle_timeout: Duration::from_secs(5 * 60),
+            max_uses: 50,
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str, default: T) -> T {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default)
+        }
+
+        let defaults = Self::default();
+        Self {
+            max_browsers: var("SOLVER_MAX_BROWSERS", defaults.max_browsers).max(1),
+            max_tabs: var("SOLVER_TABS_PER_BROWSER", defaults.max_tabs).max(1),
+            idle_timeout: Duration::from_secs(var(
+                "SOLVER_BROWSER_IDLE_SECS",
+                defaults.idle_timeout.as_secs(),
+            )),
+            max_uses: var("SOLVER_BROWSER_MAX_USES", defaults.max_uses).max(1),
+        }
+    }
+}
+
+/// Counters for /healthz
+#[derive(Debug, Clone, Default)]
+pub struct PoolStats {
+    pub browsers: usize,
+    pub tabs_in_use: usize,
+    pub launched: u64,
+    pub recycled: u64,
+    pub checkout_timeouts: u64,
+}
+
+struct Slot<B> {
+    id: u64,
+    proxy: Option<String>,
+    browser: Arc<B>,
+    tabs: usize,
+    uses: u32,
+    last_used: Instant,
+    /// No new tabs; closed once the open ones are returned
+    retired: bool,
+}
+
+enum Acquire<B> {
+    Tab(u64, Arc<B>),
+    /// Room for a new browser, made by evicting this idle one if any
+    Launch(Option<Arc<B>>),
+    Wait,
+}
+
+/// Which browser gets the next tab. Kept apart from Chrome so the
+/// bookkeeping can be tested without launching one.
+struct Slots<B> {
+    config: PoolConfig,
+    slots: Vec<Slot<B>>,
+    /// Browsers being launched, counted against `max_browsers`
+    launching: usize,
+    next_id: u64,
+}
+
+impl<B> Slots<B> {
+    fn new(config: PoolConfig) -> Self {
+        Self {
+            config,
+            slots: Vec::new(),
+            launching: 0,
+            next_id: 0,
+        }
+    }
+
+    /// A tab on the least busy browser for `proxy`, else room to launch
+    /// one, evicting an idle browser for another proxy if need be
+    fn acquire(&mut self, proxy: &Option<String>) -> Acquire<B> {
+        let max_tabs = self.config.max_tabs;
+        let best = self
+            .slots
+            .iter_mut()
+            .filter(|s| !s.retired && s.proxy == *proxy && s.tabs < max_tabs)
+            .min_by_key(|s| s.tabs);
+        if let Some(slot) = best {
+            slot.tabs += 1;
+            slot.uses += 1;
+            return Acquire::Tab(slot.id, slot.browser.clone());
+        }
+
+        let mut evicted = None;
+        if self.slots.len() + self.launching >= self.config.max_browsers {
+            let idle = self
+                .slots
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.tabs == 0)
+                .min_by_key(|(_, s)| s.last_used)
+                .map(|(index, _)| index);
+            match idle {
+                Some(index) => evicted = Some(self.slots.remove(index).browser),
+                None => return Acquire::Wait,
+            }
+        }
+        self.launching += 1;
+        Acquire::Launch(evicted)
+    }
+
+    /// A launched browser joins the pool with its first tab taken
+    fn launched(&mut self, proxy: Option<String>, browser: Arc<B>) -> u64 {
+        self.launching -= 1;
+        self.next_id += 1;
+        self.slots.push(Slot {
+            id: self.next_id,
+            proxy,
+            browser,
+            tabs: 1,
+            uses: 1,
+            last_used: Instant::now(),
+            retired: false,
+        });
+        self.next_id
+    }
+
+    fn launch_failed(&mut self) {
+        self.launching -= 1;
+    }
+
+    /// Hand a tab back; the browser leaves the pool once it is retired and
+    /// empty, and is returned so it can be closed outside the lock
+    fn release(&mut self, id: u64, failed: bool) -> Option<Arc<B>> {
+        let index = self.slots.iter().position(|s| s.id == id)?;
+        let slot = &mut self.slots[index];
+        slot.tabs -= 1;
+        slot.last_used = Instant::now();
+        if failed || slot.uses >= self.config.max_uses {
+            slot.retired = true;
+        }
+        (slot.retired && slot.tabs == 0).then(|| self.slots.remove(index).browser)
+    }
+
+    /// Browsers with no tabs for `idle_timeout`
+    fn reap(&mut self) -> Vec<Arc<B>> {
+        let idle_timeout = self.config.idle_timeout;
+        let (idle, kept) = std::mem::take(&mut self.slots)
+            .into_iter()
+            .partition(|s| s.tabs == 0 && s.last_used.elapsed() >= idle_timeout);
+        self.slots = kept;
+        idle.into_iter().map(|s: Slot<B>| s.browser).collect()
+    }
+}
+
+/// Chrome processes for the Cloudflare solver. A solve checks out a tab,
+/// which is closed and handed back when the guard drops, panics included.
+/// Browsers are checked with a CDP ping before use, replaced after a failed
+/// solve or `max_uses` solves, and closed after sitting idle.
+pub struct BrowserPool {
+    slots: Mutex<Slots<Browser>>,
+    freed: Condvar,
+    launched: AtomicU64,
+    recycled: AtomicU64,
+    checkout_timeouts: AtomicU64,
+}
+
+/// A tab checked out of the pool
+pub struct PooledTab<'a> {
+    pool: &'a BrowserPool,
+    slot: u64,
+    pub tab: Arc<Tab>,
+    failed: bool,
+}
+
+impl PooledTab<'_> {
+    /// Replace this tab's browser once the tab is back, e.g. after it hung
+    pub fn mark_failed(&mut self) {
+        self.failed = true;
+    }
+}
+
+impl Drop for PooledTab<'_> {
+    fn drop(&mut self) {
+        let _ = self.tab.close(true);
+        self.pool.release(self.slot, self.failed);
+    }
+}
+
+impl BrowserPool {
+    pub fn new(config: PoolConfig) -> Self {
+        Self {
+            slots: Mutex::new(Slots::new(config)),
+            freed: Condvar::new(),
+            launched: AtomicU64::new(0),
+            recycled: AtomicU64::new(0),
+            checkout_timeouts: AtomicU64::new(0),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(PoolConfig::from_env())
+    }
+
+    pub fn idle_timeout(&self) -> Duration {
+        self.lock().config.idle_timeout
+    }
+
+    /// A panic elsewhere while holding the lock leaves the counts intact
+    fn lock(&self) -> MutexGuard<'_, Slots<Browser>> {
+        self.slots.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// A fresh tab on a healthy browser for `proxy`, launching one with
+    /// `launch` if there's room. Blocks while every tab is busy, up to
+    /// CHECKOUT_TIMEOUT.
+    pub fn checkout(
+        &self,
+        proxy: &Option<String>,
+        launch: impl Fn() -> Option<Browser>,
+    ) -> Option<PooledTab<'_>> {
+        let deadline = Instant::now() + CHECKOUT_TIMEOUT;
+        loop {
+            let acquired = {
+                let mut slots = self.lock();
+                let idle = slots.reap();
+                self.count_recycled(idle);
+                loop {
+                    match slots.acquire(proxy) {
+                        Acquire::Wait => {
+                            let left = deadline.saturating_duration_since(Instant::now());
+                            if left.is_zero() {
+                                self.checkout_timeouts.fetch_add(1, Ordering::Relaxed);
+                                tracing::warn!("Browser pool: no free tab after {:?}", CHECKOUT_TIMEOUT);
+                                return None;
+                            }
+                            slots = self
+                                .freed
+                                .wait_timeout(slots, left)
+                                .unwrap_or_else(PoisonError::into_inner)
+                                .0;
+                        }
+                        other => break other,
+                    }
+                }
+            };
+
+            let (slot, browser) = match acquired {
+                Acquire::Tab(slot, browser) => {
+                    if browser.get_version().is_err() {
+                        tracing::warn!("Browser pool: browser {} stopped answering, replacing it", slot);
+                        self.release(slot, true);
+                        continue;
+                    }
+                    (slot, browser)
+                }
+                Acquire::Launch(evicted) => {
+                    self.count_recycled(evicted.into_iter().collect());
+                    tracing::info!("Browser pool: launching a browser");
+                    let Some(browser) = launch() else {
+                        self.lock().launch_failed();
+                        self.freed.notify_one();
+                        return None;
+                    };
+                    self.launched.fetch_add(1, Ordering::Relaxed);
+                    let browser = Arc::new(browser);
+                    (self.lock().launched(proxy.clone(), browser.clone()), browser)
+                }
+                // The loop above only stops on a tab or a launch
+                Acquire::Wait => continue,
+            };
+
+            match browser.new_tab() {
+                Ok(tab) => {
+                    return Some(PooledTab {
+                        pool: self,
+                        slot,
+                        tab,
+                        failed: false,
+                    })
+                }
+                Err(e) => {
+                    tracing::warn!("Browser pool: opening a tab failed, replacing browser {}: {}", slot, e);
+                    self.release(slot, true);
+                    if Instant::now() >= deadline {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    fn release(&self, slot: u64, failed: bool) {
+        let closed = self.lock().release(slot, failed);
+        self.count_recycled(closed.into_iter().collect());
+        self.freed.notify_all();
+    }
+
+    /// Dropping the last handle kills the Chrome process
+    fn count_recycled(&self, browsers: Vec<Arc<Browser>>) {
+        if !browsers.is_empty() {
+            self.recycled.fetch_add(browsers.len() as u64, Ordering::Relaxed);
+            tracing::info!("Browser pool: closing {} browsers", browsers.len());
+        }
+    }
+
+    /// Close browsers that have idled past `idle_timeout`
+    pub fn reap_idle(&self) {
+        let idle = self.lock().reap();
+        self.count_recycled(idle);
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        let slots = self.lock();
+        PoolStats {
+            browsers: slots.slots.len(),
+            tabs_in_use: slots.slots.iter().map(|s| s.tabs).sum(),
+            launched: self.launched.load(Ordering::Relaxed),
+            recycled: self.recycled.load(Ordering::Relaxed),
+            checkout_timeouts: self.checkout_timeouts.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Pool status for /healthz; degraded while every tab is taken
+    pub fn health(&self) -> SubsystemHealth {
+        self.reap_idle();
+        let capacity = {
+            let slots = self.lock();
+            slots.config.max_browsers * slots.config.max_tabs
+        };
+        let stats = self.stats();
+        let detail = format!(
+            "{} browsers, {}/{} tabs in use, {} launched, {} recycled, {} checkouts timed out",
+            stats.browsers, stats.tabs_in_use, capacity, stats.launched, stats.recycled, stats.checkout_timeouts
+        );
+        if stats.tabs_in_use >= capacity {
+            SubsystemHealth::degraded("browser_pool", detail)
+        } else {
+            SubsystemHealth::active("browser_pool").with_detail(detail)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slots_share_browsers_and_recycle_them() {
+        let mut slots: Slots<()> = Slots::new(PoolConfig {
+            max_browsers: 1,
+            max_tabs: 2,
+            idle_timeout: Duration::ZERO,
+            max_uses: 3,
+        });
+        let direct = None;
+
+        assert!(matches!(slots.acquire(&direct), Acquire::Launch(None)));
+        let id = slots.launched(direct.clone(), Arc::new(()));
+        assert!(matches!(slots.acquire(&direct), Acquire::Tab(i, _) if i == id));
+        assert!(matches!(slots.acquire(&direct), Acquire::Wait));
+
+        // The third use retires the browser once both tabs are back
+        assert!(slots.release(id, false).is_none());
+        assert!(matches!(slots.acquire(&direct), Acquire::Tab(..)));
+        assert!(slots.release(id, false).is_none());
+        assert!(slots.release(id, false).is_some());
+        assert!(slots.slots.is_empty());
+
+        // An idle browser for another proxy makes room
+        assert!(matches!(slots.acquire(&direct), Acquire::Launch(None)));
+        let id = slots.launched(direct.clone(), Arc::new(()));
+        assert!(slots.release(id, false).is_none());
+        assert!(matches!(slots.acquire(&Some("socks5://p:1080".into())), Acquire::Launch(Some(_))));
+        assert!(slots.slots.is_empty());
+        assert!(slots.reap().is_empty());
+    }
+}
@@ -1,10 +1,10 @@
+use super::pool::BrowserPool;
+use crate::infrastructure::scraper::HeaderProfile;
 use headless_chrome::protocol::cdp::{Emulation, Input, Page};
 use headless_chrome::{Browser, LaunchOptions, Tab};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-static BROWSER_POOL: Mutex<Option<Arc<Browser>>> = Mutex::new(None);
-
 const STEALTH_JS: &str = r#"
 (() => {
     // Remove webdriver property
@@ -30,10 +30,7 @@ const STEALTH_JS: &str = r#"
         }
     });
 
-    // Fake languages
-    Object.defineProperty(navigator, 'languages', {
-        get: () => ['en-US', 'en', 'id-ID', 'id']
-    });
+    // navigator.languages/platform come from the header profile's UA override
 
     // Fake chrome runtime
     window.chrome = {
@@ -102,11 +99,7 @@ const STEALTH_JS: &str = r#"
     };
 
     // Automation detection
-    Object.defineProperty(navigator, 'platform', { get: () => 'MacIntel' });
     Object.defineProperty(navigator, 'vendor', { get: () => 'Google Inc.' });
-    Object.defineProperty(navigator, 'appVersion', {
-        get: () => '5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36'
-    });
 
     // Remove automation flags from window
     delete window.cdc_adoQpoasnfa76pfcZLmcfl_Array;
@@ -131,7 +124,10 @@ const CLOUDFLARE_CHALLENGE_MARKERS: &[&str] = &[
 ];
 
 pub struct CloudflareSolver {
+    pool: Arc<BrowserPool>,
     max_timeout: Duration,
+    /// Chrome `--proxy-server` value
+    proxy: Option<String>,
 }
 
 pub struct SolveResult {
@@ -141,40 +137,33 @@ pub struct SolveResult {
 }
 
 impl CloudflareSolver {
-    pub fn new(max_timeout_secs: u64) -> Self {
+    pub fn new(pool: Arc<BrowserPool>, max_timeout_secs: u64) -> Self {
         Self {
+            pool,
             max_timeout: Duration::from_secs(max_timeout_secs),
+            proxy: None,
         }
     }
 
-    pub fn solve(&self, url: &str) -> Option<SolveResult> {
-        tracing::info!("CloudflareSolver: Starting solve for {}", url);
-
-        let browser = self.get_or_create_browser()?;
-        let tab = browser.new_tab().ok()?;
-
-        self.setup_stealth(&tab)?;
-        let result = self.navigate_and_solve(&tab, url);
-
-        let _ = tab.close(true);
-
-        result
+    /// Browse through `proxy`, on a pooled browser launched with it
+    pub fn with_proxy(mut self, proxy: Option<String>) -> Self {
+        self.proxy = proxy;
+        self
     }
 
-    fn get_or_create_browser(&self) -> Option<Arc<Browser>> {
-        let mut pool = BROWSER_POOL.lock().ok()?;
+    /// Blocks for up to `max_timeout` plus page loads; run it on the blocking pool
+    pub fn solve(&self, url: &str, profile: &HeaderProfile) -> Option<SolveResult> {
+        tracing::info!("CloudflareSolver: Starting solve for {}", url);
 
-        if let Some(ref browser) = *pool {
-            if browser.get_version().is_ok() {
-                tracing::info!("CloudflareSolver: Reusing existing browser");
-                return Some(browser.clone());
-            }
+        // The tab is closed and returned to the pool when `pooled` drops
+        let mut pooled = self
+            .pool
+            .checkout(&self.proxy, || self.create_stealth_browser())?;
+        if self.setup_stealth(&pooled.tab, profile).is_none() {
+            pooled.mark_failed();
+            return None;
         }
-
-        tracing::info!("CloudflareSolver: Creating new browser");
-        let browser = Arc::new(self.create_stealth_browser()?);
-        *pool = Some(browser.clone());
-        Some(browser)
+        self.navigate_and_solve(&pooled.tab, url)
     }
 
     fn create_stealth_browser(&self) -> Option<Browser> {
@@ -213,8 +202,10 @@ impl CloudflareSolver {
         let launch_options = LaunchOptions::default_builder()
             .headless(true)
             .sandbox(false)
-            .idle_browser_timeout(Duration::from_secs(120))
+            // Outlive the pool's idle recycling, which closes it first
+            .idle_browser_timeout(self.pool.idle_timeout() + Duration::from_secs(60))
             .args(args)
+            .proxy_server(self.proxy.as_deref())
             .build()
             .ok()?;
 
@@ -222,9 +213,13 @@ impl CloudflareSolver {
         Browser::new(launch_options).ok()
     }
 
-    fn setup_stealth(&self, tab: &Arc<Tab>) -> Option<()> {
-        let ua = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36";
-        tab.set_user_agent(ua, None, None).ok()?;
+    fn setup_stealth(&self, tab: &Arc<Tab>, profile: &HeaderProfile) -> Option<()> {
+        tab.set_user_agent(
+            profile.user_agent,
+            Some(profile.accept_language),
+            Some(profile.platform),
+        )
+        .ok()?;
 
         let viewport = Emulation::SetDeviceMetricsOverride {
             width: 1920,
@@ -525,27 +520,16 @@ impl CloudflareSolver {
         tracing::info!("CloudflareSolver: Clicked at ({}, {})", base_x, base_y);
     }
 
+    /// Read over CDP rather than document.cookie, which can't see
+    /// HttpOnly cookies like cf_clearance
     fn extract_cookies(&self, tab: &Arc<Tab>) -> Vec<(String, String)> {
-        let get_cookies_js = r#"
-            document.cookie.split(';').map(c => {
-                const [name, ...rest] = c.trim().split('=');
-                return { name, value: rest.join('=') };
-            })
-        "#;
-
-        if let Ok(result) = tab.evaluate(get_cookies_js, false) {
-            if let Some(arr) = result.value.and_then(|v| v.as_array().cloned()) {
-                return arr
-                    .iter()
-                    .filter_map(|c| {
-                        let name = c.get("name")?.as_str()?.to_string();
-                        let value = c.get("value")?.as_str()?.to_string();
-                        Some((name, value))
-                    })
-                    .collect();
+        match tab.get_cookies() {
+            Ok(cookies) => cookies.into_iter().map(|c| (c.name, c.value)).collect(),
+            Err(e) => {
+                tracing::warn!("CloudflareSolver: Reading cookies failed: {}", e);
+                vec![]
             }
         }
-        vec![]
     }
 }
 
@@ -557,9 +541,3 @@ fn rand_f64() -> f64 {
         .subsec_nanos();
     (nanos as f64 % 1000.0) / 1000.0
 }
-
-impl Default for CloudflareSolver {
-    fn default() -> Self {
-        Self::new(60)
-    }
-}
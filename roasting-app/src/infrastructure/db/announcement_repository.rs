@@ -0,0 +1,81 @@
+use super::entities::{announcement, Announcement};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct AnnouncementRepository {
+    db: DatabaseConnection,
+}
+
+impl AnnouncementRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        title: &str,
+        body: &str,
+        published: bool,
+    ) -> Result<announcement::Model, DbErr> {
+        let now = chrono::Utc::now();
+        let active = announcement::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            title: Set(title.to_string()),
+            body: Set(body.to_string()),
+            published: Set(published),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+        };
+        active.insert(&self.db).await
+    }
+
+    pub async fn update(
+        &self,
+        id: Uuid,
+        title: &str,
+        body: &str,
+        published: bool,
+    ) -> Result<Option<announcement::Model>, DbErr> {
+        let Some(existing) = Announcement::find_by_id(id).one(&self.db).await? else {
+            return Ok(None);
+        };
+
+        let mut active: announcement::ActiveModel = existing.into();
+        active.title = Set(title.to_string());
+        active.body = Set(body.to_string());
+        active.published = Set(published);
+        active.updated_at = Set(Some(chrono::Utc::now()));
+        active.update(&self.db).await.map(Some)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<bool, DbErr> {
+        let result = Announcement::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// All announcements including drafts, for the admin view
+    pub async fn list_all(&self) -> Result<Vec<announcement::Model>, DbErr> {
+        Announcement::find()
+            .order_by_desc(announcement::Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn list_published(&self, limit: u64) -> Result<Vec<announcement::Model>, DbErr> {
+        Announcement::find()
+            .filter(announcement::Column::Published.eq(true))
+            .order_by_desc(announcement::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn latest_published(&self) -> Result<Option<announcement::Model>, DbErr> {
+        Announcement::find()
+            .filter(announcement::Column::Published.eq(true))
+            .order_by_desc(announcement::Column::CreatedAt)
+            .one(&self.db)
+            .await
+    }
+}
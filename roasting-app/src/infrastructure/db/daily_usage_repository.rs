@@ -0,0 +1,43 @@
+use super::entities::DailyUsage;
+use chrono::NaiveDate;
+use sea_orm::{entity::*, ConnectionTrait, DatabaseConnection, DbErr, Statement};
+
+const ADD_USAGE_SQL: &str = r#"
+INSERT INTO daily_usage (day, requests, cost_micros, updated_at)
+VALUES ($1, $2, $3, NOW())
+ON CONFLICT (day) DO UPDATE SET
+    requests = daily_usage.requests + EXCLUDED.requests,
+    cost_micros = daily_usage.cost_micros + EXCLUDED.cost_micros,
+    updated_at = NOW()
+"#;
+
+/// Daily budget counters, written behind the in-memory cost tracker
+#[derive(Clone)]
+pub struct DailyUsageRepository {
+    db: DatabaseConnection,
+}
+
+impl DailyUsageRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// (requests, cost in micro-USD) recorded for `day`
+    pub async fn get(&self, day: NaiveDate) -> Result<Option<(u32, u64)>, DbErr> {
+        Ok(DailyUsage::find_by_id(day)
+            .one(&self.db)
+            .await?
+            .map(|row| (row.requests.max(0) as u32, row.cost_micros.max(0) as u64)))
+    }
+
+    pub async fn add(&self, day: NaiveDate, requests: u32, cost_micros: u64) -> Result<(), DbErr> {
+        self.db
+            .execute(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Postgres,
+                ADD_USAGE_SQL,
+                [day.into(), (requests as i32).into(), (cost_micros as i64).into()],
+            ))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "daily_usage")]
+pub struct Model {
+    /// UTC day
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub day: Date,
+    pub requests: i32,
+    /// Model spend in millionths of a USD
+    pub cost_micros: i64,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,29 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "domain_opt_outs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub domain: String,
+    pub token: String,
+    pub requested_by: Option<Uuid>,
+    pub verified_at: Option<DateTimeUtc>,
+    pub created_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for crate::domain::DomainOptOut {
+    fn from(m: Model) -> Self {
+        Self {
+            domain: m.domain,
+            token: m.token,
+            verified_at: m.verified_at,
+            created_at: m.created_at,
+        }
+    }
+}
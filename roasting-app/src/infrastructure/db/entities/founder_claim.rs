@@ -0,0 +1,45 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "founder_claims")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+    /// `DomainOptOut::normalize_domain` form
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub domain: String,
+    pub token: String,
+    pub verified_at: Option<DateTimeUtc>,
+    pub created_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for crate::domain::FounderClaim {
+    fn from(m: Model) -> Self {
+        Self {
+            domain: m.domain,
+            token: m.token,
+            verified_at: m.verified_at,
+            created_at: m.created_at,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "impersonation_logs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub admin_id: Uuid,
+    pub target_user_id: Uuid,
+    pub action: String,
+    pub path: Option<String>,
+    pub created_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for crate::domain::ImpersonationEvent {
+    fn from(m: Model) -> Self {
+        Self {
+            admin_id: m.admin_id,
+            target_user_id: m.target_user_id,
+            action: m.action,
+            path: m.path,
+            created_at: m.created_at,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "ip_bans")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub ip_range: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub reason: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub expires_at: Option<DateTimeUtc>,
+    pub created_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for crate::domain::IpBan {
+    fn from(m: Model) -> Self {
+        Self {
+            id: m.id,
+            range: m.ip_range,
+            reason: m.reason,
+            created_by: m.created_by,
+            expires_at: m.expires_at,
+            created_at: m.created_at,
+        }
+    }
+}
@@ -1,7 +1,61 @@
+pub mod announcement;
+pub mod daily_usage;
+pub mod domain_opt_out;
+pub mod founder_claim;
+pub mod impersonation_log;
+pub mod invite_code;
+pub mod ip_ban;
+pub mod notification;
+pub mod notification_preference;
+pub mod problem_report;
+pub mod push_subscription;
+pub mod request_log;
 pub mod roast;
+pub mod roast_cache;
+pub mod roast_daily_share;
+pub mod roast_daily_view;
+pub mod roast_follow_up;
+pub mod roast_job;
+pub mod roast_removal;
+pub mod roast_report;
+pub mod roast_screenshot;
+pub mod roast_version;
+pub mod scrape_attempt;
+pub mod scrape_cache;
+pub mod scrape_health_report;
+pub mod site_fingerprint;
+pub mod spice_vote;
 pub mod user;
 pub mod vote;
+pub mod watch;
 
+pub use announcement::Entity as Announcement;
+pub use daily_usage::Entity as DailyUsage;
+pub use domain_opt_out::Entity as DomainOptOut;
+pub use founder_claim::Entity as FounderClaim;
+pub use impersonation_log::Entity as ImpersonationLog;
+pub use invite_code::Entity as InviteCode;
+pub use ip_ban::Entity as IpBan;
+pub use notification::Entity as Notification;
+pub use notification_preference::Entity as NotificationPreference;
+pub use problem_report::Entity as ProblemReport;
+pub use push_subscription::Entity as PushSubscription;
+pub use request_log::Entity as RequestLog;
 pub use roast::Entity as Roast;
+pub use roast_cache::Entity as RoastCache;
+pub use roast_daily_share::Entity as RoastDailyShare;
+pub use roast_daily_view::Entity as RoastDailyView;
+pub use roast_follow_up::Entity as RoastFollowUp;
+pub use roast_job::Entity as RoastJob;
+pub use roast_removal::Entity as RoastRemoval;
+pub use roast_report::Entity as RoastReport;
+pub use roast_screenshot::Entity as RoastScreenshot;
+pub use roast_version::Entity as RoastVersion;
+pub use scrape_attempt::Entity as ScrapeAttempt;
+pub use scrape_cache::Entity as ScrapeCache;
+pub use scrape_health_report::Entity as ScrapeHealthReport;
+pub use site_fingerprint::Entity as SiteFingerprint;
+pub use spice_vote::Entity as SpiceVote;
 pub use user::Entity as User;
 pub use vote::Entity as Vote;
+pub use watch::Entity as Watch;
@@ -10,9 +10,50 @@ pub struct Model {
     pub startup_url: String,
     #[sea_orm(column_type = "Text")]
     pub roast_text: String,
+    pub language: String,
+    pub dialect: String,
+    pub category: String,
+    /// JSON-encoded `RoastSections`
+    #[sea_orm(column_type = "Text", nullable)]
+    pub sections: Option<String>,
+    pub score_originality: Option<i16>,
+    pub score_ui: Option<i16>,
+    pub score_viability: Option<i16>,
+    pub score_buzzword: Option<i16>,
+    pub ai_wrapper_confidence: Option<i16>,
+    /// JSON-encoded `SecurityPosture`
+    #[sea_orm(column_type = "Text", nullable)]
+    pub security: Option<String>,
+    /// JSON-encoded `Vec<SocialLink>`
+    #[sea_orm(column_type = "Text", nullable)]
+    pub social_links: Option<String>,
+    /// JSON-encoded `FactSheet`
+    #[sea_orm(column_type = "Text", nullable)]
+    pub fact_sheet: Option<String>,
+    /// One-line summary for crawlers; `None` for roasts created before it existed
+    #[sea_orm(column_type = "Text", nullable)]
+    pub summary: Option<String>,
+    /// Generated in family-safe mode
+    pub safe: bool,
+    /// "Ditujukan untuk", already sanitized
+    #[sea_orm(column_type = "Text", nullable)]
+    pub dedicated_to: Option<String>,
+    /// PROMPT_VARIANT the roast was generated with; `None` for default templates
+    pub prompt_variant: Option<String>,
+    /// ISO alpha-2 code of the startup's likely country; `None` when unknown
+    pub country: Option<String>,
     pub user_id: Option<Uuid>,
+    /// Shown as "Anonim" publicly; `user_id` still records the owner
+    pub anonymous: bool,
     pub fire_count: i32,
     pub created_at: Option<DateTimeUtc>,
+    /// Set when moderation or a takedown hid the roast
+    pub removed_at: Option<DateTimeUtc>,
+    /// Set by the retention job; hidden from listings but still viewable
+    pub archived_at: Option<DateTimeUtc>,
+    /// Embargo: hidden from everyone but the owner until then; the scheduler
+    /// clears it when the roast goes live
+    pub publish_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -41,3 +82,41 @@ impl Related<super::vote::Entity> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// Scorecard, present only when all four scores were stored
+    pub fn score(&self) -> Option<crate::domain::RoastScore> {
+        Some(crate::domain::RoastScore::new(
+            self.score_originality? as u8,
+            self.score_ui? as u8,
+            self.score_viability? as u8,
+            self.score_buzzword? as u8,
+        ))
+    }
+}
+
+impl From<Model> for crate::domain::RoastSummary {
+    fn from(model: Model) -> Self {
+        let score = model.score();
+        let summary = model.summary.clone().unwrap_or_else(|| {
+            let sections: Option<crate::domain::RoastSections> = model
+                .sections
+                .as_deref()
+                .and_then(|s| serde_json::from_str(s).ok());
+            crate::domain::summarize_roast(sections.as_ref(), &model.roast_text)
+        });
+        Self {
+            id: model.id,
+            startup_name: model.startup_name,
+            startup_url: model.startup_url,
+            summary,
+            category: model.category.parse().unwrap_or_default(),
+            language: model.language.parse().unwrap_or_default(),
+            safe: model.safe,
+            overall: score.map(|s| s.overall()),
+            score,
+            fire_count: model.fire_count,
+            created_at: model.created_at,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "roast_daily_shares")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub roast_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub day: Date,
+    /// `ShareChannel::as_str`
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub channel: String,
+    pub shares: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::roast::Entity",
+        from = "Column::RoastId",
+        to = "super::roast::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Roast,
+}
+
+impl Related<super::roast::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Roast.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,53 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "roast_jobs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    #[sea_orm(column_type = "Text")]
+    pub url: String,
+    pub language: String,
+    pub dialect: String,
+    pub length: String,
+    pub safe: bool,
+    pub user_id: Option<Uuid>,
+    pub anonymous: bool,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub dedicated_to: Option<String>,
+    pub status: String,
+    pub attempts: i32,
+    pub roast_id: Option<Uuid>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub created_at: Option<DateTimeUtc>,
+    pub updated_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for crate::domain::RoastJob {
+    fn from(m: Model) -> Self {
+        Self {
+            id: m.id,
+            url: m.url,
+            options: crate::domain::RoastOptions::new(m.language.parse().unwrap_or_default())
+                .with_dialect(m.dialect.parse().unwrap_or_default())
+                .with_length(m.length.parse().unwrap_or_default())
+                .with_safe(m.safe),
+            user_id: m.user_id,
+            anonymous: m.anonymous,
+            dedicated_to: m.dedicated_to,
+            status: m.status.parse().unwrap_or_default(),
+            attempts: m.attempts,
+            roast_id: m.roast_id,
+            last_error: m.last_error,
+            created_at: m.created_at,
+            updated_at: m.updated_at,
+        }
+    }
+}
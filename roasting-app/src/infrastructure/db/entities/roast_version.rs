@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A superseded text of a roast, archived when it is regenerated
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "roast_versions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub roast_id: Uuid,
+    #[sea_orm(column_type = "Text")]
+    pub roast_text: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub sections: Option<String>,
+    pub score_originality: Option<i16>,
+    pub score_ui: Option<i16>,
+    pub score_viability: Option<i16>,
+    pub score_buzzword: Option<i16>,
+    /// When this text was replaced
+    pub created_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
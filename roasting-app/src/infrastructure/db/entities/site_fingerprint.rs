@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "site_fingerprints")]
+pub struct Model {
+    /// `DomainOptOut::normalize_domain` form
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub domain: String,
+    pub content_hash: String,
+    #[sea_orm(column_type = "Text")]
+    pub title: String,
+    /// Newline-separated
+    #[sea_orm(column_type = "Text")]
+    pub headings: String,
+    pub checked_at: DateTimeUtc,
+    pub changed_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
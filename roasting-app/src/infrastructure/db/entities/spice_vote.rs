@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "spice_votes")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub roast_id: Uuid,
+    /// `user:<uuid>` or `session:<uuid>`
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub voter_key: String,
+    pub level: String,
+    pub created_at: Option<DateTimeUtc>,
+    pub updated_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::roast::Entity",
+        from = "Column::RoastId",
+        to = "super::roast::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Roast,
+}
+
+impl Related<super::roast::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Roast.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
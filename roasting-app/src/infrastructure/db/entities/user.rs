@@ -12,6 +12,8 @@ pub struct Model {
     pub email: String,
     pub name: String,
     pub avatar_url: Option<String>,
+    /// Show every roast by this user as "Anonim" publicly
+    pub hide_authorship: bool,
     pub created_at: Option<DateTimeUtc>,
     pub updated_at: Option<DateTimeUtc>,
 }
@@ -0,0 +1,56 @@
+use super::entities::{roast_follow_up, RoastFollowUp};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct FollowUpRepository {
+    db: DatabaseConnection,
+}
+
+impl FollowUpRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        roast_id: Uuid,
+        user_id: Uuid,
+        question: &str,
+        answer: &str,
+    ) -> Result<roast_follow_up::Model, DbErr> {
+        let active = roast_follow_up::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            roast_id: Set(roast_id),
+            user_id: Set(user_id),
+            question: Set(question.to_string()),
+            answer: Set(answer.to_string()),
+            created_at: Set(Some(chrono::Utc::now())),
+        };
+        active.insert(&self.db).await
+    }
+
+    pub async fn find_for_user(
+        &self,
+        roast_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<roast_follow_up::Model>, DbErr> {
+        RoastFollowUp::find()
+            .filter(roast_follow_up::Column::RoastId.eq(roast_id))
+            .filter(roast_follow_up::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await
+    }
+
+    /// Follow-ups on a roast, oldest first so they read as a conversation
+    pub async fn list_for_roast(
+        &self,
+        roast_id: Uuid,
+    ) -> Result<Vec<roast_follow_up::Model>, DbErr> {
+        RoastFollowUp::find()
+            .filter(roast_follow_up::Column::RoastId.eq(roast_id))
+            .order_by_asc(roast_follow_up::Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+}
@@ -0,0 +1,60 @@
+use super::entities::{founder_claim, FounderClaim};
+use sea_orm::{entity::*, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct FounderClaimRepository {
+    db: DatabaseConnection,
+}
+
+impl FounderClaimRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Start (or restart) a claim on `domain`. A pending claim keeps its
+    /// token so instructions already handed out stay valid.
+    pub async fn claim(&self, user_id: Uuid, domain: &str) -> Result<founder_claim::Model, DbErr> {
+        if let Some(existing) = self.find(user_id, domain).await? {
+            return Ok(existing);
+        }
+        founder_claim::ActiveModel {
+            user_id: Set(user_id),
+            domain: Set(domain.to_string()),
+            token: Set(format!("roasting-startup-founder={}", Uuid::new_v4().simple())),
+            verified_at: Set(None),
+            created_at: Set(Some(chrono::Utc::now())),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    pub async fn find(
+        &self,
+        user_id: Uuid,
+        domain: &str,
+    ) -> Result<Option<founder_claim::Model>, DbErr> {
+        FounderClaim::find_by_id((user_id, domain.to_string()))
+            .one(&self.db)
+            .await
+    }
+
+    pub async fn mark_verified(
+        &self,
+        claim: founder_claim::Model,
+    ) -> Result<founder_claim::Model, DbErr> {
+        if claim.verified_at.is_some() {
+            return Ok(claim);
+        }
+        let mut active: founder_claim::ActiveModel = claim.into();
+        active.verified_at = Set(Some(chrono::Utc::now()));
+        active.update(&self.db).await
+    }
+
+    pub async fn is_verified(&self, user_id: Uuid, domain: &str) -> Result<bool, DbErr> {
+        Ok(self
+            .find(user_id, domain)
+            .await?
+            .is_some_and(|c| c.verified_at.is_some()))
+    }
+}
@@ -0,0 +1,42 @@
+use super::entities::{impersonation_log, ImpersonationLog};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ImpersonationRepository {
+    db: DatabaseConnection,
+}
+
+impl ImpersonationRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn log(
+        &self,
+        admin_id: Uuid,
+        target_user_id: Uuid,
+        action: &str,
+        path: Option<&str>,
+    ) -> Result<impersonation_log::Model, DbErr> {
+        impersonation_log::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            admin_id: Set(admin_id),
+            target_user_id: Set(target_user_id),
+            action: Set(action.to_string()),
+            path: Set(path.map(str::to_string)),
+            created_at: Set(Some(chrono::Utc::now())),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    /// Newest events first
+    pub async fn list_recent(&self, limit: u64) -> Result<Vec<impersonation_log::Model>, DbErr> {
+        ImpersonationLog::find()
+            .order_by_desc(impersonation_log::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+}
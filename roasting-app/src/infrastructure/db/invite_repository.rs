@@ -0,0 +1,70 @@
+use super::entities::{invite_code, InviteCode};
+use sea_orm::sea_query::Expr;
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr, TransactionTrait};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct InviteRepository {
+    db: DatabaseConnection,
+}
+
+impl InviteRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Generate `count` new codes that can each be redeemed `max_uses` times
+    pub async fn create_batch(
+        &self,
+        count: u32,
+        max_uses: i32,
+        note: Option<&str>,
+        created_by: Uuid,
+    ) -> Result<Vec<invite_code::Model>, DbErr> {
+        let txn = self.db.begin().await?;
+        let mut created = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let active = invite_code::ActiveModel {
+                code: Set(new_code()),
+                max_uses: Set(max_uses),
+                uses: Set(0),
+                note: Set(note.map(str::to_string)),
+                created_by: Set(Some(created_by)),
+                created_at: Set(Some(chrono::Utc::now())),
+            };
+            created.push(active.insert(&txn).await?);
+        }
+        txn.commit().await?;
+        Ok(created)
+    }
+
+    /// All codes, newest first
+    pub async fn list(&self) -> Result<Vec<invite_code::Model>, DbErr> {
+        InviteCode::find()
+            .order_by_desc(invite_code::Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    /// Use up one redemption of `code`. Returns false when the code doesn't
+    /// exist or has no uses left; the check and increment are one statement.
+    pub async fn redeem(&self, code: &str) -> Result<bool, DbErr> {
+        let result = InviteCode::update_many()
+            .col_expr(
+                invite_code::Column::Uses,
+                Expr::col(invite_code::Column::Uses).add(1),
+            )
+            .filter(invite_code::Column::Code.eq(crate::domain::InviteCode::normalize(code)))
+            .filter(
+                Expr::col(invite_code::Column::Uses).lt(Expr::col(invite_code::Column::MaxUses)),
+            )
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected == 1)
+    }
+}
+
+fn new_code() -> String {
+    let id = Uuid::new_v4().simple().to_string();
+    format!("BETA-{}", id[..8].to_uppercase())
+}
@@ -0,0 +1,73 @@
+use super::entities::{ip_ban, IpBan};
+use chrono::{DateTime, Utc};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct IpBanRepository {
+    db: DatabaseConnection,
+}
+
+impl IpBanRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        ip_range: &str,
+        reason: Option<String>,
+        expires_at: Option<DateTime<Utc>>,
+        created_by: Option<Uuid>,
+    ) -> Result<ip_ban::Model, DbErr> {
+        ip_ban::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            ip_range: Set(ip_range.to_string()),
+            reason: Set(reason),
+            created_by: Set(created_by),
+            expires_at: Set(expires_at),
+            created_at: Set(Some(Utc::now())),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    /// Move a ban's expiry; `None` makes it permanent, a past time lifts it
+    pub async fn set_expiry(
+        &self,
+        id: Uuid,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<ip_ban::Model>, DbErr> {
+        let Some(existing) = IpBan::find_by_id(id).one(&self.db).await? else {
+            return Ok(None);
+        };
+        let mut active: ip_ban::ActiveModel = existing.into();
+        active.expires_at = Set(expires_at);
+        active.update(&self.db).await.map(Some)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<bool, DbErr> {
+        let result = IpBan::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Every ban including expired ones, newest first, for the admin view
+    pub async fn list_all(&self) -> Result<Vec<ip_ban::Model>, DbErr> {
+        IpBan::find()
+            .order_by_desc(ip_ban::Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    /// Bans still in force
+    pub async fn list_active(&self) -> Result<Vec<ip_ban::Model>, DbErr> {
+        IpBan::find()
+            .filter(
+                Condition::any()
+                    .add(ip_ban::Column::ExpiresAt.is_null())
+                    .add(ip_ban::Column::ExpiresAt.gt(Utc::now())),
+            )
+            .all(&self.db)
+            .await
+    }
+}
@@ -1,11 +1,55 @@
 pub mod entities;
+mod announcement_repository;
+mod daily_usage_repository;
+mod follow_up_repository;
+mod founder_claim_repository;
+mod impersonation_repository;
+mod invite_repository;
+mod ip_ban_repository;
+mod moderation_repository;
+mod notification_repository;
+mod opt_out_repository;
+mod problem_report_repository;
+mod push_subscription_repository;
+mod request_log_repository;
+mod retention_repository;
+mod roast_cache_repository;
+mod roast_job_repository;
 mod roast_repository;
+mod roast_stats_repository;
+mod scrape_cache_repository;
+mod scrape_log_repository;
+mod site_fingerprint_repository;
+mod spice_vote_repository;
 mod user_repository;
 mod vote_repository;
+mod watch_repository;
 
+pub use announcement_repository::AnnouncementRepository;
+pub use daily_usage_repository::DailyUsageRepository;
+pub use follow_up_repository::FollowUpRepository;
+pub use founder_claim_repository::FounderClaimRepository;
+pub use impersonation_repository::ImpersonationRepository;
+pub use invite_repository::InviteRepository;
+pub use ip_ban_repository::IpBanRepository;
+pub use moderation_repository::ModerationRepository;
+pub use notification_repository::NotificationRepository;
+pub use opt_out_repository::OptOutRepository;
+pub use problem_report_repository::ProblemReportRepository;
+pub use push_subscription_repository::PushSubscriptionRepository;
+pub use request_log_repository::RequestLogRepository;
+pub use retention_repository::RetentionRepository;
+pub use roast_cache_repository::RoastCacheRepository;
+pub use roast_job_repository::RoastJobRepository;
 pub use roast_repository::RoastRepository;
+pub use roast_stats_repository::RoastStatsRepository;
+pub use scrape_cache_repository::ScrapeCacheRepository;
+pub use scrape_log_repository::ScrapeLogRepository;
+pub use site_fingerprint_repository::SiteFingerprintRepository;
+pub use spice_vote_repository::SpiceVoteRepository;
 pub use user_repository::UserRepository;
 pub use vote_repository::VoteRepository;
+pub use watch_repository::WatchRepository;
 
 use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbErr, Statement};
 use std::time::Duration;
@@ -22,21 +66,69 @@ pub async fn create_connection(database_url: &str) -> Result<DatabaseConnection,
     Database::connect(opt).await
 }
 
-pub async fn run_migrations(db: &DatabaseConnection) -> Result<(), DbErr> {
-    // Read and execute migration file
-    let migration = include_str!("../../../../migrations/001_initial.sql");
+/// Migration files, applied in order on every startup
+const MIGRATIONS: &[&str] = &[
+    include_str!("../../../../migrations/001_initial.sql"),
+    include_str!("../../../../migrations/002_roast_language.sql"),
+    include_str!("../../../../migrations/003_roast_category.sql"),
+    include_str!("../../../../migrations/004_announcements.sql"),
+    include_str!("../../../../migrations/005_roast_dialect.sql"),
+    include_str!("../../../../migrations/006_request_logs.sql"),
+    include_str!("../../../../migrations/007_roast_sections.sql"),
+    include_str!("../../../../migrations/008_roast_jobs.sql"),
+    include_str!("../../../../migrations/009_roast_scores.sql"),
+    include_str!("../../../../migrations/010_roast_job_length.sql"),
+    include_str!("../../../../migrations/011_authorship_privacy.sql"),
+    include_str!("../../../../migrations/012_moderation.sql"),
+    include_str!("../../../../migrations/013_roast_ai_wrapper.sql"),
+    include_str!("../../../../migrations/014_roast_cache.sql"),
+    include_str!("../../../../migrations/015_roast_versions.sql"),
+    include_str!("../../../../migrations/016_roast_security.sql"),
+    include_str!("../../../../migrations/017_roast_archival.sql"),
+    include_str!("../../../../migrations/018_roast_follow_ups.sql"),
+    include_str!("../../../../migrations/019_roast_summary.sql"),
+    include_str!("../../../../migrations/020_safe_mode.sql"),
+    include_str!("../../../../migrations/021_invite_codes.sql"),
+    include_str!("../../../../migrations/022_roast_dedication.sql"),
+    include_str!("../../../../migrations/023_domain_opt_outs.sql"),
+    include_str!("../../../../migrations/024_impersonation_logs.sql"),
+    include_str!("../../../../migrations/025_scrape_health.sql"),
+    include_str!("../../../../migrations/026_spice_votes.sql"),
+    include_str!("../../../../migrations/027_roast_country.sql"),
+    include_str!("../../../../migrations/028_notifications.sql"),
+    include_str!("../../../../migrations/029_push_subscriptions.sql"),
+    include_str!("../../../../migrations/030_roast_publish_at.sql"),
+    include_str!("../../../../migrations/031_watches.sql"),
+    include_str!("../../../../migrations/032_site_fingerprints.sql"),
+    include_str!("../../../../migrations/033_vote_weights.sql"),
+    include_str!("../../../../migrations/034_roast_social_links.sql"),
+    include_str!("../../../../migrations/035_founder_analytics.sql"),
+    include_str!("../../../../migrations/036_problem_reports.sql"),
+    include_str!("../../../../migrations/037_roast_screenshots.sql"),
+    include_str!("../../../../migrations/038_roast_fact_sheets.sql"),
+    include_str!("../../../../migrations/039_scrape_cache.sql"),
+    include_str!("../../../../migrations/040_scrape_cache_warm.sql"),
+    include_str!("../../../../migrations/041_roast_sources.sql"),
+    include_str!("../../../../migrations/042_daily_usage.sql"),
+    include_str!("../../../../migrations/043_roast_costs.sql"),
+    include_str!("../../../../migrations/044_ip_bans.sql"),
+    include_str!("../../../../migrations/045_fire_milestones.sql"),
+];
 
-    // Split by semicolons and execute each statement
-    for statement in migration.split(';') {
-        let statement = statement.trim();
-        if !statement.is_empty() {
-            // Ignore errors for CREATE TABLE IF NOT EXISTS style operations
-            let _ = db
-                .execute(Statement::from_string(
-                    sea_orm::DatabaseBackend::Postgres,
-                    statement.to_string(),
-                ))
-                .await;
+pub async fn run_migrations(db: &DatabaseConnection) -> Result<(), DbErr> {
+    for migration in MIGRATIONS {
+        // Split by semicolons and execute each statement
+        for statement in migration.split(';') {
+            let statement = statement.trim();
+            if !statement.is_empty() {
+                // Ignore errors for CREATE TABLE IF NOT EXISTS style operations
+                let _ = db
+                    .execute(Statement::from_string(
+                        sea_orm::DatabaseBackend::Postgres,
+                        statement.to_string(),
+                    ))
+                    .await;
+            }
         }
     }
 
@@ -0,0 +1,153 @@
+use super::entities::{roast, roast_removal, roast_report, Roast, RoastRemoval, RoastReport};
+use crate::domain::{ModerationStats, RemovalKind, ReportStatus};
+use crate::infrastructure::cache_purger::CachePurger;
+use sea_orm::{entity::*, query::*, sea_query::Expr, DatabaseConnection, DbErr, TransactionTrait};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ModerationRepository {
+    db: DatabaseConnection,
+    purger: Option<Arc<CachePurger>>,
+}
+
+impl ModerationRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db, purger: None }
+    }
+
+    /// Purge a removed roast's CDN URLs so edge caches stop serving it
+    pub fn with_cache_purger(mut self, purger: Option<Arc<CachePurger>>) -> Self {
+        self.purger = purger;
+        self
+    }
+
+    /// File a report; returns `None` if the roast doesn't exist or is already removed
+    pub async fn report(
+        &self,
+        roast_id: Uuid,
+        reporter_id: Option<Uuid>,
+        reason: &str,
+    ) -> Result<Option<roast_report::Model>, DbErr> {
+        let live = Roast::find_by_id(roast_id)
+            .filter(roast::Column::RemovedAt.is_null())
+            .one(&self.db)
+            .await?;
+        if live.is_none() {
+            return Ok(None);
+        }
+
+        let active = roast_report::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            roast_id: Set(roast_id),
+            reporter_id: Set(reporter_id),
+            reason: Set(reason.to_string()),
+            status: Set(ReportStatus::Pending.as_str().to_string()),
+            created_at: Set(Some(chrono::Utc::now())),
+            resolved_at: Set(None),
+        };
+        active.insert(&self.db).await.map(Some)
+    }
+
+    pub async fn list_pending(&self, limit: u64) -> Result<Vec<roast_report::Model>, DbErr> {
+        RoastReport::find()
+            .filter(roast_report::Column::Status.eq(ReportStatus::Pending.as_str()))
+            .order_by_asc(roast_report::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    /// Close a report, removing the roast when `remove` is set. Every other
+    /// pending report on the same roast is closed with it.
+    pub async fn resolve_report(
+        &self,
+        report_id: Uuid,
+        remove: bool,
+    ) -> Result<Option<roast_report::Model>, DbErr> {
+        let Some(report) = RoastReport::find_by_id(report_id).one(&self.db).await? else {
+            return Ok(None);
+        };
+
+        let status = if remove {
+            self.remove_roast(report.roast_id, RemovalKind::Moderation, &report.reason)
+                .await?;
+            ReportStatus::Actioned
+        } else {
+            ReportStatus::Dismissed
+        };
+
+        RoastReport::update_many()
+            .col_expr(roast_report::Column::Status, Expr::value(status.as_str()))
+            .col_expr(roast_report::Column::ResolvedAt, Expr::value(chrono::Utc::now()))
+            .filter(roast_report::Column::RoastId.eq(report.roast_id))
+            .filter(roast_report::Column::Status.eq(ReportStatus::Pending.as_str()))
+            .exec(&self.db)
+            .await?;
+
+        RoastReport::find_by_id(report_id).one(&self.db).await
+    }
+
+    /// Hide a roast from every public view and record why. Returns `false`
+    /// if it doesn't exist or was already removed.
+    pub async fn remove_roast(
+        &self,
+        roast_id: Uuid,
+        kind: RemovalKind,
+        reason: &str,
+    ) -> Result<bool, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let Some(existing) = Roast::find_by_id(roast_id)
+            .filter(roast::Column::RemovedAt.is_null())
+            .one(&txn)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        let now = chrono::Utc::now();
+        let mut active: roast::ActiveModel = existing.into();
+        active.removed_at = Set(Some(now));
+        active.update(&txn).await?;
+
+        roast_removal::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            roast_id: Set(roast_id),
+            kind: Set(kind.as_str().to_string()),
+            reason: Set(reason.to_string()),
+            created_at: Set(Some(now)),
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        if let Some(purger) = &self.purger {
+            purger.purge_roast(roast_id);
+        }
+        Ok(true)
+    }
+
+    /// Counts only; no roast, reporter, or reason details leave this method
+    pub async fn stats(&self) -> Result<ModerationStats, DbErr> {
+        let removals_of = |kind: RemovalKind| {
+            RoastRemoval::find().filter(roast_removal::Column::Kind.eq(kind.as_str()))
+        };
+        let reports_with = |status: ReportStatus| {
+            RoastReport::find().filter(roast_report::Column::Status.eq(status.as_str()))
+        };
+        let month_ago = chrono::Utc::now() - chrono::Duration::days(30);
+
+        Ok(ModerationStats {
+            removed_roasts: RoastRemoval::find().count(&self.db).await?,
+            removed_after_reports: removals_of(RemovalKind::Moderation).count(&self.db).await?,
+            takedowns_honored: removals_of(RemovalKind::Takedown).count(&self.db).await?,
+            pending_reports: reports_with(ReportStatus::Pending).count(&self.db).await?,
+            dismissed_reports: reports_with(ReportStatus::Dismissed).count(&self.db).await?,
+            removed_last_30_days: RoastRemoval::find()
+                .filter(roast_removal::Column::CreatedAt.gte(month_ago))
+                .count(&self.db)
+                .await?,
+        })
+    }
+}
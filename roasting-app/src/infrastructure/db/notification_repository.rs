@@ -0,0 +1,104 @@
+use super::entities::{notification, notification_preference, Notification, NotificationPreference};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct NotificationRepository {
+    db: DatabaseConnection,
+}
+
+impl NotificationRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Store a notification in the user's in-app inbox
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        data: &crate::domain::Notification,
+    ) -> Result<notification::Model, DbErr> {
+        notification::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(user_id),
+            kind: Set(data.kind.as_str().to_string()),
+            title: Set(data.title.clone()),
+            body: Set(data.body.clone()),
+            link: Set(data.link.clone()),
+            read_at: Set(None),
+            created_at: Set(Some(chrono::Utc::now())),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    /// Newest first
+    pub async fn list_for_user(&self, user_id: Uuid, limit: u64) -> Result<Vec<notification::Model>, DbErr> {
+        Notification::find()
+            .filter(notification::Column::UserId.eq(user_id))
+            .order_by_desc(notification::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn unread_count(&self, user_id: Uuid) -> Result<u64, DbErr> {
+        Notification::find()
+            .filter(notification::Column::UserId.eq(user_id))
+            .filter(notification::Column::ReadAt.is_null())
+            .count(&self.db)
+            .await
+    }
+
+    /// Returns how many notifications were unread
+    pub async fn mark_all_read(&self, user_id: Uuid) -> Result<u64, DbErr> {
+        let result = Notification::update_many()
+            .col_expr(notification::Column::ReadAt, Expr::value(chrono::Utc::now()))
+            .filter(notification::Column::UserId.eq(user_id))
+            .filter(notification::Column::ReadAt.is_null())
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Stored channel settings; channels the user never touched have no row
+    pub async fn preferences(&self, user_id: Uuid) -> Result<Vec<notification_preference::Model>, DbErr> {
+        NotificationPreference::find()
+            .filter(notification_preference::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn set_preference(
+        &self,
+        user_id: Uuid,
+        channel: &str,
+        enabled: bool,
+        address: Option<String>,
+    ) -> Result<(), DbErr> {
+        let active = notification_preference::ActiveModel {
+            user_id: Set(user_id),
+            channel: Set(channel.to_string()),
+            enabled: Set(enabled),
+            address: Set(address),
+            updated_at: Set(Some(chrono::Utc::now())),
+        };
+        NotificationPreference::insert(active)
+            .on_conflict(
+                OnConflict::columns([
+                    notification_preference::Column::UserId,
+                    notification_preference::Column::Channel,
+                ])
+                .update_columns([
+                    notification_preference::Column::Enabled,
+                    notification_preference::Column::Address,
+                    notification_preference::Column::UpdatedAt,
+                ])
+                .to_owned(),
+            )
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+}
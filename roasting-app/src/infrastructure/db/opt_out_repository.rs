@@ -0,0 +1,67 @@
+use super::entities::{domain_opt_out, DomainOptOut};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct OptOutRepository {
+    db: DatabaseConnection,
+}
+
+impl OptOutRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Start (or restart) an opt-out for `domain`. A pending request keeps
+    /// its token so instructions already handed out stay valid.
+    pub async fn request(
+        &self,
+        domain: &str,
+        requested_by: Option<Uuid>,
+    ) -> Result<domain_opt_out::Model, DbErr> {
+        if let Some(existing) = self.find(domain).await? {
+            return Ok(existing);
+        }
+        domain_opt_out::ActiveModel {
+            domain: Set(domain.to_string()),
+            token: Set(format!(
+                "roasting-startup-optout={}",
+                Uuid::new_v4().simple()
+            )),
+            requested_by: Set(requested_by),
+            verified_at: Set(None),
+            created_at: Set(Some(chrono::Utc::now())),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    pub async fn find(&self, domain: &str) -> Result<Option<domain_opt_out::Model>, DbErr> {
+        DomainOptOut::find_by_id(domain.to_string())
+            .one(&self.db)
+            .await
+    }
+
+    pub async fn mark_verified(
+        &self,
+        opt_out: domain_opt_out::Model,
+    ) -> Result<domain_opt_out::Model, DbErr> {
+        if opt_out.verified_at.is_some() {
+            return Ok(opt_out);
+        }
+        let mut active: domain_opt_out::ActiveModel = opt_out.into();
+        active.verified_at = Set(Some(chrono::Utc::now()));
+        active.update(&self.db).await
+    }
+
+    /// Domains whose opt-out is verified and therefore enforced
+    pub async fn list_verified_domains(&self) -> Result<Vec<String>, DbErr> {
+        DomainOptOut::find()
+            .filter(domain_opt_out::Column::VerifiedAt.is_not_null())
+            .select_only()
+            .column(domain_opt_out::Column::Domain)
+            .into_tuple()
+            .all(&self.db)
+            .await
+    }
+}
@@ -0,0 +1,78 @@
+use super::entities::{problem_report, ProblemReport};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+/// Problems users report from error pages, the ops side of a trace ID
+#[derive(Clone)]
+pub struct ProblemReportRepository {
+    db: DatabaseConnection,
+}
+
+impl ProblemReportRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        request_id: &str,
+        reporter_id: Option<Uuid>,
+        error: &str,
+        message: &str,
+    ) -> Result<problem_report::Model, DbErr> {
+        problem_report::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            request_id: Set(request_id.to_string()),
+            reporter_id: Set(reporter_id),
+            error: Set(error.to_string()),
+            message: Set(message.to_string()),
+            created_at: Set(Some(chrono::Utc::now())),
+            resolved_at: Set(None),
+        }
+        .insert(&self.db)
+        .await
+    }
+
+    /// Whether this reporter (or, with `None`, an anonymous one) already
+    /// has an open report about `request_id`
+    pub async fn has_open(&self, request_id: &str, reporter_id: Option<Uuid>) -> Result<bool, DbErr> {
+        let reporter = match reporter_id {
+            Some(id) => problem_report::Column::ReporterId.eq(id),
+            None => problem_report::Column::ReporterId.is_null(),
+        };
+        let count = ProblemReport::find()
+            .filter(problem_report::Column::RequestId.eq(request_id))
+            .filter(reporter)
+            .filter(problem_report::Column::ResolvedAt.is_null())
+            .count(&self.db)
+            .await?;
+        Ok(count > 0)
+    }
+
+    /// Unresolved reports, oldest first
+    pub async fn list_open(&self, limit: u64) -> Result<Vec<problem_report::Model>, DbErr> {
+        ProblemReport::find()
+            .filter(problem_report::Column::ResolvedAt.is_null())
+            .order_by_asc(problem_report::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    /// Close a report and every other open one about the same request
+    pub async fn resolve(&self, report_id: Uuid) -> Result<Option<problem_report::Model>, DbErr> {
+        let Some(report) = ProblemReport::find_by_id(report_id).one(&self.db).await? else {
+            return Ok(None);
+        };
+        ProblemReport::update_many()
+            .col_expr(
+                problem_report::Column::ResolvedAt,
+                sea_orm::sea_query::Expr::value(chrono::Utc::now()),
+            )
+            .filter(problem_report::Column::RequestId.eq(report.request_id.as_str()))
+            .filter(problem_report::Column::ResolvedAt.is_null())
+            .exec(&self.db)
+            .await?;
+        ProblemReport::find_by_id(report_id).one(&self.db).await
+    }
+}
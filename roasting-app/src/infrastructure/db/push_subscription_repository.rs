@@ -0,0 +1,67 @@
+use super::entities::{push_subscription, PushSubscription};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct PushSubscriptionRepository {
+    db: DatabaseConnection,
+}
+
+impl PushSubscriptionRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Save a subscription; a browser re-subscribing (or switching accounts)
+    /// replaces the keys and owner of its endpoint
+    pub async fn upsert(&self, user_id: Uuid, endpoint: &str, p256dh: &str, auth: &str) -> Result<(), DbErr> {
+        let active = push_subscription::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(user_id),
+            endpoint: Set(endpoint.to_string()),
+            p256dh: Set(p256dh.to_string()),
+            auth: Set(auth.to_string()),
+            created_at: Set(Some(chrono::Utc::now())),
+        };
+        PushSubscription::insert(active)
+            .on_conflict(
+                OnConflict::column(push_subscription::Column::Endpoint)
+                    .update_columns([
+                        push_subscription::Column::UserId,
+                        push_subscription::Column::P256dh,
+                        push_subscription::Column::Auth,
+                    ])
+                    .to_owned(),
+            )
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<push_subscription::Model>, DbErr> {
+        PushSubscription::find()
+            .filter(push_subscription::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await
+    }
+
+    /// Returns `false` if the user had no such subscription
+    pub async fn delete(&self, user_id: Uuid, endpoint: &str) -> Result<bool, DbErr> {
+        let result = PushSubscription::delete_many()
+            .filter(push_subscription::Column::UserId.eq(user_id))
+            .filter(push_subscription::Column::Endpoint.eq(endpoint))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Drop a subscription the push service reported as expired
+    pub async fn delete_endpoint(&self, endpoint: &str) -> Result<(), DbErr> {
+        PushSubscription::delete_many()
+            .filter(push_subscription::Column::Endpoint.eq(endpoint))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+}
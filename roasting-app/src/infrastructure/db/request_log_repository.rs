@@ -0,0 +1,101 @@
+use super::entities::{request_log, RequestLog};
+use crate::domain::RouteStats;
+use chrono::{DateTime, Utc};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr, FromQueryResult, Statement};
+
+const ROUTE_STATS_SQL: &str = r#"
+SELECT
+    method,
+    route,
+    COUNT(*) AS requests,
+    AVG(CASE WHEN status >= 500 THEN 1.0 ELSE 0.0 END)::float8 AS error_rate,
+    AVG(CASE WHEN status >= 400 AND status < 500 THEN 1.0 ELSE 0.0 END)::float8 AS client_error_rate,
+    AVG(duration_ms)::float8 AS avg_ms,
+    percentile_cont(0.95) WITHIN GROUP (ORDER BY duration_ms)::float8 AS p95_ms,
+    MAX(duration_ms) AS max_ms
+FROM request_logs
+WHERE created_at >= $1
+GROUP BY method, route
+ORDER BY p95_ms DESC
+LIMIT $2
+"#;
+
+#[derive(FromQueryResult)]
+struct RouteStatsRow {
+    method: String,
+    route: String,
+    requests: i64,
+    error_rate: f64,
+    client_error_rate: f64,
+    avg_ms: f64,
+    p95_ms: f64,
+    max_ms: i32,
+}
+
+#[derive(Clone)]
+pub struct RequestLogRepository {
+    db: DatabaseConnection,
+}
+
+impl RequestLogRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn record(
+        &self,
+        method: &str,
+        route: &str,
+        status: u16,
+        duration_ms: u64,
+    ) -> Result<(), DbErr> {
+        let active = request_log::ActiveModel {
+            method: Set(method.to_string()),
+            route: Set(route.to_string()),
+            status: Set(status as i32),
+            duration_ms: Set(duration_ms.min(i32::MAX as u64) as i32),
+            created_at: Set(Some(Utc::now())),
+            ..Default::default()
+        };
+        RequestLog::insert(active).exec(&self.db).await?;
+        Ok(())
+    }
+
+    /// Per-route latency and error stats since `since`, slowest p95 first
+    pub async fn route_stats(
+        &self,
+        since: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<RouteStats>, DbErr> {
+        let rows = RouteStatsRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            ROUTE_STATS_SQL,
+            [since.into(), (limit as i64).into()],
+        ))
+        .all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| RouteStats {
+                method: r.method,
+                route: r.route,
+                requests: r.requests,
+                error_rate: r.error_rate,
+                client_error_rate: r.client_error_rate,
+                avg_ms: r.avg_ms,
+                p95_ms: r.p95_ms,
+                max_ms: r.max_ms,
+            })
+            .collect())
+    }
+
+    /// Delete log rows older than `cutoff`, returning how many were removed
+    pub async fn prune_before(&self, cutoff: DateTime<Utc>) -> Result<u64, DbErr> {
+        let result = RequestLog::delete_many()
+            .filter(request_log::Column::CreatedAt.lt(cutoff))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,123 @@
+use super::entities::{roast, roast_job, roast_version, Roast, RoastJob, RoastVersion};
+use crate::domain::JobStatus;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    entity::*, query::*, sea_query::Expr, DatabaseConnection, DbErr, FromQueryResult, Statement,
+};
+
+const COUNT_OLD_SOURCES_SQL: &str = r#"
+SELECT COUNT(*) AS count FROM roast_sources WHERE created_at < $1
+"#;
+
+const PURGE_OLD_SOURCES_SQL: &str = r#"
+DELETE FROM roast_sources WHERE created_at < $1
+"#;
+
+#[derive(FromQueryResult)]
+struct CountRow {
+    count: i64,
+}
+
+/// Bulk archive/delete queries behind the retention policies. With
+/// `dry_run` each method only counts the rows it would touch.
+#[derive(Clone)]
+pub struct RetentionRepository {
+    db: DatabaseConnection,
+}
+
+impl RetentionRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Archive live roasts that never got a single fire
+    pub async fn archive_cold_roasts(
+        &self,
+        cutoff: DateTime<Utc>,
+        dry_run: bool,
+    ) -> Result<u64, DbErr> {
+        let condition = Condition::all()
+            .add(roast::Column::FireCount.eq(0))
+            .add(roast::Column::CreatedAt.lt(cutoff))
+            .add(roast::Column::ArchivedAt.is_null())
+            .add(roast::Column::RemovedAt.is_null());
+
+        if dry_run {
+            return Roast::find().filter(condition).count(&self.db).await;
+        }
+        let result = Roast::update_many()
+            .col_expr(roast::Column::ArchivedAt, Expr::value(Utc::now()))
+            .filter(condition)
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Delete completed and failed queue jobs; pending ones are never touched
+    pub async fn purge_finished_jobs(
+        &self,
+        cutoff: DateTime<Utc>,
+        dry_run: bool,
+    ) -> Result<u64, DbErr> {
+        let condition = Condition::all()
+            .add(
+                roast_job::Column::Status
+                    .is_in([JobStatus::Completed.as_str(), JobStatus::Failed.as_str()]),
+            )
+            .add(roast_job::Column::UpdatedAt.lt(cutoff));
+
+        if dry_run {
+            return RoastJob::find().filter(condition).count(&self.db).await;
+        }
+        let result = RoastJob::delete_many()
+            .filter(condition)
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Delete superseded roast texts
+    pub async fn purge_roast_versions(
+        &self,
+        cutoff: DateTime<Utc>,
+        dry_run: bool,
+    ) -> Result<u64, DbErr> {
+        let condition = roast_version::Column::CreatedAt.lt(cutoff);
+
+        if dry_run {
+            return RoastVersion::find().filter(condition).count(&self.db).await;
+        }
+        let result = RoastVersion::delete_many()
+            .filter(condition)
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Delete stored scrape snapshots; the roasts stay, `/source` stops answering
+    pub async fn purge_roast_sources(
+        &self,
+        cutoff: DateTime<Utc>,
+        dry_run: bool,
+    ) -> Result<u64, DbErr> {
+        if dry_run {
+            let row = CountRow::find_by_statement(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Postgres,
+                COUNT_OLD_SOURCES_SQL,
+                [cutoff.into()],
+            ))
+            .one(&self.db)
+            .await?;
+            return Ok(row.map_or(0, |r| r.count.max(0) as u64));
+        }
+        let result = self
+            .db
+            .execute(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Postgres,
+                PURGE_OLD_SOURCES_SQL,
+                [cutoff.into()],
+            ))
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,71 @@
+use super::entities::{roast_cache, RoastCache};
+use crate::domain::Roast;
+use sea_orm::{entity::*, query::*, sea_query::OnConflict, DatabaseConnection, DbErr};
+
+const DEFAULT_TTL_HOURS: i64 = 24;
+
+/// Stored roasts reused for repeat submissions of the same URL and options
+#[derive(Clone)]
+pub struct RoastCacheRepository {
+    db: DatabaseConnection,
+    ttl: chrono::Duration,
+}
+
+impl RoastCacheRepository {
+    pub fn new(db: DatabaseConnection, ttl: chrono::Duration) -> Self {
+        Self { db, ttl }
+    }
+
+    /// TTL from ROAST_CACHE_TTL_HOURS (default 24); `0` disables the cache
+    pub fn from_env(db: DatabaseConnection) -> Option<Self> {
+        let hours = std::env::var("ROAST_CACHE_TTL_HOURS")
+            .ok()
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .unwrap_or(DEFAULT_TTL_HOURS);
+        (hours > 0).then(|| Self::new(db, chrono::Duration::hours(hours)))
+    }
+
+    pub async fn get(&self, cache_key: &str) -> Result<Option<Roast>, DbErr> {
+        let cutoff = chrono::Utc::now() - self.ttl;
+        let entry = RoastCache::find_by_id(cache_key.to_string())
+            .filter(roast_cache::Column::CreatedAt.gt(cutoff))
+            .one(&self.db)
+            .await?;
+
+        Ok(entry.and_then(|e| serde_json::from_str(&e.roast).ok()))
+    }
+
+    pub async fn put(&self, cache_key: &str, url: &str, roast: &Roast) -> Result<(), DbErr> {
+        let serialized = serde_json::to_string(roast).map_err(|e| DbErr::Custom(e.to_string()))?;
+        let active = roast_cache::ActiveModel {
+            cache_key: Set(cache_key.to_string()),
+            url: Set(url.to_string()),
+            roast: Set(serialized),
+            created_at: Set(chrono::Utc::now()),
+        };
+
+        RoastCache::insert(active)
+            .on_conflict(
+                OnConflict::column(roast_cache::Column::CacheKey)
+                    .update_columns([
+                        roast_cache::Column::Url,
+                        roast_cache::Column::Roast,
+                        roast_cache::Column::CreatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Drop entries older than the TTL
+    pub async fn purge_expired(&self) -> Result<u64, DbErr> {
+        let cutoff = chrono::Utc::now() - self.ttl;
+        let result = RoastCache::delete_many()
+            .filter(roast_cache::Column::CreatedAt.lte(cutoff))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
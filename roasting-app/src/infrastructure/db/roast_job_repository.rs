@@ -0,0 +1,102 @@
+use super::entities::{roast_job, RoastJob};
+use crate::domain::{JobStatus, RoastOptions};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct RoastJobRepository {
+    db: DatabaseConnection,
+}
+
+impl RoastJobRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn create_deferred(
+        &self,
+        url: &str,
+        options: RoastOptions,
+        user_id: Option<Uuid>,
+        anonymous: bool,
+        dedicated_to: Option<&str>,
+    ) -> Result<roast_job::Model, DbErr> {
+        let now = chrono::Utc::now();
+        let active = roast_job::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            url: Set(url.to_string()),
+            language: Set(options.language.as_str().to_string()),
+            dialect: Set(options.dialect.as_str().to_string()),
+            length: Set(options.length.as_str().to_string()),
+            safe: Set(options.safe),
+            user_id: Set(user_id),
+            anonymous: Set(anonymous),
+            dedicated_to: Set(dedicated_to.map(str::to_string)),
+            status: Set(JobStatus::Deferred.as_str().to_string()),
+            attempts: Set(0),
+            roast_id: Set(None),
+            last_error: Set(None),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+        };
+        active.insert(&self.db).await
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<roast_job::Model>, DbErr> {
+        RoastJob::find_by_id(id).one(&self.db).await
+    }
+
+    pub async fn list_for_user(&self, user_id: Uuid, limit: u64) -> Result<Vec<roast_job::Model>, DbErr> {
+        RoastJob::find()
+            .filter(roast_job::Column::UserId.eq(user_id))
+            .order_by_desc(roast_job::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    /// Oldest deferred jobs first
+    pub async fn list_deferred(&self, limit: u64) -> Result<Vec<roast_job::Model>, DbErr> {
+        RoastJob::find()
+            .filter(roast_job::Column::Status.eq(JobStatus::Deferred.as_str()))
+            .order_by_asc(roast_job::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    /// Update status, bumping the attempt counter when a run is started
+    pub async fn set_status(
+        &self,
+        job: roast_job::Model,
+        status: JobStatus,
+        roast_id: Option<Uuid>,
+        last_error: Option<String>,
+    ) -> Result<roast_job::Model, DbErr> {
+        let attempts = job.attempts;
+        let mut active: roast_job::ActiveModel = job.into();
+        if status == JobStatus::Running {
+            active.attempts = Set(attempts + 1);
+        }
+        active.status = Set(status.as_str().to_string());
+        if roast_id.is_some() {
+            active.roast_id = Set(roast_id);
+        }
+        active.last_error = Set(last_error);
+        active.updated_at = Set(Some(chrono::Utc::now()));
+        active.update(&self.db).await
+    }
+
+    /// Jobs left `running` by a crashed or restarted server go back to the queue
+    pub async fn requeue_running(&self) -> Result<u64, DbErr> {
+        let result = RoastJob::update_many()
+            .col_expr(
+                roast_job::Column::Status,
+                sea_orm::sea_query::Expr::value(JobStatus::Deferred.as_str()),
+            )
+            .filter(roast_job::Column::Status.eq(JobStatus::Running.as_str()))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
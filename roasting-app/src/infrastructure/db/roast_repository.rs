@@ -1,16 +1,187 @@
-use super::entities::{roast, user, vote, Roast, User, Vote};
-use crate::domain::RoastWithDetails;
-use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr, JoinType};
+use super::entities::{roast, roast_version, user, vote, Roast, RoastVersion, User, Vote};
+use crate::domain::{CountryStats, DomainOptOut, RoastCost, RoastSummary, RoastWithDetails, ScrapeSnapshot, WallTile};
+use crate::infrastructure::cache_purger::CachePurger;
+use sea_orm::sea_query::extension::postgres::PgExpr;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    entity::*, query::*, DatabaseConnection, DbErr, FromQueryResult, JoinType, Statement,
+    TransactionTrait,
+};
+use std::io::{Read, Write};
+use std::sync::Arc;
 use uuid::Uuid;
 
+const COUNTRY_STATS_SQL: &str = r#"
+SELECT country, COUNT(*) AS roasts, COALESCE(SUM(fire_count), 0) AS fires
+FROM roasts
+WHERE removed_at IS NULL AND archived_at IS NULL AND publish_at IS NULL
+GROUP BY country
+ORDER BY roasts DESC, country
+"#;
+
+/// The fact sheet's values without its JSON keys, so searching "product"
+/// doesn't match every roast that has one
+const FACT_SHEET_TEXT_SQL: &str = "concat_ws(' ', fact_sheet::jsonb ->> 'product', fact_sheet::jsonb ->> 'target_market', fact_sheet::jsonb ->> 'pricing_model', fact_sheet::jsonb ->> 'cta')";
+
+/// Host of `startup_url` without scheme, `www.`, port or path
+const URL_DOMAIN_SQL: &str =
+    r"LOWER(REGEXP_REPLACE(REGEXP_REPLACE(startup_url, '^[a-zA-Z]+://(www\.)?', ''), '[/:?#].*$', ''))";
+
+/// Each listed startup once, with its most-fired roast, most-fired first
+const WALL_SQL: &str = r#"
+SELECT roast_id, startup_name, domain, fire_count FROM (
+    SELECT DISTINCT ON (domain) id AS roast_id, startup_name, domain, fire_count, created_at
+    FROM (
+        SELECT id, startup_name, fire_count, created_at, {domain} AS domain
+        FROM roasts
+        WHERE removed_at IS NULL AND archived_at IS NULL AND publish_at IS NULL
+    ) listed
+    WHERE domain <> ''
+    ORDER BY domain, fire_count DESC, created_at DESC
+) tiles
+ORDER BY fire_count DESC, domain
+LIMIT $1 OFFSET $2
+"#;
+
+const WALL_COUNT_SQL: &str = r#"
+SELECT COUNT(DISTINCT {domain}) AS startups
+FROM roasts
+WHERE removed_at IS NULL AND archived_at IS NULL AND publish_at IS NULL
+"#;
+
+/// Clears due embargoes in one statement so two instances can't both publish a roast
+const PUBLISH_DUE_SQL: &str = r#"
+UPDATE roasts SET publish_at = NULL
+WHERE publish_at IS NOT NULL AND publish_at <= $1 AND removed_at IS NULL
+RETURNING id
+"#;
+
+#[derive(FromQueryResult)]
+struct WallRow {
+    roast_id: Uuid,
+    startup_name: String,
+    domain: String,
+    fire_count: i32,
+}
+
+#[derive(FromQueryResult)]
+struct WallCountRow {
+    startups: i64,
+}
+
+/// Recounted in one statement so concurrent toggles can't leave a stale count
+const RECOUNT_FIRE_COUNT_SQL: &str = r#"
+UPDATE roasts
+SET fire_count = (SELECT COALESCE(ROUND(SUM(weight)), 0)::int FROM votes WHERE roast_id = $1)
+WHERE id = $1
+RETURNING fire_count
+"#;
+
+#[derive(FromQueryResult)]
+struct FireCountRow {
+    fire_count: i32,
+}
+
+/// A regenerated roast replaces the screenshot of the first one
+const SAVE_SCREENSHOT_SQL: &str = r#"
+INSERT INTO roast_screenshots (roast_id, image, created_at)
+VALUES ($1, $2, NOW())
+ON CONFLICT (roast_id) DO UPDATE SET image = EXCLUDED.image, created_at = EXCLUDED.created_at
+"#;
+
+/// Same visibility as the roast page: not removed, and embargoed roasts only for their owner
+const FIND_SCREENSHOT_SQL: &str = r#"
+SELECT s.image
+FROM roast_screenshots s
+JOIN roasts r ON r.id = s.roast_id
+WHERE s.roast_id = $1
+  AND r.removed_at IS NULL
+  AND (r.publish_at IS NULL OR r.user_id = $2)
+"#;
+
+#[derive(FromQueryResult)]
+struct ScreenshotRow {
+    image: Vec<u8>,
+}
+
+/// Like the screenshot, a regenerated roast replaces the first one's source
+const SAVE_SOURCE_SQL: &str = r#"
+INSERT INTO roast_sources (roast_id, startup_info, html_gz, created_at)
+VALUES ($1, $2, $3, NOW())
+ON CONFLICT (roast_id) DO UPDATE
+SET startup_info = EXCLUDED.startup_info, html_gz = EXCLUDED.html_gz, created_at = EXCLUDED.created_at
+"#;
+
+/// Same visibility as the screenshot
+const FIND_SOURCE_SQL: &str = r#"
+SELECT s.startup_info, s.html_gz, s.created_at
+FROM roast_sources s
+JOIN roasts r ON r.id = s.roast_id
+WHERE s.roast_id = $1
+  AND r.removed_at IS NULL
+  AND (r.publish_at IS NULL OR r.user_id = $2)
+"#;
+
+const SAVE_COST_SQL: &str = r#"
+INSERT INTO roast_costs (roast_id, model, prompt_tokens, completion_tokens, cost_micros)
+VALUES ($1, $2, $3, $4, $5)
+"#;
+
+const FIND_COSTS_SQL: &str = r#"
+SELECT model, prompt_tokens, completion_tokens, cost_micros
+FROM roast_costs
+WHERE roast_id = $1
+ORDER BY id
+"#;
+
+#[derive(FromQueryResult)]
+struct CostRow {
+    model: String,
+    prompt_tokens: i32,
+    completion_tokens: i32,
+    cost_micros: i64,
+}
+
+#[derive(FromQueryResult)]
+struct SourceRow {
+    startup_info: String,
+    html_gz: Option<Vec<u8>>,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(FromQueryResult)]
+struct PublishedRow {
+    id: Uuid,
+}
+
+#[derive(FromQueryResult)]
+struct CountryStatsRow {
+    country: Option<String>,
+    roasts: i64,
+    fires: i64,
+}
+
 #[derive(Clone)]
 pub struct RoastRepository {
     db: DatabaseConnection,
+    purger: Option<Arc<CachePurger>>,
 }
 
 impl RoastRepository {
     pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+        Self { db, purger: None }
+    }
+
+    /// Purge a roast's CDN URLs whenever its public content changes
+    pub fn with_cache_purger(mut self, purger: Option<Arc<CachePurger>>) -> Self {
+        self.purger = purger;
+        self
+    }
+
+    fn invalidate(&self, id: Uuid) {
+        if let Some(purger) = &self.purger {
+            purger.purge_roast(id);
+        }
     }
 
     pub async fn create(&self, roast_data: &crate::domain::PersistedRoast) -> Result<roast::Model, DbErr> {
@@ -19,25 +190,257 @@ impl RoastRepository {
             startup_name: Set(roast_data.startup_name.clone()),
             startup_url: Set(roast_data.startup_url.clone()),
             roast_text: Set(roast_data.roast_text.clone()),
+            language: Set(roast_data.language.as_str().to_string()),
+            dialect: Set(roast_data.dialect.as_str().to_string()),
+            category: Set(roast_data.category.as_str().to_string()),
+            sections: Set(roast_data
+                .sections
+                .as_ref()
+                .and_then(|s| serde_json::to_string(s).ok())),
+            score_originality: Set(roast_data.score.map(|s| s.originality as i16)),
+            score_ui: Set(roast_data.score.map(|s| s.ui as i16)),
+            score_viability: Set(roast_data.score.map(|s| s.viability as i16)),
+            score_buzzword: Set(roast_data.score.map(|s| s.buzzword_density as i16)),
+            ai_wrapper_confidence: Set(roast_data.ai_wrapper_confidence.map(i16::from)),
+            security: Set(roast_data
+                .security
+                .as_ref()
+                .and_then(|s| serde_json::to_string(s).ok())),
+            social_links: Set(Some(&roast_data.social_links)
+                .filter(|links| !links.is_empty())
+                .and_then(|links| serde_json::to_string(links).ok())),
+            fact_sheet: Set(roast_data
+                .fact_sheet
+                .as_ref()
+                .and_then(|f| serde_json::to_string(f).ok())),
+            summary: Set(roast_data.summary.clone()),
+            safe: Set(roast_data.safe),
+            dedicated_to: Set(roast_data.dedicated_to.clone()),
+            prompt_variant: Set(roast_data.prompt_variant.clone()),
+            country: Set(roast_data.country.clone()),
             user_id: Set(roast_data.user_id),
+            anonymous: Set(roast_data.anonymous),
             fire_count: Set(roast_data.fire_count),
             created_at: Set(Some(chrono::Utc::now())),
+            removed_at: Set(None),
+            archived_at: Set(None),
+            publish_at: Set(None),
+        };
+        let created = active.insert(&self.db).await?;
+        if let Some(image) = &roast_data.screenshot {
+            // The roast is worth keeping without its screenshot
+            if let Err(e) = self.save_screenshot(created.id, image).await {
+                tracing::warn!("Failed to store screenshot of roast {}: {}", created.id, e);
+            }
+        }
+        if let Some(source) = &roast_data.source {
+            if let Err(e) = self.save_source(created.id, source).await {
+                tracing::warn!("Failed to store source of roast {}: {}", created.id, e);
+            }
+        }
+        if let Err(e) = self.save_costs(created.id, &roast_data.costs).await {
+            tracing::warn!("Failed to store costs of roast {}: {}", created.id, e);
+        }
+        Ok(created)
+    }
+
+    pub async fn save_screenshot(&self, id: Uuid, image: &[u8]) -> Result<(), DbErr> {
+        self.db
+            .execute(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Postgres,
+                SAVE_SCREENSHOT_SQL,
+                [id.into(), image.to_vec().into()],
+            ))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn save_source(&self, id: Uuid, source: &ScrapeSnapshot) -> Result<(), DbErr> {
+        let startup_info =
+            serde_json::to_string(&source.startup_info).map_err(|e| DbErr::Custom(e.to_string()))?;
+        let html_gz = match &source.html {
+            Some(html) => Some(gzip(html).map_err(|e| DbErr::Custom(e.to_string()))?),
+            None => None,
         };
-        active.insert(&self.db).await
+        self.db
+            .execute(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Postgres,
+                SAVE_SOURCE_SQL,
+                [id.into(), startup_info.into(), html_gz.into()],
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// Add completion costs to a roast; regenerations add theirs to the first
+    pub async fn save_costs(&self, id: Uuid, costs: &[RoastCost]) -> Result<(), DbErr> {
+        for cost in costs {
+            self.db
+                .execute(Statement::from_sql_and_values(
+                    sea_orm::DatabaseBackend::Postgres,
+                    SAVE_COST_SQL,
+                    [
+                        id.into(),
+                        cost.model.clone().into(),
+                        (cost.prompt_tokens as i32).into(),
+                        (cost.completion_tokens as i32).into(),
+                        (cost.cost_micros as i64).into(),
+                    ],
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Every completion spent on a roast, oldest first
+    pub async fn find_costs(&self, id: Uuid) -> Result<Vec<RoastCost>, DbErr> {
+        let rows = CostRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            FIND_COSTS_SQL,
+            [id.into()],
+        ))
+        .all(&self.db)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| RoastCost {
+                model: row.model,
+                prompt_tokens: row.prompt_tokens.max(0) as u32,
+                completion_tokens: row.completion_tokens.max(0) as u32,
+                cost_micros: row.cost_micros.max(0) as u64,
+            })
+            .collect())
+    }
+
+    /// The scrape behind a roast the user may see, with when it was stored
+    pub async fn find_source(
+        &self,
+        id: Uuid,
+        current_user_id: Option<Uuid>,
+    ) -> Result<Option<(ScrapeSnapshot, Option<chrono::DateTime<chrono::Utc>>)>, DbErr> {
+        let row = SourceRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            FIND_SOURCE_SQL,
+            [id.into(), current_user_id.into()],
+        ))
+        .one(&self.db)
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let startup_info =
+            serde_json::from_str(&row.startup_info).map_err(|e| DbErr::Custom(e.to_string()))?;
+        let html = match row.html_gz {
+            Some(bytes) => Some(gunzip(&bytes).map_err(|e| DbErr::Custom(e.to_string()))?),
+            None => None,
+        };
+        Ok(Some((ScrapeSnapshot { startup_info, html }, row.created_at)))
+    }
+
+    /// WebP screenshot of a roast the user may see
+    pub async fn find_screenshot(
+        &self,
+        id: Uuid,
+        current_user_id: Option<Uuid>,
+    ) -> Result<Option<Vec<u8>>, DbErr> {
+        Ok(ScreenshotRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            FIND_SCREENSHOT_SQL,
+            [id.into(), current_user_id.into()],
+        ))
+        .one(&self.db)
+        .await?
+        .map(|row| row.image))
     }
 
     pub async fn find_by_id(&self, id: Uuid) -> Result<Option<roast::Model>, DbErr> {
         Roast::find_by_id(id).one(&self.db).await
     }
 
+    /// Most recent roasts, newest first
+    pub async fn find_recent(&self, limit: u64) -> Result<Vec<roast::Model>, DbErr> {
+        Roast::find()
+            .filter(roast::Column::RemovedAt.is_null())
+            .filter(roast::Column::ArchivedAt.is_null())
+            .filter(roast::Column::PublishAt.is_null())
+            .order_by_desc(roast::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    /// Ids of roasts still listed whose URL is on `domain` or a subdomain of it
+    pub async fn find_listed_ids_for_domain(&self, domain: &str) -> Result<Vec<Uuid>, DbErr> {
+        // LIKE narrows the scan; the host check drops lookalikes such as notexample.com
+        let candidates: Vec<(Uuid, String)> = Roast::find()
+            .filter(roast::Column::RemovedAt.is_null())
+            .filter(Expr::cust_with_values(
+                "LOWER(startup_url) LIKE ?",
+                [format!("%{}%", domain)],
+            ))
+            .select_only()
+            .column(roast::Column::Id)
+            .column(roast::Column::StartupUrl)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        Ok(candidates
+            .into_iter()
+            .filter(|(_, url)| {
+                DomainOptOut::normalize_domain(url)
+                    .is_some_and(|host| DomainOptOut::covers(domain, &host))
+            })
+            .map(|(id, _)| id)
+            .collect())
+    }
+
+    /// The latest visible roast of the same URL made before `roast`, for the compare link
+    pub async fn find_previous_for_url(
+        &self,
+        roast: &RoastWithDetails,
+    ) -> Result<Option<Uuid>, DbErr> {
+        let Some(created_at) = roast.created_at else {
+            return Ok(None);
+        };
+        Roast::find()
+            .filter(roast::Column::RemovedAt.is_null())
+            .filter(roast::Column::PublishAt.is_null())
+            .filter(roast::Column::StartupUrl.eq(roast.startup_url.as_str()))
+            .filter(roast::Column::Id.ne(roast.id))
+            .filter(roast::Column::CreatedAt.lt(created_at))
+            .order_by_desc(roast::Column::CreatedAt)
+            .select_only()
+            .column(roast::Column::Id)
+            .into_tuple()
+            .one(&self.db)
+            .await
+    }
+
+    /// Compact summary of a visible roast for bots; `None` if missing, removed or embargoed
+    pub async fn find_summary(&self, id: Uuid) -> Result<Option<RoastSummary>, DbErr> {
+        Ok(Roast::find_by_id(id)
+            .filter(roast::Column::RemovedAt.is_null())
+            .filter(roast::Column::PublishAt.is_null())
+            .one(&self.db)
+            .await?
+            .map(RoastSummary::from))
+    }
+
     pub async fn find_by_id_with_details(
         &self,
         id: Uuid,
         current_user_id: Option<Uuid>,
     ) -> Result<Option<RoastWithDetails>, DbErr> {
         // Build query with left join to users
+        // Embargoed roasts are visible to their owner only
+        let mut published = Condition::any().add(roast::Column::PublishAt.is_null());
+        if let Some(uid) = current_user_id {
+            published = published.add(roast::Column::UserId.eq(uid));
+        }
         let query = Roast::find()
             .filter(roast::Column::Id.eq(id))
+            .filter(roast::Column::RemovedAt.is_null())
+            .filter(published)
             .join(JoinType::LeftJoin, roast::Relation::User.def())
             .column_as(user::Column::Name, "author_name")
             .column_as(user::Column::AvatarUrl, "author_avatar");
@@ -47,15 +450,7 @@ impl RoastRepository {
 
         match row {
             Some(r) => {
-                // Get user info separately
-                let author_info: Option<(Option<String>, Option<String>)> = if r.user_id.is_some() {
-                    User::find_by_id(r.user_id.unwrap())
-                        .one(&self.db)
-                        .await?
-                        .map(|u| (Some(u.name), u.avatar_url))
-                } else {
-                    None
-                };
+                let author = self.public_author(&r).await?;
 
                 // Check if current user has voted
                 let user_has_voted = match current_user_id {
@@ -72,26 +467,71 @@ impl RoastRepository {
 
                 Ok(Some(RoastWithDetails {
                     id: r.id,
+                    // Borrows `r`, so it must come before the fields that move out of it
+                    score: r.score(),
                     startup_name: r.startup_name,
                     startup_url: r.startup_url,
                     roast_text: r.roast_text,
+                    ai_wrapper_confidence: r.ai_wrapper_confidence.map(|c| c.clamp(0, 100) as u8),
+                    security: r.security.as_deref().and_then(|s| serde_json::from_str(s).ok()),
+                    social_links: r
+                        .social_links
+                        .as_deref()
+                        .and_then(|s| serde_json::from_str(s).ok())
+                        .unwrap_or_default(),
+                    fact_sheet: r.fact_sheet.as_deref().and_then(|s| serde_json::from_str(s).ok()),
+                    safe: r.safe,
+                    dedicated_to: r.dedicated_to,
+                    country: r.country,
+                    language: r.language.parse().unwrap_or_default(),
+                    dialect: r.dialect.parse().unwrap_or_default(),
+                    sections: r.sections.as_deref().and_then(|s| serde_json::from_str(s).ok()),
+                    category: r.category.parse().unwrap_or_default(),
                     fire_count: r.fire_count,
-                    author_name: author_info.as_ref().and_then(|(n, _)| n.clone()),
-                    author_avatar: author_info.and_then(|(_, a)| a),
+                    author_name: author.name,
+                    author_avatar: author.avatar,
+                    is_anonymous: author.anonymous,
+                    is_owner: current_user_id.is_some() && r.user_id == current_user_id,
                     user_has_voted,
                     created_at: r.created_at,
+                    publish_at: r.publish_at,
                 }))
             }
             None => Ok(None),
         }
     }
 
+    /// `search` matches the startup name or its fact sheet, case-insensitively
     pub async fn get_leaderboard(
         &self,
         limit: u64,
         current_user_id: Option<Uuid>,
+        safe_only: bool,
+        country: Option<&str>,
+        search: Option<&str>,
     ) -> Result<Vec<RoastWithDetails>, DbErr> {
-        let roasts: Vec<roast::Model> = Roast::find()
+        let mut query = Roast::find()
+            .filter(roast::Column::RemovedAt.is_null())
+            .filter(roast::Column::ArchivedAt.is_null())
+            .filter(roast::Column::PublishAt.is_null());
+        if safe_only {
+            query = query.filter(roast::Column::Safe.eq(true));
+        }
+        if let Some(country) = country {
+            query = query.filter(roast::Column::Country.eq(country));
+        }
+        if let Some(search) = search {
+            let pattern = format!(
+                "%{}%",
+                search.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+            );
+            query = query.filter(
+                Condition::any()
+                    .add(Expr::col(roast::Column::StartupName).ilike(pattern.as_str()))
+                    .add(Expr::cust(FACT_SHEET_TEXT_SQL).ilike(pattern.as_str())),
+            );
+        }
+        let roasts: Vec<roast::Model> = query
             .order_by_desc(roast::Column::FireCount)
             .order_by_desc(roast::Column::CreatedAt)
             .limit(limit)
@@ -100,15 +540,7 @@ impl RoastRepository {
 
         let mut results = Vec::new();
         for r in roasts {
-            // Get author info
-            let author_info: Option<(String, Option<String>)> = if let Some(uid) = r.user_id {
-                User::find_by_id(uid)
-                    .one(&self.db)
-                    .await?
-                    .map(|u| (u.name, u.avatar_url))
-            } else {
-                None
-            };
+            let author = self.public_author(&r).await?;
 
             // Check if current user has voted
             let user_has_voted = match current_user_id {
@@ -125,45 +557,286 @@ impl RoastRepository {
 
             results.push(RoastWithDetails {
                 id: r.id,
+                score: r.score(),
                 startup_name: r.startup_name,
                 startup_url: r.startup_url,
                 roast_text: r.roast_text,
+                ai_wrapper_confidence: r.ai_wrapper_confidence.map(|c| c.clamp(0, 100) as u8),
+                security: r.security.as_deref().and_then(|s| serde_json::from_str(s).ok()),
+                social_links: r
+                    .social_links
+                    .as_deref()
+                    .and_then(|s| serde_json::from_str(s).ok())
+                    .unwrap_or_default(),
+                fact_sheet: r.fact_sheet.as_deref().and_then(|s| serde_json::from_str(s).ok()),
+                safe: r.safe,
+                dedicated_to: r.dedicated_to,
+                country: r.country,
+                language: r.language.parse().unwrap_or_default(),
+                dialect: r.dialect.parse().unwrap_or_default(),
+                sections: r.sections.as_deref().and_then(|s| serde_json::from_str(s).ok()),
+                category: r.category.parse().unwrap_or_default(),
                 fire_count: r.fire_count,
-                author_name: author_info.as_ref().map(|(n, _)| n.clone()),
-                author_avatar: author_info.and_then(|(_, a)| a),
+                author_name: author.name,
+                author_avatar: author.avatar,
+                is_anonymous: author.anonymous,
+                is_owner: current_user_id.is_some() && r.user_id == current_user_id,
                 user_has_voted,
                 created_at: r.created_at,
+                publish_at: r.publish_at,
             });
         }
 
         Ok(results)
     }
 
-    pub async fn increment_fire_count(&self, id: Uuid) -> Result<i32, DbErr> {
-        let roast = Roast::find_by_id(id)
+    /// Live roast and fire counts per country, most roasted first
+    pub async fn country_stats(&self) -> Result<Vec<CountryStats>, DbErr> {
+        let rows = CountryStatsRow::find_by_statement(Statement::from_string(
+            self.db.get_database_backend(),
+            COUNTRY_STATS_SQL,
+        ))
+        .all(&self.db)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| CountryStats {
+                country: row.country,
+                roasts: row.roasts,
+                fires: row.fires,
+            })
+            .collect())
+    }
+
+    /// One tile per roasted startup for the /wall page
+    pub async fn wall(&self, limit: u64, offset: u64) -> Result<Vec<WallTile>, DbErr> {
+        let rows = WallRow::find_by_statement(Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            WALL_SQL.replace("{domain}", URL_DOMAIN_SQL),
+            [(limit as i64).into(), (offset as i64).into()],
+        ))
+        .all(&self.db)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| WallTile {
+                roast_id: row.roast_id,
+                startup_name: row.startup_name,
+                domain: row.domain,
+                fire_count: row.fire_count,
+            })
+            .collect())
+    }
+
+    /// Distinct startups on the wall, for pagination
+    pub async fn count_wall_startups(&self) -> Result<u64, DbErr> {
+        let row = WallCountRow::find_by_statement(Statement::from_string(
+            self.db.get_database_backend(),
+            WALL_COUNT_SQL.replace("{domain}", URL_DOMAIN_SQL),
+        ))
+        .one(&self.db)
+        .await?;
+        Ok(row.map_or(0, |r| r.startups.max(0) as u64))
+    }
+
+    /// Author info safe to show publicly, honoring both the roast's and the
+    /// author's privacy settings
+    async fn public_author(&self, r: &roast::Model) -> Result<PublicAuthor, DbErr> {
+        let author = match r.user_id {
+            Some(uid) => User::find_by_id(uid).one(&self.db).await?,
+            None => None,
+        };
+
+        Ok(match author {
+            Some(u) if !r.anonymous && !u.hide_authorship => PublicAuthor {
+                name: Some(u.name),
+                avatar: u.avatar_url,
+                anonymous: false,
+            },
+            _ => PublicAuthor {
+                name: None,
+                avatar: None,
+                anonymous: true,
+            },
+        })
+    }
+
+    /// Toggle per-roast anonymity; returns `None` unless `owner_id` owns the roast
+    pub async fn set_anonymous(
+        &self,
+        id: Uuid,
+        owner_id: Uuid,
+        anonymous: bool,
+    ) -> Result<Option<roast::Model>, DbErr> {
+        let Some(roast) = Roast::find_by_id(id)
+            .filter(roast::Column::UserId.eq(owner_id))
             .one(&self.db)
             .await?
-            .ok_or(DbErr::RecordNotFound("Roast not found".to_string()))?;
+        else {
+            return Ok(None);
+        };
 
-        let new_count = roast.fire_count + 1;
         let mut active: roast::ActiveModel = roast.into();
-        active.fire_count = Set(new_count);
-        active.update(&self.db).await?;
-
-        Ok(new_count)
+        active.anonymous = Set(anonymous);
+        let updated = active.update(&self.db).await?;
+        self.invalidate(id);
+        Ok(Some(updated))
     }
 
-    pub async fn decrement_fire_count(&self, id: Uuid) -> Result<i32, DbErr> {
-        let roast = Roast::find_by_id(id)
+    /// Embargo the roast until `publish_at`, or publish it now with `None`.
+    /// Returns `None` unless `owner_id` owns the live roast.
+    pub async fn schedule(
+        &self,
+        id: Uuid,
+        owner_id: Uuid,
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Option<roast::Model>, DbErr> {
+        let Some(roast) = Roast::find_by_id(id)
+            .filter(roast::Column::UserId.eq(owner_id))
+            .filter(roast::Column::RemovedAt.is_null())
             .one(&self.db)
             .await?
-            .ok_or(DbErr::RecordNotFound("Roast not found".to_string()))?;
+        else {
+            return Ok(None);
+        };
 
-        let new_count = (roast.fire_count - 1).max(0);
         let mut active: roast::ActiveModel = roast.into();
-        active.fire_count = Set(new_count);
-        active.update(&self.db).await?;
+        active.publish_at = Set(publish_at);
+        let updated = active.update(&self.db).await?;
+        self.invalidate(id);
+        Ok(Some(updated))
+    }
 
-        Ok(new_count)
+    /// Take every roast whose embargo ended by `now` live, returning their ids
+    pub async fn publish_due(&self, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<Uuid>, DbErr> {
+        let rows = PublishedRow::find_by_statement(Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            PUBLISH_DUE_SQL,
+            [now.into()],
+        ))
+        .all(&self.db)
+        .await?;
+        let ids: Vec<Uuid> = rows.into_iter().map(|row| row.id).collect();
+        for id in &ids {
+            self.invalidate(*id);
+        }
+        Ok(ids)
     }
+
+    /// Swap in a regenerated text, archiving the current one as a previous
+    /// version. Returns `None` if the roast doesn't exist or was removed.
+    pub async fn replace_with_version(
+        &self,
+        id: Uuid,
+        regenerated: &crate::domain::Roast,
+    ) -> Result<Option<roast::Model>, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let Some(existing) = Roast::find_by_id(id)
+            .filter(roast::Column::RemovedAt.is_null())
+            .one(&txn)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        roast_version::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            roast_id: Set(id),
+            roast_text: Set(existing.roast_text.clone()),
+            sections: Set(existing.sections.clone()),
+            score_originality: Set(existing.score_originality),
+            score_ui: Set(existing.score_ui),
+            score_viability: Set(existing.score_viability),
+            score_buzzword: Set(existing.score_buzzword),
+            created_at: Set(Some(chrono::Utc::now())),
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut active: roast::ActiveModel = existing.into();
+        active.roast_text = Set(regenerated.roast_text.clone());
+        active.sections = Set(regenerated
+            .sections
+            .as_ref()
+            .and_then(|s| serde_json::to_string(s).ok()));
+        active.score_originality = Set(regenerated.score.map(|s| s.originality as i16));
+        active.score_ui = Set(regenerated.score.map(|s| s.ui as i16));
+        active.score_viability = Set(regenerated.score.map(|s| s.viability as i16));
+        active.score_buzzword = Set(regenerated.score.map(|s| s.buzzword_density as i16));
+        active.ai_wrapper_confidence = Set(regenerated.ai_wrapper_confidence.map(i16::from));
+        active.security = Set(regenerated
+            .security
+            .as_ref()
+            .and_then(|s| serde_json::to_string(s).ok()));
+        if !regenerated.social_links.is_empty() {
+            active.social_links = Set(serde_json::to_string(&regenerated.social_links).ok());
+        }
+        if let Some(fact_sheet) = &regenerated.fact_sheet {
+            active.fact_sheet = Set(serde_json::to_string(fact_sheet).ok());
+        }
+        active.summary = Set(Some(regenerated.summary()));
+        active.safe = Set(regenerated.safe);
+        if regenerated.country.is_some() {
+            active.country = Set(regenerated.country.clone());
+        }
+        let updated = active.update(&txn).await?;
+
+        txn.commit().await?;
+        if let Some(image) = &regenerated.screenshot {
+            if let Err(e) = self.save_screenshot(id, image).await {
+                tracing::warn!("Failed to store screenshot of roast {}: {}", id, e);
+            }
+        }
+        if let Some(source) = &regenerated.source {
+            if let Err(e) = self.save_source(id, source).await {
+                tracing::warn!("Failed to store source of roast {}: {}", id, e);
+            }
+        }
+        if let Err(e) = self.save_costs(id, &regenerated.costs).await {
+            tracing::warn!("Failed to store costs of roast {}: {}", id, e);
+        }
+        self.invalidate(id);
+        Ok(Some(updated))
+    }
+
+    /// Previous texts of a roast, most recently replaced first
+    pub async fn list_versions(&self, id: Uuid) -> Result<Vec<roast_version::Model>, DbErr> {
+        RoastVersion::find()
+            .filter(roast_version::Column::RoastId.eq(id))
+            .order_by_desc(roast_version::Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    /// Set the fire count to the rounded sum of the roast's vote weights
+    pub async fn recount_fire_count(&self, id: Uuid) -> Result<i32, DbErr> {
+        let row = FireCountRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            RECOUNT_FIRE_COUNT_SQL,
+            [id.into()],
+        ))
+        .one(&self.db)
+        .await?
+        .ok_or(DbErr::RecordNotFound("Roast not found".to_string()))?;
+        Ok(row.fire_count)
+    }
+}
+
+fn gzip(text: &str) -> std::io::Result<Vec<u8>> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(text.as_bytes())?;
+    encoder.finish()
+}
+
+fn gunzip(bytes: &[u8]) -> std::io::Result<String> {
+    let mut text = String::new();
+    flate2::read::GzDecoder::new(bytes).read_to_string(&mut text)?;
+    Ok(text)
+}
+
+struct PublicAuthor {
+    name: Option<String>,
+    avatar: Option<String>,
+    anonymous: bool,
 }
@@ -0,0 +1,182 @@
+use super::entities::{roast_daily_share, roast_daily_view, vote, RoastDailyShare, RoastDailyView, Vote};
+use crate::domain::{DailyCount, ReferrerCount, ShareChannel, ShareCount};
+use chrono::NaiveDate;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    entity::*, query::*, ConnectionTrait, DatabaseConnection, DbErr, FromQueryResult, Statement,
+};
+use uuid::Uuid;
+
+const RECORD_VIEW_SQL: &str = r#"
+INSERT INTO roast_daily_views (roast_id, day, referrer, views)
+VALUES ($1, CURRENT_DATE, $2, 1)
+ON CONFLICT (roast_id, day, referrer) DO UPDATE SET views = roast_daily_views.views + 1
+"#;
+
+const RECORD_SHARE_SQL: &str = r#"
+INSERT INTO roast_daily_shares (roast_id, day, channel, shares)
+VALUES ($1, CURRENT_DATE, $2, 1)
+ON CONFLICT (roast_id, day, channel) DO UPDATE SET shares = roast_daily_shares.shares + 1
+"#;
+
+/// Roasts plus regenerations per URL; a one-off roast doesn't make a site popular
+const POPULAR_URLS_SQL: &str = r#"
+WITH activity AS (
+    SELECT startup_url, created_at FROM roasts WHERE removed_at IS NULL
+    UNION ALL
+    SELECT r.startup_url, v.created_at FROM roast_versions v JOIN roasts r ON r.id = v.roast_id
+)
+SELECT startup_url AS key, COUNT(*)::BIGINT AS count
+FROM activity
+WHERE created_at >= $1 AND startup_url NOT LIKE 'deck:%' AND startup_url NOT LIKE 'idea:%'
+GROUP BY startup_url
+HAVING COUNT(*) >= 2
+ORDER BY count DESC, startup_url
+LIMIT $2
+"#;
+
+#[derive(FromQueryResult)]
+struct DailyRow {
+    day: NaiveDate,
+    count: i64,
+}
+
+#[derive(FromQueryResult)]
+struct KeyedRow {
+    key: String,
+    count: i64,
+}
+
+/// Per-day view, referrer and share counters behind the founder analytics
+#[derive(Clone)]
+pub struct RoastStatsRepository {
+    db: DatabaseConnection,
+}
+
+impl RoastStatsRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn record_view(&self, roast_id: Uuid, referrer: &str) -> Result<(), DbErr> {
+        self.db
+            .execute(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Postgres,
+                RECORD_VIEW_SQL,
+                [roast_id.into(), referrer.into()],
+            ))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn record_share(&self, roast_id: Uuid, channel: ShareChannel) -> Result<(), DbErr> {
+        self.db
+            .execute(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Postgres,
+                RECORD_SHARE_SQL,
+                [roast_id.into(), channel.as_str().into()],
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// The most roasted and regenerated URLs since `since`, busiest first
+    pub async fn popular_urls(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: u64,
+    ) -> Result<Vec<String>, DbErr> {
+        let rows = KeyedRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            POPULAR_URLS_SQL,
+            [since.into(), (limit as i64).into()],
+        ))
+        .all(&self.db)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.key).collect())
+    }
+
+    pub async fn daily_views(
+        &self,
+        roast_ids: &[Uuid],
+        since: NaiveDate,
+    ) -> Result<Vec<DailyCount>, DbErr> {
+        let rows = RoastDailyView::find()
+            .select_only()
+            .column(roast_daily_view::Column::Day)
+            .column_as(Expr::cust("SUM(views)::BIGINT"), "count")
+            .filter(roast_daily_view::Column::RoastId.is_in(roast_ids.iter().copied()))
+            .filter(roast_daily_view::Column::Day.gte(since))
+            .group_by(roast_daily_view::Column::Day)
+            .order_by_asc(roast_daily_view::Column::Day)
+            .into_model::<DailyRow>()
+            .all(&self.db)
+            .await?;
+        Ok(rows.into_iter().map(|r| DailyCount { day: r.day, count: r.count }).collect())
+    }
+
+    pub async fn referrers(
+        &self,
+        roast_ids: &[Uuid],
+        since: NaiveDate,
+        limit: u64,
+    ) -> Result<Vec<ReferrerCount>, DbErr> {
+        let rows = RoastDailyView::find()
+            .select_only()
+            .column_as(roast_daily_view::Column::Referrer, "key")
+            .column_as(Expr::cust("SUM(views)::BIGINT"), "count")
+            .filter(roast_daily_view::Column::RoastId.is_in(roast_ids.iter().copied()))
+            .filter(roast_daily_view::Column::Day.gte(since))
+            .group_by(roast_daily_view::Column::Referrer)
+            .order_by_desc(Expr::cust("SUM(views)"))
+            .limit(limit)
+            .into_model::<KeyedRow>()
+            .all(&self.db)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| ReferrerCount { referrer: r.key, views: r.count })
+            .collect())
+    }
+
+    pub async fn shares(&self, roast_ids: &[Uuid], since: NaiveDate) -> Result<Vec<ShareCount>, DbErr> {
+        let rows = RoastDailyShare::find()
+            .select_only()
+            .column_as(roast_daily_share::Column::Channel, "key")
+            .column_as(Expr::cust("SUM(shares)::BIGINT"), "count")
+            .filter(roast_daily_share::Column::RoastId.is_in(roast_ids.iter().copied()))
+            .filter(roast_daily_share::Column::Day.gte(since))
+            .group_by(roast_daily_share::Column::Channel)
+            .order_by_desc(Expr::cust("SUM(shares)"))
+            .into_model::<KeyedRow>()
+            .all(&self.db)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| ShareCount { channel: r.key, shares: r.count })
+            .collect())
+    }
+
+    /// Fires given per day; self-votes never count, see `VoteRejection::OwnRoast`
+    pub async fn daily_votes(
+        &self,
+        roast_ids: &[Uuid],
+        since: NaiveDate,
+    ) -> Result<Vec<DailyCount>, DbErr> {
+        let rows = Vote::find()
+            .select_only()
+            .column_as(Expr::cust("(created_at AT TIME ZONE 'UTC')::DATE"), "day")
+            .column_as(Expr::cust("COUNT(*)"), "count")
+            .filter(vote::Column::RoastId.is_in(roast_ids.iter().copied()))
+            .filter(Expr::cust_with_values(
+                "(created_at AT TIME ZONE 'UTC')::DATE >= ?",
+                [since],
+            ))
+            .group_by(Expr::cust("(created_at AT TIME ZONE 'UTC')::DATE"))
+            .order_by_asc(Expr::cust("(created_at AT TIME ZONE 'UTC')::DATE"))
+            .into_model::<DailyRow>()
+            .all(&self.db)
+            .await?;
+        Ok(rows.into_iter().map(|r| DailyCount { day: r.day, count: r.count }).collect())
+    }
+}
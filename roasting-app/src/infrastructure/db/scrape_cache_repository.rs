@@ -0,0 +1,118 @@
+use super::entities::{scrape_cache, ScrapeCache};
+use crate::domain::StartupInfo;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+
+const DEFAULT_TTL_HOURS: i64 = 6;
+/// Warm entries are refreshed nightly; this outlives one missed night's run
+const WARM_TTL_HOURS: i64 = 36;
+
+/// Scraped sites reused for repeat roasts and regenerations of the same URL,
+/// so the site isn't fetched again for every submission
+#[derive(Clone)]
+pub struct ScrapeCacheRepository {
+    db: DatabaseConnection,
+    ttl: chrono::Duration,
+}
+
+impl ScrapeCacheRepository {
+    pub fn new(db: DatabaseConnection, ttl: chrono::Duration) -> Self {
+        Self { db, ttl }
+    }
+
+    /// TTL from SCRAPE_CACHE_TTL_HOURS (default 6); `0` disables the cache
+    pub fn from_env(db: DatabaseConnection) -> Option<Self> {
+        let hours = std::env::var("SCRAPE_CACHE_TTL_HOURS")
+            .ok()
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .unwrap_or(DEFAULT_TTL_HOURS);
+        (hours > 0).then(|| Self::new(db, chrono::Duration::hours(hours)))
+    }
+
+    /// Entries still within their TTL: the configured one, or the warm one
+    /// for popular sites
+    fn live(&self) -> Condition {
+        let now = chrono::Utc::now();
+        Condition::any()
+            .add(scrape_cache::Column::CreatedAt.gt(now - self.ttl))
+            .add(
+                Condition::all()
+                    .add(scrape_cache::Column::Warm.eq(true))
+                    .add(scrape_cache::Column::CreatedAt.gt(now - chrono::Duration::hours(WARM_TTL_HOURS))),
+            )
+    }
+
+    pub async fn get(&self, url: &str) -> Result<Option<StartupInfo>, DbErr> {
+        let entry = ScrapeCache::find_by_id(url.to_string())
+            .filter(self.live())
+            .one(&self.db)
+            .await?;
+
+        Ok(entry.and_then(|e| serde_json::from_str(&e.startup_info).ok()))
+    }
+
+    /// When the URL was last scraped, if it still has an entry
+    pub async fn cached_at(&self, url: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>, DbErr> {
+        Ok(ScrapeCache::find_by_id(url.to_string())
+            .one(&self.db)
+            .await?
+            .map(|e| e.created_at))
+    }
+
+    /// Store a scrape. `warm` marks a popular site kept by the nightly
+    /// refresh; a user's scrape of a warm site leaves it warm.
+    pub async fn put(&self, url: &str, startup_info: &StartupInfo, warm: bool) -> Result<(), DbErr> {
+        let serialized =
+            serde_json::to_string(startup_info).map_err(|e| DbErr::Custom(e.to_string()))?;
+        let active = scrape_cache::ActiveModel {
+            url: Set(url.to_string()),
+            startup_info: Set(serialized),
+            warm: Set(warm),
+            created_at: Set(chrono::Utc::now()),
+        };
+
+        let mut columns = vec![scrape_cache::Column::StartupInfo, scrape_cache::Column::CreatedAt];
+        if warm {
+            columns.push(scrape_cache::Column::Warm);
+        }
+        ScrapeCache::insert(active)
+            .on_conflict(
+                OnConflict::column(scrape_cache::Column::Url)
+                    .update_columns(columns)
+                    .to_owned(),
+            )
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Keep an entry a user's roast just stored as long as a warmed one
+    pub async fn mark_warm(&self, url: &str) -> Result<(), DbErr> {
+        ScrapeCache::update_many()
+            .col_expr(scrape_cache::Column::Warm, Expr::value(true))
+            .filter(scrape_cache::Column::Url.eq(url))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Sites that dropped out of the popular list go back to the normal TTL
+    pub async fn cool_down_except(&self, urls: &[String]) -> Result<u64, DbErr> {
+        let result = ScrapeCache::update_many()
+            .col_expr(scrape_cache::Column::Warm, Expr::value(false))
+            .filter(scrape_cache::Column::Warm.eq(true))
+            .filter(scrape_cache::Column::Url.is_not_in(urls.iter().cloned()))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Drop entries past their TTL
+    pub async fn purge_expired(&self) -> Result<u64, DbErr> {
+        let result = ScrapeCache::delete_many()
+            .filter(self.live().not())
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,155 @@
+use super::entities::{scrape_attempt, scrape_health_report, ScrapeAttempt, ScrapeHealthReport};
+use crate::domain::{FailingDomain, ScrapeMethodStats};
+use chrono::{DateTime, Utc};
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr, FromQueryResult, Statement};
+
+const METHOD_STATS_SQL: &str = r#"
+SELECT
+    method,
+    COUNT(*) AS attempts,
+    AVG(CASE WHEN success THEN 1.0 ELSE 0.0 END)::float8 AS success_rate,
+    AVG(duration_ms)::float8 AS avg_ms
+FROM scrape_attempts
+WHERE created_at >= $1
+GROUP BY method
+ORDER BY attempts DESC
+"#;
+
+const FAILING_DOMAINS_SQL: &str = r#"
+SELECT
+    domain,
+    COUNT(*) AS failures,
+    (ARRAY_AGG(error ORDER BY created_at DESC))[1] AS last_error
+FROM scrape_attempts
+WHERE created_at >= $1 AND NOT success
+GROUP BY domain
+ORDER BY failures DESC
+LIMIT $2
+"#;
+
+#[derive(FromQueryResult)]
+struct MethodStatsRow {
+    method: String,
+    attempts: i64,
+    success_rate: f64,
+    avg_ms: f64,
+}
+
+#[derive(FromQueryResult)]
+struct FailingDomainRow {
+    domain: String,
+    failures: i64,
+    last_error: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct ScrapeLogRepository {
+    db: DatabaseConnection,
+}
+
+impl ScrapeLogRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn record(
+        &self,
+        method: &str,
+        domain: &str,
+        success: bool,
+        duration_ms: u64,
+        error: Option<&str>,
+    ) -> Result<(), DbErr> {
+        let active = scrape_attempt::ActiveModel {
+            method: Set(method.to_string()),
+            domain: Set(domain.chars().take(255).collect()),
+            success: Set(success),
+            duration_ms: Set(duration_ms.min(i32::MAX as u64) as i32),
+            error: Set(error.map(|e| e.chars().take(500).collect())),
+            created_at: Set(Some(Utc::now())),
+            ..Default::default()
+        };
+        ScrapeAttempt::insert(active).exec(&self.db).await?;
+        Ok(())
+    }
+
+    /// Attempts, success rate and latency per method since `since`, busiest first
+    pub async fn method_stats(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ScrapeMethodStats>, DbErr> {
+        let rows = MethodStatsRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            METHOD_STATS_SQL,
+            [since.into()],
+        ))
+        .all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| ScrapeMethodStats {
+                method: r.method,
+                attempts: r.attempts,
+                success_rate: r.success_rate,
+                avg_ms: r.avg_ms,
+            })
+            .collect())
+    }
+
+    /// Domains with the most failed attempts since `since`
+    pub async fn failing_domains(
+        &self,
+        since: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<FailingDomain>, DbErr> {
+        let rows = FailingDomainRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            FAILING_DOMAINS_SQL,
+            [since.into(), (limit as i64).into()],
+        ))
+        .all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| FailingDomain {
+                domain: r.domain,
+                failures: r.failures,
+                last_error: r.last_error,
+            })
+            .collect())
+    }
+
+    pub async fn save_report(&self, period_hours: i64, report: &str) -> Result<(), DbErr> {
+        let active = scrape_health_report::ActiveModel {
+            period_hours: Set(period_hours as i32),
+            report: Set(report.to_string()),
+            created_at: Set(Some(Utc::now())),
+            ..Default::default()
+        };
+        ScrapeHealthReport::insert(active).exec(&self.db).await?;
+        Ok(())
+    }
+
+    /// Stored reports, newest first
+    pub async fn recent_reports(
+        &self,
+        limit: u64,
+    ) -> Result<Vec<scrape_health_report::Model>, DbErr> {
+        ScrapeHealthReport::find()
+            .order_by_desc(scrape_health_report::Column::CreatedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    /// Delete attempts older than `cutoff`, returning how many were removed
+    pub async fn prune_before(&self, cutoff: DateTime<Utc>) -> Result<u64, DbErr> {
+        let result = ScrapeAttempt::delete_many()
+            .filter(scrape_attempt::Column::CreatedAt.lt(cutoff))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
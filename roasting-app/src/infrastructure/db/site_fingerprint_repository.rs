@@ -0,0 +1,119 @@
+use super::entities::{site_fingerprint, SiteFingerprint as SiteFingerprintEntity};
+use crate::domain::SiteFingerprint;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    entity::*, query::*, ConnectionTrait, DatabaseConnection, DbErr, FromQueryResult, Statement,
+};
+
+/// Watched domains never checked or last checked before `$1`, stalest first
+const DUE_DOMAINS_SQL: &str = r#"
+SELECT w.domain
+FROM watches w
+LEFT JOIN site_fingerprints f ON f.domain = w.domain
+WHERE f.checked_at IS NULL OR f.checked_at < $1
+GROUP BY w.domain, f.checked_at
+ORDER BY f.checked_at ASC NULLS FIRST
+LIMIT $2
+"#;
+
+const PRUNE_UNWATCHED_SQL: &str = r#"
+DELETE FROM site_fingerprints f
+WHERE NOT EXISTS (SELECT 1 FROM watches w WHERE w.domain = f.domain)
+"#;
+
+#[derive(FromQueryResult)]
+struct DomainRow {
+    domain: String,
+}
+
+#[derive(Clone)]
+pub struct SiteFingerprintRepository {
+    db: DatabaseConnection,
+}
+
+impl SiteFingerprintRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn due_domains(
+        &self,
+        checked_before: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<String>, DbErr> {
+        let rows = DomainRow::find_by_statement(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Postgres,
+            DUE_DOMAINS_SQL,
+            [checked_before.into(), (limit as i64).into()],
+        ))
+        .all(&self.db)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.domain).collect())
+    }
+
+    pub async fn find(&self, domain: &str) -> Result<Option<site_fingerprint::Model>, DbErr> {
+        SiteFingerprintEntity::find_by_id(domain.to_string())
+            .one(&self.db)
+            .await
+    }
+
+    /// Store the latest fingerprint; `changed` marks it as a pivot watchers were told about
+    pub async fn save(
+        &self,
+        domain: &str,
+        content_hash: &str,
+        fingerprint: &SiteFingerprint,
+        changed: bool,
+    ) -> Result<(), DbErr> {
+        let now = Utc::now();
+        let active = site_fingerprint::ActiveModel {
+            domain: Set(domain.to_string()),
+            content_hash: Set(content_hash.to_string()),
+            title: Set(fingerprint.title.clone()),
+            headings: Set(fingerprint.headings.join("\n")),
+            checked_at: Set(now),
+            changed_at: Set(changed.then_some(now)),
+        };
+        let mut update = vec![
+            site_fingerprint::Column::ContentHash,
+            site_fingerprint::Column::Title,
+            site_fingerprint::Column::Headings,
+            site_fingerprint::Column::CheckedAt,
+        ];
+        if changed {
+            update.push(site_fingerprint::Column::ChangedAt);
+        }
+        SiteFingerprintEntity::insert(active)
+            .on_conflict(
+                OnConflict::column(site_fingerprint::Column::Domain)
+                    .update_columns(update)
+                    .to_owned(),
+            )
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Only bump `checked_at`, for sites that couldn't be fetched this round
+    pub async fn touch(&self, domain: &str) -> Result<(), DbErr> {
+        SiteFingerprintEntity::update_many()
+            .col_expr(site_fingerprint::Column::CheckedAt, Expr::value(Utc::now()))
+            .filter(site_fingerprint::Column::Domain.eq(domain))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Fingerprints of domains nobody watches anymore
+    pub async fn prune_unwatched(&self) -> Result<u64, DbErr> {
+        let result = self
+            .db
+            .execute(Statement::from_string(
+                sea_orm::DatabaseBackend::Postgres,
+                PRUNE_UNWATCHED_SQL,
+            ))
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,128 @@
+use super::entities::{spice_vote, SpiceVote};
+use crate::domain::{SpiceDistribution, SpiceLevel, VariantSpice};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr, FromQueryResult, Statement};
+use uuid::Uuid;
+
+const DISTRIBUTION_SQL: &str = r#"
+SELECT level, COUNT(*) AS answers
+FROM spice_votes
+WHERE roast_id = $1
+GROUP BY level
+"#;
+
+const BY_VARIANT_SQL: &str = r#"
+SELECT
+    r.prompt_variant AS variant,
+    s.level,
+    COUNT(*) AS answers,
+    COUNT(DISTINCT s.roast_id) AS roasts
+FROM spice_votes s
+JOIN roasts r ON r.id = s.roast_id
+WHERE r.removed_at IS NULL
+GROUP BY r.prompt_variant, s.level
+ORDER BY r.prompt_variant NULLS FIRST
+"#;
+
+#[derive(FromQueryResult)]
+struct LevelCountRow {
+    level: String,
+    answers: i64,
+}
+
+#[derive(FromQueryResult)]
+struct VariantLevelRow {
+    variant: Option<String>,
+    level: String,
+    answers: i64,
+    roasts: i64,
+}
+
+#[derive(Clone)]
+pub struct SpiceVoteRepository {
+    db: DatabaseConnection,
+}
+
+impl SpiceVoteRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Record or change a voter's answer for a roast
+    pub async fn vote(&self, roast_id: Uuid, voter_key: &str, level: SpiceLevel) -> Result<(), DbErr> {
+        let now = chrono::Utc::now();
+        let active = spice_vote::ActiveModel {
+            roast_id: Set(roast_id),
+            voter_key: Set(voter_key.to_string()),
+            level: Set(level.as_str().to_string()),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+        };
+        SpiceVote::insert(active)
+            .on_conflict(
+                OnConflict::columns([spice_vote::Column::RoastId, spice_vote::Column::VoterKey])
+                    .update_columns([spice_vote::Column::Level, spice_vote::Column::UpdatedAt])
+                    .to_owned(),
+            )
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find_vote(&self, roast_id: Uuid, voter_key: &str) -> Result<Option<SpiceLevel>, DbErr> {
+        let vote = SpiceVote::find_by_id((roast_id, voter_key.to_string()))
+            .one(&self.db)
+            .await?;
+        Ok(vote.and_then(|v| v.level.parse().ok()))
+    }
+
+    pub async fn distribution(&self, roast_id: Uuid) -> Result<SpiceDistribution, DbErr> {
+        let rows = LevelCountRow::find_by_statement(Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            DISTRIBUTION_SQL,
+            [roast_id.into()],
+        ))
+        .all(&self.db)
+        .await?;
+
+        let mut distribution = SpiceDistribution::default();
+        for row in rows {
+            if let Ok(level) = row.level.parse() {
+                distribution.add(level, row.answers);
+            }
+        }
+        Ok(distribution)
+    }
+
+    /// Answers grouped by the prompt variant that generated each roast
+    pub async fn by_variant(&self) -> Result<Vec<VariantSpice>, DbErr> {
+        let rows = VariantLevelRow::find_by_statement(Statement::from_string(
+            self.db.get_database_backend(),
+            BY_VARIANT_SQL,
+        ))
+        .all(&self.db)
+        .await?;
+
+        let mut variants: Vec<VariantSpice> = Vec::new();
+        for row in rows {
+            let Ok(level) = row.level.parse() else {
+                continue;
+            };
+            let index = match variants.iter().position(|v| v.variant == row.variant) {
+                Some(index) => index,
+                None => {
+                    variants.push(VariantSpice {
+                        variant: row.variant,
+                        roasts: 0,
+                        distribution: SpiceDistribution::default(),
+                    });
+                    variants.len() - 1
+                }
+            };
+            let entry = &mut variants[index];
+            entry.roasts = entry.roasts.max(row.roasts);
+            entry.distribution.add(level, row.answers);
+        }
+        Ok(variants)
+    }
+}
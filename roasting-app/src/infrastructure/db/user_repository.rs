@@ -41,10 +41,23 @@ impl UserRepository {
                 email: Set(user_data.email.clone()),
                 name: Set(user_data.name.clone()),
                 avatar_url: Set(user_data.avatar_url.clone()),
+                hide_authorship: Set(user_data.hide_authorship),
                 created_at: Set(Some(chrono::Utc::now())),
                 updated_at: Set(Some(chrono::Utc::now())),
             };
             active.insert(&self.db).await
         }
     }
+
+    pub async fn set_hide_authorship(&self, id: Uuid, hide: bool) -> Result<user::Model, DbErr> {
+        let user = User::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("User not found".to_string()))?;
+
+        let mut active: user::ActiveModel = user.into();
+        active.hide_authorship = Set(hide);
+        active.updated_at = Set(Some(chrono::Utc::now()));
+        active.update(&self.db).await
+    }
 }
@@ -1,8 +1,13 @@
 use super::entities::{vote, Vote};
-use crate::domain::VoteResult;
-use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use sea_orm::{entity::*, query::*, ConnectionTrait, DatabaseConnection, DbErr, Statement};
 use uuid::Uuid;
 
+const CLAIM_MILESTONE_SQL: &str = r#"
+INSERT INTO fire_milestones (roast_id, milestone)
+VALUES ($1, $2)
+ON CONFLICT (roast_id, milestone) DO NOTHING
+"#;
+
 #[derive(Clone)]
 pub struct VoteRepository {
     db: DatabaseConnection,
@@ -22,11 +27,13 @@ impl VoteRepository {
         Ok(vote.is_some())
     }
 
-    pub async fn create(&self, user_id: Uuid, roast_id: Uuid) -> Result<vote::Model, DbErr> {
+    /// `weight` comes from `VotePolicy::weigh`
+    pub async fn create(&self, user_id: Uuid, roast_id: Uuid, weight: f32) -> Result<vote::Model, DbErr> {
         let active = vote::ActiveModel {
             user_id: Set(user_id),
             roast_id: Set(roast_id),
             created_at: Set(Some(chrono::Utc::now())),
+            weight: Set(weight),
         };
         active.insert(&self.db).await
     }
@@ -40,31 +47,17 @@ impl VoteRepository {
         Ok(())
     }
 
-    /// Toggle vote and return the new state + fire count
-    pub async fn toggle(
-        &self,
-        user_id: Uuid,
-        roast_id: Uuid,
-        roast_repo: &super::RoastRepository,
-    ) -> Result<VoteResult, DbErr> {
-        let exists = self.exists(user_id, roast_id).await?;
-
-        if exists {
-            // Remove vote
-            self.delete(user_id, roast_id).await?;
-            let new_count = roast_repo.decrement_fire_count(roast_id).await?;
-            Ok(VoteResult {
-                voted: false,
-                new_fire_count: new_count,
-            })
-        } else {
-            // Add vote
-            self.create(user_id, roast_id).await?;
-            let new_count = roast_repo.increment_fire_count(roast_id).await?;
-            Ok(VoteResult {
-                voted: true,
-                new_fire_count: new_count,
-            })
-        }
+    /// Record that `roast_id` reached `milestone`. False when it already had,
+    /// so the creator is told about each milestone once.
+    pub async fn claim_milestone(&self, roast_id: Uuid, milestone: i32) -> Result<bool, DbErr> {
+        let result = self
+            .db
+            .execute(Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Postgres,
+                CLAIM_MILESTONE_SQL,
+                [roast_id.into(), milestone.into()],
+            ))
+            .await?;
+        Ok(result.rows_affected() == 1)
     }
 }
@@ -0,0 +1,77 @@
+use super::entities::{watch, Watch};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct WatchRepository {
+    db: DatabaseConnection,
+}
+
+impl WatchRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Watching an already watched domain is a no-op
+    pub async fn watch(&self, user_id: Uuid, domain: &str) -> Result<(), DbErr> {
+        let active = watch::ActiveModel {
+            user_id: Set(user_id),
+            domain: Set(domain.to_string()),
+            created_at: Set(Some(chrono::Utc::now())),
+        };
+        Watch::insert(active)
+            .on_conflict(
+                OnConflict::columns([watch::Column::UserId, watch::Column::Domain])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Returns `false` if the user wasn't watching the domain
+    pub async fn unwatch(&self, user_id: Uuid, domain: &str) -> Result<bool, DbErr> {
+        let result = Watch::delete_many()
+            .filter(watch::Column::UserId.eq(user_id))
+            .filter(watch::Column::Domain.eq(domain))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    pub async fn is_watching(&self, user_id: Uuid, domain: &str) -> Result<bool, DbErr> {
+        Ok(Watch::find_by_id((user_id, domain.to_string()))
+            .one(&self.db)
+            .await?
+            .is_some())
+    }
+
+    /// The user's watched domains, newest first
+    pub async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<watch::Model>, DbErr> {
+        Watch::find()
+            .filter(watch::Column::UserId.eq(user_id))
+            .order_by_desc(watch::Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    pub async fn count_for_user(&self, user_id: Uuid) -> Result<u64, DbErr> {
+        Watch::find()
+            .filter(watch::Column::UserId.eq(user_id))
+            .count(&self.db)
+            .await
+    }
+
+    pub async fn watchers(&self, domain: &str) -> Result<Vec<Uuid>, DbErr> {
+        Watch::find()
+            .filter(watch::Column::Domain.eq(domain))
+            .select_only()
+            .column(watch::Column::UserId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+    }
+}
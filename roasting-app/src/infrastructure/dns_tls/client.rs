@@ -0,0 +1,247 @@
+use crate::domain::{DnsTlsInfo, TlsCertificate};
+use crate::infrastructure::rdap::registrable_domain;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// DNS-over-HTTPS resolver answering in the `application/dns-json` format
+const DOH_URL: &str = "https://cloudflare-dns.com/dns-query";
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(3);
+const TYPE_MX: u16 = 15;
+const TYPE_NS: u16 = 2;
+
+/// `(MX host fragment, provider)`
+const MAIL_PROVIDERS: &[(&str, &str)] = &[
+    ("google.com", "Google Workspace"),
+    ("googlemail.com", "Google Workspace"),
+    ("outlook.com", "Microsoft 365"),
+    ("zoho.com", "Zoho Mail"),
+    ("zoho.eu", "Zoho Mail"),
+    ("zohomail.com", "Zoho Mail"),
+    ("protonmail.ch", "Proton Mail"),
+    ("mx.cloudflare.net", "Cloudflare Email Routing (forwarding only)"),
+    ("improvmx.com", "ImprovMX (forwarding only)"),
+    ("forwardemail.net", "Forward Email (forwarding only)"),
+    ("messagingengine.com", "Fastmail"),
+    ("secureserver.net", "GoDaddy"),
+    ("hostinger.com", "Hostinger"),
+    ("niagahoster.com", "Niagahoster"),
+    ("yandex.net", "Yandex"),
+    ("mailgun.org", "Mailgun"),
+    ("amazonaws.com", "Amazon SES"),
+];
+
+/// `(nameserver fragment, provider)`
+const DNS_PROVIDERS: &[(&str, &str)] = &[
+    ("ns.cloudflare.com", "Cloudflare"),
+    ("awsdns", "AWS Route 53"),
+    ("googledomains.com", "Google Domains"),
+    ("google.com", "Google Cloud DNS"),
+    ("azure-dns", "Azure DNS"),
+    ("domaincontrol.com", "GoDaddy"),
+    ("registrar-servers.com", "Namecheap"),
+    ("vercel-dns.com", "Vercel"),
+    ("nsone.net", "NS1"),
+    ("netlify.com", "Netlify"),
+    ("digitalocean.com", "DigitalOcean"),
+    ("dns-parking.com", "Hostinger"),
+    ("hostinger.com", "Hostinger"),
+    ("niagahoster.com", "Niagahoster"),
+    ("dewaweb.com", "Dewaweb"),
+    ("idwebhost.com", "IDwebhost"),
+    ("rumahweb.com", "Rumahweb"),
+    ("wixdns.net", "Wix"),
+    ("squarespacedns.com", "Squarespace"),
+];
+
+#[derive(Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Status")]
+    status: u32,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>,
+}
+
+#[derive(Deserialize)]
+struct DohAnswer {
+    #[serde(rename = "type")]
+    record_type: u16,
+    data: String,
+}
+
+/// Mail, DNS and certificate facts about a domain. Off by default because
+/// every roast then costs three more outbound requests.
+pub struct DnsTlsProbe {
+    http_client: reqwest::Client,
+    /// Accepts any certificate, so an expired or self-signed one can still
+    /// be read; nothing fetched with it is trusted
+    tls_client: reqwest::Client,
+    enabled: bool,
+}
+
+impl DnsTlsProbe {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(LOOKUP_TIMEOUT)
+                .build()
+                .expect("Failed to create HTTP client"),
+            tls_client: reqwest::Client::builder()
+                .timeout(LOOKUP_TIMEOUT)
+                .tls_info(true)
+                .danger_accept_invalid_certs(true)
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .expect("Failed to create HTTP client"),
+            enabled,
+        }
+    }
+
+    /// Enabled when DNS_TLS_LOOKUP is `true` or `1`
+    pub fn from_env() -> Self {
+        let enabled = std::env::var("DNS_TLS_LOOKUP")
+            .map(|v| matches!(v.trim(), "true" | "1"))
+            .unwrap_or(false);
+        Self::new(enabled)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub async fn lookup(&self, url: &str) -> Option<DnsTlsInfo> {
+        if !self.enabled {
+            return None;
+        }
+        let host = url::Url::parse(url).ok()?.host_str()?.to_lowercase();
+        let domain = registrable_domain(&host)?;
+
+        let (mx, ns, certificate) = tokio::join!(
+            self.resolve(&domain, TYPE_MX),
+            self.resolve(&domain, TYPE_NS),
+            self.certificate(&host),
+        );
+        // Without DNS answers the domain facts would be made up
+        let (mx, ns) = (mx?, ns?);
+
+        let mut mx: Vec<(u16, String)> = mx
+            .iter()
+            .filter_map(|data| {
+                let (preference, host) = data.split_once(' ')?;
+                Some((preference.parse().ok()?, normalize_host(host)))
+            })
+            .collect();
+        mx.sort();
+        let mx_hosts: Vec<String> = mx.into_iter().map(|(_, host)| host).collect();
+        let nameservers: Vec<String> = ns.iter().map(|host| normalize_host(host)).collect();
+
+        Some(DnsTlsInfo {
+            mail_provider: provider_for(&mx_hosts, MAIL_PROVIDERS),
+            dns_provider: provider_for(&nameservers, DNS_PROVIDERS),
+            domain,
+            mx_hosts,
+            nameservers,
+            certificate,
+        })
+    }
+
+    /// Record data for `name`, `None` when the resolver couldn't answer
+    async fn resolve(&self, name: &str, record_type: u16) -> Option<Vec<String>> {
+        let response = self
+            .http_client
+            .get(DOH_URL)
+            .query(&[("name", name), ("type", &record_type.to_string())])
+            .header("Accept", "application/dns-json")
+            .send()
+            .await;
+        let response = match response {
+            Ok(resp) if resp.status().is_success() => resp,
+            Ok(resp) => {
+                tracing::debug!("DoH lookup for {} returned {}", name, resp.status());
+                return None;
+            }
+            Err(e) => {
+                tracing::debug!("DoH lookup for {} failed: {}", name, e);
+                return None;
+            }
+        };
+        let body: DohResponse = response.json().await.ok()?;
+        // NXDOMAIN (3) still answers the question: there are no records
+        if body.status != 0 && body.status != 3 {
+            return None;
+        }
+        Some(
+            body.answer
+                .into_iter()
+                .filter(|a| a.record_type == record_type)
+                .map(|a| a.data)
+                .collect(),
+        )
+    }
+
+    async fn certificate(&self, host: &str) -> Option<TlsCertificate> {
+        let response = match self.tls_client.head(format!("https://{}/", host)).send().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                tracing::debug!("TLS handshake with {} failed: {}", host, e);
+                return None;
+            }
+        };
+        let der = response
+            .extensions()
+            .get::<reqwest::tls::TlsInfo>()?
+            .peer_certificate()?;
+        parse_certificate(der)
+    }
+}
+
+impl Default for DnsTlsProbe {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+fn parse_certificate(der: &[u8]) -> Option<TlsCertificate> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+    let issuer = cert.issuer();
+    let issuer_name = issuer
+        .iter_organization()
+        .chain(issuer.iter_common_name())
+        .find_map(|attr| attr.as_str().ok())
+        .unwrap_or("unknown")
+        .to_string();
+    Some(TlsCertificate {
+        issuer: issuer_name,
+        expires_at: chrono::DateTime::from_timestamp(cert.validity().not_after.timestamp(), 0)?,
+        self_signed: cert.issuer() == cert.subject(),
+    })
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_lowercase()
+}
+
+fn provider_for(hosts: &[String], providers: &[(&str, &str)]) -> Option<String> {
+    hosts.iter().find_map(|host| {
+        providers
+            .iter()
+            .find(|(needle, _)| host.contains(needle))
+            .map(|(_, provider)| provider.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_for() {
+        let mx = vec!["aspmx.l.google.com".to_string(), "alt1.aspmx.l.google.com".to_string()];
+        assert_eq!(provider_for(&mx, MAIL_PROVIDERS).as_deref(), Some("Google Workspace"));
+
+        let ns = vec!["ns-12.awsdns-01.com".to_string(), "kate.ns.cloudflare.com".to_string()];
+        assert_eq!(provider_for(&ns, DNS_PROVIDERS).as_deref(), Some("AWS Route 53"));
+
+        let self_hosted = vec!["mail.example.com".to_string()];
+        assert_eq!(provider_for(&self_hosted, MAIL_PROVIDERS), None);
+    }
+}
@@ -0,0 +1,103 @@
+const DEFAULT_DIMENSIONS: usize = 256;
+const MIN_TOKEN_LEN: usize = 3;
+
+const STOPWORDS: &[&str] = &[
+    "yang", "dan", "untuk", "dengan", "ini", "itu", "dari", "kamu", "kami", "the", "and", "for",
+    "with", "your", "you", "are", "this", "that", "startup", "roasting", "roast",
+];
+
+/// Dependency-free text embedder using the hashing trick.
+///
+/// Tokens (and adjacent token pairs) are hashed into a fixed number of buckets
+/// with a sign hash, then L2-normalized so cosine similarity is a dot product.
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl HashingEmbedder {
+    pub fn new() -> Self {
+        Self {
+            dimensions: DEFAULT_DIMENSIONS,
+        }
+    }
+
+    pub fn embed(&self, text: &str) -> Vec<f32> {
+        let lower = text.to_lowercase();
+        let tokens: Vec<&str> = lower
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|t| t.chars().count() >= MIN_TOKEN_LEN && !STOPWORDS.contains(t))
+            .collect();
+
+        let mut vector = vec![0.0f32; self.dimensions];
+        for token in &tokens {
+            self.add_feature(&mut vector, token, 1.0);
+        }
+        for pair in tokens.windows(2) {
+            self.add_feature(&mut vector, &format!("{} {}", pair[0], pair[1]), 0.5);
+        }
+
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|v| *v /= norm);
+        }
+        vector
+    }
+
+    fn add_feature(&self, vector: &mut [f32], feature: &str, weight: f32) {
+        let hash = fnv1a(feature.as_bytes());
+        let index = (hash % self.dimensions as u64) as usize;
+        let sign = if (hash >> 63) == 0 { 1.0 } else { -1.0 };
+        vector[index] += sign * weight;
+    }
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similar_texts_score_higher() {
+        let embedder = HashingEmbedder::new();
+        let payments = embedder.embed("Aplikasi pembayaran digital untuk UMKM, dompet digital dan paylater");
+        let wallet = embedder.embed("Dompet digital dan paylater buat UMKM, pembayaran gampang");
+        let coffee = embedder.embed("Kedai kopi susu gula aren dengan delivery ke kantor");
+
+        assert_eq!(payments.len(), DEFAULT_DIMENSIONS);
+        let norm = payments.iter().map(|v| v * v).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5);
+        assert!((cosine_similarity(&payments, &payments) - 1.0).abs() < 1e-5);
+        assert!(cosine_similarity(&payments, &wallet) > cosine_similarity(&payments, &coffee));
+    }
+
+    #[test]
+    fn test_stopwords_and_short_tokens_are_ignored() {
+        let embedder = HashingEmbedder::new();
+        let empty = embedder.embed("yang dan untuk ini itu AI di ke startup roast");
+        assert!(empty.iter().all(|v| *v == 0.0));
+        assert_eq!(cosine_similarity(&empty, &embedder.embed("kopi susu")), 0.0);
+        assert_eq!(embedder.embed("Kopi SUSU"), embedder.embed("kopi susu"));
+    }
+}
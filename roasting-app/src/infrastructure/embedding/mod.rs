@@ -0,0 +1,3 @@
+mod hashing_embedder;
+
+pub use hashing_embedder::{cosine_similarity, HashingEmbedder};
@@ -0,0 +1,282 @@
+use crate::domain::{FundingInfo, ProductLaunch};
+use crate::infrastructure::rdap::registrable_domain;
+use dashmap::DashMap;
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const CRUNCHBASE_SEARCH_URL: &str = "https://api.crunchbase.com/api/v4/searches/organizations";
+const PRODUCT_HUNT_GRAPHQL_URL: &str = "https://api.producthunt.com/v2/api/graphql";
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+/// Funding rounds and launches are news at most once a week or so
+const CACHE_TTL: Duration = Duration::from_secs(24 * 3600);
+/// Unknown startups are looked up again sooner, in case they just got listed
+const MISS_CACHE_TTL: Duration = Duration::from_secs(6 * 3600);
+const MAX_CACHE_ENTRIES: usize = 10_000;
+const MAX_INVESTORS: usize = 5;
+const MAX_LAUNCHES: usize = 3;
+
+const PRODUCT_HUNT_QUERY: &str = "query($url: String, $first: Int) { posts(url: $url, first: $first) { \
+    edges { node { name tagline votesCount } } } }";
+
+#[derive(Deserialize)]
+struct CrunchbaseSearch {
+    #[serde(default)]
+    entities: Vec<CrunchbaseEntity>,
+}
+
+#[derive(Deserialize)]
+struct CrunchbaseEntity {
+    properties: CrunchbaseOrganization,
+}
+
+#[derive(Deserialize)]
+struct CrunchbaseOrganization {
+    /// `pre_seed`, `seed`, `series_a`, ...
+    #[serde(default)]
+    last_funding_type: Option<String>,
+    #[serde(default)]
+    funding_total: Option<CrunchbaseMoney>,
+    #[serde(default)]
+    investor_identifiers: Vec<CrunchbaseIdentifier>,
+}
+
+#[derive(Deserialize)]
+struct CrunchbaseMoney {
+    value_usd: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct CrunchbaseIdentifier {
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct ProductHuntResponse {
+    data: Option<ProductHuntData>,
+}
+
+#[derive(Deserialize)]
+struct ProductHuntData {
+    posts: ProductHuntConnection,
+}
+
+#[derive(Deserialize)]
+struct ProductHuntConnection {
+    edges: Vec<ProductHuntEdge>,
+}
+
+#[derive(Deserialize)]
+struct ProductHuntEdge {
+    node: ProductHuntPost,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProductHuntPost {
+    name: String,
+    tagline: String,
+    #[serde(default)]
+    votes_count: Option<i64>,
+}
+
+/// Cached answer per registrable domain, `None` when neither source knew it
+type LookupCache = DashMap<String, (Instant, Option<FundingInfo>)>;
+
+/// Funding from Crunchbase (CRUNCHBASE_API_KEY) and launches from Product
+/// Hunt (PRODUCT_HUNT_TOKEN). Each source is used when its key is set; the
+/// lookup is off without either. Failures just mean no `FundingInfo`.
+pub struct EnrichmentClient {
+    http_client: reqwest::Client,
+    crunchbase_key: Option<String>,
+    product_hunt_token: Option<String>,
+    cache: Arc<LookupCache>,
+}
+
+impl EnrichmentClient {
+    pub fn new(crunchbase_key: Option<String>, product_hunt_token: Option<String>) -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(LOOKUP_TIMEOUT)
+                .build()
+                .expect("Failed to create HTTP client"),
+            crunchbase_key,
+            product_hunt_token,
+            cache: Arc::new(DashMap::new()),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let key = |name| std::env::var(name).ok().filter(|v: &String| !v.trim().is_empty());
+        Self::new(key("CRUNCHBASE_API_KEY"), key("PRODUCT_HUNT_TOKEN"))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.crunchbase_key.is_some() || self.product_hunt_token.is_some()
+    }
+
+    pub async fn lookup(&self, url: &str) -> Option<FundingInfo> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let parsed = url::Url::parse(url).ok()?;
+        let domain = registrable_domain(&parsed.host_str()?.to_lowercase())?;
+
+        if let Some(entry) = self.cache.get(&domain) {
+            let (fetched, info) = entry.value();
+            if is_fresh(*fetched, info) {
+                return info.clone();
+            }
+        }
+
+        let (funding, launches) = tokio::join!(
+            self.crunchbase(&domain),
+            self.product_hunt(parsed.origin().ascii_serialization())
+        );
+        let mut info = funding.unwrap_or_default();
+        info.launches = launches.unwrap_or_default();
+        let info = (!info.is_empty()).then_some(info);
+
+        if self.cache.len() >= MAX_CACHE_ENTRIES {
+            self.cache.retain(|_, (fetched, info)| is_fresh(*fetched, info));
+        }
+        self.cache.insert(domain, (Instant::now(), info.clone()));
+        info
+    }
+
+    async fn crunchbase(&self, domain: &str) -> Option<FundingInfo> {
+        let key = self.crunchbase_key.as_ref()?;
+        let body = json!({
+            "field_ids": ["identifier", "last_funding_type", "funding_total", "investor_identifiers"],
+            "query": [{
+                "type": "predicate",
+                "field_id": "website_url",
+                "operator_id": "domain_eq",
+                "values": [domain],
+            }],
+            "limit": 1,
+        });
+        let response = self
+            .http_client
+            .post(CRUNCHBASE_SEARCH_URL)
+            .header("X-cb-user-key", key)
+            .json(&body)
+            .send()
+            .await;
+        let search: CrunchbaseSearch = match response {
+            Ok(resp) if resp.status().is_success() => resp.json().await.ok()?,
+            Ok(resp) => {
+                tracing::debug!("Crunchbase lookup for {} returned {}", domain, resp.status());
+                return None;
+            }
+            Err(e) => {
+                tracing::debug!("Crunchbase lookup for {} failed: {}", domain, e);
+                return None;
+            }
+        };
+        let organization = search.entities.into_iter().next()?.properties;
+        Some(funding_info(organization))
+    }
+
+    async fn product_hunt(&self, url: String) -> Option<Vec<ProductLaunch>> {
+        let token = self.product_hunt_token.as_ref()?;
+        let body = json!({
+            "query": PRODUCT_HUNT_QUERY,
+            "variables": { "url": url, "first": MAX_LAUNCHES },
+        });
+        let response = self
+            .http_client
+            .post(PRODUCT_HUNT_GRAPHQL_URL)
+            .bearer_auth(token)
+            .json(&body)
+            .send()
+            .await;
+        let result: ProductHuntResponse = match response {
+            Ok(resp) if resp.status().is_success() => resp.json().await.ok()?,
+            Ok(resp) => {
+                tracing::debug!("Product Hunt lookup for {} returned {}", url, resp.status());
+                return None;
+            }
+            Err(e) => {
+                tracing::debug!("Product Hunt lookup for {} failed: {}", url, e);
+                return None;
+            }
+        };
+        Some(launches(result.data?))
+    }
+}
+
+impl Default for EnrichmentClient {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+fn is_fresh(fetched: Instant, info: &Option<FundingInfo>) -> bool {
+    fetched.elapsed() < if info.is_some() { CACHE_TTL } else { MISS_CACHE_TTL }
+}
+
+fn funding_info(organization: CrunchbaseOrganization) -> FundingInfo {
+    FundingInfo {
+        stage: organization.last_funding_type.map(|t| t.replace('_', " ")),
+        total_usd: organization.funding_total.and_then(|m| m.value_usd),
+        investors: organization
+            .investor_identifiers
+            .into_iter()
+            .map(|i| i.value)
+            .take(MAX_INVESTORS)
+            .collect(),
+        launches: Vec::new(),
+    }
+}
+
+fn launches(data: ProductHuntData) -> Vec<ProductLaunch> {
+    data.posts
+        .edges
+        .into_iter()
+        .map(|edge| ProductLaunch {
+            name: edge.node.name,
+            tagline: edge.node.tagline,
+            votes: edge.node.votes_count,
+        })
+        .take(MAX_LAUNCHES)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Language;
+
+    #[test]
+    fn test_crunchbase_and_product_hunt_responses() {
+        let search: CrunchbaseSearch = serde_json::from_str(
+            r#"{"count": 1, "entities": [{"uuid": "x", "properties": {
+                "identifier": {"value": "Kecil Tech", "permalink": "kecil-tech"},
+                "last_funding_type": "pre_seed",
+                "funding_total": {"value": 800000000, "currency": "IDR", "value_usd": 50000},
+                "investor_identifiers": [{"value": "Om Budi"}, {"value": "Tante Sari"}]
+            }}]}"#,
+        )
+        .unwrap();
+        let mut info = funding_info(search.entities.into_iter().next().unwrap().properties);
+
+        let ph: ProductHuntResponse = serde_json::from_str(
+            r#"{"data": {"posts": {"edges": [
+                {"node": {"name": "Kecil", "tagline": "Kasir for warungs", "votesCount": 7}}
+            ]}}}"#,
+        )
+        .unwrap();
+        info.launches = launches(ph.data.unwrap());
+
+        assert_eq!(
+            info.facts(Language::En),
+            vec![
+                "last round pre seed, $50k raised in total",
+                "backed by Om Budi, Tante Sari",
+                "Product Hunt launch \"Kecil: Kasir for warungs\" (7 upvotes)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::config::LocalLlmConfig;
+use super::model::{GenerationStats, LocalLlm, LocalLlmError};
+use super::pool::ModelPoolConfig;
+use crate::domain::{Language, RoastOptions, StartupInfo};
+
+/// Results of `roasting-api bench`: one cold model load, one untimed warm-up
+/// generation, then `runs` timed generations on the same fixed startup
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub model_id: String,
+    pub device: String,
+    pub load_time: Duration,
+    pub runs: Vec<GenerationStats>,
+    /// High-water mark of the process RSS; None where /proc is unavailable
+    pub peak_rss_bytes: Option<u64>,
+}
+
+impl BenchReport {
+    pub fn mean_tokens_per_sec(&self) -> f64 {
+        mean(self.runs.iter().map(GenerationStats::tokens_per_sec))
+    }
+
+    pub fn median_time_to_first_token(&self) -> Duration {
+        let mut ttft: Vec<Duration> = self.runs.iter().map(|r| r.time_to_first_token).collect();
+        ttft.sort();
+        ttft.get(ttft.len() / 2).copied().unwrap_or_default()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut lines = vec![
+            format!("Model:       {}", self.model_id),
+            format!("Device:      {}", self.device),
+            format!("Load time:   {:.1}s", self.load_time.as_secs_f64()),
+        ];
+        for (i, run) in self.runs.iter().enumerate() {
+            lines.push(format!(
+                "Run {:>2}:      {} prompt + {} generated tokens, TTFT {} ms, {:.1} tok/s, {:.1}s total",
+                i + 1,
+                run.prompt_tokens,
+                run.generated_tokens,
+                run.time_to_first_token.as_millis(),
+                run.tokens_per_sec(),
+                run.total.as_secs_f64(),
+            ));
+        }
+        lines.push(format!(
+            "Tokens/sec:  {:.1} (mean)",
+            self.mean_tokens_per_sec()
+        ));
+        lines.push(format!(
+            "TTFT:        {} ms (median)",
+            self.median_time_to_first_token().as_millis()
+        ));
+        lines.push(match self.peak_rss_bytes {
+            Some(bytes) => format!("Peak RSS:    {} MB", bytes / (1024 * 1024)),
+            None => "Peak RSS:    unavailable on this platform".to_string(),
+        });
+        lines.join("\n")
+    }
+}
+
+/// Load the configured model (LOCAL_LLM_* as in production) and time `runs`
+/// generations of a roast for the fixed sample startup
+pub async fn run_bench(runs: usize, options: RoastOptions) -> Result<BenchReport, LocalLlmError> {
+    let settings = LocalLlmConfig::from_env()?;
+    let started = Instant::now();
+    let pool = ModelPoolConfig::from_env()?;
+    let llm = Arc::new(LocalLlm::load(settings, &pool.model_ids()).await?);
+    let load_time = started.elapsed();
+
+    let model_id = llm.model_id().to_string();
+    let device = llm.device_description();
+    let runs = tokio::task::spawn_blocking(move || {
+        let startup = sample_startup();
+        llm.generate_roast_timed(&startup, &options)?;
+        (0..runs)
+            .map(|_| {
+                llm.generate_roast_timed(&startup, &options)
+                    .map(|(_, stats)| stats)
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })
+    .await
+    .map_err(|e| LocalLlmError::Model(e.to_string()))??;
+
+    Ok(BenchReport {
+        model_id,
+        device,
+        load_time,
+        runs,
+        peak_rss_bytes: peak_rss_bytes(),
+    })
+}
+
+/// Fixed input so numbers are comparable across models and machines
+fn sample_startup() -> StartupInfo {
+    StartupInfo::new("https://kopikilat.example".to_string())
+        .with_title(Some(
+            "KopiKilat - Kopi Susu Diantar dalam 10 Menit".to_string(),
+        ))
+        .with_description(Some(
+            "Aplikasi pesan kopi susu kekinian dengan AI yang menebak mood kamu".to_string(),
+        ))
+        .with_headings(vec![
+            "Kopi sesuai mood, ditebak AI".to_string(),
+            "Gratis ongkir pakai langganan KopiPass".to_string(),
+            "Sudah dipercaya 12 coworking space di Jakarta Selatan".to_string(),
+        ])
+        .with_content_summary(
+            "KopiKilat menghubungkan pecinta kopi dengan barista terdekat. Pilih mood, \
+             AI kami merekomendasikan racikan yang cocok, dan kurir mengantar dalam 10 menit. \
+             Berlangganan KopiPass untuk gratis ongkir dan poin loyalitas yang bisa ditukar NFT."
+                .to_string(),
+        )
+}
+
+/// Arguments of the `bench` entry point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchOptions {
+    pub runs: usize,
+    pub options: RoastOptions,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            runs: 5,
+            options: RoastOptions::new(Language::Id),
+        }
+    }
+}
+
+impl BenchOptions {
+    /// Parses `--runs N`, `--lang id|en` and `--length singkat|standar|panjang`
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut bench = Self::default();
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("{} needs a value", flag))?;
+            match flag.as_str() {
+                "--runs" => {
+                    bench.runs = value
+                        .parse()
+                        .ok()
+                        .filter(|runs| (1..=100).contains(runs))
+                        .ok_or_else(|| format!("--runs must be 1-100, got {:?}", value))?;
+                }
+                "--lang" => bench.options.language = value.parse()?,
+                "--length" => bench.options.length = value.parse()?,
+                other => return Err(format!("unknown option {}", other)),
+            }
+        }
+        Ok(bench)
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f64
+    }
+}
+
+/// VmHWM from /proc/self/status, the peak resident set size
+fn peak_rss_bytes() -> Option<u64> {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| parse_vm_hwm(&status))
+}
+
+fn parse_vm_hwm(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::RoastLength;
+
+    #[test]
+    fn test_parse_args_and_rss() {
+        assert_eq!(BenchOptions::parse(&[]).unwrap(), BenchOptions::default());
+
+        let args: Vec<String> = ["--runs", "3", "--lang", "en", "--length", "singkat"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let bench = BenchOptions::parse(&args).unwrap();
+        assert_eq!(bench.runs, 3);
+        assert_eq!(bench.options.language, Language::En);
+        assert_eq!(bench.options.length, RoastLength::Singkat);
+        assert!(BenchOptions::parse(&["--runs".to_string(), "0".to_string()]).is_err());
+        assert!(BenchOptions::parse(&["--runs".to_string()]).is_err());
+
+        let status =
+            "Name:\troasting-api\nVmPeak:\t 2048 kB\nVmHWM:\t  1536 kB\nVmRSS:\t 1024 kB\n";
+        assert_eq!(parse_vm_hwm(status), Some(1536 * 1024));
+        assert_eq!(parse_vm_hwm("Name:\tx\n"), None);
+    }
+}
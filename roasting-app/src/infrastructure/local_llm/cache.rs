@@ -0,0 +1,190 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::model::LocalLlmError;
+
+/// Touched whenever a model is loaded; eviction removes the stalest first
+const LAST_USED_MARKER: &str = ".last_used";
+
+/// One model directory under the cache root
+#[derive(Debug, Clone, Serialize)]
+pub struct CachedModel {
+    pub model_id: String,
+    pub bytes: u64,
+    /// Unix seconds of the last load, or of the directory if never loaded
+    pub last_used: Option<u64>,
+    /// Interrupted downloads leave `.part` files behind
+    pub has_partial_downloads: bool,
+    #[serde(skip)]
+    used_at: Option<SystemTime>,
+}
+
+/// Downloaded models under `~/.cache/roasting-startup/models`, one directory
+/// per model with `/` replaced by `--`
+pub struct ModelCache {
+    root: PathBuf,
+}
+
+impl ModelCache {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn from_env() -> Result<Self, LocalLlmError> {
+        let home =
+            std::env::var("HOME").map_err(|_| LocalLlmError::Io("HOME not set".to_string()))?;
+        Ok(Self::new(
+            PathBuf::from(home)
+                .join(".cache")
+                .join("roasting-startup")
+                .join("models"),
+        ))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn model_dir(&self, model_id: &str) -> PathBuf {
+        self.root.join(model_id.replace('/', "--"))
+    }
+
+    /// Cached models, most recently used first
+    pub fn list(&self) -> Result<Vec<CachedModel>, LocalLlmError> {
+        let entries = match std::fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(LocalLlmError::Io(e.to_string())),
+        };
+        let mut models = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let (bytes, has_partial_downloads) = dir_usage(&path);
+            let used_at = std::fs::metadata(path.join(LAST_USED_MARKER))
+                .or_else(|_| std::fs::metadata(&path))
+                .and_then(|m| m.modified())
+                .ok();
+            models.push(CachedModel {
+                model_id: name.replacen("--", "/", 1),
+                bytes,
+                last_used: used_at
+                    .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs()),
+                has_partial_downloads,
+                used_at,
+            });
+        }
+        models.sort_by_key(|m| std::cmp::Reverse(m.used_at));
+        Ok(models)
+    }
+
+    pub fn total_bytes(&self) -> Result<u64, LocalLlmError> {
+        Ok(self.list()?.iter().map(|m| m.bytes).sum())
+    }
+
+    /// Mark `model_id` as just used
+    pub fn touch(&self, model_id: &str) {
+        let marker = self.model_dir(model_id).join(LAST_USED_MARKER);
+        if let Err(e) = std::fs::write(&marker, b"") {
+            tracing::warn!("Failed to update {}: {}", marker.display(), e);
+        }
+    }
+
+    /// Delete a cached model, returning the bytes freed
+    pub fn evict(&self, model_id: &str) -> Result<u64, LocalLlmError> {
+        let dir = self.model_dir(model_id);
+        if model_id.contains("..") || !dir.starts_with(&self.root) || !dir.is_dir() {
+            return Err(LocalLlmError::Io(format!("{} is not cached", model_id)));
+        }
+        let (bytes, _) = dir_usage(&dir);
+        std::fs::remove_dir_all(&dir).map_err(|e| LocalLlmError::Io(e.to_string()))?;
+        tracing::info!("Evicted cached model {} ({} bytes)", model_id, bytes);
+        Ok(bytes)
+    }
+
+    /// Evict least recently used models other than `keep` until the cache
+    /// fits in `max_bytes`. Returns the evicted model ids.
+    pub fn enforce_cap(&self, keep: &[&str], max_bytes: u64) -> Result<Vec<String>, LocalLlmError> {
+        let models = self.list()?;
+        let mut total: u64 = models.iter().map(|m| m.bytes).sum();
+        let mut evicted = Vec::new();
+        for model in models.iter().rev() {
+            if total <= max_bytes {
+                break;
+            }
+            if keep.contains(&model.model_id.as_str()) {
+                continue;
+            }
+            total -= self.evict(&model.model_id)?;
+            evicted.push(model.model_id.clone());
+        }
+        if total > max_bytes {
+            tracing::warn!(
+                "Model cache is {} bytes, over its {} byte cap even with only {:?} left",
+                total,
+                max_bytes,
+                keep
+            );
+        }
+        Ok(evicted)
+    }
+}
+
+/// Total size of the files in `dir` and whether any are partial downloads
+fn dir_usage(dir: &Path) -> (u64, bool) {
+    let mut bytes = 0;
+    let mut partial = false;
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                let (sub_bytes, sub_partial) = dir_usage(&path);
+                bytes += sub_bytes;
+                partial |= sub_partial;
+            } else if let Ok(meta) = entry.metadata() {
+                bytes += meta.len();
+                partial |= path.extension().is_some_and(|e| e == "part");
+            }
+        }
+    }
+    (bytes, partial)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enforce_cap_evicts_least_recently_used() {
+        let root = std::env::temp_dir().join(format!("model-cache-test-{}", std::process::id()));
+        let cache = ModelCache::new(root.clone());
+        for (model_id, size) in [("a/old", 400), ("b/mid", 300), ("c/new", 200)] {
+            let dir = cache.model_dir(model_id);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("model.safetensors"), vec![0u8; size]).unwrap();
+            cache.touch(model_id);
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert_eq!(cache.total_bytes().unwrap(), 900);
+
+        // "a/old" is the stalest but is the model in use, so it stays
+        let evicted = cache.enforce_cap(&["a/old"], 600).unwrap();
+        assert_eq!(evicted, vec!["b/mid".to_string()]);
+        let ids: Vec<String> = cache
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|m| m.model_id)
+            .collect();
+        assert_eq!(ids, vec!["c/new", "a/old"]);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,165 @@
+use std::str::FromStr;
+
+use super::model::LocalLlmError;
+
+const DEFAULT_MODEL_ID: &str = "HuggingFaceTB/SmolLM2-135M-Instruct";
+
+/// Model and sampling settings for the local LLM, read once at startup.
+/// The model must be a Llama-architecture instruct model on Hugging Face
+/// with a single `model.safetensors` and a ChatML (`<|im_start|>`) template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalLlmConfig {
+    /// Hugging Face repo, e.g. `HuggingFaceTB/SmolLM2-360M-Instruct`
+    pub model_id: String,
+    /// Token budget for a "panjang" roast; shorter lengths get a fraction of it
+    pub max_tokens: usize,
+    pub temperature: f64,
+    pub top_p: f64,
+    pub repeat_penalty: f32,
+    /// Tokens of scraped data in the prompt; small models lose the plot past this
+    pub context_tokens: usize,
+    /// Cap on the whole model cache in MB, enforced after a download by
+    /// evicting the least recently used other models; 0 means no cap
+    pub cache_max_mb: u64,
+}
+
+impl Default for LocalLlmConfig {
+    fn default() -> Self {
+        Self {
+            model_id: DEFAULT_MODEL_ID.to_string(),
+            max_tokens: 448,
+            temperature: 0.7,
+            top_p: 0.9,
+            repeat_penalty: 1.1,
+            context_tokens: 384,
+            cache_max_mb: 0,
+        }
+    }
+}
+
+impl LocalLlmConfig {
+    /// Reads LOCAL_LLM_MODEL, LOCAL_LLM_MAX_TOKENS, LOCAL_LLM_TEMPERATURE,
+    /// LOCAL_LLM_TOP_P, LOCAL_LLM_REPEAT_PENALTY, LOCAL_LLM_CONTEXT_TOKENS and
+    /// LOCAL_LLM_CACHE_MAX_MB.
+    /// Invalid values are an error rather than a silent fallback, so a typo
+    /// stops the server at startup instead of producing odd roasts.
+    pub fn from_env() -> Result<Self, LocalLlmError> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, LocalLlmError> {
+        let defaults = Self::default();
+        let config = Self {
+            model_id: var("LOCAL_LLM_MODEL")
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .unwrap_or(defaults.model_id),
+            max_tokens: parse(&var, "LOCAL_LLM_MAX_TOKENS", defaults.max_tokens)?,
+            temperature: parse(&var, "LOCAL_LLM_TEMPERATURE", defaults.temperature)?,
+            top_p: parse(&var, "LOCAL_LLM_TOP_P", defaults.top_p)?,
+            repeat_penalty: parse(&var, "LOCAL_LLM_REPEAT_PENALTY", defaults.repeat_penalty)?,
+            context_tokens: parse(&var, "LOCAL_LLM_CONTEXT_TOKENS", defaults.context_tokens)?,
+            cache_max_mb: parse(&var, "LOCAL_LLM_CACHE_MAX_MB", defaults.cache_max_mb)?,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub(super) fn validate(&self) -> Result<(), LocalLlmError> {
+        let valid_id = self.model_id.split_once('/').is_some_and(|(owner, name)| {
+            !owner.is_empty() && !name.is_empty() && !name.contains('/')
+        }) && !self.model_id.contains("..")
+            && !self.model_id.contains(char::is_whitespace);
+        if !valid_id {
+            return Err(invalid("LOCAL_LLM_MODEL", "expected <owner>/<model>"));
+        }
+        if !(16..=4096).contains(&self.max_tokens) {
+            return Err(invalid(
+                "LOCAL_LLM_MAX_TOKENS",
+                "must be between 16 and 4096",
+            ));
+        }
+        if !(0.0..=2.0).contains(&self.temperature) {
+            return Err(invalid(
+                "LOCAL_LLM_TEMPERATURE",
+                "must be between 0.0 and 2.0",
+            ));
+        }
+        if !(self.top_p > 0.0 && self.top_p <= 1.0) {
+            return Err(invalid(
+                "LOCAL_LLM_TOP_P",
+                "must be above 0.0 and at most 1.0",
+            ));
+        }
+        if !(1.0..=2.0).contains(&self.repeat_penalty) {
+            return Err(invalid(
+                "LOCAL_LLM_REPEAT_PENALTY",
+                "must be between 1.0 and 2.0",
+            ));
+        }
+        if !(64..=4096).contains(&self.context_tokens) {
+            return Err(invalid(
+                "LOCAL_LLM_CONTEXT_TOKENS",
+                "must be between 64 and 4096",
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn parse<T: FromStr>(
+    var: &impl Fn(&str) -> Option<String>,
+    name: &str,
+    default: T,
+) -> Result<T, LocalLlmError> {
+    match var(name) {
+        Some(value) if !value.trim().is_empty() => value
+            .trim()
+            .parse()
+            .map_err(|_| invalid(name, &format!("{:?} is not a valid number", value))),
+        _ => Ok(default),
+    }
+}
+
+fn invalid(name: &str, reason: &str) -> LocalLlmError {
+    LocalLlmError::Config(format!("{}: {}", name, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(vars: &[(&str, &str)]) -> Result<LocalLlmConfig, LocalLlmError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        LocalLlmConfig::from_vars(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_defaults_and_overrides() {
+        assert_eq!(config(&[]).unwrap(), LocalLlmConfig::default());
+
+        let custom = config(&[
+            ("LOCAL_LLM_MODEL", "HuggingFaceTB/SmolLM2-360M-Instruct"),
+            ("LOCAL_LLM_MAX_TOKENS", "700"),
+            ("LOCAL_LLM_TEMPERATURE", "0"),
+        ])
+        .unwrap();
+        assert_eq!(custom.model_id, "HuggingFaceTB/SmolLM2-360M-Instruct");
+        assert_eq!(custom.max_tokens, 700);
+        assert_eq!(custom.temperature, 0.0);
+    }
+
+    #[test]
+    fn test_rejects_invalid_values() {
+        assert!(config(&[("LOCAL_LLM_MODEL", "smollm")]).is_err());
+        assert!(config(&[("LOCAL_LLM_MODEL", "../etc/passwd")]).is_err());
+        assert!(config(&[("LOCAL_LLM_MAX_TOKENS", "lots")]).is_err());
+        assert!(config(&[("LOCAL_LLM_TEMPERATURE", "5")]).is_err());
+        assert!(config(&[("LOCAL_LLM_TOP_P", "0")]).is_err());
+        assert!(config(&[("LOCAL_LLM_REPEAT_PENALTY", "0.5")]).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use candle_core::{DType, Device};
+
+/// Requested compute device, from LOCAL_LLM_DEVICE (`auto`, `cpu`, `cuda[:N]`, `metal[:N]`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DevicePreference {
+    Auto,
+    Cpu,
+    Cuda(usize),
+    Metal(usize),
+}
+
+impl DevicePreference {
+    fn parse(value: &str) -> Self {
+        let value = value.trim().to_lowercase();
+        let (kind, ordinal) = match value.split_once(':') {
+            Some((kind, ordinal)) => (kind, ordinal.parse().unwrap_or(0)),
+            None => (value.as_str(), 0),
+        };
+        match kind {
+            "cpu" => Self::Cpu,
+            "cuda" | "gpu" => Self::Cuda(ordinal),
+            "metal" => Self::Metal(ordinal),
+            "" | "auto" => Self::Auto,
+            other => {
+                tracing::warn!("Unknown LOCAL_LLM_DEVICE {:?}, using auto", other);
+                Self::Auto
+            }
+        }
+    }
+}
+
+/// Pick the device from LOCAL_LLM_DEVICE, falling back to CPU when the GPU
+/// backend wasn't compiled in (`cuda` / `metal` features) or fails to open
+pub fn select_device() -> Device {
+    let preference =
+        DevicePreference::parse(&std::env::var("LOCAL_LLM_DEVICE").unwrap_or_default());
+
+    let device = match preference {
+        DevicePreference::Cpu => None,
+        DevicePreference::Cuda(ordinal) => cuda(ordinal),
+        DevicePreference::Metal(ordinal) => metal(ordinal),
+        DevicePreference::Auto => cuda(0).or_else(|| metal(0)),
+    };
+
+    match device {
+        Some(device) => device,
+        None => {
+            if !matches!(preference, DevicePreference::Cpu | DevicePreference::Auto) {
+                tracing::warn!(
+                    "Local LLM device {:?} unavailable, falling back to CPU",
+                    preference
+                );
+            }
+            Device::Cpu
+        }
+    }
+}
+
+#[cfg(feature = "cuda")]
+fn cuda(ordinal: usize) -> Option<Device> {
+    match Device::new_cuda(ordinal) {
+        Ok(device) => Some(device),
+        Err(e) => {
+            tracing::warn!("Failed to open CUDA device {}: {}", ordinal, e);
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "cuda"))]
+fn cuda(_ordinal: usize) -> Option<Device> {
+    None
+}
+
+#[cfg(feature = "metal")]
+fn metal(ordinal: usize) -> Option<Device> {
+    match Device::new_metal(ordinal) {
+        Ok(device) => Some(device),
+        Err(e) => {
+            tracing::warn!("Failed to open Metal device {}: {}", ordinal, e);
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "metal"))]
+fn metal(_ordinal: usize) -> Option<Device> {
+    None
+}
+
+/// Weight dtype from LOCAL_LLM_DTYPE (`f32`, `f16`, `bf16`). Defaults to F16
+/// on GPUs, where half precision is much faster, and F32 on CPU.
+pub fn select_dtype(device: &Device) -> DType {
+    let default = if device.is_cpu() {
+        DType::F32
+    } else {
+        DType::F16
+    };
+    match std::env::var("LOCAL_LLM_DTYPE")
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+        .as_str()
+    {
+        "" => default,
+        "f32" => DType::F32,
+        "f16" => DType::F16,
+        "bf16" => DType::BF16,
+        other => {
+            tracing::warn!("Unknown LOCAL_LLM_DTYPE {:?}, using {:?}", other, default);
+            default
+        }
+    }
+}
+
+/// Short label for logs, e.g. "cuda:0" or "cpu"
+pub fn device_label(device: &Device) -> String {
+    match device.location() {
+        candle_core::DeviceLocation::Cpu => "cpu".to_string(),
+        candle_core::DeviceLocation::Cuda { gpu_id } => format!("cuda:{}", gpu_id),
+        candle_core::DeviceLocation::Metal { gpu_id } => format!("metal:{}", gpu_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_device_preference() {
+        assert_eq!(DevicePreference::parse(""), DevicePreference::Auto);
+        assert_eq!(DevicePreference::parse("CPU"), DevicePreference::Cpu);
+        assert_eq!(DevicePreference::parse("cuda:1"), DevicePreference::Cuda(1));
+        assert_eq!(DevicePreference::parse("metal"), DevicePreference::Metal(0));
+        assert_eq!(DevicePreference::parse("tpu"), DevicePreference::Auto);
+    }
+}
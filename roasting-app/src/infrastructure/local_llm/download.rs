@@ -0,0 +1,266 @@
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+use super::model::LocalLlmError;
+
+const HF_BASE_URL: &str = "https://huggingface.co";
+const USER_AGENT: &str = "roasting-startup/1.0";
+/// Transfer attempts per file before giving up; each one resumes the last
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Expected size and content hash of a repo file. Hugging Face only publishes
+/// a SHA256 for LFS files (the weights); small git files are checked by size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileMeta {
+    size: Option<u64>,
+    sha256: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ModelInfo {
+    #[serde(default)]
+    siblings: Vec<Sibling>,
+}
+
+#[derive(Deserialize)]
+struct Sibling {
+    rfilename: String,
+    size: Option<u64>,
+    lfs: Option<LfsInfo>,
+}
+
+#[derive(Deserialize)]
+struct LfsInfo {
+    sha256: String,
+    size: Option<u64>,
+}
+
+/// Download `filename` from the model repo into `cache_dir`, unless it is
+/// already there. Bytes stream to `<filename>.part`, which later attempts (and
+/// later startups) resume with a Range request; the file only takes its real
+/// name once its size and SHA256 match the Hub metadata.
+pub(super) async fn download_file(
+    cache_dir: &Path,
+    model_id: &str,
+    filename: &str,
+) -> Result<PathBuf, LocalLlmError> {
+    let file_path = cache_dir.join(filename);
+    if file_path.exists() {
+        tracing::info!("Using cached {}", filename);
+        return Ok(file_path);
+    }
+
+    let client = reqwest::Client::new();
+    let expected = fetch_file_meta(&client, model_id, filename).await?;
+    let url = format!("{}/{}/resolve/main/{}", HF_BASE_URL, model_id, filename);
+    let part_path = cache_dir.join(format!("{}.part", filename));
+
+    let mut attempt = 1;
+    loop {
+        match fetch_to_part(&client, &url, &part_path, filename).await {
+            Ok(()) => break,
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                tracing::warn!(
+                    "Download of {} interrupted (attempt {}/{}), resuming: {}",
+                    filename,
+                    attempt,
+                    MAX_ATTEMPTS,
+                    e
+                );
+                attempt += 1;
+            }
+            // The partial file stays behind so the next startup resumes it
+            Err(e) => return Err(e),
+        }
+    }
+
+    if let Err(e) = verify(&part_path, &expected, filename).await {
+        let _ = tokio::fs::remove_file(&part_path).await;
+        return Err(e);
+    }
+    tokio::fs::rename(&part_path, &file_path)
+        .await
+        .map_err(|e| LocalLlmError::Io(format!("Failed to move {} into place: {}", filename, e)))?;
+    Ok(file_path)
+}
+
+async fn fetch_file_meta(
+    client: &reqwest::Client,
+    model_id: &str,
+    filename: &str,
+) -> Result<FileMeta, LocalLlmError> {
+    let url = format!("{}/api/models/{}?blobs=true", HF_BASE_URL, model_id);
+    let response = client
+        .get(&url)
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .await
+        .map_err(|e| LocalLlmError::HfHub(format!("Failed to fetch metadata: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(LocalLlmError::HfHub(format!(
+            "Failed to fetch metadata for {}: HTTP {}",
+            model_id,
+            response.status()
+        )));
+    }
+    let body = response
+        .text()
+        .await
+        .map_err(|e| LocalLlmError::HfHub(format!("Failed to read metadata: {}", e)))?;
+    file_meta(&body, filename)
+}
+
+fn file_meta(body: &str, filename: &str) -> Result<FileMeta, LocalLlmError> {
+    let info: ModelInfo = serde_json::from_str(body)
+        .map_err(|e| LocalLlmError::HfHub(format!("Invalid model metadata: {}", e)))?;
+    let sibling = info
+        .siblings
+        .into_iter()
+        .find(|s| s.rfilename == filename)
+        .ok_or_else(|| LocalLlmError::HfHub(format!("{} is not in the model repo", filename)))?;
+    Ok(match sibling.lfs {
+        Some(lfs) => FileMeta {
+            size: lfs.size.or(sibling.size),
+            sha256: Some(lfs.sha256.to_lowercase()),
+        },
+        None => FileMeta {
+            size: sibling.size,
+            sha256: None,
+        },
+    })
+}
+
+/// Stream `url` onto the end of `part_path`, asking only for the missing bytes
+async fn fetch_to_part(
+    client: &reqwest::Client,
+    url: &str,
+    part_path: &Path,
+    filename: &str,
+) -> Result<(), LocalLlmError> {
+    let offset = tokio::fs::metadata(part_path)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    let mut request = client.get(url).header("User-Agent", USER_AGENT);
+    if offset > 0 {
+        tracing::info!("Resuming {} from byte {}", filename, offset);
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+    } else {
+        tracing::info!("Downloading {}...", filename);
+    }
+    let mut response = request
+        .send()
+        .await
+        .map_err(|e| LocalLlmError::HfHub(format!("Failed to download {}: {}", filename, e)))?;
+
+    let status = response.status();
+    let mut file = if status == reqwest::StatusCode::PARTIAL_CONTENT {
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(part_path)
+            .await
+    } else if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // Nothing left to fetch; verification decides whether it is any good
+        return Ok(());
+    } else if status.is_success() {
+        // The server ignored the Range header and sent everything
+        tokio::fs::File::create(part_path).await
+    } else {
+        return Err(LocalLlmError::HfHub(format!(
+            "Failed to download {}: HTTP {}",
+            filename, status
+        )));
+    }
+    .map_err(|e| LocalLlmError::Io(format!("Failed to open {}: {}", part_path.display(), e)))?;
+
+    let mut received: u64 = 0;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| LocalLlmError::HfHub(format!("Failed to read {}: {}", filename, e)))?
+    {
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| LocalLlmError::Io(format!("Failed to write {}: {}", filename, e)))?;
+        received += chunk.len() as u64;
+    }
+    file.flush()
+        .await
+        .map_err(|e| LocalLlmError::Io(format!("Failed to write {}: {}", filename, e)))?;
+
+    tracing::info!("Downloaded {} ({} bytes)", filename, offset + received);
+    Ok(())
+}
+
+async fn verify(path: &Path, expected: &FileMeta, filename: &str) -> Result<(), LocalLlmError> {
+    let size = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| LocalLlmError::Io(e.to_string()))?
+        .len();
+    if let Some(expected_size) = expected.size {
+        if size != expected_size {
+            return Err(LocalLlmError::HfHub(format!(
+                "{} is {} bytes, expected {}",
+                filename, size, expected_size
+            )));
+        }
+    }
+
+    let Some(expected_sha) = expected.sha256.clone() else {
+        return Ok(());
+    };
+    let path = path.to_path_buf();
+    let actual_sha = tokio::task::spawn_blocking(move || -> std::io::Result<String> {
+        let mut file = std::fs::File::open(path)?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher)?;
+        Ok(format!("{:x}", hasher.finalize()))
+    })
+    .await
+    .map_err(|e| LocalLlmError::Io(e.to_string()))?
+    .map_err(|e| LocalLlmError::Io(format!("Failed to hash {}: {}", filename, e)))?;
+
+    if actual_sha != expected_sha {
+        return Err(LocalLlmError::HfHub(format!(
+            "{} failed SHA256 check (got {}, expected {})",
+            filename, actual_sha, expected_sha
+        )));
+    }
+    tracing::info!("Verified {} (sha256 {})", filename, actual_sha);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_meta_from_model_info() {
+        let body = r#"{"id":"HuggingFaceTB/SmolLM2-135M-Instruct","siblings":[
+            {"rfilename":"config.json","size":861,"blobId":"abc"},
+            {"rfilename":"model.safetensors","size":269060552,
+             "lfs":{"sha256":"5AF571CBF074E6D21A03528D2330792E532CA608F24AC70A143F6B369968AB8C","size":269060552,"pointerSize":135}}
+        ]}"#;
+
+        assert_eq!(
+            file_meta(body, "config.json").unwrap(),
+            FileMeta {
+                size: Some(861),
+                sha256: None
+            }
+        );
+        assert_eq!(
+            file_meta(body, "model.safetensors").unwrap(),
+            FileMeta {
+                size: Some(269060552),
+                sha256: Some(
+                    "5af571cbf074e6d21a03528d2330792e532ca608f24ac70a143f6b369968ab8c".to_string()
+                )
+            }
+        );
+        assert!(file_meta(body, "tokenizer.json").is_err());
+    }
+}
@@ -1,3 +1,15 @@
+mod bench;
+mod cache;
+mod config;
+mod device;
+mod download;
 mod model;
+mod pool;
+mod queue;
 
-pub use model::LocalLlm;
+pub use bench::{run_bench, BenchOptions, BenchReport};
+pub use cache::{CachedModel, ModelCache};
+pub use config::LocalLlmConfig;
+pub use model::{GenerationStats, LocalLlm, LocalLlmError};
+pub use pool::{loaded_models, ModelPoolConfig, DEFAULT_MODEL_NAME};
+pub use queue::{GenerationQueue, QueueError};
@@ -2,57 +2,156 @@ use candle_core::{DType, Device, Tensor};
 use candle_nn::VarBuilder;
 use candle_transformers::generation::LogitsProcessor;
 use candle_transformers::models::llama::{Config, Llama, LlamaConfig};
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokenizers::Tokenizer;
-use tokio::sync::OnceCell;
-
-use crate::domain::StartupInfo;
-
-const MODEL_ID: &str = "HuggingFaceTB/SmolLM2-135M-Instruct";
-const HF_BASE_URL: &str = "https://huggingface.co";
-const MAX_NEW_TOKENS: usize = 256;
-const TEMPERATURE: f64 = 0.7;
-const TOP_P: f64 = 0.9;
-const REPEAT_PENALTY: f32 = 1.1;
-
-static MODEL_INSTANCE: OnceCell<Arc<LocalLlm>> = OnceCell::const_new();
 
+use crate::domain::{Language, PitchDeck, RoastLength, RoastOptions, StartupInfo};
+use crate::infrastructure::openrouter::{
+    ai_wrapper_hint, archive_hint, dialect_style, dns_tls_hint, domain_age_hint, funding_hint, pages_hint, slides_hint, performance_hint,
+    profanity_rule, security_hint, seo_hint, social_links_hint, structured_data_hint, tech_stack_hint,
+};
+use super::cache::ModelCache;
+use super::config::LocalLlmConfig;
+use super::download::download_file;
+use super::pool::{ModelPoolConfig, LOADED, LOADING};
+use super::queue::GenerationQueue;
+use super::device::{device_label, select_device, select_dtype};
+use crate::infrastructure::token_budget::{fit_to_budget, TokenCounter};
+
+/// A loaded model. Weights are read-only and every generation gets its own
+/// KV cache, so generations share the model without a lock and run in
+/// parallel up to the queue's LOCAL_LLM_CONCURRENCY.
 pub struct LocalLlm {
-    model: Mutex<Llama>,
+    model: Llama,
     tokenizer: Tokenizer,
     device: Device,
+    /// Weight and KV cache dtype
+    dtype: DType,
     config: Config,
+    settings: LocalLlmConfig,
 }
 
 impl LocalLlm {
-    pub async fn get_or_init() -> Result<Arc<Self>, LocalLlmError> {
-        MODEL_INSTANCE
-            .get_or_try_init(|| async {
-                tracing::info!("Initializing local LLM: {}", MODEL_ID);
-                let llm = Self::new().await?;
-                Ok(Arc::new(llm))
-            })
-            .await
+    /// The pool model `name`, loading it on first use
+    pub async fn get_or_init(name: &str, pool: &ModelPoolConfig) -> Result<Arc<Self>, LocalLlmError> {
+        if let Some(llm) = Self::current(name) {
+            return Ok(llm);
+        }
+        let _loading = LOADING.lock().await;
+        if let Some(llm) = Self::current(name) {
+            return Ok(llm);
+        }
+
+        let model_id = pool
+            .model_id(name)
+            .ok_or_else(|| LocalLlmError::Config(format!("unknown local model {:?}", name)))?;
+        let mut settings = LocalLlmConfig::from_env()?;
+        settings.model_id = model_id.to_string();
+        tracing::info!("Initializing local LLM {}: {}", name, settings.model_id);
+        let llm = Arc::new(Self::new(settings, &pool.model_ids()).await?);
+        LOADED
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), llm.clone());
+        Ok(llm)
+    }
+
+    /// The loaded pool model `name`, if any, without triggering a load
+    pub fn current(name: &str) -> Option<Arc<Self>> {
+        LOADED
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
             .cloned()
     }
 
-    async fn new() -> Result<Self, LocalLlmError> {
-        let device = Device::Cpu;
-        let dtype = DType::F32;
+    /// Point pool entry `name` at `model_id` at runtime, keeping the other
+    /// LOCAL_LLM_* settings. The new model is downloaded and loaded while the
+    /// old one keeps serving; then every queue slot is taken, so in-flight
+    /// generations finish before the switch and queued requests run on the
+    /// new model. Not persisted: a restart goes back to the configured models.
+    pub async fn swap(
+        name: &str,
+        model_id: &str,
+        pool: &ModelPoolConfig,
+        queue: &GenerationQueue,
+    ) -> Result<Arc<Self>, LocalLlmError> {
+        if pool.model_id(name).is_none() {
+            return Err(LocalLlmError::Config(format!(
+                "unknown local model {:?}, expected one of {}",
+                name,
+                pool.names().join(", ")
+            )));
+        }
+        let _loading = LOADING.lock().await;
+        let mut settings = match Self::current(name) {
+            Some(llm) => llm.settings.clone(),
+            None => LocalLlmConfig::from_env()?,
+        };
+        settings.model_id = model_id.trim().to_string();
+        settings.validate()?;
+
+        tracing::info!("Loading local LLM {} to replace pool model {}", settings.model_id, name);
+        let loaded: Vec<String> = LOADED
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .map(|llm| llm.model_id().to_string())
+            .collect();
+        let mut keep = pool.model_ids();
+        keep.extend(loaded.iter().map(String::as_str));
+        let llm = Arc::new(Self::new(settings, &keep).await?);
+
+        let drained = queue
+            .drain()
+            .await
+            .map_err(|e| LocalLlmError::Model(format!("failed to drain generations: {:?}", e)))?;
+        let previous = LOADED
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), llm.clone());
+        drop(drained);
+
+        tracing::info!(
+            "Local LLM {} switched from {} to {}",
+            name,
+            previous.as_ref().map_or("none", |p| p.model_id()),
+            llm.model_id()
+        );
+        Ok(llm)
+    }
+
+    /// Download (if needed) and load `settings.model_id`; `keep` lists other
+    /// cached models the cache cap must not evict
+    async fn new(settings: LocalLlmConfig, keep: &[&str]) -> Result<Self, LocalLlmError> {
+        let device = select_device();
+        let dtype = select_dtype(&device);
+        tracing::info!("Local LLM device: {} ({:?})", device_label(&device), dtype);
 
         // Create cache directory
-        let cache_dir = Self::cache_dir()?;
+        let cache = ModelCache::from_env()?;
+        let cache_dir = cache.model_dir(&settings.model_id);
         tokio::fs::create_dir_all(&cache_dir)
             .await
             .map_err(|e| LocalLlmError::Io(e.to_string()))?;
 
-        tracing::info!("Downloading model from Hugging Face: {}", MODEL_ID);
+        tracing::info!("Downloading model from Hugging Face: {}", settings.model_id);
 
         // Download files
-        let config_path = Self::download_file(&cache_dir, "config.json").await?;
-        let tokenizer_path = Self::download_file(&cache_dir, "tokenizer.json").await?;
-        let weights_path = Self::download_file(&cache_dir, "model.safetensors").await?;
+        let model_id = settings.model_id.as_str();
+        let config_path = download_file(&cache_dir, model_id, "config.json").await?;
+        let tokenizer_path = download_file(&cache_dir, model_id, "tokenizer.json").await?;
+        let weights_path = download_file(&cache_dir, model_id, "model.safetensors").await?;
+        cache.touch(model_id);
+        if settings.cache_max_mb > 0 {
+            let mut keep = keep.to_vec();
+            keep.push(model_id);
+            let evicted = cache.enforce_cap(&keep, settings.cache_max_mb * 1024 * 1024)?;
+            if !evicted.is_empty() {
+                tracing::info!("Evicted cached models to stay under LOCAL_LLM_CACHE_MAX_MB: {:?}", evicted);
+            }
+        }
 
         tracing::info!("Loading model configuration...");
         let config_str = std::fs::read_to_string(&config_path)
@@ -65,7 +164,7 @@ impl LocalLlm {
         let tokenizer = Tokenizer::from_file(&tokenizer_path)
             .map_err(|e| LocalLlmError::Tokenizer(e.to_string()))?;
 
-        tracing::info!("Loading model weights (~135MB)...");
+        tracing::info!("Loading model weights...");
         let vb = unsafe {
             VarBuilder::from_mmaped_safetensors(&[weights_path], dtype, &device)
                 .map_err(|e| LocalLlmError::Model(e.to_string()))?
@@ -77,74 +176,128 @@ impl LocalLlm {
         tracing::info!("Local LLM initialized successfully!");
 
         Ok(Self {
-            model: Mutex::new(model),
+            model,
             tokenizer,
             device,
+            dtype,
             config,
+            settings,
         })
     }
 
-    fn cache_dir() -> Result<PathBuf, LocalLlmError> {
-        let home = std::env::var("HOME").map_err(|_| LocalLlmError::Io("HOME not set".to_string()))?;
-        let model_name = MODEL_ID.replace("/", "--");
-        Ok(PathBuf::from(home)
-            .join(".cache")
-            .join("roasting-startup")
-            .join("models")
-            .join(model_name))
+    pub fn model_id(&self) -> &str {
+        &self.settings.model_id
     }
 
-    async fn download_file(cache_dir: &PathBuf, filename: &str) -> Result<PathBuf, LocalLlmError> {
-        let file_path = cache_dir.join(filename);
+    /// Device and dtype the model runs on, e.g. `cuda:0 (F16)`
+    pub fn device_description(&self) -> String {
+        format!("{} ({:?})", device_label(&self.device), self.dtype)
+    }
 
-        // Check if file already exists
-        if file_path.exists() {
-            tracing::info!("Using cached {}", filename);
-            return Ok(file_path);
-        }
+    /// Load the configured model without sharing it, so a benchmark measures
+    /// a cold load the same way the server does at startup
+    pub async fn load(settings: LocalLlmConfig, keep: &[&str]) -> Result<Self, LocalLlmError> {
+        Self::new(settings, keep).await
+    }
 
-        let url = format!(
-            "{}/{}/resolve/main/{}",
-            HF_BASE_URL, MODEL_ID, filename
-        );
+    /// Same generation as `generate_roast`, with timings for benchmarking
+    pub fn generate_roast_timed(
+        &self,
+        startup_info: &StartupInfo,
+        options: &RoastOptions,
+    ) -> Result<(String, GenerationStats), LocalLlmError> {
+        let prompt = self.build_chat_prompt(startup_info, options);
+        self.generate_timed(&prompt, self.max_new_tokens(options.length))
+    }
 
-        tracing::info!("Downloading {}...", filename);
+    pub fn generate_roast(
+        &self,
+        startup_info: &StartupInfo,
+        options: &RoastOptions,
+    ) -> Result<String, LocalLlmError> {
+        let prompt = self.build_chat_prompt(startup_info, options);
+        self.generate(&prompt, self.max_new_tokens(options.length))
+    }
 
-        let client = reqwest::Client::new();
-        let response = client
-            .get(&url)
-            .header("User-Agent", "roasting-startup/1.0")
-            .send()
-            .await
-            .map_err(|e| LocalLlmError::HfHub(format!("Failed to download {}: {}", filename, e)))?;
+    /// Roast an uploaded pitch deck from its slide text
+    pub fn generate_deck_roast(&self, deck: &PitchDeck, options: &RoastOptions) -> Result<String, LocalLlmError> {
+        let slang = match options.language {
+            Language::Id => "Indonesian slang (bahasa gaul)",
+            Language::En => "casual English slang",
+        };
+        let slides = slides_hint(&deck.slides, usize::MAX);
+        let prompt = format!(
+            r#"<|im_start|>system
+You are a brutal but funny roasting comedian. Your job is to roast startup pitch decks.
+<|im_end|>
+<|im_start|>user
+Roast the pitch deck of {name} brutally but hilariously in {slang}:
 
-        if !response.status().is_success() {
-            return Err(LocalLlmError::HfHub(format!(
-                "Failed to download {}: HTTP {}",
-                filename,
-                response.status()
-            )));
-        }
+{slides}
 
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(|e| LocalLlmError::HfHub(format!("Failed to read {}: {}", filename, e)))?;
+Requirements:
+- Use {slang}
+- Mock the jargon, the market size and the projections
+- {profanity}
+- {paragraphs}
+- End with a dramatic failure prediction
+<|im_end|>
+<|im_start|>assistant
+"#,
+            name = deck.startup_name(),
+            slang = slang,
+            slides = self.tokenizer.truncate(&slides, self.settings.context_tokens),
+            profanity = profanity_rule(&RoastOptions::new(Language::En).with_safe(options.safe)),
+            paragraphs = paragraphs(options.length),
+        );
+        self.generate(&prompt, self.max_new_tokens(options.length))
+    }
 
-        tokio::fs::write(&file_path, &bytes)
-            .await
-            .map_err(|e| LocalLlmError::Io(format!("Failed to write {}: {}", filename, e)))?;
+    /// Answer a follow-up question, with the earlier roast as context
+    pub fn follow_up(
+        &self,
+        startup_name: &str,
+        roast_text: &str,
+        question: &str,
+        options: &RoastOptions,
+    ) -> Result<String, LocalLlmError> {
+        let slang = match options.language {
+            Language::Id => "Indonesian slang (bahasa gaul)",
+            Language::En => "casual English slang",
+        };
+        let prompt = format!(
+            r#"<|im_start|>system
+You are a brutal but funny roasting comedian.
+<|im_end|>
+<|im_start|>user
+You roasted the startup {name}:
+
+{roast}
 
-        tracing::info!("Downloaded {} ({} bytes)", filename, bytes.len());
-        Ok(file_path)
+Now roast this part deeper, in {slang}, in one short paragraph: {question}
+<|im_end|>
+<|im_start|>assistant
+"#,
+            name = startup_name,
+            roast = self.tokenizer.truncate(roast_text, self.settings.context_tokens),
+            slang = slang,
+            question = question,
+        );
+        self.generate(&prompt, self.max_new_tokens(RoastLength::Singkat))
     }
 
-    pub fn generate_roast(&self, startup_info: &StartupInfo) -> Result<String, LocalLlmError> {
-        let prompt = self.build_chat_prompt(startup_info);
-        self.generate(&prompt)
+    /// LOCAL_LLM_MAX_TOKENS is the "panjang" budget; the default 448 gives 128/256/448
+    fn max_new_tokens(&self, length: RoastLength) -> usize {
+        let max = self.settings.max_tokens;
+        match length {
+            RoastLength::Singkat => max * 2 / 7,
+            RoastLength::Standar => max * 4 / 7,
+            RoastLength::Panjang => max,
+        }
     }
 
-    fn build_chat_prompt(&self, startup_info: &StartupInfo) -> String {
+    fn build_chat_prompt(&self, startup_info: &StartupInfo, options: &RoastOptions) -> String {
+        let startup_info = &fit_to_budget(startup_info, &self.tokenizer, self.settings.context_tokens);
         let title = startup_info.title.as_deref().unwrap_or("Unknown");
         let description = startup_info
             .description
@@ -156,34 +309,87 @@ impl LocalLlm {
             startup_info.headings.join(", ")
         };
         let content = &startup_info.content_summary;
+        let (target_language, slang, style) = match options.language {
+            Language::Id => (
+                "Indonesian language",
+                "Indonesian slang (bahasa gaul)",
+                dialect_style(options.dialect).system_fragment,
+            ),
+            Language::En => ("English", "casual English slang", "Keep it casual and punchy"),
+        };
 
         // SmolLM2 uses simple chat format
         format!(
             r#"<|im_start|>system
-You are a brutal but funny roasting comedian. Your job is to roast startups in Indonesian language.
+You are a brutal but funny roasting comedian. Your job is to roast startups in {target_language}.
 <|im_end|>
 <|im_start|>user
-Roast this startup brutally but hilariously in Indonesian slang (bahasa gaul):
-
-URL: {}
-Name: {}
-Description: {}
-Headings: {}
-Content: {}
+Roast this startup brutally but hilariously in {slang}:
+
+URL: {url}
+Name: {title}
+Description: {description}
+Headings: {headings}
+Content: {content}
+Other pages: {pages}
+AI wrapper: {ai_wrapper}
+Tech stack: {tech_stack}
+Structured data: {structured_data}
+Funding & launches: {funding}
+Domain age: {domain_age}
+Infrastructure: {dns_tls}
+Performance: {performance}
+Security: {security}
+SEO: {seo}
+Social media: {social_links}
+Site access: {archive}
 
 Requirements:
-- Use Indonesian slang (bahasa gaul Jakarta)
+- Use {slang}
+- Style: {style}
 - Be savage but funny
-- 2-3 short paragraphs
+- {profanity}
+- {paragraphs}
 - End with a dramatic failure prediction
 <|im_end|>
 <|im_start|>assistant
 "#,
-            startup_info.url, title, description, headings, content
+            target_language = target_language,
+            slang = slang,
+            style = style,
+            paragraphs = paragraphs(options.length),
+            profanity = profanity_rule(&RoastOptions::new(Language::En).with_safe(options.safe)),
+            url = startup_info.url,
+            title = title,
+            description = description,
+            headings = headings,
+            content = content,
+            pages = pages_hint(&startup_info.pages, Language::En),
+            ai_wrapper = ai_wrapper_hint(startup_info.ai_wrapper.as_ref(), Language::En),
+            tech_stack = tech_stack_hint(startup_info.tech_stack.as_ref(), Language::En),
+            structured_data = structured_data_hint(startup_info.structured_data.as_ref(), Language::En),
+            funding = funding_hint(startup_info.funding.as_ref(), Language::En),
+            domain_age = domain_age_hint(startup_info.domain_info.as_ref(), Language::En),
+            dns_tls = dns_tls_hint(startup_info.dns_tls.as_ref(), Language::En),
+            performance = performance_hint(startup_info.performance.as_ref(), Language::En),
+            security = security_hint(startup_info.security.as_ref(), Language::En),
+            seo = seo_hint(startup_info.seo.as_ref(), Language::En),
+            social_links = social_links_hint(startup_info.social_links.as_deref(), Language::En),
+            archive = archive_hint(startup_info.archived.as_ref(), Language::En)
         )
     }
 
-    fn generate(&self, prompt: &str) -> Result<String, LocalLlmError> {
+    fn generate(&self, prompt: &str, max_new_tokens: usize) -> Result<String, LocalLlmError> {
+        self.generate_timed(prompt, max_new_tokens).map(|(text, _)| text)
+    }
+
+    fn generate_timed(
+        &self,
+        prompt: &str,
+        max_new_tokens: usize,
+    ) -> Result<(String, GenerationStats), LocalLlmError> {
+        let started = Instant::now();
+        let mut time_to_first_token = None;
         let tokens = self
             .tokenizer
             .encode(prompt, true)
@@ -194,8 +400,8 @@ Requirements:
 
         let mut logits_processor = LogitsProcessor::new(
             rand::random(),
-            Some(TEMPERATURE),
-            Some(TOP_P),
+            Some(self.settings.temperature),
+            Some(self.settings.top_p),
         );
 
         let eos_token_id = self
@@ -207,12 +413,11 @@ Requirements:
 
         tracing::info!("Generating response ({} input tokens)...", prompt_len);
 
-        let model = self.model.lock().map_err(|e| LocalLlmError::Model(e.to_string()))?;
-
-        // Create fresh cache for each generation
+        // Fresh cache for each generation; it is the only per-request state
+        // in the forward pass, so concurrent generations don't interfere
         let mut cache = candle_transformers::models::llama::Cache::new(
             true,
-            DType::F32,
+            self.dtype,
             &self.config,
             &self.device,
         ).map_err(|e| LocalLlmError::Model(e.to_string()))?;
@@ -220,20 +425,23 @@ Requirements:
         let mut generated_tokens: Vec<u32> = Vec::new();
         let mut current_tokens = input_ids.to_vec();
 
-        for i in 0..MAX_NEW_TOKENS {
+        for i in 0..max_new_tokens {
             let input = Tensor::new(&current_tokens[..], &self.device)
                 .map_err(|e| LocalLlmError::Model(format!("Tensor creation error: {}", e)))?
                 .unsqueeze(0)
                 .map_err(|e| LocalLlmError::Model(format!("Unsqueeze error: {}", e)))?;
 
             let index_pos = if i == 0 { 0 } else { prompt_len + i - 1 };
-            let logits = model
+            let logits = self
+                .model
                 .forward(&input, index_pos, &mut cache)
                 .map_err(|e| LocalLlmError::Model(format!("Forward pass error at token {}: {}", i, e)))?;
 
             // Llama returns logits for last token only: [batch, vocab_size]
+            // Sampling runs on the host in F32 whatever the model dtype
             let logits = logits
                 .squeeze(0)
+                .and_then(|l| l.to_dtype(DType::F32))
                 .map_err(|e| LocalLlmError::Model(format!("Squeeze error: {}", e)))?;
 
             // Apply repeat penalty
@@ -245,6 +453,8 @@ Requirements:
                 .sample(&logits)
                 .map_err(|e| LocalLlmError::Model(format!("Sample error: {}", e)))?;
 
+            time_to_first_token.get_or_insert_with(|| started.elapsed());
+
             if next_token == eos_token_id {
                 tracing::info!("EOS token reached after {} tokens", i + 1);
                 break;
@@ -254,14 +464,18 @@ Requirements:
             current_tokens = vec![next_token]; // Only feed new token with KV cache
         }
 
-        drop(model);
-
         let response = self
             .tokenizer
             .decode(&generated_tokens, true)
             .map_err(|e| LocalLlmError::Tokenizer(e.to_string()))?;
 
-        Ok(response.trim().to_string())
+        let stats = GenerationStats {
+            prompt_tokens: prompt_len,
+            generated_tokens: generated_tokens.len(),
+            time_to_first_token: time_to_first_token.unwrap_or_default(),
+            total: started.elapsed(),
+        };
+        Ok((response.trim().to_string(), stats))
     }
 
     fn apply_repeat_penalty(
@@ -276,14 +490,42 @@ Requirements:
         for &token in tokens.iter().rev().take(64) {
             if let Some(logit) = logits_vec.get_mut(token as usize) {
                 if *logit > 0.0 {
-                    *logit /= REPEAT_PENALTY;
+                    *logit /= self.settings.repeat_penalty;
                 } else {
-                    *logit *= REPEAT_PENALTY;
+                    *logit *= self.settings.repeat_penalty;
                 }
             }
         }
 
-        Tensor::new(logits_vec, &self.device).map_err(|e| LocalLlmError::Model(e.to_string()))
+        Tensor::new(logits_vec, &Device::Cpu).map_err(|e| LocalLlmError::Model(e.to_string()))
+    }
+}
+
+/// Timings of one generation; the first token includes the prompt prefill
+#[derive(Debug, Clone, Copy)]
+pub struct GenerationStats {
+    pub prompt_tokens: usize,
+    pub generated_tokens: usize,
+    pub time_to_first_token: Duration,
+    pub total: Duration,
+}
+
+impl GenerationStats {
+    /// Decode speed after the first token, which is what users watch stream in
+    pub fn tokens_per_sec(&self) -> f64 {
+        let decode = self.total.saturating_sub(self.time_to_first_token).as_secs_f64();
+        if self.generated_tokens <= 1 || decode <= 0.0 {
+            return 0.0;
+        }
+        (self.generated_tokens - 1) as f64 / decode
+    }
+}
+
+fn paragraphs(length: RoastLength) -> &'static str {
+    match length {
+        RoastLength::Singkat => "1 short paragraph",
+        RoastLength::Standar => "2-3 short paragraphs",
+        RoastLength::Panjang => "4-5 paragraphs",
     }
 }
 
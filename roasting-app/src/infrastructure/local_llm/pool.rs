@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use crate::domain::RoastLength;
+use super::config::LocalLlmConfig;
+use super::model::{LocalLlm, LocalLlmError};
+
+/// Name of the only model when LOCAL_LLM_MODELS is unset
+pub const DEFAULT_MODEL_NAME: &str = "default";
+
+/// Loaded models by pool name; each entry is replaced as a whole by `LocalLlm::swap`
+pub(super) static LOADED: RwLock<BTreeMap<String, Arc<LocalLlm>>> = RwLock::new(BTreeMap::new());
+/// Serializes loads and swaps so a model is never loaded twice
+pub(super) static LOADING: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Local models that can be loaded side by side, e.g. a small fast model for
+/// quick previews next to a larger one for full roasts. Requests pick a model
+/// by name, or fall back to the route for their roast length, then to the
+/// first model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelPoolConfig {
+    /// `(name, Hugging Face repo)` in configured order
+    models: Vec<(String, String)>,
+    routes: Vec<(RoastLength, String)>,
+}
+
+impl ModelPoolConfig {
+    /// Reads LOCAL_LLM_MODELS, `name=owner/model` pairs separated by commas
+    /// (unset: one model named "default" from LOCAL_LLM_MODEL), and
+    /// LOCAL_LLM_ROUTES, `length=name` pairs such as `singkat=fast`.
+    pub fn from_env() -> Result<Self, LocalLlmError> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let models = match var("LOCAL_LLM_MODELS") {
+            Some(spec) => spec,
+            None => format!("{}={}", DEFAULT_MODEL_NAME, LocalLlmConfig::from_env()?.model_id),
+        };
+        Self::parse(&models, var("LOCAL_LLM_ROUTES").as_deref().unwrap_or_default())
+    }
+
+    fn parse(models: &str, routes: &str) -> Result<Self, LocalLlmError> {
+        let mut pool = Self {
+            models: Vec::new(),
+            routes: Vec::new(),
+        };
+        for (name, model_id) in pairs(models, "LOCAL_LLM_MODELS")? {
+            LocalLlmConfig {
+                model_id: model_id.clone(),
+                ..Default::default()
+            }
+            .validate()?;
+            if pool.model_id(&name).is_some() {
+                return Err(invalid("LOCAL_LLM_MODELS", &format!("{} is listed twice", name)));
+            }
+            pool.models.push((name, model_id));
+        }
+        if pool.models.is_empty() {
+            return Err(invalid("LOCAL_LLM_MODELS", "no models listed"));
+        }
+        for (length, name) in pairs(routes, "LOCAL_LLM_ROUTES")? {
+            let length: RoastLength = length
+                .parse()
+                .map_err(|e: String| invalid("LOCAL_LLM_ROUTES", &e))?;
+            if pool.model_id(&name).is_none() {
+                return Err(invalid("LOCAL_LLM_ROUTES", &format!("unknown model {}", name)));
+            }
+            pool.routes.push((length, name));
+        }
+        Ok(pool)
+    }
+
+    pub fn default_name(&self) -> &str {
+        &self.models[0].0
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.models.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    pub fn model_id(&self, name: &str) -> Option<&str> {
+        self.models
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, id)| id.as_str())
+    }
+
+    /// Every configured repo, so cache eviction never removes a pool model
+    pub fn model_ids(&self) -> Vec<&str> {
+        self.models.iter().map(|(_, id)| id.as_str()).collect()
+    }
+
+    /// The model a request runs on: the requested name, else the route for
+    /// its length, else the default
+    pub fn resolve(&self, requested: Option<&str>, length: RoastLength) -> Result<&str, LocalLlmError> {
+        if let Some(name) = requested {
+            return self
+                .models
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(n, _)| n.as_str())
+                .ok_or_else(|| {
+                    LocalLlmError::Config(format!(
+                        "unknown local model {:?}, expected one of {}",
+                        name,
+                        self.names().join(", ")
+                    ))
+                });
+        }
+        Ok(self
+            .routes
+            .iter()
+            .find(|(l, _)| *l == length)
+            .map_or(self.default_name(), |(_, name)| name.as_str()))
+    }
+}
+
+/// Currently loaded models with their pool names
+pub fn loaded_models() -> Vec<(String, Arc<LocalLlm>)> {
+    LOADED
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(name, llm)| (name.clone(), llm.clone()))
+        .collect()
+}
+
+fn pairs(spec: &str, var: &str) -> Result<Vec<(String, String)>, LocalLlmError> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            pair.split_once('=')
+                .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+                .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+                .ok_or_else(|| invalid(var, &format!("expected name=value, got {:?}", pair)))
+        })
+        .collect()
+}
+
+fn invalid(name: &str, reason: &str) -> LocalLlmError {
+    LocalLlmError::Config(format!("{}: {}", name, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_resolve() {
+        let pool = ModelPoolConfig::parse(
+            "fast=HuggingFaceTB/SmolLM2-135M-Instruct, full=HuggingFaceTB/SmolLM2-1.7B-Instruct",
+            "singkat=fast, panjang=full",
+        )
+        .unwrap();
+        assert_eq!(pool.default_name(), "fast");
+        assert_eq!(pool.model_id("full"), Some("HuggingFaceTB/SmolLM2-1.7B-Instruct"));
+        assert_eq!(pool.resolve(None, RoastLength::Singkat).unwrap(), "fast");
+        assert_eq!(pool.resolve(Some("full"), RoastLength::Singkat).unwrap(), "full");
+        assert_eq!(pool.resolve(None, RoastLength::Panjang).unwrap(), "full");
+        assert_eq!(pool.resolve(None, RoastLength::Standar).unwrap(), "fast");
+        assert!(pool.resolve(Some("huge"), RoastLength::Panjang).is_err());
+
+        assert!(ModelPoolConfig::parse("fast", "").is_err());
+        assert!(ModelPoolConfig::parse("fast=smollm", "").is_err());
+        assert!(ModelPoolConfig::parse("a=x/y,a=x/z", "").is_err());
+        assert!(ModelPoolConfig::parse("a=x/y", "singkat=b").is_err());
+    }
+}
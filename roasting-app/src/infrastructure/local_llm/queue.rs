@@ -0,0 +1,206 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueueError {
+    /// Every slot is busy and `waiting` requests are already queued
+    Full {
+        waiting: usize,
+    },
+    /// Waited the whole timeout at `position` without getting a slot
+    TimedOut {
+        position: usize,
+    },
+    Join(String),
+}
+
+/// Place in the waiting line; gives it back on drop, so a request that is
+/// cancelled while queued doesn't leave the count behind
+struct WaitSlot<'a>(&'a AtomicUsize);
+
+impl Drop for WaitSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Bounded queue in front of the local model. At most `concurrency`
+/// generations run at once, at most `max_waiting` requests wait for a slot,
+/// and anyone beyond that is turned away instead of piling up on the model.
+pub struct GenerationQueue {
+    slots: Arc<Semaphore>,
+    concurrency: usize,
+    max_waiting: usize,
+    wait_timeout: Duration,
+    waiting: AtomicUsize,
+}
+
+impl GenerationQueue {
+    pub fn new(concurrency: usize, max_waiting: usize, wait_timeout: Duration) -> Self {
+        let concurrency = concurrency.max(1);
+        Self {
+            slots: Arc::new(Semaphore::new(concurrency)),
+            concurrency,
+            max_waiting,
+            wait_timeout,
+            waiting: AtomicUsize::new(0),
+        }
+    }
+
+    /// Reads LOCAL_LLM_CONCURRENCY (default 1), LOCAL_LLM_QUEUE_MAX (default 8)
+    /// and LOCAL_LLM_QUEUE_TIMEOUT_SECS (default 120)
+    pub fn from_env() -> Self {
+        let var = |name: &str, default: usize| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default)
+        };
+        Self::new(
+            var("LOCAL_LLM_CONCURRENCY", 1),
+            var("LOCAL_LLM_QUEUE_MAX", 8),
+            Duration::from_secs(var("LOCAL_LLM_QUEUE_TIMEOUT_SECS", 120) as u64),
+        )
+    }
+
+    pub fn running(&self) -> usize {
+        self.concurrency - self.slots.available_permits()
+    }
+
+    pub fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::Relaxed)
+    }
+
+    pub fn max_waiting(&self) -> usize {
+        self.max_waiting
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.slots.available_permits() == 0 && self.waiting() >= self.max_waiting
+    }
+
+    /// Take every slot, waiting for running generations to finish. New jobs
+    /// queue behind this until the returned permit is dropped.
+    pub async fn drain(&self) -> Result<SemaphorePermit<'_>, QueueError> {
+        self.slots
+            .acquire_many(self.concurrency as u32)
+            .await
+            .map_err(|e| QueueError::Join(e.to_string()))
+    }
+
+    /// Run a blocking generation once a slot is free. The slot goes with
+    /// the job, so a caller that gives up doesn't free it while the
+    /// generation is still running.
+    pub async fn run<T, F>(&self, job: F) -> Result<T, QueueError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = match self.slots.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                let position = self.waiting.fetch_add(1, Ordering::Relaxed) + 1;
+                let slot = WaitSlot(&self.waiting);
+                if position > self.max_waiting {
+                    return Err(QueueError::Full {
+                        waiting: position - 1,
+                    });
+                }
+                tracing::info!("Local LLM busy, queued at position {}", position);
+                let acquired = tokio::time::timeout(self.wait_timeout, self.slots.clone().acquire_owned()).await;
+                drop(slot);
+                match acquired {
+                    Ok(Ok(permit)) => permit,
+                    Ok(Err(e)) => return Err(QueueError::Join(e.to_string())),
+                    Err(_) => return Err(QueueError::TimedOut { position }),
+                }
+            }
+        };
+
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            job()
+        })
+        .await
+        .map_err(|e| QueueError::Join(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_rejects_when_queue_is_full() {
+        let queue = Arc::new(GenerationQueue::new(1, 1, Duration::from_secs(5)));
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+
+        let running = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.run(move || rx.recv().ok()).await })
+        };
+        while queue.running() == 0 {
+            tokio::task::yield_now().await;
+        }
+        let waiting = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.run(|| 2).await })
+        };
+        while queue.waiting() == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(queue.is_full());
+        assert_eq!(queue.run(|| 3).await, Err(QueueError::Full { waiting: 1 }));
+
+        tx.send(()).unwrap();
+        assert_eq!(running.await.unwrap(), Ok(Some(())));
+        assert_eq!(waiting.await.unwrap(), Ok(2));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waiter_leaves_the_queue() {
+        let queue = Arc::new(GenerationQueue::new(1, 1, Duration::from_secs(5)));
+        let held = queue.drain().await.unwrap();
+
+        let queued = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.run(|| 1).await })
+        };
+        while queue.waiting() == 0 {
+            tokio::task::yield_now().await;
+        }
+        queued.abort();
+        assert!(queued.await.unwrap_err().is_cancelled());
+
+        assert_eq!(queue.waiting(), 0);
+        drop(held);
+        assert_eq!(queue.run(|| 2).await, Ok(2));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_caller_keeps_the_slot_until_the_job_ends() {
+        let queue = Arc::new(GenerationQueue::new(1, 1, Duration::from_secs(5)));
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+
+        let caller = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.run(move || rx.recv().ok()).await })
+        };
+        while queue.running() == 0 {
+            tokio::task::yield_now().await;
+        }
+        caller.abort();
+        assert!(caller.await.unwrap_err().is_cancelled());
+        assert_eq!(queue.running(), 1);
+
+        tx.send(()).unwrap();
+        let drained = queue.drain().await.unwrap();
+        assert_eq!(queue.running(), 1);
+        drop(drained);
+        assert_eq!(queue.running(), 0);
+    }
+}
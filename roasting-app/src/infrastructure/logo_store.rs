@@ -0,0 +1,286 @@
+use crate::domain::DomainOptOut;
+use crate::infrastructure::security::InputSanitizer;
+use ring::{digest, hmac};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Apple touch icons are 180px; anything much bigger is a hero image
+const MAX_LOGO_BYTES: usize = 512 * 1024;
+
+enum LogoBackend {
+    Filesystem {
+        root: PathBuf,
+    },
+    /// Any S3-compatible store, addressed path-style (`{endpoint}/{bucket}/{key}`)
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key_id: String,
+        secret_access_key: String,
+    },
+}
+
+/// Keeps a copy of every roasted startup's logo, keyed by domain, so pages
+/// serve it from `/logos/{domain}` instead of hotlinking the startup's site.
+/// LOGO_STORAGE selects `fs` (the default, under LOGO_DIR), `s3`
+/// (LOGO_S3_ENDPOINT, LOGO_S3_BUCKET, LOGO_S3_REGION, LOGO_S3_ACCESS_KEY_ID,
+/// LOGO_S3_SECRET_ACCESS_KEY) or `off`.
+pub struct LogoStore {
+    http_client: reqwest::Client,
+    backend: LogoBackend,
+}
+
+impl LogoStore {
+    /// None when storage is off; an incomplete S3 configuration is logged
+    /// and also disables it
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let required = |name: &str| {
+            let value = var(name);
+            if value.is_none() {
+                tracing::warn!("LOGO_STORAGE is s3 but {} is missing, logo storage disabled", name);
+            }
+            value
+        };
+        let backend = match var("LOGO_STORAGE").unwrap_or_else(|| "fs".to_string()).to_lowercase().as_str() {
+            "fs" => LogoBackend::Filesystem {
+                root: PathBuf::from(var("LOGO_DIR").unwrap_or_else(|| "data/logos".to_string())),
+            },
+            "s3" => LogoBackend::S3 {
+                endpoint: required("LOGO_S3_ENDPOINT")?.trim_end_matches('/').to_string(),
+                bucket: required("LOGO_S3_BUCKET")?,
+                region: var("LOGO_S3_REGION").unwrap_or_else(|| "us-east-1".to_string()),
+                access_key_id: required("LOGO_S3_ACCESS_KEY_ID")?,
+                secret_access_key: required("LOGO_S3_SECRET_ACCESS_KEY")?,
+            },
+            "off" => return None,
+            other => {
+                tracing::warn!("Unknown LOGO_STORAGE {:?}, logo storage disabled", other);
+                return None;
+            }
+        };
+        Some(Self {
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .redirect(reqwest::redirect::Policy::limited(3))
+                .user_agent("roasting-startup-logo/1.0")
+                .build()
+                .expect("Failed to create HTTP client"),
+            backend,
+        })
+    }
+
+    /// Download the first candidate that turns out to be a raster image and
+    /// store it under the site's domain. Sites that already have a logo are
+    /// left alone.
+    pub async fn save_from(&self, site_url: &str, candidates: &[String]) -> Result<bool, String> {
+        let domain = DomainOptOut::normalize_domain(site_url).ok_or("invalid domain")?;
+        if self.exists(&domain).await? {
+            return Ok(false);
+        }
+        for candidate in candidates {
+            if InputSanitizer::validate_url(candidate).is_err() {
+                continue;
+            }
+            match self.download(candidate).await {
+                Ok(bytes) => {
+                    self.put(&domain, &bytes).await?;
+                    tracing::info!("Stored logo of {} from {} ({} bytes)", domain, candidate, bytes.len());
+                    return Ok(true);
+                }
+                Err(e) => tracing::debug!("Logo candidate {} skipped: {}", candidate, e),
+            }
+        }
+        Ok(false)
+    }
+
+    async fn download(&self, url: &str) -> Result<Vec<u8>, String> {
+        let mut response = self.http_client.get(url).send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        if response.content_length().is_some_and(|len| len as usize > MAX_LOGO_BYTES) {
+            return Err("too large".to_string());
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            bytes.extend_from_slice(&chunk);
+            if bytes.len() > MAX_LOGO_BYTES {
+                return Err("too large".to_string());
+            }
+        }
+        sniff_image_type(&bytes).ok_or("not a raster image")?;
+        Ok(bytes)
+    }
+
+    /// The stored logo of a normalized domain
+    pub async fn get(&self, domain: &str) -> Result<Option<Vec<u8>>, String> {
+        match &self.backend {
+            LogoBackend::Filesystem { root } => match tokio::fs::read(root.join(domain)).await {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.to_string()),
+            },
+            LogoBackend::S3 { .. } => {
+                let response = self.s3_request(reqwest::Method::GET, domain, Vec::new()).await?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                if !response.status().is_success() {
+                    return Err(format!("S3 HTTP {}", response.status()));
+                }
+                response.bytes().await.map(|b| Some(b.to_vec())).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    async fn exists(&self, domain: &str) -> Result<bool, String> {
+        match &self.backend {
+            LogoBackend::Filesystem { root } => {
+                tokio::fs::try_exists(root.join(domain)).await.map_err(|e| e.to_string())
+            }
+            LogoBackend::S3 { .. } => {
+                let response = self.s3_request(reqwest::Method::HEAD, domain, Vec::new()).await?;
+                match response.status() {
+                    status if status.is_success() => Ok(true),
+                    reqwest::StatusCode::NOT_FOUND => Ok(false),
+                    status => Err(format!("S3 HTTP {}", status)),
+                }
+            }
+        }
+    }
+
+    async fn put(&self, domain: &str, bytes: &[u8]) -> Result<(), String> {
+        match &self.backend {
+            LogoBackend::Filesystem { root } => {
+                tokio::fs::create_dir_all(root).await.map_err(|e| e.to_string())?;
+                // Readers never see a half-written file
+                let part = root.join(format!("{}.part", domain));
+                tokio::fs::write(&part, bytes).await.map_err(|e| e.to_string())?;
+                tokio::fs::rename(&part, root.join(domain)).await.map_err(|e| e.to_string())
+            }
+            LogoBackend::S3 { .. } => {
+                let response = self.s3_request(reqwest::Method::PUT, domain, bytes.to_vec()).await?;
+                if !response.status().is_success() {
+                    return Err(format!("S3 HTTP {}", response.status()));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// A request signed with AWS Signature Version 4
+    async fn s3_request(
+        &self,
+        method: reqwest::Method,
+        key: &str,
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response, String> {
+        let LogoBackend::S3 {
+            endpoint,
+            bucket,
+            region,
+            access_key_id,
+            secret_access_key,
+        } = &self.backend
+        else {
+            unreachable!("s3_request on a filesystem store");
+        };
+        let url = url::Url::parse(&format!("{}/{}/{}", endpoint, bucket, key)).map_err(|e| e.to_string())?;
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let day = now.format("%Y%m%d").to_string();
+        let payload_hash = to_hex(digest::digest(&digest::SHA256, &body).as_ref());
+
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method,
+            url.path(),
+            host,
+            payload_hash,
+            amz_date,
+            signed_headers,
+            payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", day, region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            to_hex(digest::digest(&digest::SHA256, canonical_request.as_bytes()).as_ref())
+        );
+        let sign = |key: &[u8], data: &str| {
+            hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data.as_bytes())
+                .as_ref()
+                .to_vec()
+        };
+        let signing_key = ["s3", "aws4_request"].iter().fold(
+            sign(
+                &sign(format!("AWS4{}", secret_access_key).as_bytes(), &day),
+                region,
+            ),
+            |key, part| sign(&key, part),
+        );
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            access_key_id,
+            scope,
+            signed_headers,
+            to_hex(&sign(&signing_key, &string_to_sign))
+        );
+
+        let mut request = self
+            .http_client
+            .request(method, url)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header("authorization", authorization);
+        if !body.is_empty() {
+            let content_type = sniff_image_type(&body).unwrap_or("application/octet-stream");
+            request = request.header("content-type", content_type).body(body);
+        }
+        request.send().await.map_err(|e| e.to_string())
+    }
+}
+
+/// Content type from the magic bytes. SVG is deliberately not recognized:
+/// it can carry scripts, and we serve logos from our own origin.
+pub fn sniff_image_type(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [0x89, b'P', b'N', b'G', ..] => Some("image/png"),
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [b'G', b'I', b'F', b'8', ..] => Some("image/gif"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        [0x00, 0x00, 0x01, 0x00, ..] => Some("image/x-icon"),
+        _ => None,
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniffs_raster_images_only() {
+        assert_eq!(sniff_image_type(b"\x89PNG\r\n\x1a\n...."), Some("image/png"));
+        assert_eq!(sniff_image_type(b"RIFF\x24\x00\x00\x00WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff_image_type(&[0, 0, 1, 0, 1, 0]), Some("image/x-icon"));
+        assert_eq!(sniff_image_type(b"<svg xmlns=\"http://www.w3.org/2000/svg\">"), None);
+        assert_eq!(sniff_image_type(b"<!doctype html>"), None);
+    }
+}
@@ -0,0 +1,7 @@
+mod request_id;
+mod request_metrics;
+mod scrape_metrics;
+
+pub use request_id::{current_request_id, is_valid_request_id, request_id_from, with_request_id, REQUEST_ID_HEADER};
+pub use request_metrics::RequestMetrics;
+pub use scrape_metrics::ScrapeMetrics;
@@ -0,0 +1,50 @@
+use std::future::Future;
+use uuid::Uuid;
+
+/// Header the ID is read from (when a proxy already set one) and echoed in
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// The proxy's ID when it looks like one, otherwise a fresh 16-hex-digit ID
+/// short enough to read out over WhatsApp
+pub fn request_id_from(header: Option<&str>) -> String {
+    header
+        .map(str::trim)
+        .filter(|id| is_valid_request_id(id))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().simple().to_string()[..16].to_string())
+}
+
+/// Safe to log, store and put in a URL
+pub fn is_valid_request_id(id: &str) -> bool {
+    (8..=64).contains(&id.len()) && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+}
+
+/// Run a request with its ID available to `current_request_id`
+pub async fn with_request_id<F: Future>(request_id: String, request: F) -> F::Output {
+    REQUEST_ID.scope(request_id, request).await
+}
+
+/// ID of the request being handled; `None` in background tasks
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_id_is_scoped_to_the_request() {
+        assert_eq!(request_id_from(Some(" 9f1c2e7a-proxy ")), "9f1c2e7a-proxy");
+        let minted = request_id_from(Some("<script>"));
+        assert_eq!(minted.len(), 16);
+
+        assert_eq!(current_request_id(), None);
+        let seen = with_request_id(minted.clone(), async { current_request_id() }).await;
+        assert_eq!(seen, Some(minted));
+    }
+}
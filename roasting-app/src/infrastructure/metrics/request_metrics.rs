@@ -0,0 +1,83 @@
+use crate::domain::RouteStats;
+use crate::infrastructure::db::RequestLogRepository;
+use chrono::{Duration, Utc};
+use sea_orm::DbErr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const DEFAULT_SAMPLE_RATE: f64 = 0.1;
+const STATS_WINDOW_HOURS: i64 = 24;
+const RETENTION_HOURS: i64 = 48;
+const MAX_ROUTES: u64 = 100;
+
+/// Sampled per-route request logging for the admin metrics view
+pub struct RequestMetrics {
+    repo: RequestLogRepository,
+    sample_every: u64,
+    counter: AtomicU64,
+}
+
+impl RequestMetrics {
+    /// `sample_rate` is the fraction of requests to log (0.0 disables logging)
+    pub fn new(repo: RequestLogRepository, sample_rate: f64) -> Self {
+        let sample_every = if sample_rate <= 0.0 {
+            0
+        } else {
+            (1.0 / sample_rate.min(1.0)).round() as u64
+        };
+
+        Self {
+            repo,
+            sample_every,
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    pub fn from_env(repo: RequestLogRepository) -> Self {
+        let sample_rate = std::env::var("REQUEST_LOG_SAMPLE_RATE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SAMPLE_RATE);
+        Self::new(repo, sample_rate)
+    }
+
+    pub fn sample_rate(&self) -> f64 {
+        if self.sample_every == 0 {
+            0.0
+        } else {
+            1.0 / self.sample_every as f64
+        }
+    }
+
+    /// Record a finished request if it falls in the sample, without blocking the caller
+    pub fn record(&self, method: &str, route: &str, status: u16, duration_ms: u64) {
+        if self.sample_every == 0
+            || !self
+                .counter
+                .fetch_add(1, Ordering::Relaxed)
+                .is_multiple_of(self.sample_every)
+        {
+            return;
+        }
+
+        let repo = self.repo.clone();
+        let method = method.to_string();
+        let route = route.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = repo.record(&method, &route, status, duration_ms).await {
+                tracing::warn!("Failed to record request log: {}", e);
+            }
+        });
+    }
+
+    /// Route stats over the last 24 hours, slowest p95 first
+    pub async fn route_stats(&self) -> Result<Vec<RouteStats>, DbErr> {
+        let since = Utc::now() - Duration::hours(STATS_WINDOW_HOURS);
+        self.repo.route_stats(since, MAX_ROUTES).await
+    }
+
+    /// Drop log rows past the retention window
+    pub async fn prune(&self) -> Result<u64, DbErr> {
+        let cutoff = Utc::now() - Duration::hours(RETENTION_HOURS);
+        self.repo.prune_before(cutoff).await
+    }
+}
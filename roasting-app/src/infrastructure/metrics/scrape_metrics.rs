@@ -0,0 +1,41 @@
+use crate::domain::ScrapeMethod;
+use crate::infrastructure::db::ScrapeLogRepository;
+use std::time::Instant;
+
+/// Records every scraping method tried, for the scrape health report
+pub struct ScrapeMetrics {
+    repo: ScrapeLogRepository,
+}
+
+impl ScrapeMetrics {
+    pub fn new(repo: ScrapeLogRepository) -> Self {
+        Self { repo }
+    }
+
+    /// Log one attempt that started at `started`, without blocking the scrape
+    pub fn record(
+        &self,
+        method: ScrapeMethod,
+        domain: &str,
+        started: Instant,
+        error: Option<String>,
+    ) {
+        let repo = self.repo.clone();
+        let domain = domain.to_string();
+        let duration_ms = started.elapsed().as_millis() as u64;
+        tokio::spawn(async move {
+            if let Err(e) = repo
+                .record(
+                    method.as_str(),
+                    &domain,
+                    error.is_none(),
+                    duration_ms,
+                    error.as_deref(),
+                )
+                .await
+            {
+                tracing::warn!("Failed to record scrape attempt: {}", e);
+            }
+        });
+    }
+}
@@ -1,6 +1,14 @@
+pub mod circuit_breaker;
+pub mod dns_tls;
+pub mod embedding;
+pub mod enrichment;
 pub mod openrouter;
+pub mod pitch_deck;
+pub mod prompt_templates;
+pub mod rdap;
 pub mod scraper;
 pub mod security;
+pub mod token_budget;
 
 #[cfg(feature = "ssr")]
 pub mod db;
@@ -8,6 +16,24 @@ pub mod db;
 #[cfg(feature = "ssr")]
 pub mod auth;
 
+#[cfg(feature = "ssr")]
+pub mod metrics;
+
+#[cfg(feature = "ssr")]
+pub mod cache_purger;
+
+#[cfg(feature = "ssr")]
+pub mod logo_store;
+
+#[cfg(feature = "ssr")]
+pub mod notification;
+
+#[cfg(feature = "ssr")]
+pub mod ops_channel;
+
+#[cfg(feature = "ssr")]
+pub mod sticker;
+
 #[cfg(feature = "headless")]
 pub mod cloudflare;
 
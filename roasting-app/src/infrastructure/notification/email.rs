@@ -0,0 +1,56 @@
+use super::{http_client, plain_text, NotificationChannel, Recipient};
+use crate::domain::Notification;
+use async_trait::async_trait;
+
+/// Email to the account address through the Resend HTTP API
+/// (RESEND_API_KEY, sent from EMAIL_FROM). Opt-in, since people signed up
+/// with Google rather than for a newsletter.
+pub struct EmailChannel {
+    http_client: reqwest::Client,
+    api_key: String,
+    from: String,
+}
+
+impl EmailChannel {
+    /// None unless both RESEND_API_KEY and EMAIL_FROM are set
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Some(Self {
+            http_client: http_client(),
+            api_key: var("RESEND_API_KEY")?,
+            from: var("EMAIL_FROM")?,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for EmailChannel {
+    fn name(&self) -> &'static str {
+        "email"
+    }
+
+    async fn send(&self, recipient: &Recipient, notification: &Notification) -> Result<(), String> {
+        let response = self
+            .http_client
+            .post("https://api.resend.com/emails")
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({
+                "from": self.from,
+                "to": [recipient.email],
+                "subject": notification.title,
+                "text": plain_text(notification),
+            }))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use super::{NotificationChannel, Recipient};
+use crate::domain::Notification;
+use crate::infrastructure::db::NotificationRepository;
+use async_trait::async_trait;
+
+/// The inbox behind `/api/me/notifications`; on unless the user turns it off
+pub struct InAppChannel {
+    repo: NotificationRepository,
+}
+
+impl InAppChannel {
+    pub fn new(repo: NotificationRepository) -> Self {
+        Self { repo }
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for InAppChannel {
+    fn name(&self) -> &'static str {
+        "in_app"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    async fn send(&self, recipient: &Recipient, notification: &Notification) -> Result<(), String> {
+        self.repo
+            .create(recipient.user_id, notification)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
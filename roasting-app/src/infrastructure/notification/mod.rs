@@ -0,0 +1,58 @@
+mod email;
+mod in_app;
+mod telegram;
+mod web_push;
+
+pub use email::EmailChannel;
+pub use in_app::InAppChannel;
+pub use telegram::TelegramChannel;
+pub use web_push::WebPushChannel;
+
+use crate::domain::Notification;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Who a notification goes to, resolved once per event by the dispatcher
+#[derive(Debug, Clone)]
+pub struct Recipient {
+    pub user_id: Uuid,
+    pub email: String,
+    /// The user's address for this channel from their preferences
+    pub address: Option<String>,
+}
+
+/// A way of delivering notifications. Event producers never see channels;
+/// they hand a `Notification` to `NotifyUser`, which fans it out to every
+/// channel the recipient has enabled.
+#[async_trait]
+pub trait NotificationChannel: Send + Sync {
+    /// Stable key stored in `notification_preferences.channel`
+    fn name(&self) -> &'static str;
+
+    /// Whether users who never changed their preferences get this channel
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    /// Whether delivery needs a per-user address in the preferences
+    fn needs_address(&self) -> bool {
+        false
+    }
+
+    async fn send(&self, recipient: &Recipient, notification: &Notification) -> Result<(), String>;
+}
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+/// Plain-text rendering shared by the channels without rich formatting
+fn plain_text(notification: &Notification) -> String {
+    match &notification.link {
+        Some(link) => format!("{}\n{}\n{}", notification.title, notification.body, link),
+        None => format!("{}\n{}", notification.title, notification.body),
+    }
+}
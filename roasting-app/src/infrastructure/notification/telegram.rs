@@ -0,0 +1,55 @@
+use super::{http_client, plain_text, NotificationChannel, Recipient};
+use crate::domain::Notification;
+use async_trait::async_trait;
+
+/// Direct messages from the bot at TELEGRAM_BOT_TOKEN. Users opt in with the
+/// chat ID of their conversation with the bot as the channel address.
+pub struct TelegramChannel {
+    http_client: reqwest::Client,
+    bot_token: String,
+}
+
+impl TelegramChannel {
+    /// None without TELEGRAM_BOT_TOKEN
+    pub fn from_env() -> Option<Self> {
+        let bot_token = std::env::var("TELEGRAM_BOT_TOKEN")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())?;
+        Some(Self {
+            http_client: http_client(),
+            bot_token,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for TelegramChannel {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    fn needs_address(&self) -> bool {
+        true
+    }
+
+    async fn send(&self, recipient: &Recipient, notification: &Notification) -> Result<(), String> {
+        let Some(chat_id) = &recipient.address else {
+            return Err("no chat ID".to_string());
+        };
+        let response = self
+            .http_client
+            .post(format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token))
+            .json(&serde_json::json!({
+                "chat_id": chat_id,
+                "text": plain_text(notification),
+            }))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        Ok(())
+    }
+}
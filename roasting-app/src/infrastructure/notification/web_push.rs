@@ -0,0 +1,374 @@
+use super::{http_client, NotificationChannel, Recipient};
+use crate::domain::Notification;
+use crate::infrastructure::db::PushSubscriptionRepository;
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+use ring::{aead, agreement, hkdf};
+use url::Url;
+
+/// Record size announced in the aes128gcm header; payloads stay far below it
+const RECORD_SIZE: u32 = 4096;
+/// How long a push service keeps an undelivered message
+const TTL_SECS: u32 = 24 * 3600;
+const VAPID_EXPIRY_SECS: i64 = 12 * 3600;
+/// Push services browsers hand out endpoints on (Chrome/Edge via FCM,
+/// Firefox, Safari, legacy Edge via WNS); a host matches itself or any
+/// subdomain
+const PUSH_SERVICE_HOSTS: &[&str] = &[
+    "fcm.googleapis.com",
+    "push.services.mozilla.com",
+    "push.apple.com",
+    "notify.windows.com",
+];
+
+/// Browser notifications through the Push API, delivered even with the tab
+/// closed. Messages are encrypted per RFC 8291 and authenticated with VAPID
+/// (RFC 8292) keys from VAPID_PUBLIC_KEY and VAPID_PRIVATE_KEY.
+pub struct WebPushChannel {
+    http_client: reqwest::Client,
+    repo: PushSubscriptionRepository,
+    key_pair: EcdsaKeyPair,
+    /// Base64url uncompressed P-256 point, handed to `pushManager.subscribe`
+    public_key: String,
+    /// Contact for push services: VAPID_SUBJECT, else the site URL
+    subject: String,
+    rng: SystemRandom,
+}
+
+enum PushError {
+    /// The subscription expired or was revoked and should be deleted
+    Gone,
+    Failed(String),
+}
+
+impl WebPushChannel {
+    /// None unless both VAPID keys are set; keys that don't form a valid
+    /// P-256 pair are logged and leave web push off
+    pub fn from_env(repo: PushSubscriptionRepository, public_base_url: &str) -> Option<Self> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let public_key = var("VAPID_PUBLIC_KEY")?;
+        let private_key = var("VAPID_PRIVATE_KEY")?;
+        let subject = var("VAPID_SUBJECT").unwrap_or_else(|| public_base_url.to_string());
+        match Self::new(repo, &public_key, &private_key, subject) {
+            Ok(channel) => Some(channel),
+            Err(e) => {
+                tracing::warn!("Web push disabled, invalid VAPID keys: {}", e);
+                None
+            }
+        }
+    }
+
+    fn new(
+        repo: PushSubscriptionRepository,
+        public_key: &str,
+        private_key: &str,
+        subject: String,
+    ) -> Result<Self, String> {
+        let rng = SystemRandom::new();
+        let key_pair = EcdsaKeyPair::from_private_key_and_public_key(
+            &ECDSA_P256_SHA256_FIXED_SIGNING,
+            &decode(private_key)?,
+            &decode(public_key)?,
+            &rng,
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(Self {
+            http_client: http_client(),
+            repo,
+            public_key: URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+            key_pair,
+            subject,
+            rng,
+        })
+    }
+
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// Reject subscriptions we couldn't encrypt for, and endpoints off the
+    /// known push services, since the server POSTs to them
+    pub fn check_subscription(endpoint: &str, p256dh: &str, auth: &str) -> Result<(), String> {
+        let url = Url::parse(endpoint).map_err(|_| "Invalid endpoint".to_string())?;
+        if !is_push_service(&url) || endpoint.len() > 1024 {
+            return Err("Endpoint must be an HTTPS push service URL".to_string());
+        }
+        if decode(p256dh).map(|k| k.len()) != Ok(65) {
+            return Err("Invalid p256dh key".to_string());
+        }
+        if decode(auth).map(|k| k.len()) != Ok(16) {
+            return Err("Invalid auth secret".to_string());
+        }
+        Ok(())
+    }
+
+    async fn push(&self, endpoint: &str, p256dh: &str, auth: &str, payload: &[u8]) -> Result<(), PushError> {
+        let failed = |e: String| PushError::Failed(e);
+        let url = Url::parse(endpoint).map_err(|e| failed(e.to_string()))?;
+        // Stored before the allowlist existed; never POST there, drop it
+        if !is_push_service(&url) {
+            return Err(PushError::Gone);
+        }
+        let body = encrypt(&self.rng, &decode(p256dh).map_err(failed)?, &decode(auth).map_err(failed)?, payload)
+            .map_err(|_| failed("encryption failed".to_string()))?;
+        let authorization = self.vapid_authorization(&url).map_err(failed)?;
+
+        let response = self
+            .http_client
+            .post(url)
+            .header("Authorization", authorization)
+            .header("Content-Encoding", "aes128gcm")
+            .header("Content-Type", "application/octet-stream")
+            .header("TTL", TTL_SECS.to_string())
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| failed(e.to_string()))?;
+        match response.status().as_u16() {
+            200..=299 => Ok(()),
+            404 | 410 => Err(PushError::Gone),
+            status => Err(failed(format!("HTTP {}", status))),
+        }
+    }
+
+    /// `vapid t=<ES256 JWT>, k=<public key>` scoped to the push service origin
+    fn vapid_authorization(&self, endpoint: &Url) -> Result<String, String> {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ES256"}"#);
+        let claims = URL_SAFE_NO_PAD.encode(
+            serde_json::json!({
+                "aud": endpoint.origin().ascii_serialization(),
+                "exp": chrono::Utc::now().timestamp() + VAPID_EXPIRY_SECS,
+                "sub": self.subject,
+            })
+            .to_string(),
+        );
+        let signing_input = format!("{}.{}", header, claims);
+        let signature = self
+            .key_pair
+            .sign(&self.rng, signing_input.as_bytes())
+            .map_err(|e| e.to_string())?;
+        Ok(format!(
+            "vapid t={}.{}, k={}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature.as_ref()),
+            self.public_key
+        ))
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for WebPushChannel {
+    fn name(&self) -> &'static str {
+        "web_push"
+    }
+
+    /// Subscribing in the browser is the opt-in
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    async fn send(&self, recipient: &Recipient, notification: &Notification) -> Result<(), String> {
+        let subscriptions = self
+            .repo
+            .list_for_user(recipient.user_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        // Read by public/sw.js
+        let payload = serde_json::json!({
+            "title": notification.title,
+            "body": notification.body,
+            "url": notification.link,
+        })
+        .to_string();
+
+        let mut errors = Vec::new();
+        for subscription in subscriptions {
+            match self
+                .push(&subscription.endpoint, &subscription.p256dh, &subscription.auth, payload.as_bytes())
+                .await
+            {
+                Ok(()) => {}
+                Err(PushError::Gone) => {
+                    if let Err(e) = self.repo.delete_endpoint(&subscription.endpoint).await {
+                        tracing::warn!("Failed to delete expired push subscription: {}", e);
+                    }
+                }
+                Err(PushError::Failed(e)) => errors.push(e),
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+/// Base64url with or without padding, as browsers and key generators differ
+/// HTTPS on one of PUSH_SERVICE_HOSTS, default port
+fn is_push_service(url: &Url) -> bool {
+    let Some(url::Host::Domain(host)) = url.host() else {
+        return false;
+    };
+    let host = host.to_ascii_lowercase();
+    url.scheme() == "https"
+        && url.port().is_none()
+        && PUSH_SERVICE_HOSTS
+            .iter()
+            .any(|service| host == *service || host.ends_with(&format!(".{}", service)))
+}
+
+fn decode(value: &str) -> Result<Vec<u8>, String> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim().trim_end_matches('='))
+        .map_err(|e| e.to_string())
+}
+
+/// RFC 8291 `aes128gcm` body for one subscription: a fresh ECDH key and salt
+/// per message, keyed with the subscription's p256dh key and auth secret
+fn encrypt(
+    rng: &dyn SecureRandom,
+    ua_public: &[u8],
+    auth_secret: &[u8],
+    payload: &[u8],
+) -> Result<Vec<u8>, ring::error::Unspecified> {
+    let as_private = agreement::EphemeralPrivateKey::generate(&agreement::ECDH_P256, rng)?;
+    let as_public = as_private.compute_public_key()?;
+    let ecdh_secret = agreement::agree_ephemeral(
+        as_private,
+        &agreement::UnparsedPublicKey::new(&agreement::ECDH_P256, ua_public),
+        |secret| secret.to_vec(),
+    )?;
+
+    let mut salt = [0u8; 16];
+    rng.fill(&mut salt)?;
+    let (cek, nonce) = content_keys(&ecdh_secret, auth_secret, ua_public, as_public.as_ref(), &salt)?;
+
+    // A single record, so the padding delimiter is 0x02
+    let mut record = payload.to_vec();
+    record.push(2);
+    aead::LessSafeKey::new(aead::UnboundKey::new(&aead::AES_128_GCM, &cek)?).seal_in_place_append_tag(
+        aead::Nonce::try_assume_unique_for_key(&nonce)?,
+        aead::Aad::empty(),
+        &mut record,
+    )?;
+
+    let mut body = Vec::with_capacity(21 + as_public.as_ref().len() + record.len());
+    body.extend_from_slice(&salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(as_public.as_ref().len() as u8);
+    body.extend_from_slice(as_public.as_ref());
+    body.extend_from_slice(&record);
+    Ok(body)
+}
+
+/// Content encryption key and nonce derived from the shared secret
+fn content_keys(
+    ecdh_secret: &[u8],
+    auth_secret: &[u8],
+    ua_public: &[u8],
+    as_public: &[u8],
+    salt: &[u8],
+) -> Result<([u8; 16], [u8; 12]), ring::error::Unspecified> {
+    let key_info = [b"WebPush: info\0".as_slice(), ua_public, as_public].concat();
+    let mut ikm = [0u8; 32];
+    hkdf_expand(auth_secret, ecdh_secret, &key_info, &mut ikm)?;
+    let mut cek = [0u8; 16];
+    hkdf_expand(salt, &ikm, b"Content-Encoding: aes128gcm\0", &mut cek)?;
+    let mut nonce = [0u8; 12];
+    hkdf_expand(salt, &ikm, b"Content-Encoding: nonce\0", &mut nonce)?;
+    Ok((cek, nonce))
+}
+
+struct OutputLen(usize);
+
+impl hkdf::KeyType for OutputLen {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+fn hkdf_expand(salt: &[u8], ikm: &[u8], info: &[u8], out: &mut [u8]) -> Result<(), ring::error::Unspecified> {
+    hkdf::Salt::new(hkdf::HKDF_SHA256, salt)
+        .extract(ikm)
+        .expand(&[info], OutputLen(out.len()))?
+        .fill(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_FIXED};
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let rng = SystemRandom::new();
+        let ua_private = agreement::EphemeralPrivateKey::generate(&agreement::ECDH_P256, &rng).unwrap();
+        let ua_public = ua_private.compute_public_key().unwrap();
+        let auth = [7u8; 16];
+
+        let body = encrypt(&rng, ua_public.as_ref(), &auth, b"{\"title\":\"hi\"}").unwrap();
+        let salt = &body[..16];
+        assert_eq!(&body[16..20], &RECORD_SIZE.to_be_bytes());
+        let key_len = body[20] as usize;
+        let as_public = &body[21..21 + key_len];
+        let mut record = body[21 + key_len..].to_vec();
+
+        let secret = agreement::agree_ephemeral(
+            ua_private,
+            &agreement::UnparsedPublicKey::new(&agreement::ECDH_P256, as_public),
+            |s| s.to_vec(),
+        )
+        .unwrap();
+        let (cek, nonce) = content_keys(&secret, &auth, ua_public.as_ref(), as_public, salt).unwrap();
+        let plain = aead::LessSafeKey::new(aead::UnboundKey::new(&aead::AES_128_GCM, &cek).unwrap())
+            .open_in_place(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::empty(),
+                &mut record,
+            )
+            .unwrap();
+        assert_eq!(plain, b"{\"title\":\"hi\"}\x02");
+    }
+
+    #[test]
+    fn test_vapid_signature_and_subscription_checks() {
+        // Key pair from RFC 8291 appendix A
+        let public_key = "BP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A8";
+        let rng = SystemRandom::new();
+        let key_pair = EcdsaKeyPair::from_private_key_and_public_key(
+            &ECDSA_P256_SHA256_FIXED_SIGNING,
+            &decode("yfWPiYE-n46HLnH0KqZOF1fJJU3MYrct3AELtAQ-oRw").unwrap(),
+            &decode(public_key).unwrap(),
+            &rng,
+        )
+        .unwrap();
+        let signature = key_pair.sign(&rng, b"header.claims").unwrap();
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, decode(public_key).unwrap())
+            .verify(b"header.claims", signature.as_ref())
+            .unwrap();
+
+        let auth = "BTBZMqHH6r4Tts7J_aSIgg";
+        let endpoint = "https://fcm.googleapis.com/fcm/send/abc";
+        assert!(WebPushChannel::check_subscription(endpoint, public_key, auth).is_ok());
+        assert!(WebPushChannel::check_subscription("http://fcm.googleapis.com/x", public_key, auth).is_err());
+        assert!(WebPushChannel::check_subscription("https://10.0.0.1/x", public_key, auth).is_err());
+        assert!(WebPushChannel::check_subscription("https://metadata.internal/x", public_key, auth).is_err());
+        assert!(WebPushChannel::check_subscription("https://fcm.googleapis.com.evil.id/x", public_key, auth).is_err());
+        assert!(WebPushChannel::check_subscription(
+            "https://updates.push.services.mozilla.com/wpush/v2/abc",
+            public_key,
+            auth
+        )
+        .is_ok());
+        assert!(WebPushChannel::check_subscription(endpoint, auth, auth).is_err());
+    }
+}
@@ -1,28 +1,181 @@
-use super::prompt::build_roast_prompt;
+use super::prompt::{
+    build_deck_prompt, build_design_prompt, build_fact_sheet_prompt, build_follow_up_prompt, build_moderation_prompt,
+    build_roast_prompt, build_summary_prompt,
+};
 use super::types::{ChatCompletionRequest, ChatCompletionResponse};
-use crate::domain::StartupInfo;
+use crate::domain::{FactSheet, Language, PitchDeck, RoastLength, RoastOptions, StartupInfo};
+use crate::infrastructure::prompt_templates::PromptTemplates;
+use crate::infrastructure::security::{CostTracker, TokenUsage};
 use roasting_errors::AppError;
+use std::sync::Arc;
 
 const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
+const OPENROUTER_HOST: &str = "openrouter.ai";
 const MODEL: &str = "deepseek/deepseek-chat";
+const SUMMARY_MAX_TOKENS: u32 = 400;
+const FACT_SHEET_MAX_TOKENS: u32 = 300;
+const DESIGN_MAX_TOKENS: u32 = 512;
+const FOLLOW_UP_MAX_TOKENS: u32 = 512;
+const MODERATION_MAX_TOKENS: u32 = 5;
 
 pub struct OpenRouterClient {
     http_client: reqwest::Client,
     api_key: String,
+    templates: Arc<PromptTemplates>,
+    cost_tracker: Arc<CostTracker>,
+    /// Multimodal model for the design roast, from OPENROUTER_VISION_MODEL;
+    /// `None` leaves the design section to the text-only roast
+    vision_model: Option<String>,
 }
 
 impl OpenRouterClient {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(
+        api_key: String,
+        templates: Arc<PromptTemplates>,
+        cost_tracker: Arc<CostTracker>,
+    ) -> Self {
         Self {
             http_client: reqwest::Client::new(),
             api_key,
+            templates,
+            cost_tracker,
+            vision_model: std::env::var("OPENROUTER_VISION_MODEL")
+                .ok()
+                .map(|m| m.trim().to_string())
+                .filter(|m| !m.is_empty()),
         }
     }
 
-    pub async fn generate_roast(&self, startup_info: &StartupInfo) -> Result<String, AppError> {
-        let prompt = build_roast_prompt(startup_info);
-        let request = ChatCompletionRequest::new(MODEL, prompt);
+    pub fn vision_model(&self) -> Option<&str> {
+        self.vision_model.as_deref()
+    }
+
+    /// PROMPT_VARIANT the roast templates are rendered with
+    pub fn prompt_variant(&self) -> Option<&str> {
+        self.templates.variant()
+    }
+
+    /// Resolve the API host and leave an open TLS connection in the client's
+    /// pool, so the first roast skips the lookup and handshake. Returns the
+    /// number of addresses resolved.
+    pub async fn preconnect(&self) -> Result<usize, AppError> {
+        let addresses = tokio::net::lookup_host((OPENROUTER_HOST, 443))
+            .await
+            .map_err(|e| AppError::LlmError(format!("DNS lookup failed: {}", e)))?
+            .count();
+        // Any response keeps the connection pooled; the status doesn't matter
+        self.http_client
+            .head(OPENROUTER_API_URL)
+            .send()
+            .await
+            .map_err(|e| AppError::LlmError(e.to_string()))?;
+        Ok(addresses)
+    }
+
+    /// Render the roast prompt for every language and length once, so a
+    /// broken template shows up at startup. Returns the number rendered.
+    pub fn prime_prompts(&self) -> usize {
+        let startup_info = StartupInfo::new("https://example.com".to_string())
+            .with_title(Some("Example".to_string()));
+        let mut rendered = 0;
+        for language in Language::all() {
+            for length in RoastLength::all() {
+                let options = RoastOptions::new(*language).with_length(*length);
+                if !build_roast_prompt(&startup_info, &options, &self.templates).is_empty() {
+                    rendered += 1;
+                }
+            }
+        }
+        rendered
+    }
+
+    pub async fn generate_roast(
+        &self,
+        startup_info: &StartupInfo,
+        options: &RoastOptions,
+    ) -> Result<String, AppError> {
+        let prompt = build_roast_prompt(startup_info, options, &self.templates);
+        let request =
+            ChatCompletionRequest::new(MODEL, prompt).with_max_tokens(max_tokens(options.length));
+        self.complete(&request).await
+    }
+
+    pub async fn generate_deck_roast(&self, deck: &PitchDeck, options: &RoastOptions) -> Result<String, AppError> {
+        let prompt = build_deck_prompt(deck, options, &self.templates);
+        let request =
+            ChatCompletionRequest::new(MODEL, prompt).with_max_tokens(max_tokens(options.length));
+        self.complete(&request).await
+    }
 
+    /// Condense scraped content into a short list of the startup's key claims
+    pub async fn summarize(
+        &self,
+        startup_info: &StartupInfo,
+        language: Language,
+    ) -> Result<String, AppError> {
+        let prompt = build_summary_prompt(startup_info, language);
+        let request = ChatCompletionRequest::new(MODEL, prompt)
+            .with_max_tokens(SUMMARY_MAX_TOKENS)
+            .with_temperature(0.2);
+        self.complete(&request).await
+    }
+
+    /// Pull the startup's own claims into a fact sheet; `None` when the reply
+    /// had no usable JSON
+    pub async fn extract_facts(
+        &self,
+        startup_info: &StartupInfo,
+        language: Language,
+    ) -> Result<Option<FactSheet>, AppError> {
+        let prompt = build_fact_sheet_prompt(startup_info, language);
+        let request = ChatCompletionRequest::new(MODEL, prompt)
+            .with_max_tokens(FACT_SHEET_MAX_TOKENS)
+            .with_temperature(0.0);
+        Ok(FactSheet::parse(&self.complete(&request).await?))
+    }
+
+    /// Roast the landing page design from its screenshot. Errors when no
+    /// vision model is configured.
+    pub async fn roast_design(
+        &self,
+        startup_name: &str,
+        screenshot: &[u8],
+        options: &RoastOptions,
+    ) -> Result<String, AppError> {
+        let model = self
+            .vision_model
+            .as_deref()
+            .ok_or_else(|| AppError::OpenRouterError("No vision model configured".to_string()))?;
+        let request = ChatCompletionRequest::new(model, build_design_prompt(startup_name, options))
+            .with_image("image/webp", screenshot)
+            .with_max_tokens(DESIGN_MAX_TOKENS);
+        self.complete(&request).await
+    }
+
+    /// Answer a follow-up question about a roast that was already written
+    pub async fn follow_up(
+        &self,
+        startup_name: &str,
+        roast_text: &str,
+        question: &str,
+        options: &RoastOptions,
+    ) -> Result<String, AppError> {
+        let prompt = build_follow_up_prompt(startup_name, roast_text, question, options);
+        let request =
+            ChatCompletionRequest::new(MODEL, prompt).with_max_tokens(FOLLOW_UP_MAX_TOKENS);
+        self.complete(&request).await
+    }
+
+    /// Secondary moderation pass; true when the model judges the text safe
+    pub async fn classify_safe(&self, text: &str) -> Result<bool, AppError> {
+        let request = ChatCompletionRequest::new(MODEL, build_moderation_prompt(text))
+            .with_max_tokens(MODERATION_MAX_TOKENS)
+            .with_temperature(0.0);
+        let verdict = self.complete(&request).await?;
+        Ok(!verdict.trim().to_uppercase().starts_with("UNSAFE"))
+    }
+
+    async fn complete(&self, request: &ChatCompletionRequest) -> Result<String, AppError> {
         let response = self
             .http_client
             .post(OPENROUTER_API_URL)
@@ -30,7 +183,7 @@ impl OpenRouterClient {
             .header("Content-Type", "application/json")
             .header("HTTP-Referer", "https://roasting-startup.local")
             .header("X-Title", "Roasting Startup Indonesia")
-            .json(&request)
+            .json(request)
             .send()
             .await
             .map_err(|e| AppError::OpenRouterError(e.to_string()))?;
@@ -50,6 +203,18 @@ impl OpenRouterClient {
             .await
             .map_err(|e| AppError::OpenRouterError(e.to_string()))?;
 
+        match &completion.usage {
+            Some(usage) => self.cost_tracker.record_usage(
+                completion.model.as_deref().unwrap_or(&request.model),
+                &request.model,
+                TokenUsage {
+                    prompt_tokens: usage.prompt_tokens,
+                    completion_tokens: usage.completion_tokens,
+                },
+            ),
+            None => tracing::warn!("OpenRouter response had no usage block"),
+        }
+
         completion
             .choices
             .first()
@@ -57,3 +222,12 @@ impl OpenRouterClient {
             .ok_or_else(|| AppError::OpenRouterError("No response from AI".to_string()))
     }
 }
+
+/// Completion budget per length; leaves headroom for the JSON wrapper
+fn max_tokens(length: RoastLength) -> u32 {
+    match length {
+        RoastLength::Singkat => 1024,
+        RoastLength::Standar => 2048,
+        RoastLength::Panjang => 3072,
+    }
+}
@@ -0,0 +1,68 @@
+use crate::domain::Dialect;
+
+/// Prompt fragment and few-shot bank for one regional slang style
+pub struct DialectStyle {
+    pub system_fragment: &'static str,
+    pub examples: &'static [&'static str],
+}
+
+const JAKARTA: DialectStyle = DialectStyle {
+    system_fragment: "Gunakan bahasa Indonesia gaul Jakarta (gue, lo, anjir, sumpah, kocak).",
+    examples: &[
+        "Gue liat landing page lo, sumpah kayak template gratisan yang lupa diganti. Lo yakin ini startup, bukan tugas kuliah yang kelewat deadline?",
+        "Anjir, tagline-nya \"revolusi industri\", padahal fiturnya cuma form kontak. Revolusi dari mana, bos?",
+    ],
+};
+
+const JAKSEL: DialectStyle = DialectStyle {
+    system_fragment: "Gunakan gaya anak Jakarta Selatan: campur Indonesia-Inggris (which is, literally, basically, honestly, kinda), sok artsy dan sok global.",
+    examples: &[
+        "Honestly, value proposition lo tuh kinda confusing, which is literally red flag buat investor. Like, lo jualan apa sih sebenernya?",
+        "Basically ini cuma spreadsheet yang dikasih gradient, which is fine, tapi jangan ngaku disrupting the industry dong, bestie.",
+    ],
+};
+
+const JAWA: DialectStyle = DialectStyle {
+    system_fragment: "Gunakan bahasa Indonesia dengan logat dan selipan kata Jawa (ndak, lho, wes, piye, ora, mas/mbak, kok iso).",
+    examples: &[
+        "Piye to mas, website-e loading-e suwe tenan, aku wes sempet masak mie sak panci. Kok iso ngaku \"super cepat\"?",
+        "Wes ta lah, fitur-e ndak ono sing jelas. Iki startup opo arisan RT sing diwenehi domain .io?",
+    ],
+};
+
+const MEDAN: DialectStyle = DialectStyle {
+    system_fragment: "Gunakan logat Medan yang ceplas-ceplos dan keras (kau, bah, kek mana, pigi, cemana, macam, lae).",
+    examples: &[
+        "Bah! Kek mana pulak kau bilang ini aplikasi nomor satu, lae? Download-nya aja macam antrian sembako.",
+        "Cemana kau mau dapat investor kalo halaman pricing-mu kosong macam dompet tanggal tua?",
+    ],
+};
+
+const SUNDA: DialectStyle = DialectStyle {
+    system_fragment: "Gunakan bahasa Indonesia dengan selipan kata Sunda yang halus tapi nyelekit (euy, mah, atuh, teh, meni, pisan, kumaha).",
+    examples: &[
+        "Aduh euy, website-na meni rame pisan animasina, tapi produkna mah teu aya. Kumaha atuh ieu teh?",
+        "Startup teh ngaku AI, ari dibuka mah ngan chatbot nu jawabna \"mohon maaf\" wae. Lieur pisan, Kang.",
+    ],
+};
+
+pub fn dialect_style(dialect: Dialect) -> &'static DialectStyle {
+    match dialect {
+        Dialect::Jakarta => &JAKARTA,
+        Dialect::Jaksel => &JAKSEL,
+        Dialect::Jawa => &JAWA,
+        Dialect::Medan => &MEDAN,
+        Dialect::Sunda => &SUNDA,
+    }
+}
+
+impl DialectStyle {
+    /// Few-shot examples rendered as a bulleted prompt block
+    pub fn examples_block(&self) -> String {
+        self.examples
+            .iter()
+            .map(|e| format!("- \"{}\"", e))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
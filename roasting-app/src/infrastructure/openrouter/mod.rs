@@ -1,5 +1,11 @@
 mod client;
+mod dialect;
 mod prompt;
 mod types;
 
 pub use client::OpenRouterClient;
+pub use dialect::{dialect_style, DialectStyle};
+pub use prompt::{
+    ai_wrapper_hint, archive_hint, build_follow_up_prompt, dns_tls_hint, domain_age_hint, funding_hint, pages_hint,
+    performance_hint, profanity_rule, security_hint, seo_hint, slides_hint, social_links_hint, structured_data_hint, tech_stack_hint,
+};
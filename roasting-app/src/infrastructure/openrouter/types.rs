@@ -1,3 +1,5 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize)]
@@ -11,12 +13,44 @@ pub struct ChatCompletionRequest {
 #[derive(Debug, Serialize)]
 pub struct Message {
     pub role: String,
-    pub content: String,
+    pub content: MessageBody,
+}
+
+/// Plain text, or text plus images for multimodal models
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum MessageBody {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImageUrl {
+    /// `https://` or a `data:` URL
+    pub url: String,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ChatCompletionResponse {
     pub choices: Vec<Choice>,
+    /// Model that actually served the request, which can differ from the one asked for
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Usage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -35,10 +69,37 @@ impl ChatCompletionRequest {
             model: model.to_string(),
             messages: vec![Message {
                 role: "user".to_string(),
-                content: prompt,
+                content: MessageBody::Text(prompt),
             }],
             max_tokens: 2048,
             temperature: 0.9,
         }
     }
+
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Attach an image to the prompt as a base64 `data:` URL
+    pub fn with_image(mut self, content_type: &str, bytes: &[u8]) -> Self {
+        if let Some(message) = self.messages.last_mut() {
+            let text = match std::mem::replace(&mut message.content, MessageBody::Parts(Vec::new())) {
+                MessageBody::Text(text) => vec![ContentPart::Text { text }],
+                MessageBody::Parts(parts) => parts,
+            };
+            let image = ContentPart::ImageUrl {
+                image_url: ImageUrl {
+                    url: format!("data:{};base64,{}", content_type, STANDARD.encode(bytes)),
+                },
+            };
+            message.content = MessageBody::Parts(text.into_iter().chain([image]).collect());
+        }
+        self
+    }
 }
@@ -0,0 +1,43 @@
+/// Incoming webhook for operator notices (OPS_WEBHOOK_URL). The payload
+/// carries both `text` (Slack, Mattermost) and `content` (Discord).
+pub struct OpsChannel {
+    http_client: reqwest::Client,
+    webhook_url: Option<String>,
+}
+
+impl OpsChannel {
+    pub fn from_env() -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .expect("Failed to create HTTP client"),
+            webhook_url: std::env::var("OPS_WEBHOOK_URL")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.webhook_url.is_some()
+    }
+
+    /// Post `message`; does nothing when no webhook is configured
+    pub async fn post(&self, message: &str) -> Result<(), String> {
+        let Some(url) = &self.webhook_url else {
+            return Ok(());
+        };
+        let response = self
+            .http_client
+            .post(url)
+            .json(&serde_json::json!({ "text": message, "content": message }))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        Ok(())
+    }
+}
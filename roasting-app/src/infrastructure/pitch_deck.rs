@@ -0,0 +1,113 @@
+use crate::domain::PitchDeck;
+use roasting_errors::AppError;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// Largest upload accepted; decks heavier than this are mostly images anyway
+pub const MAX_DECK_BYTES: usize = 10 * 1024 * 1024;
+/// Slides past this are appendix material nobody reads
+const MAX_SLIDES: usize = 40;
+const MAX_SLIDE_CHARS: usize = 1500;
+const MAX_FILE_NAME_CHARS: usize = 100;
+/// A parse still running after this is abandoned; the blocking thread
+/// finishes on its own but the request stops waiting for it
+const EXTRACT_TIMEOUT: Duration = Duration::from_secs(20);
+/// Parses running at once, abandoned ones included, so crafted PDFs can't
+/// take over the blocking pool
+const MAX_CONCURRENT_EXTRACTIONS: usize = 2;
+
+static EXTRACTIONS: LazyLock<Arc<Semaphore>> =
+    LazyLock::new(|| Arc::new(Semaphore::new(MAX_CONCURRENT_EXTRACTIONS)));
+
+/// Pull the text of each slide out of an uploaded PDF. Fails for anything
+/// that isn't a PDF, is encrypted or broken, or has no text layer at all
+/// (a deck exported as images).
+pub async fn extract_deck(file_name: &str, bytes: Vec<u8>) -> Result<PitchDeck, AppError> {
+    if bytes.len() > MAX_DECK_BYTES {
+        return Err(AppError::InvalidDeck(format!(
+            "file lebih dari {} MB",
+            MAX_DECK_BYTES / (1024 * 1024)
+        )));
+    }
+    if !bytes.starts_with(b"%PDF-") {
+        return Err(AppError::InvalidDeck("bukan file PDF".to_string()));
+    }
+
+    let busy = || AppError::QueueFull("Lagi banyak deck yang dibaca, coba lagi sebentar lagi.".to_string());
+    let permit = tokio::time::timeout(EXTRACT_TIMEOUT, EXTRACTIONS.clone().acquire_owned())
+        .await
+        .map_err(|_| busy())?
+        .map_err(|_| busy())?;
+    let task = tokio::task::spawn_blocking(move || {
+        // Held until the parse really ends, not just until we stop waiting
+        let _permit = permit;
+        // pdf-extract panics on some malformed files instead of returning an error
+        std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem_by_pages(&bytes))
+    });
+    let pages = match tokio::time::timeout(EXTRACT_TIMEOUT, task).await {
+        Ok(Ok(Ok(Ok(pages)))) => pages,
+        Ok(Ok(Ok(Err(e)))) => return Err(AppError::InvalidDeck(e.to_string())),
+        Ok(Ok(Err(_))) => return Err(AppError::InvalidDeck("PDF rusak".to_string())),
+        Ok(Err(e)) => return Err(AppError::Internal(format!("Deck extraction task failed: {}", e))),
+        Err(_) => return Err(AppError::InvalidDeck("PDF terlalu lama dibaca".to_string())),
+    };
+
+    let slides = slides_from_pages(pages);
+    if slides.is_empty() {
+        return Err(AppError::InvalidDeck("tidak ada teks, sepertinya hasil scan".to_string()));
+    }
+    let file_name = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(MAX_FILE_NAME_CHARS)
+        .collect();
+    Ok(PitchDeck { file_name, slides })
+}
+
+/// Trimmed, non-empty lines per page; pages without text are dropped
+fn slides_from_pages(pages: Vec<String>) -> Vec<String> {
+    pages
+        .iter()
+        .map(|page| {
+            let text = page
+                .lines()
+                .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+            text.chars().take(MAX_SLIDE_CHARS).collect::<String>()
+        })
+        .filter(|slide| !slide.is_empty())
+        .take(MAX_SLIDES)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_slides_and_rejected_uploads() {
+        let pages = vec![
+            "  Kecil Tech \n\n  Kasir   untuk warung ".to_string(),
+            " \n ".to_string(),
+            "Market size: $10B".to_string(),
+        ];
+        let deck = PitchDeck {
+            file_name: "Kecil Tech Seed (v3).pdf".to_string(),
+            slides: slides_from_pages(pages),
+        };
+        assert_eq!(deck.slides, vec!["Kecil Tech\nKasir untuk warung", "Market size: $10B"]);
+        assert_eq!(deck.startup_name(), "Kecil Tech");
+        assert_eq!(deck.source_url(), "deck:kecil-tech-seed-v3");
+        assert!(PitchDeck::is_deck_url(&deck.source_url()));
+
+        let not_pdf = extract_deck("deck.pdf", b"<html>".to_vec()).await;
+        assert!(matches!(not_pdf, Err(AppError::InvalidDeck(_))));
+        let broken = extract_deck("deck.pdf", b"%PDF-1.4\ngarbage".to_vec()).await;
+        assert!(matches!(broken, Err(AppError::InvalidDeck(_))));
+    }
+}
@@ -0,0 +1,48 @@
+/// Render a handlebars-style template, replacing `{{ name }}` placeholders.
+///
+/// Unknown placeholders render as empty strings and are logged, so a typo in
+/// a template degrades the prompt instead of failing the roast.
+pub fn render_template(template: &str, vars: &[(&str, &str)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+
+        match after.find("}}") {
+            Some(end) => {
+                let name = after[..end].trim();
+                match vars.iter().find(|(key, _)| *key == name) {
+                    Some((_, value)) => output.push_str(value),
+                    None => tracing::warn!("Unknown prompt template variable: {}", name),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_replaces_variables() {
+        let rendered = render_template("Halo {{name}}, dari {{ city }}!", &[("name", "Budi"), ("city", "Medan")]);
+        assert_eq!(rendered, "Halo Budi, dari Medan!");
+    }
+
+    #[test]
+    fn test_render_unknown_and_unclosed() {
+        assert_eq!(render_template("a{{missing}}b", &[]), "ab");
+        assert_eq!(render_template("a{{open", &[]), "a{{open");
+    }
+}
@@ -0,0 +1,5 @@
+mod engine;
+mod store;
+
+pub use engine::render_template;
+pub use store::PromptTemplates;
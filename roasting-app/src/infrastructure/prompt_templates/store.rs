@@ -0,0 +1,97 @@
+use super::engine::render_template;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+const TEMPLATE_EXTENSION: &str = "txt";
+
+/// Prompt templates loaded from a directory, keyed by file stem.
+///
+/// Variants use a dotted suffix: `roast_id.txt` is the base template and
+/// `roast_id.pedas.txt` is its "pedas" variant. Templates can be reloaded at
+/// runtime without restarting the server.
+pub struct PromptTemplates {
+    dir: PathBuf,
+    variant: Option<String>,
+    templates: RwLock<HashMap<String, String>>,
+}
+
+impl PromptTemplates {
+    pub fn new(dir: impl Into<PathBuf>, variant: Option<String>) -> Self {
+        let store = Self {
+            dir: dir.into(),
+            variant,
+            templates: RwLock::new(HashMap::new()),
+        };
+        if let Err(e) = store.reload() {
+            tracing::warn!(
+                "No prompt templates loaded from {}: {} (using built-in prompts)",
+                store.dir.display(),
+                e
+            );
+        }
+        store
+    }
+
+    pub fn from_env() -> Self {
+        let dir = std::env::var("PROMPT_TEMPLATES_DIR").unwrap_or_else(|_| "prompts".to_string());
+        let variant = std::env::var("PROMPT_VARIANT").ok().filter(|v| !v.is_empty());
+        Self::new(dir, variant)
+    }
+
+    /// Re-read every template file from disk, returning the loaded names
+    pub fn reload(&self) -> Result<Vec<String>, std::io::Error> {
+        let loaded = Self::read_dir(&self.dir)?;
+        let mut names: Vec<String> = loaded.keys().cloned().collect();
+        names.sort();
+
+        *self.templates.write().unwrap() = loaded;
+        tracing::info!(
+            "Loaded {} prompt templates from {}",
+            names.len(),
+            self.dir.display()
+        );
+        Ok(names)
+    }
+
+    fn read_dir(dir: &Path) -> Result<HashMap<String, String>, std::io::Error> {
+        let mut templates = HashMap::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(TEMPLATE_EXTENSION) {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                templates.insert(name.to_string(), std::fs::read_to_string(&path)?);
+            }
+        }
+        Ok(templates)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.templates.read().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_deref()
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Render `name`, preferring the configured variant when it exists
+    pub fn render(&self, name: &str, vars: &[(&str, &str)]) -> Option<String> {
+        let templates = self.templates.read().unwrap();
+        let variant_template = self
+            .variant
+            .as_ref()
+            .and_then(|v| templates.get(&format!("{}.{}", name, v)));
+
+        variant_template
+            .or_else(|| templates.get(name))
+            .map(|template| render_template(template, vars))
+    }
+}
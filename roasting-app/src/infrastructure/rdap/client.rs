@@ -0,0 +1,257 @@
+use crate::domain::DomainInfo;
+use dashmap::DashMap;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Bootstrap service that redirects to the right registry's RDAP server
+const RDAP_BASE_URL: &str = "https://rdap.org/domain";
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+/// Longest a roast waits on the lookup; a slower one still finishes in the
+/// background and fills the cache for the next roast of the domain
+const WAIT_BUDGET: Duration = Duration::from_secs(1);
+/// Registration dates don't change, so answers are kept for a day
+const CACHE_TTL: Duration = Duration::from_secs(24 * 3600);
+/// Failed lookups are retried sooner in case the registry was just slow
+const MISS_CACHE_TTL: Duration = Duration::from_secs(3600);
+/// Expired answers are swept once the cache grows this large
+const MAX_CACHE_ENTRIES: usize = 10_000;
+
+/// Second-level labels under which registrations happen one level deeper,
+/// e.g. `example.co.id`
+const SECOND_LEVEL_SUFFIXES: &[&str] = &[
+    "co", "ac", "or", "go", "web", "sch", "my", "biz", "ponpes", "desa", "com", "net", "org",
+    "gov", "edu",
+];
+
+#[derive(Deserialize)]
+struct RdapDomain {
+    #[serde(default)]
+    events: Vec<RdapEvent>,
+    #[serde(default)]
+    entities: Vec<RdapEntity>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RdapEvent {
+    event_action: String,
+    event_date: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RdapEntity {
+    #[serde(default)]
+    roles: Vec<String>,
+    /// jCard: `["vcard", [["fn", {}, "text", "Name"], ...]]`
+    #[serde(default)]
+    vcard_array: Option<serde_json::Value>,
+}
+
+/// Cached answer per registrable domain, `None` for a failed lookup
+type LookupCache = DashMap<String, (Instant, Option<DomainInfo>)>;
+
+/// Best-effort domain age lookup; every failure just means no `DomainInfo`
+pub struct RdapClient {
+    http_client: reqwest::Client,
+    enabled: bool,
+    cache: Arc<LookupCache>,
+}
+
+impl RdapClient {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(LOOKUP_TIMEOUT)
+                .build()
+                .expect("Failed to create HTTP client"),
+            enabled,
+            cache: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Enabled unless RDAP_LOOKUP is `false` or `0`
+    pub fn from_env() -> Self {
+        let enabled = std::env::var("RDAP_LOOKUP")
+            .map(|v| !matches!(v.trim(), "false" | "0"))
+            .unwrap_or(true);
+        Self::new(enabled)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Answers from the cache, otherwise waits at most `WAIT_BUDGET` for
+    /// the registry
+    pub async fn lookup(&self, url: &str) -> Option<DomainInfo> {
+        if !self.enabled {
+            return None;
+        }
+
+        let host = url::Url::parse(url).ok()?.host_str()?.to_lowercase();
+        let domain = registrable_domain(&host)?;
+
+        if let Some(entry) = self.cache.get(&domain) {
+            let (fetched, info) = entry.value();
+            if is_fresh(*fetched, info) {
+                return info.clone();
+            }
+        }
+
+        let http_client = self.http_client.clone();
+        let cache = self.cache.clone();
+        let task = tokio::spawn(async move {
+            let info = fetch_domain_info(&http_client, &domain).await;
+            if cache.len() >= MAX_CACHE_ENTRIES {
+                cache.retain(|_, (fetched, info)| is_fresh(*fetched, info));
+            }
+            cache.insert(domain, (Instant::now(), info.clone()));
+            info
+        });
+        match tokio::time::timeout(WAIT_BUDGET, task).await {
+            Ok(Ok(info)) => info,
+            Ok(Err(e)) => {
+                tracing::debug!("RDAP lookup task failed: {}", e);
+                None
+            }
+            Err(_) => {
+                tracing::debug!("RDAP lookup for {} still running, roasting without it", host);
+                None
+            }
+        }
+    }
+
+}
+
+fn is_fresh(fetched: Instant, info: &Option<DomainInfo>) -> bool {
+    fetched.elapsed() < if info.is_some() { CACHE_TTL } else { MISS_CACHE_TTL }
+}
+
+async fn fetch_domain_info(http_client: &reqwest::Client, domain: &str) -> Option<DomainInfo> {
+    let response = match http_client
+        .get(format!("{}/{}", RDAP_BASE_URL, domain))
+        .header("Accept", "application/rdap+json")
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status().is_success() => resp,
+        Ok(resp) => {
+            tracing::debug!("RDAP lookup for {} returned {}", domain, resp.status());
+            return None;
+        }
+        Err(e) => {
+            tracing::debug!("RDAP lookup for {} failed: {}", domain, e);
+            return None;
+        }
+    };
+
+    let body: RdapDomain = response.json().await.ok()?;
+    Some(parse_domain_info(domain.to_string(), body))
+}
+
+impl Default for RdapClient {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+fn parse_domain_info(domain: String, body: RdapDomain) -> DomainInfo {
+    let registered_at = body
+        .events
+        .iter()
+        .find(|e| e.event_action == "registration")
+        .and_then(|e| chrono::DateTime::parse_from_rfc3339(&e.event_date).ok())
+        .map(|d| d.with_timezone(&chrono::Utc));
+
+    let registrar = body
+        .entities
+        .iter()
+        .find(|e| e.roles.iter().any(|r| r == "registrar"))
+        .and_then(|e| e.vcard_array.as_ref())
+        .and_then(|v| v.get(1)?.as_array().cloned())
+        .and_then(|props| {
+            props.iter().find_map(|p| {
+                let p = p.as_array()?;
+                (p.first()?.as_str()? == "fn").then(|| p.get(3)?.as_str().map(str::to_string))?
+            })
+        });
+
+    DomainInfo {
+        domain,
+        registered_at,
+        registrar,
+    }
+}
+
+/// The part of `host` someone actually registered, e.g. `app.example.co.id`
+/// -> `example.co.id`. Returns `None` for IPs and single-label hosts.
+pub fn registrable_domain(host: &str) -> Option<String> {
+    let host = host.trim_end_matches('.');
+    if host.parse::<std::net::IpAddr>().is_ok() {
+        return None;
+    }
+
+    let labels: Vec<&str> = host.split('.').filter(|l| !l.is_empty()).collect();
+    if labels.len() < 2 {
+        return None;
+    }
+
+    let second_level = labels[labels.len() - 2];
+    let take = if labels.len() >= 3
+        && labels[labels.len() - 1].len() == 2
+        && SECOND_LEVEL_SUFFIXES.contains(&second_level)
+    {
+        3
+    } else {
+        2
+    };
+    Some(labels[labels.len() - take..].join("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registrable_domain() {
+        assert_eq!(
+            registrable_domain("www.tokopedia.com").as_deref(),
+            Some("tokopedia.com")
+        );
+        assert_eq!(
+            registrable_domain("app.perfect10.co.id").as_deref(),
+            Some("perfect10.co.id")
+        );
+        assert_eq!(
+            registrable_domain("example.io").as_deref(),
+            Some("example.io")
+        );
+        assert_eq!(registrable_domain("127.0.0.1"), None);
+    }
+
+    #[test]
+    fn test_parse_rdap_response() {
+        let body: RdapDomain = serde_json::from_str(
+            r#"{
+                "events": [
+                    {"eventAction": "registration", "eventDate": "2026-09-20T10:00:00Z"},
+                    {"eventAction": "expiration", "eventDate": "2027-09-20T10:00:00Z"}
+                ],
+                "entities": [{
+                    "roles": ["registrar"],
+                    "vcardArray": ["vcard", [["version", {}, "text", "4.0"], ["fn", {}, "text", "Namecheap, Inc."]]]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let info = parse_domain_info("example.com".to_string(), body);
+        assert_eq!(info.registrar.as_deref(), Some("Namecheap, Inc."));
+        assert_eq!(
+            info.registered_at.map(|d| d.to_rfc3339()),
+            Some("2026-09-20T10:00:00+00:00".to_string())
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::domain::AiWrapperScore;
+
+struct Signal {
+    label: &'static str,
+    weight: u8,
+    needles: &'static [&'static str],
+}
+
+/// Matched against the lowercased raw HTML, so script sources and inline
+/// bundles count as well as visible copy
+const SIGNALS: &[Signal] = &[
+    Signal {
+        label: "references openai.com",
+        weight: 30,
+        needles: &["api.openai.com", "openai.com"],
+    },
+    Signal {
+        label: "ships an LLM SDK to the browser",
+        weight: 25,
+        needles: &["@ai-sdk", "langchain", "openai-node", "dangerouslyallowbrowser"],
+    },
+    Signal {
+        label: "streaming chat widget",
+        weight: 20,
+        needles: &["text/event-stream", "new eventsource", "/api/chat", "chat-widget", "chatbot-widget"],
+    },
+    Signal {
+        label: "names a GPT model",
+        weight: 15,
+        needles: &["gpt-4", "gpt-3.5", "chatgpt"],
+    },
+    Signal {
+        label: "\"powered by GPT\" marketing copy",
+        weight: 15,
+        needles: &["powered by gpt", "powered by chatgpt", "powered by openai", "built on gpt", "built with openai"],
+    },
+    Signal {
+        label: "prompt-style copy",
+        weight: 10,
+        needles: &["enter your prompt", "type a prompt", "ask anything", "generate in seconds", "ai-powered"],
+    },
+];
+
+/// Score how much a page looks like a thin ChatGPT wrapper
+pub fn detect_ai_wrapper(html: &str) -> AiWrapperScore {
+    let haystack = html.to_lowercase();
+    let matched: Vec<&Signal> = SIGNALS
+        .iter()
+        .filter(|s| s.needles.iter().any(|n| haystack.contains(n)))
+        .collect();
+
+    AiWrapperScore {
+        confidence: matched.iter().map(|s| s.weight as u32).sum::<u32>().min(100) as u8,
+        signals: matched.iter().map(|s| s.label.to_string()).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_wrapper_signals() {
+        let html = r#"<html><body><h1>Ask anything, powered by ChatGPT</h1>
+            <script>fetch("/api/chat", { headers: { accept: "text/event-stream" } })</script>
+            </body></html>"#;
+        let score = detect_ai_wrapper(html);
+        assert!(score.is_likely());
+        assert_eq!(score.signals.len(), 4);
+
+        assert_eq!(detect_ai_wrapper("<h1>Payroll for cats</h1>").confidence, 0);
+    }
+}
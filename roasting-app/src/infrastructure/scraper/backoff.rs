@@ -0,0 +1,67 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::time::Duration;
+
+const BASE_DELAY: Duration = Duration::from_secs(1);
+const MAX_DELAY: Duration = Duration::from_secs(8);
+/// A target asking for a longer pause than this is not waited for
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// Statuses worth another try: rate limited or a flaky upstream. 403 and
+/// 503 mean bot protection and go to the fallbacks instead.
+pub(super) fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::BAD_GATEWAY | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Connection refused or reset and timeouts; not bad URLs or redirect loops
+pub(super) fn is_transient_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout() || (error.is_request() && !error.is_builder())
+}
+
+/// Delay before retry number `attempt` (from 0): exponential with equal
+/// jitter, or the target's Retry-After seconds. `None` when the target
+/// wants us gone for longer than we're willing to wait.
+pub(super) fn retry_delay(attempt: u32, headers: Option<&HeaderMap>) -> Option<Duration> {
+    let retry_after = headers
+        .and_then(|h| h.get(RETRY_AFTER))
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs);
+    if let Some(retry_after) = retry_after {
+        return (retry_after <= MAX_RETRY_AFTER).then_some(retry_after);
+    }
+
+    let ceiling = BASE_DELAY.saturating_mul(1 << attempt.min(8)).min(MAX_DELAY);
+    let half = ceiling / 2;
+    let mut bytes = [0u8; 4];
+    let jitter = match SystemRandom::new().fill(&mut bytes) {
+        Ok(()) => half.mul_f64(u32::from_le_bytes(bytes) as f64 / u32::MAX as f64),
+        Err(_) => Duration::ZERO,
+    };
+    Some(half + jitter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_retry_delay_grows_and_honours_retry_after() {
+        for attempt in 0..6 {
+            let delay = retry_delay(attempt, None).unwrap();
+            let ceiling = BASE_DELAY.saturating_mul(1 << attempt).min(MAX_DELAY);
+            assert!(delay >= ceiling / 2 && delay <= ceiling, "{:?}", delay);
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+        assert_eq!(retry_delay(0, Some(&headers)), Some(Duration::from_secs(3)));
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(retry_delay(0, Some(&headers)), None);
+    }
+}
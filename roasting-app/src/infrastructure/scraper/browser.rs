@@ -0,0 +1,224 @@
+use super::header_profile::HeaderProfile;
+use chromiumoxide::cdp::browser_protocol::network::SetUserAgentOverrideParams;
+use chromiumoxide::cdp::browser_protocol::page::AddScriptToEvaluateOnNewDocumentParams;
+use chromiumoxide::{Browser, BrowserConfig, Page};
+use futures::StreamExt;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use url::Url;
+
+/// How often page state is polled while waiting
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Polls with nothing new loaded or rendered before the page counts as settled
+const QUIET_POLLS: u32 = 3;
+
+const STEALTH_ARGS: &[&str] = &[
+    "--disable-blink-features=AutomationControlled",
+    "--disable-features=IsolateOrigins,site-per-process",
+    "--disable-site-isolation-trials",
+    "--disable-web-security",
+    "--disable-features=BlockInsecurePrivateNetworkRequests",
+    "--no-first-run",
+    "--no-default-browser-check",
+    "--disable-default-apps",
+    "--disable-popup-blocking",
+    "--disable-extensions",
+    "--disable-component-extensions-with-background-pages",
+    "--disable-background-networking",
+    "--disable-sync",
+    "--disable-translate",
+    "--metrics-recording-only",
+    "--mute-audio",
+    "--no-pings",
+    "--start-maximized",
+    "--lang=id-ID",
+];
+
+const STEALTH_JS: &str = r#"
+    Object.defineProperty(navigator, 'webdriver', {
+        get: () => undefined,
+        configurable: true
+    });
+    delete navigator.__proto__.webdriver;
+
+    Object.defineProperty(navigator, 'plugins', {
+        get: () => {
+            const plugins = [
+                { name: 'Chrome PDF Plugin', filename: 'internal-pdf-viewer' },
+                { name: 'Chrome PDF Viewer', filename: 'mhjfbmdgcfjbbpaeojofohoefgiehjai' },
+                { name: 'Native Client', filename: 'internal-nacl-plugin' }
+            ];
+            plugins.length = 3;
+            return plugins;
+        }
+    });
+
+    window.chrome = {
+        runtime: {
+            PlatformOs: { MAC: 'mac', WIN: 'win', ANDROID: 'android', CROS: 'cros', LINUX: 'linux', OPENBSD: 'openbsd' },
+            PlatformArch: { ARM: 'arm', X86_32: 'x86-32', X86_64: 'x86-64' },
+            PlatformNaclArch: { ARM: 'arm', X86_32: 'x86-32', X86_64: 'x86-64' },
+            RequestUpdateCheckStatus: { THROTTLED: 'throttled', NO_UPDATE: 'no_update', UPDATE_AVAILABLE: 'update_available' },
+            OnInstalledReason: { INSTALL: 'install', UPDATE: 'update', CHROME_UPDATE: 'chrome_update', SHARED_MODULE_UPDATE: 'shared_module_update' },
+            OnRestartRequiredReason: { APP_UPDATE: 'app_update', OS_UPDATE: 'os_update', PERIODIC: 'periodic' }
+        }
+    };
+
+    Object.defineProperty(navigator, 'permissions', {
+        get: () => ({
+            query: (params) => Promise.resolve({ state: 'granted', onchange: null })
+        })
+    });
+
+    const originalQuery = window.navigator.permissions.query;
+    window.navigator.permissions.query = (parameters) => (
+        parameters.name === 'notifications' ?
+            Promise.resolve({ state: Notification.permission }) :
+            originalQuery(parameters)
+    );
+
+    Object.defineProperty(navigator, 'maxTouchPoints', { get: () => 0 });
+    Object.defineProperty(navigator, 'hardwareConcurrency', { get: () => 8 });
+    Object.defineProperty(navigator, 'deviceMemory', { get: () => 8 });
+
+    const getParameter = WebGLRenderingContext.prototype.getParameter;
+    WebGLRenderingContext.prototype.getParameter = function(parameter) {
+        if (parameter === 37445) return 'Intel Inc.';
+        if (parameter === 37446) return 'Intel Iris OpenGL Engine';
+        return getParameter.call(this, parameter);
+    };
+"#;
+
+/// Resources fetched so far and rendered text length; unchanged across a few
+/// polls means the network went idle and client-side rendering is done
+const ACTIVITY_JS: &str = r#"[
+    document.readyState === 'complete',
+    performance.getEntriesByType('resource').length,
+    document.body ? document.body.innerText.length : 0
+]"#;
+
+/// One Chrome process driven over CDP, closed with `close`
+pub(super) struct BrowserSession {
+    browser: Browser,
+    handler: JoinHandle<()>,
+}
+
+impl BrowserSession {
+    /// Launch Chrome, through `proxy` (a `--proxy-server` value) if given.
+    /// VISIBLE_BROWSER shows the window, which some challenges go easier on.
+    pub(super) async fn launch(proxy: Option<&str>) -> Result<Self, String> {
+        let mut config = BrowserConfig::builder()
+            .no_sandbox()
+            .window_size(1920, 1080)
+            .viewport(None)
+            .args(STEALTH_ARGS.iter().copied());
+        if let Some(proxy) = proxy {
+            config = config.arg(format!("--proxy-server={}", proxy));
+        }
+        if std::env::var("VISIBLE_BROWSER").is_ok() {
+            tracing::info!("Using visible browser mode for better Cloudflare bypass");
+            config = config.with_head();
+        }
+
+        let (browser, mut events) = Browser::launch(config.build()?)
+            .await
+            .map_err(|e| e.to_string())?;
+        let handler = tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                if event.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self { browser, handler })
+    }
+
+    /// A tab with the stealth patches and `profile`'s identity, navigated to
+    /// `url` and loaded
+    pub(super) async fn open(&self, url: &Url, profile: &HeaderProfile) -> Result<Page, String> {
+        let page = self
+            .browser
+            .new_page("about:blank")
+            .await
+            .map_err(|e| e.to_string())?;
+        let identity = SetUserAgentOverrideParams::builder()
+            .user_agent(profile.user_agent)
+            .accept_language(profile.accept_language)
+            .platform(profile.platform)
+            .build()?;
+        page.set_user_agent(identity).await.map_err(|e| e.to_string())?;
+        page.evaluate_on_new_document(AddScriptToEvaluateOnNewDocumentParams::new(STEALTH_JS))
+            .await
+            .map_err(|e| e.to_string())?;
+        page.goto(url.as_str()).await.map_err(|e| e.to_string())?;
+        Ok(page)
+    }
+
+    /// Open and close a blank tab, to check the browser works
+    pub(super) async fn open_blank(&self) -> Result<(), String> {
+        let page = self
+            .browser
+            .new_page("about:blank")
+            .await
+            .map_err(|e| e.to_string())?;
+        page.close().await.map_err(|e| e.to_string())
+    }
+
+    /// Shut Chrome down and reap the process
+    pub(super) async fn close(mut self) {
+        if let Err(e) = self.browser.close().await {
+            tracing::debug!("Closing browser failed: {}", e);
+        }
+        let _ = self.browser.wait().await;
+        self.handler.abort();
+    }
+}
+
+/// Wait until the page stops loading resources and rendering text, at most `timeout`
+pub(super) async fn wait_until_settled(page: &Page, timeout: Duration) {
+    let deadline = Instant::now() + timeout;
+    let mut last = None;
+    let mut quiet = 0;
+    while Instant::now() < deadline {
+        let activity = page
+            .evaluate(ACTIVITY_JS)
+            .await
+            .ok()
+            .and_then(|result| result.into_value::<(bool, u64, u64)>().ok());
+        match activity {
+            Some((true, resources, text)) if last == Some((resources, text)) => {
+                quiet += 1;
+                if quiet >= QUIET_POLLS {
+                    return;
+                }
+            }
+            Some((_, resources, text)) => {
+                last = Some((resources, text));
+                quiet = 0;
+            }
+            None => {}
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    tracing::debug!("Page still busy after {:?}", timeout);
+}
+
+/// Poll the page's HTML until `done` accepts it; the last HTML read either way
+pub(super) async fn wait_for_content(
+    page: &Page,
+    timeout: Duration,
+    done: impl Fn(&str) -> bool,
+) -> Result<(String, bool), String> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let html = page.content().await.map_err(|e| e.to_string())?;
+        if done(&html) {
+            return Ok((html, true));
+        }
+        if Instant::now() >= deadline {
+            return Ok((html, false));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
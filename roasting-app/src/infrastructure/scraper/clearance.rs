@@ -0,0 +1,122 @@
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE, USER_AGENT};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Cloudflare issues cf_clearance for 30 minutes by default; sites can
+/// shorten it, in which case the next fetch just hits the challenge again
+const CLEARANCE_TTL: Duration = Duration::from_secs(30 * 60);
+const MAX_CLEARANCES: usize = 500;
+
+struct Clearance {
+    cookie: HeaderValue,
+    user_agent: HeaderValue,
+    stored: Instant,
+}
+
+/// Cookies from a solved Cloudflare challenge, per host. Direct fetches to
+/// the host (crawled pages, the next roast of the same site) send them
+/// along with the browser's User-Agent, which cf_clearance is bound to, so
+/// the challenge isn't solved in a browser all over again.
+#[derive(Default)]
+pub struct ClearanceStore {
+    hosts: Mutex<HashMap<String, Clearance>>,
+}
+
+impl ClearanceStore {
+    /// Keep the cookies a solver ended up with for `host`. Nothing is kept
+    /// without cf_clearance: the other cookies don't get past the challenge.
+    pub fn store(&self, host: &str, cookies: &[(String, String)], user_agent: &str) {
+        if !cookies.iter().any(|(name, _)| name == "cf_clearance") {
+            return;
+        }
+        let cookie = cookies
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let (Ok(cookie), Ok(user_agent)) = (HeaderValue::from_str(&cookie), HeaderValue::from_str(user_agent))
+        else {
+            return;
+        };
+
+        let mut hosts = self.hosts.lock().unwrap();
+        if hosts.len() >= MAX_CLEARANCES {
+            hosts.retain(|_, c| c.stored.elapsed() < CLEARANCE_TTL);
+        }
+        tracing::debug!("Keeping Cloudflare clearance for {}", host);
+        hosts.insert(
+            key(host),
+            Clearance {
+                cookie,
+                user_agent,
+                stored: Instant::now(),
+            },
+        );
+    }
+
+    /// Add `host`'s clearance to a request, replacing its User-Agent
+    pub fn apply(&self, host: &str, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let mut hosts = self.hosts.lock().unwrap();
+        let key = key(host);
+        let headers = match hosts.get(&key) {
+            Some(c) if c.stored.elapsed() < CLEARANCE_TTL => {
+                let mut headers = HeaderMap::new();
+                headers.insert(COOKIE, c.cookie.clone());
+                headers.insert(USER_AGENT, c.user_agent.clone());
+                headers
+            }
+            Some(_) => {
+                hosts.remove(&key);
+                return request;
+            }
+            None => return request,
+        };
+        request.headers(headers)
+    }
+
+    /// Drop `host`'s clearance once the site challenges or blocks it again
+    pub fn forget(&self, host: &str) {
+        if self.hosts.lock().unwrap().remove(&key(host)).is_some() {
+            tracing::debug!("Cloudflare clearance for {} no longer accepted", host);
+        }
+    }
+}
+
+/// cf_clearance is set on the registrable domain, so www. shares it
+fn key(host: &str) -> String {
+    let host = host.to_ascii_lowercase();
+    host.strip_prefix("www.").map(str::to_string).unwrap_or(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clearance_replaces_user_agent_and_adds_cookie() {
+        let store = ClearanceStore::default();
+        let client = reqwest::Client::new();
+        let cookies = vec![
+            ("__cf_bm".to_string(), "abc".to_string()),
+            ("cf_clearance".to_string(), "xyz".to_string()),
+        ];
+        store.store("www.kecil.id", &cookies, "Chrome/131");
+
+        let request = store
+            .apply("kecil.id", client.get("https://kecil.id/").header(USER_AGENT, "Other/1.0"))
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()[COOKIE], "__cf_bm=abc; cf_clearance=xyz");
+        assert_eq!(request.headers().get_all(USER_AGENT).iter().count(), 1);
+        assert_eq!(request.headers()[USER_AGENT], "Chrome/131");
+
+        store.forget("kecil.id");
+        let request = store.apply("kecil.id", client.get("https://kecil.id/")).build().unwrap();
+        assert!(request.headers().get(COOKIE).is_none());
+
+        store.store("lain.id", &cookies[..1], "Chrome/131");
+        let request = store.apply("lain.id", client.get("https://lain.id/")).build().unwrap();
+        assert!(request.headers().get(COOKIE).is_none());
+    }
+}
@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+/// Limits for one scrape: how long and how far requests go, how much is
+/// downloaded and how much text is kept for the prompt. Every field has an
+/// env override, read by `from_env`.
+#[derive(Debug, Clone)]
+pub struct ScraperConfig {
+    /// Whole-request timeout for the site's pages (SCRAPE_TIMEOUT_SECS)
+    pub request_timeout: Duration,
+    /// Redirects followed before giving up (SCRAPE_MAX_REDIRECTS)
+    pub max_redirects: usize,
+    /// Landing page and archive bodies are cut off here, so a 100 MB page
+    /// never gets downloaded (SCRAPE_MAX_BODY_BYTES)
+    pub max_body_bytes: usize,
+    /// Landing page text kept for the prompt (SCRAPE_CONTENT_BYTES)
+    pub max_content_bytes: usize,
+    /// Text kept per crawled page (SCRAPE_PAGE_CONTENT_BYTES)
+    pub max_page_content_bytes: usize,
+    /// Paragraphs read from the landing page (SCRAPE_MAX_PARAGRAPHS)
+    pub max_paragraphs: usize,
+    /// Internal pages fetched after the landing page, 0 to turn crawling off (SCRAPE_MAX_PAGES)
+    pub max_pages: usize,
+    /// HTML bytes all crawled pages together may download (SCRAPE_CRAWL_BYTES)
+    pub crawl_budget: usize,
+    /// Landing page assets sized with a HEAD request, 0 to skip (SCRAPE_ASSET_PROBES)
+    pub max_asset_probes: usize,
+    /// Extra attempts at the landing page after a transient failure (SCRAPE_RETRIES)
+    pub max_retries: u32,
+}
+
+impl Default for ScraperConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(15),
+            max_redirects: 5,
+            max_body_bytes: 5 * 1024 * 1024,
+            max_content_bytes: 4000,
+            max_page_content_bytes: 1500,
+            max_paragraphs: 15,
+            max_pages: 3,
+            crawl_budget: 1024 * 1024,
+            max_asset_probes: 15,
+            max_retries: 2,
+        }
+    }
+}
+
+impl ScraperConfig {
+    /// Defaults, overridden by whichever SCRAPE_* variables parse
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str, default: T) -> T {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default)
+        }
+
+        let defaults = Self::default();
+        Self {
+            request_timeout: Duration::from_secs(var(
+                "SCRAPE_TIMEOUT_SECS",
+                defaults.request_timeout.as_secs(),
+            )),
+            max_redirects: var("SCRAPE_MAX_REDIRECTS", defaults.max_redirects),
+            max_body_bytes: var("SCRAPE_MAX_BODY_BYTES", defaults.max_body_bytes),
+            max_content_bytes: var("SCRAPE_CONTENT_BYTES", defaults.max_content_bytes),
+            max_page_content_bytes: var("SCRAPE_PAGE_CONTENT_BYTES", defaults.max_page_content_bytes),
+            max_paragraphs: var("SCRAPE_MAX_PARAGRAPHS", defaults.max_paragraphs),
+            max_pages: var("SCRAPE_MAX_PAGES", defaults.max_pages),
+            crawl_budget: var("SCRAPE_CRAWL_BYTES", defaults.crawl_budget),
+            max_asset_probes: var("SCRAPE_ASSET_PROBES", defaults.max_asset_probes),
+            max_retries: var("SCRAPE_RETRIES", defaults.max_retries),
+        }
+    }
+
+    /// Base HTTP client settings for the site's pages
+    pub fn client_builder(&self) -> reqwest::ClientBuilder {
+        reqwest::Client::builder()
+            .timeout(self.request_timeout)
+            .redirect(reqwest::redirect::Policy::limited(self.max_redirects))
+    }
+}
@@ -0,0 +1,253 @@
+use crate::domain::SubsystemHealth;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const DEFAULT_SESSION_TTL_MINS: u64 = 30;
+/// Each session is a browser inside FlareSolverr
+const DEFAULT_MAX_SESSIONS: usize = 5;
+const MAX_TIMEOUT_MS: u32 = 60_000;
+
+#[derive(Serialize)]
+struct FlareSolverrRequest<'a> {
+    cmd: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<&'a str>,
+    #[serde(rename = "maxTimeout", skip_serializing_if = "Option::is_none")]
+    max_timeout: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_ttl_minutes: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct FlareSolverrResponse {
+    status: String,
+    #[serde(default)]
+    message: String,
+    solution: Option<Solution>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct Solution {
+    pub response: String,
+    #[serde(default)]
+    pub cookies: Vec<SolutionCookie>,
+    #[serde(rename = "userAgent", default)]
+    pub user_agent: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SolutionCookie {
+    pub name: String,
+    pub value: String,
+}
+
+/// What to do before a request for a host
+struct Claim {
+    session: String,
+    /// The session has to be created first
+    create: bool,
+    /// Expired or evicted sessions to destroy
+    destroy: Vec<String>,
+}
+
+/// Sessions we believe FlareSolverr holds, by id, with when each was created
+struct Sessions {
+    ttl: Duration,
+    max: usize,
+    created: HashMap<String, Instant>,
+}
+
+impl Sessions {
+    fn claim(&mut self, host: &str) -> Claim {
+        let session = session_id(host);
+        let ttl = self.ttl;
+        let mut destroy: Vec<String> = self
+            .created
+            .iter()
+            .filter(|(_, created)| created.elapsed() >= ttl)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &destroy {
+            self.created.remove(id);
+        }
+
+        let create = !self.created.contains_key(&session);
+        if create {
+            if self.created.len() >= self.max {
+                let oldest = self
+                    .created
+                    .iter()
+                    .min_by_key(|(_, created)| **created)
+                    .map(|(id, _)| id.clone());
+                if let Some(oldest) = oldest {
+                    self.created.remove(&oldest);
+                    destroy.push(oldest);
+                }
+            }
+            self.created.insert(session.clone(), Instant::now());
+        }
+        Claim {
+            session,
+            create,
+            destroy,
+        }
+    }
+}
+
+/// Stable per host, so concurrent scrapes of one site and a restarted
+/// server land on the same FlareSolverr session instead of leaking one
+fn session_id(host: &str) -> String {
+    let host = host.to_ascii_lowercase();
+    format!("roasting-{}", host.strip_prefix("www.").unwrap_or(&host))
+}
+
+/// FlareSolverr client that keeps one session per scraped domain. A session
+/// keeps its browser and cookies between requests, so once a site's
+/// challenge is solved the next scrape of it skips straight to the page.
+/// Sessions are destroyed after FLARESOLVERR_SESSION_TTL_MINS, and the
+/// oldest goes when FLARESOLVERR_MAX_SESSIONS would be exceeded.
+pub(super) struct FlareSolverr {
+    endpoint: String,
+    client: reqwest::Client,
+    sessions: Mutex<Sessions>,
+}
+
+impl FlareSolverr {
+    /// `None` without FLARESOLVERR_URL
+    pub(super) fn from_env(client: reqwest::Client) -> Option<Self> {
+        let url = std::env::var("FLARESOLVERR_URL").ok()?;
+        let ttl_mins = std::env::var("FLARESOLVERR_SESSION_TTL_MINS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_SESSION_TTL_MINS);
+        let max = std::env::var("FLARESOLVERR_MAX_SESSIONS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_MAX_SESSIONS);
+        Some(Self {
+            endpoint: format!("{}/v1", url.trim_end_matches('/')),
+            client,
+            sessions: Mutex::new(Sessions {
+                ttl: Duration::from_secs(ttl_mins * 60),
+                max,
+                created: HashMap::new(),
+            }),
+        })
+    }
+
+    /// GET `url` through FlareSolverr, in `host`'s session when sessions
+    /// are on (FLARESOLVERR_MAX_SESSIONS above 0)
+    pub(super) async fn get(&self, url: &str, host: &str) -> Option<Solution> {
+        let claim = {
+            let mut sessions = self.sessions.lock().unwrap();
+            (sessions.max > 0).then(|| sessions.claim(host))
+        };
+        let mut session = None;
+        if let Some(claim) = claim {
+            for id in &claim.destroy {
+                tracing::debug!("Destroying FlareSolverr session {}", id);
+                self.command("sessions.destroy", Some(id)).await;
+            }
+            if !claim.create || self.command("sessions.create", Some(&claim.session)).await.is_some() {
+                session = Some(claim.session);
+            } else {
+                self.sessions.lock().unwrap().created.remove(&claim.session);
+            }
+        }
+
+        let ttl_mins = self.sessions.lock().unwrap().ttl.as_secs() / 60;
+        let request = FlareSolverrRequest {
+            cmd: "request.get",
+            url: Some(url),
+            max_timeout: Some(MAX_TIMEOUT_MS),
+            session: session.as_deref(),
+            session_ttl_minutes: session.as_ref().map(|_| ttl_mins),
+        };
+        let result = self.send(&request).await;
+        if result.is_none() {
+            // A session FlareSolverr lost or that broke mid-request starts over next time
+            if let Some(session) = &session {
+                self.sessions.lock().unwrap().created.remove(session);
+                self.command("sessions.destroy", Some(session)).await;
+            }
+        }
+        result?.solution
+    }
+
+    async fn command(&self, cmd: &str, session: Option<&str>) -> Option<FlareSolverrResponse> {
+        self.send(&FlareSolverrRequest {
+            cmd,
+            url: None,
+            max_timeout: None,
+            session,
+            session_ttl_minutes: None,
+        })
+        .await
+    }
+
+    async fn send(&self, request: &FlareSolverrRequest<'_>) -> Option<FlareSolverrResponse> {
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .json(request)
+            .timeout(Duration::from_millis(MAX_TIMEOUT_MS as u64) + Duration::from_secs(10))
+            .send()
+            .await;
+        let result: FlareSolverrResponse = match response {
+            Ok(response) => response.json().await.ok()?,
+            Err(e) => {
+                tracing::warn!("FlareSolverr {} failed: {}", request.cmd, e);
+                return None;
+            }
+        };
+        if result.status != "ok" {
+            tracing::warn!(
+                "FlareSolverr {} returned {}: {}",
+                request.cmd,
+                result.status,
+                result.message
+            );
+            return None;
+        }
+        Some(result)
+    }
+
+    pub(super) fn health(&self) -> SubsystemHealth {
+        let sessions = self.sessions.lock().unwrap();
+        SubsystemHealth::active("flaresolverr")
+            .with_detail(format!("{}/{} sessions", sessions.created.len(), sessions.max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sessions_are_reused_and_evicted() {
+        let mut sessions = Sessions {
+            ttl: Duration::from_secs(60),
+            max: 2,
+            created: HashMap::new(),
+        };
+        let first = sessions.claim("www.Kecil.id");
+        assert_eq!(first.session, "roasting-kecil.id");
+        assert!(first.create);
+        assert!(!sessions.claim("kecil.id").create);
+
+        std::thread::sleep(Duration::from_millis(2));
+        sessions.claim("lain.id");
+        let third = sessions.claim("ketiga.id");
+        assert!(third.create);
+        assert_eq!(third.destroy, vec!["roasting-kecil.id".to_string()]);
+
+        sessions.ttl = Duration::ZERO;
+        let expired = sessions.claim("lain.id");
+        assert!(expired.create);
+        assert_eq!(expired.destroy.len(), 2);
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Drop sticky assignments once this many domains have been seen
+const MAX_STICKY_DOMAINS: usize = 10_000;
+
+/// A consistent browser identity: the UA plus the headers a real browser of
+/// that kind would send alongside it
+#[derive(Debug)]
+pub struct HeaderProfile {
+    pub name: &'static str,
+    pub user_agent: &'static str,
+    pub accept: &'static str,
+    pub accept_language: &'static str,
+    /// Client hints, only sent by Chromium-based browsers
+    pub sec_ch_ua: Option<&'static str>,
+    pub sec_ch_ua_platform: Option<&'static str>,
+    /// Value for `navigator.platform` in headless sessions
+    pub platform: &'static str,
+    pub chromium: bool,
+}
+
+const CHROMIUM_ACCEPT: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8";
+const FIREFOX_ACCEPT: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,*/*;q=0.8";
+const SAFARI_ACCEPT: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+
+const CHROME_122_BRANDS: &str =
+    r#""Chromium";v="122", "Not(A:Brand";v="24", "Google Chrome";v="122""#;
+const EDGE_122_BRANDS: &str =
+    r#""Chromium";v="122", "Not(A:Brand";v="24", "Microsoft Edge";v="122""#;
+
+pub const PROFILES: &[HeaderProfile] = &[
+    HeaderProfile {
+        name: "chrome-mac",
+        user_agent: "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36",
+        accept: CHROMIUM_ACCEPT,
+        accept_language: "id-ID,id;q=0.9,en-US;q=0.8,en;q=0.7",
+        sec_ch_ua: Some(CHROME_122_BRANDS),
+        sec_ch_ua_platform: Some(r#""macOS""#),
+        platform: "MacIntel",
+        chromium: true,
+    },
+    HeaderProfile {
+        name: "chrome-windows",
+        user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36",
+        accept: CHROMIUM_ACCEPT,
+        accept_language: "en-US,en;q=0.9,id;q=0.8",
+        sec_ch_ua: Some(CHROME_122_BRANDS),
+        sec_ch_ua_platform: Some(r#""Windows""#),
+        platform: "Win32",
+        chromium: true,
+    },
+    HeaderProfile {
+        name: "chrome-linux",
+        user_agent: "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36",
+        accept: CHROMIUM_ACCEPT,
+        accept_language: "en-US,en;q=0.9",
+        sec_ch_ua: Some(CHROME_122_BRANDS),
+        sec_ch_ua_platform: Some(r#""Linux""#),
+        platform: "Linux x86_64",
+        chromium: true,
+    },
+    HeaderProfile {
+        name: "edge-windows",
+        user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36 Edg/122.0.0.0",
+        accept: CHROMIUM_ACCEPT,
+        accept_language: "id,en-US;q=0.9,en;q=0.8",
+        sec_ch_ua: Some(EDGE_122_BRANDS),
+        sec_ch_ua_platform: Some(r#""Windows""#),
+        platform: "Win32",
+        chromium: true,
+    },
+    HeaderProfile {
+        name: "firefox-windows",
+        user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:123.0) Gecko/20100101 Firefox/123.0",
+        accept: FIREFOX_ACCEPT,
+        accept_language: "en-US,en;q=0.5",
+        sec_ch_ua: None,
+        sec_ch_ua_platform: None,
+        platform: "Win32",
+        chromium: false,
+    },
+    HeaderProfile {
+        name: "safari-mac",
+        user_agent: "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.3 Safari/605.1.15",
+        accept: SAFARI_ACCEPT,
+        accept_language: "id-ID,id;q=0.9",
+        sec_ch_ua: None,
+        sec_ch_ua_platform: None,
+        platform: "MacIntel",
+        chromium: false,
+    },
+];
+
+impl HeaderProfile {
+    /// Attach this profile's navigation headers to a request
+    pub fn apply(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let mut request = request
+            .header("User-Agent", self.user_agent)
+            .header("Accept", self.accept)
+            .header("Accept-Language", self.accept_language)
+            .header("Accept-Encoding", "gzip, deflate, br")
+            .header("Connection", "keep-alive")
+            .header("Upgrade-Insecure-Requests", "1")
+            .header("Sec-Fetch-Dest", "document")
+            .header("Sec-Fetch-Mode", "navigate")
+            .header("Sec-Fetch-Site", "none")
+            .header("Sec-Fetch-User", "?1");
+
+        if let (Some(brands), Some(platform)) = (self.sec_ch_ua, self.sec_ch_ua_platform) {
+            request = request
+                .header("sec-ch-ua", brands)
+                .header("sec-ch-ua-mobile", "?0")
+                .header("sec-ch-ua-platform", platform)
+                .header("Cache-Control", "max-age=0");
+        }
+
+        request
+    }
+}
+
+/// Hands out header profiles, keeping each domain on the same profile so a
+/// site sees one consistent visitor instead of a new browser every request.
+pub struct ProfileRotator {
+    pool: Vec<&'static HeaderProfile>,
+    next: AtomicUsize,
+    sticky: Mutex<HashMap<String, usize>>,
+}
+
+impl ProfileRotator {
+    /// Build a rotator over the named profiles, or every profile if `names` is empty
+    pub fn new(names: &[&str]) -> Self {
+        let mut pool: Vec<&'static HeaderProfile> = PROFILES
+            .iter()
+            .filter(|p| names.is_empty() || names.contains(&p.name))
+            .collect();
+
+        if pool.is_empty() {
+            tracing::warn!("No known header profiles in {:?}, using all profiles", names);
+            pool = PROFILES.iter().collect();
+        }
+
+        Self {
+            pool,
+            next: AtomicUsize::new(0),
+            sticky: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reads a comma-separated list of profile names from SCRAPER_HEADER_PROFILES
+    pub fn from_env() -> Self {
+        let names = std::env::var("SCRAPER_HEADER_PROFILES").unwrap_or_default();
+        let names: Vec<&str> = names
+            .split(',')
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .collect();
+        Self::new(&names)
+    }
+
+    /// The profile assigned to `domain`, assigning the next one in the pool on first use
+    pub fn for_domain(&self, domain: &str) -> &'static HeaderProfile {
+        let mut sticky = self.sticky.lock().unwrap();
+        if sticky.len() >= MAX_STICKY_DOMAINS && !sticky.contains_key(domain) {
+            sticky.clear();
+        }
+
+        let index = *sticky
+            .entry(domain.to_string())
+            .or_insert_with(|| self.next.fetch_add(1, Ordering::Relaxed) % self.pool.len());
+        self.pool[index]
+    }
+
+    /// Profile for a headless Chrome session; falls back to a Chromium
+    /// profile when the domain's HTTP profile would not match the browser
+    pub fn for_headless(&self, domain: &str) -> &'static HeaderProfile {
+        let profile = self.for_domain(domain);
+        if profile.chromium {
+            return profile;
+        }
+        self.pool
+            .iter()
+            .copied()
+            .find(|p| p.chromium)
+            .unwrap_or(&PROFILES[0])
+    }
+
+    /// Move `domain` to the next profile, e.g. after it blocked the current one
+    pub fn rotate(&self, domain: &str) -> &'static HeaderProfile {
+        let mut sticky = self.sticky.lock().unwrap();
+        let index = sticky
+            .get(domain)
+            .map(|i| (i + 1) % self.pool.len())
+            .unwrap_or_else(|| self.next.fetch_add(1, Ordering::Relaxed) % self.pool.len());
+        sticky.insert(domain.to_string(), index);
+        self.pool[index]
+    }
+
+    pub fn profile_names(&self) -> Vec<&'static str> {
+        self.pool.iter().map(|p| p.name).collect()
+    }
+}
+
+impl Default for ProfileRotator {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_stickiness_and_rotation() {
+        let rotator = ProfileRotator::new(&["chrome-mac", "firefox-windows"]);
+        let first = rotator.for_domain("example.com");
+        assert_eq!(rotator.for_domain("example.com").name, first.name);
+        assert_ne!(rotator.for_domain("other.com").name, first.name);
+
+        let rotated = rotator.rotate("example.com");
+        assert_ne!(rotated.name, first.name);
+        assert_eq!(rotator.for_domain("example.com").name, rotated.name);
+    }
+
+    #[test]
+    fn test_headless_always_chromium() {
+        let rotator = ProfileRotator::new(&["firefox-windows", "safari-mac"]);
+        assert!(!rotator.for_domain("a.com").chromium);
+        assert!(rotator.for_headless("a.com").chromium);
+    }
+}
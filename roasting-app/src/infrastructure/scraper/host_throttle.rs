@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+const DEFAULT_INTERVAL_MS: u64 = 2000;
+/// Hosts remembered before slots in the past are dropped
+const MAX_TRACKED_HOSTS: usize = 1000;
+
+/// Spaces out requests to the same host, so a roast's pre-flight, robots.txt,
+/// landing page, sitemaps and crawled pages never hit a small startup's
+/// server back to back. Concurrent callers queue up: each one reserves the
+/// next free slot for the host and sleeps until it comes.
+pub struct HostThrottle {
+    interval: Duration,
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostThrottle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// SCRAPE_HOST_INTERVAL_MS between requests to one host, default 2000; 0 turns spacing off
+    pub fn from_env() -> Self {
+        let interval = std::env::var("SCRAPE_HOST_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_INTERVAL_MS);
+        Self::new(Duration::from_millis(interval))
+    }
+
+    /// Wait for this request's turn at `host`
+    pub async fn wait(&self, host: &str) {
+        if self.interval.is_zero() || host.is_empty() {
+            return;
+        }
+        let slot = self.reserve(host);
+        if slot > Instant::now() {
+            tracing::debug!("Waiting {:?} before the next request to {}", slot - Instant::now(), host);
+            tokio::time::sleep_until(slot).await;
+        }
+    }
+
+    fn reserve(&self, host: &str) -> Instant {
+        let now = Instant::now();
+        let mut next_slot = self.next_slot.lock().unwrap();
+        if next_slot.len() >= MAX_TRACKED_HOSTS {
+            next_slot.retain(|_, slot| *slot > now);
+        }
+        let slot = next_slot
+            .get(host)
+            .copied()
+            .filter(|slot| *slot > now)
+            .unwrap_or(now);
+        next_slot.insert(host.to_string(), slot + self.interval);
+        slot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_to_one_host_are_spaced() {
+        let throttle = HostThrottle::new(Duration::from_secs(2));
+        let now = Instant::now();
+        let first = throttle.reserve("kecil.id");
+        let second = throttle.reserve("kecil.id");
+        let third = throttle.reserve("kecil.id");
+        assert!(first - now < Duration::from_secs(1));
+        assert_eq!(second - first, Duration::from_secs(2));
+        assert_eq!(third - second, Duration::from_secs(2));
+        assert!(throttle.reserve("lain.id") - now < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,129 @@
+use super::structured_data::extract_structured_data;
+use crate::domain::{PageKind, PageSection, StartupInfo};
+use scraper::{Html, Selector};
+use url::Url;
+
+const MAX_POSTS: usize = 5;
+const MAX_POST_CHARS: usize = 300;
+const MAX_ABOUT_CHARS: usize = 2000;
+
+/// The company's slug for a `linkedin.com/company/<slug>` URL, on any
+/// LinkedIn subdomain (`id.linkedin.com` is what Indonesian links share)
+pub(super) fn company_slug(url: &Url) -> Option<&str> {
+    let host = url.host_str()?;
+    if host != "linkedin.com" && !host.ends_with(".linkedin.com") {
+        return None;
+    }
+    let mut segments = url.path_segments()?;
+    if segments.next()? != "company" {
+        return None;
+    }
+    segments.next().filter(|slug| !slug.is_empty())
+}
+
+/// Name, tagline, about text, industry, employee count and recent posts
+/// from the public company page. `None` for the login wall LinkedIn shows
+/// instead when it doesn't like the visitor.
+pub(super) fn parse_company_page(url: &str, html: &str) -> Option<StartupInfo> {
+    let document = Html::parse_document(html);
+    let mut structured = extract_structured_data(&document).unwrap_or_default();
+
+    let name = first_text(&document, "h1.top-card-layout__title").or(structured.organization.clone())?;
+    let tagline = first_text(&document, ".top-card-layout__headline");
+    let industry = about_field(&document, "industry");
+    let about = first_text(&document, r#"[data-test-id="about-us__description"]"#)
+        .map(|about| about.chars().take(MAX_ABOUT_CHARS).collect::<String>())
+        .unwrap_or_default();
+
+    // "51-200 employees", or "karyawan" on the Indonesian locale
+    if let Some(size) = about_field(&document, "size") {
+        let size = size.trim_end_matches("employees").trim_end_matches("karyawan").trim();
+        structured.employees = Some(size.to_string());
+    }
+    if structured.founding_date.is_none() {
+        structured.founding_date = about_field(&document, "foundedOn");
+    }
+    structured.organization = Some(name.clone());
+
+    let posts = select(r#"[data-test-id="main-feed-activity-card__commentary"]"#);
+    let posts: Vec<String> = document
+        .select(&posts)
+        .filter_map(|el| clean(&el.text().collect::<String>()))
+        .map(|post| post.chars().take(MAX_POST_CHARS).collect())
+        .take(MAX_POSTS)
+        .collect();
+    let pages = if posts.is_empty() {
+        Vec::new()
+    } else {
+        vec![PageSection {
+            kind: PageKind::Blog,
+            url: format!("{}/posts", url.trim_end_matches('/')),
+            title: Some("LinkedIn posts".to_string()),
+            content: posts.join(" | "),
+        }]
+    };
+
+    let headings = [Some(name.clone()), tagline.clone(), industry].into_iter().flatten().collect();
+    Some(
+        StartupInfo::new(url.to_string())
+            .with_title(Some(format!("{} | LinkedIn", name)))
+            .with_description(tagline)
+            .with_headings(headings)
+            .with_content_summary(about)
+            .with_pages(pages)
+            .with_structured_data((!structured.is_empty()).then_some(structured)),
+    )
+}
+
+fn select(css: &str) -> Selector {
+    Selector::parse(css).expect("valid selector")
+}
+
+fn first_text(document: &Html, css: &str) -> Option<String> {
+    document
+        .select(&select(css))
+        .find_map(|el| clean(&el.text().collect::<String>()))
+}
+
+/// A `<dd>` of the "About us" definition list, by its test id suffix
+fn about_field(document: &Html, field: &str) -> Option<String> {
+    first_text(document, &format!(r#"[data-test-id="about-us__{}"] dd"#, field))
+}
+
+fn clean(raw: &str) -> Option<String> {
+    let text = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_company_page() {
+        let url = Url::parse("https://id.linkedin.com/company/kecil-tech/").unwrap();
+        assert_eq!(company_slug(&url), Some("kecil-tech"));
+        assert_eq!(company_slug(&Url::parse("https://www.linkedin.com/in/budi").unwrap()), None);
+        assert_eq!(company_slug(&Url::parse("https://notlinkedin.com/company/x").unwrap()), None);
+
+        let html = r#"<html><body>
+            <h1 class="top-card-layout__title">Kecil Tech</h1>
+            <h4 class="top-card-layout__headline"> Solusi kasir untuk UMKM </h4>
+            <p data-test-id="about-us__description">Kami membantu warung go digital.</p>
+            <div data-test-id="about-us__industry"><dt>Industri</dt><dd>Software Development</dd></div>
+            <div data-test-id="about-us__size"><dt>Ukuran</dt><dd>11-50 karyawan</dd></div>
+            <div data-test-id="about-us__foundedOn"><dt>Didirikan</dt><dd>2021</dd></div>
+            <p data-test-id="main-feed-activity-card__commentary">We're hiring!</p>
+            <p data-test-id="main-feed-activity-card__commentary">Series A 🚀</p>
+        </body></html>"#;
+        let info = parse_company_page(url.as_str(), html).unwrap();
+        assert_eq!(info.description.as_deref(), Some("Solusi kasir untuk UMKM"));
+        assert_eq!(info.headings, vec!["Kecil Tech", "Solusi kasir untuk UMKM", "Software Development"]);
+        let structured = info.structured_data.unwrap();
+        assert_eq!(structured.employees.as_deref(), Some("11-50"));
+        assert_eq!(structured.founding_date.as_deref(), Some("2021"));
+        assert_eq!(info.pages[0].content, "We're hiring! | Series A 🚀");
+
+        assert!(parse_company_page(url.as_str(), "<form class=\"authwall\"></form>").is_none());
+    }
+}
@@ -0,0 +1,108 @@
+use scraper::{Html, Selector};
+use url::Url;
+
+/// Enough to get past one broken or oversized candidate, few enough that a
+/// page full of logos (a "trusted by" strip) isn't downloaded wholesale
+const MAX_LOGO_CANDIDATES: usize = 6;
+
+/// Icon, logo and og:image URLs of a page, best first. Square icons beat the
+/// wide og:image banner; `/favicon.ico` is always the last resort. SVGs are
+/// skipped since they are served from our own origin later.
+pub fn extract_logo_candidates(document: &Html, base_url: &str) -> Vec<String> {
+    let Ok(base) = Url::parse(base_url) else {
+        return Vec::new();
+    };
+    let select = |css: &str| Selector::parse(css).expect("valid selector");
+    // (priority, size, url): lower priority first, bigger icons first
+    let mut ranked: Vec<(u8, u32, String)> = Vec::new();
+
+    for link in document.select(&select("link[rel][href]")) {
+        let rel = link.value().attr("rel").unwrap_or_default().to_lowercase();
+        let priority = if rel.contains("apple-touch-icon") {
+            0
+        } else if rel.split_whitespace().any(|r| r == "icon") {
+            1
+        } else {
+            continue;
+        };
+        if link.value().attr("type").is_some_and(|t| t.contains("svg")) {
+            continue;
+        }
+        let size = link
+            .value()
+            .attr("sizes")
+            .and_then(|s| s.split(['x', 'X']).next()?.parse().ok())
+            .unwrap_or(0);
+        ranked.push((priority, size, link.value().attr("href").unwrap_or_default().to_string()));
+    }
+
+    for img in document.select(&select("header img[src], img[src]")) {
+        let el = img.value();
+        let looks_like_logo = ["class", "id", "alt", "src"]
+            .iter()
+            .filter_map(|attr| el.attr(attr))
+            .any(|value| value.to_lowercase().contains("logo"));
+        if looks_like_logo {
+            ranked.push((2, 0, el.attr("src").unwrap_or_default().to_string()));
+        }
+    }
+
+    for meta in document.select(&select("meta[property][content]")) {
+        if meta.value().attr("property").is_some_and(|p| p.eq_ignore_ascii_case("og:image")) {
+            ranked.push((3, 0, meta.value().attr("content").unwrap_or_default().to_string()));
+        }
+    }
+
+    ranked.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+    let mut candidates: Vec<String> = Vec::new();
+    let fallback = base.join("/favicon.ico").ok().map(String::from);
+    let resolved = ranked
+        .into_iter()
+        .filter_map(|(_, _, href)| base.join(href.trim()).ok())
+        .filter(|u| matches!(u.scheme(), "http" | "https"))
+        .filter(|u| !u.path().to_lowercase().ends_with(".svg"))
+        .map(String::from)
+        .chain(fallback);
+    for url in resolved {
+        if !candidates.contains(&url) {
+            candidates.push(url);
+        }
+    }
+    if candidates.len() > MAX_LOGO_CANDIDATES {
+        // Keep the favicon fallback even when the page had plenty of candidates
+        let favicon = candidates.pop();
+        candidates.truncate(MAX_LOGO_CANDIDATES - 1);
+        candidates.extend(favicon);
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranks_touch_icons_before_banners() {
+        let html = r#"<html><head>
+            <meta property="og:image" content="https://cdn.acme.id/banner.jpg">
+            <link rel="icon" href="/favicon-16.png" sizes="16x16">
+            <link rel="icon" href="/favicon-32.png" sizes="32x32">
+            <link rel="icon" type="image/svg+xml" href="/icon.svg">
+            <link rel="apple-touch-icon" href="/apple-touch-icon.png">
+            </head><body>
+            <header><img class="site-logo" src="img/logo.png"><img src="/hero.jpg"></header>
+            </body></html>"#;
+        let candidates = extract_logo_candidates(&Html::parse_document(html), "https://acme.id/about");
+        assert_eq!(
+            candidates,
+            vec![
+                "https://acme.id/apple-touch-icon.png",
+                "https://acme.id/favicon-32.png",
+                "https://acme.id/favicon-16.png",
+                "https://acme.id/img/logo.png",
+                "https://cdn.acme.id/banner.jpg",
+                "https://acme.id/favicon.ico",
+            ]
+        );
+    }
+}
@@ -1,3 +1,35 @@
+mod ai_wrapper_detector;
+mod backoff;
+mod clearance;
+mod config;
+mod flaresolverr;
+#[cfg(feature = "headless")]
+mod browser;
+mod header_profile;
+mod host_throttle;
+mod linkedin;
+mod logo;
+mod perf_probe;
+mod proxy_rotator;
+mod robots;
+mod security_probe;
+mod seo_audit;
+mod sitemap;
+mod social_links;
+mod structured_data;
+mod tech_detector;
 mod website_scraper;
 
+pub use ai_wrapper_detector::detect_ai_wrapper;
+pub use config::ScraperConfig;
+pub use header_profile::{HeaderProfile, ProfileRotator, PROFILES};
+pub use host_throttle::HostThrottle;
+pub use logo::extract_logo_candidates;
+pub use proxy_rotator::{Proxy, ProxyRotator};
+pub use security_probe::{scan_page_security, SecurityProbe};
+pub use seo_audit::audit_seo;
+pub use social_links::extract_social_links;
+pub use structured_data::extract_structured_data;
+pub use tech_detector::detect_tech_stack;
 pub use website_scraper::WebsiteScraper;
+pub(crate) use website_scraper::read_capped;
@@ -0,0 +1,169 @@
+use super::website_scraper::same_site;
+use crate::domain::{AssetKind, PageAsset, PagePerformance};
+use scraper::{Html, Selector};
+use std::time::Duration;
+use url::Url;
+
+const ASSET_TIMEOUT: Duration = Duration::from_secs(4);
+/// Largest assets reported back to the prompt
+const MAX_HEAVIEST: usize = 5;
+
+/// Resources the landing page loads, in the order they're worth sizing
+pub(super) struct PageAssets {
+    pub assets: Vec<(Url, AssetKind)>,
+    pub blocking_scripts: u32,
+    pub image_count: u32,
+}
+
+/// Find the page's images, videos, scripts and stylesheets. Videos come
+/// first since they're the usual 14 MB surprise, then images. Only assets
+/// on the page's own site are kept: the page picks the URLs, and we don't
+/// send requests wherever it points.
+pub(super) fn collect_assets(html: &str, base: &Url) -> PageAssets {
+    let document = Html::parse_document(html);
+    let select = |css: &str| Selector::parse(css).expect("valid selector");
+
+    let blocking_scripts = document
+        .select(&select("head script[src]"))
+        .filter(|el| {
+            let attrs = el.value();
+            attrs.attr("async").is_none()
+                && attrs.attr("defer").is_none()
+                && attrs.attr("type") != Some("module")
+        })
+        .count() as u32;
+
+    let sources: [(&str, &str, AssetKind); 6] = [
+        ("video[src]", "src", AssetKind::Video),
+        ("video source[src]", "src", AssetKind::Video),
+        ("video[poster]", "poster", AssetKind::Image),
+        ("img[src]", "src", AssetKind::Image),
+        ("script[src]", "src", AssetKind::Script),
+        ("link[rel~='stylesheet'][href]", "href", AssetKind::Stylesheet),
+    ];
+    let mut assets: Vec<(Url, AssetKind)> = Vec::new();
+    let mut image_count = 0;
+    for (css, attr, kind) in sources {
+        for el in document.select(&select(css)) {
+            if el.value().name() == "img" {
+                image_count += 1;
+            }
+            let Some(url) = el
+                .value()
+                .attr(attr)
+                .filter(|src| !src.trim_start().starts_with("data:"))
+                .and_then(|src| base.join(src.trim()).ok())
+                .filter(|url| matches!(url.scheme(), "http" | "https") && same_site(url, base))
+            else {
+                continue;
+            };
+            if !assets.iter().any(|(seen, _)| *seen == url) {
+                assets.push((url, kind));
+            }
+        }
+    }
+
+    PageAssets {
+        assets,
+        blocking_scripts,
+        image_count,
+    }
+}
+
+/// Size up to `limit` assets with parallel HEAD requests. Assets whose
+/// server doesn't send a Content-Length are left out, and so are redirects,
+/// which `client` must not follow.
+pub(super) async fn measure_assets(
+    client: &reqwest::Client,
+    assets: &[(Url, AssetKind)],
+    limit: usize,
+) -> Vec<PageAsset> {
+    let mut probes = tokio::task::JoinSet::new();
+    for (url, kind) in assets.iter().take(limit).cloned() {
+        let client = client.clone();
+        probes.spawn(async move {
+            let response = client
+                .head(url.as_str())
+                .timeout(ASSET_TIMEOUT)
+                .send()
+                .await
+                .ok()
+                .filter(|r| r.status().is_success())?;
+            let bytes = response
+                .headers()
+                .get(reqwest::header::CONTENT_LENGTH)?
+                .to_str()
+                .ok()?
+                .parse()
+                .ok()?;
+            Some(PageAsset {
+                url: url.to_string(),
+                kind,
+                bytes,
+            })
+        });
+    }
+    let mut measured = Vec::new();
+    while let Some(result) = probes.join_next().await {
+        if let Ok(Some(asset)) = result {
+            measured.push(asset);
+        }
+    }
+    measured
+}
+
+pub(super) fn page_performance(
+    ttfb: Duration,
+    html_bytes: usize,
+    page: &PageAssets,
+    mut measured: Vec<PageAsset>,
+) -> PagePerformance {
+    let total_bytes = html_bytes as u64 + measured.iter().map(|a| a.bytes).sum::<u64>();
+    measured.sort_by_key(|a| std::cmp::Reverse(a.bytes));
+    measured.truncate(MAX_HEAVIEST);
+    PagePerformance {
+        ttfb_ms: ttfb.as_millis() as u64,
+        html_bytes: html_bytes as u64,
+        total_bytes,
+        blocking_scripts: page.blocking_scripts,
+        image_count: page.image_count,
+        heaviest: measured,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collects_assets_and_blocking_scripts() {
+        let html = r#"<html><head>
+            <script src="/js/jquery.min.js"></script>
+            <script src="https://cdn.example.net/analytics.js"></script>
+            <script src="/js/app.js" defer></script>
+            <script type="module" src="/js/main.js"></script>
+            <link rel="preload stylesheet" href="/css/site.css">
+            </head><body>
+            <video autoplay muted poster="/img/poster.jpg"><source src="/media/hero.mp4" type="video/mp4"></video>
+            <img src="/img/logo.png"><img src="/img/logo.png"><img src="data:image/gif;base64,R0lGOD">
+            </body></html>"#;
+        let base = Url::parse("https://kopikilat.id/").unwrap();
+
+        let page = collect_assets(html, &base);
+        assert_eq!(page.blocking_scripts, 2);
+        assert_eq!(page.image_count, 3);
+        let urls: Vec<&str> = page.assets.iter().map(|(u, _)| u.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://kopikilat.id/media/hero.mp4",
+                "https://kopikilat.id/img/poster.jpg",
+                "https://kopikilat.id/img/logo.png",
+                "https://kopikilat.id/js/jquery.min.js",
+                "https://kopikilat.id/js/app.js",
+                "https://kopikilat.id/js/main.js",
+                "https://kopikilat.id/css/site.css",
+            ]
+        );
+    }
+}
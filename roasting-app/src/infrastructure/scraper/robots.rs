@@ -0,0 +1,171 @@
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Product token matched against `User-agent` lines; other groups fall back to `*`
+pub const ROBOTS_AGENT: &str = "roasting-startup";
+/// robots.txt files larger than this are treated as allowing everything (RFC 9309 asks for at least 500 KiB)
+pub const MAX_ROBOTS_BYTES: usize = 512 * 1024;
+const CACHE_TTL: Duration = Duration::from_secs(3600);
+
+/// The `Allow`/`Disallow` rules from a robots.txt that apply to us
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsRules {
+    /// `(allow, pattern)`
+    rules: Vec<(bool, String)>,
+}
+
+impl RobotsRules {
+    /// Rules for a site without a usable robots.txt
+    pub fn allow_all() -> Self {
+        Self::default()
+    }
+
+    /// Groups naming `agent` win over `*` groups; several matching groups are merged
+    pub fn parse(text: &str, agent: &str) -> Self {
+        let agent = agent.to_lowercase();
+        let mut specific = Vec::new();
+        let mut wildcard = Vec::new();
+
+        let mut group_agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        group_agents.clear();
+                        in_rules = false;
+                    }
+                    group_agents.push(value.to_lowercase());
+                }
+                key @ ("allow" | "disallow") => {
+                    in_rules = true;
+                    // An empty Disallow allows everything, which is the default anyway
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = (key == "allow", value.to_string());
+                    if group_agents.iter().any(|a| !a.is_empty() && agent.starts_with(a.as_str())) {
+                        specific.push(rule.clone());
+                    }
+                    if group_agents.iter().any(|a| a == "*") {
+                        wildcard.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            rules: if specific.is_empty() { wildcard } else { specific },
+        }
+    }
+
+    /// `path` includes the query string. The longest matching pattern
+    /// decides; on a tie Allow wins.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        let mut best: Option<(usize, bool)> = None;
+        for (allow, pattern) in &self.rules {
+            if !pattern_matches(pattern, path) {
+                continue;
+            }
+            let len = pattern.len();
+            best = match best {
+                Some((best_len, best_allow)) if best_len > len || (best_len == len && best_allow) => {
+                    Some((best_len, best_allow))
+                }
+                _ => Some((len, *allow)),
+            };
+        }
+        best.is_none_or(|(_, allow)| allow)
+    }
+}
+
+/// Prefix match with `*` for any run of characters and a trailing `$` anchoring the end
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let Some(first) = parts.first() else {
+        return true;
+    };
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    if parts.len() == 1 {
+        return !anchored || rest.is_empty();
+    }
+    let last = parts.len() - 1;
+    for (i, part) in parts.iter().enumerate().skip(1) {
+        if i == last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Parsed robots.txt per origin, refetched after an hour
+#[derive(Default)]
+pub struct RobotsCache {
+    entries: DashMap<String, (Instant, Arc<RobotsRules>)>,
+}
+
+impl RobotsCache {
+    pub fn get(&self, origin: &str) -> Option<Arc<RobotsRules>> {
+        let entry = self.entries.get(origin)?;
+        let (fetched, rules) = entry.value();
+        (fetched.elapsed() < CACHE_TTL).then(|| rules.clone())
+    }
+
+    pub fn insert(&self, origin: &str, rules: RobotsRules) -> Arc<RobotsRules> {
+        let rules = Arc::new(rules);
+        self.entries
+            .insert(origin.to_string(), (Instant::now(), rules.clone()));
+        rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_and_precedence() {
+        let robots = "\
+User-agent: *
+Disallow: /private
+Allow: /private/pricing$
+
+User-agent: GPTBot
+User-agent: roasting-startup
+Disallow: /
+Allow: /about
+";
+        let ours = RobotsRules::parse(robots, ROBOTS_AGENT);
+        assert!(!ours.is_allowed("/"));
+        assert!(ours.is_allowed("/about/team"));
+
+        let others = RobotsRules::parse(robots, "somebot");
+        assert!(others.is_allowed("/"));
+        assert!(!others.is_allowed("/private/docs"));
+        assert!(others.is_allowed("/private/pricing"));
+        assert!(!others.is_allowed("/private/pricing?x=1"));
+
+        let wildcards = RobotsRules::parse("User-agent: *\nDisallow: /*.pdf$\nDisallow: /\nAllow: /$", "x");
+        assert!(wildcards.is_allowed("/"));
+        assert!(!wildcards.is_allowed("/deck.pdf"));
+        assert!(!wildcards.is_allowed("/pricing"));
+        assert!(RobotsRules::parse("User-agent: *\nDisallow:", "x").is_allowed("/"));
+    }
+}
@@ -0,0 +1,175 @@
+use super::website_scraper::read_capped;
+use crate::domain::{SecurityPosture, StartupInfo};
+use std::time::Duration;
+use url::Url;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Enough of a response body to recognise the file without keeping it
+const SNIFF_BYTES: usize = 2048;
+
+/// Resource attributes that make an HTTPS page load insecure content
+const MIXED_CONTENT_NEEDLES: &[&str] = &[
+    "src=\"http://",
+    "src='http://",
+    "href=\"http://",
+    "href='http://",
+];
+
+/// Posture visible in the page itself: mixed content and "Index of /" listings
+pub fn scan_page_security(url: &str, html: &str) -> SecurityPosture {
+    let https = url.starts_with("https://");
+    let lower = html.to_lowercase();
+
+    // Plain links to other HTTP sites are fine; only stylesheets and embedded
+    // resources count as mixed content
+    let mixed_content = https
+        && lower.split('<').any(|tag| {
+            let is_resource = tag.starts_with("script")
+                || tag.starts_with("img")
+                || tag.starts_with("iframe")
+                || (tag.starts_with("link") && tag.contains("stylesheet"));
+            is_resource && MIXED_CONTENT_NEEDLES.iter().any(|n| tag.contains(n))
+        });
+
+    SecurityPosture {
+        https,
+        mixed_content,
+        directory_listing: lower.contains("<title>index of /"),
+        exposed_files: Vec::new(),
+    }
+}
+
+/// Checks for common misconfigurations beyond the scraped page: HTTPS
+/// availability and publicly readable `.env` / `.git` files. Only the fact
+/// that something is exposed is recorded, never its contents.
+pub struct SecurityProbe {
+    http_client: reqwest::Client,
+    enabled: bool,
+}
+
+impl SecurityProbe {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(PROBE_TIMEOUT)
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .expect("Failed to create HTTP client"),
+            enabled,
+        }
+    }
+
+    /// Enabled unless SECURITY_PROBE is `false` or `0`
+    pub fn from_env() -> Self {
+        let enabled = std::env::var("SECURITY_PROBE")
+            .map(|v| !matches!(v.trim(), "false" | "0"))
+            .unwrap_or(true);
+        Self::new(enabled)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Complete the page-level posture from `parse_html` with network checks
+    pub async fn check(&self, info: StartupInfo) -> StartupInfo {
+        if !self.enabled {
+            return info;
+        }
+        let Ok(origin) = Url::parse(&info.url) else {
+            return info;
+        };
+
+        let (https, env_exposed, git_exposed) = tokio::join!(
+            self.serves_https(&origin),
+            self.exposed(&origin, "/.env", looks_like_env),
+            self.exposed(&origin, "/.git/HEAD", |body| body.starts_with("ref: refs/")),
+        );
+
+        let mut posture = info.security.clone().unwrap_or_default();
+        posture.https |= https;
+        posture.exposed_files = [(env_exposed, "/.env"), (git_exposed, "/.git")]
+            .into_iter()
+            .filter(|(exposed, _)| *exposed)
+            .map(|(_, path)| path.to_string())
+            .collect();
+
+        if posture.is_leaky() {
+            tracing::info!(url = %info.url, issues = ?posture.issues(crate::domain::Language::En), "Security posture issues found");
+        }
+        info.with_security(Some(posture))
+    }
+
+    async fn serves_https(&self, origin: &Url) -> bool {
+        if origin.scheme() == "https" {
+            return true;
+        }
+        let mut https = origin.clone();
+        if https.set_scheme("https").is_err() {
+            return false;
+        }
+        https.set_path("/");
+        self.http_client.head(https.as_str()).send().await.is_ok()
+    }
+
+    async fn exposed(&self, origin: &Url, path: &str, recognise: fn(&str) -> bool) -> bool {
+        let Ok(target) = origin.join(path) else {
+            return false;
+        };
+        let response = match self.http_client.get(target).send().await {
+            Ok(resp) if resp.status().is_success() => resp,
+            _ => return false,
+        };
+        let Ok(body) = read_capped(response, SNIFF_BYTES).await else {
+            return false;
+        };
+        let head = String::from_utf8_lossy(&body);
+        // Catch-all routes answer 200 with the app's HTML for any path
+        !head.to_lowercase().contains("<html") && recognise(&head)
+    }
+}
+
+impl Default for SecurityProbe {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+fn looks_like_env(body: &str) -> bool {
+    body.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .take(5)
+        .filter(|l| {
+            l.split_once('=').is_some_and(|(key, _)| {
+                !key.is_empty()
+                    && key
+                        .chars()
+                        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            })
+        })
+        .count()
+        >= 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_page_security() {
+        let html = r#"<html><head><title>Index of /</title>
+            <link rel="stylesheet" href="http://cdn.example.com/a.css"></head>
+            <body><a href="http://partner.example.com">partner</a></body></html>"#;
+        let posture = scan_page_security("https://example.com", html);
+        assert!(posture.https && posture.mixed_content && posture.directory_listing);
+
+        let clean = scan_page_security(
+            "https://example.com",
+            r#"<a href="http://partner.example.com">partner</a><img src="/logo.png">"#,
+        );
+        assert!(!clean.is_leaky());
+        assert!(looks_like_env("APP_KEY=abc\nDB_PASSWORD=secret\n"));
+        assert!(!looks_like_env("<!doctype html>"));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::domain::SeoAudit;
+use scraper::{Html, Selector};
+
+/// Shorter words like "ai" or "di" repeat naturally
+const MIN_REPEATED_WORD_CHARS: usize = 4;
+const TITLE_SEPARATORS: &[char] = &['|', ',', '·'];
+/// Dashes only separate when spaced, so "e-commerce" stays one piece
+const SPACED_SEPARATORS: &[&str] = &[" - ", " – ", " — ", ": "];
+
+/// Audit the on-page SEO basics of a parsed landing page
+pub fn audit_seo(document: &Html) -> SeoAudit {
+    let select = |css: &str| Selector::parse(css).expect("valid selector");
+    let text_of = |el: scraper::ElementRef| {
+        el.text()
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let title = document.select(&select("title")).next().map(text_of).unwrap_or_default();
+    let meta_description = document
+        .select(&select("meta[name]"))
+        .find(|el| el.value().attr("name").is_some_and(|n| n.eq_ignore_ascii_case("description")))
+        .and_then(|el| el.value().attr("content"))
+        .unwrap_or_default();
+    let noindex = document
+        .select(&select("meta[name]"))
+        .filter(|el| {
+            el.value()
+                .attr("name")
+                .is_some_and(|n| n.eq_ignore_ascii_case("robots") || n.eq_ignore_ascii_case("googlebot"))
+        })
+        .filter_map(|el| el.value().attr("content"))
+        .any(|content| {
+            let content = content.to_lowercase();
+            content.contains("noindex") || content.contains("none")
+        });
+
+    let h1s: Vec<String> = document.select(&select("h1")).map(text_of).collect();
+    let mut duplicate_h1s: Vec<String> = Vec::new();
+    for (i, h1) in h1s.iter().enumerate() {
+        if !h1.is_empty()
+            && h1s[..i].iter().any(|prev| prev.eq_ignore_ascii_case(h1))
+            && !duplicate_h1s.iter().any(|d| d.eq_ignore_ascii_case(h1))
+        {
+            duplicate_h1s.push(h1.clone());
+        }
+    }
+
+    let images: Vec<_> = document.select(&select("img")).collect();
+    let images_missing_alt = images.iter().filter(|img| img.value().attr("alt").is_none()).count();
+
+    SeoAudit {
+        title_missing: title.is_empty(),
+        title_chars: title.chars().count(),
+        title_segments: title_segments(&title),
+        repeated_title_word: repeated_word(&title),
+        meta_description_missing: meta_description.trim().is_empty(),
+        h1_count: h1s.len() as u32,
+        duplicate_h1s,
+        images: images.len() as u32,
+        images_missing_alt: images_missing_alt as u32,
+        noindex,
+    }
+}
+
+fn title_segments(title: &str) -> u32 {
+    let normalized = SPACED_SEPARATORS
+        .iter()
+        .fold(title.to_string(), |t, sep| t.replace(sep, "|"));
+    normalized
+        .split(TITLE_SEPARATORS)
+        .filter(|piece| !piece.trim().is_empty())
+        .count() as u32
+}
+
+/// First word of the title used three or more times
+fn repeated_word(title: &str) -> Option<String> {
+    let lower = title.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= MIN_REPEATED_WORD_CHARS)
+        .collect();
+    words
+        .iter()
+        .find(|word| words.iter().filter(|w| w == word).count() >= 3)
+        .map(|word| word.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_flags_stuffing_noindex_and_missing_basics() {
+        let html = r#"<html><head>
+            <title>Best Payroll | Payroll App | Payroll Software | HR | Indonesia</title>
+            <meta name="ROBOTS" content="NOINDEX, follow">
+            </head><body>
+            <h1>Payroll  made easy</h1><h1>Payroll made easy</h1><h1>Pricing</h1>
+            <img src="a.png"><img src="b.png"><img src="c.png" alt=""><img src="d.png">
+            </body></html>"#;
+        let audit = audit_seo(&Html::parse_document(html));
+
+        assert!(audit.noindex);
+        assert!(audit.meta_description_missing);
+        assert_eq!(audit.repeated_title_word.as_deref(), Some("payroll"));
+        assert_eq!(audit.title_segments, 5);
+        assert!(audit.is_keyword_stuffed());
+        assert_eq!(audit.h1_count, 3);
+        assert_eq!(audit.duplicate_h1s, vec!["Payroll made easy".to_string()]);
+        assert_eq!((audit.images, audit.images_missing_alt), (4, 3));
+
+        let clean = r#"<html><head><title>KopiKilat</title>
+            <meta name="description" content="Coffee in 10 minutes"></head>
+            <body><h1>Coffee in 10 minutes</h1><img src="a.png" alt="Latte"></body></html>"#;
+        let audit = audit_seo(&Html::parse_document(clean));
+        assert!(audit.issues(crate::domain::Language::En).is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+use super::website_scraper::same_site;
+use regex_lite::Regex;
+use std::sync::LazyLock;
+use url::Url;
+
+/// Sitemaps larger than this are cut off and parsed as far as they got
+pub const MAX_SITEMAP_BYTES: usize = 512 * 1024;
+/// Child sitemaps followed from a sitemap index
+pub const MAX_CHILD_SITEMAPS: usize = 2;
+/// What the sitemap protocol assumes when `<priority>` is missing
+const DEFAULT_PRIORITY: f32 = 0.5;
+
+static URL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<url\b[^>]*>(.*?)</url>").expect("valid regex"));
+static SITEMAP_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<sitemap\b[^>]*>(.*?)</sitemap>").expect("valid regex"));
+static LOC_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<loc>(.*?)</loc>").expect("valid regex"));
+static PRIORITY_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<priority>(.*?)</priority>").expect("valid regex"));
+
+/// One `<url>` entry of a sitemap
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapEntry {
+    pub loc: String,
+    pub priority: f32,
+}
+
+/// A parsed sitemap.xml: a `<urlset>` fills `entries`, a `<sitemapindex>`
+/// fills `children` with the sitemaps it points to
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sitemap {
+    pub entries: Vec<SitemapEntry>,
+    pub children: Vec<String>,
+}
+
+impl Sitemap {
+    /// Lenient on purpose: a truncated or sloppy sitemap still yields the
+    /// entries before the damage
+    pub fn parse(xml: &str) -> Self {
+        let entries = URL_RE
+            .captures_iter(xml)
+            .filter_map(|block| {
+                let block = &block[1];
+                let loc = tag_text(&LOC_RE, block)?;
+                let priority = tag_text(&PRIORITY_RE, block)
+                    .and_then(|p| p.parse::<f32>().ok())
+                    .filter(|p| (0.0..=1.0).contains(p))
+                    .unwrap_or(DEFAULT_PRIORITY);
+                Some(SitemapEntry { loc, priority })
+            })
+            .collect();
+        let children = SITEMAP_RE
+            .captures_iter(xml)
+            .filter_map(|block| tag_text(&LOC_RE, &block[1]))
+            .collect();
+        Self { entries, children }
+    }
+
+    /// Child sitemaps worth following, page sitemaps (`page-sitemap.xml`,
+    /// `sitemap-pages.xml`) before post and product ones. Gzipped children
+    /// are skipped, and so are children on another host than `site`, which
+    /// would otherwise let a sitemap send us to any server it names.
+    pub fn child_sitemaps(&self, site: &Url) -> Vec<Url> {
+        let mut children: Vec<Url> = self
+            .children
+            .iter()
+            .filter(|loc| !loc.ends_with(".gz"))
+            .filter_map(|loc| Url::parse(loc).ok())
+            .filter(|url| same_site(url, site))
+            .collect();
+        children.sort_by_key(|url| !url.path().to_lowercase().contains("page"));
+        children.truncate(MAX_CHILD_SITEMAPS);
+        children
+    }
+}
+
+fn tag_text(re: &Regex, block: &str) -> Option<String> {
+    let text = re.captures(block)?[1].trim().to_string();
+    let text = text
+        .strip_prefix("<![CDATA[")
+        .and_then(|t| t.strip_suffix("]]>"))
+        .map(str::trim)
+        .map(str::to_string)
+        .unwrap_or(text);
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_urlset_and_index() {
+        let urlset = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>https://example.com/</loc><priority>1.0</priority></url>
+  <url>
+    <loc><![CDATA[https://example.com/pricing?plan=a&amp;b]]></loc>
+    <priority>0.8</priority>
+  </url>
+  <url><loc>https://example.com/blog/post</loc><priority>high</priority></url>
+  <url><priority>0.9</priority></url>
+</urlset>"#;
+        let sitemap = Sitemap::parse(urlset);
+        assert!(sitemap.children.is_empty());
+        assert_eq!(
+            sitemap.entries,
+            vec![
+                SitemapEntry { loc: "https://example.com/".to_string(), priority: 1.0 },
+                SitemapEntry { loc: "https://example.com/pricing?plan=a&b".to_string(), priority: 0.8 },
+                SitemapEntry { loc: "https://example.com/blog/post".to_string(), priority: 0.5 },
+            ]
+        );
+
+        let index = r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>http://169.254.169.254/page-sitemap.xml</loc></sitemap>
+  <sitemap><loc>https://example.com/post-sitemap.xml</loc></sitemap>
+  <sitemap><loc>https://example.com/product-sitemap.xml.gz</loc></sitemap>
+  <sitemap><loc>https://example.com/page-sitemap.xml</loc></sitemap>
+  <sitemap><loc>https://example.com/category-sitemap.xml</loc></sitemap>
+</sitemapindex>"#;
+        let sitemap = Sitemap::parse(index);
+        assert!(sitemap.entries.is_empty());
+        let site = Url::parse("https://www.example.com/").unwrap();
+        let children: Vec<String> = sitemap.child_sitemaps(&site).iter().map(Url::to_string).collect();
+        // The off-site child is dropped before it can take a slot
+        assert_eq!(
+            children,
+            vec!["https://example.com/page-sitemap.xml", "https://example.com/post-sitemap.xml"]
+        );
+    }
+}
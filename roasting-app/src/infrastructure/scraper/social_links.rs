@@ -0,0 +1,118 @@
+use crate::domain::{SocialLink, SocialPlatform};
+use scraper::{Html, Selector};
+use url::Url;
+
+/// Where sites put their own profiles; share buttons and embedded posts
+/// elsewhere on the page often point at someone else's
+const CHROME_SELECTOR: &str = "header a[href], footer a[href], nav a[href]";
+/// First path segments that are app routes rather than profiles
+const RESERVED_SEGMENTS: &[&str] = &[
+    "p", "reel", "reels", "explore", "accounts", "stories", "share", "sharer", "intent", "home",
+    "hashtag", "search", "i", "tag", "discover", "login", "signup",
+];
+
+/// Profiles linked from the page header and footer, one per platform,
+/// falling back to the whole page when the chrome links none
+pub fn extract_social_links(document: &Html, base_url: &str) -> Vec<SocialLink> {
+    let base = Url::parse(base_url).ok();
+    let collect = |css: &str| {
+        let selector = Selector::parse(css).expect("valid selector");
+        let mut links: Vec<SocialLink> = Vec::new();
+        for href in document.select(&selector).filter_map(|el| el.value().attr("href")) {
+            let parsed = match &base {
+                Some(base) => base.join(href.trim()),
+                None => Url::parse(href.trim()),
+            };
+            let Some(link) = parsed.ok().and_then(|u| social_link(&u)) else {
+                continue;
+            };
+            if !links.iter().any(|l| l.platform == link.platform) {
+                links.push(link);
+            }
+        }
+        links
+    };
+
+    let links = collect(CHROME_SELECTOR);
+    if links.is_empty() {
+        collect("a[href]")
+    } else {
+        links
+    }
+}
+
+fn social_link(url: &Url) -> Option<SocialLink> {
+    let host = url.host_str()?.to_lowercase();
+    // www., m. and LinkedIn's country subdomains like id.linkedin.com
+    let host = ["instagram.com", "tiktok.com", "linkedin.com", "x.com", "twitter.com"]
+        .into_iter()
+        .find(|d| host == *d || host.ends_with(&format!(".{}", d)))?;
+    let mut segments = url.path_segments()?.filter(|s| !s.is_empty());
+    let first = segments.next()?;
+
+    let (platform, handle) = match host {
+        "instagram.com" => (SocialPlatform::Instagram, format!("@{}", profile_name(first)?)),
+        "tiktok.com" => {
+            let name = first.strip_prefix('@')?;
+            (SocialPlatform::TikTok, format!("@{}", profile_name(name)?))
+        }
+        "linkedin.com" => match first {
+            "company" | "in" | "school" => (SocialPlatform::LinkedIn, profile_name(segments.next()?)?.to_string()),
+            _ => return None,
+        },
+        "x.com" | "twitter.com" => (SocialPlatform::X, format!("@{}", profile_name(first)?)),
+        _ => return None,
+    };
+
+    Some(SocialLink {
+        platform,
+        url: format!("https://{}{}", url.host_str()?, url.path().trim_end_matches('/')),
+        handle,
+    })
+}
+
+/// The segment when it looks like an account name, not an app route
+fn profile_name(segment: &str) -> Option<&str> {
+    let valid = !segment.is_empty()
+        && segment.len() <= 100
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    (valid && !RESERVED_SEGMENTS.contains(&segment.to_lowercase().as_str())).then_some(segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_profiles_and_skips_share_links() {
+        let html = r#"<html><body>
+            <main>
+                <a href="https://twitter.com/intent/tweet?text=hi">Share</a>
+                <a href="https://www.instagram.com/someone_else/">Testimonial</a>
+            </main>
+            <footer>
+                <a href="https://www.instagram.com/acme.id/?hl=en">IG</a>
+                <a href="https://www.instagram.com/p/Cabc123/">Latest post</a>
+                <a href="https://www.tiktok.com/@acme">TikTok</a>
+                <a href="https://id.linkedin.com/company/acme-inc/">LinkedIn</a>
+                <a href="https://x.com/share?url=x">Share on X</a>
+                <a href="https://twitter.com/AcmeHQ">Twitter</a>
+            </footer>
+        </body></html>"#;
+        let links = extract_social_links(&Html::parse_document(html), "https://acme.id");
+        let found: Vec<(SocialPlatform, &str)> =
+            links.iter().map(|l| (l.platform, l.handle.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (SocialPlatform::Instagram, "@acme.id"),
+                (SocialPlatform::TikTok, "@acme"),
+                (SocialPlatform::LinkedIn, "acme-inc"),
+                (SocialPlatform::X, "@AcmeHQ"),
+            ]
+        );
+        assert_eq!(links[0].url, "https://www.instagram.com/acme.id");
+    }
+}
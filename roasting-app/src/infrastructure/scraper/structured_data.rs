@@ -0,0 +1,255 @@
+use crate::domain::{Offer, Rating, StructuredData};
+use scraper::{ElementRef, Html, Selector};
+use serde_json::Value;
+
+/// JSON-LD blocks past this are skipped rather than parsed
+const MAX_JSON_LD_BYTES: usize = 64 * 1024;
+const MAX_VALUE_CHARS: usize = 80;
+const MAX_FOUNDERS: usize = 5;
+const MAX_OFFERS: usize = 5;
+/// Nesting followed into `@graph`, offers and founders
+const MAX_DEPTH: usize = 6;
+
+const ORGANIZATION_TYPES: &[&str] = &["organization", "corporation", "localbusiness", "onlinebusiness", "ngo"];
+const PRODUCT_TYPES: &[&str] = &[
+    "product",
+    "softwareapplication",
+    "webapplication",
+    "mobileapplication",
+    "service",
+];
+
+/// Organization and Product facts from the page's JSON-LD blocks, with
+/// microdata filling in what JSON-LD left out. `None` when the page has
+/// neither.
+pub fn extract_structured_data(document: &Html) -> Option<StructuredData> {
+    let scripts = Selector::parse(r#"script[type="application/ld+json"]"#).expect("valid selector");
+    let mut data = StructuredData::default();
+    for script in document.select(&scripts) {
+        let raw = script.text().collect::<String>();
+        if raw.len() > MAX_JSON_LD_BYTES {
+            continue;
+        }
+        // Some CMSes wrap the JSON in an HTML comment or CDATA section
+        let raw = raw
+            .trim()
+            .trim_start_matches("<!--")
+            .trim_end_matches("-->")
+            .trim_start_matches("//<![CDATA[")
+            .trim_end_matches("//]]>");
+        match serde_json::from_str::<Value>(raw) {
+            Ok(value) => visit(&value, None, 0, &mut data),
+            Err(e) => tracing::debug!("Skipping malformed JSON-LD block: {}", e),
+        }
+    }
+    read_microdata(document, &mut data);
+    (!data.is_empty()).then_some(data)
+}
+
+fn visit(value: &Value, product: Option<&str>, depth: usize, data: &mut StructuredData) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let node = match value {
+        Value::Array(items) => {
+            for item in items {
+                visit(item, product, depth + 1, data);
+            }
+            return;
+        }
+        Value::Object(node) => node,
+        _ => return,
+    };
+    if let Some(graph) = node.get("@graph") {
+        visit(graph, product, depth + 1, data);
+    }
+
+    if has_type(value, ORGANIZATION_TYPES) {
+        fill(&mut data.organization, node.get("name"));
+        fill(&mut data.founding_date, node.get("foundingDate"));
+        fill(&mut data.employees, node.get("numberOfEmployees"));
+        for founder in as_list(node.get("founder")) {
+            if data.founders.len() < MAX_FOUNDERS {
+                if let Some(name) = text(founder).filter(|n| !data.founders.contains(n)) {
+                    data.founders.push(name);
+                }
+            }
+        }
+        read_rating(node.get("aggregateRating"), data);
+    }
+    if has_type(value, PRODUCT_TYPES) {
+        let name = node.get("name").and_then(text);
+        for offer in as_list(node.get("offers")) {
+            visit(offer, name.as_deref(), depth + 1, data);
+        }
+        read_rating(node.get("aggregateRating"), data);
+    }
+    if has_type(value, &["offer", "aggregateoffer"]) && data.offers.len() < MAX_OFFERS {
+        let price = node.get("price").or_else(|| node.get("lowPrice")).and_then(text);
+        if let Some(price) = price {
+            data.offers.push(Offer {
+                name: node.get("name").and_then(text).or_else(|| product.map(str::to_string)),
+                price,
+                currency: node.get("priceCurrency").and_then(text),
+            });
+        }
+    }
+    if let Some(publisher) = node.get("publisher") {
+        visit(publisher, product, depth + 1, data);
+    }
+}
+
+fn has_type(value: &Value, types: &[&str]) -> bool {
+    as_list(value.get("@type"))
+        .filter_map(Value::as_str)
+        .any(|t| {
+            let t = t.rsplit('/').next().unwrap_or(t).to_ascii_lowercase();
+            types.contains(&t.as_str())
+        })
+}
+
+/// A single value or an array of them
+fn as_list(value: Option<&Value>) -> impl Iterator<Item = &Value> {
+    let items: &[Value] = match value {
+        Some(Value::Array(items)) => items,
+        Some(single) => std::slice::from_ref(single),
+        None => &[],
+    };
+    items.iter()
+}
+
+/// A string, a number, or an object's `name`, `value` or `@value`
+fn text(value: &Value) -> Option<String> {
+    let raw = match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Object(node) => {
+            return ["name", "value", "@value"]
+                .iter()
+                .find_map(|key| node.get(*key))
+                .and_then(text)
+        }
+        Value::Array(items) => return items.first().and_then(text),
+        _ => return None,
+    };
+    clean(&raw)
+}
+
+fn clean(raw: &str) -> Option<String> {
+    let text = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then(|| text.chars().take(MAX_VALUE_CHARS).collect())
+}
+
+fn fill(field: &mut Option<String>, value: Option<&Value>) {
+    if field.is_none() {
+        *field = value.and_then(text);
+    }
+}
+
+fn read_rating(value: Option<&Value>, data: &mut StructuredData) {
+    let Some(rating) = value.filter(|_| data.rating.is_none()) else {
+        return;
+    };
+    let Some(score) = rating.get("ratingValue").and_then(text) else {
+        return;
+    };
+    let count = ["ratingCount", "reviewCount"]
+        .iter()
+        .filter_map(|key| rating.get(*key))
+        .find_map(|v| v.as_u64().or_else(|| v.as_str()?.trim().parse().ok()));
+    data.rating = Some(Rating {
+        value: score,
+        best: rating.get("bestRating").and_then(text),
+        count,
+    });
+}
+
+/// `itemprop` founding date, founders and prices for sites on microdata
+fn read_microdata(document: &Html, data: &mut StructuredData) {
+    let select = |css: &str| Selector::parse(css).expect("valid selector");
+    let prop_text = |el: ElementRef| {
+        el.value()
+            .attr("content")
+            .map(str::to_string)
+            .unwrap_or_else(|| el.text().collect::<String>())
+    };
+
+    if data.founding_date.is_none() {
+        data.founding_date = document
+            .select(&select(r#"[itemprop="foundingDate"]"#))
+            .next()
+            .and_then(|el| clean(&prop_text(el)));
+    }
+    if data.founders.is_empty() {
+        let name = select(r#"[itemprop="name"]"#);
+        data.founders = document
+            .select(&select(r#"[itemprop="founder"]"#))
+            .filter_map(|el| {
+                let el = el.select(&name).next().unwrap_or(el);
+                clean(&prop_text(el))
+            })
+            .take(MAX_FOUNDERS)
+            .collect();
+    }
+    if data.offers.is_empty() {
+        let currency = select(r#"[itemprop="priceCurrency"]"#);
+        let name = select(r#"[itemprop="name"]"#);
+        data.offers = document
+            .select(&select(r#"[itemprop="price"]"#))
+            .filter_map(|el| {
+                let price = clean(&prop_text(el))?;
+                let scope = el
+                    .ancestors()
+                    .filter_map(ElementRef::wrap)
+                    .find(|a| a.value().attr("itemscope").is_some());
+                Some(Offer {
+                    name: scope
+                        .and_then(|s| s.select(&name).next())
+                        .and_then(|n| clean(&prop_text(n))),
+                    price,
+                    currency: scope
+                        .and_then(|s| s.select(&currency).next())
+                        .and_then(|c| clean(&prop_text(c))),
+                })
+            })
+            .take(MAX_OFFERS)
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_ld_graph_and_microdata_prices() {
+        let html = r#"<html><head>
+            <script type="application/ld+json">{"@context": "https://schema.org", "@graph": [
+                {"@type": "Organization", "name": "Kecil Tech", "foundingDate": "2023",
+                 "founder": [{"@type": "Person", "name": "Budi"}, "Sari"],
+                 "numberOfEmployees": {"@type": "QuantitativeValue", "value": 4}},
+                {"@type": ["SoftwareApplication"], "name": "Kecil",
+                 "offers": {"@type": "Offer", "price": 99000, "priceCurrency": "IDR"},
+                 "aggregateRating": {"ratingValue": "4.9", "reviewCount": "12"}}
+            ]}</script>
+            <script type="application/ld+json">{not json</script>
+            </head><body>
+            <div itemscope itemtype="https://schema.org/Offer">
+                <span itemprop="name">Pro</span>
+                <meta itemprop="price" content="10"><meta itemprop="priceCurrency" content="USD">
+            </div></body></html>"#;
+        let data = extract_structured_data(&Html::parse_document(html)).unwrap();
+        assert_eq!(data.organization.as_deref(), Some("Kecil Tech"));
+        assert_eq!(data.founding_date.as_deref(), Some("2023"));
+        assert_eq!(data.founders, vec!["Budi", "Sari"]);
+        assert_eq!(data.employees.as_deref(), Some("4"));
+        assert_eq!(data.offers.len(), 1);
+        assert_eq!(data.offers[0].describe(), "Kecil: 99000 IDR");
+        assert_eq!(data.rating.as_ref().unwrap().count, Some(12));
+
+        let microdata = Html::parse_document(&html.replace("application/ld+json", "text/plain"));
+        let data = extract_structured_data(&microdata).unwrap();
+        assert_eq!(data.offers[0].describe(), "Pro: 10 USD");
+        assert!(extract_structured_data(&Html::parse_document("<p>hi</p>")).is_none());
+    }
+}
@@ -0,0 +1,347 @@
+use crate::domain::{TechCategory, TechStack, Technology};
+use regex_lite::Regex;
+use reqwest::header::HeaderMap;
+use std::sync::LazyLock;
+
+static GENERATOR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)<meta\s[^>]*name\s*=\s*["']generator["'][^>]*>"#).expect("valid regex")
+});
+static CONTENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)content\s*=\s*["']([^"']*)["']"#).expect("valid regex"));
+
+struct Fingerprint {
+    name: &'static str,
+    category: TechCategory,
+    /// Matched against the lowercased raw HTML, mostly script and asset URLs
+    html: &'static [&'static str],
+    /// Prefix of the `<meta name="generator">` content
+    generator: &'static [&'static str],
+    /// `(header, needle)`; an empty needle only asks for the header to be present
+    headers: &'static [(&'static str, &'static str)],
+    /// Cookie name prefixes from `Set-Cookie`
+    cookies: &'static [&'static str],
+}
+
+const FINGERPRINTS: &[Fingerprint] = &[
+    Fingerprint {
+        name: "Lovable",
+        category: TechCategory::AiBuilder,
+        html: &["cdn.gpteng.co", "gptengineer.js", "lovable-tagger", ".lovable.app"],
+        generator: &["lovable"],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Bolt.new",
+        category: TechCategory::AiBuilder,
+        html: &["bolt.new/", ".bolt.host"],
+        generator: &[],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "v0",
+        category: TechCategory::AiBuilder,
+        html: &["v0.dev/", ".vusercontent.net"],
+        generator: &["v0.dev", "v0.app"],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Bubble",
+        category: TechCategory::NoCode,
+        html: &["bubble_page_load_data", "/package/run_js/", "cdn.bubble.io", "meta.cdn.bubble.io"],
+        generator: &[],
+        headers: &[("x-bubble-perf", "")],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Webflow",
+        category: TechCategory::NoCode,
+        html: &["data-wf-page", "assets.website-files.com", "cdn.prod.website-files.com"],
+        generator: &["webflow"],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Framer",
+        category: TechCategory::NoCode,
+        html: &["framerusercontent.com", "data-framer-"],
+        generator: &["framer"],
+        headers: &[("server", "framer")],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Wix",
+        category: TechCategory::NoCode,
+        html: &["static.wixstatic.com", "static.parastorage.com"],
+        generator: &["wix.com"],
+        headers: &[("x-wix-request-id", "")],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Squarespace",
+        category: TechCategory::NoCode,
+        html: &["static1.squarespace.com", "assets.squarespace.com"],
+        generator: &["squarespace"],
+        headers: &[],
+        cookies: &["ss_cvr"],
+    },
+    Fingerprint {
+        name: "Carrd",
+        category: TechCategory::NoCode,
+        html: &["carrd.co/"],
+        generator: &["carrd"],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "WordPress",
+        category: TechCategory::Cms,
+        html: &["/wp-content/", "/wp-includes/", "/wp-json/"],
+        generator: &["wordpress"],
+        headers: &[("link", "api.w.org")],
+        cookies: &["wordpress_", "wp-settings"],
+    },
+    Fingerprint {
+        name: "Elementor",
+        category: TechCategory::Cms,
+        html: &["/plugins/elementor/", "elementor-kit-"],
+        generator: &["elementor"],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Ghost",
+        category: TechCategory::Cms,
+        html: &["ghost-portal", "/ghost/api/"],
+        generator: &["ghost"],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Shopify",
+        category: TechCategory::Ecommerce,
+        html: &["cdn.shopify.com", "shopify.theme"],
+        generator: &[],
+        headers: &[("x-shopid", ""), ("powered-by", "shopify")],
+        cookies: &["_shopify_"],
+    },
+    Fingerprint {
+        name: "WooCommerce",
+        category: TechCategory::Ecommerce,
+        html: &["/plugins/woocommerce/"],
+        generator: &["woocommerce"],
+        headers: &[],
+        cookies: &["woocommerce_"],
+    },
+    Fingerprint {
+        name: "Next.js",
+        category: TechCategory::Framework,
+        html: &["/_next/static/", "__next_data__"],
+        generator: &["next.js"],
+        headers: &[("x-powered-by", "next.js"), ("x-nextjs-cache", "")],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Nuxt",
+        category: TechCategory::Framework,
+        html: &["/_nuxt/", "window.__nuxt__"],
+        generator: &["nuxt"],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Gatsby",
+        category: TechCategory::Framework,
+        html: &["id=\"___gatsby\""],
+        generator: &["gatsby"],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "SvelteKit",
+        category: TechCategory::Framework,
+        html: &["__sveltekit", "data-sveltekit-"],
+        generator: &[],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Angular",
+        category: TechCategory::Framework,
+        html: &["ng-version="],
+        generator: &[],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Vite",
+        category: TechCategory::Framework,
+        html: &["/@vite/client", "type=\"module\" crossorigin src=\"/assets/index-"],
+        generator: &[],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Laravel",
+        category: TechCategory::Backend,
+        html: &[],
+        generator: &[],
+        headers: &[],
+        cookies: &["laravel_session"],
+    },
+    Fingerprint {
+        name: "Ruby on Rails",
+        category: TechCategory::Backend,
+        html: &["content=\"authenticity_token\""],
+        generator: &[],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Django",
+        category: TechCategory::Backend,
+        html: &["name=\"csrfmiddlewaretoken\""],
+        generator: &[],
+        headers: &[],
+        cookies: &["csrftoken"],
+    },
+    Fingerprint {
+        name: "Express",
+        category: TechCategory::Backend,
+        html: &[],
+        generator: &[],
+        headers: &[("x-powered-by", "express")],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "PHP",
+        category: TechCategory::Backend,
+        html: &[],
+        generator: &[],
+        headers: &[("x-powered-by", "php")],
+        cookies: &["phpsessid"],
+    },
+    Fingerprint {
+        name: "ASP.NET",
+        category: TechCategory::Backend,
+        html: &["__viewstate"],
+        generator: &[],
+        headers: &[("x-powered-by", "asp.net"), ("x-aspnet-version", "")],
+        cookies: &["asp.net_sessionid"],
+    },
+    Fingerprint {
+        name: "Vercel",
+        category: TechCategory::Hosting,
+        html: &[],
+        generator: &[],
+        headers: &[("server", "vercel"), ("x-vercel-id", "")],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Netlify",
+        category: TechCategory::Hosting,
+        html: &[],
+        generator: &[],
+        headers: &[("server", "netlify"), ("x-nf-request-id", "")],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "GitHub Pages",
+        category: TechCategory::Hosting,
+        html: &[],
+        generator: &[],
+        headers: &[("server", "github.com")],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Cloudflare",
+        category: TechCategory::Hosting,
+        html: &[],
+        generator: &[],
+        headers: &[("server", "cloudflare"), ("cf-ray", "")],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "jQuery",
+        category: TechCategory::Library,
+        html: &["jquery.min.js", "jquery-3.", "jquery/3."],
+        generator: &[],
+        headers: &[],
+        cookies: &[],
+    },
+    Fingerprint {
+        name: "Bootstrap",
+        category: TechCategory::Library,
+        html: &["bootstrap.min.css", "bootstrap.bundle.min.js"],
+        generator: &[],
+        headers: &[],
+        cookies: &[],
+    },
+];
+
+/// Fingerprint the site's stack from its HTML and, when the page was
+/// fetched directly, its response headers and cookies
+pub fn detect_tech_stack(html: &str, headers: &HeaderMap) -> TechStack {
+    let haystack = html.to_lowercase();
+    let generators: Vec<String> = GENERATOR_RE
+        .find_iter(html)
+        .filter_map(|tag| CONTENT_RE.captures(tag.as_str()))
+        .map(|c| c[1].trim().to_lowercase())
+        .collect();
+    let cookies: Vec<String> = headers
+        .get_all(reqwest::header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .filter_map(|v| v.split('=').next())
+        .map(|name| name.trim().to_lowercase())
+        .collect();
+
+    let technologies = FINGERPRINTS
+        .iter()
+        .filter(|f| {
+            f.html.iter().any(|n| haystack.contains(n))
+                || f.generator.iter().any(|g| generators.iter().any(|v| v.starts_with(g)))
+                || f.headers.iter().any(|(name, needle)| {
+                    headers
+                        .get_all(*name)
+                        .iter()
+                        .filter_map(|v| v.to_str().ok())
+                        .any(|v| v.to_lowercase().contains(needle))
+                })
+                || f.cookies.iter().any(|c| cookies.iter().any(|name| name.starts_with(c)))
+        })
+        .map(|f| Technology {
+            name: f.name.to_string(),
+            category: f.category,
+        })
+        .collect();
+
+    TechStack { technologies }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_detects_stack_from_html_headers_and_cookies() {
+        let html = r#"<html><head>
+            <meta content="WordPress 6.5" name="generator">
+            <link rel="stylesheet" href="/wp-content/plugins/elementor/assets/frontend.min.css">
+            <script src="https://cdn.gpteng.co/gptengineer.js" type="module"></script>
+            </head><body><h1>Hi</h1></body></html>"#;
+        let mut headers = HeaderMap::new();
+        headers.insert("server", HeaderValue::from_static("cloudflare"));
+        headers.append("set-cookie", HeaderValue::from_static("PHPSESSID=abc; path=/"));
+
+        let stack = detect_tech_stack(html, &headers);
+        let names: Vec<&str> = stack.technologies.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Lovable", "WordPress", "Elementor", "PHP", "Cloudflare"]);
+        assert!(stack.is_vibe_coded());
+
+        assert!(detect_tech_stack("<h1>Payroll for cats</h1>", &HeaderMap::new()).is_empty());
+    }
+}
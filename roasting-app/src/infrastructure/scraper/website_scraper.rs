@@ -1,14 +1,68 @@
-use crate::domain::StartupInfo;
+use super::ai_wrapper_detector::detect_ai_wrapper;
+use super::backoff::{is_transient_error, is_transient_status, retry_delay};
+use super::clearance::ClearanceStore;
+use super::config::ScraperConfig;
+use super::flaresolverr::FlareSolverr;
+#[cfg(feature = "headless")]
+use super::browser::{wait_for_content, wait_until_settled, BrowserSession};
+use super::header_profile::{HeaderProfile, ProfileRotator};
+use super::host_throttle::HostThrottle;
+use super::linkedin;
+use super::logo::extract_logo_candidates;
+use super::perf_probe::{collect_assets, measure_assets, page_performance};
+use super::proxy_rotator::ProxyRotator;
+use super::robots::{RobotsCache, RobotsRules, MAX_ROBOTS_BYTES, ROBOTS_AGENT};
+use super::security_probe::scan_page_security;
+use super::seo_audit::audit_seo;
+use super::sitemap::{Sitemap, MAX_SITEMAP_BYTES};
+use super::social_links::extract_social_links;
+use super::structured_data::extract_structured_data;
+use super::tech_detector::detect_tech_stack;
+use crate::domain::{
+    detect_country, ArchiveSnapshot, PageKind, PagePerformance, PageSection, ScrapeMethod, SiteFingerprint,
+    StartupInfo, SubsystemHealth,
+};
+use crate::infrastructure::circuit_breaker::CircuitBreaker;
+use crate::infrastructure::metrics::ScrapeMetrics;
 use roasting_errors::AppError;
 use scraper::{Html, Selector};
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Instant;
 use url::Url;
 
-const USER_AGENTS: &[&str] = &[
-    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36",
-    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36",
-    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36",
-];
+/// Title and headings sit near the top; no need for the whole page
+const MAX_FINGERPRINT_BYTES: usize = 256 * 1024;
+
+/// Consecutive failed browser sessions before headless scraping is paused
+const HEADLESS_FAILURE_THRESHOLD: u32 = 5;
+const HEADLESS_COOLDOWN: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Whole browser session for one headless scrape or screenshot
+#[cfg(feature = "headless")]
+const HEADLESS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+/// Waiting for the network to go quiet after the load event
+#[cfg(feature = "headless")]
+const SETTLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(8);
+#[cfg(feature = "headless")]
+const CHALLENGE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+#[cfg(feature = "headless")]
+const SPA_RENDER_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(6);
+
+/// Screenshots stop here on endless pages; CSS pixels, before scaling
+#[cfg(feature = "headless")]
+const MAX_SCREENSHOT_HEIGHT: f64 = 8000.0;
+/// Half the 1920px browser window is plenty for the roast page
+#[cfg(feature = "headless")]
+const SCREENSHOT_SCALE: f64 = 0.5;
+#[cfg(feature = "headless")]
+const MAX_SCREENSHOT_BYTES: usize = 3 * 1024 * 1024;
+
+/// HTML kept with the roast as its source snapshot, see `StartupInfo::raw_html`
+const MAX_SNAPSHOT_BYTES: usize = 1024 * 1024;
+
+/// Fallback reason when robots.txt disallows the page
+const ROBOTS_BLOCKED: &str = "robots.txt melarang crawler";
 
 const CLOUDFLARE_CHALLENGE_INDICATORS: &[&str] = &[
     "cf-browser-verification",
@@ -42,82 +96,297 @@ const SPA_INDICATORS: &[&str] = &[
     "initializing",
 ];
 
-#[derive(Serialize)]
-struct FlareSolverrRequest {
-    cmd: String,
-    url: String,
-    #[serde(rename = "maxTimeout")]
-    max_timeout: u32,
+/// Internet Archive availability API response
+#[derive(Deserialize)]
+struct WaybackAvailability {
+    #[serde(default)]
+    archived_snapshots: WaybackSnapshots,
 }
 
-#[derive(Deserialize)]
-struct FlareSolverrResponse {
-    status: String,
-    solution: Option<FlareSolverrSolution>,
+#[derive(Deserialize, Default)]
+struct WaybackSnapshots {
+    closest: Option<WaybackClosest>,
 }
 
 #[derive(Deserialize)]
-struct FlareSolverrSolution {
-    response: String,
+struct WaybackClosest {
+    available: bool,
+    status: String,
+    timestamp: String,
 }
 
 pub struct WebsiteScraper {
     http_client: reqwest::Client,
+    /// HEAD requests for page assets; never follows redirects
+    asset_client: reqwest::Client,
+    profiles: ProfileRotator,
+    /// Outbound proxies for requests to the scraped site (SCRAPE_PROXIES)
+    proxies: ProxyRotator,
+    /// Spacing between requests to one scraped host (SCRAPE_HOST_INTERVAL_MS)
+    throttle: HostThrottle,
+    /// Cookies from solved Cloudflare challenges, sent on direct fetches
+    clearance: ClearanceStore,
+    /// Tried first when FLARESOLVERR_URL is set
+    flaresolverr: Option<FlareSolverr>,
+    config: ScraperConfig,
+    /// Kill switch for browser-based fallbacks (HEADLESS_SCRAPING)
+    headless_enabled: bool,
+    headless_breaker: CircuitBreaker,
+    /// Browsers for the Cloudflare solver
+    #[cfg(feature = "headless")]
+    browser_pool: Arc<crate::infrastructure::cloudflare::BrowserPool>,
+    metrics: Option<Arc<ScrapeMetrics>>,
+    /// Skip pages robots.txt disallows (RESPECT_ROBOTS_TXT)
+    respect_robots: bool,
+    robots: RobotsCache,
 }
 
 impl WebsiteScraper {
     pub fn new() -> Self {
+        Self::with_config(ScraperConfig::from_env())
+    }
+
+    pub fn with_config(config: ScraperConfig) -> Self {
+        let http_client = config
+            .client_builder()
+            .build()
+            .expect("Failed to create HTTP client");
+        let asset_client = config
+            .client_builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to create HTTP client");
         Self {
-            http_client: reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(15))
-                .redirect(reqwest::redirect::Policy::limited(5))
-                .build()
-                .expect("Failed to create HTTP client"),
+            flaresolverr: FlareSolverr::from_env(http_client.clone()),
+            http_client,
+            asset_client,
+            profiles: ProfileRotator::from_env(),
+            proxies: ProxyRotator::from_env(|| config.client_builder()),
+            throttle: HostThrottle::from_env(),
+            clearance: ClearanceStore::default(),
+            headless_enabled: std::env::var("HEADLESS_SCRAPING")
+                .map(|v| !matches!(v.trim(), "false" | "0"))
+                .unwrap_or(true),
+            headless_breaker: CircuitBreaker::new(HEADLESS_FAILURE_THRESHOLD, HEADLESS_COOLDOWN),
+            #[cfg(feature = "headless")]
+            browser_pool: Arc::new(crate::infrastructure::cloudflare::BrowserPool::from_env()),
+            metrics: None,
+            respect_robots: std::env::var("RESPECT_ROBOTS_TXT")
+                .map(|v| matches!(v.trim(), "true" | "1"))
+                .unwrap_or(false),
+            robots: RobotsCache::default(),
+            config,
+        }
+    }
+
+    /// Client for requests to `host`: through its proxy, or direct
+    fn client_for(&self, host: &str) -> &reqwest::Client {
+        self.proxies
+            .for_domain(host)
+            .map(|proxy| &proxy.client)
+            .unwrap_or(&self.http_client)
+    }
+
+    /// Chrome `--proxy-server` for browser sessions on `host`
+    #[cfg(feature = "headless")]
+    fn browser_proxy(&self, host: &str) -> Option<String> {
+        self.proxies.for_domain(host).map(|proxy| proxy.server.clone())
+    }
+
+    /// Record each method tried for the scrape health report
+    pub fn with_metrics(mut self, metrics: Arc<ScrapeMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    fn record(&self, method: ScrapeMethod, url: &Url, started: Instant, error: Option<String>) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record(method, url.host_str().unwrap_or_default(), started, error);
         }
     }
 
+    /// Record a fallback method: it only counts as a success if it found real content
+    fn record_fallback(
+        &self,
+        method: ScrapeMethod,
+        url: &Url,
+        started: Instant,
+        result: Option<&StartupInfo>,
+    ) {
+        let error = match result {
+            Some(info) if !self.is_content_minimal(info) => None,
+            Some(_) => Some("minimal content".to_string()),
+            None => Some("no content".to_string()),
+        };
+        self.record(method, url, started, error);
+    }
+
+    /// Headless browser and FlareSolverr status for /healthz
+    pub fn health(&self) -> Vec<SubsystemHealth> {
+        let headless = if !cfg!(feature = "headless") {
+            SubsystemHealth::disabled("headless", "not compiled in")
+        } else if !self.headless_enabled {
+            SubsystemHealth::disabled("headless", "HEADLESS_SCRAPING=false")
+        } else if self.headless_breaker.is_open() {
+            SubsystemHealth::degraded(
+                "headless",
+                format!(
+                    "paused after {} failed browser sessions",
+                    self.headless_breaker.consecutive_failures()
+                ),
+            )
+        } else {
+            SubsystemHealth::active("headless")
+        };
+        let flaresolverr = match &self.flaresolverr {
+            Some(flaresolverr) => flaresolverr.health(),
+            None => SubsystemHealth::disabled("flaresolverr", "FLARESOLVERR_URL not set"),
+        };
+        let health = vec![headless, flaresolverr, self.proxies.health()];
+        #[cfg(feature = "headless")]
+        let health = health
+            .into_iter()
+            .chain(self.headless_enabled.then(|| self.browser_pool.health()))
+            .collect();
+        health
+    }
+
+    #[cfg(feature = "headless")]
+    fn headless_allowed(&self) -> bool {
+        self.headless_enabled && self.headless_breaker.allow()
+    }
+
     pub async fn scrape(&self, url: &str) -> Result<StartupInfo, AppError> {
         let parsed_url =
             Url::parse(url).map_err(|_| AppError::InvalidUrl("URL tidak valid".to_string()))?;
 
-        if let Some(flaresolverr_url) = std::env::var("FLARESOLVERR_URL").ok() {
-            if let Some(info) = self.try_flaresolverr(&flaresolverr_url, &parsed_url).await {
+        if self.respect_robots && !self.robots_allowed(&parsed_url).await {
+            tracing::info!("robots.txt disallows {}, using URL-only fallback", url);
+            self.record(
+                ScrapeMethod::UrlOnly,
+                &parsed_url,
+                Instant::now(),
+                Some(ROBOTS_BLOCKED.to_string()),
+            );
+            return Ok(self.create_fallback_info(&parsed_url, Some(ROBOTS_BLOCKED.to_string())));
+        }
+
+        // LinkedIn answers plain fetches with a 999 and FlareSolverr with the
+        // login wall, so company pages go straight to the browser
+        #[cfg(feature = "headless")]
+        if linkedin::company_slug(&parsed_url).is_some() {
+            let started = Instant::now();
+            let result = self.try_headless_scrape(&parsed_url).await;
+            self.record_fallback(ScrapeMethod::LinkedIn, &parsed_url, started, result.as_ref());
+            if let Some(info) = result.filter(|info| !self.is_content_minimal(info)) {
+                tracing::info!("Got LinkedIn company page {}", url);
+                return Ok(info);
+            }
+            tracing::warn!("LinkedIn company page {} not readable, trying the usual methods", url);
+        }
+
+        if let Some(flaresolverr) = &self.flaresolverr {
+            let started = Instant::now();
+            let result = self.try_flaresolverr(flaresolverr, &parsed_url).await;
+            self.record(
+                ScrapeMethod::FlareSolverr,
+                &parsed_url,
+                started,
+                result.is_none().then(|| "no solution".to_string()),
+            );
+            if let Some(info) = result {
                 tracing::info!("FlareSolverr succeeded for {}", url);
                 return Ok(info);
             }
             tracing::warn!("FlareSolverr failed for {}, falling back to direct scraping", url);
         }
 
-        match self.try_scrape(&parsed_url).await {
+        let started = Instant::now();
+        let result = self.try_scrape(&parsed_url).await;
+        self.record(
+            ScrapeMethod::Http,
+            &parsed_url,
+            started,
+            match &result {
+                Ok(info) if self.is_content_minimal(info) => Some("minimal content".to_string()),
+                Ok(_) => None,
+                Err(e) => Some(e.to_string()),
+            },
+        );
+        match result {
             Ok(info) => {
                 if self.is_content_minimal(&info) {
                     tracing::info!("Detected SPA or minimal content for {}", url);
 
                     #[cfg(feature = "headless")]
                     {
-                        if let Some(cf_info) = self.try_cloudflare_solver(&parsed_url) {
+                        let started = Instant::now();
+                        let cf_result = self.try_cloudflare_solver(&parsed_url).await;
+                        self.record_fallback(
+                            ScrapeMethod::CloudflareSolver,
+                            &parsed_url,
+                            started,
+                            cf_result.as_ref(),
+                        );
+                        if let Some(cf_info) = cf_result {
                             if !self.is_content_minimal(&cf_info) {
                                 tracing::info!("CloudflareSolver got content for {}", url);
-                                return Ok(cf_info);
+                                return Ok(carry_pages(cf_info, &info));
                             }
                         }
 
                         tracing::warn!("CloudflareSolver didn't help for {}, trying headless", url);
 
-                        if let Some(headless_info) = self.try_headless_scrape(&parsed_url) {
+                        let started = Instant::now();
+                        let headless_result = self.try_headless_scrape(&parsed_url).await;
+                        self.record_fallback(
+                            ScrapeMethod::Headless,
+                            &parsed_url,
+                            started,
+                            headless_result.as_ref(),
+                        );
+                        if let Some(headless_info) = headless_result {
                             if !self.is_content_minimal(&headless_info) {
                                 tracing::info!("Headless scraping got better content for {}", url);
-                                return Ok(headless_info);
+                                return Ok(carry_pages(headless_info, &info));
                             }
                         }
                     }
 
                     tracing::warn!("All browser methods failed for {}, trying Google Cache", url);
 
-                    if let Some(cache_info) = self.try_google_cache(&parsed_url).await {
+                    let started = Instant::now();
+                    let cache_result = self.try_google_cache(&parsed_url).await;
+                    self.record_fallback(
+                        ScrapeMethod::GoogleCache,
+                        &parsed_url,
+                        started,
+                        cache_result.as_ref(),
+                    );
+                    if let Some(cache_info) = cache_result {
                         if !self.is_content_minimal(&cache_info) {
                             tracing::info!("Google Cache got better content for {}", url);
-                            return Ok(cache_info);
+                            return Ok(carry_pages(cache_info, &info));
+                        }
+                    }
+
+                    let started = Instant::now();
+                    let wayback_result = self.try_wayback(&parsed_url).await;
+                    self.record_fallback(
+                        ScrapeMethod::Wayback,
+                        &parsed_url,
+                        started,
+                        wayback_result.as_ref(),
+                    );
+                    if let Some(wayback_info) = wayback_result {
+                        if !self.is_content_minimal(&wayback_info) {
+                            tracing::info!("Wayback Machine got better content for {}", url);
+                            // The site answered, it just had nothing to say
+                            let archived = wayback_info
+                                .archived
+                                .clone()
+                                .map(|snapshot| snapshot.with_live_site_up(true));
+                            return Ok(carry_pages(wayback_info.with_archived(archived), &info));
                         }
                     }
                 }
@@ -127,59 +396,242 @@ impl WebsiteScraper {
                 tracing::warn!("HTTP scraping failed for {}: {}", url, e);
 
                 #[cfg(feature = "headless")]
-                if let Some(info) = self.try_headless_scrape(&parsed_url) {
-                    tracing::info!("Headless scraping succeeded for {}", url);
-                    return Ok(info);
+                {
+                    let started = Instant::now();
+                    let headless_result = self.try_headless_scrape(&parsed_url).await;
+                    self.record_fallback(
+                        ScrapeMethod::Headless,
+                        &parsed_url,
+                        started,
+                        headless_result.as_ref(),
+                    );
+                    if let Some(info) = headless_result {
+                        tracing::info!("Headless scraping succeeded for {}", url);
+                        return Ok(info);
+                    }
                 }
 
-                if let Some(cache_info) = self.try_google_cache(&parsed_url).await {
+                let started = Instant::now();
+                let cache_result = self.try_google_cache(&parsed_url).await;
+                self.record_fallback(
+                    ScrapeMethod::GoogleCache,
+                    &parsed_url,
+                    started,
+                    cache_result.as_ref(),
+                );
+                if let Some(cache_info) = cache_result {
                     tracing::info!("Google Cache succeeded for {}", url);
                     return Ok(cache_info);
                 }
 
+                let started = Instant::now();
+                let wayback_result = self.try_wayback(&parsed_url).await;
+                self.record_fallback(
+                    ScrapeMethod::Wayback,
+                    &parsed_url,
+                    started,
+                    wayback_result.as_ref(),
+                );
+                if let Some(wayback_info) = wayback_result {
+                    tracing::info!("Wayback Machine succeeded for {}", url);
+                    return Ok(wayback_info);
+                }
+
                 tracing::warn!("All scraping methods failed for {}, using URL-only fallback", url);
+                self.record(
+                    ScrapeMethod::UrlOnly,
+                    &parsed_url,
+                    Instant::now(),
+                    Some(e.to_string()),
+                );
                 Ok(self.create_fallback_info(&parsed_url, Some(e.to_string())))
             }
         }
     }
 
-    async fn try_flaresolverr(&self, flaresolverr_url: &str, parsed_url: &Url) -> Option<StartupInfo> {
-        tracing::info!("Attempting FlareSolverr for {}", parsed_url);
+    /// Pre-flight for a submitted URL so one startup isn't stored under
+    /// several spellings: `http://` is tried as `https://` first, and
+    /// redirects that stay on the site are followed. A URL that can't be
+    /// reached either way is returned as is.
+    pub async fn resolve_url(&self, url: &str) -> String {
+        let Ok(parsed) = Url::parse(url) else {
+            return url.to_string();
+        };
+        let mut candidates = Vec::new();
+        if parsed.scheme() == "http" {
+            let mut https = parsed.clone();
+            if https.set_scheme("https").is_ok() {
+                if https.port() == Some(80) {
+                    let _ = https.set_port(None);
+                }
+                candidates.push(https);
+            }
+        }
+        candidates.push(parsed.clone());
 
-        let request = FlareSolverrRequest {
-            cmd: "request.get".to_string(),
-            url: parsed_url.to_string(),
-            max_timeout: 60000,
+        for candidate in candidates {
+            // Any answer, even a bot-protection 403, proves the scheme is served
+            let Some(mut resolved) = self.preflight(&candidate).await else {
+                continue;
+            };
+            resolved.set_fragment(None);
+            if !same_site(&resolved, &parsed) || !matches!(resolved.scheme(), "http" | "https") {
+                resolved = candidate;
+            }
+            if resolved != parsed {
+                tracing::info!("Resolved {} to {}", parsed, resolved);
+            }
+            return resolved.to_string();
+        }
+        url.to_string()
+    }
+
+    /// Title and headings of the landing page from one plain GET, for change
+    /// detection. `None` when robots.txt forbids it, the page is behind a
+    /// challenge, or there is nothing to compare.
+    pub async fn fingerprint(&self, url: &str) -> Option<SiteFingerprint> {
+        let parsed_url = Url::parse(url).ok()?;
+        if self.respect_robots && !self.robots_allowed(&parsed_url).await {
+            return None;
+        }
+        let body = match self
+            .fetch_page(&parsed_url, &parsed_url, MAX_FINGERPRINT_BYTES)
+            .await
+        {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::debug!("Fingerprint fetch for {} failed: {}", url, e);
+                return None;
+            }
+        };
+        let html = String::from_utf8_lossy(&body);
+        if self.is_cloudflare_challenge(&html) {
+            return None;
+        }
+        let document = Html::parse_document(&html);
+        let fingerprint = SiteFingerprint {
+            title: self.extract_title(&document).unwrap_or_default(),
+            headings: self.extract_headings(&document),
         };
+        (!fingerprint.is_empty()).then_some(fingerprint)
+    }
 
-        let response = self
-            .http_client
-            .post(format!("{}/v1", flaresolverr_url))
-            .json(&request)
-            .timeout(std::time::Duration::from_secs(70))
+    /// Where `url` ends up after redirects; HEAD first, GET for servers that refuse HEAD
+    async fn preflight(&self, url: &Url) -> Option<Url> {
+        let host = url.host_str().unwrap_or_default();
+        let profile = self.profiles.for_domain(host);
+        let client = self.client_for(host);
+        let timeout = std::time::Duration::from_secs(5);
+        self.throttle.wait(host).await;
+        let response = profile
+            .apply(client.head(url.as_str()))
+            .timeout(timeout)
             .send()
-            .await
-            .ok()?;
+            .await;
+        let response = match response {
+            Ok(response)
+                if response.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED
+                    || response.status() == reqwest::StatusCode::NOT_IMPLEMENTED =>
+            {
+                self.throttle.wait(host).await;
+                profile
+                    .apply(client.get(url.as_str()))
+                    .timeout(timeout)
+                    .send()
+                    .await
+            }
+            other => other,
+        };
+        match response {
+            Ok(response) => Some(response.url().clone()),
+            Err(e) => {
+                tracing::debug!("Pre-flight for {} failed: {}", url, e);
+                None
+            }
+        }
+    }
 
-        let result: FlareSolverrResponse = response.json().await.ok()?;
+    /// Whether the site's robots.txt lets us fetch `parsed_url`. A missing,
+    /// unreachable or oversized robots.txt allows everything.
+    async fn robots_allowed(&self, parsed_url: &Url) -> bool {
+        let origin = parsed_url.origin().ascii_serialization();
+        let rules = match self.robots.get(&origin) {
+            Some(rules) => rules,
+            None => {
+                let rules = self
+                    .fetch_robots(&origin, parsed_url.host_str().unwrap_or_default())
+                    .await;
+                self.robots.insert(&origin, rules)
+            }
+        };
+        let path = match parsed_url.query() {
+            Some(query) => format!("{}?{}", parsed_url.path(), query),
+            None => parsed_url.path().to_string(),
+        };
+        rules.is_allowed(&path)
+    }
 
-        if result.status != "ok" {
-            tracing::warn!("FlareSolverr returned non-ok status: {}", result.status);
-            return None;
+    async fn fetch_robots(&self, origin: &str, host: &str) -> RobotsRules {
+        self.throttle.wait(host).await;
+        let response = self
+            .client_for(host)
+            .get(format!("{}/robots.txt", origin))
+            .header("User-Agent", ROBOTS_AGENT)
+            .timeout(std::time::Duration::from_secs(5))
+            .send()
+            .await;
+        let text = match response {
+            // One byte over the cap tells an oversized file from one that fits exactly
+            Ok(response) if response.status().is_success() => read_text(response, MAX_ROBOTS_BYTES + 1).await.ok(),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::debug!("robots.txt for {} unreachable: {}", origin, e);
+                None
+            }
+        };
+        match text {
+            Some(text) if text.len() <= MAX_ROBOTS_BYTES => RobotsRules::parse(&text, ROBOTS_AGENT),
+            _ => RobotsRules::allow_all(),
         }
+    }
 
-        let html = result.solution?.response;
-        self.parse_html(parsed_url.as_str(), &html).ok()
+    async fn try_flaresolverr(&self, flaresolverr: &FlareSolverr, parsed_url: &Url) -> Option<StartupInfo> {
+        tracing::info!("Attempting FlareSolverr for {}", parsed_url);
+
+        let host = parsed_url.host_str().unwrap_or_default();
+        let solution = flaresolverr.get(parsed_url.as_str(), host).await?;
+        let cookies: Vec<(String, String)> = solution
+            .cookies
+            .into_iter()
+            .map(|c| (c.name, c.value))
+            .collect();
+        self.clearance.store(host, &cookies, &solution.user_agent);
+        self.parse_html(parsed_url.as_str(), &solution.response).ok()
     }
 
+    /// The solver drives a blocking browser with multi-second waits, so it
+    /// runs on the blocking pool instead of stalling the async workers
     #[cfg(feature = "headless")]
-    fn try_cloudflare_solver(&self, parsed_url: &Url) -> Option<StartupInfo> {
+    async fn try_cloudflare_solver(&self, parsed_url: &Url) -> Option<StartupInfo> {
         use crate::infrastructure::cloudflare::CloudflareSolver;
 
+        if !self.headless_allowed() {
+            return None;
+        }
         tracing::info!("Attempting CloudflareSolver for {}", parsed_url);
 
-        let solver = CloudflareSolver::new(20);
-        let result = solver.solve(parsed_url.as_str())?;
+        let host = parsed_url.host_str().unwrap_or_default();
+        let solver = CloudflareSolver::new(self.browser_pool.clone(), 20).with_proxy(self.browser_proxy(host));
+        let profile = self.profiles.for_headless(host);
+        let user_agent = profile.user_agent;
+        let url = parsed_url.to_string();
+        let result = match tokio::task::spawn_blocking(move || solver.solve(&url, profile)).await {
+            Ok(result) => result?,
+            Err(e) => {
+                tracing::error!("CloudflareSolver task for {} failed: {}", parsed_url, e);
+                return None;
+            }
+        };
 
         if !result.success {
             tracing::warn!("CloudflareSolver did not succeed for {}", parsed_url);
@@ -190,8 +642,16 @@ impl WebsiteScraper {
             "CloudflareSolver succeeded, got {} cookies",
             result.cookies.len()
         );
+        self.clearance.store(host, &result.cookies, user_agent);
 
-        self.parse_html(parsed_url.as_str(), &result.html).ok()
+        // The crawl before the challenge only got challenge pages; with the
+        // clearance stored it goes through directly
+        let info = self.parse_html(parsed_url.as_str(), &result.html).ok()?;
+        if self.config.max_pages == 0 {
+            return Some(info);
+        }
+        let pages = self.crawl_pages(parsed_url, &result.html).await;
+        Some(info.with_pages(pages))
     }
 
     async fn try_google_cache(&self, parsed_url: &Url) -> Option<StartupInfo> {
@@ -202,11 +662,16 @@ impl WebsiteScraper {
             urlencoding::encode(parsed_url.as_str())
         );
 
+        let profile = self
+            .profiles
+            .for_domain(parsed_url.host_str().unwrap_or_default());
+
         let response = match tokio::time::timeout(
             std::time::Duration::from_secs(5),
             self.http_client
                 .get(&cache_url)
-                .header("User-Agent", USER_AGENTS[0])
+                .header("User-Agent", profile.user_agent)
+                .header("Accept-Language", profile.accept_language)
                 .send()
         ).await {
             Ok(Ok(resp)) => resp,
@@ -227,7 +692,7 @@ impl WebsiteScraper {
 
         let html = match tokio::time::timeout(
             std::time::Duration::from_secs(5),
-            response.text()
+            read_text(response, self.config.max_body_bytes)
         ).await {
             Ok(Ok(text)) => text,
             _ => {
@@ -244,10 +709,70 @@ impl WebsiteScraper {
         self.parse_html(parsed_url.as_str(), &html).ok()
     }
 
+    /// Scrape the most recent archive.org snapshot, tagged so the roast knows
+    /// the live site was unreachable
+    async fn try_wayback(&self, parsed_url: &Url) -> Option<StartupInfo> {
+        tracing::info!("Attempting Wayback Machine for {}", parsed_url);
+
+        let availability: WaybackAvailability = match self
+            .http_client
+            .get("https://archive.org/wayback/available")
+            .query(&[("url", parsed_url.as_str())])
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await
+        {
+            Ok(response) => response.json().await.ok()?,
+            Err(e) => {
+                tracing::warn!("Wayback availability request failed: {}", e);
+                return None;
+            }
+        };
+        let closest = availability
+            .archived_snapshots
+            .closest
+            .filter(|c| c.available && c.status == "200")?;
+        if closest.timestamp.len() != 14 || !closest.timestamp.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        // `id_` serves the page as captured, without the Wayback toolbar and link rewriting
+        let raw_url = format!(
+            "https://web.archive.org/web/{}id_/{}",
+            closest.timestamp, parsed_url
+        );
+        let response = match self
+            .http_client
+            .get(&raw_url)
+            .timeout(self.config.request_timeout)
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                tracing::warn!("Wayback snapshot returned {}", response.status());
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!("Wayback snapshot request failed: {}", e);
+                return None;
+            }
+        };
+        let html = read_text(response, self.config.max_body_bytes).await.ok()?;
+
+        let snapshot = ArchiveSnapshot::new(
+            format!("https://web.archive.org/web/{}/{}", closest.timestamp, parsed_url),
+            &closest.timestamp,
+        );
+        self.parse_html(parsed_url.as_str(), &html)
+            .ok()
+            .map(|info| info.with_archived(Some(snapshot)))
+    }
+
     fn is_content_minimal(&self, info: &StartupInfo) -> bool {
         let has_headings = !info.headings.is_empty();
         let has_content = !info.content_summary.trim().is_empty() && info.content_summary.len() > 50;
-        let has_description = info.description.as_ref().map_or(false, |d| d.len() > 20);
+        let has_description = info.description.as_ref().is_some_and(|d| d.len() > 20);
 
         if has_headings && has_content {
             return false;
@@ -270,211 +795,519 @@ impl WebsiteScraper {
     }
 
     async fn try_scrape(&self, parsed_url: &Url) -> Result<StartupInfo, AppError> {
-        let ua_index = (std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs()
-            % USER_AGENTS.len() as u64) as usize;
+        let host = parsed_url.host_str().unwrap_or_default();
+        let profile = self.profiles.for_domain(host);
+        tracing::debug!("Using header profile {} for {}", profile.name, host);
 
-        let response = self
-            .http_client
-            .get(parsed_url.as_str())
-            .header("User-Agent", USER_AGENTS[ua_index])
-            .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8")
-            .header("Accept-Language", "id-ID,id;q=0.9,en-US;q=0.8,en;q=0.7")
-            .header("Accept-Encoding", "gzip, deflate, br")
-            .header("Connection", "keep-alive")
-            .header("Upgrade-Insecure-Requests", "1")
-            .header("Sec-Fetch-Dest", "document")
-            .header("Sec-Fetch-Mode", "navigate")
-            .header("Sec-Fetch-Site", "none")
-            .header("Sec-Fetch-User", "?1")
-            .header("Cache-Control", "max-age=0")
-            .send()
+        let (response, ttfb) = self
+            .fetch_landing(parsed_url, profile)
             .await
             .map_err(|e| AppError::ScrapingFailed(e.to_string()))?;
 
         let status = response.status();
         if status == reqwest::StatusCode::FORBIDDEN || status == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+            // This identity got flagged, present a different one (and IP) next time
+            self.profiles.rotate(host);
+            self.proxies.record(host, false);
+            self.proxies.rotate(host);
+            self.clearance.forget(host);
             return Err(AppError::ScrapingFailed("Cloudflare or bot protection detected".to_string()));
         }
+        self.proxies.record(host, true);
 
         if !status.is_success() {
             return Err(AppError::ScrapingFailed(format!("HTTP {}", status)));
         }
+        let headers = response.headers().clone();
 
-        let html = response
-            .text()
+        let html = read_text(response, self.config.max_body_bytes)
             .await
-            .map_err(|e| AppError::ScrapingFailed(e.to_string()))?;
+            .map_err(AppError::ScrapingFailed)?;
 
         if html.len() < 100 {
             return Err(AppError::ScrapingFailed("Empty or minimal content".to_string()));
         }
 
         if self.is_cloudflare_challenge(&html) {
+            self.clearance.forget(host);
             return Err(AppError::ScrapingFailed("Cloudflare challenge page detected".to_string()));
         }
 
-        self.parse_html(parsed_url.as_str(), &html)
+        // Fetched directly, so headers and cookies can sharpen the fingerprint
+        let info = self
+            .parse_html(parsed_url.as_str(), &html)?
+            .with_tech_stack(Some(detect_tech_stack(&html, &headers)));
+        // Thin SPA shells are crawled too: their sitemap still lists the real pages
+        let (performance, pages) = tokio::join!(
+            self.measure_performance(parsed_url, ttfb, &html),
+            async {
+                if self.config.max_pages == 0 {
+                    Vec::new()
+                } else {
+                    self.crawl_pages(parsed_url, &html).await
+                }
+            }
+        );
+        Ok(info.with_performance(Some(performance)).with_pages(pages))
     }
 
-    #[cfg(feature = "headless")]
-    fn try_headless_scrape(&self, parsed_url: &Url) -> Option<StartupInfo> {
-        use headless_chrome::{Browser, LaunchOptions};
+    /// GET the landing page, retrying connection failures, timeouts and
+    /// 429/502/504 up to `max_retries` times with jittered backoff. The
+    /// last transient response is returned as is once retries run out.
+    async fn fetch_landing(
+        &self,
+        parsed_url: &Url,
+        profile: &HeaderProfile,
+    ) -> Result<(reqwest::Response, std::time::Duration), reqwest::Error> {
+        let host = parsed_url.host_str().unwrap_or_default();
+        let mut attempt = 0;
+        loop {
+            self.throttle.wait(host).await;
+            let started = Instant::now();
+            let request = profile.apply(self.client_for(host).get(parsed_url.as_str()));
+            let result = self
+                .clearance
+                .apply(host, request)
+                .send()
+                .await;
+            let ttfb = started.elapsed();
+
+            let (delay, reason) = match result {
+                Ok(response) if is_transient_status(response.status()) && attempt < self.config.max_retries => {
+                    match retry_delay(attempt, Some(response.headers())) {
+                        Some(delay) => (delay, format!("HTTP {}", response.status())),
+                        None => return Ok((response, ttfb)),
+                    }
+                }
+                Ok(response) => return Ok((response, ttfb)),
+                Err(e) => {
+                    if e.is_connect() {
+                        self.proxies.record(host, false);
+                        self.proxies.rotate(host);
+                    }
+                    if !is_transient_error(&e) || attempt >= self.config.max_retries {
+                        return Err(e);
+                    }
+                    (retry_delay(attempt, None).unwrap_or_default(), e.to_string())
+                }
+            };
+            attempt += 1;
+            tracing::info!(
+                "Retrying {} in {:?} ({}/{}): {}",
+                parsed_url,
+                delay,
+                attempt,
+                self.config.max_retries,
+                reason
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
 
-        tracing::info!("Attempting stealth headless scrape for {}", parsed_url);
+    /// TTFB, weight and render-blocking scripts of the landing page; asset
+    /// sizes come from HEAD requests, the biggest suspects first
+    async fn measure_performance(
+        &self,
+        parsed_url: &Url,
+        ttfb: std::time::Duration,
+        html: &str,
+    ) -> PagePerformance {
+        let page = collect_assets(html, parsed_url);
+        let measured = measure_assets(&self.asset_client, &page.assets, self.config.max_asset_probes).await;
+        page_performance(ttfb, html.len(), &page, measured)
+    }
 
-        let stealth_args = vec![
-            std::ffi::OsStr::new("--disable-blink-features=AutomationControlled"),
-            std::ffi::OsStr::new("--disable-features=IsolateOrigins,site-per-process"),
-            std::ffi::OsStr::new("--disable-site-isolation-trials"),
-            std::ffi::OsStr::new("--disable-web-security"),
-            std::ffi::OsStr::new("--disable-features=BlockInsecurePrivateNetworkRequests"),
-            std::ffi::OsStr::new("--no-first-run"),
-            std::ffi::OsStr::new("--no-default-browser-check"),
-            std::ffi::OsStr::new("--disable-default-apps"),
-            std::ffi::OsStr::new("--disable-popup-blocking"),
-            std::ffi::OsStr::new("--disable-extensions"),
-            std::ffi::OsStr::new("--disable-component-extensions-with-background-pages"),
-            std::ffi::OsStr::new("--disable-background-networking"),
-            std::ffi::OsStr::new("--disable-sync"),
-            std::ffi::OsStr::new("--disable-translate"),
-            std::ffi::OsStr::new("--metrics-recording-only"),
-            std::ffi::OsStr::new("--mute-audio"),
-            std::ffi::OsStr::new("--no-pings"),
-            std::ffi::OsStr::new("--window-size=1920,1080"),
-            std::ffi::OsStr::new("--start-maximized"),
-            std::ffi::OsStr::new("--lang=id-ID"),
-        ];
+    /// Fetch the pricing, about, careers and blog pages the landing page
+    /// links to, one of each, until `max_pages` or the byte budget runs out.
+    /// Kinds the landing page doesn't link to are looked up in sitemap.xml.
+    async fn crawl_pages(&self, parsed_url: &Url, html: &str) -> Vec<PageSection> {
+        let mut links = self.discover_pages(parsed_url, html);
+        if links.len() < self.config.max_pages {
+            for (kind, url) in self.sitemap_pages(parsed_url).await {
+                if links.len() >= self.config.max_pages {
+                    break;
+                }
+                if !links.iter().any(|(k, _)| *k == kind) {
+                    links.push((kind, url));
+                }
+            }
+            links.sort_by_key(|(kind, _)| PageKind::ALL.iter().position(|k| k == kind));
+        }
+        let mut budget = self.config.crawl_budget;
+        let mut pages = Vec::new();
+        for (kind, url) in links {
+            if budget == 0 {
+                break;
+            }
+            if self.respect_robots && !self.robots_allowed(&url).await {
+                continue;
+            }
+            match self.fetch_page(parsed_url, &url, budget).await {
+                Ok(body) => {
+                    budget -= body.len();
+                    if let Some(page) = self.parse_page(kind, &url, &body) {
+                        pages.push(page);
+                    }
+                }
+                Err(e) => tracing::debug!("Skipping {} page {}: {}", kind.as_str(), url, e),
+            }
+        }
+        pages
+    }
 
-        let use_visible_browser = std::env::var("VISIBLE_BROWSER").is_ok();
+    /// Same-site links whose path looks like a page kind, the shortest path
+    /// per kind, in `PageKind::ALL` order
+    fn discover_pages(&self, parsed_url: &Url, html: &str) -> Vec<(PageKind, Url)> {
+        let Ok(selector) = Selector::parse("a[href]") else {
+            return Vec::new();
+        };
+        let document = Html::parse_document(html);
+        let mut found: Vec<(PageKind, Url)> = Vec::new();
+        for href in document.select(&selector).filter_map(|a| a.value().attr("href")) {
+            let Ok(mut url) = parsed_url.join(href) else {
+                continue;
+            };
+            url.set_fragment(None);
+            url.set_query(None);
+            if !matches!(url.scheme(), "http" | "https")
+                || !same_site(&url, parsed_url)
+                || url.path() == parsed_url.path()
+            {
+                continue;
+            }
+            let Some(kind) = PageKind::from_path(url.path()) else {
+                continue;
+            };
+            match found.iter_mut().find(|(k, _)| *k == kind) {
+                Some(existing) if url.path().len() < existing.1.path().len() => existing.1 = url,
+                Some(_) => {}
+                None => found.push((kind, url)),
+            }
+        }
+        found.sort_by_key(|(kind, _)| PageKind::ALL.iter().position(|k| k == kind));
+        found.truncate(self.config.max_pages);
+        found
+    }
 
-        let launch_options = LaunchOptions::default_builder()
-            .headless(!use_visible_browser)
-            .sandbox(false)
-            .idle_browser_timeout(std::time::Duration::from_secs(90))
-            .args(stealth_args)
-            .build()
-            .ok()?;
+    /// Pages from the site's sitemap.xml that look like a page kind, the
+    /// highest `<priority>` per kind (shortest path on a tie), best first.
+    /// A sitemap index is followed one level down.
+    async fn sitemap_pages(&self, parsed_url: &Url) -> Vec<(PageKind, Url)> {
+        let Ok(sitemap_url) = parsed_url.join("/sitemap.xml") else {
+            return Vec::new();
+        };
+        let Some(sitemap) = self.fetch_sitemap(parsed_url, &sitemap_url).await else {
+            return Vec::new();
+        };
+        let mut entries = sitemap.entries.clone();
+        for child_url in sitemap.child_sitemaps(parsed_url) {
+            if let Some(child) = self.fetch_sitemap(parsed_url, &child_url).await {
+                entries.extend(child.entries);
+            }
+        }
 
-        if use_visible_browser {
-            tracing::info!("Using visible browser mode for better Cloudflare bypass");
+        let mut found: Vec<(PageKind, f32, Url)> = Vec::new();
+        for entry in entries {
+            let Ok(mut url) = Url::parse(&entry.loc) else {
+                continue;
+            };
+            url.set_fragment(None);
+            url.set_query(None);
+            if !same_site(&url, parsed_url) || url.path() == parsed_url.path() {
+                continue;
+            }
+            let Some(kind) = PageKind::from_path(url.path()) else {
+                continue;
+            };
+            match found.iter_mut().find(|(k, _, _)| *k == kind) {
+                Some(existing)
+                    if entry.priority > existing.1
+                        || (entry.priority == existing.1 && url.path().len() < existing.2.path().len()) =>
+                {
+                    *existing = (kind, entry.priority, url)
+                }
+                Some(_) => {}
+                None => found.push((kind, entry.priority, url)),
+            }
         }
+        found.sort_by(|a, b| b.1.total_cmp(&a.1));
+        tracing::debug!("sitemap.xml of {} lists {} page kinds", parsed_url, found.len());
+        found.into_iter().map(|(kind, _, url)| (kind, url)).collect()
+    }
 
-        let browser = Browser::new(launch_options).ok()?;
-        let tab = browser.new_tab().ok()?;
+    async fn fetch_sitemap(&self, site: &Url, url: &Url) -> Option<Sitemap> {
+        let host = url.host_str().unwrap_or_default();
+        self.throttle.wait(host).await;
+        let request = self
+            .client_for(site.host_str().unwrap_or_default())
+            .get(url.as_str())
+            .header("User-Agent", ROBOTS_AGENT);
+        let response = self
+            .clearance
+            .apply(host, request)
+            .timeout(std::time::Duration::from_secs(5))
+            .send()
+            .await;
+        let response = match response {
+            Ok(response) if response.status().is_success() && same_site(response.url(), site) => response,
+            Ok(_) => return None,
+            Err(e) => {
+                tracing::debug!("Sitemap {} unreachable: {}", url, e);
+                return None;
+            }
+        };
+        let body = read_capped(response, MAX_SITEMAP_BYTES).await.ok()?;
+        Some(Sitemap::parse(&String::from_utf8_lossy(&body)))
+    }
 
-        let ua = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36";
-        let _ = tab.set_user_agent(ua, None, None);
+    /// The page body, cut off at `budget` bytes. Redirects off the site and
+    /// non-HTML responses are skipped.
+    async fn fetch_page(&self, site: &Url, url: &Url, budget: usize) -> Result<Vec<u8>, String> {
+        let host = url.host_str().unwrap_or_default();
+        let profile = self.profiles.for_domain(host);
+        self.throttle.wait(host).await;
+        let request = profile.apply(self.client_for(host).get(url.as_str()));
+        let response = self
+            .clearance
+            .apply(host, request)
+            .timeout(std::time::Duration::from_secs(8))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        if !same_site(response.url(), site) {
+            return Err("redirected off the site".to_string());
+        }
+        let is_html = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_none_or(|v| v.contains("html"));
+        if !is_html {
+            return Err("not HTML".to_string());
+        }
+        read_capped(response, budget).await
+    }
 
-        let stealth_js = r#"
-            Object.defineProperty(navigator, 'webdriver', {
-                get: () => undefined,
-                configurable: true
-            });
-            delete navigator.__proto__.webdriver;
-
-            Object.defineProperty(navigator, 'plugins', {
-                get: () => {
-                    const plugins = [
-                        { name: 'Chrome PDF Plugin', filename: 'internal-pdf-viewer' },
-                        { name: 'Chrome PDF Viewer', filename: 'mhjfbmdgcfjbbpaeojofohoefgiehjai' },
-                        { name: 'Native Client', filename: 'internal-nacl-plugin' }
-                    ];
-                    plugins.length = 3;
-                    return plugins;
-                }
-            });
+    fn parse_page(&self, kind: PageKind, url: &Url, body: &[u8]) -> Option<PageSection> {
+        let html = String::from_utf8_lossy(body);
+        if self.is_cloudflare_challenge(&html) {
+            self.clearance.forget(url.host_str().unwrap_or_default());
+            return None;
+        }
+        let document = Html::parse_document(&html);
+        let headings = self.extract_headings(&document);
+        // Pricing tables and job lists live in headings as much as in paragraphs
+        let mut content = headings.iter().skip(1).cloned().collect::<Vec<_>>().join("; ");
+        if !content.is_empty() {
+            content.push_str(". ");
+        }
+        content.push_str(&self.extract_content_summary(&document, self.config.max_page_content_bytes));
+        let content = truncate_bytes(content.trim(), self.config.max_page_content_bytes);
+        if content.is_empty() {
+            return None;
+        }
+        Some(PageSection {
+            kind,
+            url: url.to_string(),
+            title: headings.into_iter().next().or_else(|| self.extract_title(&document)),
+            content,
+        })
+    }
 
-            Object.defineProperty(navigator, 'languages', {
-                get: () => ['id-ID', 'id', 'en-US', 'en']
+    #[cfg(feature = "headless")]
+    async fn try_headless_scrape(&self, parsed_url: &Url) -> Option<StartupInfo> {
+        if !self.headless_allowed() {
+            tracing::debug!("Headless scraping switched off or paused, skipping {}", parsed_url);
+            return None;
+        }
+        let result = tokio::time::timeout(HEADLESS_TIMEOUT, self.run_headless_scrape(parsed_url))
+            .await
+            .unwrap_or_else(|_| {
+                tracing::warn!("Headless scrape of {} timed out", parsed_url);
+                None
             });
+        self.headless_breaker.track(result.ok_or(())).ok()
+    }
 
-            window.chrome = {
-                runtime: {
-                    PlatformOs: { MAC: 'mac', WIN: 'win', ANDROID: 'android', CROS: 'cros', LINUX: 'linux', OPENBSD: 'openbsd' },
-                    PlatformArch: { ARM: 'arm', X86_32: 'x86-32', X86_64: 'x86-64' },
-                    PlatformNaclArch: { ARM: 'arm', X86_32: 'x86-32', X86_64: 'x86-64' },
-                    RequestUpdateCheckStatus: { THROTTLED: 'throttled', NO_UPDATE: 'no_update', UPDATE_AVAILABLE: 'update_available' },
-                    OnInstalledReason: { INSTALL: 'install', UPDATE: 'update', CHROME_UPDATE: 'chrome_update', SHARED_MODULE_UPDATE: 'shared_module_update' },
-                    OnRestartRequiredReason: { APP_UPDATE: 'app_update', OS_UPDATE: 'os_update', PERIODIC: 'periodic' }
-                }
+    /// Check that the browser launches and opens a tab. Each headless scrape
+    /// launches its own browser, so this only catches a missing or broken
+    /// Chrome before the first roast needs it.
+    pub async fn check_headless(&self) -> SubsystemHealth {
+        #[cfg(feature = "headless")]
+        {
+            if !self.headless_enabled {
+                return SubsystemHealth::disabled("headless", "HEADLESS_SCRAPING=false");
+            }
+            let session = match BrowserSession::launch(None).await {
+                Ok(session) => session,
+                Err(e) => return SubsystemHealth::degraded("headless", format!("browser failed to launch: {}", e)),
             };
+            let health = match session.open_blank().await {
+                Ok(()) => SubsystemHealth::active("headless"),
+                Err(e) => SubsystemHealth::degraded("headless", format!("no tab: {}", e)),
+            };
+            session.close().await;
+            health
+        }
+        #[cfg(not(feature = "headless"))]
+        SubsystemHealth::disabled("headless", "not compiled in")
+    }
 
-            Object.defineProperty(navigator, 'permissions', {
-                get: () => ({
-                    query: (params) => Promise.resolve({ state: 'granted', onchange: null })
-                })
-            });
-
-            const originalQuery = window.navigator.permissions.query;
-            window.navigator.permissions.query = (parameters) => (
-                parameters.name === 'notifications' ?
-                    Promise.resolve({ state: Notification.permission }) :
-                    originalQuery(parameters)
-            );
-
-            Object.defineProperty(navigator, 'maxTouchPoints', { get: () => 0 });
-            Object.defineProperty(navigator, 'hardwareConcurrency', { get: () => 8 });
-            Object.defineProperty(navigator, 'deviceMemory', { get: () => 8 });
+    /// Start a full-page WebP screenshot of the live site in the background.
+    /// `None` without the headless browser, while it is switched off or
+    /// paused, or with SCRAPE_SCREENSHOTS=false.
+    pub fn start_screenshot(&self, url: &str) -> Option<tokio::task::JoinHandle<Option<Vec<u8>>>> {
+        #[cfg(feature = "headless")]
+        {
+            let enabled = std::env::var("SCRAPE_SCREENSHOTS")
+                .map(|v| !matches!(v.trim(), "false" | "0"))
+                .unwrap_or(true);
+            if !enabled || !self.headless_allowed() {
+                return None;
+            }
+            let parsed_url = Url::parse(url).ok()?;
+            let host = parsed_url.host_str().unwrap_or_default();
+            let profile = self.profiles.for_headless(host);
+            let proxy = self.browser_proxy(host);
+            Some(tokio::spawn(async move {
+                let session = match BrowserSession::launch(proxy.as_deref()).await {
+                    Ok(session) => session,
+                    Err(e) => {
+                        tracing::warn!("Browser for screenshot of {} failed to launch: {}", parsed_url, e);
+                        return None;
+                    }
+                };
+                let image = tokio::time::timeout(
+                    HEADLESS_TIMEOUT,
+                    Self::capture_screenshot(&session, &parsed_url, profile),
+                )
+                .await
+                .ok()
+                .flatten();
+                session.close().await;
+                image
+            }))
+        }
+        #[cfg(not(feature = "headless"))]
+        {
+            let _ = url;
+            None
+        }
+    }
 
-            const getParameter = WebGLRenderingContext.prototype.getParameter;
-            WebGLRenderingContext.prototype.getParameter = function(parameter) {
-                if (parameter === 37445) return 'Intel Inc.';
-                if (parameter === 37446) return 'Intel Iris OpenGL Engine';
-                return getParameter.call(this, parameter);
-            };
-        "#;
-
-        use headless_chrome::protocol::cdp::Page;
-        let add_script = Page::AddScriptToEvaluateOnNewDocument {
-            source: stealth_js.to_string(),
-            world_name: None,
-            include_command_line_api: None,
-            run_immediately: None,
+    #[cfg(feature = "headless")]
+    async fn capture_screenshot(
+        session: &BrowserSession,
+        parsed_url: &Url,
+        profile: &HeaderProfile,
+    ) -> Option<Vec<u8>> {
+        use chromiumoxide::cdp::browser_protocol::page::{CaptureScreenshotFormat, Viewport};
+        use chromiumoxide::page::ScreenshotParams;
+
+        let page = match session.open(parsed_url, profile).await {
+            Ok(page) => page,
+            Err(e) => {
+                tracing::warn!("Navigation for screenshot of {} failed: {}", parsed_url, e);
+                return None;
+            }
         };
-        let _ = tab.call_method(add_script);
+        // Late images, web fonts and client-side rendering
+        wait_until_settled(&page, SETTLE_TIMEOUT).await;
 
-        tab.navigate_to(parsed_url.as_str()).ok()?;
+        let html = page.content().await.ok()?.to_lowercase();
+        if CLOUDFLARE_CHALLENGE_INDICATORS.iter().any(|i| html.contains(i)) {
+            tracing::info!("Skipping screenshot of {}: challenge page", parsed_url);
+            return None;
+        }
 
-        if tab.wait_until_navigated().is_err() {
-            tracing::warn!("Navigation timeout for {}", parsed_url);
+        let content = page.layout_metrics().await.ok()?.css_content_size;
+        let params = ScreenshotParams::builder()
+            .format(CaptureScreenshotFormat::Webp)
+            .quality(70)
+            .clip(Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: content.width,
+                height: content.height.min(MAX_SCREENSHOT_HEIGHT),
+                scale: SCREENSHOT_SCALE,
+            })
+            .from_surface(true)
+            .capture_beyond_viewport(true)
+            .build();
+        let image = page.screenshot(params).await.ok()?;
+        if image.len() > MAX_SCREENSHOT_BYTES {
+            tracing::info!("Screenshot of {} too large ({} bytes)", parsed_url, image.len());
+            return None;
         }
+        Some(image)
+    }
 
-        std::thread::sleep(std::time::Duration::from_secs(3));
+    #[cfg(feature = "headless")]
+    async fn run_headless_scrape(&self, parsed_url: &Url) -> Option<StartupInfo> {
+        tracing::info!("Attempting stealth headless scrape for {}", parsed_url);
 
-        let html = tab.get_content().ok()?;
+        let host = parsed_url.host_str().unwrap_or_default();
+        let session = match BrowserSession::launch(self.browser_proxy(host).as_deref()).await {
+            Ok(session) => session,
+            Err(e) => {
+                tracing::warn!("Browser failed to launch: {}", e);
+                return None;
+            }
+        };
+        let html = self.render_page(&session, parsed_url, self.profiles.for_headless(host)).await;
+        session.close().await;
+        self.parse_html(parsed_url.as_str(), &html?).ok()
+    }
+
+    /// Load the page and wait for it to settle, for a Cloudflare challenge to
+    /// clear and for an SPA shell to render its text
+    #[cfg(feature = "headless")]
+    async fn render_page(
+        &self,
+        session: &BrowserSession,
+        parsed_url: &Url,
+        profile: &HeaderProfile,
+    ) -> Option<String> {
+        let page = match session.open(parsed_url, profile).await {
+            Ok(page) => page,
+            Err(e) => {
+                tracing::warn!("Navigation to {} failed: {}", parsed_url, e);
+                return None;
+            }
+        };
+        wait_until_settled(&page, SETTLE_TIMEOUT).await;
+        let html = page.content().await.ok()?;
 
         if self.is_cloudflare_challenge(&html) {
             tracing::info!("Cloudflare detected, waiting for challenge to auto-solve...");
-
-            for attempt in 1..=4 {
-                std::thread::sleep(std::time::Duration::from_secs(5));
-                let html = tab.get_content().ok()?;
-
-                if !self.is_cloudflare_challenge(&html) {
-                    tracing::info!("Cloudflare bypassed after {} attempts", attempt);
-                    return self.parse_html(parsed_url.as_str(), &html).ok();
-                }
-
-                tracing::info!("Cloudflare still present, attempt {}/4", attempt);
+            let started = Instant::now();
+            let (html, solved) = wait_for_content(&page, CHALLENGE_TIMEOUT, |html| !self.is_cloudflare_challenge(html))
+                .await
+                .ok()?;
+            if !solved {
+                tracing::warn!("Cloudflare challenge not bypassed for {}", parsed_url);
+                return None;
             }
-
-            tracing::warn!("Cloudflare challenge not bypassed for {}", parsed_url);
-            return None;
+            tracing::info!("Cloudflare bypassed after {:?}", started.elapsed());
+            wait_until_settled(&page, SETTLE_TIMEOUT).await;
+            return page.content().await.ok().or(Some(html));
         }
 
         if self.is_spa_loading(&html) {
             tracing::info!("SPA still loading, waiting for client-side render...");
-            std::thread::sleep(std::time::Duration::from_secs(4));
-            let html = tab.get_content().ok()?;
-            return self.parse_html(parsed_url.as_str(), &html).ok();
+            let (html, _) = wait_for_content(&page, SPA_RENDER_TIMEOUT, |html| !self.is_spa_loading(html))
+                .await
+                .ok()?;
+            return Some(html);
         }
 
-        self.parse_html(parsed_url.as_str(), &html).ok()
+        Some(html)
     }
 
+    #[cfg(feature = "headless")]
     fn is_spa_loading(&self, html: &str) -> bool {
         let lower = html.to_lowercase();
         let has_spa_marker = SPA_INDICATORS.iter().any(|i| lower.contains(i));
@@ -538,9 +1371,12 @@ impl WebsiteScraper {
 
         let reason = error_reason.unwrap_or_else(|| "tidak dapat diakses".to_string());
         let is_cloudflare = reason.to_lowercase().contains("cloudflare");
+        let blocks_crawlers = reason == ROBOTS_BLOCKED;
 
         let protection_note = if is_cloudflare {
             "Website ini dilindungi Cloudflare (takut banget di-scrape, pasti ada yang disembunyiin)"
+        } else if blocks_crawlers {
+            "Website ini melarang crawler lewat robots.txt (segitu takutnya dibaca orang)"
         } else {
             "Website tidak dapat diakses"
         };
@@ -600,25 +1436,69 @@ impl WebsiteScraper {
         );
 
         StartupInfo::new(url.to_string())
-            .with_title(Some(format!("{} - [{}]", main_name.to_uppercase(), if is_cloudflare { "Cloudflare Protected" } else { "Unreachable" })))
+            .with_title(Some(format!("{} - [{}]", main_name.to_uppercase(), if is_cloudflare { "Cloudflare Protected" } else if blocks_crawlers { "Blocks Crawlers" } else { "Unreachable" })))
             .with_description(Some(description_parts.join(", ")))
             .with_headings(headings)
             .with_content_summary(content)
+            .with_country(detect_country(domain, None, "").map(str::to_string))
     }
 
     fn parse_html(&self, url: &str, html: &str) -> Result<StartupInfo, AppError> {
+        let is_linkedin = Url::parse(url).is_ok_and(|u| linkedin::company_slug(&u).is_some());
+        if is_linkedin {
+            if let Some(info) = linkedin::parse_company_page(url, html) {
+                return Ok(info.with_raw_html(Some(truncate_bytes(html, MAX_SNAPSHOT_BYTES))));
+            }
+        }
+
         let document = Html::parse_document(html);
 
         let title = self.extract_title(&document);
         let description = self.extract_meta_description(&document);
         let headings = self.extract_headings(&document);
-        let content_summary = self.extract_content_summary(&document);
+        let content_summary = self.extract_content_summary(&document, self.config.max_content_bytes);
+        let country = self.extract_country(url, &document);
 
         Ok(StartupInfo::new(url.to_string())
             .with_title(title)
             .with_description(description)
             .with_headings(headings)
-            .with_content_summary(content_summary))
+            .with_content_summary(content_summary)
+            .with_ai_wrapper(Some(detect_ai_wrapper(html)))
+            .with_security(Some(scan_page_security(url, html)))
+            .with_seo(Some(audit_seo(&document)))
+            .with_social_links(Some(extract_social_links(&document, url)))
+            .with_logo_candidates(extract_logo_candidates(&document, url))
+            .with_tech_stack(Some(detect_tech_stack(html, &reqwest::header::HeaderMap::new())))
+            .with_structured_data(extract_structured_data(&document))
+            .with_country(country)
+            .with_raw_html(Some(truncate_bytes(html, MAX_SNAPSHOT_BYTES))))
+    }
+
+    /// Country from the TLD, `<html lang>`, and the footer, address and
+    /// `tel:` links where contact details usually live
+    fn extract_country(&self, url: &str, document: &Html) -> Option<String> {
+        let host = Url::parse(url).ok()?.host_str()?.to_string();
+        let html_lang = Selector::parse("html")
+            .ok()
+            .and_then(|sel| document.select(&sel).next())
+            .and_then(|el| el.value().attr("lang"));
+
+        let mut contact = String::new();
+        if let Ok(selector) = Selector::parse("footer, address") {
+            for element in document.select(&selector) {
+                contact.extend(element.text());
+                contact.push(' ');
+            }
+        }
+        if let Ok(selector) = Selector::parse("a[href^='tel:']") {
+            for element in document.select(&selector) {
+                contact.push_str(element.value().attr("href").unwrap_or_default());
+                contact.push(' ');
+            }
+        }
+
+        detect_country(&host, html_lang, &contact).map(str::to_string)
     }
 
     fn extract_title(&self, document: &Html) -> Option<String> {
@@ -657,30 +1537,79 @@ impl WebsiteScraper {
         headings
     }
 
-    fn extract_content_summary(&self, document: &Html) -> String {
+    fn extract_content_summary(&self, document: &Html, max_bytes: usize) -> String {
         let selector = Selector::parse("p").ok();
         let mut content = String::new();
 
         if let Some(sel) = selector {
-            for element in document.select(&sel).take(5) {
+            for element in document.select(&sel).take(self.config.max_paragraphs) {
                 let text = element.text().collect::<String>().trim().to_string();
                 if !text.is_empty() && text.len() > 20 {
                     content.push_str(&text);
                     content.push(' ');
                 }
-                if content.len() > 500 {
+                if content.len() > max_bytes {
                     break;
                 }
             }
         }
 
-        if content.len() > 500 {
-            content.truncate(500);
-            content.push_str("...");
+        // Generous cap only; prompts trim to their own token budgets
+        truncate_bytes(&content, max_bytes)
+    }
+}
+
+/// A fallback's content with the pages crawled next to the thin landing
+/// page, unless the fallback brought its own. The live site's timings are
+/// kept too, since fallbacks render the page somewhere else.
+fn carry_pages(better: StartupInfo, thin: &StartupInfo) -> StartupInfo {
+    let better = if better.performance.is_none() {
+        better.with_performance(thin.performance.clone())
+    } else {
+        better
+    };
+    if better.pages.is_empty() {
+        better.with_pages(thin.pages.clone())
+    } else {
+        better
+    }
+}
+
+/// The response body, cut off at `cap` bytes without downloading the rest
+pub(crate) async fn read_capped(mut response: reqwest::Response, cap: usize) -> Result<Vec<u8>, String> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        let take = chunk.len().min(cap - body.len());
+        body.extend_from_slice(&chunk[..take]);
+        if body.len() >= cap {
+            break;
         }
+    }
+    Ok(body)
+}
+
+/// The body as text, cut off at `cap` bytes
+async fn read_text(response: reqwest::Response, cap: usize) -> Result<String, String> {
+    let body = read_capped(response, cap).await?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Same host, ignoring a `www.` prefix on either side
+pub(super) fn same_site(a: &Url, b: &Url) -> bool {
+    let host = |u: &Url| u.host_str().map(|h| h.trim_start_matches("www.").to_lowercase());
+    host(a).is_some() && host(a) == host(b)
+}
 
-        content
+/// At most `max_bytes` of `text` on a char boundary, with `...` when cut
+fn truncate_bytes(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
     }
+    format!("{}...", &text[..end])
 }
 
 impl Default for WebsiteScraper {
@@ -0,0 +1,187 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// The resolved address of whoever sent a request, stored as a request
+/// extension and provided to server functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+/// An address or CIDR range, e.g. `10.0.0.0/8` or `::1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IpRange {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl IpRange {
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr.trim(), Some(prefix.trim().parse::<u8>().ok()?)),
+            None => (s.trim(), None),
+        };
+        let network: IpAddr = addr.parse().ok()?;
+        let max = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max);
+        (prefix <= max).then_some(Self { network, prefix })
+    }
+
+    /// IPv4-mapped IPv6 addresses, as a dual-stack listener reports IPv4
+    /// peers, match IPv4 ranges
+    pub(crate) fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The bare address for a single host, otherwise `network/prefix`
+impl std::fmt::Display for IpRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let max = if self.network.is_ipv4() { 32 } else { 128 };
+        if self.prefix == max {
+            write!(f, "{}", self.network)
+        } else {
+            write!(f, "{}/{}", self.network, self.prefix)
+        }
+    }
+}
+
+/// Reverse proxies whose `X-Forwarded-For` / `X-Real-IP` headers are
+/// believed (TRUSTED_PROXIES, comma-separated addresses or CIDR ranges,
+/// loopback by default). Headers from anyone else are ignored, otherwise
+/// a client could dodge the rate limit by making up its own address.
+#[derive(Debug, Clone)]
+pub struct TrustedProxies {
+    ranges: Vec<IpRange>,
+}
+
+impl TrustedProxies {
+    pub fn new(ranges: &str) -> Self {
+        let ranges = ranges
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .filter_map(|s| {
+                let range = IpRange::parse(s);
+                if range.is_none() {
+                    tracing::warn!("Ignoring invalid TRUSTED_PROXIES entry: {}", s.trim());
+                }
+                range
+            })
+            .collect();
+        Self { ranges }
+    }
+
+    pub fn from_env() -> Self {
+        match std::env::var("TRUSTED_PROXIES") {
+            Ok(ranges) => Self::new(&ranges),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.ranges.iter().any(|range| range.contains(ip))
+    }
+
+    /// The client behind `peer`, the address the connection came from.
+    /// `X-Forwarded-For` is read right to left, skipping our own proxies,
+    /// since only the entries they appended can be believed. When every
+    /// readable hop is one of ours, the leftmost of them is the client.
+    /// `X-Real-IP` is only read from proxies that don't send
+    /// `X-Forwarded-For` at all, since others may pass it through untouched.
+    pub fn resolve(&self, peer: IpAddr, forwarded_for: Option<&str>, real_ip: Option<&str>) -> IpAddr {
+        let peer = peer.to_canonical();
+        if !self.is_trusted(peer) {
+            return peer;
+        }
+        let Some(forwarded_for) = forwarded_for else {
+            return real_ip.and_then(parse_hop).unwrap_or(peer);
+        };
+        let mut client = peer;
+        for hop in forwarded_for.rsplit(',').map_while(parse_hop) {
+            client = hop;
+            if !self.is_trusted(hop) {
+                break;
+            }
+        }
+        client
+    }
+}
+
+/// One address from a forwarding header, with any `:port` dropped
+fn parse_hop(hop: &str) -> Option<IpAddr> {
+    let hop = hop.trim();
+    hop.parse::<IpAddr>()
+        .or_else(|_| hop.parse::<SocketAddr>().map(|addr| addr.ip()))
+        .or_else(|_| hop.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>())
+        .ok()
+        .map(|ip| ip.to_canonical())
+}
+
+impl Default for TrustedProxies {
+    fn default() -> Self {
+        Self {
+            ranges: vec![
+                IpRange { network: IpAddr::V4(Ipv4Addr::LOCALHOST), prefix: 8 },
+                IpRange { network: IpAddr::V6(Ipv6Addr::LOCALHOST), prefix: 128 },
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_client_ip() {
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        let proxies = TrustedProxies::new("127.0.0.1, 10.0.0.0/8, bogus");
+        assert_eq!(proxies.ranges.len(), 2);
+
+        // Untrusted peers can't pick their own address
+        assert_eq!(proxies.resolve(ip("203.0.113.7"), Some("1.2.3.4"), Some("1.2.3.4")), ip("203.0.113.7"));
+        // Spoofed leftmost entries are skipped past the first untrusted hop
+        assert_eq!(
+            proxies.resolve(ip("127.0.0.1"), Some("1.2.3.4, 198.51.100.9, 10.1.2.3"), None),
+            ip("198.51.100.9")
+        );
+        assert_eq!(proxies.resolve(ip("10.0.0.2"), None, Some(" 198.51.100.9 ")), ip("198.51.100.9"));
+        assert_eq!(proxies.resolve(ip("127.0.0.1"), Some("garbage"), None), ip("127.0.0.1"));
+        // X-Real-IP is ignored once X-Forwarded-For is there, even if it only lists our proxies
+        assert_eq!(
+            proxies.resolve(ip("127.0.0.1"), Some("10.0.0.5, 10.0.0.6"), Some("1.2.3.4")),
+            ip("10.0.0.5")
+        );
+        assert_eq!(proxies.resolve(ip("127.0.0.1"), Some("garbage, 10.0.0.6"), Some("1.2.3.4")), ip("10.0.0.6"));
+        // Ports are dropped
+        assert_eq!(
+            proxies.resolve(ip("127.0.0.1"), Some("198.51.100.9:51234, 10.0.0.6:80"), None),
+            ip("198.51.100.9")
+        );
+        assert_eq!(proxies.resolve(ip("127.0.0.1"), Some("[2001:db8::1]:443"), None), ip("2001:db8::1"));
+        assert!(TrustedProxies::default().is_trusted(ip("::1")));
+    }
+
+    #[test]
+    fn test_ipv4_mapped_peers_match_ipv4_ranges() {
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        let range = IpRange::parse("203.0.113.0/24").unwrap();
+        assert!(range.contains(ip("::ffff:203.0.113.7")));
+        assert!(!range.contains(ip("::ffff:198.51.100.9")));
+
+        // A dual-stack listener sees the local proxy as ::ffff:127.0.0.1
+        let proxies = TrustedProxies::default();
+        assert_eq!(
+            proxies.resolve(ip("::ffff:127.0.0.1"), Some("198.51.100.9"), None),
+            ip("198.51.100.9")
+        );
+        assert_eq!(proxies.resolve(ip("::ffff:203.0.113.7"), None, None), ip("203.0.113.7"));
+    }
+}
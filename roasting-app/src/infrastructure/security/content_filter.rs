@@ -0,0 +1,231 @@
+use regex_lite::Regex;
+use std::fmt;
+
+/// Slurs that are never acceptable, regardless of how roast-y the tone is.
+/// Casual swearing (anjir, bangsat, goblok) is part of the style and not listed.
+const DEFAULT_SLURS: &[&str] = &[
+    "nigger", "nigga", "chink", "faggot", "tranny", "retard", "bencong", "banci",
+];
+
+/// Terms that attack suku, agama, ras, or antargolongan (SARA)
+const DEFAULT_SARA: &[&str] = &[
+    "kafir",
+    "aseng",
+    "cina loh",
+    "dasar cina",
+    "dasar jawa",
+    "dasar batak",
+    "kadrun",
+    "onta arab",
+    "pribumi asli",
+    "antek asing",
+    "babi kristen",
+    "teroris islam",
+];
+
+/// Swearing that is fine in a normal roast but masked in family-safe mode
+const DEFAULT_PROFANITY: &[&str] = &[
+    "anjir", "anjay", "anjrit", "anjing", "bangsat", "goblok", "goblog", "tolol", "bego",
+    "kampret", "sialan", "brengsek", "bajingan", "keparat", "tai", "taik", "asu", "jancuk",
+    "jancok", "cuk", "cok", "pukimak", "kontol", "memek", "ngentot", "damn", "dammit", "hell",
+    "crap", "shit", "bullshit", "fuck", "fucking", "wtf", "ass", "bitch",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentViolation {
+    Slur(String),
+    Sara(String),
+    Doxxing(&'static str),
+    Classifier,
+}
+
+impl fmt::Display for ContentViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Slur(term) => write!(f, "slur: {}", term),
+            Self::Sara(term) => write!(f, "SARA: {}", term),
+            Self::Doxxing(kind) => write!(f, "doxxing: {}", kind),
+            Self::Classifier => write!(f, "flagged by safety classifier"),
+        }
+    }
+}
+
+/// Post-generation check for slurs, SARA content, and personal data in
+/// roast text. The word list is extended from `MODERATION_WORDLIST`.
+pub struct ContentFilter {
+    slurs: Vec<String>,
+    sara: Vec<String>,
+    doxxing: Vec<(&'static str, Regex)>,
+    profanity: Vec<&'static str>,
+    llm_check: bool,
+}
+
+impl ContentFilter {
+    pub fn new(extra_terms: Vec<String>, llm_check: bool) -> Self {
+        let mut slurs: Vec<String> = DEFAULT_SLURS.iter().map(|t| t.to_string()).collect();
+        slurs.extend(
+            extra_terms
+                .into_iter()
+                .map(|t| normalize(&t))
+                .filter(|t| !t.is_empty()),
+        );
+
+        Self {
+            slurs,
+            sara: DEFAULT_SARA.iter().map(|t| t.to_string()).collect(),
+            doxxing: vec![
+                (
+                    "phone number",
+                    Regex::new(r"(?:\+62|\b62|\b0)[\s-]?8\d{2}[\s-]?\d{3,4}[\s-]?\d{3,5}\b")
+                        .unwrap(),
+                ),
+                ("NIK", Regex::new(r"\b\d{16}\b").unwrap()),
+                (
+                    "home address",
+                    Regex::new(r"(?i)\b(?:rumahnya|alamat rumah|tinggal) di jl\.?\s").unwrap(),
+                ),
+            ],
+            profanity: DEFAULT_PROFANITY.to_vec(),
+            llm_check,
+        }
+    }
+
+    /// Reads extra terms (one per line, `#` for comments) from the file at
+    /// MODERATION_WORDLIST and the classifier toggle from MODERATION_LLM_CHECK
+    pub fn from_env() -> Self {
+        let extra_terms = match std::env::var("MODERATION_WORDLIST") {
+            Ok(path) => match std::fs::read_to_string(&path) {
+                Ok(contents) => contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(str::to_string)
+                    .collect(),
+                Err(e) => {
+                    tracing::warn!("Failed to read moderation word list {}: {}", path, e);
+                    Vec::new()
+                }
+            },
+            Err(_) => Vec::new(),
+        };
+        let llm_check = std::env::var("MODERATION_LLM_CHECK")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        Self::new(extra_terms, llm_check)
+    }
+
+    /// Whether generated text should also go through the LLM safety classifier
+    pub fn llm_check(&self) -> bool {
+        self.llm_check
+    }
+
+    pub fn check(&self, text: &str) -> Result<(), ContentViolation> {
+        // Pad with spaces so terms only match on word boundaries
+        let normalized = format!(" {} ", normalize(text));
+
+        if let Some(term) = find_term(&normalized, &self.slurs) {
+            return Err(ContentViolation::Slur(term));
+        }
+        if let Some(term) = find_term(&normalized, &self.sara) {
+            return Err(ContentViolation::Sara(term));
+        }
+        if let Some((kind, _)) = self.doxxing.iter().find(|(_, re)| re.is_match(text)) {
+            return Err(ContentViolation::Doxxing(kind));
+        }
+        Ok(())
+    }
+}
+
+impl ContentFilter {
+    /// Mask profanity for family-safe roasts, keeping the first letter
+    /// ("goblok" becomes "g*****") so the sentence still reads naturally
+    pub fn scrub_profanity(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut word = String::new();
+        for c in text.chars() {
+            if c.is_alphanumeric() {
+                word.push(c);
+            } else {
+                self.push_masked(&mut out, &word);
+                word.clear();
+                out.push(c);
+            }
+        }
+        self.push_masked(&mut out, &word);
+        out
+    }
+
+    fn push_masked(&self, out: &mut String, word: &str) {
+        if !self.profanity.contains(&word.to_lowercase().as_str()) {
+            out.push_str(word);
+            return;
+        }
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.push(first);
+            out.extend(chars.map(|_| '*'));
+        }
+    }
+}
+
+impl Default for ContentFilter {
+    fn default() -> Self {
+        Self::new(Vec::new(), false)
+    }
+}
+
+/// Lowercase and collapse everything that is not a letter or digit into single spaces
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn find_term(normalized: &str, terms: &[String]) -> Option<String> {
+    terms
+        .iter()
+        .find(|term| normalized.contains(&format!(" {} ", term)))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_flags_slurs_sara_and_doxxing() {
+        let filter = ContentFilter::new(vec!["Kampret Banget".to_string()], false);
+
+        assert!(filter.check("Anjir, landing page lo goblok banget").is_ok());
+        assert!(filter.check("Produk buat para bancakan kantor").is_ok());
+        assert_eq!(
+            filter.check("Founder-nya BANCI, bro"),
+            Err(ContentViolation::Slur("banci".to_string()))
+        );
+        assert_eq!(
+            filter.check("UI-nya kampret-banget"),
+            Err(ContentViolation::Slur("kampret banget".to_string()))
+        );
+        assert!(matches!(
+            filter.check("Startup buat para kafir"),
+            Err(ContentViolation::Sara(_))
+        ));
+        assert_eq!(
+            filter.check("Telepon aja CEO-nya di 0812-3456-7890"),
+            Err(ContentViolation::Doxxing("phone number"))
+        );
+        assert!(filter.check("Raised 2024, 10000 users").is_ok());
+    }
+
+    #[test]
+    fn test_scrub_profanity() {
+        let filter = ContentFilter::default();
+        assert_eq!(
+            filter.scrub_profanity("Anjir, UI-nya goblok! Hellofresh aman."),
+            "A****, UI-nya g*****! Hellofresh aman."
+        );
+    }
+}
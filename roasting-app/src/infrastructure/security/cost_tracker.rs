@@ -1,49 +1,305 @@
+use super::model_pricing::{ModelPricing, PricingTable};
+use crate::domain::RoastCost;
+use crate::infrastructure::db::DailyUsageRepository;
 use chrono::{DateTime, Utc};
-use std::sync::atomic::{AtomicU32, Ordering};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Mutex;
 
-const DAILY_REQUEST_LIMIT: u32 = 100;
-const ESTIMATED_COST_PER_REQUEST_CENTS: u32 = 5;
-const DAILY_COST_LIMIT_CENTS: u32 = 500;
+const DEFAULT_DAILY_REQUEST_LIMIT: u32 = 100;
+/// Headroom kept free for the next request until real roasts have been
+/// costed, since a roast's cost is only known once the provider reports usage
+const DEFAULT_ROAST_COST_MICROS: u64 = 50_000;
+/// Recent roasts whose average cost is the headroom kept for the next one
+const ROAST_COST_SAMPLES: usize = 20;
+const DEFAULT_DAILY_COST_LIMIT_MICROS: u64 = 5_000_000;
+/// Per-request usage records kept for the admin stats endpoint
+const RECENT_USAGE_LIMIT: usize = 50;
+
+/// Token counts reported by the provider for one completion
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageRecord {
+    pub at: DateTime<Utc>,
+    pub model: String,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModelUsage {
+    pub requests: u32,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Snapshot of today's spend for the admin stats endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct CostStats {
+    pub since: DateTime<Utc>,
+    pub requests: u32,
+    pub request_limit: u32,
+    pub cost_usd: f64,
+    pub cost_limit_usd: f64,
+    /// Headroom a new request needs under the cost limit
+    pub estimated_roast_cost_usd: f64,
+    pub by_model: HashMap<String, ModelUsage>,
+    pub recent: Vec<UsageRecord>,
+    pub pricing: HashMap<String, ModelPricing>,
+}
+
+tokio::task_local! {
+    /// Completions made by the roast generation running on this task
+    static ROAST_COSTS: RefCell<Vec<RoastCost>>;
+}
+
+#[derive(Default)]
+struct DailyUsage {
+    by_model: HashMap<String, ModelUsage>,
+    recent: VecDeque<UsageRecord>,
+}
 
 pub struct CostTracker {
     daily_requests: AtomicU32,
-    daily_cost_cents: AtomicU32,
+    daily_cost_micros: AtomicU64,
+    usage: Mutex<DailyUsage>,
+    pricing: PricingTable,
+    daily_request_limit: u32,
+    daily_cost_limit_micros: u64,
+    /// Where today's counters are written behind the atomics, so a restart
+    /// picks up the day's spend instead of a fresh budget
+    usage_repo: Option<DailyUsageRepository>,
+    /// Total cost of the latest roasts, newest last
+    recent_roast_costs: Mutex<VecDeque<u64>>,
     last_reset: Mutex<DateTime<Utc>>,
 }
 
 impl CostTracker {
     pub fn new() -> Self {
+        Self::with_pricing(PricingTable::from_env())
+    }
+
+    pub fn with_pricing(pricing: PricingTable) -> Self {
         Self {
             daily_requests: AtomicU32::new(0),
-            daily_cost_cents: AtomicU32::new(0),
+            daily_cost_micros: AtomicU64::new(0),
+            usage: Mutex::new(DailyUsage::default()),
+            pricing,
+            daily_request_limit: DEFAULT_DAILY_REQUEST_LIMIT,
+            daily_cost_limit_micros: DEFAULT_DAILY_COST_LIMIT_MICROS,
+            usage_repo: None,
+            recent_roast_costs: Mutex::new(VecDeque::with_capacity(ROAST_COST_SAMPLES)),
             last_reset: Mutex::new(Utc::now()),
         }
     }
 
+    /// Pricing from MODEL_PRICING; DAILY_REQUEST_LIMIT and
+    /// DAILY_COST_LIMIT_USD override the default 100 requests and $5 a day
+    pub fn from_env() -> Self {
+        let tracker = Self::new();
+        let request_limit = match std::env::var("DAILY_REQUEST_LIMIT").ok().map(|v| v.trim().parse::<u32>()) {
+            None => DEFAULT_DAILY_REQUEST_LIMIT,
+            Some(Ok(n)) => n,
+            Some(Err(_)) => {
+                tracing::warn!("Ignoring DAILY_REQUEST_LIMIT, it must be a whole number");
+                DEFAULT_DAILY_REQUEST_LIMIT
+            }
+        };
+        let default_usd = micros_to_usd(DEFAULT_DAILY_COST_LIMIT_MICROS);
+        let cost_limit_usd = match std::env::var("DAILY_COST_LIMIT_USD").ok().map(|v| v.trim().parse::<f64>()) {
+            None => default_usd,
+            Some(Ok(usd)) if usd.is_finite() && usd >= 0.0 => usd,
+            Some(_) => {
+                tracing::warn!("Ignoring DAILY_COST_LIMIT_USD, it must be a dollar amount like 5 or 2.50");
+                default_usd
+            }
+        };
+        tracker.with_limits(request_limit, cost_limit_usd)
+    }
+
+    pub fn with_limits(mut self, daily_requests: u32, daily_cost_usd: f64) -> Self {
+        self.daily_request_limit = daily_requests;
+        self.daily_cost_limit_micros = (daily_cost_usd * 1_000_000.0).round() as u64;
+        self
+    }
+
+    pub fn with_usage_repo(mut self, repo: DailyUsageRepository) -> Self {
+        self.usage_repo = Some(repo);
+        self
+    }
+
+    /// Pick up today's persisted counters, e.g. after a restart
+    pub async fn rehydrate(&self) {
+        let Some(repo) = &self.usage_repo else {
+            return;
+        };
+        self.maybe_reset_daily();
+        match repo.get(Utc::now().date_naive()).await {
+            Ok(Some((requests, cost_micros))) => {
+                self.daily_requests.fetch_max(requests, Ordering::SeqCst);
+                self.daily_cost_micros.fetch_max(cost_micros, Ordering::SeqCst);
+                tracing::info!(
+                    "Restored today's usage: {} requests, ${:.2}",
+                    requests,
+                    micros_to_usd(cost_micros)
+                );
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to load today's usage: {}", e),
+        }
+    }
+
+    /// Add to today's persisted counters without blocking the request
+    fn persist(&self, requests: u32, cost_micros: u64) {
+        let Some(repo) = self.usage_repo.clone() else {
+            return;
+        };
+        let day = Utc::now().date_naive();
+        tokio::spawn(async move {
+            if let Err(e) = repo.add(day, requests, cost_micros).await {
+                tracing::warn!("Failed to persist daily usage: {}", e);
+            }
+        });
+    }
+
     pub fn check_and_increment(&self) -> Result<(), CostLimitError> {
         self.maybe_reset_daily();
 
         let current_requests = self.daily_requests.load(Ordering::SeqCst);
-        let current_cost = self.daily_cost_cents.load(Ordering::SeqCst);
+        let current_cost = self.daily_cost_micros.load(Ordering::SeqCst);
 
-        if current_requests >= DAILY_REQUEST_LIMIT {
+        if current_requests >= self.daily_request_limit {
             return Err(CostLimitError::DailyRequestLimitReached);
         }
 
-        if current_cost + ESTIMATED_COST_PER_REQUEST_CENTS > DAILY_COST_LIMIT_CENTS {
+        if current_cost + self.estimated_roast_cost_micros() > self.daily_cost_limit_micros {
             return Err(CostLimitError::DailyCostLimitReached);
         }
 
         self.daily_requests.fetch_add(1, Ordering::SeqCst);
-        self.daily_cost_cents
-            .fetch_add(ESTIMATED_COST_PER_REQUEST_CENTS, Ordering::SeqCst);
+        self.persist(1, 0);
 
         Ok(())
     }
 
+    /// Record the actual usage of one completion. It's priced as the model
+    /// that answered, else as the `requested` one (providers answer with
+    /// versioned slugs); with neither in the table it's charged the default
+    /// roast cost, so an unpriced model can't spend past the daily limit.
+    pub fn record_usage(&self, model: &str, requested: &str, usage: TokenUsage) {
+        self.maybe_reset_daily();
+
+        let priced = [model, requested]
+            .into_iter()
+            .find_map(|m| self.pricing.get(m).map(|pricing| (m, pricing)));
+        let (model, cost_micros) = match priced {
+            Some((m, pricing)) => (m, pricing.cost_micros(usage.prompt_tokens, usage.completion_tokens)),
+            None => {
+                tracing::warn!(
+                    "No pricing configured for model {}, charging the default roast cost",
+                    model
+                );
+                (model, DEFAULT_ROAST_COST_MICROS)
+            }
+        };
+        self.daily_cost_micros.fetch_add(cost_micros, Ordering::SeqCst);
+        if cost_micros > 0 {
+            self.persist(0, cost_micros);
+        }
+        let cost_usd = micros_to_usd(cost_micros);
+        // Outside a metered roast generation there is nothing to attribute it to
+        let _ = ROAST_COSTS.try_with(|costs| {
+            costs.borrow_mut().push(RoastCost {
+                model: model.to_string(),
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                cost_micros,
+            })
+        });
+
+        let mut daily = self.usage.lock().unwrap();
+        let entry = daily.by_model.entry(model.to_string()).or_default();
+        entry.requests += 1;
+        entry.prompt_tokens += usage.prompt_tokens as u64;
+        entry.completion_tokens += usage.completion_tokens as u64;
+        entry.cost_usd += cost_usd;
+
+        if daily.recent.len() >= RECENT_USAGE_LIMIT {
+            daily.recent.pop_front();
+        }
+        daily.recent.push_back(UsageRecord {
+            at: Utc::now(),
+            model: model.to_string(),
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            cost_usd,
+        });
+    }
+
+    /// Run a roast generation, collecting the cost of every completion it
+    /// makes on this task
+    pub async fn metered<F: Future>(generation: F) -> (F::Output, Vec<RoastCost>) {
+        ROAST_COSTS
+            .scope(RefCell::new(Vec::new()), async move {
+                let output = generation.await;
+                (output, ROAST_COSTS.with(RefCell::take))
+            })
+            .await
+    }
+
+    /// Remember what one roast cost, sizing the headroom for the next. A
+    /// roast that made no completion, or one without a price, isn't a sample
+    pub fn record_roast(&self, costs: &[RoastCost]) {
+        if costs.is_empty() || costs.iter().any(|c| self.pricing.get(&c.model).is_none()) {
+            return;
+        }
+        let total = costs.iter().map(|c| c.cost_micros).sum();
+        let mut recent = self.recent_roast_costs.lock().unwrap();
+        if recent.len() >= ROAST_COST_SAMPLES {
+            recent.pop_front();
+        }
+        recent.push_back(total);
+    }
+
+    /// Average cost of the latest roasts, or a fixed guess before the first
+    pub fn estimated_roast_cost_micros(&self) -> u64 {
+        let recent = self.recent_roast_costs.lock().unwrap();
+        if recent.is_empty() {
+            return DEFAULT_ROAST_COST_MICROS;
+        }
+        recent.iter().sum::<u64>() / recent.len() as u64
+    }
+
     pub fn get_remaining_requests(&self) -> u32 {
-        DAILY_REQUEST_LIMIT.saturating_sub(self.daily_requests.load(Ordering::SeqCst))
+        self.daily_request_limit.saturating_sub(self.daily_requests.load(Ordering::SeqCst))
+    }
+
+    pub fn stats(&self) -> CostStats {
+        self.maybe_reset_daily();
+
+        let since = *self.last_reset.lock().unwrap();
+        let daily = self.usage.lock().unwrap();
+        CostStats {
+            since,
+            requests: self.daily_requests.load(Ordering::SeqCst),
+            request_limit: self.daily_request_limit,
+            cost_usd: micros_to_usd(self.daily_cost_micros.load(Ordering::SeqCst)),
+            cost_limit_usd: micros_to_usd(self.daily_cost_limit_micros),
+            estimated_roast_cost_usd: micros_to_usd(self.estimated_roast_cost_micros()),
+            by_model: daily.by_model.clone(),
+            recent: daily.recent.iter().rev().cloned().collect(),
+            pricing: self.pricing.models().clone(),
+        }
     }
 
     fn maybe_reset_daily(&self) {
@@ -52,13 +308,18 @@ impl CostTracker {
 
         if now.date_naive() != last_reset.date_naive() {
             self.daily_requests.store(0, Ordering::SeqCst);
-            self.daily_cost_cents.store(0, Ordering::SeqCst);
+            self.daily_cost_micros.store(0, Ordering::SeqCst);
+            *self.usage.lock().unwrap() = DailyUsage::default();
             *last_reset = now;
             tracing::info!("Daily cost tracker reset");
         }
     }
 }
 
+fn micros_to_usd(micros: u64) -> f64 {
+    micros as f64 / 1_000_000.0
+}
+
 impl Default for CostTracker {
     fn default() -> Self {
         Self::new()
@@ -83,3 +344,42 @@ impl CostLimitError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage() -> TokenUsage {
+        TokenUsage {
+            prompt_tokens: 1_000_000,
+            completion_tokens: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unpriced_models_charge_the_default_and_skip_the_estimate() {
+        let tracker = CostTracker::with_pricing(PricingTable::new());
+
+        // A versioned slug is priced as the model that was asked for
+        let ((), costs) = CostTracker::metered(async {
+            tracker.record_usage("deepseek/deepseek-chat-v3-0324", "deepseek/deepseek-chat", usage());
+        })
+        .await;
+        assert_eq!(costs[0].model, "deepseek/deepseek-chat");
+        assert_eq!(costs[0].cost_micros, 140_000);
+        tracker.record_roast(&costs);
+        assert_eq!(tracker.estimated_roast_cost_micros(), 140_000);
+
+        let ((), costs) = CostTracker::metered(async {
+            tracker.record_usage("mystery/model", "mystery/model", usage());
+        })
+        .await;
+        assert_eq!(costs[0].cost_micros, DEFAULT_ROAST_COST_MICROS);
+        tracker.record_roast(&costs);
+        assert_eq!(tracker.estimated_roast_cost_micros(), 140_000);
+        assert_eq!(
+            tracker.daily_cost_micros.load(Ordering::SeqCst),
+            140_000 + DEFAULT_ROAST_COST_MICROS
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use super::client_ip::IpRange;
+use crate::domain::IpBan;
+use std::net::IpAddr;
+use std::sync::RwLock;
+
+/// In-memory copy of the active IP bans, checked on every request before
+/// anything else runs. Loaded from the database at startup, reloaded after
+/// admin changes and periodically so other instances pick them up.
+#[derive(Default)]
+pub struct IpBanList {
+    bans: RwLock<Vec<(IpRange, IpBan)>>,
+}
+
+impl IpBanList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Canonical form of an address or CIDR range, `None` if it isn't one
+    pub fn normalize_range(range: &str) -> Option<String> {
+        IpRange::parse(range).map(|range| range.to_string())
+    }
+
+    pub fn replace(&self, bans: Vec<IpBan>) {
+        let bans = bans
+            .into_iter()
+            .filter_map(|ban| match IpRange::parse(&ban.range) {
+                Some(range) => Some((range, ban)),
+                None => {
+                    tracing::warn!("Ignoring ban {} with invalid range {}", ban.id, ban.range);
+                    None
+                }
+            })
+            .collect();
+        *self.bans.write().unwrap() = bans;
+    }
+
+    pub fn len(&self) -> usize {
+        self.bans.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The ban covering `ip`, skipping any that expired since the last reload
+    pub fn banning(&self, ip: IpAddr) -> Option<IpBan> {
+        let now = chrono::Utc::now();
+        self.bans
+            .read()
+            .unwrap()
+            .iter()
+            .find(|(range, ban)| range.contains(ip) && ban.is_active_at(now))
+            .map(|(_, ban)| ban.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn ban(range: &str, expires_in_hours: Option<i64>) -> IpBan {
+        IpBan {
+            id: Uuid::new_v4(),
+            range: range.to_string(),
+            reason: None,
+            created_by: None,
+            expires_at: expires_in_hours.map(|h| chrono::Utc::now() + chrono::Duration::hours(h)),
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_bans_ranges_until_expiry() {
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        let list = IpBanList::new();
+        list.replace(vec![
+            ban("198.51.100.0/24", None),
+            ban("203.0.113.7", Some(1)),
+            ban("2001:db8::/32", Some(-1)),
+            ban("bogus", None),
+        ]);
+        assert_eq!(list.len(), 3);
+
+        assert!(list.banning(ip("198.51.100.42")).is_some());
+        assert!(list.banning(ip("198.51.101.1")).is_none());
+        assert!(list.banning(ip("203.0.113.7")).is_some());
+        assert!(list.banning(ip("203.0.113.8")).is_none());
+        // Expired bans stop applying before the next reload drops them
+        assert!(list.banning(ip("2001:db8::1")).is_none());
+
+        assert_eq!(IpBanList::normalize_range(" 10.0.0.0/8 ").as_deref(), Some("10.0.0.0/8"));
+        assert_eq!(IpBanList::normalize_range("::1/128").as_deref(), Some("::1"));
+        assert_eq!(IpBanList::normalize_range("10.0.0.0/33"), None);
+    }
+}
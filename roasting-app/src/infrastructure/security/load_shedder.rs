@@ -0,0 +1,239 @@
+use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const DEFAULT_MAX_IN_FLIGHT: usize = 12;
+const DEFAULT_MAX_P95_SECS: u64 = 60;
+/// Generations kept for the p95; older ones age out so a quiet spell
+/// after a spike stops shedding
+const LATENCY_SAMPLES: usize = 50;
+const LATENCY_WINDOW: Duration = Duration::from_secs(300);
+/// A handful of slow roasts is not a trend
+const MIN_LATENCY_SAMPLES: usize = 10;
+/// A retry token is honoured after this wait, so retries can't stampede
+pub const RETRY_AFTER: Duration = Duration::from_secs(30);
+const RETRY_TOKEN_TTL: Duration = Duration::from_secs(15 * 60);
+const MAX_RETRY_TOKENS: usize = 10_000;
+
+/// Why a submission was turned away
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShedReason {
+    InFlight { in_flight: usize, max: usize },
+    Latency { p95_ms: u64, max_ms: u64 },
+}
+
+impl std::fmt::Display for ShedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InFlight { in_flight, max } => write!(f, "{} roasts generating (max {})", in_flight, max),
+            Self::Latency { p95_ms, max_ms } => write!(f, "p95 {} ms (max {} ms)", p95_ms, max_ms),
+        }
+    }
+}
+
+/// A turned-away submission; the token lets the same person back in
+/// after `RETRY_AFTER`
+#[derive(Debug, Clone)]
+pub struct Shed {
+    pub reason: ShedReason,
+    pub retry_token: String,
+}
+
+/// Current load against the thresholds, for the admin metrics
+#[derive(Debug, Clone, Serialize)]
+pub struct LoadStats {
+    pub in_flight: usize,
+    /// 0 when the in-flight limit is off
+    pub max_in_flight: usize,
+    pub p95_ms: Option<u64>,
+    /// `None` when the latency limit is off
+    pub max_p95_ms: Option<u64>,
+    pub overloaded: bool,
+    pub shed_total: u64,
+    pub retries_admitted: u64,
+}
+
+/// Turns away anonymous roast submissions while too many roasts are being
+/// generated or recent ones got slow, so logged-in users and roasts already
+/// running keep the capacity. LOAD_SHED_MAX_IN_FLIGHT (default 12) and
+/// LOAD_SHED_P95_SECS (default 60) set the thresholds, 0 turns one off.
+pub struct LoadShedder {
+    max_in_flight: usize,
+    max_p95: Option<Duration>,
+    in_flight: AtomicUsize,
+    /// (finished at, took)
+    latencies: Mutex<VecDeque<(Instant, Duration)>>,
+    /// Retry token to when it was issued
+    retry_tokens: DashMap<String, Instant>,
+    shed_total: AtomicU64,
+    retries_admitted: AtomicU64,
+}
+
+/// One generation in progress; its latency is recorded when dropped
+pub struct InFlight {
+    shedder: Arc<LoadShedder>,
+    started: Instant,
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.shedder.in_flight.fetch_sub(1, Ordering::Relaxed);
+        let now = Instant::now();
+        let mut latencies = self.shedder.latencies.lock().unwrap_or_else(|e| e.into_inner());
+        if latencies.len() == LATENCY_SAMPLES {
+            latencies.pop_front();
+        }
+        latencies.push_back((now, now - self.started));
+    }
+}
+
+impl LoadShedder {
+    pub fn new(max_in_flight: usize, max_p95: Option<Duration>) -> Self {
+        Self {
+            max_in_flight,
+            max_p95,
+            in_flight: AtomicUsize::new(0),
+            latencies: Mutex::new(VecDeque::with_capacity(LATENCY_SAMPLES)),
+            retry_tokens: DashMap::new(),
+            shed_total: AtomicU64::new(0),
+            retries_admitted: AtomicU64::new(0),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let number = |name: &str, default: u64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .unwrap_or(default)
+        };
+        let max_p95 = number("LOAD_SHED_P95_SECS", DEFAULT_MAX_P95_SECS);
+        Self::new(
+            number("LOAD_SHED_MAX_IN_FLIGHT", DEFAULT_MAX_IN_FLIGHT as u64) as usize,
+            (max_p95 > 0).then(|| Duration::from_secs(max_p95)),
+        )
+    }
+
+    /// Count a generation until the guard is dropped
+    pub fn track(self: &Arc<Self>) -> InFlight {
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        InFlight {
+            shedder: self.clone(),
+            started: Instant::now(),
+        }
+    }
+
+    fn p95(&self) -> Option<Duration> {
+        let cutoff = Instant::now().checked_sub(LATENCY_WINDOW);
+        let mut recent: Vec<Duration> = self
+            .latencies
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(at, _)| cutoff.is_none_or(|cutoff| *at >= cutoff))
+            .map(|(_, took)| *took)
+            .collect();
+        if recent.len() < MIN_LATENCY_SAMPLES {
+            return None;
+        }
+        recent.sort();
+        Some(recent[(recent.len() * 95).div_ceil(100) - 1])
+    }
+
+    pub fn overloaded(&self) -> Option<ShedReason> {
+        let in_flight = self.in_flight.load(Ordering::Relaxed);
+        if self.max_in_flight > 0 && in_flight >= self.max_in_flight {
+            return Some(ShedReason::InFlight {
+                in_flight,
+                max: self.max_in_flight,
+            });
+        }
+        match (self.max_p95, self.p95()) {
+            (Some(max), Some(p95)) if p95 > max => Some(ShedReason::Latency {
+                p95_ms: p95.as_millis() as u64,
+                max_ms: max.as_millis() as u64,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Admit an anonymous submission, or turn it away with a fresh retry
+    /// token. A token older than `RETRY_AFTER` gets in once, overloaded or not.
+    pub fn admit_anonymous(&self, retry_token: Option<&str>) -> Result<(), Shed> {
+        if let Some(token) = retry_token {
+            let redeemable = self
+                .retry_tokens
+                .remove_if(token, |_, issued| issued.elapsed() >= RETRY_AFTER)
+                .is_some_and(|(_, issued)| issued.elapsed() < RETRY_TOKEN_TTL);
+            if redeemable {
+                self.retries_admitted.fetch_add(1, Ordering::Relaxed);
+                return Ok(());
+            }
+        }
+        let Some(reason) = self.overloaded() else {
+            return Ok(());
+        };
+        self.shed_total.fetch_add(1, Ordering::Relaxed);
+        // A token that was presented too early is replaced rather than stacked
+        if let Some(token) = retry_token {
+            self.retry_tokens.remove(token);
+        }
+        Err(Shed {
+            reason,
+            retry_token: self.issue_retry_token(),
+        })
+    }
+
+    fn issue_retry_token(&self) -> String {
+        if self.retry_tokens.len() >= MAX_RETRY_TOKENS {
+            self.retry_tokens.retain(|_, issued| issued.elapsed() < RETRY_TOKEN_TTL);
+        }
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        self.retry_tokens.insert(token.clone(), Instant::now());
+        token
+    }
+
+    pub fn stats(&self) -> LoadStats {
+        LoadStats {
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            max_in_flight: self.max_in_flight,
+            p95_ms: self.p95().map(|p| p.as_millis() as u64),
+            max_p95_ms: self.max_p95.map(|p| p.as_millis() as u64),
+            overloaded: self.overloaded().is_some(),
+            shed_total: self.shed_total.load(Ordering::Relaxed),
+            retries_admitted: self.retries_admitted.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sheds_past_in_flight_limit_until_the_token_matures() {
+        let shedder = Arc::new(LoadShedder::new(2, None));
+        let _first = shedder.track();
+        assert!(shedder.admit_anonymous(None).is_ok());
+
+        let second = shedder.track();
+        let shed = shedder.admit_anonymous(None).unwrap_err();
+        assert_eq!(shed.reason, ShedReason::InFlight { in_flight: 2, max: 2 });
+        // Too early: turned away again with a new token
+        let again = shedder.admit_anonymous(Some(&shed.retry_token)).unwrap_err();
+        assert_ne!(again.retry_token, shed.retry_token);
+
+        shedder
+            .retry_tokens
+            .insert(again.retry_token.clone(), Instant::now() - RETRY_AFTER);
+        assert!(shedder.admit_anonymous(Some(&again.retry_token)).is_ok());
+        assert!(shedder.admit_anonymous(Some(&again.retry_token)).is_err());
+
+        drop(second);
+        assert!(shedder.admit_anonymous(None).is_ok());
+        assert_eq!(shedder.stats().shed_total, 3);
+    }
+}
@@ -1,7 +1,19 @@
 mod rate_limiter;
+mod client_ip;
 mod cost_tracker;
 mod input_sanitizer;
+mod ip_ban_list;
+mod load_shedder;
+mod model_pricing;
+mod content_filter;
+mod opt_out_list;
 
-pub use rate_limiter::{RateLimiter, RateLimitError};
-pub use cost_tracker::{CostTracker, CostLimitError};
+pub use rate_limiter::{Client as RateLimitClient, MemoryStore, Quota, RateLimitError, RateLimitStore, RateLimiter, RateLimits, RedisStore};
+pub use client_ip::{ClientIp, TrustedProxies};
+pub use cost_tracker::{CostTracker, CostLimitError, CostStats, TokenUsage};
 pub use input_sanitizer::InputSanitizer;
+pub use ip_ban_list::IpBanList;
+pub use load_shedder::{LoadShedder, LoadStats, Shed, ShedReason, RETRY_AFTER};
+pub use model_pricing::{ModelPricing, PricingTable};
+pub use content_filter::{ContentFilter, ContentViolation};
+pub use opt_out_list::OptOutList;
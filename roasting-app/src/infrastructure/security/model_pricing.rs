@@ -0,0 +1,124 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// USD per million tokens for one model
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ModelPricing {
+    pub prompt_per_million: f64,
+    pub completion_per_million: f64,
+}
+
+impl ModelPricing {
+    /// Cost in micro-dollars (1e-6 USD)
+    pub fn cost_micros(&self, prompt_tokens: u32, completion_tokens: u32) -> u64 {
+        (prompt_tokens as f64 * self.prompt_per_million
+            + completion_tokens as f64 * self.completion_per_million)
+            .round() as u64
+    }
+}
+
+/// Built-in OpenRouter list prices, overridable with MODEL_PRICING
+const DEFAULT_PRICING: &[(&str, f64, f64)] = &[("deepseek/deepseek-chat", 0.14, 0.28)];
+
+/// Per-model prices used to turn reported token usage into cost
+#[derive(Debug, Clone)]
+pub struct PricingTable {
+    models: HashMap<String, ModelPricing>,
+}
+
+impl PricingTable {
+    pub fn new() -> Self {
+        Self {
+            models: DEFAULT_PRICING
+                .iter()
+                .map(|(model, prompt, completion)| {
+                    (
+                        model.to_string(),
+                        ModelPricing {
+                            prompt_per_million: *prompt,
+                            completion_per_million: *completion,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Defaults plus MODEL_PRICING entries like
+    /// `deepseek/deepseek-chat=0.14:0.28,openai/*=0.15:0.6`
+    /// (USD per million prompt:completion tokens; `provider/*` prices every
+    /// model of a provider without its own entry)
+    pub fn from_env() -> Self {
+        let mut table = Self::new();
+        if let Ok(spec) = std::env::var("MODEL_PRICING") {
+            table.extend_from_spec(&spec);
+        }
+        table
+    }
+
+    fn extend_from_spec(&mut self, spec: &str) {
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let parsed = entry.split_once('=').and_then(|(model, prices)| {
+                let (prompt, completion) = prices.split_once(':')?;
+                Some((
+                    model.trim().to_string(),
+                    ModelPricing {
+                        prompt_per_million: prompt.trim().parse().ok()?,
+                        completion_per_million: completion.trim().parse().ok()?,
+                    },
+                ))
+            });
+
+            match parsed {
+                Some((model, pricing)) => {
+                    self.models.insert(model, pricing);
+                }
+                None => tracing::warn!("Ignoring malformed MODEL_PRICING entry: {}", entry),
+            }
+        }
+    }
+
+    /// The model's own price, else its provider's
+    pub fn get(&self, model: &str) -> Option<ModelPricing> {
+        self.models.get(model).copied().or_else(|| {
+            let (provider, _) = model.split_once('/')?;
+            self.models.get(&format!("{}/*", provider)).copied()
+        })
+    }
+
+    pub fn models(&self) -> &HashMap<String, ModelPricing> {
+        &self.models
+    }
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_overrides_and_cost() {
+        let mut table = PricingTable::new();
+        table.extend_from_spec("deepseek/deepseek-chat=1:2, bad-entry, openai/gpt-4o-mini=0.15:0.6");
+
+        let deepseek = table.get("deepseek/deepseek-chat").unwrap();
+        assert_eq!(deepseek.cost_micros(1_000, 500), 2_000);
+        assert!(table.get("openai/gpt-4o-mini").is_some());
+        assert!(table.get("bad-entry").is_none());
+    }
+
+    #[test]
+    fn test_provider_wildcard() {
+        let mut table = PricingTable::new();
+        table.extend_from_spec("anthropic/*=3:15, anthropic/claude-3-haiku=0.25:1.25");
+
+        assert_eq!(table.get("anthropic/claude-3.5-sonnet").unwrap().prompt_per_million, 3.0);
+        assert_eq!(table.get("anthropic/claude-3-haiku").unwrap().prompt_per_million, 0.25);
+        assert!(table.get("mistralai/mistral-7b").is_none());
+    }
+}
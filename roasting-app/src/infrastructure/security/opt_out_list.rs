@@ -0,0 +1,64 @@
+use crate::domain::DomainOptOut;
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+/// In-memory copy of the verified opt-out domains, checked on every roast
+/// request. Loaded from the database at startup and updated on verification.
+#[derive(Default)]
+pub struct OptOutList {
+    domains: RwLock<HashSet<String>>,
+}
+
+impl OptOutList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn replace(&self, domains: Vec<String>) {
+        *self.domains.write().unwrap() = domains.into_iter().collect();
+    }
+
+    pub fn insert(&self, domain: &str) {
+        self.domains.write().unwrap().insert(domain.to_string());
+    }
+
+    pub fn len(&self) -> usize {
+        self.domains.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The opted-out domain covering `url`, checking the host and each parent domain
+    pub fn blocking_domain(&self, url: &str) -> Option<String> {
+        let host = DomainOptOut::normalize_domain(url)?;
+        let domains = self.domains.read().unwrap();
+        let mut candidate = host.as_str();
+        loop {
+            if domains.contains(candidate) {
+                return Some(candidate.to_string());
+            }
+            candidate = candidate.split_once('.')?.1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_domain_and_subdomains() {
+        let list = OptOutList::new();
+        list.replace(vec!["example.com".to_string()]);
+
+        assert_eq!(
+            list.blocking_domain("https://www.example.com/pricing"),
+            Some("example.com".to_string())
+        );
+        assert!(list.blocking_domain("https://app.example.com").is_some());
+        assert!(list.blocking_domain("https://notexample.com").is_none());
+        assert!(list.blocking_domain("https://example.co.id").is_none());
+    }
+}
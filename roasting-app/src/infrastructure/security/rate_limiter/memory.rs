@@ -0,0 +1,173 @@
+use super::{Client, Quota, RateLimitError, RateLimitStore};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const CLEANUP_INTERVAL_SECS: u64 = 300;
+
+/// Generic cell rate algorithm: `limit` requests per `period` on average,
+/// of which up to `limit` may arrive back to back. Unlike fixed windows
+/// there is no boundary where two full windows' worth can slip through.
+#[derive(Debug, Clone, Copy)]
+struct Gcra {
+    /// Time one request "costs"
+    emission_interval: Duration,
+    /// How far ahead of now the theoretical arrival time may run
+    burst_tolerance: Duration,
+}
+
+impl Gcra {
+    const fn new(limit: u32, period: Duration) -> Self {
+        let emission_interval = Duration::from_nanos(period.as_nanos() as u64 / limit as u64);
+        Self {
+            emission_interval,
+            burst_tolerance: Duration::from_nanos(
+                emission_interval.as_nanos() as u64 * (limit as u64 - 1),
+            ),
+        }
+    }
+
+    /// The theoretical arrival time after admitting a request at `now`, or
+    /// how long to wait until one would be admitted
+    fn admit(&self, tat: Instant, now: Instant) -> Result<Instant, Duration> {
+        let tat = tat.max(now);
+        let limit = now + self.burst_tolerance;
+        if tat > limit {
+            return Err(tat - limit);
+        }
+        Ok(tat + self.emission_interval)
+    }
+}
+
+/// Theoretical arrival times for one client; in the past means a full bucket
+#[derive(Clone)]
+struct RequestRecord {
+    minute_tat: Instant,
+    hour_tat: Instant,
+}
+
+/// Token buckets in this process only; the default, and the fallback when
+/// a shared store is unreachable
+pub struct MemoryStore {
+    requests: DashMap<Client, RequestRecord>,
+    last_cleanup: Mutex<Instant>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self {
+            requests: DashMap::new(),
+            last_cleanup: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub fn check_now(&self, client: Client, quota: Quota) -> Result<(), RateLimitError> {
+        self.maybe_cleanup();
+        self.check_at(client, quota, Instant::now())
+    }
+
+    fn check_at(&self, client: Client, quota: Quota, now: Instant) -> Result<(), RateLimitError> {
+        let per_minute = Gcra::new(quota.per_minute, Duration::from_secs(60));
+        let per_hour = Gcra::new(quota.per_hour, Duration::from_secs(3600));
+        let mut record = self.requests.entry(client).or_insert_with(|| RequestRecord {
+            minute_tat: now,
+            hour_tat: now,
+        });
+
+        // Both limits must pass before either is charged
+        let minute_tat = per_minute
+            .admit(record.minute_tat, now)
+            .map_err(|retry_after| RateLimitError::TooManyRequestsPerMinute {
+                retry_after,
+                limit: quota.per_minute,
+            })?;
+        let hour_tat = per_hour
+            .admit(record.hour_tat, now)
+            .map_err(|retry_after| RateLimitError::TooManyRequestsPerHour {
+                retry_after,
+                limit: quota.per_hour,
+            })?;
+
+        record.minute_tat = minute_tat;
+        record.hour_tat = hour_tat;
+        Ok(())
+    }
+
+    fn maybe_cleanup(&self) {
+        let mut last_cleanup = self.last_cleanup.lock().unwrap();
+        if last_cleanup.elapsed() > Duration::from_secs(CLEANUP_INTERVAL_SECS) {
+            // Clients whose buckets have fully refilled carry no state
+            let now = Instant::now();
+            self.requests
+                .retain(|_, v| v.minute_tat > now || v.hour_tat > now);
+            *last_cleanup = now;
+        }
+    }
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for MemoryStore {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn check(&self, client: Client, quota: Quota) -> Result<(), RateLimitError> {
+        self.check_now(client, quota)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::RateLimits;
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_burst_then_steady_rate() {
+        let limiter = MemoryStore::new();
+        let quota = RateLimits::default().anonymous;
+        let ip = Client::Ip(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        let start = Instant::now();
+
+        for _ in 0..quota.per_minute {
+            assert!(limiter.check_at(ip, quota, start).is_ok());
+        }
+        let err = limiter.check_at(ip, quota, start).unwrap_err();
+        assert!(matches!(err, RateLimitError::TooManyRequestsPerMinute { limit: 5, .. }));
+        assert_eq!(err.retry_after(), Duration::from_secs(12));
+        assert_eq!(err.headers()[0], ("retry-after", "12".to_string()));
+        assert_eq!(err.headers()[1], ("x-ratelimit-limit", "5".to_string()));
+
+        // One request's worth refills every 12 seconds, no window reset needed
+        assert!(limiter.check_at(ip, quota, start + Duration::from_secs(11)).is_err());
+        assert!(limiter.check_at(ip, quota, start + Duration::from_secs(12)).is_ok());
+        assert!(limiter.check_at(ip, quota, start + Duration::from_secs(12)).is_err());
+    }
+
+    #[test]
+    fn test_users_have_their_own_quota() {
+        let limiter = MemoryStore::new();
+        let limits = RateLimits::default();
+        let ip = Client::Ip(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        let user = Client::User(Uuid::new_v4());
+        let start = Instant::now();
+
+        for _ in 0..limits.anonymous.per_minute {
+            assert!(limiter.check_at(ip, limits.for_client(ip), start).is_ok());
+        }
+        // A shared NAT IP running dry doesn't block a logged-in user behind it
+        for _ in 0..limits.user.per_minute {
+            assert!(limiter.check_at(user, limits.for_client(user), start).is_ok());
+        }
+        let err = limiter.check_at(user, limits.for_client(user), start).unwrap_err();
+        assert_eq!(err.retry_after(), Duration::from_secs(6));
+    }
+}
@@ -0,0 +1,236 @@
+mod memory;
+mod redis_store;
+
+pub use memory::MemoryStore;
+pub use redis_store::RedisStore;
+
+use async_trait::async_trait;
+use roasting_errors::AppError;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+const DEFAULT_REQUESTS_PER_MINUTE: u32 = 5;
+const DEFAULT_REQUESTS_PER_HOUR: u32 = 20;
+/// Logged-in users are accountable, so they get a bigger quota of their own
+const DEFAULT_USER_REQUESTS_PER_MINUTE: u32 = 10;
+const DEFAULT_USER_REQUESTS_PER_HOUR: u32 = 60;
+
+/// Who a quota belongs to: a logged-in user wherever they connect from,
+/// otherwise the IP, which an office or ISP NAT may share among many people
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Client {
+    Ip(IpAddr),
+    User(Uuid),
+}
+
+impl Client {
+    /// Stable key for stores shared between instances
+    pub fn key(&self) -> String {
+        match self {
+            Self::Ip(ip) => format!("ip:{}", ip),
+            Self::User(id) => format!("user:{}", id),
+        }
+    }
+}
+
+/// Requests one client may make; both must be at least 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    pub per_minute: u32,
+    pub per_hour: u32,
+}
+
+/// Quotas for anonymous and logged-in clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimits {
+    pub anonymous: Quota,
+    pub user: Quota,
+}
+
+impl RateLimits {
+    /// RATE_LIMIT_PER_MINUTE, RATE_LIMIT_PER_HOUR, RATE_LIMIT_USER_PER_MINUTE
+    /// and RATE_LIMIT_USER_PER_HOUR override the defaults
+    pub fn from_env() -> Self {
+        let number = |name: &str, default: u32| {
+            match std::env::var(name).ok().map(|v| v.trim().parse::<u32>()) {
+                None => default,
+                Some(Ok(n)) if n > 0 => n,
+                Some(_) => {
+                    tracing::warn!("Ignoring {}, it must be a positive number", name);
+                    default
+                }
+            }
+        };
+        let defaults = Self::default();
+        Self {
+            anonymous: Quota {
+                per_minute: number("RATE_LIMIT_PER_MINUTE", defaults.anonymous.per_minute),
+                per_hour: number("RATE_LIMIT_PER_HOUR", defaults.anonymous.per_hour),
+            },
+            user: Quota {
+                per_minute: number("RATE_LIMIT_USER_PER_MINUTE", defaults.user.per_minute),
+                per_hour: number("RATE_LIMIT_USER_PER_HOUR", defaults.user.per_hour),
+            },
+        }
+    }
+
+    pub fn for_client(&self, client: Client) -> Quota {
+        match client {
+            Client::Ip(_) => self.anonymous,
+            Client::User(_) => self.user,
+        }
+    }
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            anonymous: Quota {
+                per_minute: DEFAULT_REQUESTS_PER_MINUTE,
+                per_hour: DEFAULT_REQUESTS_PER_HOUR,
+            },
+            user: Quota {
+                per_minute: DEFAULT_USER_REQUESTS_PER_MINUTE,
+                per_hour: DEFAULT_USER_REQUESTS_PER_HOUR,
+            },
+        }
+    }
+}
+
+/// Where request counts live. The in-memory store is exact but each
+/// instance counts on its own; behind a load balancer use one all instances
+/// share.
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Charge one request to `client`, unless either limit of `quota` is used up
+    async fn check(&self, client: Client, quota: Quota) -> Result<(), RateLimitError>;
+}
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    store: Arc<dyn RateLimitStore>,
+    limits: RateLimits,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::with_store(Arc::new(MemoryStore::new()))
+    }
+
+    pub fn with_store(store: Arc<dyn RateLimitStore>) -> Self {
+        Self {
+            store,
+            limits: RateLimits::default(),
+        }
+    }
+
+    pub fn with_limits(mut self, limits: RateLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Limits from `RateLimits::from_env`. RATE_LIMIT_STORE=redis shares
+    /// counts through REDIS_URL; anything else keeps them in memory
+    pub fn from_env() -> Self {
+        let limits = RateLimits::from_env();
+        let store = std::env::var("RATE_LIMIT_STORE").unwrap_or_default();
+        if store.trim() != "redis" {
+            return Self::new().with_limits(limits);
+        }
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+        match RedisStore::new(&url) {
+            Ok(store) => Self::with_store(Arc::new(store)).with_limits(limits),
+            Err(e) => {
+                tracing::warn!("Invalid REDIS_URL, rate limiting in memory instead: {}", e);
+                Self::new().with_limits(limits)
+            }
+        }
+    }
+
+    pub fn store_name(&self) -> &'static str {
+        self.store.name()
+    }
+
+    pub fn limits(&self) -> RateLimits {
+        self.limits
+    }
+
+    /// Charge a request to the logged-in user's quota, or to the IP's for
+    /// anonymous requests
+    pub async fn check_rate_limit(&self, ip: IpAddr, user_id: Option<Uuid>) -> Result<(), RateLimitError> {
+        let client = user_id.map_or(Client::Ip(ip), Client::User);
+        self.store.check(client, self.limits.for_client(client)).await
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Carries the exact wait until the next request would be admitted and the
+/// limit that was hit
+#[derive(Debug, Clone)]
+pub enum RateLimitError {
+    TooManyRequestsPerMinute { retry_after: Duration, limit: u32 },
+    TooManyRequestsPerHour { retry_after: Duration, limit: u32 },
+}
+
+impl RateLimitError {
+    pub fn retry_after(&self) -> Duration {
+        match self {
+            Self::TooManyRequestsPerMinute { retry_after, .. }
+            | Self::TooManyRequestsPerHour { retry_after, .. } => *retry_after,
+        }
+    }
+
+    pub fn limit(&self) -> u32 {
+        match self {
+            Self::TooManyRequestsPerMinute { limit, .. } | Self::TooManyRequestsPerHour { limit, .. } => *limit,
+        }
+    }
+
+    /// Whole seconds to wait, rounded up so retrying on time succeeds
+    pub fn retry_after_secs(&self) -> u64 {
+        let wait = self.retry_after();
+        wait.as_secs() + u64::from(wait.subsec_nanos() > 0)
+    }
+
+    /// `Retry-After` and `X-RateLimit-*` headers for the 429 response.
+    /// Reset is in seconds from now, like `Retry-After`.
+    pub fn headers(&self) -> [(&'static str, String); 4] {
+        let secs = self.retry_after_secs().to_string();
+        [
+            ("retry-after", secs.clone()),
+            ("x-ratelimit-limit", self.limit().to_string()),
+            ("x-ratelimit-remaining", "0".to_string()),
+            ("x-ratelimit-reset", secs),
+        ]
+    }
+
+    pub fn message_id(&self) -> String {
+        let secs = self.retry_after_secs();
+        match self {
+            Self::TooManyRequestsPerMinute { .. } => {
+                format!("Terlalu banyak request! Tunggu {} detik lagi.", secs)
+            }
+            Self::TooManyRequestsPerHour { .. } => {
+                format!(
+                    "Kamu sudah mencapai batas per jam. Tunggu {} menit lagi.",
+                    secs.div_ceil(60)
+                )
+            }
+        }
+    }
+}
+
+impl From<RateLimitError> for AppError {
+    fn from(e: RateLimitError) -> Self {
+        AppError::RateLimited(e.retry_after_secs())
+    }
+}
@@ -0,0 +1,156 @@
+use super::{Client, MemoryStore, Quota, RateLimitError, RateLimitStore};
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::Script;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+const KEY_PREFIX: &str = "ratelimit";
+/// A rate limit check shouldn't hold a roast up for longer than this
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(300);
+/// After a failed connect, limit in memory for this long before trying again
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Checks both counters before charging either, so a rejected request
+/// doesn't eat into the hourly quota. Each counter's window starts with its
+/// first request and ends when the key expires.
+const CHECK_SCRIPT: &str = r#"
+local minute = tonumber(redis.call('GET', KEYS[1]) or '0')
+if minute >= tonumber(ARGV[1]) then
+    return {1, redis.call('PTTL', KEYS[1])}
+end
+local hour = tonumber(redis.call('GET', KEYS[2]) or '0')
+if hour >= tonumber(ARGV[2]) then
+    return {2, redis.call('PTTL', KEYS[2])}
+end
+if redis.call('INCR', KEYS[1]) == 1 then
+    redis.call('EXPIRE', KEYS[1], 60)
+end
+if redis.call('INCR', KEYS[2]) == 1 then
+    redis.call('EXPIRE', KEYS[2], 3600)
+end
+return {0, 0}
+"#;
+
+/// Fixed-window counters in Redis, shared by every instance behind the load
+/// balancer. While Redis is unreachable each instance limits on its own.
+pub struct RedisStore {
+    client: redis::Client,
+    connection: OnceCell<ConnectionManager>,
+    connect_failed_at: Mutex<Option<Instant>>,
+    script: Script,
+    fallback: MemoryStore,
+}
+
+impl RedisStore {
+    /// Only parses the URL; the connection is made on first use
+    pub fn new(url: &str) -> Result<Self, redis::RedisError> {
+        Ok(Self {
+            client: redis::Client::open(url)?,
+            connection: OnceCell::new(),
+            connect_failed_at: Mutex::new(None),
+            script: Script::new(CHECK_SCRIPT),
+            fallback: MemoryStore::new(),
+        })
+    }
+
+    /// Whether the last connect failed too recently to try again
+    fn backing_off(&self) -> bool {
+        if self.connection.initialized() {
+            return false;
+        }
+        self.connect_failed_at
+            .lock()
+            .unwrap()
+            .is_some_and(|at| at.elapsed() < RECONNECT_BACKOFF)
+    }
+
+    async fn connection(&self) -> Result<ConnectionManager, redis::RedisError> {
+        let connect = async {
+            match tokio::time::timeout(CONNECT_TIMEOUT, ConnectionManager::new(self.client.clone())).await {
+                Ok(result) => result,
+                Err(_) => Err(redis::RedisError::from((
+                    redis::ErrorKind::IoError,
+                    "connect timed out",
+                ))),
+            }
+        };
+        let result = self.connection.get_or_try_init(|| connect).await.cloned();
+        if result.is_err() {
+            *self.connect_failed_at.lock().unwrap() = Some(Instant::now());
+        }
+        result
+    }
+
+    async fn check_shared(&self, client: Client, quota: Quota) -> Result<Result<(), RateLimitError>, redis::RedisError> {
+        let mut connection = self.connection().await?;
+        let key = client.key();
+        let (exceeded, ttl_ms): (u8, i64) = self
+            .script
+            .key(format!("{}:{}:minute", KEY_PREFIX, key))
+            .key(format!("{}:{}:hour", KEY_PREFIX, key))
+            .arg(quota.per_minute)
+            .arg(quota.per_hour)
+            .invoke_async(&mut connection)
+            .await?;
+
+        let retry_after = Duration::from_millis(ttl_ms.max(0) as u64);
+        Ok(match exceeded {
+            1 => Err(RateLimitError::TooManyRequestsPerMinute {
+                retry_after,
+                limit: quota.per_minute,
+            }),
+            2 => Err(RateLimitError::TooManyRequestsPerHour {
+                retry_after,
+                limit: quota.per_hour,
+            }),
+            _ => Ok(()),
+        })
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for RedisStore {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn check(&self, client: Client, quota: Quota) -> Result<(), RateLimitError> {
+        if self.backing_off() {
+            return self.fallback.check_now(client, quota);
+        }
+        match self.check_shared(client, quota).await {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::warn!("Redis rate limit check failed, limiting in memory: {}", e);
+                self.fallback.check_now(client, quota)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[tokio::test]
+    async fn test_falls_back_to_memory_while_redis_is_down() {
+        // Nothing listens on port 1, so every connect is refused
+        let store = RedisStore::new("redis://127.0.0.1:1").unwrap();
+        let quota = Quota {
+            per_minute: 1,
+            per_hour: 10,
+        };
+        let ip = Client::Ip(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+
+        assert!(store.check(ip, quota).await.is_ok());
+        assert!(store.backing_off());
+
+        let started = Instant::now();
+        let err = store.check(ip, quota).await.unwrap_err();
+        assert!(matches!(err, RateLimitError::TooManyRequestsPerMinute { limit: 1, .. }));
+        assert!(started.elapsed() < CONNECT_TIMEOUT);
+    }
+}
@@ -0,0 +1,311 @@
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont};
+use image::codecs::webp::WebPEncoder;
+use image::{Rgba, RgbaImage};
+use roasting_errors::AppError;
+
+/// WhatsApp sticker packs want 512x512 WebP under 100KB
+const SIZE: u32 = 512;
+const MARGIN: f32 = 20.0;
+const BORDER: f32 = 10.0;
+const RADIUS: f32 = 48.0;
+const PADDING: f32 = 36.0;
+/// Punchlines longer than this are cut at a word boundary
+const MAX_PUNCHLINE_CHARS: usize = 150;
+const DEFAULT_FONT: &str = "/usr/share/fonts/truetype/dejavu/DejaVuSans-Bold.ttf";
+
+// Rosé Pine Dawn, same palette as the web UI
+const CARD: Rgba<u8> = Rgba([255, 250, 243, 255]);
+const LOVE: Rgba<u8> = Rgba([180, 99, 122, 255]);
+const TEXT: Rgba<u8> = Rgba([87, 82, 121, 255]);
+const SUBTLE: Rgba<u8> = Rgba([121, 117, 147, 255]);
+
+/// Renders a roast punchline as a static WebP sticker: a rounded card on a
+/// transparent background with the startup name, the punchline, and branding.
+pub struct StickerRenderer {
+    font: Option<FontVec>,
+}
+
+impl StickerRenderer {
+    /// Loads the TTF/OTF font at STICKER_FONT (default: DejaVu Sans Bold).
+    /// Without a usable font, stickers are disabled rather than failing startup.
+    pub fn from_env() -> Self {
+        let path = std::env::var("STICKER_FONT").unwrap_or_else(|_| DEFAULT_FONT.to_string());
+        let font = match std::fs::read(&path) {
+            Ok(bytes) => match FontVec::try_from_vec(bytes) {
+                Ok(font) => Some(font),
+                Err(e) => {
+                    tracing::warn!("Sticker font {} is not a valid font: {}", path, e);
+                    None
+                }
+            },
+            Err(e) => {
+                tracing::warn!(
+                    "Sticker font {} not readable, stickers disabled: {}",
+                    path,
+                    e
+                );
+                None
+            }
+        };
+        Self { font }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.font.is_some()
+    }
+
+    /// Lossless WebP bytes for a sticker of `punchline`
+    pub fn render(&self, startup_name: &str, punchline: &str) -> Result<Vec<u8>, AppError> {
+        let font = self
+            .font
+            .as_ref()
+            .ok_or_else(|| AppError::Internal("sticker font not loaded".to_string()))?;
+
+        let mut img = RgbaImage::from_pixel(SIZE, SIZE, Rgba([0, 0, 0, 0]));
+        let outer = SIZE as f32 - 2.0 * MARGIN;
+        fill_rounded_rect(&mut img, MARGIN, MARGIN, outer, outer, RADIUS, LOVE);
+        fill_rounded_rect(
+            &mut img,
+            MARGIN + BORDER,
+            MARGIN + BORDER,
+            outer - 2.0 * BORDER,
+            outer - 2.0 * BORDER,
+            RADIUS - BORDER,
+            CARD,
+        );
+
+        let left = MARGIN + BORDER + PADDING;
+        let width = SIZE as f32 - 2.0 * left;
+        let top = MARGIN + BORDER + PADDING;
+        let bottom = SIZE as f32 - top;
+
+        // Header: startup name, one line
+        let header = PxScale::from(30.0);
+        let name = truncate_to_width(startup_name, width, |s| text_width(font, header, s));
+        let header_height = font.as_scaled(header).height();
+        draw_centered(&mut img, font, header, top, &name, LOVE);
+
+        // Footer branding
+        let footer = PxScale::from(20.0);
+        let footer_height = font.as_scaled(footer).height();
+        draw_centered(
+            &mut img,
+            font,
+            footer,
+            bottom - footer_height,
+            "roasting-startup",
+            SUBTLE,
+        );
+
+        // Punchline: the largest size that fits between header and footer
+        let body_top = top + header_height + 16.0;
+        let body_height = bottom - footer_height - 16.0 - body_top;
+        let punchline = shorten(punchline, MAX_PUNCHLINE_CHARS);
+        let (scale, lines) = fit_text(font, &punchline, width, body_height);
+        let line_height = font.as_scaled(scale).height() * 1.1;
+        let mut y = body_top + (body_height - line_height * lines.len() as f32) / 2.0;
+        for line in &lines {
+            draw_centered(&mut img, font, scale, y, line, TEXT);
+            y += line_height;
+        }
+
+        let mut bytes = Vec::new();
+        img.write_with_encoder(WebPEncoder::new_lossless(&mut bytes))
+            .map_err(|e| AppError::Internal(format!("sticker encoding failed: {}", e)))?;
+        Ok(bytes)
+    }
+}
+
+/// Largest font size from 48px down to 22px whose wrapped lines fit the box;
+/// at the smallest size, lines that don't fit are dropped with an ellipsis
+fn fit_text(font: &FontVec, text: &str, width: f32, height: f32) -> (PxScale, Vec<String>) {
+    let mut size = 48.0;
+    loop {
+        let scale = PxScale::from(size);
+        let mut lines = wrap_text(text, width, |s| text_width(font, scale, s));
+        let line_height = font.as_scaled(scale).height() * 1.1;
+        let max_lines = ((height / line_height).floor() as usize).max(1);
+        if lines.len() <= max_lines {
+            return (scale, lines);
+        }
+        if size <= 22.0 {
+            lines.truncate(max_lines);
+            if let Some(last) = lines.last_mut() {
+                *last = truncate_to_width(&format!("{}…", last), width, |s| {
+                    text_width(font, scale, s)
+                });
+            }
+            return (scale, lines);
+        }
+        size -= 4.0;
+    }
+}
+
+/// Greedy word wrap; words wider than a whole line are broken by character
+fn wrap_text(text: &str, max_width: f32, measure: impl Fn(&str) -> f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if measure(&candidate) <= max_width {
+            current = candidate;
+            continue;
+        }
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        for c in word.chars() {
+            current.push(c);
+            if measure(&current) > max_width && current.chars().count() > 1 {
+                current.pop();
+                lines.push(std::mem::take(&mut current));
+                current.push(c);
+            }
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Cut `text` at a word boundary near `max_chars`, adding an ellipsis
+fn shorten(text: &str, max_chars: usize) -> String {
+    let text = text.trim();
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    let cut = match cut.rfind(' ') {
+        Some(i) if i > max_chars / 2 => &cut[..i],
+        _ => cut.as_str(),
+    };
+    format!(
+        "{}…",
+        cut.trim_end_matches(|c: char| c.is_ascii_punctuation() || c == ' ')
+    )
+}
+
+fn truncate_to_width(text: &str, max_width: f32, measure: impl Fn(&str) -> f32) -> String {
+    if measure(text) <= max_width {
+        return text.to_string();
+    }
+    let mut chars: Vec<char> = text.chars().collect();
+    while !chars.is_empty() {
+        chars.pop();
+        let candidate = format!("{}…", chars.iter().collect::<String>().trim_end());
+        if measure(&candidate) <= max_width {
+            return candidate;
+        }
+    }
+    "…".to_string()
+}
+
+fn text_width(font: &FontVec, scale: PxScale, text: &str) -> f32 {
+    let scaled = font.as_scaled(scale);
+    let mut width = 0.0;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(prev) = previous {
+            width += scaled.kern(prev, id);
+        }
+        width += scaled.h_advance(id);
+        previous = Some(id);
+    }
+    width
+}
+
+/// Draw one line horizontally centered, with `top` as the top of the line box
+fn draw_centered(
+    img: &mut RgbaImage,
+    font: &FontVec,
+    scale: PxScale,
+    top: f32,
+    text: &str,
+    color: Rgba<u8>,
+) {
+    let scaled = font.as_scaled(scale);
+    let mut x = (SIZE as f32 - text_width(font, scale, text)) / 2.0;
+    let baseline = top + scaled.ascent();
+    let mut previous = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(prev) = previous {
+            x += scaled.kern(prev, id);
+        }
+        let glyph = id.with_scale_and_position(scale, ab_glyph::point(x, baseline));
+        if let Some(outlined) = font.outline_glyph(glyph) {
+            let bounds = outlined.px_bounds();
+            outlined.draw(|gx, gy, coverage| {
+                let px = bounds.min.x as i32 + gx as i32;
+                let py = bounds.min.y as i32 + gy as i32;
+                blend(img, px, py, color, coverage);
+            });
+        }
+        x += scaled.h_advance(id);
+        previous = Some(id);
+    }
+}
+
+fn fill_rounded_rect(img: &mut RgbaImage, x: f32, y: f32, w: f32, h: f32, r: f32, color: Rgba<u8>) {
+    for py in y.floor() as u32..(y + h).ceil().min(SIZE as f32) as u32 {
+        for px in x.floor() as u32..(x + w).ceil().min(SIZE as f32) as u32 {
+            // Distance past the rounded corner, antialiased over one pixel
+            let cx = (px as f32 + 0.5).clamp(x + r, x + w - r);
+            let cy = (py as f32 + 0.5).clamp(y + r, y + h - r);
+            let distance = ((px as f32 + 0.5 - cx).powi(2) + (py as f32 + 0.5 - cy).powi(2)).sqrt();
+            let coverage = (r + 0.5 - distance).clamp(0.0, 1.0);
+            blend(img, px as i32, py as i32, color, coverage);
+        }
+    }
+}
+
+/// Source-over blend of `color` at `coverage` onto a straight-alpha pixel
+fn blend(img: &mut RgbaImage, x: i32, y: i32, color: Rgba<u8>, coverage: f32) {
+    if x < 0 || y < 0 || x >= SIZE as i32 || y >= SIZE as i32 || coverage <= 0.0 {
+        return;
+    }
+    let dst = img.get_pixel_mut(x as u32, y as u32);
+    let sa = coverage.min(1.0) * color[3] as f32 / 255.0;
+    let da = dst[3] as f32 / 255.0;
+    let out_a = sa + da * (1.0 - sa);
+    if out_a <= 0.0 {
+        return;
+    }
+    for i in 0..3 {
+        let value = (color[i] as f32 * sa + dst[i] as f32 * da * (1.0 - sa)) / out_a;
+        dst[i] = value.round() as u8;
+    }
+    dst[3] = (out_a * 255.0).round() as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_and_shorten() {
+        let measure = |s: &str| s.chars().count() as f32;
+        assert_eq!(
+            wrap_text("startup ini bakal tutup sebelum series A", 12.0, measure),
+            vec!["startup ini", "bakal tutup", "sebelum", "series A"]
+        );
+        assert_eq!(
+            wrap_text("supercalifragilistic", 8.0, measure),
+            vec!["supercal", "ifragili", "stic"]
+        );
+        assert_eq!(
+            shorten("Bakal bangkrut, pasti.", 50),
+            "Bakal bangkrut, pasti."
+        );
+        assert_eq!(
+            shorten("Bakal bangkrut sebelum sempat pivot ke AI", 24),
+            "Bakal bangkrut sebelum…"
+        );
+    }
+}
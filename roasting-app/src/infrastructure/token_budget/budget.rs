@@ -0,0 +1,153 @@
+use super::counter::TokenCounter;
+use crate::domain::{PageSection, StartupInfo};
+
+/// Most of the budget the title may take, however long the page title is
+const TITLE_MAX_SHARE: usize = 10;
+/// Most of the budget the meta description may take
+const DESCRIPTION_MAX_SHARE: usize = 4;
+/// Headings may use at most half of what is left after title and description
+const HEADINGS_MAX_SHARE: usize = 2;
+/// With crawled pages, the landing page body keeps at most half of the rest
+const LANDING_MAX_SHARE: usize = 2;
+
+/// Trim scraped data to fit `budget` tokens. Title and description are kept
+/// first, then headings, and the body text gets whatever remains, split
+/// evenly with any crawled pages.
+pub fn fit_to_budget(
+    startup_info: &StartupInfo,
+    counter: &impl TokenCounter,
+    budget: usize,
+) -> StartupInfo {
+    let mut remaining = budget;
+    let mut take = |text: &str, limit: usize| -> String {
+        let text = counter.truncate(text, limit.min(remaining));
+        remaining -= counter.count(text).min(remaining);
+        text.trim_end().to_string()
+    };
+
+    let title = startup_info
+        .title
+        .as_deref()
+        .map(|t| take(t, budget / TITLE_MAX_SHARE));
+    let description = startup_info
+        .description
+        .as_deref()
+        .map(|d| take(d, budget / DESCRIPTION_MAX_SHARE));
+
+    let mut heading_budget = remaining / HEADINGS_MAX_SHARE;
+    let mut headings = Vec::new();
+    for heading in &startup_info.headings {
+        let tokens = counter.count(heading);
+        if tokens > heading_budget {
+            break;
+        }
+        heading_budget -= tokens;
+        remaining -= tokens;
+        headings.push(heading.clone());
+    }
+
+    let landing_budget = if startup_info.pages.is_empty() {
+        remaining
+    } else {
+        remaining / LANDING_MAX_SHARE
+    };
+    let content_summary = counter
+        .truncate(&startup_info.content_summary, landing_budget)
+        .trim_end()
+        .to_string();
+    remaining -= counter.count(&content_summary).min(remaining);
+
+    let page_budget = remaining / startup_info.pages.len().max(1);
+    let pages = startup_info
+        .pages
+        .iter()
+        .filter_map(|page| {
+            let mut page_remaining = page_budget;
+            let title = page.title.as_deref().map(|t| {
+                let t = counter.truncate(t, page_remaining).trim_end();
+                page_remaining -= counter.count(t).min(page_remaining);
+                t.to_string()
+            });
+            let content = counter.truncate(&page.content, page_remaining).trim_end();
+            (!content.is_empty()).then(|| PageSection {
+                kind: page.kind,
+                url: page.url.clone(),
+                title,
+                content: content.to_string(),
+            })
+        })
+        .collect();
+
+    StartupInfo {
+        url: startup_info.url.clone(),
+        title,
+        description,
+        headings,
+        content_summary,
+        ai_wrapper: startup_info.ai_wrapper.clone(),
+        domain_info: startup_info.domain_info.clone(),
+        dns_tls: startup_info.dns_tls.clone(),
+        security: startup_info.security.clone(),
+        tech_stack: startup_info.tech_stack.clone(),
+        structured_data: startup_info.structured_data.clone(),
+        funding: startup_info.funding.clone(),
+        performance: startup_info.performance.clone(),
+        seo: startup_info.seo.clone(),
+        social_links: startup_info.social_links.clone(),
+        logo_candidates: startup_info.logo_candidates.clone(),
+        country: startup_info.country.clone(),
+        archived: startup_info.archived.clone(),
+        pages,
+        raw_html: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::token_budget::EstimatedTokenCounter;
+
+    #[test]
+    fn test_estimated_counter() {
+        let counter = EstimatedTokenCounter;
+        assert_eq!(counter.count(""), 0);
+        assert_eq!(counter.count("hello world"), 4);
+        assert_eq!(counter.count("2024!"), 3);
+        assert_eq!(counter.truncate("aaaa bbbb cccc", 2).trim_end(), "aaaa bbbb");
+    }
+
+    #[test]
+    fn test_body_trimmed_before_title_and_description() {
+        let info = StartupInfo::new("https://example.com".to_string())
+            .with_title(Some("Acme".to_string()))
+            .with_description(Some("Payroll for cats".to_string()))
+            .with_headings(vec!["Fast".to_string(), "Cheap".to_string()])
+            .with_content_summary("word ".repeat(500));
+
+        let fitted = fit_to_budget(&info, &EstimatedTokenCounter, 60);
+        assert_eq!(fitted.title.as_deref(), Some("Acme"));
+        assert_eq!(fitted.description.as_deref(), Some("Payroll for cats"));
+        assert_eq!(fitted.headings.len(), 2);
+        assert!(EstimatedTokenCounter.count(&fitted.content_summary) <= 60);
+        assert!(!fitted.content_summary.is_empty());
+    }
+
+    #[test]
+    fn test_pages_share_the_body_budget() {
+        let page = PageSection {
+            kind: crate::domain::PageKind::Pricing,
+            url: "https://example.com/pricing".to_string(),
+            title: Some("Pricing".to_string()),
+            content: "price ".repeat(500),
+        };
+        let info = StartupInfo::new("https://example.com".to_string())
+            .with_content_summary("word ".repeat(500))
+            .with_pages(vec![page]);
+
+        let fitted = fit_to_budget(&info, &EstimatedTokenCounter, 100);
+        let landing = EstimatedTokenCounter.count(&fitted.content_summary);
+        assert!(landing <= 50);
+        assert_eq!(fitted.pages.len(), 1);
+        assert!(landing + EstimatedTokenCounter.count(&fitted.pages[0].content) <= 100);
+    }
+}
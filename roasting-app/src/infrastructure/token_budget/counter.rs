@@ -0,0 +1,119 @@
+/// Counts prompt tokens for a particular model family
+pub trait TokenCounter {
+    fn count(&self, text: &str) -> usize;
+
+    /// Longest prefix of `text` that fits in `max_tokens`
+    fn truncate<'a>(&self, text: &'a str, max_tokens: usize) -> &'a str;
+}
+
+/// Offline approximation of tiktoken-style BPE (cl100k and friends) for
+/// hosted models whose tokenizer we don't ship.
+///
+/// Text is pre-split the way those tokenizers do (letter runs, digit groups of
+/// three, punctuation, whitespace), then each piece is costed: ~4 ASCII letters
+/// per token, one token per non-ASCII character. Errs slightly high, which is
+/// the safe side for a budget.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EstimatedTokenCounter;
+
+#[derive(PartialEq, Clone, Copy)]
+enum PieceKind {
+    Letters,
+    Digits,
+    Space,
+    Other,
+}
+
+impl EstimatedTokenCounter {
+    /// `(end_byte, tokens)` for each pre-tokenized piece of `text`
+    fn pieces(text: &str) -> Vec<(usize, usize)> {
+        let mut pieces = Vec::new();
+        let mut chars = text.char_indices().peekable();
+
+        while let Some((_, c)) = chars.next() {
+            let kind = Self::kind(c);
+            let mut len = 1;
+            let mut non_ascii = usize::from(!c.is_ascii());
+            let mut end = text.len();
+
+            while let Some(&(i, next)) = chars.peek() {
+                if Self::kind(next) != kind || kind == PieceKind::Other {
+                    end = i;
+                    break;
+                }
+                len += 1;
+                non_ascii += usize::from(!next.is_ascii());
+                chars.next();
+            }
+
+            let tokens = match kind {
+                // Whitespace merges into the following word
+                PieceKind::Space => 0,
+                PieceKind::Letters => non_ascii + (len - non_ascii).div_ceil(4),
+                PieceKind::Digits => len.div_ceil(3),
+                PieceKind::Other => 1,
+            };
+            pieces.push((end, tokens));
+        }
+
+        pieces
+    }
+
+    fn kind(c: char) -> PieceKind {
+        if c.is_alphabetic() {
+            PieceKind::Letters
+        } else if c.is_numeric() {
+            PieceKind::Digits
+        } else if c.is_whitespace() {
+            PieceKind::Space
+        } else {
+            PieceKind::Other
+        }
+    }
+}
+
+impl TokenCounter for EstimatedTokenCounter {
+    fn count(&self, text: &str) -> usize {
+        Self::pieces(text).iter().map(|(_, tokens)| tokens).sum()
+    }
+
+    fn truncate<'a>(&self, text: &'a str, max_tokens: usize) -> &'a str {
+        let mut used = 0;
+        let mut end = 0;
+        for (piece_end, tokens) in Self::pieces(text) {
+            if used + tokens > max_tokens {
+                break;
+            }
+            used += tokens;
+            end = piece_end;
+        }
+        &text[..end]
+    }
+}
+
+/// Exact counts from the local model's own tokenizer
+#[cfg(feature = "local-llm")]
+impl TokenCounter for tokenizers::Tokenizer {
+    fn count(&self, text: &str) -> usize {
+        self.encode(text, false)
+            .map(|e| e.get_ids().len())
+            .unwrap_or_else(|_| EstimatedTokenCounter.count(text))
+    }
+
+    fn truncate<'a>(&self, text: &'a str, max_tokens: usize) -> &'a str {
+        let Ok(encoding) = self.encode(text, false) else {
+            return EstimatedTokenCounter.truncate(text, max_tokens);
+        };
+
+        match encoding.get_offsets().get(max_tokens) {
+            Some(&(start, _)) => {
+                let mut end = start.min(text.len());
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                &text[..end]
+            }
+            None => text,
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod budget;
+mod counter;
+
+pub use budget::fit_to_budget;
+pub use counter::{EstimatedTokenCounter, TokenCounter};
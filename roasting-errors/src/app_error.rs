@@ -21,6 +21,28 @@ pub enum AppError {
     #[error("Request timeout")]
     Timeout,
 
+    #[error("Roast ditahan moderasi: {0}")]
+    ContentRejected(String),
+
+    #[error("Website menolak di-roast: {0}")]
+    OptedOut(String),
+
+    /// Uploaded pitch deck that isn't a readable PDF
+    #[error("Pitch deck tidak bisa dibaca: {0}")]
+    InvalidDeck(String),
+
+    /// Free-text startup idea too short or empty after cleaning
+    #[error("Deskripsi ide tidak valid: {0}")]
+    InvalidDescription(String),
+
+    /// Local model queue is full; the message carries the queue position
+    #[error("Antrian AI penuh: {0}")]
+    QueueFull(String),
+
+    /// Too many requests; carries the seconds until the next one is admitted
+    #[error("Terlalu banyak request, coba lagi dalam {0} detik")]
+    RateLimited(u64),
+
     #[error("Terjadi kesalahan internal: {0}")]
     Internal(String),
 }
@@ -35,6 +57,22 @@ impl FromStr for AppError {
             Ok(AppError::ScrapingFailed(s.to_string()))
         } else if s.starts_with("Gagal menghubungi") {
             Ok(AppError::OpenRouterError(s.to_string()))
+        } else if s.starts_with("Roast ditahan moderasi") {
+            Ok(AppError::ContentRejected(s.to_string()))
+        } else if s.starts_with("Website menolak di-roast") {
+            Ok(AppError::OptedOut(s.to_string()))
+        } else if s.starts_with("Pitch deck tidak bisa dibaca") {
+            Ok(AppError::InvalidDeck(s.to_string()))
+        } else if s.starts_with("Deskripsi ide tidak valid") {
+            Ok(AppError::InvalidDescription(s.to_string()))
+        } else if let Some(message) = s.strip_prefix("Antrian AI penuh: ") {
+            Ok(AppError::QueueFull(message.to_string()))
+        } else if let Some(secs) = s
+            .strip_prefix("Terlalu banyak request, coba lagi dalam ")
+            .and_then(|rest| rest.strip_suffix(" detik"))
+            .and_then(|secs| secs.parse().ok())
+        {
+            Ok(AppError::RateLimited(secs))
         } else if s.contains("tidak ditemukan") {
             Ok(AppError::NotFound)
         } else if s.contains("timeout") {
@@ -54,6 +92,12 @@ impl AppError {
             Self::LlmError(_) => "AI lokal lagi error. Coba lagi nanti.",
             Self::NotFound => "Website tidak ditemukan.",
             Self::Timeout => "Request terlalu lama. Coba lagi.",
+            Self::ContentRejected(_) => "Roast-nya kebablasan dan ditahan moderasi. Coba lagi.",
+            Self::OptedOut(_) => "Pemilik website ini sudah minta untuk tidak di-roast. Cari korban lain ya!",
+            Self::InvalidDeck(_) => "Pitch deck-nya tidak bisa dibaca. Upload PDF berisi teks, bukan hasil scan.",
+            Self::InvalidDescription(_) => "Ceritain idenya lebih lengkap dong, minimal satu-dua kalimat.",
+            Self::QueueFull(message) => message,
+            Self::RateLimited(_) => "Terlalu banyak request! Tunggu sebentar lalu coba lagi.",
             Self::Internal(_) => "Ada masalah di server. Coba lagi nanti.",
         }
     }
@@ -62,7 +106,7 @@ impl AppError {
 #[cfg(feature = "ssr")]
 mod ssr_impl {
     use super::AppError;
-    use axum::http::StatusCode;
+    use axum::http::{header, StatusCode};
     use axum::response::{IntoResponse, Response};
     use axum::Json;
 
@@ -80,6 +124,16 @@ mod ssr_impl {
                 AppError::LlmError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
                 AppError::NotFound => (StatusCode::NOT_FOUND, "Not found".to_string()),
                 AppError::Timeout => (StatusCode::GATEWAY_TIMEOUT, "Timeout".to_string()),
+                AppError::ContentRejected(msg) => (StatusCode::UNPROCESSABLE_ENTITY, msg.clone()),
+                AppError::OptedOut(msg) => (StatusCode::FORBIDDEN, msg.clone()),
+                AppError::InvalidDeck(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+                AppError::InvalidDescription(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+                AppError::QueueFull(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg.clone()),
+                AppError::RateLimited(secs) => {
+                    let message = self.to_string();
+                    let retry_after = [(header::RETRY_AFTER, secs.to_string())];
+                    return (StatusCode::TOO_MANY_REQUESTS, retry_after, Json(ErrorResponse { message })).into_response();
+                }
                 AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
             };
             (status, Json(ErrorResponse { message })).into_response()
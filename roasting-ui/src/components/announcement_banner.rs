@@ -0,0 +1,60 @@
+use leptos::prelude::*;
+use roasting_app::domain::Announcement;
+use server_fn::ServerFnError;
+
+#[server(GetLatestAnnouncementFn, "/api", endpoint = "latest_announcement")]
+pub async fn get_latest_announcement() -> Result<Option<Announcement>, ServerFnError> {
+    use roasting_app::AppContext;
+
+    let ctx = expect_context::<AppContext>();
+
+    ctx.announcement_repo
+        .latest_published()
+        .await
+        .map(|a| a.map(Announcement::from))
+        .map_err(|e| ServerFnError::new(e.to_string()))
+}
+
+/// Dismissible banner for the newest published announcement.
+/// Dismissal is remembered per announcement id in localStorage.
+#[component]
+pub fn AnnouncementBanner() -> impl IntoView {
+    let announcement = Resource::new(|| (), |_| get_latest_announcement());
+
+    view! {
+        <Suspense fallback=|| ()>
+            {move || {
+                announcement.get().and_then(|result| result.ok().flatten()).map(|a| {
+                    let id = a.id.to_string();
+                    view! {
+                        <div class="announcement-banner" id="announcement-banner" data-announcement-id={id}>
+                            <div class="announcement-banner__content">
+                                <strong class="announcement-banner__title">{a.title}</strong>
+                                <span class="announcement-banner__body">{a.body}</span>
+                                <a href="/changelog" class="announcement-banner__link">"Lihat changelog"</a>
+                            </div>
+                            <button
+                                type="button"
+                                class="announcement-banner__dismiss"
+                                aria-label="Tutup"
+                                onclick="var b=document.getElementById('announcement-banner');localStorage.setItem('dismissed_announcement',b.dataset.announcementId);b.style.display='none';"
+                            >
+                                "×"
+                            </button>
+                        </div>
+                        <script>
+                            r#"
+                            (function() {
+                                var b = document.getElementById('announcement-banner');
+                                if (b && localStorage.getItem('dismissed_announcement') === b.dataset.announcementId) {
+                                    b.style.display = 'none';
+                                }
+                            })();
+                            "#
+                        </script>
+                    }
+                })
+            }}
+        </Suspense>
+    }
+}
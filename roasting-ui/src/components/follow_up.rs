@@ -0,0 +1,38 @@
+use leptos::prelude::*;
+use roasting_app::domain::RoastFollowUp;
+use server_fn::ServerFnError;
+
+/// Ask one "roast lebih dalam" follow-up on an existing roast.
+/// Requires login and spends the same rate/cost budget as a new roast.
+#[server(RoastFollowUpFn, "/api", endpoint = "roast_follow_up")]
+pub async fn roast_follow_up(
+    roast_id: uuid::Uuid,
+    question: String,
+) -> Result<RoastFollowUp, ServerFnError> {
+    use roasting_app::AppContext;
+    use roasting_app::infrastructure::security::ClientIp;
+    use std::net::{IpAddr, Ipv4Addr};
+    use tower_sessions::Session;
+
+    let ctx = expect_context::<AppContext>();
+    let session = expect_context::<Session>();
+
+    let Some(user_id) = session.get::<uuid::Uuid>("user_id").await.ok().flatten() else {
+        return Err(ServerFnError::new(
+            "Login dulu untuk minta roast lebih dalam",
+        ));
+    };
+
+    let client_ip = use_context::<ClientIp>().map_or(IpAddr::V4(Ipv4Addr::LOCALHOST), |ip| ip.0);
+    if let Err(e) = ctx.rate_limiter.check_rate_limit(client_ip, Some(user_id)).await {
+        return Err(crate::rate_limited(e));
+    }
+    if let Err(e) = ctx.cost_tracker.check_and_increment() {
+        return Err(ServerFnError::new(e.message_id()));
+    }
+
+    ctx.ask_follow_up
+        .execute(roast_id, user_id, &question)
+        .await
+        .map_err(|e| ServerFnError::new(e.user_message()))
+}
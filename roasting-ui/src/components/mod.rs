@@ -1,9 +1,15 @@
+mod announcement_banner;
 mod error_display;
+mod follow_up;
 mod loading_spinner;
 mod roast_display;
+mod scorecard;
 mod url_input;
 
+pub use announcement_banner::{get_latest_announcement, AnnouncementBanner, GetLatestAnnouncementFn};
 pub use error_display::ErrorDisplay;
+pub use follow_up::{roast_follow_up, RoastFollowUpFn};
 pub use loading_spinner::LoadingSpinner;
 pub use roast_display::RoastDisplay;
+pub use scorecard::Scorecard;
 pub use url_input::UrlInput;
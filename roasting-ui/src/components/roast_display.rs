@@ -1,5 +1,7 @@
 use leptos::prelude::*;
-use roasting_app::domain::Roast;
+use super::Scorecard;
+use crate::i18n::labels;
+use roasting_app::domain::{AiWrapperScore, Roast, RoastSections};
 
 fn simple_markdown_to_html(text: &str) -> String {
     let mut result = String::new();
@@ -58,20 +60,44 @@ fn fix_em_tags(text: &str) -> String {
     result
 }
 
+fn sections_to_html(sections: &RoastSections, language: roasting_app::domain::Language) -> String {
+    sections
+        .entries(language)
+        .iter()
+        .map(|(title, body)| {
+            format!(
+                r#"<section class="roast__section"><h3 class="roast__section-title">{}</h3>{}</section>"#,
+                title,
+                simple_markdown_to_html(body)
+            )
+        })
+        .collect()
+}
+
 #[component]
 pub fn RoastDisplay(roast: Roast) -> impl IntoView {
-    let html_content = simple_markdown_to_html(&roast.roast_text);
+    let html_content = match &roast.sections {
+        Some(sections) => sections_to_html(sections, roast.language),
+        None => simple_markdown_to_html(&roast.roast_text),
+    };
+    let labels = labels(roast.language);
+    let ai_badge = roast
+        .ai_wrapper_confidence
+        .filter(|c| *c >= AiWrapperScore::LIKELY)
+        .map(|c| view! { <span class="ai-badge">"🤖 " {labels.ai_wrapper_badge} " · " {c} "%"</span> });
 
     view! {
         <div class="roast">
             <h2 class="roast__title">
-                "Roasting: " {roast.startup_name}
+                {labels.roast_title_prefix} {roast.startup_name}
             </h2>
+            {ai_badge}
             <div class="roast__content" inner_html=html_content>
             </div>
+            {roast.score.map(|score| view! { <Scorecard score=score language=roast.language/> })}
             <div class="roast__actions">
                 <a href="/" class="roast__button roast__button--primary">
-                    "Roast Lagi!"
+                    {labels.roast_again}
                 </a>
             </div>
         </div>
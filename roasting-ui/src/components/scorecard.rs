@@ -0,0 +1,34 @@
+use crate::i18n::labels;
+use leptos::prelude::*;
+use roasting_app::domain::{Language, RoastScore};
+
+#[component]
+pub fn Scorecard(score: RoastScore, language: Language) -> impl IntoView {
+    let labels = labels(language);
+    let rows = score
+        .entries(language)
+        .into_iter()
+        .map(|(label, value)| {
+            let width = format!("width: {}%", value as u32 * 10);
+            view! {
+                <div class="scorecard__row">
+                    <span class="scorecard__label">{label}</span>
+                    <div class="scorecard__bar">
+                        <div class="scorecard__fill" style=width></div>
+                    </div>
+                    <span class="scorecard__value">{value}"/10"</span>
+                </div>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div class="scorecard">
+            <h3 class="scorecard__title">{labels.scorecard_title}</h3>
+            {rows}
+            <p class="scorecard__overall">
+                {labels.overall_score} ": " <strong>{format!("{:.1}", score.overall())}</strong> "/10"
+            </p>
+        </div>
+    }
+}
@@ -0,0 +1,151 @@
+use roasting_app::domain::Language;
+
+/// UI copy that changes with the roast's output language
+pub struct Labels {
+    pub html_lang: &'static str,
+    pub roast_title_prefix: &'static str,
+    pub roast_again: &'static str,
+    pub leaderboard: &'static str,
+    pub login_to_vote: &'static str,
+    pub own_roast_vote: &'static str,
+    pub battle_prompt: &'static str,
+    pub battle_cta: &'static str,
+    pub scorecard_title: &'static str,
+    pub overall_score: &'static str,
+    pub ai_wrapper_badge: &'static str,
+    pub regenerate: &'static str,
+    pub regenerate_confirm: &'static str,
+    pub regenerating: &'static str,
+    pub push_subscribe: &'static str,
+    pub push_subscribed: &'static str,
+    pub schedule_publish: &'static str,
+    pub publish_now: &'static str,
+    pub embargoed_until: &'static str,
+    pub watch: &'static str,
+    pub unwatch: &'static str,
+    pub login_to_watch: &'static str,
+    pub founder_title: &'static str,
+    pub founder_views: &'static str,
+    pub founder_shares: &'static str,
+    pub founder_fires_per_day: &'static str,
+    pub founder_referrers: &'static str,
+    /// Referrer shown for visits without a `Referer`
+    pub founder_direct: &'static str,
+    pub follow_up_title: &'static str,
+    pub follow_up_placeholder: &'static str,
+    pub follow_up_cta: &'static str,
+    pub dedicated_to: &'static str,
+    pub share_description: &'static str,
+    pub share_whatsapp: &'static str,
+    /// WhatsApp text for a gifted roast; `{name}` and `{startup}` are filled in
+    pub whatsapp_gift: &'static str,
+    /// WhatsApp text for a plain share; `{startup}` is filled in
+    pub whatsapp_share: &'static str,
+    pub compare_link: &'static str,
+    pub social_links_title: &'static str,
+    pub screenshot_caption: &'static str,
+    pub fact_sheet_title: &'static str,
+    pub compare_title: &'static str,
+    /// Summary line of the compare view; `{count}` is filled in
+    pub compare_changed: &'static str,
+    pub spice_title: &'static str,
+    /// Answer count under the spice bar; `{count}` is filled in
+    pub spice_answers: &'static str,
+}
+
+pub fn labels(language: Language) -> Labels {
+    match language {
+        Language::Id => Labels {
+            html_lang: "id",
+            roast_title_prefix: "Roasting: ",
+            roast_again: "Roast Lagi!",
+            leaderboard: "Leaderboard",
+            login_to_vote: "Kamu harus login untuk vote. Login dengan Google?",
+            own_roast_vote: "Roast sendiri nggak bisa di-vote. Suruh temanmu aja!",
+            battle_prompt: "Mau diadu sama startup serupa ini?",
+            battle_cta: "Adu!",
+            scorecard_title: "Rapor Startup",
+            overall_score: "Nilai akhir",
+            ai_wrapper_badge: "Bungkus ChatGPT",
+            regenerate: "Roast Ulang",
+            regenerate_confirm: "Roast ulang startup ini? Versi sekarang tetap disimpan sebagai versi lama.",
+            regenerating: "Lagi di-roast...",
+            push_subscribe: "🔔 Kabari aku kalau roast ini panas",
+            push_subscribed: "🔔 Notifikasi aktif",
+            schedule_publish: "⏰ Jadwalkan tayang",
+            publish_now: "Tayangkan sekarang",
+            embargoed_until: "🔒 Belum tayang, cuma kamu yang bisa lihat. Tayang:",
+            watch: "👀 Pantau startup ini",
+            unwatch: "👀 Berhenti memantau",
+            login_to_watch: "Kamu harus login untuk memantau startup ini. Login dengan Google?",
+            founder_title: "📊 Statistik founder (cuma kamu yang lihat)",
+            founder_views: "dilihat",
+            founder_shares: "dibagikan",
+            founder_fires_per_day: "🔥 per hari",
+            founder_referrers: "Datang dari",
+            founder_direct: "langsung / aplikasi chat",
+            follow_up_title: "Roast Lebih Dalam",
+            follow_up_placeholder: "contoh: roast bagian pricing-nya",
+            follow_up_cta: "Gas!",
+            dedicated_to: "Ditujukan untuk",
+            share_description: "Startup ini baru aja di-roast habis-habisan 🔥",
+            share_whatsapp: "Kirim via WhatsApp",
+            whatsapp_gift: "Halo {name}, ada kado roast buat kamu 🎁🔥 {startup} barusan di-roast:",
+            whatsapp_share: "{startup} barusan di-roast habis-habisan 🔥",
+            compare_link: "Bandingkan dengan roast sebelumnya",
+            social_links_title: "Sosmed resminya",
+            screenshot_caption: "Barang buktinya",
+            fact_sheet_title: "Fakta (katanya)",
+            compare_title: "Dulu vs Sekarang",
+            compare_changed: "{count} kalimat berubah",
+            spice_title: "Seberapa pedas roast ini?",
+            spice_answers: "{count} suara",
+        },
+        Language::En => Labels {
+            html_lang: "en",
+            roast_title_prefix: "Roasted: ",
+            roast_again: "Roast Another!",
+            leaderboard: "Leaderboard",
+            login_to_vote: "You need to log in to vote. Log in with Google?",
+            own_roast_vote: "You can't fire your own roast. Ask a friend instead!",
+            battle_prompt: "Want to pit it against this similar startup?",
+            battle_cta: "Fight!",
+            scorecard_title: "Scorecard",
+            overall_score: "Overall",
+            ai_wrapper_badge: "ChatGPT wrapper",
+            regenerate: "Regenerate",
+            regenerate_confirm: "Roast this startup again? The current text is kept as a previous version.",
+            regenerating: "Roasting...",
+            push_subscribe: "🔔 Notify me when this roast catches fire",
+            push_subscribed: "🔔 Notifications on",
+            schedule_publish: "⏰ Schedule publish",
+            publish_now: "Publish now",
+            embargoed_until: "🔒 Not public yet, only you can see it. Goes live:",
+            watch: "👀 Watch this startup",
+            unwatch: "👀 Stop watching",
+            login_to_watch: "You need to log in to watch this startup. Log in with Google?",
+            founder_title: "📊 Founder stats (only you can see this)",
+            founder_views: "views",
+            founder_shares: "shares",
+            founder_fires_per_day: "🔥 per day",
+            founder_referrers: "Top referrers",
+            founder_direct: "direct / chat apps",
+            follow_up_title: "Roast Deeper",
+            follow_up_placeholder: "e.g. roast their pricing",
+            follow_up_cta: "Go!",
+            dedicated_to: "Dedicated to",
+            share_description: "This startup just got roasted 🔥",
+            share_whatsapp: "Send via WhatsApp",
+            whatsapp_gift: "Hey {name}, got a roast gift for you 🎁🔥 {startup} just got roasted:",
+            whatsapp_share: "{startup} just got roasted 🔥",
+            compare_link: "Compare with the previous roast",
+            social_links_title: "Their socials",
+            screenshot_caption: "Exhibit A",
+            fact_sheet_title: "Facts (allegedly)",
+            compare_title: "Then vs Now",
+            compare_changed: "{count} sentences changed",
+            spice_title: "How spicy was this roast?",
+            spice_answers: "{count} votes",
+        },
+    }
+}
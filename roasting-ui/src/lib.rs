@@ -1,4 +1,5 @@
 pub mod components;
+pub mod i18n;
 pub mod pages;
 
 use leptos::prelude::*;
@@ -27,6 +28,24 @@ pub fn App() -> impl IntoView {
     }
 }
 
+/// Turn a rate limit hit into a 429 with `Retry-After` and `X-RateLimit-*`
+/// headers and an error the client can parse back into `AppError::RateLimited`
+#[cfg(feature = "ssr")]
+pub(crate) fn rate_limited(e: roasting_app::infrastructure::security::RateLimitError) -> server_fn::ServerFnError {
+    use axum::http::{HeaderName, HeaderValue, StatusCode};
+    use leptos_axum::ResponseOptions;
+
+    if let Some(response) = use_context::<ResponseOptions>() {
+        response.set_status(StatusCode::TOO_MANY_REQUESTS);
+        for (name, value) in e.headers() {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                response.insert_header(HeaderName::from_static(name), value);
+            }
+        }
+    }
+    server_fn::ServerFnError::new(roasting_errors::AppError::from(e))
+}
+
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
 pub fn hydrate() {
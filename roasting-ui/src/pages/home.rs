@@ -1,7 +1,9 @@
 use leptos::prelude::*;
-use roasting_app::domain::{Roast, RoastWithDetails, User};
+use roasting_app::domain::{Dialect, Language, Roast, RoastLength, RoastWithDetails, User};
 use server_fn::ServerFnError;
 
+use crate::components::AnnouncementBanner;
+
 #[server(GetCurrentUserFn, "/api", endpoint = "current_user")]
 pub async fn get_current_user() -> Result<Option<User>, ServerFnError> {
     use roasting_app::AppContext;
@@ -46,6 +48,7 @@ pub async fn get_current_user() -> Result<Option<User>, ServerFnError> {
                 email: m.email,
                 name: m.name,
                 avatar_url: m.avatar_url,
+                hide_authorship: m.hide_authorship,
                 created_at: m.created_at,
                 updated_at: m.updated_at,
             }))
@@ -55,28 +58,86 @@ pub async fn get_current_user() -> Result<Option<User>, ServerFnError> {
 }
 
 #[server(GenerateRoastFn, "/api", endpoint = "generate_roast")]
-pub async fn generate_roast(url: String) -> Result<Roast, ServerFnError> {
-    use roasting_app::infrastructure::security::InputSanitizer;
+pub async fn generate_roast(
+    url: String,
+    language: Option<Language>,
+    dialect: Option<Dialect>,
+    length: Option<RoastLength>,
+    regenerate: Option<bool>,
+    safe: Option<bool>,
+    invite_code: Option<String>,
+) -> Result<Roast, ServerFnError> {
+    use roasting_app::domain::RoastOptions;
+    use roasting_app::infrastructure::security::{ClientIp, InputSanitizer};
     use roasting_app::AppContext;
     use std::net::{IpAddr, Ipv4Addr};
+    use tower_sessions::Session;
 
     let ctx = expect_context::<AppContext>();
+    let session = expect_context::<Session>();
+    let user_id: Option<uuid::Uuid> = session.get("user_id").await.ok().flatten();
 
-    let client_ip = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+    if ctx.login_required && user_id.is_none() {
+        return Err(ServerFnError::new(
+            "Login dulu ya, bikin roast sekarang butuh akun Google.",
+        ));
+    }
 
-    if let Err(e) = ctx.rate_limiter.check_rate_limit(client_ip) {
-        return Err(ServerFnError::new(e.message_id()));
+    if ctx.invite_only {
+        let unlocked: bool = session.get("invite_ok").await.ok().flatten().unwrap_or(false);
+        if !unlocked {
+            let code = invite_code.as_deref().map(str::trim).unwrap_or_default();
+            let redeemed = !code.is_empty()
+                && ctx
+                    .invite_repo
+                    .redeem(code)
+                    .await
+                    .map_err(|e| ServerFnError::new(e.to_string()))?;
+            if !redeemed {
+                return Err(ServerFnError::new(
+                    "Roasting Startup lagi private beta. Butuh kode undangan yang valid.",
+                ));
+            }
+            let _ = session.insert("invite_ok", true).await;
+        }
     }
 
-    if let Err(e) = ctx.cost_tracker.check_and_increment() {
-        return Err(ServerFnError::new(e.message_id()));
+    let client_ip = use_context::<ClientIp>().map_or(IpAddr::V4(Ipv4Addr::LOCALHOST), |ip| ip.0);
+
+    if let Err(e) = ctx.rate_limiter.check_rate_limit(client_ip, user_id).await {
+        return Err(crate::rate_limited(e));
     }
 
     let validated_url = InputSanitizer::validate_url(&url)
         .map_err(|e| ServerFnError::new(e.user_message()))?;
+    ctx.generate_roast
+        .ensure_roastable(&validated_url)
+        .map_err(|e| ServerFnError::new(e.user_message()))?;
+    let validated_url = ctx.generate_roast.resolve_url(&validated_url).await;
+    let options = RoastOptions::new(language.unwrap_or_default())
+        .with_dialect(dialect.unwrap_or_default())
+        .with_length(length.unwrap_or_default())
+        .with_safe(safe.unwrap_or(false));
+
+    if !regenerate.unwrap_or(false) {
+        if let Some(roast) = ctx.generate_roast.cached(&validated_url, options).await {
+            return Ok(roast);
+        }
+    }
+
+    // Logged-in users keep their place when generation is overloaded
+    if user_id.is_none() && ctx.load_shedder.admit_anonymous(None).is_err() {
+        return Err(ServerFnError::new(
+            "Lagi rame banget! Coba lagi sebentar lagi, atau login biar didahulukan.",
+        ));
+    }
+
+    if let Err(e) = ctx.cost_tracker.check_and_increment() {
+        return Err(ServerFnError::new(e.message_id()));
+    }
 
     ctx.generate_roast
-        .execute(validated_url)
+        .execute(validated_url, options)
         .await
         .map_err(|e| ServerFnError::new(e.user_message()))
 }
@@ -88,7 +149,7 @@ pub async fn get_leaderboard() -> Result<Vec<RoastWithDetails>, ServerFnError> {
     let ctx = expect_context::<AppContext>();
 
     ctx.roast_repo
-        .get_leaderboard(10, None)
+        .get_leaderboard(10, None, false, None, None)
         .await
         .map_err(|e| ServerFnError::new(e.to_string()))
 }
@@ -98,6 +159,8 @@ pub fn HomePage() -> impl IntoView {
     let leaderboard = Resource::new(|| (), |_| get_leaderboard());
 
     view! {
+        <AnnouncementBanner/>
+
         <div class="hero">
             <h1 class="hero__title">"Hancurkan Startup-mu"</h1>
             <p class="hero__subtitle">
@@ -116,8 +179,59 @@ pub fn HomePage() -> impl IntoView {
                         name="url"
                         class="url-form__input"
                         placeholder="Masukkan URL startup... (contoh: https://perfect10.id)"
-                        required
                     />
+                    <details class="url-form__idea">
+                        <summary>"Belum punya website? Ceritain aja idenya"</summary>
+                        <input
+                            type="text"
+                            name="idea_name"
+                            class="url-form__input"
+                            placeholder="Nama startup (opsional)"
+                            maxlength="60"
+                        />
+                        <textarea
+                            name="description"
+                            class="url-form__input url-form__textarea"
+                            placeholder="Startup-mu ngapain, buat siapa, dan kenapa bakal sukses..."
+                            maxlength="2000"
+                        ></textarea>
+                    </details>
+                    <input
+                        type="text"
+                        name="dedicated_to"
+                        class="url-form__input"
+                        placeholder="Ditujukan untuk... (opsional, contoh: Budi si CEO)"
+                        maxlength="60"
+                    />
+                    <select name="language" class="url-form__select">
+                        {Language::all().iter().map(|lang| view! {
+                            <option value={lang.as_str()}>{lang.display_name()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                    <select name="dialect" class="url-form__select">
+                        {Dialect::all().iter().map(|dialect| view! {
+                            <option value={dialect.as_str()}>{dialect.display_name()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                    <select name="length" class="url-form__select">
+                        {RoastLength::all().iter().map(|length| view! {
+                            <option value={length.as_str()} selected={*length == RoastLength::default()}>
+                                {length.display_name()}
+                            </option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                    <label class="url-form__checkbox">
+                        <input type="checkbox" name="anonymous" value="true"/>
+                        "Tampilkan sebagai Anonim"
+                    </label>
+                    <label class="url-form__checkbox">
+                        <input type="checkbox" name="regenerate" value="true"/>
+                        "Roast ulang, jangan pakai hasil lama"
+                    </label>
+                    <label class="url-form__checkbox">
+                        <input type="checkbox" name="safe" value="true"/>
+                        "Mode aman keluarga (tanpa kata kasar)"
+                    </label>
                     <button
                         type="submit"
                         class="url-form__button"
@@ -226,6 +340,12 @@ fn AuthSection() -> impl IntoView {
                                 avatarEl.style.display = 'none';
                             }
                             document.getElementById('auth-section').classList.add('auth-section--logged-in');
+                        } else if (data.login_required) {
+                            document.getElementById('login-hint').textContent = 'Login dulu untuk bikin roast. Lihat-lihat roast tetap bebas tanpa login.';
+                            var button = document.querySelector('.url-form__button');
+                            if (button) {
+                                button.textContent = 'Login untuk Roast';
+                            }
                         }
                     })
                     .catch(err => console.error('Auth check failed:', err));